@@ -30,12 +30,12 @@ pub async fn like_comment(
 
   let mut recipient_ids = Vec::<LocalUserId>::new();
 
-  // Don't do a downvote if site has downvotes disabled
-  check_downvotes_enabled(data.score, &local_site)?;
-
   let comment_id = data.comment_id;
   let orig_comment = CommentView::read(&mut context.pool(), comment_id, None).await?;
 
+  // Don't do a downvote if site or community has downvotes disabled
+  check_downvotes_enabled(data.score, &local_site, &orig_comment.community)?;
+
   check_community_ban(
     local_user_view.person.id,
     orig_comment.community.id,
@@ -0,0 +1,35 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  comment::{ListCommentLikes, ListCommentLikesResponse},
+  context::LemmyContext,
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::local_site::LocalSite;
+use lemmy_db_views::structs::{CommentView, VoteView};
+use lemmy_utils::error::{LemmyError, LemmyErrorType};
+
+/// Lists the votes on a comment, so that mods can spot vote manipulation
+#[tracing::instrument(skip(context))]
+pub async fn list_comment_likes(
+  data: Query<ListCommentLikes>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListCommentLikesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  if !local_site.enable_vote_viewer {
+    Err(LemmyErrorType::VoteViewerDisabled)?
+  }
+
+  let comment_view = CommentView::read(&mut context.pool(), data.comment_id, None).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    comment_view.community.id,
+  )
+  .await?;
+
+  let comment_likes =
+    VoteView::list_for_comment(&mut context.pool(), data.comment_id, data.page, data.limit).await?;
+
+  Ok(Json(ListCommentLikesResponse { comment_likes }))
+}
@@ -1,3 +1,6 @@
 pub mod distinguish;
 pub mod like;
+pub mod list_comment_likes;
+pub mod react;
 pub mod save;
+pub mod subscribe;
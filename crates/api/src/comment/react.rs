@@ -0,0 +1,70 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  build_response::build_comment_response,
+  comment::{CommentResponse, CreateCommentReaction},
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_ban, check_community_deleted_or_removed, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::comment::{CommentReaction, CommentReactionForm},
+  traits::Reactable,
+};
+use lemmy_db_views::structs::CommentView;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::is_valid_emoji_reaction,
+};
+use std::ops::Deref;
+
+#[tracing::instrument(skip(context))]
+pub async fn react_to_comment(
+  data: Json<CreateCommentReaction>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommentResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let emoji = data.emoji.trim();
+  is_valid_emoji_reaction(emoji)?;
+
+  let comment_id = data.comment_id;
+  let orig_comment = CommentView::read(&mut context.pool(), comment_id, None).await?;
+
+  check_community_ban(
+    local_user_view.person.id,
+    orig_comment.community.id,
+    &mut context.pool(),
+  )
+  .await?;
+  check_community_deleted_or_removed(orig_comment.community.id, &mut context.pool()).await?;
+
+  let person_id = local_user_view.person.id;
+  if data.add {
+    let reaction_form = CommentReactionForm {
+      comment_id,
+      person_id,
+      emoji: emoji.to_string(),
+    };
+    CommentReaction::react(&mut context.pool(), &reaction_form)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntReact)?;
+  } else {
+    CommentReaction::unreact(&mut context.pool(), person_id, comment_id, emoji).await?;
+  }
+
+  ActivityChannel::submit_activity(
+    SendActivityData::ReactPostOrComment(
+      orig_comment.comment.ap_id,
+      local_user_view.person.clone(),
+      orig_comment.community,
+      emoji.to_string(),
+      data.add,
+    ),
+    &context,
+  )
+  .await?;
+
+  Ok(Json(
+    build_comment_response(context.deref(), comment_id, Some(local_user_view), vec![]).await?,
+  ))
+}
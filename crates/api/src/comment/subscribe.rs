@@ -0,0 +1,63 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  comment::{SubscribeToThread, ThreadSubscriptionResponse},
+  context::LemmyContext,
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::{
+  source::{
+    comment::Comment,
+    post::Post,
+    thread_subscription::{ThreadSubscription, ThreadSubscriptionForm},
+  },
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn subscribe_to_thread(
+  data: Json<SubscribeToThread>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ThreadSubscriptionResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let person_id = local_user_view.person.id;
+
+  let thread_subscription = if data.subscribe {
+    Post::read(&mut context.pool(), data.post_id)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntFindPost)?;
+    // Make sure the comment is in that post
+    if let Some(comment_id) = data.comment_id {
+      let comment = Comment::read(&mut context.pool(), comment_id)
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntFindComment)?;
+      if comment.post_id != data.post_id {
+        return Err(LemmyErrorType::CouldntFindComment)?;
+      }
+    }
+    let form = ThreadSubscriptionForm {
+      person_id,
+      post_id: data.post_id,
+      comment_id: data.comment_id,
+    };
+    let subscription = ThreadSubscription::subscribe(&mut context.pool(), &form)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateNotification)?;
+    Some(subscription)
+  } else {
+    ThreadSubscription::unsubscribe(
+      &mut context.pool(),
+      person_id,
+      data.post_id,
+      data.comment_id,
+    )
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateNotification)?;
+    None
+  };
+
+  Ok(Json(ThreadSubscriptionResponse {
+    thread_subscription,
+  }))
+}
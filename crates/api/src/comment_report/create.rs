@@ -1,16 +1,11 @@
-use crate::check_report_reason;
+use crate::check_report_reason_with_id;
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_common::{
   comment::{CommentReportResponse, CreateCommentReport},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{
-    check_community_ban,
-    local_user_view_from_jwt,
-    sanitize_html,
-    send_new_report_email_to_admins,
-  },
+  utils::{check_community_ban, local_user_view_from_jwt, send_new_report_email_to_admins},
 };
 use lemmy_db_schema::{
   source::{
@@ -31,20 +26,29 @@ pub async fn create_comment_report(
   let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
   let local_site = LocalSite::read(&mut context.pool()).await?;
 
-  let reason = sanitize_html(data.reason.trim());
-  check_report_reason(&reason, &local_site)?;
-
   let person_id = local_user_view.person.id;
   let comment_id = data.comment_id;
   let comment_view = CommentView::read(&mut context.pool(), comment_id, None).await?;
 
+  let reason = check_report_reason_with_id(
+    &data.reason,
+    data.reason_id,
+    data.rule_id,
+    Some(comment_view.community.id),
+    &local_site,
+    &mut context.pool(),
+  )
+  .await?;
+
   check_community_ban(person_id, comment_view.community.id, &mut context.pool()).await?;
 
   let report_form = CommentReportForm {
     creator_id: person_id,
     comment_id,
     original_comment_text: comment_view.comment.content,
-    reason,
+    reason: reason.clone(),
+    reason_id: data.reason_id,
+    rule_id: data.rule_id,
   };
 
   let report = CommentReport::report(&mut context.pool(), &report_form)
@@ -70,7 +74,7 @@ pub async fn create_comment_report(
       comment_view.comment.ap_id.inner().clone(),
       local_user_view.person,
       comment_view.community,
-      data.reason.clone(),
+      reason,
     ),
     &context,
   )
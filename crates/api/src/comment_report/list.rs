@@ -24,6 +24,7 @@ pub async fn list_comment_reports(
   let comment_reports = CommentReportQuery {
     community_id,
     unresolved_only,
+    reason_id: data.reason_id,
     page,
     limit,
   }
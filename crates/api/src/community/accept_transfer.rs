@@ -0,0 +1,103 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{AcceptCommunityTransfer, GetCommunityResponse},
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::{
+  source::{
+    community::{Community, CommunityModerator, CommunityModeratorForm, CommunityTransferRequest},
+    moderator::{ModTransferCommunity, ModTransferCommunityForm},
+  },
+  traits::{Crud, Joinable},
+};
+use lemmy_db_views_actor::structs::{CommunityModeratorView, CommunityView};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn accept_community_transfer(
+  data: Json<AcceptCommunityTransfer>,
+  context: Data<LemmyContext>,
+) -> Result<Json<GetCommunityResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let person_id = local_user_view.person.id;
+  let community_id = data.community_id;
+
+  let transfer = CommunityTransferRequest::read(&mut context.pool(), community_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CommunityTransferNotFound)?;
+  if transfer.recipient_id != person_id {
+    return Err(LemmyErrorType::CommunityTransferNotFound)?;
+  }
+  CommunityTransferRequest::delete(&mut context.pool(), community_id).await?;
+
+  if data.accept {
+    let mut community_mods =
+      CommunityModeratorView::for_community(&mut context.pool(), community_id).await?;
+
+    // The new owner might have been removed as mod in the meantime
+    let creator_index = community_mods
+      .iter()
+      .position(|r| r.moderator.id == person_id)
+      .ok_or(LemmyErrorType::NotAModerator)?;
+
+    // You have to re-do the community_moderator table, reordering it.
+    // Add the transferee to the top
+    let creator_person = community_mods.remove(creator_index);
+    community_mods.insert(0, creator_person);
+
+    // Delete all the mods
+    CommunityModerator::delete_for_community(&mut context.pool(), community_id).await?;
+
+    // TODO: this should probably be a bulk operation
+    // Re-add the mods, in the new order
+    for cmod in &community_mods {
+      let community_moderator_form = CommunityModeratorForm {
+        community_id: cmod.community.id,
+        person_id: cmod.moderator.id,
+      };
+
+      CommunityModerator::join(&mut context.pool(), &community_moderator_form)
+        .await
+        .with_lemmy_type(LemmyErrorType::CommunityModeratorAlreadyExists)?;
+    }
+
+    // Mod tables
+    let form = ModTransferCommunityForm {
+      mod_person_id: transfer.requester_id,
+      other_person_id: person_id,
+      community_id,
+    };
+
+    ModTransferCommunity::create(&mut context.pool(), &form).await?;
+
+    // Remote instances pick up the new mod order when they refetch the moderators collection
+    let community = Community::read(&mut context.pool(), community_id).await?;
+    ActivityChannel::submit_activity(
+      SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+      &context,
+    )
+    .await?;
+  }
+
+  let community_view =
+    CommunityView::read(&mut context.pool(), community_id, Some(person_id), None)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntFindCommunity)?;
+
+  let moderators = CommunityModeratorView::for_community(&mut context.pool(), community_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindCommunity)?;
+
+  Ok(Json(GetCommunityResponse {
+    community_view,
+    site: None,
+    moderators,
+    discussion_languages: vec![],
+    categories: vec![],
+    rules: vec![],
+    pending_transfer: None,
+  }))
+}
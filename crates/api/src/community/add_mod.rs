@@ -49,7 +49,7 @@ pub async fn add_mod_to_community(
 
   // Mod tables
   let form = ModAddCommunityForm {
-    mod_person_id: local_user_view.person.id,
+    mod_person_id: Some(local_user_view.person.id),
     other_person_id: data.person_id,
     community_id: data.community_id,
     removed: Some(!data.added),
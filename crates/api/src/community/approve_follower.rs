@@ -0,0 +1,63 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{ApproveCommunityPendingFollower, ApproveCommunityPendingFollowerResponse},
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{
+    community::{Community, CommunityFollower, CommunityFollowerForm},
+    person::Person,
+  },
+  traits::{Crud, Followable},
+};
+use lemmy_db_views_actor::structs::PersonView;
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn approve_follower(
+  data: Json<ApproveCommunityPendingFollower>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ApproveCommunityPendingFollowerResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.community_id,
+  )
+  .await?;
+
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  if !community.local {
+    return Err(LemmyErrorType::ObjectNotLocal)?;
+  }
+
+  if data.approve {
+    CommunityFollower::follow_accepted(&mut context.pool(), data.community_id, data.follower_id)
+      .await
+      .with_lemmy_type(LemmyErrorType::CommunityFollowerNotPending)?;
+  } else {
+    let form = CommunityFollowerForm {
+      community_id: data.community_id,
+      person_id: data.follower_id,
+      pending: true,
+    };
+    CommunityFollower::unfollow(&mut context.pool(), &form).await?;
+  }
+
+  let follower = Person::read(&mut context.pool(), data.follower_id).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::ApproveCommunityFollower(community, follower, data.approve),
+    &context,
+  )
+  .await?;
+
+  let person_view = PersonView::read(&mut context.pool(), data.follower_id).await?;
+
+  Ok(Json(ApproveCommunityPendingFollowerResponse {
+    person_view,
+    approved: data.approve,
+  }))
+}
@@ -5,6 +5,7 @@ use lemmy_api_common::{
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
+    add_community_strike,
     is_mod_or_admin,
     local_user_view_from_jwt,
     remove_user_data_in_community,
@@ -19,6 +20,7 @@ use lemmy_db_schema::{
       CommunityPersonBan,
       CommunityPersonBanForm,
     },
+    community_strike::CommunityStrikeForm,
     moderator::{ModBanFromCommunity, ModBanFromCommunityForm},
   },
   traits::{Bannable, Crud, Followable},
@@ -93,6 +95,18 @@ pub async fn ban_from_community(
 
   ModBanFromCommunity::create(&mut context.pool(), &form).await?;
 
+  if data.ban && data.strike.unwrap_or(false) {
+    let strike_form = CommunityStrikeForm {
+      community_id: data.community_id,
+      person_id: data.person_id,
+      mod_person_id: local_user_view.person.id,
+      post_id: None,
+      comment_id: None,
+      reason: sanitize_html_opt(&data.reason),
+    };
+    add_community_strike(&strike_form, &local_user_view.person, &data.auth, &context).await?;
+  }
+
   let person_view = PersonView::read(&mut context.pool(), data.person_id).await?;
 
   ActivityChannel::submit_activity(
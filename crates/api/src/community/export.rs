@@ -0,0 +1,582 @@
+use activitypub_federation::config::Data;
+use actix_web::{
+  http::header::{ContentDisposition, ContentType, DispositionParam, DispositionType},
+  web::{Bytes, Json, Query},
+  HttpResponse,
+};
+use chrono::NaiveDateTime;
+use futures::{stream::try_unfold, TryStreamExt};
+use lemmy_api_common::{
+  community::{CommunityExportResponse, ExportCommunity, GetCommunityExport},
+  community_archive::{
+    ArchivedComment,
+    ArchivedCommunity,
+    ArchivedCommunityRule,
+    ArchivedModlogAction,
+    ArchivedModlogEntry,
+    ArchivedPost,
+    COMMUNITY_ARCHIVE_VERSION,
+  },
+  context::LemmyContext,
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityExportId, CommunityId, DbUrl},
+  source::{
+    comment::Comment,
+    community::Community,
+    community_export::{CommunityExport, CommunityExportForm},
+    community_rule::CommunityRule,
+    post::Post,
+  },
+  traits::Crud,
+  utils::{naive_now, DELETED_REPLACEMENT_TEXT, FETCH_LIMIT_MAX},
+};
+use lemmy_db_views_actor::structs::CommunityModeratorView;
+use lemmy_db_views_moderator::structs::{
+  ModAddCommunityView,
+  ModBanFromCommunityView,
+  ModFeaturePostView,
+  ModLockPostView,
+  ModRemoveCommentView,
+  ModRemovePostView,
+  ModTransferCommunityView,
+  ModlogListParams,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
+  spawn_try_task,
+};
+use serde::Serialize;
+use std::{collections::HashMap, future::Future};
+use tracing::warn;
+
+/// How many posts or comments are loaded from the database at once.
+const ARCHIVE_BATCH_SIZE: i64 = 1000;
+
+#[tracing::instrument(skip(context))]
+pub async fn export_community(
+  data: Json<ExportCommunity>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityExportResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.community_id,
+  )
+  .await?;
+
+  if let Some(community_export) =
+    CommunityExport::read_pending(&mut context.pool(), data.community_id).await?
+  {
+    return Ok(Json(CommunityExportResponse { community_export }));
+  }
+
+  let form = CommunityExportForm {
+    community_id: data.community_id,
+    creator_id: local_user_view.person.id,
+  };
+  let community_export = CommunityExport::create(&mut context.pool(), &form).await?;
+
+  let export_id = community_export.id;
+  let community_id = data.community_id;
+  let context = context.reset_request_count();
+  spawn_try_task(async move {
+    match write_archive(export_id, community_id, &context).await {
+      Ok(()) => {
+        CommunityExport::finish(&mut context.pool(), export_id).await?;
+      }
+      Err(e) => {
+        warn!("Failed to export community {}: {e}", community_id.0);
+        CommunityExport::fail(&mut context.pool(), export_id, e.error_type.to_string()).await?;
+      }
+    }
+    Ok(())
+  });
+
+  Ok(Json(CommunityExportResponse { community_export }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn get_community_export(
+  data: Query<GetCommunityExport>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityExportResponse>, LemmyError> {
+  let community_export = read_export_for_mod(&data, &context).await?;
+  Ok(Json(CommunityExportResponse { community_export }))
+}
+
+/// Returns the finished archive as a json file. It is streamed from the database chunk by chunk.
+#[tracing::instrument(skip(context))]
+pub async fn download_community_export(
+  data: Query<GetCommunityExport>,
+  context: Data<LemmyContext>,
+) -> Result<HttpResponse, LemmyError> {
+  let community_export = read_export_for_mod(&data, &context).await?;
+  if community_export.finished.is_none() || community_export.error.is_some() {
+    Err(LemmyErrorType::CommunityExportNotFinished)?;
+  }
+  let community = Community::read(&mut context.pool(), community_export.community_id).await?;
+
+  let filename = format!(
+    "{}-{}.json",
+    community.name,
+    community_export.published.format("%Y-%m-%d")
+  );
+  let export_id = community_export.id;
+  let context = (*context).clone();
+  let chunks = try_unfold(0, move |seq| {
+    let context = context.clone();
+    async move {
+      let chunk = CommunityExport::read_chunk(&mut context.pool(), export_id, seq).await?;
+      Ok::<_, LemmyError>(chunk.map(|c| (Bytes::from(c), seq + 1)))
+    }
+  })
+  .map_err(actix_web::Error::from);
+  Ok(
+    HttpResponse::Ok()
+      .content_type(ContentType::json())
+      .insert_header(ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![DispositionParam::Filename(filename)],
+      })
+      .streaming(chunks),
+  )
+}
+
+async fn read_export_for_mod(
+  data: &GetCommunityExport,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<CommunityExport> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, context).await?;
+  let community_export = CommunityExport::read(&mut context.pool(), data.export_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindCommunityExport)?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    community_export.community_id,
+  )
+  .await?;
+  Ok(community_export)
+}
+
+/// Writes the archive into the database one batch of posts or comments at a time.
+async fn write_archive(
+  export_id: CommunityExportId,
+  community_id: CommunityId,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  let moderators = CommunityModeratorView::for_community(&mut context.pool(), community_id)
+    .await?
+    .into_iter()
+    .map(|m| m.moderator.actor_id)
+    .collect();
+  let rules = CommunityRule::for_community(&mut context.pool(), community_id)
+    .await?
+    .into_iter()
+    .map(|r| ArchivedCommunityRule {
+      title: r.title,
+      description: r.description,
+    })
+    .collect();
+  let community = ArchivedCommunity {
+    actor_id: community.actor_id,
+    name: community.name,
+    title: community.title,
+    description: community.description,
+    icon: community.icon,
+    banner: community.banner,
+    nsfw: community.nsfw,
+    posting_restricted_to_mods: community.posting_restricted_to_mods,
+    published: community.published,
+    moderators,
+    rules,
+  };
+  let mut writer = ArchiveWriter::new(&community, naive_now())?;
+
+  writer.start_list("posts");
+  let mut last_post_id = None;
+  loop {
+    let batch = Post::list_for_archive(
+      &mut context.pool(),
+      community_id,
+      last_post_id,
+      ARCHIVE_BATCH_SIZE,
+    )
+    .await?;
+    let batch_len = batch.len();
+    for (mut post, creator) in batch {
+      last_post_id = Some(post.id);
+      if post.deleted || post.removed {
+        post.name = DELETED_REPLACEMENT_TEXT.to_string();
+        post.url = None;
+        post.body = None;
+      }
+      writer.push(&ArchivedPost {
+        ap_id: post.ap_id,
+        creator,
+        name: post.name,
+        url: post.url,
+        body: post.body,
+        nsfw: post.nsfw,
+        locked: post.locked,
+        featured: post.featured_community,
+        removed: post.removed,
+        deleted: post.deleted,
+        published: post.published,
+        updated: post.updated,
+      })?;
+    }
+    writer.save_chunk(export_id, context).await?;
+    if batch_len < ARCHIVE_BATCH_SIZE as usize {
+      break;
+    }
+  }
+  writer.end_list();
+
+  writer.start_list("comments");
+  let mut last_comment_id = None;
+  loop {
+    let batch = Comment::list_for_archive(
+      &mut context.pool(),
+      community_id,
+      last_comment_id,
+      ARCHIVE_BATCH_SIZE,
+    )
+    .await?;
+    let batch_len = batch.len();
+    let parent_ids: Vec<CommentId> = batch
+      .iter()
+      .filter_map(|(comment, ..)| comment.parent_comment_id())
+      .collect();
+    let parent_ap_ids: HashMap<CommentId, DbUrl> =
+      Comment::list_ap_ids(&mut context.pool(), &parent_ids)
+        .await?
+        .into_iter()
+        .collect();
+    for (mut comment, creator, post) in batch {
+      last_comment_id = Some(comment.id);
+      let parent = comment
+        .parent_comment_id()
+        .and_then(|p| parent_ap_ids.get(&p).cloned());
+      if comment.deleted || comment.removed {
+        comment.content = DELETED_REPLACEMENT_TEXT.to_string();
+      }
+      writer.push(&ArchivedComment {
+        ap_id: comment.ap_id,
+        post,
+        parent,
+        creator,
+        content: comment.content,
+        distinguished: comment.distinguished,
+        removed: comment.removed,
+        deleted: comment.deleted,
+        published: comment.published,
+        updated: comment.updated,
+      })?;
+    }
+    writer.save_chunk(export_id, context).await?;
+    if batch_len < ARCHIVE_BATCH_SIZE as usize {
+      break;
+    }
+  }
+  writer.end_list();
+
+  writer.start_list("modlog");
+  for entry in build_modlog(community_id, context).await? {
+    writer.push(&entry)?;
+  }
+  writer.end_list();
+  writer.end();
+  writer.save_chunk(export_id, context).await?;
+  Ok(())
+}
+
+/// Builds the json document of a [CommunityArchive] piece by piece, so that the whole archive is
+/// never held in memory. Whatever was written since the last chunk is saved as the next chunk.
+struct ArchiveWriter {
+  next_seq: i32,
+  buffer: String,
+  first_item: bool,
+}
+
+impl ArchiveWriter {
+  /// Starts the document with everything except the lists of posts, comments and modlog entries.
+  fn new(community: &ArchivedCommunity, exported_at: NaiveDateTime) -> LemmyResult<Self> {
+    let buffer = format!(
+      r#"{{"version":{},"exported_at":{},"community":{}"#,
+      COMMUNITY_ARCHIVE_VERSION,
+      serde_json::to_string(&exported_at)?,
+      serde_json::to_string(community)?
+    );
+    Ok(ArchiveWriter {
+      next_seq: 0,
+      buffer,
+      first_item: true,
+    })
+  }
+
+  fn start_list(&mut self, name: &str) {
+    self.buffer.push_str(&format!(r#","{name}":["#));
+    self.first_item = true;
+  }
+
+  fn push<T: Serialize>(&mut self, item: &T) -> LemmyResult<()> {
+    if !self.first_item {
+      self.buffer.push(',');
+    }
+    self.buffer.push_str(&serde_json::to_string(item)?);
+    self.first_item = false;
+    Ok(())
+  }
+
+  fn end_list(&mut self) {
+    self.buffer.push(']');
+  }
+
+  fn end(&mut self) {
+    self.buffer.push('}');
+  }
+
+  async fn save_chunk(
+    &mut self,
+    export_id: CommunityExportId,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<()> {
+    if self.buffer.is_empty() {
+      return Ok(());
+    }
+    let chunk = std::mem::take(&mut self.buffer);
+    CommunityExport::add_chunk(&mut context.pool(), export_id, self.next_seq, &chunk).await?;
+    self.next_seq += 1;
+    Ok(())
+  }
+}
+
+async fn build_modlog(
+  community_id: CommunityId,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Vec<ArchivedModlogEntry>> {
+  let mut modlog = vec![];
+
+  let removed_posts = list_all_pages(community_id, |params| async move {
+    ModRemovePostView::list(&mut context.pool(), params).await
+  })
+  .await?;
+  modlog.extend(removed_posts.into_iter().map(|v| ArchivedModlogEntry {
+    action: if v.mod_remove_post.removed {
+      ArchivedModlogAction::RemovePost
+    } else {
+      ArchivedModlogAction::RestorePost
+    },
+    moderator: v.moderator.map(|m| m.actor_id),
+    target: v.post.ap_id,
+    reason: v.mod_remove_post.reason,
+    expires: None,
+    when_: v.mod_remove_post.when_,
+  }));
+
+  let locked_posts = list_all_pages(community_id, |params| async move {
+    ModLockPostView::list(&mut context.pool(), params).await
+  })
+  .await?;
+  modlog.extend(locked_posts.into_iter().map(|v| ArchivedModlogEntry {
+    action: if v.mod_lock_post.locked {
+      ArchivedModlogAction::LockPost
+    } else {
+      ArchivedModlogAction::UnlockPost
+    },
+    moderator: v.moderator.map(|m| m.actor_id),
+    target: v.post.ap_id,
+    reason: None,
+    expires: None,
+    when_: v.mod_lock_post.when_,
+  }));
+
+  let featured_posts = list_all_pages(community_id, |params| async move {
+    ModFeaturePostView::list(&mut context.pool(), params).await
+  })
+  .await?;
+  modlog.extend(
+    featured_posts
+      .into_iter()
+      // Featuring posts on the instance isn't part of the community
+      .filter(|v| v.mod_feature_post.is_featured_community)
+      .map(|v| ArchivedModlogEntry {
+        action: if v.mod_feature_post.featured {
+          ArchivedModlogAction::FeaturePost
+        } else {
+          ArchivedModlogAction::UnfeaturePost
+        },
+        moderator: v.moderator.map(|m| m.actor_id),
+        target: v.post.ap_id,
+        reason: None,
+        expires: None,
+        when_: v.mod_feature_post.when_,
+      }),
+  );
+
+  let removed_comments = list_all_pages(community_id, |params| async move {
+    ModRemoveCommentView::list(&mut context.pool(), params).await
+  })
+  .await?;
+  modlog.extend(removed_comments.into_iter().map(|v| ArchivedModlogEntry {
+    action: if v.mod_remove_comment.removed {
+      ArchivedModlogAction::RemoveComment
+    } else {
+      ArchivedModlogAction::RestoreComment
+    },
+    moderator: v.moderator.map(|m| m.actor_id),
+    target: v.comment.ap_id,
+    reason: v.mod_remove_comment.reason,
+    expires: None,
+    when_: v.mod_remove_comment.when_,
+  }));
+
+  let bans = list_all_pages(community_id, |params| async move {
+    ModBanFromCommunityView::list(&mut context.pool(), params).await
+  })
+  .await?;
+  modlog.extend(bans.into_iter().map(|v| ArchivedModlogEntry {
+    action: if v.mod_ban_from_community.banned {
+      ArchivedModlogAction::BanFromCommunity
+    } else {
+      ArchivedModlogAction::UnbanFromCommunity
+    },
+    moderator: v.moderator.map(|m| m.actor_id),
+    target: v.banned_person.actor_id,
+    reason: v.mod_ban_from_community.reason,
+    expires: v.mod_ban_from_community.expires,
+    when_: v.mod_ban_from_community.when_,
+  }));
+
+  let added_mods = list_all_pages(community_id, |params| async move {
+    ModAddCommunityView::list(&mut context.pool(), params).await
+  })
+  .await?;
+  modlog.extend(added_mods.into_iter().map(|v| ArchivedModlogEntry {
+    action: if v.mod_add_community.removed {
+      ArchivedModlogAction::RemoveModerator
+    } else {
+      ArchivedModlogAction::AddModerator
+    },
+    moderator: v.moderator.map(|m| m.actor_id),
+    target: v.modded_person.actor_id,
+    reason: None,
+    expires: None,
+    when_: v.mod_add_community.when_,
+  }));
+
+  let transfers = list_all_pages(community_id, |params| async move {
+    ModTransferCommunityView::list(&mut context.pool(), params).await
+  })
+  .await?;
+  modlog.extend(transfers.into_iter().map(|v| ArchivedModlogEntry {
+    action: ArchivedModlogAction::TransferCommunity,
+    moderator: v.moderator.map(|m| m.actor_id),
+    target: v.modded_person.actor_id,
+    reason: None,
+    expires: None,
+    when_: v.mod_transfer_community.when_,
+  }));
+
+  modlog.sort_by_key(|m| std::cmp::Reverse(m.when_));
+  Ok(modlog)
+}
+
+/// Modlog views are paginated, so this keeps reading pages until all entries of the community
+/// are loaded.
+async fn list_all_pages<T, E, F, Fut>(community_id: CommunityId, list: F) -> LemmyResult<Vec<T>>
+where
+  F: Fn(ModlogListParams) -> Fut,
+  Fut: Future<Output = Result<Vec<T>, E>>,
+  LemmyError: From<E>,
+{
+  let mut all = vec![];
+  let mut page = 1;
+  loop {
+    let params = ModlogListParams {
+      community_id: Some(community_id),
+      mod_person_id: None,
+      other_person_id: None,
+      page: Some(page),
+      limit: Some(FETCH_LIMIT_MAX),
+      hide_modlog_names: false,
+    };
+    let entries = list(params).await?;
+    let len = entries.len();
+    all.extend(entries);
+    if len < FETCH_LIMIT_MAX as usize {
+      break;
+    }
+    page += 1;
+  }
+  Ok(all)
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::ArchiveWriter;
+  use lemmy_api_common::community_archive::{
+    ArchivedCommunity,
+    ArchivedModlogAction,
+    ArchivedModlogEntry,
+    CommunityArchive,
+  };
+  use lemmy_db_schema::utils::naive_now;
+  use url::Url;
+
+  #[test]
+  fn test_archive_writer() {
+    let actor_id = Url::parse("https://example.com/c/test").unwrap().into();
+    let community = ArchivedCommunity {
+      actor_id,
+      name: "test".to_string(),
+      title: "Test".to_string(),
+      description: None,
+      icon: None,
+      banner: None,
+      nsfw: false,
+      posting_restricted_to_mods: false,
+      published: naive_now(),
+      moderators: vec![],
+      rules: vec![],
+    };
+    let exported_at = naive_now();
+    let mut writer = ArchiveWriter::new(&community, exported_at).unwrap();
+    let mut json = String::new();
+
+    writer.start_list("posts");
+    writer.end_list();
+    writer.start_list("comments");
+    writer.end_list();
+    json.push_str(&std::mem::take(&mut writer.buffer));
+    writer.start_list("modlog");
+    let entry = ArchivedModlogEntry {
+      action: ArchivedModlogAction::BanFromCommunity,
+      moderator: None,
+      target: Url::parse("https://example.com/u/bob").unwrap().into(),
+      reason: None,
+      expires: None,
+      when_: naive_now(),
+    };
+    writer.push(&entry).unwrap();
+    json.push_str(&std::mem::take(&mut writer.buffer));
+    writer.push(&entry).unwrap();
+    writer.end_list();
+    writer.end();
+    json.push_str(&writer.buffer);
+
+    let archive: CommunityArchive = serde_json::from_str(&json).unwrap();
+    assert_eq!(exported_at, archive.exported_at);
+    assert_eq!(community, archive.community);
+    assert!(archive.posts.is_empty());
+    assert_eq!(vec![entry.clone(), entry], archive.modlog);
+  }
+}
@@ -4,7 +4,12 @@ use lemmy_api_common::{
   community::{CommunityResponse, FollowCommunity},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{check_community_ban, check_community_deleted_or_removed, local_user_view_from_jwt},
+  utils::{
+    check_community_ban,
+    check_community_deleted_or_removed,
+    is_mod_or_admin,
+    local_user_view_from_jwt,
+  },
 };
 use lemmy_db_schema::{
   source::{
@@ -35,6 +40,13 @@ pub async fn follow_community(
       check_community_ban(local_user_view.person.id, community.id, &mut context.pool()).await?;
       check_community_deleted_or_removed(community.id, &mut context.pool()).await?;
 
+      // Restricted communities only let in followers which were approved by a mod
+      if community.requires_follow_approval {
+        community_follower_form.pending =
+          is_mod_or_admin(&mut context.pool(), local_user_view.person.id, community.id)
+            .await
+            .is_err();
+      }
       CommunityFollower::follow(&mut context.pool(), &community_follower_form)
         .await
         .with_lemmy_type(LemmyErrorType::CommunityFollowerAlreadyExists)?;
@@ -5,7 +5,7 @@ use lemmy_api_common::{
   community::{CommunityResponse, HideCommunity},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{is_admin, local_user_view_from_jwt, sanitize_html_opt},
+  utils::{check_admin_role, local_user_view_from_jwt, sanitize_html_opt},
 };
 use lemmy_db_schema::{
   source::{
@@ -13,6 +13,7 @@ use lemmy_db_schema::{
     moderator::{ModHideCommunity, ModHideCommunityForm},
   },
   traits::Crud,
+  AdminRole,
 };
 use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
 
@@ -23,7 +24,7 @@ pub async fn hide_community(
 ) -> Result<Json<CommunityResponse>, LemmyError> {
   // Verify its a admin (only admin can hide or unhide it)
   let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
-  is_admin(&local_user_view)?;
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
 
   let community_form = CommunityUpdateForm::builder()
     .hidden(Some(data.hidden))
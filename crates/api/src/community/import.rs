@@ -0,0 +1,641 @@
+use activitypub_federation::{config::Data, http_signatures::generate_actor_keypair};
+use actix_web::web::{Json, Query};
+use chrono::NaiveDateTime;
+use lemmy_api_common::{
+  community::{CommunityImportResponse, GetCommunityImport, ImportCommunity},
+  community_archive::{
+    ArchivedComment,
+    ArchivedCommunity,
+    ArchivedPost,
+    CommunityArchive,
+    RedditArchive,
+    COMMUNITY_ARCHIVE_VERSION,
+  },
+  context::LemmyContext,
+  utils::{
+    check_admin_role,
+    generate_followers_url,
+    generate_inbox_url,
+    generate_local_apub_endpoint,
+    generate_shared_inbox_url,
+    local_site_to_slur_regex,
+    local_user_view_from_jwt,
+    sanitize_html,
+    sanitize_html_opt,
+    EndpointType,
+  },
+};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, CommunityImportId, DbUrl, InstanceId, PersonId, PostId},
+  source::{
+    comment::{Comment, CommentInsertForm, CommentUpdateForm},
+    community::{
+      Community,
+      CommunityFollower,
+      CommunityFollowerForm,
+      CommunityInsertForm,
+      CommunityModerator,
+      CommunityModeratorForm,
+    },
+    community_import::{CommunityImport, CommunityImportForm, CommunityImportObject},
+    community_rule::{CommunityRule, CommunityRuleForm},
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm, PostUpdateForm},
+  },
+  traits::{ApubActor, Crud, Followable, Joinable},
+  utils::DbPool,
+  AdminRole,
+};
+use lemmy_db_views::structs::SiteView;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
+  spawn_try_task,
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_actor_name, is_valid_body_field, is_valid_post_title},
+  },
+};
+use std::{collections::HashMap, time::Duration};
+use tokio::time::sleep;
+use tracing::warn;
+use url::{ParseError, Url};
+
+/// Archives are much larger than other api requests, so the import endpoint has its own limit.
+pub const COMMUNITY_IMPORT_MAX_BYTES: usize = 100 * 1024 * 1024;
+
+/// Posts and comments are created in batches of this size, with a pause after each batch so that
+/// large imports don't slow down the instance.
+const IMPORT_BATCH_SIZE: usize = 100;
+const IMPORT_BATCH_DELAY: Duration = Duration::from_secs(1);
+
+#[tracing::instrument(skip(context, data))]
+pub async fn import_community(
+  data: Json<ImportCommunity>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityImportResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_site = site_view.local_site;
+  let data = data.into_inner();
+
+  let (archive, account_mapping) = match (data.archive, data.reddit_archive) {
+    (Some(archive), None) if archive.version == COMMUNITY_ARCHIVE_VERSION => {
+      let mapping = data
+        .account_mapping
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| Ok((Url::parse(&m.original)?.into(), m.person_id)))
+        .collect::<Result<HashMap<DbUrl, PersonId>, ParseError>>()
+        .with_lemmy_type(LemmyErrorType::InvalidCommunityArchive)?;
+      (archive, mapping)
+    }
+    (None, Some(reddit_archive)) => {
+      let mapping = data
+        .account_mapping
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| Ok((reddit_user_url(&m.original)?, m.person_id)))
+        .collect::<Result<HashMap<DbUrl, PersonId>, ParseError>>()
+        .with_lemmy_type(LemmyErrorType::InvalidCommunityArchive)?;
+      (reddit_to_archive(reddit_archive, &data.name)?, mapping)
+    }
+    _ => return Err(LemmyErrorType::InvalidCommunityArchive)?,
+  };
+  // Content can only be attributed to local accounts
+  for person_id in account_mapping.values() {
+    let person = Person::read(&mut context.pool(), *person_id).await?;
+    if !person.local {
+      return Err(LemmyErrorType::InvalidCommunityArchive)?;
+    }
+  }
+
+  let name = sanitize_html(&data.name);
+  let title = sanitize_html(data.title.as_ref().unwrap_or(&archive.community.title));
+  let description = sanitize_html_opt(&archive.community.description);
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  check_slurs(&name, &slur_regex)?;
+  check_slurs(&title, &slur_regex)?;
+  is_valid_actor_name(&data.name, local_site.actor_name_max_length as usize)?;
+  is_valid_body_field(&description, false)?;
+
+  let community_actor_id = generate_local_apub_endpoint(
+    EndpointType::Community,
+    &data.name,
+    &context.settings().get_protocol_and_hostname(),
+  )?;
+  // An import which was interrupted continues in the community which it created, the posts and
+  // comments which were already imported are skipped
+  let community = match Community::read_from_apub_id(&mut context.pool(), &community_actor_id)
+    .await?
+  {
+    Some(existing) => {
+      let interrupted =
+        CommunityImport::read_latest_for_community(&mut context.pool(), existing.id)
+          .await?
+          .and_then(|i| i.error)
+          .is_some_and(|e| e == LemmyErrorType::CommunityImportInterrupted.to_string());
+      if !interrupted {
+        return Err(LemmyErrorType::CommunityAlreadyExists)?;
+      }
+      existing
+    }
+    None => {
+      let keypair = generate_actor_keypair()?;
+      let community_form = CommunityInsertForm::builder()
+        .name(name)
+        .title(title)
+        .description(description)
+        .icon(archive.community.icon.clone())
+        .banner(archive.community.banner.clone())
+        .nsfw(Some(archive.community.nsfw))
+        .posting_restricted_to_mods(Some(archive.community.posting_restricted_to_mods))
+        .actor_id(Some(community_actor_id.clone()))
+        .private_key(Some(keypair.private_key))
+        .public_key(keypair.public_key)
+        .followers_url(Some(generate_followers_url(&community_actor_id)?))
+        .inbox_url(Some(generate_inbox_url(&community_actor_id)?))
+        .shared_inbox_url(Some(generate_shared_inbox_url(&community_actor_id)?))
+        .instance_id(site_view.site.instance_id)
+        .build();
+      let community = Community::create(&mut context.pool(), &community_form)
+        .await
+        .with_lemmy_type(LemmyErrorType::CommunityAlreadyExists)?;
+
+      // The importing admin moderates and follows the community, together with those moderators of
+      // the archive who have a local account
+      let mut moderators = vec![local_user_view.person.id];
+      for moderator in &archive.community.moderators {
+        let local_moderator = find_local_account(&mut context.pool(), &account_mapping, moderator)
+          .await?
+          .filter(|m| !moderators.contains(m));
+        moderators.extend(local_moderator);
+      }
+      for person_id in moderators {
+        let form = CommunityModeratorForm {
+          community_id: community.id,
+          person_id,
+        };
+        CommunityModerator::join(&mut context.pool(), &form)
+          .await
+          .with_lemmy_type(LemmyErrorType::CommunityModeratorAlreadyExists)?;
+      }
+      let follower_form = CommunityFollowerForm {
+        community_id: community.id,
+        person_id: local_user_view.person.id,
+        pending: false,
+      };
+      CommunityFollower::follow(&mut context.pool(), &follower_form)
+        .await
+        .with_lemmy_type(LemmyErrorType::CommunityFollowerAlreadyExists)?;
+
+      let rules = archive
+        .community
+        .rules
+        .iter()
+        .enumerate()
+        .map(|(position, r)| CommunityRuleForm {
+          community_id: community.id,
+          position: position as i32,
+          title: sanitize_html(&r.title),
+          description: sanitize_html_opt(&r.description),
+        })
+        .collect();
+      CommunityRule::replace(&mut context.pool(), community.id, rules).await?;
+      community
+    }
+  };
+
+  let form = CommunityImportForm {
+    community_id: community.id,
+    creator_id: local_user_view.person.id,
+  };
+  let community_import = CommunityImport::create(&mut context.pool(), &form).await?;
+
+  let import_id = community_import.id;
+  let accounts = ImportAccounts {
+    mapping: account_mapping,
+    instance_id: site_view.site.instance_id,
+  };
+  let context = context.reset_request_count();
+  spawn_try_task(async move {
+    let res = import_content(import_id, community.id, archive, accounts, &context).await;
+    let error = res.err().map(|e| {
+      warn!("Failed to import community {}: {e}", community.name);
+      e.error_type.to_string()
+    });
+    CommunityImport::finish(&mut context.pool(), import_id, error).await?;
+    Ok(())
+  });
+
+  Ok(Json(CommunityImportResponse { community_import }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn get_community_import(
+  data: Query<GetCommunityImport>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityImportResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+  let community_import = CommunityImport::read(&mut context.pool(), data.import_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindCommunityImport)?;
+  Ok(Json(CommunityImportResponse { community_import }))
+}
+
+/// Decides which account posts and comments of an author in the archive are attributed to.
+struct ImportAccounts {
+  /// Authors which are already resolved, initially those mapped by the admin.
+  mapping: HashMap<DbUrl, PersonId>,
+  instance_id: InstanceId,
+}
+
+impl ImportAccounts {
+  async fn resolve(
+    &mut self,
+    author: &DbUrl,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<PersonId> {
+    if let Some(person_id) = self.mapping.get(author) {
+      return Ok(*person_id);
+    }
+    let person_id = match find_local_account(&mut context.pool(), &self.mapping, author).await? {
+      Some(person_id) => person_id,
+      None => self.create_placeholder(author, context).await?,
+    };
+    self.mapping.insert(author.clone(), person_id);
+    Ok(person_id)
+  }
+
+  /// Creates a local account without login for an author of the archive. Its display name shows
+  /// the original account.
+  async fn create_placeholder(
+    &self,
+    author: &DbUrl,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<PersonId> {
+    let original_name = author
+      .path_segments()
+      .and_then(|mut s| s.next_back())
+      .unwrap_or_default();
+    let base_name: String = original_name
+      .chars()
+      .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+      .take(20)
+      .collect();
+    let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+    let mut suffix = 1;
+    let (name, actor_id) = loop {
+      let name = if suffix == 1 {
+        format!("archived_{base_name}")
+      } else {
+        format!("archived_{base_name}_{suffix}")
+      };
+      let actor_id =
+        generate_local_apub_endpoint(EndpointType::Person, &name, &protocol_and_hostname)?;
+      if Person::read_from_apub_id(&mut context.pool(), &actor_id)
+        .await?
+        .is_none()
+      {
+        break (name, actor_id);
+      }
+      suffix += 1;
+    };
+
+    let keypair = generate_actor_keypair()?;
+    let display_name = format!("{original_name}@{}", author.domain().unwrap_or_default());
+    let form = PersonInsertForm::builder()
+      .name(name)
+      .display_name(Some(display_name))
+      .actor_id(Some(actor_id.clone()))
+      .private_key(Some(keypair.private_key))
+      .public_key(keypair.public_key)
+      .inbox_url(Some(generate_inbox_url(&actor_id)?))
+      .shared_inbox_url(Some(generate_shared_inbox_url(&actor_id)?))
+      .instance_id(self.instance_id)
+      .build();
+    Ok(Person::create(&mut context.pool(), &form).await?.id)
+  }
+}
+
+/// The mapped account of the author, or the author itself if it is a local account.
+async fn find_local_account(
+  pool: &mut DbPool<'_>,
+  mapping: &HashMap<DbUrl, PersonId>,
+  author: &DbUrl,
+) -> LemmyResult<Option<PersonId>> {
+  if let Some(person_id) = mapping.get(author) {
+    return Ok(Some(*person_id));
+  }
+  let person = Person::read_from_apub_id(pool, author).await?;
+  Ok(person.filter(|p| p.local).map(|p| p.id))
+}
+
+/// Creates the posts and then the comments of the archive, in batches. Objects which appear in the
+/// archive more than once, which were already imported before, or which aren't valid on this
+/// instance, are skipped.
+async fn import_content(
+  import_id: CommunityImportId,
+  community_id: CommunityId,
+  archive: CommunityArchive,
+  mut accounts: ImportAccounts,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+
+  let mut posts: HashMap<DbUrl, PostId> = HashMap::new();
+  for batch in archive.posts.chunks(IMPORT_BATCH_SIZE) {
+    let (mut imported, mut skipped) = (0, 0);
+    for archived in batch {
+      if let Some((object, object_community_id)) =
+        CommunityImport::read_object(&mut context.pool(), &archived.ap_id).await?
+      {
+        // Replies can only be attached to posts in this community
+        if let (Some(post_id), true) = (object.post_id, object_community_id == community_id) {
+          posts.insert(archived.ap_id.clone(), post_id);
+        }
+        skipped += 1;
+        continue;
+      }
+      let name = sanitize_html(&archived.name.replace('\n', " "));
+      let body = sanitize_html_opt(&archived.body);
+      if posts.contains_key(&archived.ap_id)
+        || is_valid_post_title(&name).is_err()
+        || is_valid_body_field(&body, true).is_err()
+      {
+        skipped += 1;
+        continue;
+      }
+      let creator_id = accounts.resolve(&archived.creator, context).await?;
+      let form = PostInsertForm::builder()
+        .name(name)
+        .creator_id(creator_id)
+        .community_id(community_id)
+        .url(archived.url.clone())
+        .body(body)
+        .nsfw(Some(archived.nsfw))
+        .locked(Some(archived.locked))
+        .featured_community(Some(archived.featured))
+        .removed(Some(archived.removed))
+        .deleted(Some(archived.deleted))
+        .published(Some(archived.published))
+        .updated(archived.updated)
+        .build();
+      let post = Post::create(&mut context.pool(), &form)
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntCreatePost)?;
+      let ap_id = generate_local_apub_endpoint(
+        EndpointType::Post,
+        &post.id.to_string(),
+        &protocol_and_hostname,
+      )?;
+      Post::update(
+        &mut context.pool(),
+        post.id,
+        &PostUpdateForm::builder().ap_id(Some(ap_id)).build(),
+      )
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreatePost)?;
+      let object = CommunityImportObject {
+        source_ap_id: archived.ap_id.to_string(),
+        import_id,
+        post_id: Some(post.id),
+        comment_id: None,
+      };
+      CommunityImport::add_object(&mut context.pool(), &object).await?;
+      posts.insert(archived.ap_id.clone(), post.id);
+      imported += 1;
+    }
+    CommunityImport::add_progress(&mut context.pool(), import_id, imported, 0, skipped).await?;
+    sleep(IMPORT_BATCH_DELAY).await;
+  }
+
+  let mut comments: HashMap<DbUrl, Comment> = HashMap::new();
+  for batch in archive.comments.chunks(IMPORT_BATCH_SIZE) {
+    let (mut imported, mut skipped) = (0, 0);
+    for archived in batch {
+      if let Some((object, object_community_id)) =
+        CommunityImport::read_object(&mut context.pool(), &archived.ap_id).await?
+      {
+        if let (Some(comment_id), true) = (object.comment_id, object_community_id == community_id) {
+          let comment = Comment::read(&mut context.pool(), comment_id).await?;
+          comments.insert(archived.ap_id.clone(), comment);
+        }
+        skipped += 1;
+        continue;
+      }
+      let content = sanitize_html(&archived.content);
+      let post_id = posts.get(&archived.post);
+      let duplicate = comments.contains_key(&archived.ap_id);
+      let valid = is_valid_body_field(&Some(content.clone()), false).is_ok();
+      let (Some(post_id), false, true) = (post_id, duplicate, valid) else {
+        skipped += 1;
+        continue;
+      };
+      // Replies whose parent is missing become top level comments
+      let parent_path = archived
+        .parent
+        .as_ref()
+        .and_then(|p| comments.get(p))
+        .map(|parent| parent.path.clone());
+      let creator_id = accounts.resolve(&archived.creator, context).await?;
+      let form = CommentInsertForm::builder()
+        .creator_id(creator_id)
+        .post_id(*post_id)
+        .content(content)
+        .distinguished(Some(archived.distinguished))
+        .removed(Some(archived.removed))
+        .deleted(Some(archived.deleted))
+        .published(Some(archived.published))
+        .updated(archived.updated)
+        .build();
+      let comment = Comment::create(&mut context.pool(), &form, parent_path.as_ref())
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntCreateComment)?;
+      let ap_id = generate_local_apub_endpoint(
+        EndpointType::Comment,
+        &comment.id.to_string(),
+        &protocol_and_hostname,
+      )?;
+      let comment = Comment::update(
+        &mut context.pool(),
+        comment.id,
+        &CommentUpdateForm::builder().ap_id(Some(ap_id)).build(),
+      )
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreateComment)?;
+      let object = CommunityImportObject {
+        source_ap_id: archived.ap_id.to_string(),
+        import_id,
+        post_id: None,
+        comment_id: Some(comment.id),
+      };
+      CommunityImport::add_object(&mut context.pool(), &object).await?;
+      comments.insert(archived.ap_id.clone(), comment);
+      imported += 1;
+    }
+    CommunityImport::add_progress(&mut context.pool(), import_id, 0, imported, skipped).await?;
+    sleep(IMPORT_BATCH_DELAY).await;
+  }
+  Ok(())
+}
+
+/// Converts a reddit archive into the format of lemmy archives. Reddit objects are identified by
+/// their urls on reddit.
+fn reddit_to_archive(reddit: RedditArchive, name: &str) -> LemmyResult<CommunityArchive> {
+  let mut submissions = reddit.submissions;
+  submissions.sort_by(|a, b| a.created_utc.total_cmp(&b.created_utc));
+  let posts = submissions
+    .into_iter()
+    .map(|s| {
+      let url = s
+        .url
+        .filter(|_| !s.is_self)
+        .and_then(|u| Url::parse(&u).ok())
+        .map(Into::into);
+      let body = s.selftext.filter(|b| !b.is_empty());
+      Ok(ArchivedPost {
+        ap_id: reddit_post_url(&s.id)?,
+        creator: reddit_user_url(&s.author)?,
+        name: s.title,
+        url,
+        deleted: body.as_deref() == Some("[deleted]"),
+        removed: body.as_deref() == Some("[removed]"),
+        body,
+        nsfw: s.over_18,
+        locked: s.locked,
+        featured: s.stickied,
+        published: from_reddit_timestamp(s.created_utc),
+        updated: None,
+      })
+    })
+    .collect::<LemmyResult<Vec<_>>>()?;
+
+  // Sorting by time puts parents before their replies
+  let mut reddit_comments = reddit.comments;
+  reddit_comments.sort_by(|a, b| a.created_utc.total_cmp(&b.created_utc));
+  let comments = reddit_comments
+    .into_iter()
+    .map(|c| {
+      let post_id = c.link_id.trim_start_matches("t3_");
+      let parent = match c.parent_id.strip_prefix("t1_") {
+        Some(parent_id) => Some(reddit_comment_url(post_id, parent_id)?),
+        None => None,
+      };
+      Ok(ArchivedComment {
+        ap_id: reddit_comment_url(post_id, &c.id)?,
+        post: reddit_post_url(post_id)?,
+        parent,
+        creator: reddit_user_url(&c.author)?,
+        deleted: c.body == "[deleted]",
+        removed: c.body == "[removed]",
+        content: c.body,
+        distinguished: c.distinguished.as_deref() == Some("moderator"),
+        published: from_reddit_timestamp(c.created_utc),
+        updated: None,
+      })
+    })
+    .collect::<LemmyResult<Vec<_>>>()?;
+
+  Ok(CommunityArchive {
+    version: COMMUNITY_ARCHIVE_VERSION,
+    exported_at: lemmy_db_schema::utils::naive_now(),
+    community: ArchivedCommunity {
+      actor_id: Url::parse(&format!("https://www.reddit.com/r/{name}"))?.into(),
+      name: name.to_string(),
+      title: name.to_string(),
+      description: None,
+      icon: None,
+      banner: None,
+      nsfw: false,
+      posting_restricted_to_mods: false,
+      published: lemmy_db_schema::utils::naive_now(),
+      moderators: vec![],
+      rules: vec![],
+    },
+    posts,
+    comments,
+    modlog: vec![],
+  })
+}
+
+fn reddit_user_url(name: &str) -> Result<DbUrl, ParseError> {
+  Ok(Url::parse(&format!("https://www.reddit.com/user/{name}"))?.into())
+}
+
+fn reddit_post_url(id: &str) -> Result<DbUrl, ParseError> {
+  Ok(Url::parse(&format!("https://www.reddit.com/comments/{id}"))?.into())
+}
+
+fn reddit_comment_url(post_id: &str, id: &str) -> Result<DbUrl, ParseError> {
+  Ok(Url::parse(&format!("https://www.reddit.com/comments/{post_id}/_/{id}"))?.into())
+}
+
+fn from_reddit_timestamp(created_utc: f64) -> NaiveDateTime {
+  NaiveDateTime::from_timestamp_opt(created_utc as i64, 0).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::reddit_to_archive;
+  use lemmy_api_common::community_archive::{RedditArchive, RedditComment, RedditSubmission};
+
+  #[test]
+  fn test_reddit_to_archive() {
+    let submission = RedditSubmission {
+      id: "abc".to_string(),
+      author: "alice".to_string(),
+      title: "A post".to_string(),
+      selftext: Some("Hello".to_string()),
+      url: Some("https://www.reddit.com/r/test/comments/abc/a_post/".to_string()),
+      is_self: true,
+      over_18: false,
+      locked: false,
+      stickied: true,
+      created_utc: 1_600_000_000.0,
+    };
+    let reply = RedditComment {
+      id: "c2".to_string(),
+      author: "alice".to_string(),
+      body: "[deleted]".to_string(),
+      link_id: "t3_abc".to_string(),
+      parent_id: "t1_c1".to_string(),
+      distinguished: None,
+      created_utc: 1_600_000_200.0,
+    };
+    let top_level = RedditComment {
+      id: "c1".to_string(),
+      author: "bob".to_string(),
+      body: "First".to_string(),
+      link_id: "t3_abc".to_string(),
+      parent_id: "t3_abc".to_string(),
+      distinguished: Some("moderator".to_string()),
+      created_utc: 1_600_000_100.0,
+    };
+    let reddit = RedditArchive {
+      submissions: vec![submission],
+      comments: vec![reply, top_level],
+    };
+
+    let archive = reddit_to_archive(reddit, "test").unwrap();
+    assert_eq!(1, archive.posts.len());
+    let post = &archive.posts[0];
+    assert_eq!("https://www.reddit.com/comments/abc", post.ap_id.as_str());
+    assert_eq!("https://www.reddit.com/user/alice", post.creator.as_str());
+    assert_eq!(None, post.url);
+    assert!(post.featured);
+
+    // Comments are sorted so that the parent comes first
+    assert_eq!(2, archive.comments.len());
+    let (first, second) = (&archive.comments[0], &archive.comments[1]);
+    assert_eq!(None, first.parent);
+    assert!(first.distinguished);
+    assert_eq!(post.ap_id, first.post);
+    assert_eq!(Some(first.ap_id.clone()), second.parent);
+    assert!(second.deleted);
+  }
+}
@@ -0,0 +1,34 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  community::{ListCommunitiesResponse, ListHiddenCommunities},
+  context::LemmyContext,
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::SortType;
+use lemmy_db_views_actor::community_view::CommunityQuery;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_hidden_communities(
+  data: Query<ListHiddenCommunities>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListCommunitiesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  is_admin(&local_user_view)?;
+
+  // Without a local user, so that blocked and nsfw communities are listed too
+  let communities = CommunityQuery {
+    sort: Some(SortType::New),
+    is_mod_or_admin: Some(true),
+    show_nsfw: Some(true),
+    hidden_only: Some(true),
+    page: data.page,
+    limit: data.limit,
+    ..Default::default()
+  }
+  .list(&mut context.pool())
+  .await?;
+
+  Ok(Json(ListCommunitiesResponse { communities }))
+}
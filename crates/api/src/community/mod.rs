@@ -1,6 +1,17 @@
+pub mod accept_transfer;
 pub mod add_mod;
+pub mod approve_follower;
 pub mod ban;
 pub mod block;
+pub mod export;
 pub mod follow;
 pub mod hide;
+pub mod import;
+pub mod list_hidden;
+pub mod list_pending_follows;
+pub mod notification;
+pub mod rule;
+pub mod snooze;
+pub mod strikes;
 pub mod transfer;
+pub mod word_filter;
@@ -0,0 +1,46 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{CommunityNotificationResponse, SetCommunityNotification},
+  context::LemmyContext,
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::source::community_notification::{
+  CommunityNotification,
+  CommunityNotificationForm,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn set_community_notification(
+  data: Json<SetCommunityNotification>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityNotificationResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let person_id = local_user_view.person.id;
+
+  let community_notification = if data.enabled {
+    let min_interval_minutes = data.min_interval_minutes.unwrap_or(0);
+    if min_interval_minutes < 0 {
+      return Err(LemmyErrorType::InvalidNotificationInterval)?;
+    }
+    let form = CommunityNotificationForm {
+      person_id,
+      community_id: data.community_id,
+      min_interval_minutes,
+    };
+    let subscription = CommunityNotification::subscribe(&mut context.pool(), &form)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateNotification)?;
+    Some(subscription)
+  } else {
+    CommunityNotification::unsubscribe(&mut context.pool(), person_id, data.community_id)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateNotification)?;
+    None
+  };
+
+  Ok(Json(CommunityNotificationResponse {
+    community_notification,
+  }))
+}
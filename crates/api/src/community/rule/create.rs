@@ -0,0 +1,80 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{CommunityRuleResponse, CreateCommunityRule},
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{
+    is_mod_or_admin,
+    local_site_to_slur_regex,
+    local_user_view_from_jwt,
+    sanitize_html,
+    sanitize_html_opt,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    community_rule::{CommunityRule, CommunityRuleForm},
+    local_site::LocalSite,
+  },
+  traits::Crud,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::{
+    slurs::{check_slurs, check_slurs_opt},
+    validation::{is_valid_body_field, is_valid_post_title},
+  },
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn create_community_rule(
+  data: Json<CreateCommunityRule>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityRuleResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.community_id,
+  )
+  .await?;
+
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  check_slurs(&data.title, &slur_regex)?;
+  check_slurs_opt(&data.description, &slur_regex)?;
+  is_valid_post_title(&data.title)?;
+  is_valid_body_field(&data.description, false)?;
+
+  // New rules go after the existing ones by default
+  let position = match data.position {
+    Some(position) => position,
+    None => CommunityRule::for_community(&mut context.pool(), data.community_id)
+      .await?
+      .last()
+      .map(|r| r.position + 1)
+      .unwrap_or_default(),
+  };
+
+  let form = CommunityRuleForm {
+    community_id: data.community_id,
+    position,
+    title: sanitize_html(data.title.trim()),
+    description: sanitize_html_opt(&data.description),
+  };
+  let rule = CommunityRule::create(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateCommunityRule)?;
+
+  // Federate the new rules as part of the community
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person, community),
+    &context,
+  )
+  .await?;
+
+  Ok(Json(CommunityRuleResponse { rule }))
+}
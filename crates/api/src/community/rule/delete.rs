@@ -0,0 +1,45 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{DeleteCommunityRule, DeleteCommunityRuleResponse},
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{community::Community, community_rule::CommunityRule},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_community_rule(
+  data: Json<DeleteCommunityRule>,
+  context: Data<LemmyContext>,
+) -> Result<Json<DeleteCommunityRuleResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let rule = CommunityRule::read(&mut context.pool(), data.id)
+    .await
+    .with_lemmy_type(LemmyErrorType::InvalidCommunityRule)?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    rule.community_id,
+  )
+  .await?;
+
+  // Reports which refer to the rule are kept
+  CommunityRule::delete(&mut context.pool(), data.id).await?;
+
+  let community = Community::read(&mut context.pool(), rule.community_id).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person, community),
+    &context,
+  )
+  .await?;
+
+  Ok(Json(DeleteCommunityRuleResponse {
+    id: data.id,
+    success: true,
+  }))
+}
@@ -0,0 +1,72 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{CommunityRuleResponse, EditCommunityRule},
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{
+    is_mod_or_admin,
+    local_site_to_slur_regex,
+    local_user_view_from_jwt,
+    sanitize_html,
+    sanitize_html_opt,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    community_rule::{CommunityRule, CommunityRuleForm},
+    local_site::LocalSite,
+  },
+  traits::Crud,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::{
+    slurs::{check_slurs, check_slurs_opt},
+    validation::{is_valid_body_field, is_valid_post_title},
+  },
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn update_community_rule(
+  data: Json<EditCommunityRule>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityRuleResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let orig_rule = CommunityRule::read(&mut context.pool(), data.id)
+    .await
+    .with_lemmy_type(LemmyErrorType::InvalidCommunityRule)?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    orig_rule.community_id,
+  )
+  .await?;
+
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  check_slurs(&data.title, &slur_regex)?;
+  check_slurs_opt(&data.description, &slur_regex)?;
+  is_valid_post_title(&data.title)?;
+  is_valid_body_field(&data.description, false)?;
+
+  let form = CommunityRuleForm {
+    community_id: orig_rule.community_id,
+    position: data.position.unwrap_or(orig_rule.position),
+    title: sanitize_html(data.title.trim()),
+    description: sanitize_html_opt(&data.description),
+  };
+  let rule = CommunityRule::update(&mut context.pool(), data.id, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateCommunityRule)?;
+
+  let community = Community::read(&mut context.pool(), rule.community_id).await?;
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person, community),
+    &context,
+  )
+  .await?;
+
+  Ok(Json(CommunityRuleResponse { rule }))
+}
@@ -0,0 +1,47 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{SnoozeCommunity, SnoozeCommunityResponse},
+  context::LemmyContext,
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::{
+  source::community_snooze::{CommunitySnooze, CommunitySnoozeForm},
+  utils::naive_now,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::time::naive_from_unix,
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn snooze_community(
+  data: Json<SnoozeCommunity>,
+  context: Data<LemmyContext>,
+) -> Result<Json<SnoozeCommunityResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let person_id = local_user_view.person.id;
+
+  let community_snooze = if let Some(until) = data.until {
+    let expires = naive_from_unix(until);
+    if expires <= naive_now() {
+      return Err(LemmyErrorType::CouldntSnoozeCommunity)?;
+    }
+    let form = CommunitySnoozeForm {
+      person_id,
+      community_id: data.community_id,
+      expires,
+    };
+    let snooze = CommunitySnooze::snooze(&mut context.pool(), &form)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntSnoozeCommunity)?;
+    Some(snooze)
+  } else {
+    CommunitySnooze::unsnooze(&mut context.pool(), person_id, data.community_id)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntSnoozeCommunity)?;
+    None
+  };
+
+  Ok(Json(SnoozeCommunityResponse { community_snooze }))
+}
@@ -0,0 +1,40 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  community::{ListCommunityStrikes, ListCommunityStrikesResponse},
+  context::LemmyContext,
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{community::Community, community_strike::CommunityStrike},
+  traits::Crud,
+};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_community_strikes(
+  data: Query<ListCommunityStrikes>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListCommunityStrikesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.community_id,
+  )
+  .await?;
+
+  let strikes =
+    CommunityStrike::list_for_person(&mut context.pool(), data.community_id, data.person_id)
+      .await?;
+
+  // Suggest how close the user is to an automatic ban
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  let threshold = i64::from(community.strike_ban_threshold);
+  let strikes_until_ban = (threshold > 0).then(|| (threshold - strikes.len() as i64).max(0));
+
+  Ok(Json(ListCommunityStrikesResponse {
+    strikes,
+    strikes_until_ban,
+  }))
+}
@@ -1,26 +1,19 @@
 use crate::Perform;
 use actix_web::web::Data;
-use anyhow::Context;
 use lemmy_api_common::{
   community::{GetCommunityResponse, TransferCommunity},
   context::LemmyContext,
-  utils::{is_admin, is_top_mod, local_user_view_from_jwt},
+  utils::{check_admin_role, is_top_mod, local_user_view_from_jwt},
 };
 use lemmy_db_schema::{
-  source::{
-    community::{CommunityModerator, CommunityModeratorForm},
-    moderator::{ModTransferCommunity, ModTransferCommunityForm},
-  },
-  traits::{Crud, Joinable},
+  source::community::{Community, CommunityTransferRequest, CommunityTransferRequestForm},
+  traits::Crud,
+  AdminRole,
 };
 use lemmy_db_views_actor::structs::{CommunityModeratorView, CommunityView};
-use lemmy_utils::{
-  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
-  location_info,
-};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
 
-// TODO: we dont do anything for federation here, it should be updated the next time the community
-//       gets fetched. i hope we can get rid of the community creator role soon.
+/// Only creates a transfer request, the mods are reordered once the new owner accepts it.
 #[async_trait::async_trait(?Send)]
 impl Perform for TransferCommunity {
   type Response = GetCommunityResponse;
@@ -35,51 +28,34 @@ impl Perform for TransferCommunity {
 
     // Fetch the community mods
     let community_id = data.community_id;
-    let mut community_mods =
+    let community_mods =
       CommunityModeratorView::for_community(&mut context.pool(), community_id).await?;
 
     // Make sure transferrer is either the top community mod, or an admin
     if !(is_top_mod(&local_user_view, &community_mods).is_ok()
-      || is_admin(&local_user_view).is_ok())
+      || check_admin_role(&local_user_view, AdminRole::ContentAdmin).is_ok())
     {
       return Err(LemmyErrorType::NotAnAdmin)?;
     }
 
-    // You have to re-do the community_moderator table, reordering it.
-    // Add the transferee to the top
-    let creator_index = community_mods
-      .iter()
-      .position(|r| r.moderator.id == data.person_id)
-      .context(location_info!())?;
-    let creator_person = community_mods.remove(creator_index);
-    community_mods.insert(0, creator_person);
-
-    // Delete all the mods
-    let community_id = data.community_id;
-
-    CommunityModerator::delete_for_community(&mut context.pool(), community_id).await?;
-
-    // TODO: this should probably be a bulk operation
-    // Re-add the mods, in the new order
-    for cmod in &community_mods {
-      let community_moderator_form = CommunityModeratorForm {
-        community_id: cmod.community.id,
-        person_id: cmod.moderator.id,
-      };
-
-      CommunityModerator::join(&mut context.pool(), &community_moderator_form)
-        .await
-        .with_lemmy_type(LemmyErrorType::CommunityModeratorAlreadyExists)?;
+    let community = Community::read(&mut context.pool(), community_id).await?;
+    if !community.local {
+      return Err(LemmyErrorType::ObjectNotLocal)?;
     }
 
-    // Mod tables
-    let form = ModTransferCommunityForm {
-      mod_person_id: local_user_view.person.id,
-      other_person_id: data.person_id,
-      community_id: data.community_id,
+    // The new owner has to be an existing mod, and needs to accept before anything changes
+    if !community_mods
+      .iter()
+      .any(|m| m.moderator.id == data.person_id)
+    {
+      return Err(LemmyErrorType::NotAModerator)?;
+    }
+    let form = CommunityTransferRequestForm {
+      community_id,
+      requester_id: local_user_view.person.id,
+      recipient_id: data.person_id,
     };
-
-    ModTransferCommunity::create(&mut context.pool(), &form).await?;
+    let pending_transfer = CommunityTransferRequest::create(&mut context.pool(), &form).await?;
 
     let community_id = data.community_id;
     let person_id = local_user_view.person.id;
@@ -99,6 +75,9 @@ impl Perform for TransferCommunity {
       site: None,
       moderators,
       discussion_languages: vec![],
+      categories: vec![],
+      rules: vec![],
+      pending_transfer: Some(pending_transfer),
     })
   }
 }
@@ -0,0 +1,45 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{CommunityWordFilterResponse, CreateCommunityWordFilter},
+  context::LemmyContext,
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::community_word_filter::{CommunityWordFilter, CommunityWordFilterForm},
+  traits::Crud,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::build_and_check_regex,
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn create_community_word_filter(
+  data: Json<CreateCommunityWordFilter>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityWordFilterResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.community_id,
+  )
+  .await?;
+
+  let pattern = data.pattern.trim();
+  if build_and_check_regex(&Some(pattern))?.is_none() {
+    Err(LemmyErrorType::InvalidRegex)?
+  }
+
+  let form = CommunityWordFilterForm {
+    community_id: data.community_id,
+    pattern: pattern.to_string(),
+    action: data.action,
+  };
+  let word_filter = CommunityWordFilter::create(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateWordFilter)?;
+
+  Ok(Json(CommunityWordFilterResponse { word_filter }))
+}
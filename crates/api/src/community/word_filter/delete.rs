@@ -0,0 +1,30 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  community::{DeleteCommunityWordFilter, DeleteCommunityWordFilterResponse},
+  context::LemmyContext,
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{source::community_word_filter::CommunityWordFilter, traits::Crud};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_community_word_filter(
+  data: Json<DeleteCommunityWordFilter>,
+  context: Data<LemmyContext>,
+) -> Result<Json<DeleteCommunityWordFilterResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let word_filter = CommunityWordFilter::read(&mut context.pool(), data.id).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    word_filter.community_id,
+  )
+  .await?;
+
+  CommunityWordFilter::delete(&mut context.pool(), data.id).await?;
+  Ok(Json(DeleteCommunityWordFilterResponse {
+    id: data.id,
+    success: true,
+  }))
+}
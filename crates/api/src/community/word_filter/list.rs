@@ -0,0 +1,28 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  community::{ListCommunityWordFilters, ListCommunityWordFiltersResponse},
+  context::LemmyContext,
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::community_word_filter::CommunityWordFilter;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_community_word_filters(
+  data: Query<ListCommunityWordFilters>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListCommunityWordFiltersResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.community_id,
+  )
+  .await?;
+
+  let word_filters =
+    CommunityWordFilter::for_community(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListCommunityWordFiltersResponse { word_filters }))
+}
@@ -1,10 +1,18 @@
 use actix_web::web::Data;
 use base64::{engine::general_purpose::STANDARD_NO_PAD as base64, Engine};
 use captcha::Captcha;
-use lemmy_api_common::{context::LemmyContext, utils::local_site_to_slur_regex};
-use lemmy_db_schema::source::local_site::LocalSite;
+use lemmy_api_common::{
+  context::LemmyContext,
+  utils::{local_site_to_slur_regex, sanitize_html},
+};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, CommunityRuleId, ReportReasonId},
+  source::{community_rule::CommunityRule, local_site::LocalSite, report_reason::ReportReason},
+  traits::Crud,
+  utils::DbPool,
+};
 use lemmy_utils::{
-  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::slurs::check_slurs,
 };
 use std::io::Cursor;
@@ -17,6 +25,7 @@ pub mod post;
 pub mod post_report;
 pub mod private_message;
 pub mod private_message_report;
+pub mod report_reason;
 pub mod site;
 
 #[async_trait::async_trait(?Send)]
@@ -74,71 +83,51 @@ pub(crate) fn check_report_reason(reason: &str, local_site: &LocalSite) -> Resul
   Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-  #![allow(clippy::unwrap_used)]
-  #![allow(clippy::indexing_slicing)]
-
-  use lemmy_api_common::utils::check_validator_time;
-  use lemmy_db_schema::{
-    source::{
-      instance::Instance,
-      local_user::{LocalUser, LocalUserInsertForm},
-      person::{Person, PersonInsertForm},
-      secret::Secret,
-    },
-    traits::Crud,
-    utils::build_db_pool_for_tests,
+/// Returns the reason to store for a report, which has a listed reason, a community rule, free text,
+/// or a combination of them. The free text is only required without a listed reason or rule. If it
+/// is missing, the name of the listed reason or the title of the rule is stored instead, so that
+/// clients which don't know about them can still show it.
+pub(crate) async fn check_report_reason_with_id(
+  reason: &Option<String>,
+  reason_id: Option<ReportReasonId>,
+  rule_id: Option<CommunityRuleId>,
+  community_id: Option<CommunityId>,
+  local_site: &LocalSite,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<String> {
+  let reason = sanitize_html(reason.as_deref().unwrap_or_default().trim());
+
+  // Community reasons can only be used for content in that community
+  let reason_name = if let Some(reason_id) = reason_id {
+    let report_reason = ReportReason::read(pool, reason_id)
+      .await
+      .with_lemmy_type(LemmyErrorType::InvalidReportReason)?;
+    if report_reason.community_id.is_some() && report_reason.community_id != community_id {
+      Err(LemmyErrorType::InvalidReportReason)?
+    }
+    Some(report_reason.name)
+  } else {
+    None
   };
-  use lemmy_utils::{claims::Claims, settings::SETTINGS};
-  use serial_test::serial;
-
-  #[tokio::test]
-  #[serial]
-  async fn test_should_not_validate_user_token_after_password_change() {
-    let pool = &build_db_pool_for_tests().await;
-    let pool = &mut pool.into();
-    let secret = Secret::init(pool).await.unwrap();
-    let settings = &SETTINGS.to_owned();
 
-    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+  // Rules always belong to the community of the reported content
+  let rule_title = if let Some(rule_id) = rule_id {
+    let rule = CommunityRule::read(pool, rule_id)
       .await
-      .unwrap();
-
-    let new_person = PersonInsertForm::builder()
-      .name("Gerry9812".into())
-      .public_key("pubkey".to_string())
-      .instance_id(inserted_instance.id)
-      .build();
-
-    let inserted_person = Person::create(pool, &new_person).await.unwrap();
-
-    let local_user_form = LocalUserInsertForm::builder()
-      .person_id(inserted_person.id)
-      .password_encrypted("123456".to_string())
-      .build();
-
-    let inserted_local_user = LocalUser::create(pool, &local_user_form).await.unwrap();
-
-    let jwt = Claims::jwt(
-      inserted_local_user.id.0,
-      &secret.jwt_secret,
-      &settings.hostname,
-    )
-    .unwrap();
-    let claims = Claims::decode(&jwt, &secret.jwt_secret).unwrap().claims;
-    let check = check_validator_time(&inserted_local_user.validator_time, &claims);
-    assert!(check.is_ok());
-
-    // The check should fail, since the validator time is now newer than the jwt issue time
-    let updated_local_user =
-      LocalUser::update_password(pool, inserted_local_user.id, "password111")
-        .await
-        .unwrap();
-    let check_after = check_validator_time(&updated_local_user.validator_time, &claims);
-    assert!(check_after.is_err());
+      .with_lemmy_type(LemmyErrorType::InvalidCommunityRule)?;
+    if Some(rule.community_id) != community_id {
+      Err(LemmyErrorType::InvalidCommunityRule)?
+    }
+    Some(rule.title)
+  } else {
+    None
+  };
 
-    let num_deleted = Person::delete(pool, inserted_person.id).await.unwrap();
-    assert_eq!(1, num_deleted);
+  match reason_name.or(rule_title) {
+    Some(name) if reason.is_empty() => Ok(name),
+    _ => {
+      check_report_reason(&reason, local_site)?;
+      Ok(reason)
+    }
   }
 }
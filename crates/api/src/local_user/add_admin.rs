@@ -3,15 +3,18 @@ use actix_web::web::Data;
 use lemmy_api_common::{
   context::LemmyContext,
   person::{AddAdmin, AddAdminResponse},
-  utils::{is_admin, local_user_view_from_jwt},
+  utils::{check_admin_role, local_user_view_from_jwt},
 };
 use lemmy_db_schema::{
   source::{
+    local_user::{LocalUser, LocalUserUpdateForm},
     moderator::{ModAdd, ModAddForm},
     person::{Person, PersonUpdateForm},
   },
   traits::Crud,
+  AdminRole,
 };
+use lemmy_db_views::structs::LocalUserView;
 use lemmy_db_views_actor::structs::PersonView;
 use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
 
@@ -24,8 +27,8 @@ impl Perform for AddAdmin {
     let data: &AddAdmin = self;
     let local_user_view = local_user_view_from_jwt(&data.auth, context).await?;
 
-    // Make sure user is an admin
-    is_admin(&local_user_view)?;
+    // Only full admins can manage admins
+    check_admin_role(&local_user_view, AdminRole::FullAdmin)?;
 
     let added = data.added;
     let added_person_id = data.person_id;
@@ -37,6 +40,17 @@ impl Perform for AddAdmin {
     .await
     .with_lemmy_type(LemmyErrorType::CouldntUpdateUser)?;
 
+    // The role is stored with the local user, and removed together with the admin status
+    if let Ok(added_user) = LocalUserView::read_person(&mut context.pool(), added_person_id).await {
+      let admin_role = added.then(|| data.role.unwrap_or_default());
+      let form = LocalUserUpdateForm::builder()
+        .admin_role(Some(admin_role))
+        .build();
+      LocalUser::update(&mut context.pool(), added_user.local_user.id, &form)
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntUpdateUser)?;
+    }
+
     // Mod tables
     let form = ModAddForm {
       mod_person_id: local_user_view.person.id,
@@ -4,7 +4,13 @@ use lemmy_api_common::{
   context::LemmyContext,
   person::{BanPerson, BanPersonResponse},
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{is_admin, local_user_view_from_jwt, remove_user_data, sanitize_html_opt},
+  utils::{
+    check_admin_role,
+    check_admin_target,
+    local_user_view_from_jwt,
+    remove_user_data,
+    sanitize_html_opt,
+  },
 };
 use lemmy_db_schema::{
   source::{
@@ -12,6 +18,7 @@ use lemmy_db_schema::{
     person::{Person, PersonUpdateForm},
   },
   traits::Crud,
+  AdminRole,
 };
 use lemmy_db_views_actor::structs::PersonView;
 use lemmy_utils::{
@@ -26,7 +33,11 @@ pub async fn ban_from_site(
   let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
 
   // Make sure user is an admin
-  is_admin(&local_user_view)?;
+  check_admin_role(&local_user_view, AdminRole::UserAdmin)?;
+  let target = Person::read(&mut context.pool(), data.person_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindPerson)?;
+  check_admin_target(&local_user_view, &target)?;
 
   is_valid_body_field(&data.reason, false)?;
 
@@ -10,7 +10,10 @@ use lemmy_db_schema::{
   traits::Blockable,
 };
 use lemmy_db_views_actor::structs::PersonView;
-use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::time::naive_from_unix,
+};
 
 #[async_trait::async_trait(?Send)]
 impl Perform for BlockPerson {
@@ -32,6 +35,7 @@ impl Perform for BlockPerson {
     let person_block_form = PersonBlockForm {
       person_id,
       target_id,
+      expires: Some(data.expires.map(naive_from_unix)),
     };
 
     let target_person_view = PersonView::read(&mut context.pool(), target_id).await?;
@@ -1,60 +1,58 @@
-use crate::Perform;
-use actix_web::web::Data;
-use bcrypt::verify;
+use activitypub_federation::config::Data;
+use actix_web::{web::Json, HttpRequest};
 use lemmy_api_common::{
+  breached_password::check_breached_password,
   context::LemmyContext,
   person::{ChangePassword, LoginResponse},
-  utils::{local_user_view_from_jwt, password_length_check},
+  utils::{
+    create_login_session,
+    delete_all_login_sessions,
+    local_user_view_from_jwt,
+    password_length_check,
+  },
 };
-use lemmy_db_schema::source::local_user::LocalUser;
+use lemmy_db_schema::source::{local_site::LocalSite, local_user::LocalUser};
 use lemmy_utils::{
-  claims::Claims,
   error::{LemmyError, LemmyErrorType},
+  password::verify_password,
 };
 
-#[async_trait::async_trait(?Send)]
-impl Perform for ChangePassword {
-  type Response = LoginResponse;
+#[tracing::instrument(skip(context))]
+pub async fn change_password(
+  data: Json<ChangePassword>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+) -> Result<Json<LoginResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(data.auth.as_ref(), &context).await?;
 
-  #[tracing::instrument(skip(self, context))]
-  async fn perform(&self, context: &Data<LemmyContext>) -> Result<LoginResponse, LemmyError> {
-    let data: &ChangePassword = self;
-    let local_user_view = local_user_view_from_jwt(data.auth.as_ref(), context).await?;
+  password_length_check(&data.new_password)?;
 
-    password_length_check(&data.new_password)?;
-
-    // Make sure passwords match
-    if data.new_password != data.new_password_verify {
-      return Err(LemmyErrorType::PasswordsDoNotMatch)?;
-    }
+  // Make sure passwords match
+  if data.new_password != data.new_password_verify {
+    return Err(LemmyErrorType::PasswordsDoNotMatch)?;
+  }
 
-    // Check the old password
-    let valid: bool = verify(
-      &data.old_password,
-      &local_user_view.local_user.password_encrypted,
-    )
-    .unwrap_or(false);
-    if !valid {
-      return Err(LemmyErrorType::IncorrectLogin)?;
-    }
+  // Check the old password
+  let valid = verify_password(
+    &data.old_password,
+    &local_user_view.local_user.password_encrypted,
+  );
+  if !valid {
+    return Err(LemmyErrorType::IncorrectLogin)?;
+  }
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  check_breached_password(&data.new_password, &local_site, &context).await?;
 
-    let local_user_id = local_user_view.local_user.id;
-    let new_password = data.new_password.clone();
-    let updated_local_user =
-      LocalUser::update_password(&mut context.pool(), local_user_id, &new_password).await?;
+  let local_user_id = local_user_view.local_user.id;
+  let new_password = data.new_password.clone();
+  LocalUser::update_password(&mut context.pool(), local_user_id, &new_password).await?;
 
-    // Return the jwt
-    Ok(LoginResponse {
-      jwt: Some(
-        Claims::jwt(
-          updated_local_user.id.0,
-          &context.secret().jwt_secret,
-          &context.settings().hostname,
-        )?
-        .into(),
-      ),
-      verify_email_sent: false,
-      registration_created: false,
-    })
-  }
+  // Log out everywhere else, and return a new login token
+  delete_all_login_sessions(local_user_id, &context).await?;
+  let jwt = create_login_session(local_user_id, &req, &context).await?;
+  Ok(Json(LoginResponse {
+    jwt: Some(jwt),
+    verify_email_sent: false,
+    registration_created: false,
+  }))
 }
@@ -1,11 +1,13 @@
 use crate::Perform;
 use actix_web::web::Data;
 use lemmy_api_common::{
+  breached_password::check_breached_password,
   context::LemmyContext,
   person::{LoginResponse, PasswordChangeAfterReset},
-  utils::password_length_check,
+  utils::{delete_all_login_sessions, password_length_check},
 };
 use lemmy_db_schema::source::{
+  local_site::LocalSite,
   local_user::LocalUser,
   password_reset_request::PasswordResetRequest,
 };
@@ -31,6 +33,8 @@ impl Perform for PasswordChangeAfterReset {
     if data.password != data.password_verify {
       return Err(LemmyErrorType::PasswordsDoNotMatch)?;
     }
+    let local_site = LocalSite::read(&mut context.pool()).await?;
+    check_breached_password(&data.password, &local_site, context).await?;
 
     // Update the user with the new password
     let password = data.password.clone();
@@ -38,6 +42,9 @@ impl Perform for PasswordChangeAfterReset {
       .await
       .with_lemmy_type(LemmyErrorType::CouldntUpdateUser)?;
 
+    // Whoever knew the old password is logged out
+    delete_all_login_sessions(local_user_id, context).await?;
+
     Ok(LoginResponse {
       jwt: None,
       verify_email_sent: false,
@@ -0,0 +1,90 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{ChangeUsername, ChangeUsernameResponse},
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{
+    generate_inbox_url,
+    generate_local_apub_endpoint,
+    local_site_to_slur_regex,
+    local_user_view_from_jwt,
+    sanitize_html,
+    EndpointType,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    local_site::LocalSite,
+    person::{Person, PersonUpdateForm},
+    person_alias::{PersonAlias, PersonAliasForm},
+  },
+  traits::Crud,
+  utils::naive_now,
+};
+use lemmy_db_views_actor::structs::PersonView;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::{slurs::check_slurs, validation::is_valid_actor_name},
+};
+
+/// Renames the local user, and moves their actor to the new name. The old name is kept as an
+/// alias, so that other instances can follow the move and old links keep working.
+#[tracing::instrument(skip(context))]
+pub async fn change_username(
+  data: Json<ChangeUsername>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ChangeUsernameResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let person = local_user_view.person;
+
+  let aliases = PersonAlias::list_for_person(&mut context.pool(), person.id).await?;
+  if aliases.len() >= usize::try_from(local_site.max_username_changes).unwrap_or_default() {
+    return Err(LemmyErrorType::UsernameChangeLimitReached)?;
+  }
+
+  is_valid_actor_name(
+    &data.new_username,
+    local_site.actor_name_max_length as usize,
+  )?;
+  check_slurs(&data.new_username, &local_site_to_slur_regex(&local_site))?;
+  let reserved = PersonAlias::is_name_reserved(
+    &mut context.pool(),
+    &data.new_username,
+    Some(person.id),
+    local_site.username_reuse_cooldown_days,
+  )
+  .await?;
+  if reserved {
+    return Err(LemmyErrorType::UsernameRecentlyUsed)?;
+  }
+
+  let actor_id = generate_local_apub_endpoint(
+    EndpointType::Person,
+    &data.new_username,
+    &context.settings().get_protocol_and_hostname(),
+  )?;
+  let form = PersonUpdateForm::builder()
+    .name(Some(sanitize_html(&data.new_username)))
+    .actor_id(Some(actor_id.clone()))
+    .inbox_url(Some(generate_inbox_url(&actor_id)?))
+    .updated(Some(Some(naive_now())))
+    .build();
+  let updated_person = Person::update(&mut context.pool(), person.id, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::UserAlreadyExists)?;
+
+  let form = PersonAliasForm {
+    person_id: person.id,
+    name: person.name,
+    actor_id: person.actor_id,
+  };
+  PersonAlias::create(&mut context.pool(), &form).await?;
+
+  ActivityChannel::submit_activity(SendActivityData::UpdatePerson(updated_person), &context)
+    .await?;
+
+  let person_view = PersonView::read(&mut context.pool(), person.id).await?;
+  Ok(Json(ChangeUsernameResponse { person_view }))
+}
@@ -0,0 +1,35 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{ForceLogout, LogoutResponse},
+  utils::{
+    check_admin_role,
+    check_admin_target,
+    delete_all_login_sessions,
+    local_user_view_from_jwt,
+  },
+};
+use lemmy_db_schema::AdminRole;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn force_logout(
+  data: Json<ForceLogout>,
+  context: Data<LemmyContext>,
+) -> Result<Json<LogoutResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::UserAdmin)?;
+
+  // Only local users can be logged in here
+  let target = LocalUserView::read_person(&mut context.pool(), data.person_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindPerson)?;
+  check_admin_target(&local_user_view, &target.person)?;
+  delete_all_login_sessions(target.local_user.id, &context).await?;
+
+  Ok(Json(LogoutResponse {}))
+}
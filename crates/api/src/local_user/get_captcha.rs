@@ -3,13 +3,13 @@ use actix_web::web::Data;
 use captcha::{gen, Difficulty};
 use lemmy_api_common::{
   context::LemmyContext,
-  person::{CaptchaResponse, GetCaptcha, GetCaptchaResponse},
+  person::{CaptchaResponse, GetCaptcha, GetCaptchaResponse, HostedCaptcha},
 };
 use lemmy_db_schema::source::{
   captcha_answer::{CaptchaAnswer, CaptchaAnswerForm},
   local_site::LocalSite,
 };
-use lemmy_utils::error::LemmyError;
+use lemmy_utils::{error::LemmyError, settings::structs::CaptchaProvider};
 
 #[async_trait::async_trait(?Send)]
 impl Perform for GetCaptcha {
@@ -19,8 +19,30 @@ impl Perform for GetCaptcha {
   async fn perform(&self, context: &Data<LemmyContext>) -> Result<Self::Response, LemmyError> {
     let local_site = LocalSite::read(&mut context.pool()).await?;
 
-    if !local_site.captcha_enabled {
-      return Ok(GetCaptchaResponse { ok: None });
+    let config = &context.settings().captcha;
+
+    if !local_site.captcha_enabled && !config.require_for_posts {
+      return Ok(GetCaptchaResponse {
+        ok: None,
+        hosted: None,
+      });
+    }
+
+    let provider = match config.provider {
+      CaptchaProvider::Builtin => None,
+      CaptchaProvider::HCaptcha => Some("hcaptcha"),
+      CaptchaProvider::MCaptcha => Some("mcaptcha"),
+      CaptchaProvider::Turnstile => Some("turnstile"),
+    };
+    if let Some(provider) = provider {
+      return Ok(GetCaptchaResponse {
+        ok: None,
+        hosted: Some(HostedCaptcha {
+          provider: provider.to_string(),
+          site_key: config.site_key.clone(),
+          mcaptcha_url: config.mcaptcha_url.as_ref().map(ToString::to_string),
+        }),
+      });
     }
 
     let captcha = gen(match local_site.captcha_difficulty.as_str() {
@@ -45,6 +67,7 @@ impl Perform for GetCaptcha {
         wav,
         uuid: captcha.uuid.to_string(),
       }),
+      hosted: None,
     })
   }
 }
@@ -0,0 +1,139 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::Duration;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{
+    CreateInvite,
+    InviteResponse,
+    ListInviteUses,
+    ListInviteUsesResponse,
+    ListInvites,
+    ListInvitesResponse,
+    RevokeInvite,
+  },
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::{
+  source::{
+    invite::{Invite, InviteInsertForm},
+    local_site::LocalSite,
+    person::Person,
+  },
+  traits::Crud,
+  utils::naive_now,
+};
+use lemmy_db_views::structs::{InviteUseView, InviteView};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::time::naive_from_unix,
+};
+use uuid::Uuid;
+
+/// How many users can register with an invite which isn't created by an admin.
+const USER_INVITE_MAX_USES: i32 = 10;
+
+/// Invites which aren't created by an admin need to expire within this many days.
+const USER_INVITE_MAX_DAYS: i64 = 30;
+
+#[tracing::instrument(skip(context))]
+pub async fn create_invite(
+  data: Json<CreateInvite>,
+  context: Data<LemmyContext>,
+) -> Result<Json<InviteResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  if !local_user_view.person.admin && !local_site.users_can_create_invites {
+    Err(LemmyErrorType::OnlyAdminsCanCreateInvites)?
+  }
+
+  // Invites of users are limited, so that they can't open up registration for everyone
+  let admin = local_user_view.person.admin;
+  let max_uses = data.max_uses.unwrap_or(1);
+  if max_uses < 1 || (!admin && max_uses > USER_INVITE_MAX_USES) {
+    Err(LemmyErrorType::InvalidInviteMaxUses)?
+  }
+  let expires = data.expires.map(naive_from_unix);
+  let latest_expiry = naive_now() + Duration::days(USER_INVITE_MAX_DAYS);
+  let valid_expiry = match expires {
+    Some(e) => e > naive_now() && (admin || e <= latest_expiry),
+    None => admin,
+  };
+  if !valid_expiry {
+    Err(LemmyErrorType::InvalidInviteExpires)?
+  }
+
+  let form = InviteInsertForm {
+    code: Uuid::new_v4().simple().to_string(),
+    creator_id: local_user_view.person.id,
+    max_uses,
+    expires,
+  };
+  let invite = Invite::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(InviteResponse {
+    invite_view: InviteView {
+      invite,
+      creator: local_user_view.person,
+    },
+  }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn list_invites(
+  data: Query<ListInvites>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListInvitesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Only admins can see the invites of others
+  let creator_id = if local_user_view.person.admin {
+    data.creator_id
+  } else {
+    Some(local_user_view.person.id)
+  };
+  let invites = InviteView::list(&mut context.pool(), creator_id, data.page, data.limit).await?;
+
+  Ok(Json(ListInvitesResponse { invites }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn revoke_invite(
+  data: Json<RevokeInvite>,
+  context: Data<LemmyContext>,
+) -> Result<Json<InviteResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  let invite = Invite::read(&mut context.pool(), data.invite_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindInvite)?;
+  if invite.creator_id != local_user_view.person.id && !local_user_view.person.admin {
+    Err(LemmyErrorType::CouldntFindInvite)?
+  }
+
+  let invite = Invite::revoke(&mut context.pool(), invite.id).await?;
+  let creator = Person::read(&mut context.pool(), invite.creator_id).await?;
+
+  Ok(Json(InviteResponse {
+    invite_view: InviteView { invite, creator },
+  }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn list_invite_uses(
+  data: Query<ListInviteUses>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListInviteUsesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Only admins can see who was invited by others
+  let inviter_id = if local_user_view.person.admin {
+    data.inviter_id
+  } else {
+    Some(local_user_view.person.id)
+  };
+  let invite_uses =
+    InviteUseView::list(&mut context.pool(), inviter_id, data.page, data.limit).await?;
+
+  Ok(Json(ListInviteUsesResponse { invite_uses }))
+}
@@ -0,0 +1,21 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{ListLogins, ListLoginsResponse},
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::source::login_session::LoginSession;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_logins(
+  data: Query<ListLogins>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListLoginsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  let logins = LoginSession::list(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListLoginsResponse { logins }))
+}
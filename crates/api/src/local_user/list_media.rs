@@ -0,0 +1,27 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{ListMedia, ListMediaResponse},
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::source::local_image::LocalImage;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_media(
+  data: Query<ListMedia>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListMediaResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  let images = LocalImage::list_for_person(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.page,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(ListMediaResponse { images }))
+}
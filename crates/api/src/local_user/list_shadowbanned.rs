@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{GetShadowbannedPersons, ShadowbannedPersonsResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_views_actor::structs::PersonView;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_shadowbanned(
+  data: Query<GetShadowbannedPersons>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ShadowbannedPersonsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let shadowbanned = PersonView::shadowbanned(&mut context.pool()).await?;
+
+  Ok(Json(ShadowbannedPersonsResponse { shadowbanned }))
+}
@@ -1,82 +1,93 @@
-use crate::Perform;
-use actix_web::web::Data;
-use bcrypt::verify;
+use activitypub_federation::config::Data;
+use actix_web::{web::Json, HttpRequest};
 use lemmy_api_common::{
   context::LemmyContext,
   person::{Login, LoginResponse},
-  utils::{check_registration_application, check_user_valid},
+  utils::{
+    check_registration_application,
+    check_user_valid,
+    create_login_session,
+    send_login_alert_email,
+  },
+};
+use lemmy_db_schema::{
+  source::local_user::{LocalUser, LocalUserUpdateForm},
+  traits::Crud,
 };
 use lemmy_db_views::structs::{LocalUserView, SiteView};
 use lemmy_utils::{
-  claims::Claims,
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  password::{hash_password, password_needs_rehash, verify_password},
   utils::validation::check_totp_2fa_valid,
 };
 
-#[async_trait::async_trait(?Send)]
-impl Perform for Login {
-  type Response = LoginResponse;
-
-  #[tracing::instrument(skip(context))]
-  async fn perform(&self, context: &Data<LemmyContext>) -> Result<LoginResponse, LemmyError> {
-    let data: &Login = self;
+#[tracing::instrument(skip(context))]
+pub async fn login(
+  data: Json<Login>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+) -> Result<Json<LoginResponse>, LemmyError> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
 
-    let site_view = SiteView::read_local(&mut context.pool()).await?;
+  // Fetch that username / email
+  let username_or_email = data.username_or_email.clone();
+  let local_user_view =
+    LocalUserView::find_by_email_or_name(&mut context.pool(), &username_or_email)
+      .await
+      .with_lemmy_type(LemmyErrorType::IncorrectLogin)?;
 
-    // Fetch that username / email
-    let username_or_email = data.username_or_email.clone();
-    let local_user_view =
-      LocalUserView::find_by_email_or_name(&mut context.pool(), &username_or_email)
-        .await
-        .with_lemmy_type(LemmyErrorType::IncorrectLogin)?;
+  // Verify the password
+  let valid = verify_password(
+    &data.password,
+    &local_user_view.local_user.password_encrypted,
+  );
+  if !valid {
+    return Err(LemmyErrorType::IncorrectLogin)?;
+  }
+  check_user_valid(
+    local_user_view.person.banned,
+    local_user_view.person.ban_expires,
+    local_user_view.person.deleted,
+  )?;
 
-    // Verify the password
-    let valid: bool = verify(
-      &data.password,
-      &local_user_view.local_user.password_encrypted,
-    )
-    .unwrap_or(false);
-    if !valid {
-      return Err(LemmyErrorType::IncorrectLogin)?;
-    }
-    check_user_valid(
-      local_user_view.person.banned,
-      local_user_view.person.ban_expires,
-      local_user_view.person.deleted,
-    )?;
+  // Check if the user's email is verified if email verification is turned on
+  // However, skip checking verification if the user is an admin
+  if !local_user_view.person.admin
+    && site_view.local_site.require_email_verification
+    && !local_user_view.local_user.email_verified
+  {
+    return Err(LemmyErrorType::EmailNotVerified)?;
+  }
 
-    // Check if the user's email is verified if email verification is turned on
-    // However, skip checking verification if the user is an admin
-    if !local_user_view.person.admin
-      && site_view.local_site.require_email_verification
-      && !local_user_view.local_user.email_verified
-    {
-      return Err(LemmyErrorType::EmailNotVerified)?;
-    }
+  check_registration_application(&local_user_view, &site_view.local_site, &mut context.pool())
+    .await?;
 
-    check_registration_application(&local_user_view, &site_view.local_site, &mut context.pool())
-      .await?;
+  // Check the totp
+  check_totp_2fa_valid(
+    &local_user_view.local_user.totp_2fa_secret,
+    &data.totp_2fa_token,
+    &site_view.site.name,
+    &local_user_view.person.name,
+  )?;
 
-    // Check the totp
-    check_totp_2fa_valid(
-      &local_user_view.local_user.totp_2fa_secret,
-      &data.totp_2fa_token,
-      &site_view.site.name,
-      &local_user_view.person.name,
-    )?;
+  // Upgrade bcrypt hashes, or hashes with outdated parameters, now that the password is known
+  if password_needs_rehash(&local_user_view.local_user.password_encrypted) {
+    let form = LocalUserUpdateForm::builder()
+      .password_encrypted(Some(hash_password(&data.password)?))
+      .build();
+    LocalUser::update(&mut context.pool(), local_user_view.local_user.id, &form)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateUser)?;
+  }
 
-    // Return the jwt
-    Ok(LoginResponse {
-      jwt: Some(
-        Claims::jwt(
-          local_user_view.local_user.id.0,
-          &context.secret().jwt_secret,
-          &context.settings().hostname,
-        )?
-        .into(),
-      ),
-      verify_email_sent: false,
-      registration_created: false,
-    })
+  // Return the login token
+  let jwt = create_login_session(local_user_view.local_user.id, &req, &context).await?;
+  if let Err(e) = send_login_alert_email(&local_user_view, &jwt, &context).await {
+    tracing::warn!("Failed to send login alert email: {e}");
   }
+  Ok(Json(LoginResponse {
+    jwt: Some(jwt),
+    verify_email_sent: false,
+    registration_created: false,
+  }))
 }
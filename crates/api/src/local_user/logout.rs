@@ -0,0 +1,21 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{Logout, LogoutResponse},
+  utils::{delete_login_session, local_user_view_from_jwt},
+};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn logout(
+  data: Json<Logout>,
+  context: Data<LemmyContext>,
+) -> Result<Json<LogoutResponse>, LemmyError> {
+  // Make sure the token is valid
+  local_user_view_from_jwt(&data.auth, &context).await?;
+
+  delete_login_session(&data.auth, &context).await?;
+
+  Ok(Json(LogoutResponse {}))
+}
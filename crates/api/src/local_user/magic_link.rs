@@ -0,0 +1,92 @@
+use activitypub_federation::config::Data;
+use actix_web::{web::Json, HttpRequest};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{LoginResponse, RedeemMagicLink, RequestMagicLink, RequestMagicLinkResponse},
+  utils::{
+    check_registration_application,
+    check_user_valid,
+    create_login_session,
+    send_login_alert_email,
+    send_magic_link_email,
+  },
+};
+use lemmy_db_schema::source::magic_login_token::MagicLoginToken;
+use lemmy_db_views::structs::{LocalUserView, SiteView};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  session::hash_session_token,
+  utils::validation::check_totp_2fa_valid,
+};
+
+/// Emails a single use login link to the user with the given email.
+#[tracing::instrument(skip(context))]
+pub async fn request_magic_link(
+  data: Json<RequestMagicLink>,
+  context: Data<LemmyContext>,
+) -> Result<Json<RequestMagicLinkResponse>, LemmyError> {
+  // Fetch that email
+  let email = data.email.to_lowercase();
+  let local_user_view = LocalUserView::find_by_email(&mut context.pool(), &email)
+    .await
+    .with_lemmy_type(LemmyErrorType::IncorrectLogin)?;
+
+  // Check for too many attempts (to limit potential abuse)
+  let recent_count =
+    MagicLoginToken::get_recent_count(&mut context.pool(), local_user_view.local_user.id).await?;
+  if recent_count >= 3 {
+    return Err(LemmyErrorType::MagicLinkLimitReached)?;
+  }
+
+  send_magic_link_email(&local_user_view, &mut context.pool(), context.settings()).await?;
+  Ok(Json(RequestMagicLinkResponse {}))
+}
+
+/// Logs in with the token of a login link. The token is used up even if the login fails later on,
+/// so a new link has to be requested then.
+#[tracing::instrument(skip(context))]
+pub async fn redeem_magic_link(
+  data: Json<RedeemMagicLink>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+) -> Result<Json<LoginResponse>, LemmyError> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+
+  let local_user_id =
+    MagicLoginToken::redeem(&mut context.pool(), &hash_session_token(&data.token))
+      .await
+      .with_lemmy_type(LemmyErrorType::InvalidMagicLink)?;
+  let local_user_view = LocalUserView::read(&mut context.pool(), local_user_id).await?;
+
+  // The same checks as for a login with password
+  check_user_valid(
+    local_user_view.person.banned,
+    local_user_view.person.ban_expires,
+    local_user_view.person.deleted,
+  )?;
+  if !local_user_view.person.admin
+    && site_view.local_site.require_email_verification
+    && !local_user_view.local_user.email_verified
+  {
+    return Err(LemmyErrorType::EmailNotVerified)?;
+  }
+  check_registration_application(&local_user_view, &site_view.local_site, &mut context.pool())
+    .await?;
+  check_totp_2fa_valid(
+    &local_user_view.local_user.totp_2fa_secret,
+    &data.totp_2fa_token,
+    &site_view.site.name,
+    &local_user_view.person.name,
+  )?;
+
+  // Return the login token
+  let jwt = create_login_session(local_user_view.local_user.id, &req, &context).await?;
+  if let Err(e) = send_login_alert_email(&local_user_view, &jwt, &context).await {
+    tracing::warn!("Failed to send login alert email: {e}");
+  }
+  Ok(Json(LoginResponse {
+    jwt: Some(jwt),
+    verify_email_sent: false,
+    registration_created: false,
+  }))
+}
@@ -0,0 +1,72 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{MergePersons, MergePersonsResponse},
+  utils::{
+    check_admin_role,
+    check_admin_target,
+    delete_all_login_sessions,
+    local_user_view_from_jwt,
+    sanitize_html_opt,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    moderator::{AdminMergePerson, AdminMergePersonForm},
+    person::Person,
+  },
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_db_views_actor::structs::PersonView;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::is_valid_body_field,
+};
+
+/// Moves the posts, comments, follows and votes of a duplicate account to another local account,
+/// and disables the duplicate.
+#[tracing::instrument(skip(context))]
+pub async fn merge_person(
+  data: Json<MergePersons>,
+  context: Data<LemmyContext>,
+) -> Result<Json<MergePersonsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::UserAdmin)?;
+
+  is_valid_body_field(&data.reason, false)?;
+
+  if data.source_person_id == data.target_person_id {
+    return Err(LemmyErrorType::InvalidAccountMerge)?;
+  }
+  // Both accounts have to be local
+  let source = LocalUserView::read_person(&mut context.pool(), data.source_person_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::InvalidAccountMerge)?;
+  let target = LocalUserView::read_person(&mut context.pool(), data.target_person_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::InvalidAccountMerge)?;
+  check_admin_target(&local_user_view, &source.person)?;
+  check_admin_target(&local_user_view, &target.person)?;
+
+  Person::merge_into(&mut context.pool(), source.person.id, target.person.id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateUser)?;
+  delete_all_login_sessions(source.local_user.id, &context).await?;
+
+  // Mod tables
+  let form = AdminMergePersonForm {
+    admin_person_id: local_user_view.person.id,
+    source_person_id: source.person.id,
+    target_person_id: target.person.id,
+    reason: sanitize_html_opt(&data.reason),
+  };
+  AdminMergePerson::create(&mut context.pool(), &form).await?;
+
+  let person_view = PersonView::read(&mut context.pool(), target.person.id).await?;
+  Ok(Json(MergePersonsResponse { person_view }))
+}
@@ -3,11 +3,23 @@ pub mod ban_person;
 pub mod block;
 pub mod change_password;
 pub mod change_password_after_reset;
+pub mod change_username;
+pub mod force_logout;
 pub mod get_captcha;
+pub mod invite;
 pub mod list_banned;
+pub mod list_logins;
+pub mod list_media;
+pub mod list_shadowbanned;
 pub mod login;
+pub mod logout;
+pub mod magic_link;
+pub mod merge_person;
+pub mod note;
 pub mod notifications;
 pub mod report_count;
 pub mod reset_password;
+pub mod revoke_login;
 pub mod save_settings;
+pub mod shadowban_person;
 pub mod verify_email;
@@ -0,0 +1,52 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{PersonNoteResponse, SetPersonNote},
+  utils::{local_user_view_from_jwt, sanitize_html},
+};
+use lemmy_db_schema::{
+  source::{
+    person::Person,
+    person_note::{PersonNote, PersonNoteForm},
+  },
+  traits::Crud,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::check_person_note,
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn set_person_note(
+  data: Json<SetPersonNote>,
+  context: Data<LemmyContext>,
+) -> Result<Json<PersonNoteResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let person_id = local_user_view.person.id;
+  let target_id = data.person_id;
+
+  // Make sure the user exists
+  Person::read(&mut context.pool(), target_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindPerson)?;
+
+  let Some(note) = &data.note else {
+    PersonNote::delete(&mut context.pool(), person_id, target_id).await?;
+    return Ok(Json(PersonNoteResponse { person_note: None }));
+  };
+  check_person_note(note, &data.color)?;
+
+  let form = PersonNoteForm {
+    person_id,
+    target_id,
+    note: sanitize_html(note),
+    color: data.color.clone(),
+    updated: None,
+  };
+  let person_note = PersonNote::set(&mut context.pool(), &form).await?;
+
+  Ok(Json(PersonNoteResponse {
+    person_note: Some(person_note),
+  }))
+}
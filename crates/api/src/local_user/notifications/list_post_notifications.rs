@@ -0,0 +1,38 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{GetPostNotifications, GetPostNotificationsResponse, PostNotificationView},
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::source::community_notification::PostNotification;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_post_notifications(
+  data: Query<GetPostNotifications>,
+  context: Data<LemmyContext>,
+) -> Result<Json<GetPostNotificationsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  let notifications = PostNotification::list(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.unread_only.unwrap_or_default(),
+    data.page,
+    data.limit,
+  )
+  .await?
+  .into_iter()
+  .map(
+    |(post_notification, post, community, creator)| PostNotificationView {
+      post_notification,
+      post,
+      community,
+      creator,
+    },
+  )
+  .collect();
+
+  Ok(Json(GetPostNotificationsResponse { notifications }))
+}
@@ -7,6 +7,7 @@ use lemmy_api_common::{
 };
 use lemmy_db_schema::source::{
   comment_reply::CommentReply,
+  community_notification::PostNotification,
   person_mention::PersonMention,
   private_message::PrivateMessage,
 };
@@ -37,6 +38,11 @@ impl Perform for MarkAllAsRead {
       .await
       .with_lemmy_type(LemmyErrorType::CouldntUpdatePrivateMessage)?;
 
+    // Mark all new post notifications as read
+    PostNotification::mark_all_as_read(&mut context.pool(), person_id)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateNotification)?;
+
     Ok(GetRepliesResponse { replies: vec![] })
   }
 }
@@ -0,0 +1,32 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{MarkPostNotificationAsRead, PostNotificationResponse},
+  utils::local_user_view_from_jwt,
+};
+use lemmy_db_schema::source::community_notification::PostNotification;
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn mark_post_notification_as_read(
+  data: Json<MarkPostNotificationAsRead>,
+  context: Data<LemmyContext>,
+) -> Result<Json<PostNotificationResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  let post_notification_id = data.post_notification_id;
+  let read_post_notification =
+    PostNotification::read(&mut context.pool(), post_notification_id).await?;
+
+  if local_user_view.person.id != read_post_notification.recipient_id {
+    return Err(LemmyErrorType::CouldntUpdateNotification)?;
+  }
+
+  let post_notification =
+    PostNotification::mark_as_read(&mut context.pool(), post_notification_id, data.read)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateNotification)?;
+
+  Ok(Json(PostNotificationResponse { post_notification }))
+}
@@ -5,6 +5,7 @@ use lemmy_api_common::{
   person::{GetUnreadCount, GetUnreadCountResponse},
   utils::local_user_view_from_jwt,
 };
+use lemmy_db_schema::source::community_notification::PostNotification;
 use lemmy_db_views::structs::PrivateMessageView;
 use lemmy_db_views_actor::structs::{CommentReplyView, PersonMentionView};
 use lemmy_utils::error::LemmyError;
@@ -27,10 +28,13 @@ impl Perform for GetUnreadCount {
     let private_messages =
       PrivateMessageView::get_unread_messages(&mut context.pool(), person_id).await?;
 
+    let post_notifications = PostNotification::count_unread(&mut context.pool(), person_id).await?;
+
     Ok(Self::Response {
       replies,
       mentions,
       private_messages,
+      post_notifications,
     })
   }
 }
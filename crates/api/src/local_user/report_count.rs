@@ -3,6 +3,7 @@ use actix_web::web::Data;
 use lemmy_api_common::{
   context::LemmyContext,
   person::{GetReportCount, GetReportCountResponse},
+  report_reason::ReportReasonCount,
   utils::local_user_view_from_jwt,
 };
 use lemmy_db_views::structs::{CommentReportView, PostReportView, PrivateMessageReportView};
@@ -37,11 +38,41 @@ impl Perform for GetReportCount {
       None
     };
 
+    let mut counts_by_reason = [
+      CommentReportView::get_report_count_by_reason(
+        &mut context.pool(),
+        person_id,
+        admin,
+        community_id,
+      )
+      .await?,
+      PostReportView::get_report_count_by_reason(
+        &mut context.pool(),
+        person_id,
+        admin,
+        community_id,
+      )
+      .await?,
+    ]
+    .concat();
+    if private_message_reports.is_some() {
+      counts_by_reason
+        .extend(PrivateMessageReportView::get_report_count_by_reason(&mut context.pool()).await?);
+    }
+    let mut reason_counts: Vec<ReportReasonCount> = vec![];
+    for (reason_id, reports) in counts_by_reason {
+      match reason_counts.iter_mut().find(|c| c.reason_id == reason_id) {
+        Some(count) => count.reports += reports,
+        None => reason_counts.push(ReportReasonCount { reason_id, reports }),
+      }
+    }
+
     Ok(GetReportCountResponse {
       community_id,
       comment_reports,
       post_reports,
       private_message_reports,
+      reason_counts,
     })
   }
 }
@@ -0,0 +1,25 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{LogoutResponse, RevokeLogin},
+  utils::{local_user_view_from_jwt, revoke_login_session},
+};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn revoke_login(
+  data: Json<RevokeLogin>,
+  context: Data<LemmyContext>,
+) -> Result<Json<LogoutResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  revoke_login_session(
+    data.login_session_id,
+    local_user_view.local_user.id,
+    &context,
+  )
+  .await?;
+
+  Ok(Json(LogoutResponse {}))
+}
@@ -2,8 +2,14 @@ use crate::Perform;
 use actix_web::web::Data;
 use lemmy_api_common::{
   context::LemmyContext,
+  disposable_email::check_disposable_email,
   person::{LoginResponse, SaveUserSettings},
-  utils::{local_user_view_from_jwt, sanitize_html_opt, send_verification_email},
+  utils::{
+    check_email_domain_allowed,
+    local_user_view_from_jwt,
+    sanitize_html_opt,
+    send_verification_email,
+  },
 };
 use lemmy_db_schema::{
   source::{
@@ -13,10 +19,10 @@ use lemmy_db_schema::{
   },
   traits::Crud,
   utils::{diesel_option_overwrite, diesel_option_overwrite_to_url},
+  SortType,
 };
 use lemmy_db_views::structs::SiteView;
 use lemmy_utils::{
-  claims::Claims,
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
   utils::validation::{
     build_totp_2fa,
@@ -48,20 +54,6 @@ impl Perform for SaveUserSettings {
     let email_deref = data.email.as_deref().map(str::to_lowercase);
     let email = diesel_option_overwrite(email_deref.clone());
 
-    if let Some(Some(email)) = &email {
-      let previous_email = local_user_view.local_user.email.clone().unwrap_or_default();
-      // Only send the verification email if there was an email change
-      if previous_email.ne(email) {
-        send_verification_email(
-          &local_user_view,
-          email,
-          &mut context.pool(),
-          context.settings(),
-        )
-        .await?;
-      }
-    }
-
     // When the site requires email, make sure email is not Some(None). IE, an overwrite to a None value
     if let Some(email) = &email {
       if email.is_none() && site_view.local_site.require_email_verification {
@@ -69,6 +61,31 @@ impl Perform for SaveUserSettings {
       }
     }
 
+    if let Some(email) = &email {
+      let previous_email = local_user_view.local_user.email.as_deref();
+      // Only check the new email and send the verification email if there was an email change
+      if previous_email != email.as_deref() {
+        check_email_domain_allowed(email.as_deref(), &mut context.pool()).await?;
+        // Existing users have no registration application which could be reviewed, so
+        // disposable emails are refused even if signups with them only need a review
+        if check_disposable_email(email.as_deref(), context)
+          .await?
+          .is_some()
+        {
+          return Err(LemmyErrorType::DisposableEmail)?;
+        }
+        if let Some(email) = email {
+          send_verification_email(
+            &local_user_view,
+            email,
+            &mut context.pool(),
+            context.settings(),
+          )
+          .await?;
+        }
+      }
+    }
+
     if let Some(Some(bio)) = &bio {
       is_valid_bio_field(bio)?;
     }
@@ -88,6 +105,10 @@ impl Perform for SaveUserSettings {
     let person_id = local_user_view.person.id;
     let default_listing_type = data.default_listing_type;
     let default_sort_type = data.default_sort_type;
+    // The default is used for posts, which can't be sorted by trending
+    if default_sort_type == Some(SortType::Trending) {
+      Err(LemmyErrorType::InvalidSortType)?
+    }
     let theme = sanitize_html_opt(&data.theme);
 
     let person_form = PersonUpdateForm::builder()
@@ -140,35 +161,33 @@ impl Perform for SaveUserSettings {
       .totp_2fa_url(totp_2fa_url)
       .open_links_in_new_tab(data.open_links_in_new_tab)
       .infinite_scroll_enabled(data.infinite_scroll_enabled)
+      .anonymize_outgoing_votes(data.anonymize_outgoing_votes)
+      .notify_comment_replies(data.notify_comment_replies)
+      .notify_post_replies(data.notify_post_replies)
+      .notify_mentions(data.notify_mentions)
+      .notify_private_messages(data.notify_private_messages)
+      .auto_subscribe_threads(data.auto_subscribe_threads)
+      .email_mod_queue_digest(data.email_mod_queue_digest)
+      .email_login_alerts(data.email_login_alerts)
       .build();
 
     let local_user_res =
       LocalUser::update(&mut context.pool(), local_user_id, &local_user_form).await;
-    let updated_local_user = match local_user_res {
-      Ok(u) => u,
-      Err(e) => {
-        let err_type = if e.to_string()
-          == "duplicate key value violates unique constraint \"local_user_email_key\""
-        {
-          LemmyErrorType::EmailAlreadyExists
-        } else {
-          LemmyErrorType::UserAlreadyExists
-        };
+    if let Err(e) = local_user_res {
+      let err_type = if e.to_string()
+        == "duplicate key value violates unique constraint \"local_user_email_key\""
+      {
+        LemmyErrorType::EmailAlreadyExists
+      } else {
+        LemmyErrorType::UserAlreadyExists
+      };
 
-        return Err(e).with_lemmy_type(err_type);
-      }
-    };
+      return Err(e).with_lemmy_type(err_type);
+    }
 
-    // Return the jwt
+    // The login stays the same
     Ok(LoginResponse {
-      jwt: Some(
-        Claims::jwt(
-          updated_local_user.id.0,
-          &context.secret().jwt_secret,
-          &context.settings().hostname,
-        )?
-        .into(),
-      ),
+      jwt: Some(data.auth.clone()),
       verify_email_sent: false,
       registration_created: false,
     })
@@ -0,0 +1,68 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  person::{ShadowbanPerson, ShadowbanPersonResponse},
+  utils::{check_admin_role, check_admin_target, local_user_view_from_jwt, sanitize_html_opt},
+};
+use lemmy_db_schema::{
+  source::{
+    moderator::{ModShadowban, ModShadowbanForm},
+    person::{Person, PersonUpdateForm},
+  },
+  traits::Crud,
+  utils::naive_now,
+  AdminRole,
+};
+use lemmy_db_views_actor::structs::PersonView;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::is_valid_body_field,
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn shadowban_from_site(
+  data: Json<ShadowbanPerson>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ShadowbanPersonResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::UserAdmin)?;
+  let target = Person::read(&mut context.pool(), data.person_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindPerson)?;
+  check_admin_target(&local_user_view, &target)?;
+
+  is_valid_body_field(&data.reason, false)?;
+
+  // Content published before the shadowban stays visible
+  let shadowbanned_at = data.shadowban.then(naive_now);
+
+  Person::update(
+    &mut context.pool(),
+    data.person_id,
+    &PersonUpdateForm::builder()
+      .shadowbanned_at(Some(shadowbanned_at))
+      .build(),
+  )
+  .await
+  .with_lemmy_type(LemmyErrorType::CouldntUpdateUser)?;
+
+  // Mod tables
+  let form = ModShadowbanForm {
+    mod_person_id: local_user_view.person.id,
+    other_person_id: data.person_id,
+    reason: sanitize_html_opt(&data.reason),
+    shadowbanned: data.shadowban,
+  };
+
+  ModShadowban::create(&mut context.pool(), &form).await?;
+
+  let person_view = PersonView::read(&mut context.pool(), data.person_id).await?;
+
+  Ok(Json(ShadowbanPersonResponse {
+    person_view,
+    shadowbanned: data.shadowban,
+  }))
+}
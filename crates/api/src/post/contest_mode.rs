@@ -0,0 +1,78 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Duration;
+use lemmy_api_common::{
+  build_response::build_post_response,
+  context::LemmyContext,
+  post::{PostResponse, SetContestMode},
+  utils::{
+    check_community_ban,
+    check_community_deleted_or_removed,
+    is_mod_or_admin,
+    local_user_view_from_jwt,
+  },
+};
+use lemmy_db_schema::{
+  source::post::{Post, PostUpdateForm},
+  traits::Crud,
+  utils::naive_now,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorType};
+
+const DEFAULT_CONTEST_MODE_HOURS: i32 = 24;
+const MAX_CONTEST_MODE_HOURS: i32 = 24 * 30;
+
+#[tracing::instrument(skip(context))]
+pub async fn set_contest_mode(
+  data: Json<SetContestMode>,
+  context: Data<LemmyContext>,
+) -> Result<Json<PostResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  let post_id = data.post_id;
+  let orig_post = Post::read(&mut context.pool(), post_id).await?;
+
+  check_community_ban(
+    local_user_view.person.id,
+    orig_post.community_id,
+    &mut context.pool(),
+  )
+  .await?;
+  check_community_deleted_or_removed(orig_post.community_id, &mut context.pool()).await?;
+
+  // Verify that only the mods can toggle contest mode
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    orig_post.community_id,
+  )
+  .await?;
+
+  let contest_mode_until = if data.enabled {
+    let hours = data.duration_hours.unwrap_or(DEFAULT_CONTEST_MODE_HOURS);
+    if !(1..=MAX_CONTEST_MODE_HOURS).contains(&hours) {
+      Err(LemmyErrorType::InvalidContestModeDuration)?
+    }
+    Some(naive_now() + Duration::hours(hours.into()))
+  } else {
+    None
+  };
+
+  // Contest mode only affects local API responses, so it isn't federated
+  Post::update(
+    &mut context.pool(),
+    post_id,
+    &PostUpdateForm::builder()
+      .contest_mode_until(Some(contest_mode_until))
+      .build(),
+  )
+  .await?;
+
+  build_post_response(
+    &context,
+    orig_post.community_id,
+    local_user_view.person.id,
+    post_id,
+  )
+  .await
+}
@@ -6,9 +6,9 @@ use lemmy_api_common::{
   post::{FeaturePost, PostResponse},
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
+    check_admin_role,
     check_community_ban,
     check_community_deleted_or_removed,
-    is_admin,
     is_mod_or_admin,
     local_user_view_from_jwt,
   },
@@ -19,6 +19,7 @@ use lemmy_db_schema::{
     post::{Post, PostUpdateForm},
   },
   traits::Crud,
+  AdminRole,
   PostFeatureType,
 };
 use lemmy_utils::error::LemmyError;
@@ -50,7 +51,7 @@ pub async fn feature_post(
     )
     .await?;
   } else {
-    is_admin(&local_user_view)?;
+    check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
   }
 
   // Update the post
@@ -32,12 +32,14 @@ pub async fn like_post(
   let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
   let local_site = LocalSite::read(&mut context.pool()).await?;
 
-  // Don't do a downvote if site has downvotes disabled
-  check_downvotes_enabled(data.score, &local_site)?;
-
-  // Check for a community ban
   let post_id = data.post_id;
   let post = Post::read(&mut context.pool(), post_id).await?;
+  let community = Community::read(&mut context.pool(), post.community_id).await?;
+
+  // Don't do a downvote if site or community has downvotes disabled
+  check_downvotes_enabled(data.score, &local_site, &community)?;
+
+  // Check for a community ban
 
   check_community_ban(
     local_user_view.person.id,
@@ -73,7 +75,7 @@ pub async fn like_post(
     SendActivityData::LikePostOrComment(
       post.ap_id,
       local_user_view.person.clone(),
-      Community::read(&mut context.pool(), post.community_id).await?,
+      community,
       data.score,
     ),
     &context,
@@ -0,0 +1,38 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  post::{ListPostLikes, ListPostLikesResponse},
+  utils::{is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{local_site::LocalSite, post::Post},
+  traits::Crud,
+};
+use lemmy_db_views::structs::VoteView;
+use lemmy_utils::error::{LemmyError, LemmyErrorType};
+
+/// Lists the votes on a post, so that mods can spot vote manipulation
+#[tracing::instrument(skip(context))]
+pub async fn list_post_likes(
+  data: Query<ListPostLikes>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListPostLikesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  if !local_site.enable_vote_viewer {
+    Err(LemmyErrorType::VoteViewerDisabled)?
+  }
+
+  let post = Post::read(&mut context.pool(), data.post_id).await?;
+  is_mod_or_admin(
+    &mut context.pool(),
+    local_user_view.person.id,
+    post.community_id,
+  )
+  .await?;
+
+  let post_likes =
+    VoteView::list_for_post(&mut context.pool(), post.id, data.page, data.limit).await?;
+
+  Ok(Json(ListPostLikesResponse { post_likes }))
+}
@@ -1,6 +1,9 @@
+pub mod contest_mode;
 pub mod feature;
 pub mod get_link_metadata;
 pub mod like;
+pub mod list_post_likes;
 pub mod lock;
 pub mod mark_read;
+pub mod react;
 pub mod save;
@@ -0,0 +1,70 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  build_response::build_post_response,
+  context::LemmyContext,
+  post::{CreatePostReaction, PostResponse},
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_ban, check_community_deleted_or_removed, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    post::{Post, PostReaction, PostReactionForm},
+  },
+  traits::{Crud, Reactable},
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::is_valid_emoji_reaction,
+};
+use std::ops::Deref;
+
+#[tracing::instrument(skip(context))]
+pub async fn react_to_post(
+  data: Json<CreatePostReaction>,
+  context: Data<LemmyContext>,
+) -> Result<Json<PostResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let emoji = data.emoji.trim();
+  is_valid_emoji_reaction(emoji)?;
+
+  let post_id = data.post_id;
+  let post = Post::read(&mut context.pool(), post_id).await?;
+
+  check_community_ban(
+    local_user_view.person.id,
+    post.community_id,
+    &mut context.pool(),
+  )
+  .await?;
+  check_community_deleted_or_removed(post.community_id, &mut context.pool()).await?;
+
+  let person_id = local_user_view.person.id;
+  if data.add {
+    let reaction_form = PostReactionForm {
+      post_id,
+      person_id,
+      emoji: emoji.to_string(),
+    };
+    PostReaction::react(&mut context.pool(), &reaction_form)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntReact)?;
+  } else {
+    PostReaction::unreact(&mut context.pool(), person_id, post_id, emoji).await?;
+  }
+
+  ActivityChannel::submit_activity(
+    SendActivityData::ReactPostOrComment(
+      post.ap_id,
+      local_user_view.person.clone(),
+      Community::read(&mut context.pool(), post.community_id).await?,
+      emoji.to_string(),
+      data.add,
+    ),
+    &context,
+  )
+  .await?;
+
+  build_post_response(context.deref(), post.community_id, person_id, post_id).await
+}
@@ -1,16 +1,11 @@
-use crate::check_report_reason;
+use crate::check_report_reason_with_id;
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_common::{
   context::LemmyContext,
   post::{CreatePostReport, PostReportResponse},
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{
-    check_community_ban,
-    local_user_view_from_jwt,
-    sanitize_html,
-    send_new_report_email_to_admins,
-  },
+  utils::{check_community_ban, local_user_view_from_jwt, send_new_report_email_to_admins},
 };
 use lemmy_db_schema::{
   source::{
@@ -31,13 +26,20 @@ pub async fn create_post_report(
   let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
   let local_site = LocalSite::read(&mut context.pool()).await?;
 
-  let reason = sanitize_html(data.reason.trim());
-  check_report_reason(&reason, &local_site)?;
-
   let person_id = local_user_view.person.id;
   let post_id = data.post_id;
   let post_view = PostView::read(&mut context.pool(), post_id, None, None).await?;
 
+  let reason = check_report_reason_with_id(
+    &data.reason,
+    data.reason_id,
+    data.rule_id,
+    Some(post_view.community.id),
+    &local_site,
+    &mut context.pool(),
+  )
+  .await?;
+
   check_community_ban(person_id, post_view.community.id, &mut context.pool()).await?;
 
   let report_form = PostReportForm {
@@ -46,7 +48,9 @@ pub async fn create_post_report(
     original_post_name: post_view.post.name,
     original_post_url: post_view.post.url,
     original_post_body: post_view.post.body,
-    reason,
+    reason: reason.clone(),
+    reason_id: data.reason_id,
+    rule_id: data.rule_id,
   };
 
   let report = PostReport::report(&mut context.pool(), &report_form)
@@ -71,7 +75,7 @@ pub async fn create_post_report(
       post_view.post.ap_id.inner().clone(),
       local_user_view.person,
       post_view.community,
-      data.reason.clone(),
+      reason,
     ),
     &context,
   )
@@ -30,6 +30,7 @@ impl Perform for ListPostReports {
     let post_reports = PostReportQuery {
       community_id,
       unresolved_only,
+      reason_id: data.reason_id,
       page,
       limit,
     }
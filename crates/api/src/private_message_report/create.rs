@@ -1,9 +1,9 @@
-use crate::{check_report_reason, Perform};
+use crate::{check_report_reason_with_id, Perform};
 use actix_web::web::Data;
 use lemmy_api_common::{
   context::LemmyContext,
   private_message::{CreatePrivateMessageReport, PrivateMessageReportResponse},
-  utils::{local_user_view_from_jwt, sanitize_html, send_new_report_email_to_admins},
+  utils::{local_user_view_from_jwt, send_new_report_email_to_admins},
 };
 use lemmy_db_schema::{
   source::{
@@ -25,8 +25,15 @@ impl Perform for CreatePrivateMessageReport {
     let local_user_view = local_user_view_from_jwt(&self.auth, context).await?;
     let local_site = LocalSite::read(&mut context.pool()).await?;
 
-    let reason = sanitize_html(self.reason.trim());
-    check_report_reason(&reason, &local_site)?;
+    let reason = check_report_reason_with_id(
+      &self.reason,
+      self.reason_id,
+      None,
+      None,
+      &local_site,
+      &mut context.pool(),
+    )
+    .await?;
 
     let person_id = local_user_view.person.id;
     let private_message_id = self.private_message_id;
@@ -37,6 +44,7 @@ impl Perform for CreatePrivateMessageReport {
       private_message_id,
       original_pm_text: private_message.content,
       reason: reason.clone(),
+      reason_id: self.reason_id,
     };
 
     let report = PrivateMessageReport::report(&mut context.pool(), &report_form)
@@ -23,6 +23,7 @@ impl Perform for ListPrivateMessageReports {
     let limit = self.limit;
     let private_message_reports = PrivateMessageReportQuery {
       unresolved_only,
+      reason_id: self.reason_id,
       page,
       limit,
     }
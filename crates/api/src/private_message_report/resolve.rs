@@ -3,9 +3,13 @@ use actix_web::web::Data;
 use lemmy_api_common::{
   context::LemmyContext,
   private_message::{PrivateMessageReportResponse, ResolvePrivateMessageReport},
-  utils::{is_admin, local_user_view_from_jwt},
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::private_message_report::PrivateMessageReport,
+  traits::Reportable,
+  AdminRole,
 };
-use lemmy_db_schema::{source::private_message_report::PrivateMessageReport, traits::Reportable};
 use lemmy_db_views::structs::PrivateMessageReportView;
 use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
 
@@ -17,7 +21,7 @@ impl Perform for ResolvePrivateMessageReport {
   async fn perform(&self, context: &Data<LemmyContext>) -> Result<Self::Response, LemmyError> {
     let local_user_view = local_user_view_from_jwt(&self.auth, context).await?;
 
-    is_admin(&local_user_view)?;
+    check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
 
     let report_id = self.report_id;
     let person_id = local_user_view.person.id;
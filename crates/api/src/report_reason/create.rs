@@ -0,0 +1,44 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  report_reason::{CreateReportReason, ReportReasonResponse},
+  utils::{check_admin_role, is_mod_or_admin, local_user_view_from_jwt, sanitize_html},
+};
+use lemmy_db_schema::{
+  source::report_reason::{ReportReason, ReportReasonForm},
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn create_report_reason(
+  data: Json<CreateReportReason>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ReportReasonResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Site wide reasons can only be added by admins
+  match data.community_id {
+    Some(community_id) => {
+      is_mod_or_admin(&mut context.pool(), local_user_view.person.id, community_id).await?
+    }
+    None => check_admin_role(&local_user_view, AdminRole::ContentAdmin)?,
+  }
+
+  let name = sanitize_html(data.name.trim());
+  if name.is_empty() {
+    Err(LemmyErrorType::InvalidReportReason)?
+  }
+
+  let form = ReportReasonForm {
+    community_id: data.community_id,
+    name,
+  };
+  let report_reason = ReportReason::create(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateReportReason)?;
+
+  Ok(Json(ReportReasonResponse { report_reason }))
+}
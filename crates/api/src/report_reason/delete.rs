@@ -0,0 +1,32 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  report_reason::{DeleteReportReason, DeleteReportReasonResponse},
+  utils::{check_admin_role, is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{source::report_reason::ReportReason, traits::Crud, AdminRole};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_report_reason(
+  data: Json<DeleteReportReason>,
+  context: Data<LemmyContext>,
+) -> Result<Json<DeleteReportReasonResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let report_reason = ReportReason::read(&mut context.pool(), data.id).await?;
+
+  // Site wide reasons can only be deleted by admins
+  match report_reason.community_id {
+    Some(community_id) => {
+      is_mod_or_admin(&mut context.pool(), local_user_view.person.id, community_id).await?
+    }
+    None => check_admin_role(&local_user_view, AdminRole::ContentAdmin)?,
+  }
+
+  ReportReason::delete(&mut context.pool(), data.id).await?;
+  Ok(Json(DeleteReportReasonResponse {
+    id: data.id,
+    success: true,
+  }))
+}
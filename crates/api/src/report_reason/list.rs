@@ -0,0 +1,18 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  report_reason::{ListReportReasons, ListReportReasonsResponse},
+};
+use lemmy_db_schema::source::report_reason::ReportReason;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_report_reasons(
+  data: Query<ListReportReasons>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListReportReasonsResponse>, LemmyError> {
+  let report_reasons = ReportReason::list(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListReportReasonsResponse { report_reasons }))
+}
@@ -0,0 +1,27 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{ListActivityDeliveries, ListActivityDeliveriesResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::sent_activity_delivery::SentActivityDelivery;
+use lemmy_utils::error::LemmyError;
+use url::Url;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_activity_deliveries(
+  data: Query<ListActivityDeliveries>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListActivityDeliveriesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let activity_id = Url::parse(&data.activity_id)?.into();
+  let deliveries =
+    SentActivityDelivery::list_for_activity(&mut context.pool(), &activity_id).await?;
+
+  Ok(Json(ListActivityDeliveriesResponse { deliveries }))
+}
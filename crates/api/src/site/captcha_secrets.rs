@@ -0,0 +1,40 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{SetCaptchaSecrets, SetCaptchaSecretsResponse},
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::secret::{Secret, SecretUpdateForm},
+  utils::diesel_option_overwrite,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn set_captcha_secrets(
+  data: Json<SetCaptchaSecrets>,
+  context: Data<LemmyContext>,
+) -> Result<Json<SetCaptchaSecretsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FullAdmin)?;
+
+  let data = data.into_inner();
+  let form = SecretUpdateForm {
+    hcaptcha_secret: diesel_option_overwrite(data.hcaptcha_secret.map(|s| s.into_inner())),
+    mcaptcha_secret: diesel_option_overwrite(data.mcaptcha_secret.map(|s| s.into_inner())),
+    turnstile_secret: diesel_option_overwrite(data.turnstile_secret.map(|s| s.into_inner())),
+  };
+  let secret = Secret::update(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateCaptchaSecret)?;
+
+  Ok(Json(SetCaptchaSecretsResponse {
+    hcaptcha_secret_set: secret.hcaptcha_secret.is_some(),
+    mcaptcha_secret_set: secret.mcaptcha_secret.is_some(),
+    turnstile_secret_set: secret.turnstile_secret.is_some(),
+  }))
+}
@@ -0,0 +1,105 @@
+use crate::site::DELIVERY_RETRY_DAYS;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::Duration;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{GetAdminDashboard, GetAdminDashboardResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  aggregates::structs::SiteStatsHistory,
+  source::{
+    activity::{ReceivedActivity, SentActivity},
+    local_image::LocalImage,
+    local_site::LocalSite,
+    local_user::LocalUser,
+    sent_activity_delivery::SentActivityDelivery,
+  },
+  utils::{database_size, naive_now},
+  ListingType,
+  SortType,
+};
+use lemmy_db_views::structs::{
+  CommentReportView,
+  PostReportView,
+  PrivateMessageReportView,
+  RegistrationApplicationView,
+};
+use lemmy_db_views_actor::community_view::CommunityQuery;
+use lemmy_utils::error::LemmyError;
+
+/// How many days of signups and statistics are shown on the dashboard
+const DASHBOARD_DAYS: i32 = 30;
+
+/// How many of the most active local communities are shown on the dashboard
+const DASHBOARD_TOP_COMMUNITIES: i64 = 10;
+
+#[tracing::instrument(skip(context))]
+pub async fn get_admin_dashboard(
+  data: Query<GetAdminDashboard>,
+  context: Data<LemmyContext>,
+) -> Result<Json<GetAdminDashboardResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  let person_id = local_user_view.person.id;
+  let day_ago = naive_now() - Duration::days(1);
+  let history_since = naive_now().date() - Duration::days(DASHBOARD_DAYS.into());
+
+  let signups = LocalUser::signups_per_day(&mut context.pool(), DASHBOARD_DAYS).await?;
+  let stats_history =
+    SiteStatsHistory::list(&mut context.pool(), Some(history_since), None).await?;
+  let unresolved_post_reports =
+    PostReportView::get_report_count(&mut context.pool(), person_id, true, None).await?;
+  let unresolved_comment_reports =
+    CommentReportView::get_report_count(&mut context.pool(), person_id, true, None).await?;
+  let unresolved_private_message_reports =
+    PrivateMessageReportView::get_report_count(&mut context.pool()).await?;
+  let unread_registration_applications = RegistrationApplicationView::get_unread_count(
+    &mut context.pool(),
+    local_site.require_email_verification,
+  )
+  .await?;
+  let activities_sent_day = SentActivity::count_since(&mut context.pool(), day_ago).await?;
+  let activities_received_day = ReceivedActivity::count_since(&mut context.pool(), day_ago).await?;
+  let (deliveries_day, failed_deliveries_day) =
+    SentActivityDelivery::count_since(&mut context.pool(), day_ago).await?;
+  let pending_delivery_retries = SentActivityDelivery::count_pending_retries(
+    &mut context.pool(),
+    naive_now() - Duration::days(DELIVERY_RETRY_DAYS),
+  )
+  .await?;
+  let top_communities = CommunityQuery {
+    listing_type: Some(ListingType::Local),
+    sort: Some(SortType::TopWeek),
+    is_mod_or_admin: Some(true),
+    show_nsfw: Some(true),
+    limit: Some(DASHBOARD_TOP_COMMUNITIES),
+    ..Default::default()
+  }
+  .list(&mut context.pool())
+  .await?;
+  let local_images = LocalImage::count(&mut context.pool()).await?;
+  let database_size = database_size(&mut context.pool()).await?;
+
+  Ok(Json(GetAdminDashboardResponse {
+    signups,
+    stats_history,
+    unresolved_post_reports,
+    unresolved_comment_reports,
+    unresolved_private_message_reports,
+    unread_registration_applications,
+    activities_sent_day,
+    activities_received_day,
+    deliveries_day,
+    failed_deliveries_day,
+    pending_delivery_retries,
+    top_communities,
+    local_images,
+    database_size,
+  }))
+}
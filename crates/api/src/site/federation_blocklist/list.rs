@@ -0,0 +1,31 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  blocklist_subscription::{BlockedInstance, ListBlockedInstances, ListBlockedInstancesResponse},
+  context::LemmyContext,
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::federation_blocklist::FederationBlockList;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_blocked_instances(
+  data: Query<ListBlockedInstances>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListBlockedInstancesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let blocked_instances = FederationBlockList::list(&mut context.pool(), data.page, data.limit)
+    .await?
+    .into_iter()
+    .map(|(blocklist, instance)| BlockedInstance {
+      instance,
+      blocklist,
+    })
+    .collect();
+
+  Ok(Json(ListBlockedInstancesResponse { blocked_instances }))
+}
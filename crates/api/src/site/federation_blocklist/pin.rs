@@ -0,0 +1,34 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  blocklist_subscription::{BlockedInstance, BlockedInstanceResponse, PinBlockedInstance},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{federation_blocklist::FederationBlockList, instance::Instance},
+  AdminRole,
+};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn pin_blocked_instance(
+  data: Json<PinBlockedInstance>,
+  context: Data<LemmyContext>,
+) -> Result<Json<BlockedInstanceResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FederationAdmin)?;
+
+  let blocklist =
+    FederationBlockList::set_pinned(&mut context.pool(), data.instance_id, data.pinned).await?;
+  let instance = Instance::read(&mut context.pool(), data.instance_id).await?;
+
+  Ok(Json(BlockedInstanceResponse {
+    blocked_instance: BlockedInstance {
+      instance,
+      blocklist,
+    },
+  }))
+}
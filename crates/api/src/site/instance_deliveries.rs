@@ -0,0 +1,27 @@
+use crate::site::DELIVERY_RETRY_DAYS;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::Duration;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{ListInstanceDeliveries, ListInstanceDeliveriesResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{source::sent_activity_delivery::SentActivityDelivery, utils::naive_now};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_instance_deliveries(
+  data: Query<ListInstanceDeliveries>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListInstanceDeliveriesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let since = naive_now() - Duration::days(DELIVERY_RETRY_DAYS);
+  let instances = SentActivityDelivery::stats_per_instance(&mut context.pool(), since).await?;
+
+  Ok(Json(ListInstanceDeliveriesResponse { instances }))
+}
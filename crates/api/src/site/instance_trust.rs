@@ -0,0 +1,28 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{InstanceWithTrust, ListInstanceTrust, ListInstanceTrustResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::instance_trust::InstanceTrust;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_instance_trust(
+  data: Query<ListInstanceTrust>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListInstanceTrustResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let instances = InstanceTrust::list(&mut context.pool(), data.page, data.limit)
+    .await?
+    .into_iter()
+    .map(|(instance, trust)| InstanceWithTrust { instance, trust })
+    .collect();
+
+  Ok(Json(ListInstanceTrustResponse { instances }))
+}
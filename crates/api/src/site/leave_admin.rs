@@ -8,7 +8,11 @@ use lemmy_api_common::{
 use lemmy_db_schema::{
   source::{
     actor_language::SiteLanguage,
+    announcement::Announcement,
+    category::Category,
+    email_domain::EmailDomain,
     language::Language,
+    local_user::{LocalUser, LocalUserUpdateForm},
     moderator::{ModAdd, ModAddForm},
     person::{Person, PersonUpdateForm},
     tagline::Tagline,
@@ -46,6 +50,10 @@ impl Perform for LeaveAdmin {
       &PersonUpdateForm::builder().admin(Some(false)).build(),
     )
     .await?;
+    let form = LocalUserUpdateForm::builder()
+      .admin_role(Some(None))
+      .build();
+    LocalUser::update(&mut context.pool(), local_user_view.local_user.id, &form).await?;
 
     // Mod tables
     let form = ModAddForm {
@@ -63,8 +71,12 @@ impl Perform for LeaveAdmin {
     let all_languages = Language::read_all(&mut context.pool()).await?;
     let discussion_languages = SiteLanguage::read_local_raw(&mut context.pool()).await?;
     let taglines = Tagline::get_all(&mut context.pool(), site_view.local_site.id).await?;
+    let tagline = Tagline::get_random(&mut context.pool(), site_view.local_site.id).await?;
     let custom_emojis =
       CustomEmojiView::get_all(&mut context.pool(), site_view.local_site.id).await?;
+    let categories = Category::get_all(&mut context.pool()).await?;
+    let allowed_email_domains = EmailDomain::list(&mut context.pool(), true).await?;
+    let announcements = Announcement::list_active(&mut context.pool()).await?;
 
     Ok(GetSiteResponse {
       site_view,
@@ -74,7 +86,14 @@ impl Perform for LeaveAdmin {
       all_languages,
       discussion_languages,
       taglines,
+      tagline,
+      announcements,
       custom_emojis,
+      categories,
+      allowed_email_domains,
+      blocked_email_domains: None,
+      disposable_email_exceptions: None,
+      deleted_content_retention_days: context.settings().deleted_content_retention_days,
     })
   }
 }
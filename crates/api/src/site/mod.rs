@@ -1,5 +1,21 @@
+pub mod activity_deliveries;
+pub mod captcha_secrets;
+pub mod dashboard;
 mod federated_instances;
+pub mod federation_blocklist;
+pub mod instance_deliveries;
+pub mod instance_trust;
 mod leave_admin;
 mod mod_log;
+pub mod preview_markdown;
 mod purge;
+pub mod rate_limit;
 mod registration_applications;
+pub mod replay_activity;
+pub mod requeue_deliveries;
+pub mod rotate_keys;
+pub mod scheduled_job;
+pub mod stats_history;
+
+/// How long the federation queue keeps retrying a failed delivery
+const DELIVERY_RETRY_DAYS: i64 = 3;
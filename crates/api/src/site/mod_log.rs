@@ -11,6 +11,7 @@ use lemmy_db_schema::{
   ModlogActionType,
 };
 use lemmy_db_views_moderator::structs::{
+  AdminMergePersonView,
   AdminPurgeCommentView,
   AdminPurgeCommunityView,
   AdminPurgePersonView,
@@ -25,6 +26,7 @@ use lemmy_db_views_moderator::structs::{
   ModRemoveCommentView,
   ModRemoveCommunityView,
   ModRemovePostView,
+  ModShadowbanView,
   ModTransferCommunityView,
   ModlogListParams,
 };
@@ -121,6 +123,22 @@ impl Perform for GetModlog {
       _ => Default::default(),
     };
 
+    // Shadowbans are only visible to admins, and only in the full modlog
+    let shadowbanned = match type_ {
+      All | ModShadowban if is_admin && data.community_id.is_none() => {
+        ModShadowbanView::list(&mut context.pool(), params).await?
+      }
+      _ => Default::default(),
+    };
+
+    // Account merges reveal alt accounts, so they are also admin only
+    let admin_merged_persons = match type_ {
+      All | AdminMergePerson if is_admin && data.community_id.is_none() => {
+        AdminMergePersonView::list(&mut context.pool(), params).await?
+      }
+      _ => Default::default(),
+    };
+
     // These arrays are only for the full modlog, when a community isn't given
     let (
       banned,
@@ -192,6 +210,8 @@ impl Perform for GetModlog {
       admin_purged_posts,
       admin_purged_comments,
       hidden_communities,
+      shadowbanned,
+      admin_merged_persons,
     })
   }
 }
@@ -0,0 +1,23 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  markdown::{PreviewMarkdown, PreviewMarkdownResponse},
+  utils::local_user_view_from_jwt,
+};
+use lemmy_utils::{
+  error::LemmyError,
+  utils::{markdown::markdown_to_html, validation::is_valid_body_field},
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn preview_markdown(
+  data: Json<PreviewMarkdown>,
+  context: Data<LemmyContext>,
+) -> Result<Json<PreviewMarkdownResponse>, LemmyError> {
+  local_user_view_from_jwt(&data.auth, &context).await?;
+  is_valid_body_field(&Some(data.markdown.clone()), true)?;
+
+  let html = markdown_to_html(&data.markdown);
+  Ok(Json(PreviewMarkdownResponse { html }))
+}
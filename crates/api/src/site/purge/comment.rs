@@ -3,7 +3,7 @@ use actix_web::web::Data;
 use lemmy_api_common::{
   context::LemmyContext,
   site::{PurgeComment, PurgeItemResponse},
-  utils::{is_admin, local_user_view_from_jwt, sanitize_html_opt},
+  utils::{check_admin_role, local_user_view_from_jwt, sanitize_html_opt},
 };
 use lemmy_db_schema::{
   source::{
@@ -11,6 +11,7 @@ use lemmy_db_schema::{
     moderator::{AdminPurgeComment, AdminPurgeCommentForm},
   },
   traits::Crud,
+  AdminRole,
 };
 use lemmy_utils::error::LemmyError;
 
@@ -24,7 +25,7 @@ impl Perform for PurgeComment {
     let local_user_view = local_user_view_from_jwt(&data.auth, context).await?;
 
     // Only let admin purge an item
-    is_admin(&local_user_view)?;
+    check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
 
     let comment_id = data.comment_id;
 
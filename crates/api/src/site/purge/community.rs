@@ -4,7 +4,12 @@ use lemmy_api_common::{
   context::LemmyContext,
   request::purge_image_from_pictrs,
   site::{PurgeCommunity, PurgeItemResponse},
-  utils::{is_admin, local_user_view_from_jwt, purge_image_posts_for_community, sanitize_html_opt},
+  utils::{
+    check_admin_role,
+    local_user_view_from_jwt,
+    purge_image_posts_for_community,
+    sanitize_html_opt,
+  },
 };
 use lemmy_db_schema::{
   source::{
@@ -12,6 +17,7 @@ use lemmy_db_schema::{
     moderator::{AdminPurgeCommunity, AdminPurgeCommunityForm},
   },
   traits::Crud,
+  AdminRole,
 };
 use lemmy_utils::error::LemmyError;
 
@@ -25,7 +31,7 @@ impl Perform for PurgeCommunity {
     let local_user_view = local_user_view_from_jwt(&data.auth, context).await?;
 
     // Only let admin purge an item
-    is_admin(&local_user_view)?;
+    check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
 
     let community_id = data.community_id;
 
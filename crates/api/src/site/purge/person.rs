@@ -4,7 +4,13 @@ use lemmy_api_common::{
   context::LemmyContext,
   request::purge_image_from_pictrs,
   site::{PurgeItemResponse, PurgePerson},
-  utils::{is_admin, local_user_view_from_jwt, purge_image_posts_for_person, sanitize_html_opt},
+  utils::{
+    check_admin_role,
+    check_admin_target,
+    local_user_view_from_jwt,
+    purge_image_posts_for_person,
+    sanitize_html_opt,
+  },
 };
 use lemmy_db_schema::{
   source::{
@@ -12,6 +18,7 @@ use lemmy_db_schema::{
     person::Person,
   },
   traits::Crud,
+  AdminRole,
 };
 use lemmy_utils::error::LemmyError;
 
@@ -25,11 +32,12 @@ impl Perform for PurgePerson {
     let local_user_view = local_user_view_from_jwt(&data.auth, context).await?;
 
     // Only let admin purge an item
-    is_admin(&local_user_view)?;
+    check_admin_role(&local_user_view, AdminRole::UserAdmin)?;
 
     // Read the person to get their images
     let person_id = data.person_id;
     let person = Person::read(&mut context.pool(), person_id).await?;
+    check_admin_target(&local_user_view, &person)?;
 
     if let Some(banner) = person.banner {
       purge_image_from_pictrs(context.client(), context.settings(), &banner)
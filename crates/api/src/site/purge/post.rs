@@ -4,7 +4,7 @@ use lemmy_api_common::{
   context::LemmyContext,
   request::purge_image_from_pictrs,
   site::{PurgeItemResponse, PurgePost},
-  utils::{is_admin, local_user_view_from_jwt, sanitize_html_opt},
+  utils::{check_admin_role, local_user_view_from_jwt, sanitize_html_opt},
 };
 use lemmy_db_schema::{
   source::{
@@ -12,6 +12,7 @@ use lemmy_db_schema::{
     post::Post,
   },
   traits::Crud,
+  AdminRole,
 };
 use lemmy_utils::error::LemmyError;
 
@@ -25,7 +26,7 @@ impl Perform for PurgePost {
     let local_user_view = local_user_view_from_jwt(&data.auth, context).await?;
 
     // Only let admin purge an item
-    is_admin(&local_user_view)?;
+    check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
 
     let post_id = data.post_id;
 
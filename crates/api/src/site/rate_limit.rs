@@ -0,0 +1,156 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{
+    EditRateLimits,
+    GetRateLimits,
+    ListRateLimitOverrides,
+    ListRateLimitOverridesResponse,
+    PersonRateLimitOverride,
+    RateLimitsResponse,
+    SetRateLimitOverride,
+  },
+  utils::{
+    check_admin_role,
+    is_admin,
+    local_site_rate_limit_to_rate_limit_config,
+    local_user_view_from_jwt,
+    update_user_rate_limits,
+  },
+};
+use lemmy_db_schema::{
+  source::{
+    local_site_rate_limit::{LocalSiteRateLimit, LocalSiteRateLimitUpdateForm},
+    rate_limit_override::{RateLimitOverride, RateLimitOverrideForm},
+  },
+  utils::naive_now,
+  AdminRole,
+};
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
+  utils::validation::check_rate_limit,
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn get_rate_limits(
+  data: Query<GetRateLimits>,
+  context: Data<LemmyContext>,
+) -> Result<Json<RateLimitsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let rate_limits = LocalSiteRateLimit::read(&mut context.pool()).await?;
+  Ok(Json(RateLimitsResponse { rate_limits }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn edit_rate_limits(
+  data: Json<EditRateLimits>,
+  context: Data<LemmyContext>,
+) -> Result<Json<RateLimitsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FullAdmin)?;
+
+  check_rate_limit(&data.message, &data.message_per_second)?;
+  check_rate_limit(&data.post, &data.post_per_second)?;
+  check_rate_limit(&data.register, &data.register_per_second)?;
+  check_rate_limit(&data.image, &data.image_per_second)?;
+  check_rate_limit(&data.comment, &data.comment_per_second)?;
+  check_rate_limit(&data.search, &data.search_per_second)?;
+
+  let form = LocalSiteRateLimitUpdateForm::builder()
+    .message(data.message)
+    .message_per_second(data.message_per_second)
+    .post(data.post)
+    .post_per_second(data.post_per_second)
+    .register(data.register)
+    .register_per_second(data.register_per_second)
+    .image(data.image)
+    .image_per_second(data.image_per_second)
+    .comment(data.comment)
+    .comment_per_second(data.comment_per_second)
+    .search(data.search)
+    .search_per_second(data.search_per_second)
+    .updated(Some(Some(naive_now())))
+    .build();
+  LocalSiteRateLimit::update(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateRateLimits)?;
+
+  // Refresh the in-memory rate limiter, so that the new limits apply without a restart
+  let rate_limits = LocalSiteRateLimit::read(&mut context.pool()).await?;
+  context
+    .settings_updated_channel()
+    .send(local_site_rate_limit_to_rate_limit_config(&rate_limits))
+    .await?;
+
+  Ok(Json(RateLimitsResponse { rate_limits }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn set_rate_limit_override(
+  data: Json<SetRateLimitOverride>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListRateLimitOverridesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FullAdmin)?;
+
+  // Only local users send requests to this instance
+  let target = LocalUserView::read_person(&mut context.pool(), data.person_id).await?;
+  if let Some(limit_percent) = data.limit_percent {
+    if limit_percent < 0 {
+      return Err(LemmyErrorType::InvalidRateLimit)?;
+    }
+    let form = RateLimitOverrideForm {
+      local_user_id: target.local_user.id,
+      limit_percent,
+    };
+    RateLimitOverride::set(&mut context.pool(), &form)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateRateLimits)?;
+  } else {
+    RateLimitOverride::remove(&mut context.pool(), target.local_user.id)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateRateLimits)?;
+  }
+
+  update_user_rate_limits(&mut context.pool(), context.settings_updated_channel()).await?;
+
+  let overrides = list_overrides(&context).await?;
+  Ok(Json(ListRateLimitOverridesResponse { overrides }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn list_rate_limit_overrides(
+  data: Query<ListRateLimitOverrides>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListRateLimitOverridesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let overrides = list_overrides(&context).await?;
+  Ok(Json(ListRateLimitOverridesResponse { overrides }))
+}
+
+async fn list_overrides(context: &LemmyContext) -> LemmyResult<Vec<PersonRateLimitOverride>> {
+  Ok(
+    RateLimitOverride::list(&mut context.pool())
+      .await?
+      .into_iter()
+      .map(|(rate_limit_override, person)| PersonRateLimitOverride {
+        person,
+        rate_limit_override,
+      })
+      .collect(),
+  )
+}
@@ -0,0 +1,41 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  site::{ReplayActivity, ReplayActivityResponse},
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{source::activity::SentActivity, AdminRole};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+use url::Url;
+
+#[tracing::instrument(skip(context))]
+pub async fn replay_activity(
+  data: Json<ReplayActivity>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ReplayActivityResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FederationAdmin)?;
+
+  let activity_id = Url::parse(&data.activity_id)?.into();
+  let inbox = Url::parse(&data.inbox)?;
+  let activity = SentActivity::read_from_apub_id(&mut context.pool(), &activity_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindActivity)?;
+
+  // Private messages must not be sent to anyone else
+  if activity.sensitive {
+    return Err(LemmyErrorType::CantReplaySensitiveActivity)?;
+  }
+
+  ActivityChannel::submit_activity(
+    SendActivityData::ReplayActivity(activity, vec![inbox]),
+    &context,
+  )
+  .await?;
+
+  Ok(Json(ReplayActivityResponse { success: true }))
+}
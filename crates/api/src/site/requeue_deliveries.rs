@@ -0,0 +1,64 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Duration;
+use lemmy_api_common::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  site::{RequeueFailedDeliveries, RequeueFailedDeliveriesResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{activity::SentActivity, sent_activity_delivery::SentActivityDelivery},
+  utils::naive_now,
+};
+use lemmy_utils::error::LemmyError;
+use std::collections::HashMap;
+
+/// How old failed deliveries can be to be requeued
+const REQUEUE_DAYS: i64 = 7;
+
+/// How many failed deliveries are requeued at most for each request
+const MAX_REQUEUE: i64 = 1000;
+
+#[tracing::instrument(skip(context))]
+pub async fn requeue_failed_deliveries(
+  data: Json<RequeueFailedDeliveries>,
+  context: Data<LemmyContext>,
+) -> Result<Json<RequeueFailedDeliveriesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let since = naive_now() - Duration::days(REQUEUE_DAYS);
+  let failed = SentActivityDelivery::list_failed_for_domain(
+    &mut context.pool(),
+    data.domain.trim(),
+    since,
+    MAX_REQUEUE,
+  )
+  .await?;
+  let requeued = failed.len() as i64;
+
+  // Send each activity only once, to all of its failed inboxes
+  let mut inboxes_by_activity = HashMap::<_, Vec<_>>::new();
+  for (activity_id, inbox) in failed {
+    inboxes_by_activity
+      .entry(activity_id)
+      .or_default()
+      .push(inbox.into());
+  }
+  let activity_ids = inboxes_by_activity.keys().cloned().collect::<Vec<_>>();
+  let activities = SentActivity::read_from_apub_ids(&mut context.pool(), &activity_ids).await?;
+  for activity in activities {
+    if let Some(inboxes) = inboxes_by_activity.remove(&activity.ap_id) {
+      ActivityChannel::submit_activity(
+        SendActivityData::ReplayActivity(activity, inboxes),
+        &context,
+      )
+      .await?;
+    }
+  }
+
+  Ok(Json(RequeueFailedDeliveriesResponse { requeued }))
+}
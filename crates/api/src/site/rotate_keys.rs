@@ -0,0 +1,50 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{RotateActorKeys, RotateActorKeysResponse},
+  utils::{
+    check_admin_role,
+    local_user_view_from_jwt,
+    rotate_community_keys,
+    rotate_person_keys,
+    rotate_site_keys,
+  },
+};
+use lemmy_db_schema::{
+  source::{community::Community, person::Person},
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_db_views::structs::SiteView;
+use lemmy_utils::error::{LemmyError, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn rotate_actor_keys(
+  data: Json<RotateActorKeys>,
+  context: Data<LemmyContext>,
+) -> Result<Json<RotateActorKeysResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FederationAdmin)?;
+
+  if let Some(person_id) = data.person_id {
+    let person = Person::read(&mut context.pool(), person_id).await?;
+    if !person.local {
+      return Err(LemmyErrorType::CantRotateRemoteKeys)?;
+    }
+    rotate_person_keys(&person, &context).await?;
+  } else if let Some(community_id) = data.community_id {
+    let community = Community::read(&mut context.pool(), community_id).await?;
+    if !community.local {
+      return Err(LemmyErrorType::CantRotateRemoteKeys)?;
+    }
+    rotate_community_keys(&community, local_user_view.person, &context).await?;
+  } else {
+    let site = SiteView::read_local(&mut context.pool()).await?.site;
+    rotate_site_keys(&site, &context).await?;
+  }
+
+  Ok(Json(RotateActorKeysResponse { success: true }))
+}
@@ -0,0 +1,57 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Duration;
+use lemmy_api_common::{
+  context::LemmyContext,
+  scheduled_job::{EditScheduledJob, ScheduledJobResponse},
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::scheduled_job::{ScheduledJob, ScheduledJobUpdateForm},
+  utils::naive_now,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+/// Shortest allowed interval between two runs of a job
+const MIN_INTERVAL_SECONDS: i32 = 60;
+
+#[tracing::instrument(skip(context))]
+pub async fn edit_scheduled_job(
+  data: Json<EditScheduledJob>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ScheduledJobResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FullAdmin)?;
+
+  if data
+    .interval_seconds
+    .is_some_and(|i| i < MIN_INTERVAL_SECONDS)
+  {
+    Err(LemmyErrorType::InvalidScheduledJobInterval)?
+  }
+
+  // With a new interval, the next run is counted from the end of the last one
+  let next_run = if let Some(interval_seconds) = data.interval_seconds {
+    let scheduled_job = ScheduledJob::read(&mut context.pool(), &data.name)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntFindScheduledJob)?;
+    let last_run = scheduled_job.last_finished.unwrap_or_else(naive_now);
+    Some(last_run + Duration::seconds(interval_seconds.into()))
+  } else {
+    None
+  };
+
+  let form = ScheduledJobUpdateForm {
+    interval_seconds: data.interval_seconds,
+    enabled: data.enabled,
+    next_run,
+  };
+  let scheduled_job = ScheduledJob::update(&mut context.pool(), &data.name, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateScheduledJob)?;
+
+  Ok(Json(ScheduledJobResponse { scheduled_job }))
+}
@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  scheduled_job::{ListScheduledJobs, ListScheduledJobsResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::scheduled_job::ScheduledJob;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_scheduled_jobs(
+  data: Query<ListScheduledJobs>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListScheduledJobsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let scheduled_jobs = ScheduledJob::list(&mut context.pool()).await?;
+
+  Ok(Json(ListScheduledJobsResponse { scheduled_jobs }))
+}
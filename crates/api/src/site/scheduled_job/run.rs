@@ -0,0 +1,42 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  scheduled_job::{RunScheduledJob, ScheduledJobResponse},
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::scheduled_job::{ScheduledJob, ScheduledJobUpdateForm},
+  utils::naive_now,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn run_scheduled_job(
+  data: Json<RunScheduledJob>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ScheduledJobResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FullAdmin)?;
+
+  let scheduled_job = ScheduledJob::read(&mut context.pool(), &data.name)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindScheduledJob)?;
+  if !scheduled_job.enabled {
+    Err(LemmyErrorType::ScheduledJobDisabled)?
+  }
+
+  // The scheduler picks the job up on its next check for due jobs
+  let form = ScheduledJobUpdateForm {
+    next_run: Some(naive_now()),
+    ..Default::default()
+  };
+  let scheduled_job = ScheduledJob::update(&mut context.pool(), &data.name, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateScheduledJob)?;
+
+  Ok(Json(ScheduledJobResponse { scheduled_job }))
+}
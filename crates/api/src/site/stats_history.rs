@@ -0,0 +1,35 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use chrono::{NaiveDate, NaiveDateTime};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{GetSiteStatsHistory, GetSiteStatsHistoryResponse},
+  utils::{check_private_instance, local_user_view_from_jwt_opt},
+};
+use lemmy_db_schema::{aggregates::structs::SiteStatsHistory, source::local_site::LocalSite};
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+
+#[tracing::instrument(skip(context))]
+pub async fn get_site_stats_history(
+  data: Query<GetSiteStatsHistory>,
+  context: Data<LemmyContext>,
+) -> Result<Json<GetSiteStatsHistoryResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt_opt(data.auth.as_ref(), &context).await;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let since = data.since.map(day_from_unix).transpose()?;
+  let until = data.until.map(day_from_unix).transpose()?;
+  let history = SiteStatsHistory::list(&mut context.pool(), since, until).await?;
+
+  Ok(Json(GetSiteStatsHistoryResponse { history }))
+}
+
+fn day_from_unix(time: i64) -> LemmyResult<NaiveDate> {
+  Ok(
+    NaiveDateTime::from_timestamp_opt(time, 0)
+      .ok_or(LemmyErrorType::InvalidUnixTime)?
+      .date(),
+  )
+}
@@ -0,0 +1,85 @@
+use crate::sensitive::Sensitive;
+use lemmy_db_schema::{
+  newtypes::AnnouncementId,
+  source::announcement::Announcement,
+  AnnouncementSeverity,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create an announcement banner. The message is markdown.
+pub struct CreateAnnouncement {
+  pub message: String,
+  pub severity: Option<AnnouncementSeverity>,
+  /// A unix timestamp of when to start showing the announcement. Defaults to now.
+  pub start_time: Option<i64>,
+  /// A unix timestamp of when to stop showing the announcement. It is shown until deleted if
+  /// this is empty.
+  pub end_time: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Edit an announcement banner.
+pub struct EditAnnouncement {
+  pub id: AnnouncementId,
+  pub message: String,
+  pub severity: Option<AnnouncementSeverity>,
+  pub start_time: Option<i64>,
+  pub end_time: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete an announcement banner.
+pub struct DeleteAnnouncement {
+  pub id: AnnouncementId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for deleting an announcement.
+pub struct DeleteAnnouncementResponse {
+  pub id: AnnouncementId,
+  pub success: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches all announcements, including past and scheduled ones.
+pub struct ListAnnouncements {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for an announcement.
+pub struct AnnouncementResponse {
+  pub announcement: Announcement,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A list of announcements.
+pub struct ListAnnouncementsResponse {
+  pub announcements: Vec<Announcement>,
+}
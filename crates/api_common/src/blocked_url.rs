@@ -0,0 +1,70 @@
+use crate::sensitive::Sensitive;
+use lemmy_db_schema::{newtypes::BlockedUrlId, source::blocked_url::BlockedUrl};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Block a domain (and its subdomains), or a url prefix, from being linked in posts.
+pub struct CreateBlockedUrl {
+  pub url: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Edit a blocked url.
+pub struct EditBlockedUrl {
+  pub id: BlockedUrlId,
+  pub url: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Unblock a url.
+pub struct DeleteBlockedUrl {
+  pub id: BlockedUrlId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for deleting a blocked url.
+pub struct DeleteBlockedUrlResponse {
+  pub id: BlockedUrlId,
+  pub success: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches a list of blocked urls.
+pub struct ListBlockedUrls {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a blocked url.
+pub struct BlockedUrlResponse {
+  pub blocked_url: BlockedUrl,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A list of blocked urls.
+pub struct ListBlockedUrlsResponse {
+  pub blocked_urls: Vec<BlockedUrl>,
+}
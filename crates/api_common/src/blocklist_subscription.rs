@@ -0,0 +1,120 @@
+use crate::sensitive::Sensitive;
+use lemmy_db_schema::{
+  newtypes::{BlocklistSubscriptionId, InstanceId},
+  source::{
+    blocklist_subscription::BlocklistSubscription,
+    federation_blocklist::FederationBlockList,
+    instance::Instance,
+  },
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Subscribe to an external blocklist, which is synced into the federation blocklist.
+pub struct CreateBlocklistSubscription {
+  pub url: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Unsubscribe from a blocklist. Its entries are removed from the federation blocklist, unless they
+/// are pinned.
+pub struct DeleteBlocklistSubscription {
+  pub id: BlocklistSubscriptionId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for deleting a blocklist subscription.
+pub struct DeleteBlocklistSubscriptionResponse {
+  pub id: BlocklistSubscriptionId,
+  pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Sync a blocklist subscription right away, instead of waiting for the scheduled sync.
+pub struct SyncBlocklistSubscription {
+  pub id: BlocklistSubscriptionId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the blocklist subscriptions.
+pub struct ListBlocklistSubscriptions {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a blocklist subscription.
+pub struct BlocklistSubscriptionResponse {
+  pub blocklist_subscription: BlocklistSubscription,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A list of blocklist subscriptions.
+pub struct ListBlocklistSubscriptionsResponse {
+  pub blocklist_subscriptions: Vec<BlocklistSubscription>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the federation blocklist, with the source and reason of each entry.
+pub struct ListBlockedInstances {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An entry of the federation blocklist.
+pub struct BlockedInstance {
+  pub instance: Instance,
+  pub blocklist: FederationBlockList,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The federation blocklist.
+pub struct ListBlockedInstancesResponse {
+  pub blocked_instances: Vec<BlockedInstance>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Pin an entry of the federation blocklist, so that blocklist syncs never remove it.
+pub struct PinBlockedInstance {
+  pub instance_id: InstanceId,
+  pub pinned: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for pinning an entry of the federation blocklist.
+pub struct BlockedInstanceResponse {
+  pub blocked_instance: BlockedInstance,
+}
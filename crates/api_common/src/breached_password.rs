@@ -0,0 +1,210 @@
+use crate::context::LemmyContext;
+use lemmy_db_schema::source::local_site::LocalSite;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorType, LemmyResult},
+  settings::structs::BreachedPasswordsConfig,
+};
+use openssl::sha::sha1;
+use std::fs;
+use tokio::{sync::OnceCell, task::spawn_blocking};
+use tracing::warn;
+use url::Url;
+
+/// Loaded on first use, so that the file is only read on instances which need it.
+static BLOOM_FILTER: OnceCell<Option<BloomFilter>> = OnceCell::const_new();
+
+struct BloomFilter {
+  bits: Vec<u8>,
+  hashes: u32,
+}
+
+impl BloomFilter {
+  fn load(config: &BreachedPasswordsConfig) -> Option<Self> {
+    let path = config.bloom_filter_file.as_ref()?;
+    match fs::read(path) {
+      Ok(bits) if !bits.is_empty() => Some(BloomFilter {
+        bits,
+        hashes: config.bloom_filter_hashes,
+      }),
+      Ok(_) => {
+        warn!("Breached password bloom filter {path} is empty");
+        None
+      }
+      Err(e) => {
+        warn!("Failed to read breached password bloom filter {path}: {e}");
+        None
+      }
+    }
+  }
+
+  /// Whether the password may be in the set. The positions are derived from the SHA-1 hash with
+  /// double hashing, so the filter has to be built the same way.
+  fn contains(&self, digest: &[u8; 20]) -> bool {
+    let num_bits = self.bits.len() as u64 * 8;
+    let (h1, h2) = split_digest(digest);
+    (0..u64::from(self.hashes)).all(|i| {
+      let pos = h1.wrapping_add(i.wrapping_mul(h2)) % num_bits;
+      self
+        .bits
+        .get((pos / 8) as usize)
+        .is_some_and(|byte| byte & (1 << (pos % 8)) != 0)
+    })
+  }
+}
+
+fn split_digest(digest: &[u8; 20]) -> (u64, u64) {
+  let mut h1 = [0; 8];
+  let mut h2 = [0; 8];
+  h1.copy_from_slice(&digest[..8]);
+  h2.copy_from_slice(&digest[8..16]);
+  (u64::from_be_bytes(h1), u64::from_be_bytes(h2))
+}
+
+/// Rejects passwords which are known from data breaches, if this is enabled for the site.
+///
+/// Only the first five characters of the password hash are sent to the range API (k-anonymity).
+/// If the API can't be reached, the offline bloom filter is used if there is one, otherwise the
+/// password is accepted.
+pub async fn check_breached_password(
+  password: &str,
+  local_site: &LocalSite,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  if !local_site.check_breached_passwords {
+    return Ok(());
+  }
+  let config = &context.settings().breached_passwords;
+  let digest = sha1(password.as_bytes());
+
+  let breached = match fetch_is_breached(&digest, config, context).await {
+    Ok(Some(breached)) => breached,
+    res => {
+      if let Err(e) = res {
+        warn!("Failed to check for breached password: {e}");
+      }
+      let filter = BLOOM_FILTER
+        .get_or_init(|| async {
+          // The file can be large, so it is read without blocking the runtime
+          let config = config.clone();
+          spawn_blocking(move || BloomFilter::load(&config))
+            .await
+            .ok()
+            .flatten()
+        })
+        .await;
+      match filter {
+        Some(filter) => filter.contains(&digest),
+        None => false,
+      }
+    }
+  };
+  if breached {
+    Err(LemmyErrorType::BreachedPassword)?
+  }
+  Ok(())
+}
+
+/// Looks up the hash with the range API. Returns `None` if no API is configured.
+async fn fetch_is_breached(
+  digest: &[u8; 20],
+  config: &BreachedPasswordsConfig,
+  context: &LemmyContext,
+) -> LemmyResult<Option<bool>> {
+  let Some(api_url) = &config.api_url else {
+    return Ok(None);
+  };
+  let hash = to_upper_hex(digest);
+  let (prefix, suffix) = hash.split_at(5);
+  let url = range_url(api_url, prefix)?;
+  let text = context
+    .client()
+    .get(url.as_str())
+    // Pads the response with fake entries, so that its size doesn't reveal the prefix
+    .header("Add-Padding", "true")
+    .send()
+    .await?
+    .error_for_status()?
+    .text()
+    .await
+    .map_err(LemmyError::from)?;
+  Ok(Some(range_contains(&text, suffix)))
+}
+
+/// The url for looking up the hashes with the given prefix. The API may be served from a sub path,
+/// which is only kept by `join` if it ends with a slash.
+fn range_url(api_url: &Url, prefix: &str) -> LemmyResult<Url> {
+  let mut api_url = api_url.clone();
+  if !api_url.path().ends_with('/') {
+    api_url.set_path(&format!("{}/", api_url.path()));
+  }
+  Ok(api_url.join(&format!("range/{prefix}"))?)
+}
+
+/// Checks a range response with one `SUFFIX:COUNT` line per hash. Padding entries have a count of
+/// zero.
+fn range_contains(text: &str, suffix: &str) -> bool {
+  text.lines().any(|line| match line.trim().split_once(':') {
+    Some((s, count)) => s.eq_ignore_ascii_case(suffix) && count.trim() != "0",
+    None => false,
+  })
+}
+
+fn to_upper_hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+
+  #[test]
+  fn test_range_contains() {
+    let hash = to_upper_hex(&sha1(b"password"));
+    assert_eq!("5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8", hash);
+    let (_, suffix) = hash.split_at(5);
+
+    let response = "0018A45C4D1DEF81644B54AB7F969B88D65:1\r\n\
+                    1E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\r\n\
+                    011053FD0102E94D6AE2F8B83D76FAF94F6:0";
+    assert!(range_contains(response, suffix));
+    assert!(!range_contains(
+      response,
+      "011053FD0102E94D6AE2F8B83D76FAF94F6"
+    ));
+    assert!(!range_contains(
+      response,
+      "00000000000000000000000000000000000"
+    ));
+  }
+
+  #[test]
+  fn test_range_url() {
+    for api_url in ["https://example.com", "https://example.com/"] {
+      let url = range_url(&Url::parse(api_url).unwrap(), "5BAA6").unwrap();
+      assert_eq!("https://example.com/range/5BAA6", url.as_str());
+    }
+    for api_url in ["https://example.com/pwned", "https://example.com/pwned/"] {
+      let url = range_url(&Url::parse(api_url).unwrap(), "5BAA6").unwrap();
+      assert_eq!("https://example.com/pwned/range/5BAA6", url.as_str());
+    }
+  }
+
+  #[test]
+  fn test_bloom_filter() {
+    let mut filter = BloomFilter {
+      bits: vec![0; 1024],
+      hashes: 7,
+    };
+    let breached = sha1(b"password");
+    let (h1, h2) = split_digest(&breached);
+    for i in 0..7u64 {
+      let pos = h1.wrapping_add(i.wrapping_mul(h2)) % (1024 * 8);
+      filter.bits[(pos / 8) as usize] |= 1 << (pos % 8);
+    }
+    assert!(filter.contains(&breached));
+    assert!(!filter.contains(&sha1(b"correct horse battery staple")));
+  }
+}
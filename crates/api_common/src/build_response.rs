@@ -3,7 +3,13 @@ use crate::{
   community::CommunityResponse,
   context::LemmyContext,
   post::PostResponse,
-  utils::{check_person_block, get_interface_language, is_mod_or_admin, send_email_to_user},
+  utils::{
+    check_person_block,
+    get_interface_language,
+    is_mod_or_admin,
+    send_email_to_user,
+    NotificationType,
+  },
 };
 use actix_web::web::Json;
 use lemmy_db_schema::{
@@ -12,15 +18,22 @@ use lemmy_db_schema::{
     actor_language::CommunityLanguage,
     comment::Comment,
     comment_reply::{CommentReply, CommentReplyInsertForm},
+    community::Community,
+    community_notification::{CommunityNotification, PostNotification, PostNotificationInsertForm},
     person::Person,
     person_mention::{PersonMention, PersonMentionInsertForm},
     post::Post,
+    thread_subscription::ThreadSubscription,
   },
-  traits::Crud,
+  traits::{ApubActor, Crud},
+  utils::naive_now,
 };
 use lemmy_db_views::structs::{CommentView, LocalUserView, PostView};
-use lemmy_db_views_actor::structs::CommunityView;
-use lemmy_utils::{error::LemmyError, utils::mention::MentionData};
+use lemmy_db_views_actor::structs::{CommunityModeratorView, CommunityView};
+use lemmy_utils::{
+  error::{LemmyError, LemmyResult},
+  utils::mention::MentionData,
+};
 
 pub async fn build_comment_response(
   context: &LemmyContext,
@@ -91,6 +104,10 @@ pub async fn send_local_notifs(
   context: &LemmyContext,
 ) -> Result<Vec<LocalUserId>, LemmyError> {
   let mut recipient_ids = Vec::new();
+  // Nobody else can see the comment, so don't notify anyone
+  if person.is_shadowbanned_for(comment.published) {
+    return Ok(recipient_ids);
+  }
   let inbox_link = format!("{}/inbox", context.settings().get_protocol_and_hostname());
 
   // Send the local mentions
@@ -108,7 +125,8 @@ pub async fn send_local_notifs(
 
       let user_mention_form = PersonMentionInsertForm {
         recipient_id: mention_user_view.person.id,
-        comment_id: comment.id,
+        comment_id: Some(comment.id),
+        post_id: None,
         read: None,
       };
 
@@ -123,6 +141,7 @@ pub async fn send_local_notifs(
         let lang = get_interface_language(&mention_user_view);
         send_email_to_user(
           &mention_user_view,
+          NotificationType::Mention,
           &lang.notification_mentioned_by_subject(&person.name),
           &lang.notification_mentioned_by_body(&comment.content, &inbox_link, &person.name),
           context.settings(),
@@ -166,6 +185,7 @@ pub async fn send_local_notifs(
           let lang = get_interface_language(&parent_user_view);
           send_email_to_user(
             &parent_user_view,
+            NotificationType::CommentReply,
             &lang.notification_comment_reply_subject(&person.name),
             &lang.notification_comment_reply_body(&comment.content, &inbox_link, &person.name),
             context.settings(),
@@ -203,6 +223,7 @@ pub async fn send_local_notifs(
           let lang = get_interface_language(&parent_user_view);
           send_email_to_user(
             &parent_user_view,
+            NotificationType::PostReply,
             &lang.notification_post_reply_subject(&person.name),
             &lang.notification_post_reply_body(&comment.content, &inbox_link, &person.name),
             context.settings(),
@@ -213,5 +234,221 @@ pub async fn send_local_notifs(
     }
   }
 
+  // Send comment_reply to the users who subscribed to the post or to a comment above this one
+  let subscriptions = ThreadSubscription::list_for_comment(&mut context.pool(), comment).await?;
+  for subscription in subscriptions {
+    let subscriber_id = subscription.person_id;
+    let creator_blocked = check_person_block(person.id, subscriber_id, &mut context.pool())
+      .await
+      .is_err();
+    if subscriber_id == person.id || creator_blocked {
+      continue;
+    }
+    let user_view = LocalUserView::read_person(&mut context.pool(), subscriber_id).await;
+    if let Ok(subscriber_view) = user_view {
+      // Already notified as parent creator, through a mention or another subscription
+      if recipient_ids.contains(&subscriber_view.local_user.id) {
+        continue;
+      }
+      recipient_ids.push(subscriber_view.local_user.id);
+
+      let comment_reply_form = CommentReplyInsertForm {
+        recipient_id: subscriber_view.person.id,
+        comment_id: comment.id,
+        read: None,
+      };
+      CommentReply::create(&mut context.pool(), &comment_reply_form)
+        .await
+        .ok();
+
+      if do_send_email {
+        let lang = get_interface_language(&subscriber_view);
+        send_email_to_user(
+          &subscriber_view,
+          NotificationType::CommentReply,
+          &lang.notification_comment_reply_subject(&person.name),
+          &lang.notification_comment_reply_body(&comment.content, &inbox_link, &person.name),
+          context.settings(),
+        )
+        .await
+      }
+    }
+  }
+
   Ok(recipient_ids)
 }
+
+/// Notifies the users who want to know about new posts in the community. Each subscription gets
+/// at most one notification per interval.
+pub async fn send_community_post_notifs(
+  post: &Post,
+  creator: &Person,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  // Nobody else can see the post
+  if post.removed || post.deleted || creator.is_shadowbanned_for(post.published) {
+    return Ok(());
+  }
+  let now = naive_now();
+  let subscriptions =
+    CommunityNotification::list_due(&mut context.pool(), post.community_id, now).await?;
+  for subscription in subscriptions {
+    let recipient_id = subscription.person_id;
+    let creator_blocked = check_person_block(creator.id, recipient_id, &mut context.pool())
+      .await
+      .is_err();
+    if recipient_id == creator.id || creator_blocked {
+      continue;
+    }
+    let form = PostNotificationInsertForm {
+      recipient_id,
+      post_id: post.id,
+    };
+    // The post may be received again through federation, only notify about it once
+    if PostNotification::create(&mut context.pool(), &form)
+      .await?
+      .is_some()
+    {
+      CommunityNotification::mark_notified(&mut context.pool(), subscription.id, now).await?;
+    }
+  }
+  Ok(())
+}
+
+/// Notifies local users who are mentioned in the body of a post.
+#[tracing::instrument(skip_all)]
+pub async fn send_post_mention_notifs(
+  mentions: Vec<MentionData>,
+  post: &Post,
+  creator: &Person,
+  do_send_email: bool,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  // Nobody else can see the post
+  if post.removed || post.deleted || creator.is_shadowbanned_for(post.published) {
+    return Ok(());
+  }
+  let inbox_link = format!("{}/inbox", context.settings().get_protocol_and_hostname());
+
+  for mention in mentions
+    .iter()
+    .filter(|m| m.is_local(&context.settings().hostname) && m.name.ne(&creator.name))
+  {
+    let Ok(mention_user_view) =
+      LocalUserView::read_from_name(&mut context.pool(), &mention.name).await
+    else {
+      continue;
+    };
+    let recipient_id = mention_user_view.person.id;
+    let creator_blocked = check_person_block(creator.id, recipient_id, &mut context.pool())
+      .await
+      .is_err();
+    if creator_blocked {
+      continue;
+    }
+
+    let user_mention_form = PersonMentionInsertForm {
+      recipient_id,
+      comment_id: None,
+      post_id: Some(post.id),
+      read: None,
+    };
+
+    // Allow this to fail softly, since post edits might re-update or replace it
+    PersonMention::create(&mut context.pool(), &user_mention_form)
+      .await
+      .ok();
+
+    if do_send_email {
+      let lang = get_interface_language(&mention_user_view);
+      let content = post.body.as_deref().unwrap_or(&post.name);
+      send_email_to_user(
+        &mention_user_view,
+        NotificationType::Mention,
+        &lang.notification_mentioned_by_subject(&creator.name),
+        &lang.notification_mentioned_by_body(content, &inbox_link, &creator.name),
+        context.settings(),
+      )
+      .await
+    }
+  }
+  Ok(())
+}
+
+/// Notifies the mods of local communities which are mentioned in a post or comment, if they
+/// enabled it. They receive the same notification as for a mention of themselves.
+#[tracing::instrument(skip_all)]
+pub async fn send_community_mention_notifs(
+  mentions: Vec<MentionData>,
+  post: &Post,
+  comment: Option<&Comment>,
+  creator: &Person,
+  do_send_email: bool,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let (hidden, published, content) = match comment {
+    Some(c) => (c.removed || c.deleted, c.published, c.content.as_str()),
+    None => (
+      post.removed || post.deleted,
+      post.published,
+      post.body.as_deref().unwrap_or(&post.name),
+    ),
+  };
+  // Nobody else can see the post or comment
+  if hidden || creator.is_shadowbanned_for(published) {
+    return Ok(());
+  }
+  let inbox_link = format!("{}/inbox", context.settings().get_protocol_and_hostname());
+
+  for mention in mentions
+    .iter()
+    .filter(|m| m.is_local(&context.settings().hostname))
+  {
+    let Ok(community) = Community::read_from_name(&mut context.pool(), &mention.name, false).await
+    else {
+      continue;
+    };
+    if !community.notify_mods_on_mention {
+      continue;
+    }
+    let mods = CommunityModeratorView::for_community(&mut context.pool(), community.id).await?;
+    for moderator in mods.into_iter().filter(|m| m.moderator.id != creator.id) {
+      let recipient_id = moderator.moderator.id;
+      let Ok(mod_user_view) = LocalUserView::read_person(&mut context.pool(), recipient_id).await
+      else {
+        continue;
+      };
+      let creator_blocked = check_person_block(creator.id, recipient_id, &mut context.pool())
+        .await
+        .is_err();
+      if creator_blocked {
+        continue;
+      }
+
+      let user_mention_form = PersonMentionInsertForm {
+        recipient_id,
+        comment_id: comment.map(|c| c.id),
+        post_id: comment.is_none().then_some(post.id),
+        read: None,
+      };
+
+      // Allow this to fail softly, since edits might re-update or replace it
+      PersonMention::create(&mut context.pool(), &user_mention_form)
+        .await
+        .ok();
+
+      if do_send_email {
+        let lang = get_interface_language(&mod_user_view);
+        send_email_to_user(
+          &mod_user_view,
+          NotificationType::Mention,
+          &lang.notification_mentioned_by_subject(&creator.name),
+          &lang.notification_mentioned_by_body(content, &inbox_link, &creator.name),
+          context.settings(),
+        )
+        .await
+      }
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,190 @@
+use crate::context::LemmyContext;
+use async_trait::async_trait;
+use lemmy_db_schema::source::{
+  captcha_answer::{CaptchaAnswer, CheckCaptchaAnswer},
+  secret::Secret,
+};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  settings::structs::CaptchaProvider,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+const HCAPTCHA_VERIFY_URL: &str = "https://api.hcaptcha.com/siteverify";
+const TURNSTILE_VERIFY_URL: &str = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
+
+/// The captcha fields which are sent by the client. For the built in captcha this is the uuid of
+/// the generated captcha and the typed answer, for hosted services only the token returned by the
+/// widget is sent, as answer.
+pub struct CaptchaInput<'a> {
+  pub uuid: Option<&'a str>,
+  pub answer: Option<&'a str>,
+}
+
+/// Verifies a captcha on the server side.
+#[async_trait]
+pub trait CaptchaVerifier: Send + Sync {
+  async fn verify(&self, input: &CaptchaInput<'_>, context: &LemmyContext) -> LemmyResult<bool>;
+}
+
+/// Captcha generated by Lemmy itself, with the answer stored in the database
+pub struct Builtin;
+
+#[async_trait]
+impl CaptchaVerifier for Builtin {
+  async fn verify(&self, input: &CaptchaInput<'_>, context: &LemmyContext) -> LemmyResult<bool> {
+    let Some(uuid) = input.uuid else {
+      return Ok(false);
+    };
+    let uuid = uuid::Uuid::parse_str(uuid)?;
+    let check = CaptchaAnswer::check_captcha(
+      &mut context.pool(),
+      CheckCaptchaAnswer {
+        uuid,
+        answer: input.answer.unwrap_or_default().to_string(),
+      },
+    )
+    .await?;
+    Ok(check)
+  }
+}
+
+#[derive(Deserialize)]
+struct SiteVerifyResponse {
+  success: bool,
+}
+
+/// hCaptcha and Cloudflare Turnstile share the same siteverify api
+pub struct SiteVerify {
+  url: &'static str,
+  secret: fn(&Secret) -> Option<&String>,
+}
+
+#[async_trait]
+impl CaptchaVerifier for SiteVerify {
+  async fn verify(&self, input: &CaptchaInput<'_>, context: &LemmyContext) -> LemmyResult<bool> {
+    let Some(token) = input.answer else {
+      return Ok(false);
+    };
+    let secrets = Secret::read_secrets(&mut context.pool()).await?;
+    let secret = (self.secret)(&secrets).ok_or(LemmyErrorType::CaptchaNotConfigured)?;
+    let mut params = vec![("secret", secret.as_str()), ("response", token)];
+    if let Some(site_key) = &context.settings().captcha.site_key {
+      params.push(("sitekey", site_key));
+    }
+    let res: SiteVerifyResponse = context
+      .client()
+      .post(self.url)
+      .form(&params)
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?;
+    Ok(res.success)
+  }
+}
+
+#[derive(Serialize)]
+struct MCaptchaVerifyRequest<'a> {
+  token: &'a str,
+  key: &'a str,
+  secret: &'a str,
+}
+
+#[derive(Deserialize)]
+struct MCaptchaVerifyResponse {
+  valid: bool,
+}
+
+/// A self hosted mCaptcha instance
+pub struct MCaptcha;
+
+#[async_trait]
+impl CaptchaVerifier for MCaptcha {
+  async fn verify(&self, input: &CaptchaInput<'_>, context: &LemmyContext) -> LemmyResult<bool> {
+    let Some(token) = input.answer else {
+      return Ok(false);
+    };
+    let config = &context.settings().captcha;
+    let (Some(url), Some(key)) = (&config.mcaptcha_url, &config.site_key) else {
+      return Err(LemmyErrorType::CaptchaNotConfigured)?;
+    };
+    let secrets = Secret::read_secrets(&mut context.pool()).await?;
+    let secret = secrets
+      .mcaptcha_secret
+      .ok_or(LemmyErrorType::CaptchaNotConfigured)?;
+    let res: MCaptchaVerifyResponse = context
+      .client()
+      .post(mcaptcha_verify_url(url)?.as_str())
+      .json(&MCaptchaVerifyRequest {
+        token,
+        key,
+        secret: &secret,
+      })
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?;
+    Ok(res.valid)
+  }
+}
+
+fn mcaptcha_verify_url(instance: &Url) -> LemmyResult<Url> {
+  Ok(instance.join("api/v1/pow/siteverify")?)
+}
+
+fn captcha_verifier(provider: CaptchaProvider) -> Box<dyn CaptchaVerifier> {
+  match provider {
+    CaptchaProvider::Builtin => Box::new(Builtin),
+    CaptchaProvider::HCaptcha => Box::new(SiteVerify {
+      url: HCAPTCHA_VERIFY_URL,
+      secret: |s| s.hcaptcha_secret.as_ref(),
+    }),
+    CaptchaProvider::Turnstile => Box::new(SiteVerify {
+      url: TURNSTILE_VERIFY_URL,
+      secret: |s| s.turnstile_secret.as_ref(),
+    }),
+    CaptchaProvider::MCaptcha => Box::new(MCaptcha),
+  }
+}
+
+/// Verifies the captcha with the provider from the config, and returns an error if it wasn't
+/// solved.
+pub async fn check_captcha(input: &CaptchaInput<'_>, context: &LemmyContext) -> LemmyResult<()> {
+  let verifier = captcha_verifier(context.settings().captcha.provider);
+  if verifier.verify(input, context).await? {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::CaptchaIncorrect)?
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+
+  #[test]
+  fn test_mcaptcha_verify_url() {
+    let url = Url::parse("https://mcaptcha.example.com").unwrap();
+    assert_eq!(
+      "https://mcaptcha.example.com/api/v1/pow/siteverify",
+      mcaptcha_verify_url(&url).unwrap().as_str()
+    );
+  }
+
+  #[test]
+  fn test_site_verify_response() {
+    let res: SiteVerifyResponse =
+      serde_json::from_str(r#"{"success":true,"error-codes":[],"hostname":"example.com"}"#)
+        .unwrap();
+    assert!(res.success);
+    let res: MCaptchaVerifyResponse = serde_json::from_str(r#"{"valid":false}"#).unwrap();
+    assert!(!res.valid);
+  }
+}
@@ -0,0 +1,56 @@
+use crate::sensitive::Sensitive;
+use lemmy_db_schema::{newtypes::CategoryId, source::category::Category};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create a category, which communities can then assign to themselves.
+pub struct CreateCategory {
+  pub name: String,
+  pub description: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Edit a category.
+pub struct EditCategory {
+  pub id: CategoryId,
+  pub name: Option<String>,
+  /// An empty description removes it.
+  pub description: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a category. It is also removed from all communities.
+pub struct DeleteCategory {
+  pub id: CategoryId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for deleting a category.
+pub struct DeleteCategoryResponse {
+  pub id: CategoryId,
+  pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a category.
+pub struct CategoryResponse {
+  pub category: Category,
+}
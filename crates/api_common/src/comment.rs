@@ -1,10 +1,20 @@
 use crate::sensitive::Sensitive;
 use lemmy_db_schema::{
-  newtypes::{CommentId, CommentReportId, CommunityId, LanguageId, LocalUserId, PostId},
+  newtypes::{
+    CommentId,
+    CommentReportId,
+    CommunityId,
+    CommunityRuleId,
+    LanguageId,
+    LocalUserId,
+    PostId,
+    ReportReasonId,
+  },
+  source::thread_subscription::ThreadSubscription,
   CommentSortType,
   ListingType,
 };
-use lemmy_db_views::structs::{CommentReportView, CommentView};
+use lemmy_db_views::structs::{CommentReportView, CommentView, VoteView};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
@@ -76,6 +86,8 @@ pub struct RemoveComment {
   pub comment_id: CommentId,
   pub removed: bool,
   pub reason: Option<String>,
+  /// Record a strike against the creator in the community.
+  pub strike: Option<bool>,
   pub auth: Sensitive<String>,
 }
 
@@ -89,6 +101,28 @@ pub struct SaveComment {
   pub auth: Sensitive<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get notified about all new comments in a post, or below a comment.
+pub struct SubscribeToThread {
+  pub post_id: PostId,
+  /// Subscribe to the replies below this comment, instead of the whole post.
+  pub comment_id: Option<CommentId>,
+  pub subscribe: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The thread subscription. None after unsubscribing.
+pub struct ThreadSubscriptionResponse {
+  pub thread_subscription: Option<ThreadSubscription>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -110,6 +144,39 @@ pub struct CreateCommentLike {
   pub auth: Sensitive<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the votes on a comment. Only for mods and admins.
+pub struct ListCommentLikes {
+  pub comment_id: CommentId,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The comment likes response.
+pub struct ListCommentLikesResponse {
+  pub comment_likes: Vec<VoteView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Add or remove an emoji reaction on a comment.
+pub struct CreateCommentReaction {
+  pub comment_id: CommentId,
+  /// A unicode emoji, or a custom emoji shortcode like `:blobcat:`.
+  pub emoji: String,
+  /// Set to false to remove the reaction.
+  pub add: bool,
+  pub auth: Sensitive<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -126,24 +193,56 @@ pub struct GetComments {
   pub post_id: Option<PostId>,
   pub parent_id: Option<CommentId>,
   pub saved_only: Option<bool>,
+  /// Only show comments with open reports, in communities you moderate.
+  pub reported_only: Option<bool>,
+  /// Continue a depth-first walk of the tree after the comment with this path. Pass the path of
+  /// the parent comment (or `0` for the whole post) to start, and `next_page` after that.
+  pub page_cursor: Option<String>,
   pub auth: Option<Sensitive<String>>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
 /// The comment list response.
 pub struct GetCommentsResponse {
   pub comments: Vec<CommentView>,
+  /// The cursor for the next page of a paginated tree fetch, if there are more comments.
+  pub next_page: Option<String>,
+  /// For comments of a single post, the returned comments which were created since your previous
+  /// visit to the post.
+  pub new_comments: Option<Vec<CommentId>>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Load more replies under a comment.
+pub struct GetCommentChildren {
+  pub parent_id: CommentId,
+  /// How many levels of replies to fetch. Defaults to only the direct children.
+  pub max_depth: Option<i32>,
+  /// The `next_page` of a previous response.
+  pub page_cursor: Option<String>,
+  pub limit: Option<i64>,
+  pub auth: Option<Sensitive<String>>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
 /// Report a comment.
 pub struct CreateCommentReport {
   pub comment_id: CommentId,
-  pub reason: String,
+  /// Free text, which is only required without a listed reason.
+  pub reason: Option<String>,
+  /// One of the reasons from ListReportReasons.
+  pub reason_id: Option<ReportReasonId>,
+  /// The community rule which the content breaks.
+  pub rule_id: Option<CommunityRuleId>,
   pub auth: Sensitive<String>,
 }
 
@@ -177,6 +276,8 @@ pub struct ListCommentReports {
   pub unresolved_only: Option<bool>,
   /// if no community is given, it returns reports for all communities moderated by the auth user
   pub community_id: Option<CommunityId>,
+  /// Only shows the reports with this listed reason
+  pub reason_id: Option<ReportReasonId>,
   pub auth: Sensitive<String>,
 }
 
@@ -1,11 +1,43 @@
-use crate::sensitive::Sensitive;
+use crate::{
+  community_archive::{CommunityArchive, RedditArchive},
+  sensitive::Sensitive,
+};
 use lemmy_db_schema::{
-  newtypes::{CommunityId, LanguageId, PersonId},
-  source::site::Site,
+  newtypes::{
+    CategoryId,
+    CommunityApplicationId,
+    CommunityExportId,
+    CommunityId,
+    CommunityImportId,
+    CommunityRuleId,
+    CommunityWordFilterId,
+    LanguageId,
+    PersonId,
+  },
+  source::{
+    category::Category,
+    community::CommunityTransferRequest,
+    community_export::CommunityExport,
+    community_import::CommunityImport,
+    community_notification::CommunityNotification,
+    community_rule::CommunityRule,
+    community_snooze::CommunitySnooze,
+    community_strike::CommunityStrike,
+    community_word_filter::CommunityWordFilter,
+    site::Site,
+  },
   ListingType,
+  PostTypeRestriction,
   SortType,
+  WordFilterAction,
+};
+use lemmy_db_views::structs::CommunityApplicationView;
+use lemmy_db_views_actor::structs::{
+  CommunityFollowerView,
+  CommunityModeratorView,
+  CommunityView,
+  PersonView,
 };
-use lemmy_db_views_actor::structs::{CommunityModeratorView, CommunityView, PersonView};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
@@ -33,6 +65,11 @@ pub struct GetCommunityResponse {
   pub site: Option<Site>,
   pub moderators: Vec<CommunityModeratorView>,
   pub discussion_languages: Vec<LanguageId>,
+  pub categories: Vec<Category>,
+  /// The rules of the community, in order.
+  pub rules: Vec<CommunityRule>,
+  /// A transfer waiting to be accepted, only shown to mods and the new owner.
+  pub pending_transfer: Option<CommunityTransferRequest>,
 }
 
 #[skip_serializing_none]
@@ -55,7 +92,35 @@ pub struct CreateCommunity {
   pub nsfw: Option<bool>,
   /// Whether to restrict posting only to moderators.
   pub posting_restricted_to_mods: Option<bool>,
+  /// Whether to allow downvotes. Has no effect if downvotes are disabled for the whole site.
+  pub enable_downvotes: Option<bool>,
+  /// Whether new followers need to be approved by a mod.
+  pub requires_follow_approval: Option<bool>,
+  /// Which kinds of posts are allowed in the community.
+  pub post_type_restriction: Option<PostTypeRestriction>,
+  /// Whether all posts in the community are marked as NSFW.
+  pub nsfw_only: Option<bool>,
+  /// Minimum number of seconds between posts of a user, 0 if disabled.
+  pub post_slow_mode_seconds: Option<i32>,
+  /// Minimum number of seconds between comments of a user, 0 if disabled.
+  pub comment_slow_mode_seconds: Option<i32>,
+  /// Whether content without a language is allowed in the community.
+  pub allow_undetermined_language: Option<bool>,
+  /// Number of strikes after which a user is temporarily banned, 0 if disabled.
+  pub strike_ban_threshold: Option<i32>,
+  /// How many days a ban for reaching the strike threshold lasts.
+  pub strike_ban_days: Option<i32>,
+  /// Minimum age of accounts which post or comment in the community, 0 if disabled.
+  pub min_account_age_days: Option<i32>,
+  /// Minimum site-wide karma of users who post or comment in the community, 0 if disabled.
+  pub min_site_karma: Option<i32>,
+  /// Minimum karma within the community of users who post or comment in it, 0 if disabled.
+  pub min_community_karma: Option<i32>,
+  /// Whether the mods are notified when the community is mentioned in a post or comment.
+  pub notify_mods_on_mention: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
+  /// The categories of the community, up to three.
+  pub category_ids: Option<Vec<CategoryId>>,
   pub auth: Sensitive<String>,
 }
 
@@ -68,6 +133,47 @@ pub struct CommunityResponse {
   pub discussion_languages: Vec<LanguageId>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response of an action done to a community application.
+pub struct CommunityApplicationResponse {
+  pub community_application: CommunityApplicationView,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Lists community applications. Admins see all of them, other users only their own.
+pub struct ListCommunityApplications {
+  /// Only shows the applications which weren't approved or denied yet.
+  pub unread_only: Option<bool>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The list of community applications.
+pub struct ListCommunityApplicationsResponse {
+  pub community_applications: Vec<CommunityApplicationView>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Approves a community application, which creates the community. Only admins can do this.
+pub struct ApproveCommunityApplication {
+  pub id: CommunityApplicationId,
+  pub approve: bool,
+  pub deny_reason: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -77,6 +183,8 @@ pub struct ListCommunities {
   pub type_: Option<ListingType>,
   pub sort: Option<SortType>,
   pub show_nsfw: Option<bool>,
+  /// Only list communities in this category.
+  pub category_id: Option<CategoryId>,
   pub page: Option<i64>,
   pub limit: Option<i64>,
   pub auth: Option<Sensitive<String>>,
@@ -102,6 +210,8 @@ pub struct BanFromCommunity {
   pub remove_data: Option<bool>,
   pub reason: Option<String>,
   pub expires: Option<i64>,
+  /// Record a strike against the user in the community.
+  pub strike: Option<bool>,
   pub auth: Sensitive<String>,
 }
 
@@ -152,7 +262,35 @@ pub struct EditCommunity {
   pub nsfw: Option<bool>,
   /// Whether to restrict posting only to moderators.
   pub posting_restricted_to_mods: Option<bool>,
+  /// Whether to allow downvotes. Has no effect if downvotes are disabled for the whole site.
+  pub enable_downvotes: Option<bool>,
+  /// Whether new followers need to be approved by a mod.
+  pub requires_follow_approval: Option<bool>,
+  /// Which kinds of posts are allowed in the community.
+  pub post_type_restriction: Option<PostTypeRestriction>,
+  /// Whether all posts in the community are marked as NSFW.
+  pub nsfw_only: Option<bool>,
+  /// Minimum number of seconds between posts of a user, 0 if disabled.
+  pub post_slow_mode_seconds: Option<i32>,
+  /// Minimum number of seconds between comments of a user, 0 if disabled.
+  pub comment_slow_mode_seconds: Option<i32>,
+  /// Whether content without a language is allowed in the community.
+  pub allow_undetermined_language: Option<bool>,
+  /// Number of strikes after which a user is temporarily banned, 0 if disabled.
+  pub strike_ban_threshold: Option<i32>,
+  /// How many days a ban for reaching the strike threshold lasts.
+  pub strike_ban_days: Option<i32>,
+  /// Minimum age of accounts which post or comment in the community, 0 if disabled.
+  pub min_account_age_days: Option<i32>,
+  /// Minimum site-wide karma of users who post or comment in the community, 0 if disabled.
+  pub min_site_karma: Option<i32>,
+  /// Minimum karma within the community of users who post or comment in it, 0 if disabled.
+  pub min_community_karma: Option<i32>,
+  /// Whether the mods are notified when the community is mentioned in a post or comment.
+  pub notify_mods_on_mention: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
+  /// The categories of the community, up to three.
+  pub category_ids: Option<Vec<CategoryId>>,
   pub auth: Sensitive<String>,
 }
 
@@ -169,6 +307,17 @@ pub struct HideCommunity {
   pub auth: Sensitive<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the communities which were hidden by admins. Only for admins.
+pub struct ListHiddenCommunities {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -203,6 +352,166 @@ pub struct FollowCommunity {
   pub auth: Sensitive<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the follow requests of a community which are waiting for approval.
+pub struct ListCommunityPendingFollows {
+  pub community_id: CommunityId,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The pending follow requests response.
+pub struct ListCommunityPendingFollowsResponse {
+  pub items: Vec<CommunityFollowerView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Approve or deny a pending follow request.
+pub struct ApproveCommunityPendingFollower {
+  pub community_id: CommunityId,
+  pub follower_id: PersonId,
+  pub approve: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for approving or denying a follow request.
+pub struct ApproveCommunityPendingFollowerResponse {
+  pub person_view: PersonView,
+  pub approved: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get notified about new posts in a community you follow. At most one notification is sent per
+/// interval.
+pub struct SetCommunityNotification {
+  pub community_id: CommunityId,
+  pub enabled: bool,
+  pub min_interval_minutes: Option<i32>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The new post notification settings for a community. None if they are disabled.
+pub struct CommunityNotificationResponse {
+  pub community_notification: Option<CommunityNotification>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Hide a community from your feeds until `until` (a unix timestamp), without unsubscribing.
+/// Without `until`, an existing snooze is removed.
+pub struct SnoozeCommunity {
+  pub community_id: CommunityId,
+  pub until: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The snooze of a community. None if it isn't snoozed.
+pub struct SnoozeCommunityResponse {
+  pub community_snooze: Option<CommunitySnooze>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Start generating an archive of the community, in the format of
+/// [crate::community_archive::CommunityArchive]. Only for mods and admins.
+///
+/// If an export of the community is already being generated, that one is returned instead.
+pub struct ExportCommunity {
+  pub community_id: CommunityId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get the status of a community export, or download the archive once it is finished.
+pub struct GetCommunityExport {
+  pub export_id: CommunityExportId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A community export response.
+pub struct CommunityExportResponse {
+  pub community_export: CommunityExport,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create a new local community from an archive, either one exported by lemmy or a reddit archive.
+/// The posts and comments are imported in the background. Only for admins.
+///
+/// If the last import into the community with this name was interrupted, importing the archive
+/// again continues it. Posts and comments which were already imported are never imported twice.
+pub struct ImportCommunity {
+  pub name: String,
+  /// Defaults to the title in the archive.
+  pub title: Option<String>,
+  pub archive: Option<CommunityArchive>,
+  pub reddit_archive: Option<RedditArchive>,
+  /// Local accounts which posts and comments of the given authors are attributed to. Other
+  /// authors get placeholder accounts, unless they already have a local account.
+  pub account_mapping: Option<Vec<ImportAccountMapping>>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Attribute content of an author in an archive to a local account.
+pub struct ImportAccountMapping {
+  /// The actor id of the author, or the username for reddit archives.
+  pub original: String,
+  pub person_id: PersonId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get the progress of a community import.
+pub struct GetCommunityImport {
+  pub import_id: CommunityImportId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A community import response.
+pub struct CommunityImportResponse {
+  pub community_import: CommunityImport,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -232,3 +541,143 @@ pub struct TransferCommunity {
   pub person_id: PersonId,
   pub auth: Sensitive<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Accept or decline a pending community transfer. Only the new owner can do this.
+pub struct AcceptCommunityTransfer {
+  pub community_id: CommunityId,
+  pub accept: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Add a word filter to a community. Only mods can do this.
+pub struct CreateCommunityWordFilter {
+  pub community_id: CommunityId,
+  /// A case insensitive regex.
+  pub pattern: String,
+  pub action: WordFilterAction,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a community word filter.
+pub struct CommunityWordFilterResponse {
+  pub word_filter: CommunityWordFilter,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a community word filter.
+pub struct DeleteCommunityWordFilter {
+  pub id: CommunityWordFilterId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for deleting a community word filter.
+pub struct DeleteCommunityWordFilterResponse {
+  pub id: CommunityWordFilterId,
+  pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the word filters of a community.
+pub struct ListCommunityWordFilters {
+  pub community_id: CommunityId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The word filters of a community.
+pub struct ListCommunityWordFiltersResponse {
+  pub word_filters: Vec<CommunityWordFilter>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Add a rule to a community.
+pub struct CreateCommunityRule {
+  pub community_id: CommunityId,
+  pub title: String,
+  pub description: Option<String>,
+  /// Defaults to after the existing rules.
+  pub position: Option<i32>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Edit a community rule.
+pub struct EditCommunityRule {
+  pub id: CommunityRuleId,
+  pub title: String,
+  pub description: Option<String>,
+  /// Defaults to the current position.
+  pub position: Option<i32>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a community rule.
+pub struct CommunityRuleResponse {
+  pub rule: CommunityRule,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a community rule.
+pub struct DeleteCommunityRule {
+  pub id: CommunityRuleId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for deleting a community rule.
+pub struct DeleteCommunityRuleResponse {
+  pub id: CommunityRuleId,
+  pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the strikes of a user in a community (only doable by mods).
+pub struct ListCommunityStrikes {
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The strikes of a user in a community, newest first.
+pub struct ListCommunityStrikesResponse {
+  pub strikes: Vec<CommunityStrike>,
+  /// How many more strikes lead to a temporary ban, if the community has a threshold.
+  pub strikes_until_ban: Option<i64>,
+}
@@ -0,0 +1,198 @@
+//! The format of community archives, which mods and admins can export for backups or to move a
+//! community to another instance. Archives are JSON documents of a [CommunityArchive].
+//!
+//! People are referenced by their actor id, posts and comments by their activitypub id, so that
+//! they can be matched up on any instance. Timestamps are UTC without timezone.
+
+use lemmy_db_schema::newtypes::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+/// Version of the archive format. It is increased on incompatible changes.
+pub const COMMUNITY_ARCHIVE_VERSION: i32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Everything in a community at the time of the export.
+pub struct CommunityArchive {
+  /// The [COMMUNITY_ARCHIVE_VERSION] of the format.
+  pub version: i32,
+  pub exported_at: chrono::NaiveDateTime,
+  pub community: ArchivedCommunity,
+  /// Ordered by their creation.
+  pub posts: Vec<ArchivedPost>,
+  /// Ordered by their creation, so that parents come before their replies.
+  pub comments: Vec<ArchivedComment>,
+  /// Newest first.
+  pub modlog: Vec<ArchivedModlogEntry>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The settings of the community.
+pub struct ArchivedCommunity {
+  pub actor_id: DbUrl,
+  pub name: String,
+  pub title: String,
+  pub description: Option<String>,
+  pub icon: Option<DbUrl>,
+  pub banner: Option<DbUrl>,
+  pub nsfw: bool,
+  pub posting_restricted_to_mods: bool,
+  pub published: chrono::NaiveDateTime,
+  /// Actor ids of the moderators, the top moderator first.
+  pub moderators: Vec<DbUrl>,
+  pub rules: Vec<ArchivedCommunityRule>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A community rule, in the order in which the rules are shown.
+pub struct ArchivedCommunityRule {
+  pub title: String,
+  pub description: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A post, including removed and deleted ones. Their name, url and body are blanked out.
+pub struct ArchivedPost {
+  pub ap_id: DbUrl,
+  /// Actor id of the creator.
+  pub creator: DbUrl,
+  pub name: String,
+  pub url: Option<DbUrl>,
+  pub body: Option<String>,
+  pub nsfw: bool,
+  pub locked: bool,
+  /// Whether the post is featured in the community.
+  pub featured: bool,
+  pub removed: bool,
+  pub deleted: bool,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A comment, including removed and deleted ones. Their content is blanked out.
+pub struct ArchivedComment {
+  pub ap_id: DbUrl,
+  /// Activitypub id of the post which the comment belongs to.
+  pub post: DbUrl,
+  /// Activitypub id of the comment which this replies to. Not set for top level comments.
+  pub parent: Option<DbUrl>,
+  /// Actor id of the creator.
+  pub creator: DbUrl,
+  pub content: String,
+  /// Whether the comment is distinguished by a moderator.
+  pub distinguished: bool,
+  pub removed: bool,
+  pub deleted: bool,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A moderator action in the community.
+pub struct ArchivedModlogEntry {
+  pub action: ArchivedModlogAction,
+  /// Actor id of the moderator, if it is known.
+  pub moderator: Option<DbUrl>,
+  /// Activitypub id of the post or comment, or actor id of the person the action applies to.
+  pub target: DbUrl,
+  pub reason: Option<String>,
+  /// When a ban ends.
+  pub expires: Option<chrono::NaiveDateTime>,
+  pub when_: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+#[serde(rename_all = "snake_case")]
+/// The kind of a moderator action.
+pub enum ArchivedModlogAction {
+  RemovePost,
+  RestorePost,
+  LockPost,
+  UnlockPost,
+  FeaturePost,
+  UnfeaturePost,
+  RemoveComment,
+  RestoreComment,
+  BanFromCommunity,
+  UnbanFromCommunity,
+  AddModerator,
+  RemoveModerator,
+  TransferCommunity,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Posts and comments of a subreddit, in the shape of reddit's api and data dumps. Unknown fields
+/// are ignored, so the objects can be passed through unchanged.
+pub struct RedditArchive {
+  pub submissions: Vec<RedditSubmission>,
+  pub comments: Vec<RedditComment>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A reddit post.
+pub struct RedditSubmission {
+  /// The base36 id, without `t3_` prefix.
+  pub id: String,
+  pub author: String,
+  pub title: String,
+  pub selftext: Option<String>,
+  pub url: Option<String>,
+  #[serde(default)]
+  pub is_self: bool,
+  #[serde(default)]
+  pub over_18: bool,
+  #[serde(default)]
+  pub locked: bool,
+  #[serde(default)]
+  pub stickied: bool,
+  /// Unix timestamp in seconds.
+  pub created_utc: f64,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A reddit comment.
+pub struct RedditComment {
+  /// The base36 id, without `t1_` prefix.
+  pub id: String,
+  pub author: String,
+  pub body: String,
+  /// The post, with `t3_` prefix.
+  pub link_id: String,
+  /// The post for top level comments, with `t3_` prefix, otherwise the parent comment with `t1_`
+  /// prefix.
+  pub parent_id: String,
+  /// Set to `moderator` for distinguished comments.
+  pub distinguished: Option<String>,
+  /// Unix timestamp in seconds.
+  pub created_utc: f64,
+}
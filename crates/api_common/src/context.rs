@@ -7,11 +7,16 @@ use lemmy_utils::{
   settings::{structs::Settings, SETTINGS},
 };
 use reqwest_middleware::ClientWithMiddleware;
-use std::sync::Arc;
+use std::sync::{
+  atomic::{AtomicUsize, Ordering},
+  Arc,
+};
 
 #[derive(Clone)]
 pub struct LemmyContext {
   pool: ActualDbPool,
+  read_replicas: Arc<Vec<ActualDbPool>>,
+  next_read_replica: Arc<AtomicUsize>,
   client: Arc<ClientWithMiddleware>,
   secret: Arc<Secret>,
   rate_limit_cell: RateLimitCell,
@@ -26,14 +31,38 @@ impl LemmyContext {
   ) -> LemmyContext {
     LemmyContext {
       pool,
+      read_replicas: Arc::new(Vec::new()),
+      next_read_replica: Arc::new(AtomicUsize::new(0)),
       client: Arc::new(client),
       secret: Arc::new(secret),
       rate_limit_cell,
     }
   }
+  /// Uses the given pools for [LemmyContext::read_pool].
+  pub fn with_read_replicas(mut self, read_replicas: Vec<ActualDbPool>) -> LemmyContext {
+    self.read_replicas = Arc::new(read_replicas);
+    self
+  }
+  /// Copy of this context which uses a different primary pool, eg for a separate workload.
+  pub fn with_pool(&self, pool: ActualDbPool) -> LemmyContext {
+    LemmyContext {
+      pool,
+      ..self.clone()
+    }
+  }
   pub fn pool(&self) -> DbPool<'_> {
     DbPool::Pool(&self.pool)
   }
+  /// Pool for read-only listing queries. Picks the read replicas in turn, or the primary database
+  /// if none are configured. Data read from here may lag slightly behind, so anything which needs
+  /// to see its own writes must use [LemmyContext::pool] instead.
+  pub fn read_pool(&self) -> DbPool<'_> {
+    if self.read_replicas.is_empty() {
+      return self.pool();
+    }
+    let index = self.next_read_replica.fetch_add(1, Ordering::Relaxed) % self.read_replicas.len();
+    DbPool::Pool(&self.read_replicas[index])
+  }
   pub fn inner_pool(&self) -> &ActualDbPool {
     &self.pool
   }
@@ -0,0 +1,132 @@
+use crate::context::LemmyContext;
+use lemmy_db_schema::source::disposable_email_domain::DisposableEmailDomain;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorType, LemmyResult},
+  settings::structs::DisposableEmailConfig,
+};
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use tracing::info;
+
+/// Disposable email domains which ship with Lemmy, so that detection works before the configured
+/// list was fetched.
+static BUNDLED_DOMAINS: Lazy<HashSet<String>> = Lazy::new(|| {
+  parse_domain_list(include_str!("../assets/disposable_email_domains.txt"))
+    .into_iter()
+    .collect()
+});
+
+/// Parses a list with one domain per line. Empty lines and comments starting with `#` are skipped.
+fn parse_domain_list(text: &str) -> Vec<String> {
+  text
+    .lines()
+    .map(str::trim)
+    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+    .map(str::to_lowercase)
+    .collect()
+}
+
+/// The domain of an email address, followed by its parent domains without the top level domain.
+/// For `user@mail.example.com` these are `mail.example.com` and `example.com`.
+fn email_domain_suffixes(email: &str) -> Vec<String> {
+  let Some((_, domain)) = email.rsplit_once('@') else {
+    return vec![];
+  };
+  let domain = domain.trim().to_lowercase();
+  let mut suffixes = vec![];
+  let mut rest = domain.as_str();
+  loop {
+    suffixes.push(rest.to_string());
+    match rest.split_once('.') {
+      Some((_, parent)) if parent.contains('.') => rest = parent,
+      _ => break,
+    }
+  }
+  suffixes
+}
+
+/// Checks whether the email address of a new user belongs to a disposable email provider, unless
+/// admins made an exception for its domain. Depending on the config such signups are refused, or
+/// the reason for a review of the registration is returned.
+pub async fn check_disposable_email(
+  email: Option<&str>,
+  context: &LemmyContext,
+) -> LemmyResult<Option<String>> {
+  let (Some(config), Some(email)) = (&context.settings().disposable_email, email) else {
+    return Ok(None);
+  };
+  let domains = email_domain_suffixes(email);
+  let stored = DisposableEmailDomain::read_for_domains(&mut context.pool(), &domains).await?;
+  if stored.iter().any(|d| d.exception) {
+    return Ok(None);
+  }
+  let is_disposable = !stored.is_empty() || domains.iter().any(|d| BUNDLED_DOMAINS.contains(d));
+  if !is_disposable {
+    Ok(None)
+  } else if config.reject {
+    Err(LemmyErrorType::DisposableEmail)?
+  } else {
+    Ok(Some(
+      "Signed up with a disposable email address".to_string(),
+    ))
+  }
+}
+
+/// Fetches the configured list of disposable email domains and stores it, in addition to the
+/// bundled list. Returns the number of fetched domains.
+pub async fn sync_disposable_email_domains(
+  config: &DisposableEmailConfig,
+  context: &LemmyContext,
+) -> LemmyResult<usize> {
+  let Some(url) = &config.list_url else {
+    return Ok(0);
+  };
+  info!("Fetching disposable email domains: {url}");
+  let response = context
+    .client()
+    .get(url.as_str())
+    .send()
+    .await?
+    .error_for_status()?;
+  let text = response.text().await.map_err(LemmyError::from)?;
+  let domains = parse_domain_list(&text);
+  if domains.is_empty() {
+    // Most likely the list moved or its format changed, so keep the previous one
+    Err(LemmyErrorType::EmptyBlocklist)?
+  }
+  let count = domains.len();
+  DisposableEmailDomain::replace_fetched(&mut context.pool(), domains).await?;
+  Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+
+  #[test]
+  fn test_parse_domain_list() {
+    let list = "# comment\nTrash.example\n\n  temp.example \n";
+    assert_eq!(
+      vec!["trash.example".to_string(), "temp.example".to_string()],
+      parse_domain_list(list)
+    );
+    assert!(BUNDLED_DOMAINS.contains("mailinator.com"));
+    assert!(!BUNDLED_DOMAINS.iter().any(|d| d.starts_with('#')));
+  }
+
+  #[test]
+  fn test_email_domain_suffixes() {
+    assert_eq!(
+      vec!["mail.example.com".to_string(), "example.com".to_string()],
+      email_domain_suffixes("User@Mail.Example.com")
+    );
+    assert_eq!(
+      vec!["example.com".to_string()],
+      email_domain_suffixes("user@example.com")
+    );
+    assert!(email_domain_suffixes("example.com").is_empty());
+  }
+}
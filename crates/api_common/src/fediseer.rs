@@ -0,0 +1,131 @@
+use crate::{context::LemmyContext, utils::sync_blocklist_subscription};
+use lemmy_db_schema::{
+  newtypes::InstanceId,
+  source::{
+    blocklist_subscription::{BlocklistSubscription, BlocklistSubscriptionForm},
+    instance::Instance,
+    instance_trust::{InstanceTrust, InstanceTrustForm},
+  },
+  traits::Crud,
+};
+use lemmy_utils::{error::LemmyResult, settings::structs::FediseerConfig};
+use reqwest_middleware::ClientWithMiddleware;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::info;
+use url::Url;
+
+#[derive(Deserialize)]
+struct FediseerInstances {
+  instances: Vec<FediseerInstance>,
+}
+
+#[derive(Deserialize)]
+struct FediseerInstance {
+  domain: String,
+  guarantor: Option<String>,
+  #[serde(default)]
+  endorsements: i32,
+  censure_reasons: Option<Vec<String>>,
+  #[serde(default)]
+  censure_count: i32,
+}
+
+/// Fetches guarantees, endorsements and the censures given by trusted instances from Fediseer,
+/// and stores them for all known instances. Returns the number of instances with trust signals.
+pub async fn sync_fediseer(config: &FediseerConfig, context: &LemmyContext) -> LemmyResult<usize> {
+  let guaranteed = fetch_instances(context.client(), &config.url.join("api/v1/whitelist")?).await?;
+  let censured = match censures_url(config)? {
+    Some(url) => fetch_instances(context.client(), &url).await?,
+    None => vec![],
+  };
+
+  let known: HashMap<String, InstanceId> = Instance::read_all(&mut context.pool())
+    .await?
+    .into_iter()
+    .map(|i| (i.domain, i.id))
+    .collect();
+  let mut forms: HashMap<InstanceId, InstanceTrustForm> = HashMap::new();
+  for i in guaranteed.into_iter().chain(censured) {
+    let Some(instance_id) = known.get(&i.domain) else {
+      continue;
+    };
+    let form = forms
+      .entry(*instance_id)
+      .or_insert_with(|| InstanceTrustForm {
+        instance_id: *instance_id,
+        ..Default::default()
+      });
+    // Instances which are only in the censure list don't have a guarantor
+    form.guarantor = i.guarantor.or(form.guarantor.take());
+    form.endorsements = form.endorsements.max(i.endorsements);
+    form.censures = form.censures.max(i.censure_count);
+    if let Some(reasons) = i.censure_reasons.filter(|r| !r.is_empty()) {
+      form.censure_reasons = Some(reasons.join(", "));
+    }
+  }
+  let count = forms.len();
+  InstanceTrust::replace_all(&mut context.pool(), forms.into_values().collect()).await?;
+
+  sync_censure_subscription(config, context).await?;
+  Ok(count)
+}
+
+async fn fetch_instances(
+  client: &ClientWithMiddleware,
+  url: &Url,
+) -> LemmyResult<Vec<FediseerInstance>> {
+  info!("Fetching trust signals from Fediseer: {}", url);
+  let res: FediseerInstances = client
+    .get(url.as_str())
+    .send()
+    .await?
+    .error_for_status()?
+    .json()
+    .await?;
+  Ok(res.instances)
+}
+
+/// Lists the instances which were censured by any of the trusted instances
+fn censures_url(config: &FediseerConfig) -> LemmyResult<Option<Url>> {
+  let trusted: Vec<_> = config
+    .trusted_instances
+    .iter()
+    .map(|i| i.trim())
+    .filter(|i| !i.is_empty())
+    .collect();
+  if trusted.is_empty() {
+    return Ok(None);
+  }
+  let path = format!("api/v1/censures_given/{}", trusted.join(","));
+  Ok(Some(config.url.join(&path)?))
+}
+
+/// Censured instances are restricted through a blocklist subscription, so that they show up with
+/// their source, and admins can pin or remove single entries. Subscriptions for a previous list of
+/// trusted instances are removed.
+async fn sync_censure_subscription(
+  config: &FediseerConfig,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let url = if config.restrict_censured {
+    censures_url(config)?.map(|u| u.to_string())
+  } else {
+    None
+  };
+  let prefix = config.url.join("api/v1/censures_given/")?;
+  let mut subscribed = false;
+  for subscription in BlocklistSubscription::list(&mut context.pool()).await? {
+    if Some(&subscription.url) == url.as_ref() {
+      subscribed = true;
+    } else if subscription.url.starts_with(prefix.as_str()) {
+      BlocklistSubscription::delete(&mut context.pool(), subscription.id).await?;
+    }
+  }
+  if let (Some(url), false) = (url, subscribed) {
+    let form = BlocklistSubscriptionForm { url };
+    let subscription = BlocklistSubscription::create(&mut context.pool(), &form).await?;
+    sync_blocklist_subscription(&subscription, context).await?;
+  }
+  Ok(())
+}
@@ -0,0 +1,142 @@
+use crate::utils::check_community_language;
+use lemmy_db_schema::{
+  newtypes::LanguageId,
+  source::{community::Community, language::Language},
+  utils::DbPool,
+};
+use lemmy_utils::error::LemmyResult;
+use whatlang::Lang;
+
+/// Shorter texts are too often detected wrong
+const MIN_DETECTION_LENGTH: usize = 20;
+
+/// Detects the language of a post or comment which was submitted without one, so that it shows up
+/// in language filtered listings. Returns `None` if the detection isn't reliable, or if the
+/// language isn't allowed in the community.
+pub async fn detect_language(
+  texts: &[&str],
+  community: &Community,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<Option<LanguageId>> {
+  let Some(code) = detect_language_code(&texts.join("\n")) else {
+    return Ok(None);
+  };
+  let Some(language_id) = Language::read_id_from_code(pool, Some(code)).await? else {
+    return Ok(None);
+  };
+  let allowed = check_community_language(community, Some(language_id), pool)
+    .await
+    .is_ok();
+  Ok(allowed.then_some(language_id))
+}
+
+/// The ISO 639-1 code of the language, which is used in the language table
+fn detect_language_code(text: &str) -> Option<&'static str> {
+  if text.chars().count() < MIN_DETECTION_LENGTH {
+    return None;
+  }
+  let info = whatlang::detect(text).filter(whatlang::Info::is_reliable)?;
+  Some(iso_639_1(info.lang()))
+}
+
+fn iso_639_1(lang: Lang) -> &'static str {
+  match lang {
+    Lang::Epo => "eo",
+    Lang::Eng => "en",
+    Lang::Rus => "ru",
+    Lang::Cmn => "zh",
+    Lang::Spa => "es",
+    Lang::Por => "pt",
+    Lang::Ita => "it",
+    Lang::Ben => "bn",
+    Lang::Fra => "fr",
+    Lang::Deu => "de",
+    Lang::Ukr => "uk",
+    Lang::Kat => "ka",
+    Lang::Ara => "ar",
+    Lang::Hin => "hi",
+    Lang::Jpn => "ja",
+    Lang::Heb => "he",
+    Lang::Yid => "yi",
+    Lang::Pol => "pl",
+    Lang::Amh => "am",
+    Lang::Jav => "jv",
+    Lang::Kor => "ko",
+    Lang::Nob => "nb",
+    Lang::Dan => "da",
+    Lang::Swe => "sv",
+    Lang::Fin => "fi",
+    Lang::Tur => "tr",
+    Lang::Nld => "nl",
+    Lang::Hun => "hu",
+    Lang::Ces => "cs",
+    Lang::Ell => "el",
+    Lang::Bul => "bg",
+    Lang::Bel => "be",
+    Lang::Mar => "mr",
+    Lang::Kan => "kn",
+    Lang::Ron => "ro",
+    Lang::Slv => "sl",
+    Lang::Hrv => "hr",
+    Lang::Srp => "sr",
+    Lang::Mkd => "mk",
+    Lang::Lit => "lt",
+    Lang::Lav => "lv",
+    Lang::Est => "et",
+    Lang::Tam => "ta",
+    Lang::Vie => "vi",
+    Lang::Urd => "ur",
+    Lang::Tha => "th",
+    Lang::Guj => "gu",
+    Lang::Uzb => "uz",
+    Lang::Pan => "pa",
+    Lang::Aze => "az",
+    Lang::Ind => "id",
+    Lang::Tel => "te",
+    Lang::Pes => "fa",
+    Lang::Mal => "ml",
+    Lang::Ori => "or",
+    Lang::Mya => "my",
+    Lang::Nep => "ne",
+    Lang::Sin => "si",
+    Lang::Khm => "km",
+    Lang::Tuk => "tk",
+    Lang::Aka => "ak",
+    Lang::Zul => "zu",
+    Lang::Sna => "sn",
+    Lang::Afr => "af",
+    Lang::Lat => "la",
+    Lang::Slk => "sk",
+    Lang::Cat => "ca",
+    Lang::Tgl => "tl",
+    Lang::Hye => "hy",
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+
+  #[test]
+  fn test_detect_language_code() {
+    assert_eq!(
+      Some("en"),
+      detect_language_code("The quick brown fox jumps over the lazy dog, again and again.")
+    );
+    assert_eq!(
+      Some("de"),
+      detect_language_code("Ich habe heute keine Zeit, weil ich noch arbeiten muss.")
+    );
+    assert_eq!(None, detect_language_code("Hello there"));
+  }
+
+  #[test]
+  fn test_iso_639_1() {
+    for lang in Lang::all() {
+      assert_eq!(2, iso_639_1(*lang).len());
+    }
+  }
+}
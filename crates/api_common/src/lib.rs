@@ -1,19 +1,41 @@
+pub mod announcement;
+pub mod blocked_url;
+pub mod blocklist_subscription;
+#[cfg(feature = "full")]
+pub mod breached_password;
 #[cfg(feature = "full")]
 pub mod build_response;
+#[cfg(feature = "full")]
+pub mod captcha;
+pub mod category;
 pub mod comment;
 pub mod community;
+pub mod community_archive;
 #[cfg(feature = "full")]
 pub mod context;
 pub mod custom_emoji;
+#[cfg(feature = "full")]
+pub mod disposable_email;
+#[cfg(feature = "full")]
+pub mod fediseer;
+#[cfg(feature = "full")]
+pub mod language_detection;
+pub mod markdown;
 pub mod person;
 pub mod post;
 pub mod private_message;
+pub mod report_reason;
 #[cfg(feature = "full")]
 pub mod request;
+pub mod scheduled_job;
 #[cfg(feature = "full")]
 pub mod send_activity;
 pub mod sensitive;
 pub mod site;
+pub mod slur_filter;
+#[cfg(feature = "full")]
+pub mod spam;
+pub mod tagline;
 #[cfg(feature = "full")]
 pub mod utils;
 
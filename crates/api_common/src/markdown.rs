@@ -0,0 +1,21 @@
+use crate::sensitive::Sensitive;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Render markdown the same way as it is sent to other instances.
+pub struct PreviewMarkdown {
+  pub markdown: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The rendered html of a markdown preview.
+pub struct PreviewMarkdownResponse {
+  pub html: String,
+}
@@ -1,11 +1,31 @@
-use crate::sensitive::Sensitive;
+use crate::{report_reason::ReportReasonCount, sensitive::Sensitive};
 use lemmy_db_schema::{
-  newtypes::{CommentReplyId, CommunityId, LanguageId, PersonId, PersonMentionId},
+  aggregates::structs::CommunityPersonAggregates,
+  newtypes::{
+    CommentReplyId,
+    CommunityId,
+    InviteId,
+    LanguageId,
+    LoginSessionId,
+    PersonId,
+    PersonMentionId,
+    PostNotificationId,
+  },
+  source::{
+    community::Community,
+    community_notification::PostNotification,
+    local_image::LocalImage,
+    login_session::LoginSession,
+    person::Person,
+    person_note::PersonNote,
+    post::Post,
+  },
+  AdminRole,
   CommentSortType,
   ListingType,
   SortType,
 };
-use lemmy_db_views::structs::{CommentView, PostView};
+use lemmy_db_views::structs::{CommentView, InviteUseView, InviteView, PostView};
 use lemmy_db_views_actor::structs::{
   CommentReplyView,
   CommunityModeratorView,
@@ -49,6 +69,8 @@ pub struct Register {
   pub honeypot: Option<String>,
   /// An answer is mandatory if require application is enabled on the server
   pub answer: Option<String>,
+  /// An invite code is mandatory if the server is in invite mode
+  pub invite_code: Option<String>,
 }
 
 #[skip_serializing_none]
@@ -66,8 +88,25 @@ pub struct GetCaptcha {
 #[cfg_attr(feature = "full", ts(export))]
 /// A wrapper for the captcha response.
 pub struct GetCaptchaResponse {
-  /// Will be None if captchas are disabled.
+  /// Will be None if captchas are disabled, or a hosted captcha service is used.
   pub ok: Option<CaptchaResponse>,
+  /// Set if a hosted captcha service is used instead of the built in captcha. The token returned
+  /// by its widget is sent as `captcha_answer`.
+  pub hosted: Option<HostedCaptcha>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A hosted captcha service.
+pub struct HostedCaptcha {
+  /// One of `hcaptcha`, `mcaptcha` or `turnstile`
+  pub provider: String,
+  /// The public site key for the captcha widget
+  pub site_key: Option<String>,
+  /// Address of the mCaptcha instance
+  pub mcaptcha_url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -137,6 +176,22 @@ pub struct SaveUserSettings {
   pub open_links_in_new_tab: Option<bool>,
   /// Enable infinite scroll
   pub infinite_scroll_enabled: Option<bool>,
+  /// Federate your votes through a pseudonymous actor instead of your account.
+  pub anonymize_outgoing_votes: Option<bool>,
+  /// Send notifications for replies to your comments.
+  pub notify_comment_replies: Option<bool>,
+  /// Send notifications for comments on your posts.
+  pub notify_post_replies: Option<bool>,
+  /// Send notifications when you are mentioned.
+  pub notify_mentions: Option<bool>,
+  /// Send notifications for private messages.
+  pub notify_private_messages: Option<bool>,
+  /// Subscribe to the replies below your own comments.
+  pub auto_subscribe_threads: Option<bool>,
+  /// Send a regular email summary of your mod queue.
+  pub email_mod_queue_digest: Option<bool>,
+  /// Send an email when someone logs in from a new device or network.
+  pub email_login_alerts: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -150,6 +205,23 @@ pub struct ChangePassword {
   pub auth: Sensitive<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Changes your username. The old name keeps pointing to your account.
+pub struct ChangeUsername {
+  pub new_username: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for a username change.
+pub struct ChangeUsernameResponse {
+  pub person_view: PersonView,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -183,6 +255,7 @@ pub struct GetPersonDetails {
   pub auth: Option<Sensitive<String>>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -192,6 +265,8 @@ pub struct GetPersonDetailsResponse {
   pub comments: Vec<CommentView>,
   pub posts: Vec<PostView>,
   pub moderates: Vec<CommunityModeratorView>,
+  /// The person's scores within the requested community. Only returned to mods of that community.
+  pub community_counts: Option<CommunityPersonAggregates>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -202,6 +277,7 @@ pub struct MarkAllAsRead {
   pub auth: Sensitive<String>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -209,6 +285,8 @@ pub struct MarkAllAsRead {
 pub struct AddAdmin {
   pub person_id: PersonId,
   pub added: bool,
+  /// What the admin can do. Defaults to a full admin.
+  pub role: Option<AdminRole>,
   pub auth: Sensitive<String>,
 }
 
@@ -261,13 +339,77 @@ pub struct BanPersonResponse {
   pub banned: bool,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Merges a duplicate local account into another one. Content, follows and votes are moved to the
+/// target, and the source account is disabled.
+///
+/// The merge is only done locally. It isn't federated, so other instances keep showing the moved
+/// content and votes under the source account.
+pub struct MergePersons {
+  pub source_person_id: PersonId,
+  pub target_person_id: PersonId,
+  pub reason: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for merging accounts.
+pub struct MergePersonsResponse {
+  pub person_view: PersonView,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Shadowban a person from the site. Their account keeps working, but new content is only
+/// visible to themselves, mods and admins, and isn't federated.
+pub struct ShadowbanPerson {
+  pub person_id: PersonId,
+  pub shadowban: bool,
+  pub reason: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a shadowbanned person.
+pub struct ShadowbanPersonResponse {
+  pub person_view: PersonView,
+  pub shadowbanned: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
-/// Block a person.
+/// Get a list of shadowbanned persons.
+pub struct GetShadowbannedPersons {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The list of shadowbanned persons.
+pub struct ShadowbannedPersonsResponse {
+  pub shadowbanned: Vec<PersonView>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Block a person. With `expires` (a unix timestamp), the block is lifted at that time.
 pub struct BlockPerson {
   pub person_id: PersonId,
   pub block: bool,
+  pub expires: Option<i64>,
   pub auth: Sensitive<String>,
 }
 
@@ -280,6 +422,101 @@ pub struct BlockPersonResponse {
   pub blocked: bool,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Set your private note about another user. Without a note, the existing one is removed.
+pub struct SetPersonNote {
+  pub person_id: PersonId,
+  pub note: Option<String>,
+  /// A hex color like `#ff8800`, to show the note as a colored tag.
+  pub color: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for setting a note about a user.
+pub struct PersonNoteResponse {
+  pub person_note: Option<PersonNote>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Create an invite code. Admins can always do this, other users only if the site allows it.
+pub struct CreateInvite {
+  /// How many users can register with the code, defaults to 1. At most 10 for users who aren't
+  /// admins.
+  pub max_uses: Option<i32>,
+  /// A unix timestamp after which the code can't be used anymore. Users who aren't admins have to
+  /// set it, at most 30 days in the future.
+  pub expires: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for creating or revoking an invite.
+pub struct InviteResponse {
+  pub invite_view: InviteView,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List your invites. Admins can list the invites of all users, or of the given one.
+pub struct ListInvites {
+  pub creator_id: Option<PersonId>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A list of invites.
+pub struct ListInvitesResponse {
+  pub invites: Vec<InviteView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Revoke one of your invites, or any invite as an admin.
+pub struct RevokeInvite {
+  pub invite_id: InviteId,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the users who registered with your invites. Admins can list who was invited by whom for
+/// all users, or for the given inviter.
+pub struct ListInviteUses {
+  pub inviter_id: Option<PersonId>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A list of users who registered with an invite.
+pub struct ListInviteUsesResponse {
+  pub invite_uses: Vec<InviteUseView>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -359,6 +596,103 @@ pub struct CommentReplyResponse {
   pub comment_reply_view: CommentReplyView,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Get your notifications about new posts in communities.
+pub struct GetPostNotifications {
+  pub unread_only: Option<bool>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A notification about a new post, with the post, its community and its creator.
+pub struct PostNotificationView {
+  pub post_notification: PostNotification,
+  pub post: Post,
+  pub community: Community,
+  pub creator: Person,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The new post notifications response.
+pub struct GetPostNotificationsResponse {
+  pub notifications: Vec<PostNotificationView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Mark a new post notification as read.
+pub struct MarkPostNotificationAsRead {
+  pub post_notification_id: PostNotificationId,
+  pub read: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for a new post notification action.
+pub struct PostNotificationResponse {
+  pub post_notification: PostNotification,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// End the login session of this token.
+pub struct Logout {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response of logging out.
+pub struct LogoutResponse {}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the places where you are logged in.
+pub struct ListLogins {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Your login sessions, newest first.
+pub struct ListLoginsResponse {
+  pub logins: Vec<LoginSession>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// End one of your login sessions, eg one from a login alert email which wasn't you.
+pub struct RevokeLogin {
+  pub login_session_id: LoginSessionId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// End all login sessions of a user, eg if their account was compromised. Only for admins.
+pub struct ForceLogout {
+  pub person_id: PersonId,
+  pub auth: Sensitive<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -388,6 +722,31 @@ pub struct PasswordReset {
 /// The response of a password reset.
 pub struct PasswordResetResponse {}
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Requests a single use login link via email, to log in without your password.
+pub struct RequestMagicLink {
+  pub email: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response of a login link request.
+pub struct RequestMagicLinkResponse {}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Logs in with the token from an emailed login link.
+pub struct RedeemMagicLink {
+  pub token: Sensitive<String>,
+  /// Required if the account has two-factor authentication enabled.
+  pub totp_2fa_token: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -418,6 +777,8 @@ pub struct GetReportCountResponse {
   pub comment_reports: i64,
   pub post_reports: i64,
   pub private_message_reports: Option<i64>,
+  /// The number of unresolved reports for each listed reason.
+  pub reason_counts: Vec<ReportReasonCount>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -436,6 +797,7 @@ pub struct GetUnreadCountResponse {
   pub replies: i64,
   pub mentions: i64,
   pub private_messages: i64,
+  pub post_notifications: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug)]
@@ -451,3 +813,22 @@ pub struct VerifyEmail {
 #[cfg_attr(feature = "full", ts(export))]
 /// A response to verifying your email.
 pub struct VerifyEmailResponse {}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the images you have uploaded.
+pub struct ListMedia {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Your uploaded images.
+pub struct ListMediaResponse {
+  pub images: Vec<LocalImage>,
+}
@@ -1,11 +1,20 @@
 use crate::sensitive::Sensitive;
 use lemmy_db_schema::{
-  newtypes::{CommentId, CommunityId, DbUrl, LanguageId, PostId, PostReportId},
+  newtypes::{
+    CommentId,
+    CommunityId,
+    CommunityRuleId,
+    DbUrl,
+    LanguageId,
+    PostId,
+    PostReportId,
+    ReportReasonId,
+  },
   ListingType,
   PostFeatureType,
   SortType,
 };
-use lemmy_db_views::structs::{PostReportView, PostView};
+use lemmy_db_views::structs::{PaginationCursor, PostReportView, PostView, VoteView};
 use lemmy_db_views_actor::structs::{CommunityModeratorView, CommunityView};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -29,6 +38,13 @@ pub struct CreatePost {
   pub honeypot: Option<String>,
   pub nsfw: Option<bool>,
   pub language_id: Option<LanguageId>,
+  /// The length in seconds, if the url links to an audio file. Audio isn't decoded on the server,
+  /// so clients which upload the file should send it.
+  pub audio_duration: Option<i32>,
+  /// The UUID of the captcha item, if captchas are required for posts.
+  pub captcha_uuid: Option<String>,
+  /// Your captcha answer, or the token of a hosted captcha service.
+  pub captcha_answer: Option<String>,
   pub auth: Sensitive<String>,
 }
 
@@ -71,20 +87,27 @@ pub struct GetPosts {
   pub type_: Option<ListingType>,
   pub sort: Option<SortType>,
   pub page: Option<i64>,
+  /// Continue the listing after this cursor. Faster than `page` for deep pages.
+  pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
   pub community_id: Option<CommunityId>,
   pub community_name: Option<String>,
   pub saved_only: Option<bool>,
   pub moderator_view: Option<bool>,
+  /// Only show posts with open reports, in communities you moderate.
+  pub reported_only: Option<bool>,
   pub auth: Option<Sensitive<String>>,
 }
 
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
 /// The post list response.
 pub struct GetPostsResponse {
   pub posts: Vec<PostView>,
+  /// The cursor for the next page, to pass as `page_cursor`.
+  pub next_page: Option<PaginationCursor>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -98,6 +121,39 @@ pub struct CreatePostLike {
   pub auth: Sensitive<String>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// List the votes on a post. Only for mods and admins.
+pub struct ListPostLikes {
+  pub post_id: PostId,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The post likes response.
+pub struct ListPostLikesResponse {
+  pub post_likes: Vec<VoteView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Add or remove an emoji reaction on a post.
+pub struct CreatePostReaction {
+  pub post_id: PostId,
+  /// A unicode emoji, or a custom emoji shortcode like `:blobcat:`.
+  pub emoji: String,
+  /// Set to false to remove the reaction.
+  pub add: bool,
+  pub auth: Sensitive<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -112,6 +168,8 @@ pub struct EditPost {
   pub body: Option<String>,
   pub nsfw: Option<bool>,
   pub language_id: Option<LanguageId>,
+  /// The length in seconds, if the url links to an audio file.
+  pub audio_duration: Option<i32>,
   pub auth: Sensitive<String>,
 }
 
@@ -134,6 +192,8 @@ pub struct RemovePost {
   pub post_id: PostId,
   pub removed: bool,
   pub reason: Option<String>,
+  /// Record a strike against the creator in the community.
+  pub strike: Option<bool>,
   pub auth: Sensitive<String>,
 }
 
@@ -157,6 +217,18 @@ pub struct LockPost {
   pub auth: Sensitive<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Toggle contest mode for a post (hides scores and shuffles comments for non-mods).
+pub struct SetContestMode {
+  pub post_id: PostId,
+  pub enabled: bool,
+  /// How long contest mode lasts, defaults to 24 hours.
+  pub duration_hours: Option<i32>,
+  pub auth: Sensitive<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -178,13 +250,19 @@ pub struct SavePost {
   pub auth: Sensitive<String>,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
 /// Create a post report.
 pub struct CreatePostReport {
   pub post_id: PostId,
-  pub reason: String,
+  /// Free text, which is only required without a listed reason.
+  pub reason: Option<String>,
+  /// One of the reasons from ListReportReasons.
+  pub reason_id: Option<ReportReasonId>,
+  /// The community rule which the content breaks.
+  pub rule_id: Option<CommunityRuleId>,
   pub auth: Sensitive<String>,
 }
 
@@ -218,6 +296,8 @@ pub struct ListPostReports {
   pub unresolved_only: Option<bool>,
   /// if no community is given, it returns reports for all communities moderated by the auth user
   pub community_id: Option<CommunityId>,
+  /// Only shows the reports with this listed reason
+  pub reason_id: Option<ReportReasonId>,
   pub auth: Sensitive<String>,
 }
 
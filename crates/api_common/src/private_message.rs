@@ -1,5 +1,10 @@
 use crate::sensitive::Sensitive;
-use lemmy_db_schema::newtypes::{PersonId, PrivateMessageId, PrivateMessageReportId};
+use lemmy_db_schema::newtypes::{
+  PersonId,
+  PrivateMessageId,
+  PrivateMessageReportId,
+  ReportReasonId,
+};
 use lemmy_db_views::structs::{PrivateMessageReportView, PrivateMessageView};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -75,13 +80,17 @@ pub struct PrivateMessageResponse {
   pub private_message_view: PrivateMessageView,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
 /// Create a report for a private message.
 pub struct CreatePrivateMessageReport {
   pub private_message_id: PrivateMessageId,
-  pub reason: String,
+  /// Free text, which is only required without a listed reason.
+  pub reason: Option<String>,
+  /// One of the reasons from ListReportReasons.
+  pub reason_id: Option<ReportReasonId>,
   pub auth: Sensitive<String>,
 }
 
@@ -114,6 +123,8 @@ pub struct ListPrivateMessageReports {
   pub limit: Option<i64>,
   /// Only shows the unresolved reports
   pub unresolved_only: Option<bool>,
+  /// Only shows the reports with this listed reason
+  pub reason_id: Option<ReportReasonId>,
   pub auth: Sensitive<String>,
 }
 
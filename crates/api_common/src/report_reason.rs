@@ -0,0 +1,73 @@
+use crate::sensitive::Sensitive;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, ReportReasonId},
+  source::report_reason::ReportReason,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Add a reason which can be picked when reporting content. Site wide reasons can only be added by
+/// admins, community reasons by the mods of the community.
+pub struct CreateReportReason {
+  pub community_id: Option<CommunityId>,
+  pub name: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a report reason. Reports which used it are kept, without a listed reason.
+pub struct DeleteReportReason {
+  pub id: ReportReasonId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for deleting a report reason.
+pub struct DeleteReportReasonResponse {
+  pub id: ReportReasonId,
+  pub success: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Lists the site wide report reasons, and those of the community if one is given.
+pub struct ListReportReasons {
+  pub community_id: Option<CommunityId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a report reason.
+pub struct ReportReasonResponse {
+  pub report_reason: ReportReason,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A list of report reasons.
+pub struct ListReportReasonsResponse {
+  pub report_reasons: Vec<ReportReason>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The number of unresolved reports with a listed reason.
+pub struct ReportReasonCount {
+  pub reason_id: ReportReasonId,
+  pub reports: i64,
+}
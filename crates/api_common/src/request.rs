@@ -1,19 +1,37 @@
 use crate::post::SiteMetadata;
 use encoding::{all::encodings, DecoderTrap};
-use lemmy_db_schema::newtypes::DbUrl;
+use lemmy_db_schema::{newtypes::DbUrl, source::blocklist_subscription::BlocklistEntry};
 use lemmy_utils::{
-  error::{LemmyError, LemmyErrorType},
+  error::{LemmyError, LemmyErrorType, LemmyResult},
   settings::structs::Settings,
   version::VERSION,
   REQWEST_TIMEOUT,
 };
+use once_cell::sync::Lazy;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use regex::Regex;
 use reqwest_middleware::ClientWithMiddleware;
 use serde::Deserialize;
 use tracing::info;
 use url::Url;
 use webpage::HTML;
 
+/// oEmbed endpoints of popular sites, so that no discovery is needed for them
+const OEMBED_PROVIDERS: &[(&str, &str)] = &[
+  ("youtube.com", "https://www.youtube.com/oembed"),
+  ("youtu.be", "https://www.youtube.com/oembed"),
+  ("vimeo.com", "https://vimeo.com/api/oembed.json"),
+  ("soundcloud.com", "https://soundcloud.com/oembed"),
+];
+
+static OEMBED_LINK_REGEX: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r#"(?i)<link[^>]+type=["']application/json\+oembed["'][^>]*>"#).expect("compile regex")
+});
+static HREF_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r#"(?i)\shref=["']([^"']+)["']"#).expect("compile regex"));
+static IFRAME_SRC_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r#"(?i)<iframe[^>]*\ssrc=["']([^"']+)["']"#).expect("compile regex"));
+
 /// Fetches the post link html tags (like title, description, image, etc)
 #[tracing::instrument(skip_all)]
 pub async fn fetch_site_metadata(
@@ -27,11 +45,84 @@ pub async fn fetch_site_metadata(
   // https://github.com/LemmyNet/lemmy/issues/1964
   let html_bytes = response.bytes().await.map_err(LemmyError::from)?.to_vec();
 
-  let tags = html_to_site_metadata(&html_bytes, url)?;
+  let mut tags = html_to_site_metadata(&html_bytes, url)?;
+
+  // The iframe from oEmbed is preferred over the opengraph video, because it also works for audio
+  // and is what the site itself suggests for embedding. Errors are ignored, as the opengraph tags
+  // are still usable.
+  if let Some(endpoint) = oembed_endpoint(url, &String::from_utf8_lossy(&html_bytes)) {
+    if let Ok(oembed) = fetch_oembed(client, &endpoint).await {
+      let iframe = oembed
+        .html
+        .as_deref()
+        .and_then(|html| oembed_iframe_src(html, &endpoint));
+      if let Some(iframe) = iframe {
+        tags.embed_video_url = Some(iframe.into());
+      }
+      tags.title = tags.title.or(oembed.title);
+    }
+  }
 
   Ok(tags)
 }
 
+#[derive(Deserialize, Debug)]
+struct OEmbed {
+  title: Option<String>,
+  html: Option<String>,
+}
+
+#[tracing::instrument(skip_all)]
+async fn fetch_oembed(client: &ClientWithMiddleware, endpoint: &Url) -> LemmyResult<OEmbed> {
+  info!("Fetching oEmbed data from: {}", endpoint);
+  Ok(
+    client
+      .get(endpoint.as_str())
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?,
+  )
+}
+
+/// The oEmbed url for a link, either from the list of known providers, or from a link tag in the
+/// html of the page.
+fn oembed_endpoint(url: &Url, html: &str) -> Option<Url> {
+  let host = url.host_str()?;
+  let provider = OEMBED_PROVIDERS
+    .iter()
+    .find(|(domain, _)| host == *domain || host.ends_with(&format!(".{domain}")));
+  if let Some((_, endpoint)) = provider {
+    let mut endpoint = Url::parse(endpoint).ok()?;
+    endpoint
+      .query_pairs_mut()
+      .append_pair("url", url.as_str())
+      .append_pair("format", "json");
+    return Some(endpoint);
+  }
+
+  let link = OEMBED_LINK_REGEX.find(html)?;
+  let href = HREF_REGEX.captures(link.as_str())?.get(1)?;
+  let endpoint = url.join(&href.as_str().replace("&amp;", "&")).ok()?;
+  match endpoint.scheme() {
+    "http" | "https" => Some(endpoint),
+    _ => None,
+  }
+}
+
+/// Extracts the iframe url from oEmbed html, which is stored instead of the html itself. Only https
+/// iframes on the domain of the oEmbed endpoint (or its subdomains) are accepted, so a provider
+/// can't embed arbitrary sites.
+fn oembed_iframe_src(html: &str, endpoint: &Url) -> Option<Url> {
+  let src = IFRAME_SRC_REGEX.captures(html)?.get(1)?;
+  let src = Url::parse(&src.as_str().replace("&amp;", "&")).ok()?;
+  let provider = endpoint.host_str()?.trim_start_matches("www.");
+  let host = src.host_str()?;
+  let same_domain = host == provider || host.ends_with(&format!(".{provider}"));
+  (src.scheme() == "https" && same_domain).then_some(src)
+}
+
 fn html_to_site_metadata(html_bytes: &[u8], url: &Url) -> Result<SiteMetadata, LemmyError> {
   let html = String::from_utf8_lossy(html_bytes);
 
@@ -244,6 +335,15 @@ pub async fn fetch_site_data(
   }
 }
 
+/// Whether fetching site data for a url returned nothing usable, so it should be retried later
+pub fn is_site_data_missing(metadata: &Option<SiteMetadata>, thumbnail: &Option<DbUrl>) -> bool {
+  let metadata_missing = match metadata {
+    Some(m) => m.title.is_none() && m.description.is_none() && m.image.is_none(),
+    None => true,
+  };
+  metadata_missing && thumbnail.is_none()
+}
+
 #[tracing::instrument(skip_all)]
 async fn is_image_content_type(client: &ClientWithMiddleware, url: &Url) -> Result<(), LemmyError> {
   let response = client.get(url.as_str()).send().await?;
@@ -260,6 +360,175 @@ async fn is_image_content_type(client: &ClientWithMiddleware, url: &Url) -> Resu
   }
 }
 
+/// Returns the mime type of a link if it points to an audio file, which makes the post an audio
+/// post.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_audio_mime_type(client: &ClientWithMiddleware, url: &Url) -> Option<String> {
+  let response = client.get(url.as_str()).send().await.ok()?;
+  let content_type = response.headers().get("Content-Type")?.to_str().ok()?;
+  audio_mime_type(content_type)
+}
+
+fn audio_mime_type(content_type: &str) -> Option<String> {
+  let mime_type = content_type.split(';').next()?.trim().to_lowercase();
+  mime_type.starts_with("audio/").then_some(mime_type)
+}
+
+/// Fetches an external blocklist. It can be json (a list of domains, a list of objects with
+/// `domain` and `comment` like the Mastodon api, or Fediseer censures), a Mastodon domain block
+/// csv export, or plain text with one domain per line, optionally followed by a comma and the
+/// reason.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_blocklist(
+  client: &ClientWithMiddleware,
+  url: &Url,
+) -> Result<Vec<BlocklistEntry>, LemmyError> {
+  info!("Fetching blocklist: {}", url);
+  let response = client.get(url.as_str()).send().await?.error_for_status()?;
+  let text = response.text().await.map_err(LemmyError::from)?;
+  let entries = parse_blocklist(&text);
+  if entries.is_empty() {
+    // Most likely the list moved or its format changed, so don't unblock everything
+    Err(LemmyErrorType::EmptyBlocklist)?;
+  }
+  Ok(entries)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonBlocklistEntry {
+  Domain(String),
+  Object {
+    domain: String,
+    severity: Option<String>,
+    #[serde(alias = "public_comment", alias = "reason")]
+    comment: Option<String>,
+  },
+}
+
+#[derive(Deserialize)]
+struct FediseerCensure {
+  domain: String,
+  censure_reasons: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonBlocklist {
+  List(Vec<JsonBlocklistEntry>),
+  /// Censures as returned by the Fediseer api
+  Fediseer {
+    instances: Vec<FediseerCensure>,
+  },
+}
+
+fn parse_blocklist(text: &str) -> Vec<BlocklistEntry> {
+  let entries = match serde_json::from_str::<JsonBlocklist>(text) {
+    Ok(JsonBlocklist::List(json)) => json
+      .into_iter()
+      .filter_map(|e| match e {
+        JsonBlocklistEntry::Domain(domain) => Some((domain, None)),
+        JsonBlocklistEntry::Object {
+          domain,
+          severity,
+          comment,
+        } => is_suspension(severity.as_deref()).then_some((domain, comment)),
+      })
+      .collect(),
+    Ok(JsonBlocklist::Fediseer { instances }) => instances
+      .into_iter()
+      .map(|i| (i.domain, i.censure_reasons.map(|r| r.join(", "))))
+      .collect(),
+    Err(_) => parse_blocklist_csv(text),
+  };
+  entries
+    .into_iter()
+    .filter_map(|(domain, reason)| {
+      Some(BlocklistEntry {
+        domain: clean_blocklist_domain(&domain)?,
+        reason: reason
+          .map(|r| r.trim().to_string())
+          .filter(|r| !r.is_empty()),
+      })
+    })
+    .collect()
+}
+
+fn parse_blocklist_csv(text: &str) -> Vec<(String, Option<String>)> {
+  // Mastodon exports start with a header like `#domain,#severity,...,#public_comment`
+  let mut columns: Option<Vec<String>> = None;
+  let mut entries = Vec::new();
+  for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+    let fields = split_csv_line(line);
+    if line.starts_with("#domain") || line.starts_with("domain,") {
+      columns = Some(
+        fields
+          .iter()
+          .map(|f| f.trim_start_matches('#').to_string())
+          .collect(),
+      );
+      continue;
+    } else if line.starts_with('#') {
+      continue;
+    }
+    let field = |name: &str| {
+      let columns = columns.as_ref()?;
+      let index = columns.iter().position(|c| c == name)?;
+      fields.get(index).cloned()
+    };
+    match columns {
+      Some(_) if is_suspension(field("severity").as_deref()) => {
+        if let Some(domain) = field("domain") {
+          entries.push((domain, field("public_comment").or(field("comment"))));
+        }
+      }
+      Some(_) => {}
+      None => {
+        let mut fields = fields.into_iter();
+        if let Some(domain) = fields.next() {
+          entries.push((domain, fields.next()));
+        }
+      }
+    }
+  }
+  entries
+}
+
+/// Only suspensions are defederated, Mastodon also has limited domains which can still federate
+fn is_suspension(severity: Option<&str>) -> bool {
+  matches!(severity, None | Some("") | Some("suspend"))
+}
+
+/// Splits a csv line at commas, except inside of quotes
+fn split_csv_line(line: &str) -> Vec<String> {
+  let mut fields = vec![String::new()];
+  let mut in_quotes = false;
+  let mut chars = line.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' if in_quotes && chars.peek() == Some(&'"') => {
+        chars.next();
+        fields.last_mut().expect("fields aren't empty").push('"');
+      }
+      '"' => in_quotes = !in_quotes,
+      ',' if !in_quotes => fields.push(String::new()),
+      c => fields.last_mut().expect("fields aren't empty").push(c),
+    }
+  }
+  fields
+}
+
+/// Returns the domain in lowercase, or none if it isn't a valid domain
+fn clean_blocklist_domain(domain: &str) -> Option<String> {
+  let domain = domain.trim();
+  // Mastodon obfuscates parts of some domains with asterisks
+  if domain.is_empty() || domain.contains('*') || domain.contains('/') {
+    return None;
+  }
+  let url = Url::parse(&format!("https://{domain}")).ok()?;
+  url.host_str().map(ToString::to_string)
+}
+
 pub fn build_user_agent(settings: &Settings) -> String {
   format!(
     "Lemmy/{}; +{}",
@@ -274,11 +543,16 @@ mod tests {
   #![allow(clippy::indexing_slicing)]
 
   use crate::request::{
+    audio_mime_type,
     build_user_agent,
     fetch_site_metadata,
     html_to_site_metadata,
+    oembed_endpoint,
+    oembed_iframe_src,
+    parse_blocklist,
     SiteMetadata,
   };
+  use lemmy_db_schema::source::blocklist_subscription::BlocklistEntry;
   use lemmy_utils::settings::SETTINGS;
   use url::Url;
 
@@ -318,6 +592,56 @@ mod tests {
   //   assert!(res_other.is_err());
   // }
 
+  #[test]
+  fn test_audio_mime_type() {
+    assert_eq!(
+      Some("audio/mpeg".to_string()),
+      audio_mime_type("audio/mpeg")
+    );
+    assert_eq!(
+      Some("audio/ogg".to_string()),
+      audio_mime_type("Audio/Ogg; codecs=opus")
+    );
+    assert_eq!(None, audio_mime_type("video/mp4"));
+    assert_eq!(None, audio_mime_type("text/html; charset=utf-8"));
+  }
+
+  #[test]
+  fn test_oembed_endpoint() {
+    // Known providers don't need discovery
+    let url = Url::parse("https://m.youtube.com/watch?v=dQw4w9WgXcQ").unwrap();
+    assert_eq!(
+      "https://www.youtube.com/oembed?url=https%3A%2F%2Fm.youtube.com%2Fwatch%3Fv%3DdQw4w9WgXcQ&format=json",
+      oembed_endpoint(&url, "").unwrap().as_str()
+    );
+
+    let url = Url::parse("https://example.com/video").unwrap();
+    assert!(oembed_endpoint(&url, "<!DOCTYPE html><html></html>").is_none());
+    let html = r#"<!DOCTYPE html><html><head><link rel="alternate" type="application/json+oembed" href="/oembed?url=https%3A%2F%2Fexample.com%2Fvideo&amp;format=json"></head></html>"#;
+    assert_eq!(
+      "https://example.com/oembed?url=https%3A%2F%2Fexample.com%2Fvideo&format=json",
+      oembed_endpoint(&url, html).unwrap().as_str()
+    );
+  }
+
+  #[test]
+  fn test_oembed_iframe_src() {
+    let endpoint = Url::parse("https://vimeo.com/api/oembed.json").unwrap();
+    let html = r#"<iframe src="https://player.vimeo.com/video/76979871?h=8272103f6e&amp;app_id=122963" width="640" height="360" allowfullscreen></iframe>"#;
+    assert_eq!(
+      "https://player.vimeo.com/video/76979871?h=8272103f6e&app_id=122963",
+      oembed_iframe_src(html, &endpoint).unwrap().as_str()
+    );
+
+    // Iframes on other domains, without https or scripts are rejected
+    let html = r#"<iframe src="https://evil.example/vimeo.com"></iframe>"#;
+    assert!(oembed_iframe_src(html, &endpoint).is_none());
+    let html = r#"<iframe src="http://player.vimeo.com/video/76979871"></iframe>"#;
+    assert!(oembed_iframe_src(html, &endpoint).is_none());
+    let html = r#"<script src="https://player.vimeo.com/api.js"></script>"#;
+    assert!(oembed_iframe_src(html, &endpoint).is_none());
+  }
+
   #[test]
   fn test_resolve_image_url() {
     // url that lists the opengraph fields
@@ -359,4 +683,48 @@ mod tests {
       Some(Url::parse("https://example.com/image.jpg").unwrap().into())
     );
   }
+
+  #[test]
+  fn test_parse_blocklist() {
+    let entry = |domain: &str, reason: Option<&str>| BlocklistEntry {
+      domain: domain.to_string(),
+      reason: reason.map(ToString::to_string),
+    };
+
+    let plain = "# spam instances\nSpam.example\n\nabuse.example, harassment\n";
+    assert_eq!(
+      vec![
+        entry("spam.example", None),
+        entry("abuse.example", Some("harassment"))
+      ],
+      parse_blocklist(plain)
+    );
+
+    let mastodon_csv = "#domain,#severity,#reject_media,#reject_reports,#public_comment,#obfuscate
+spam.example,suspend,false,false,\"spam, and \"\"more\"\"\",false
+limited.example,silence,false,false,,false
+hidden*.example,suspend,false,false,,true";
+    assert_eq!(
+      vec![entry("spam.example", Some("spam, and \"more\""))],
+      parse_blocklist(mastodon_csv)
+    );
+
+    let json = r#"[{"domain": "spam.example", "severity": "suspend", "comment": "spam"},
+      {"domain": "limited.example", "severity": "silence"}]"#;
+    assert_eq!(
+      vec![entry("spam.example", Some("spam"))],
+      parse_blocklist(json)
+    );
+    assert_eq!(
+      vec![entry("spam.example", None)],
+      parse_blocklist(r#"["spam.example", "https://invalid.example/path"]"#)
+    );
+
+    let fediseer = r#"{"instances": [{"domain": "spam.example", "censure_reasons": ["spam", "bots"],
+      "censure_count": 2}], "domains": ["spam.example"]}"#;
+    assert_eq!(
+      vec![entry("spam.example", Some("spam, bots"))],
+      parse_blocklist(fediseer)
+    );
+  }
 }
@@ -0,0 +1,52 @@
+use crate::sensitive::Sensitive;
+use lemmy_db_schema::source::scheduled_job::ScheduledJob;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the background jobs of the scheduler.
+pub struct ListScheduledJobs {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The background jobs of the scheduler.
+pub struct ListScheduledJobsResponse {
+  pub scheduled_jobs: Vec<ScheduledJob>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Runs a background job as soon as possible, instead of waiting for its next scheduled run.
+pub struct RunScheduledJob {
+  pub name: String,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Change the schedule of a background job.
+pub struct EditScheduledJob {
+  pub name: String,
+  /// Seconds between runs, at least one minute.
+  pub interval_seconds: Option<i32>,
+  pub enabled: Option<bool>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a background job.
+pub struct ScheduledJobResponse {
+  pub scheduled_job: ScheduledJob,
+}
@@ -9,11 +9,13 @@ use futures::future::BoxFuture;
 use lemmy_db_schema::{
   newtypes::{CommunityId, DbUrl, PersonId},
   source::{
+    activity::SentActivity,
     comment::Comment,
     community::Community,
     person::Person,
     post::Post,
     private_message::PrivateMessage,
+    site::Site,
   },
 };
 use lemmy_db_views::structs::PrivateMessageView;
@@ -48,7 +50,9 @@ pub enum SendActivityData {
   DeleteComment(Comment, Person, Community),
   RemoveComment(Comment, Person, Community, Option<String>),
   LikePostOrComment(DbUrl, Person, Community, i16),
+  ReactPostOrComment(DbUrl, Person, Community, String, bool),
   FollowCommunity(Community, Person, bool),
+  ApproveCommunityFollower(Community, Person, bool),
   UpdateCommunity(Person, Community),
   DeleteCommunity(Person, Community, bool),
   RemoveCommunity(Person, Community, Option<String>, bool),
@@ -59,7 +63,10 @@ pub enum SendActivityData {
   UpdatePrivateMessage(PrivateMessageView),
   DeletePrivateMessage(Person, PrivateMessage, bool),
   DeleteUser(Person),
+  UpdatePerson(Person),
+  UpdateSite(Site),
   CreateReport(Url, Person, Community, String),
+  ReplayActivity(SentActivity, Vec<Url>),
 }
 
 // TODO: instead of static, move this into LemmyContext. make sure that stopping the process with
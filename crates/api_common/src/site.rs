@@ -1,7 +1,21 @@
 use crate::sensitive::Sensitive;
 use lemmy_db_schema::{
-  newtypes::{CommentId, CommunityId, LanguageId, PersonId, PostId},
-  source::{instance::Instance, language::Language, tagline::Tagline},
+  aggregates::structs::SiteStatsHistory,
+  newtypes::{CategoryId, CommentId, CommunityId, LanguageId, PersonId, PostId},
+  source::{
+    announcement::Announcement,
+    category::Category,
+    community_last_seen::CommunityUnreadPosts,
+    instance::Instance,
+    instance_trust::InstanceTrust,
+    language::Language,
+    local_site_rate_limit::LocalSiteRateLimit,
+    local_user::DailySignups,
+    person::Person,
+    rate_limit_override::RateLimitOverride,
+    sent_activity_delivery::{InstanceDeliveryStats, SentActivityDelivery},
+    tagline::Tagline,
+  },
   ListingType,
   ModlogActionType,
   RegistrationMode,
@@ -25,6 +39,7 @@ use lemmy_db_views_actor::structs::{
   PersonView,
 };
 use lemmy_db_views_moderator::structs::{
+  AdminMergePersonView,
   AdminPurgeCommentView,
   AdminPurgeCommunityView,
   AdminPurgePersonView,
@@ -39,6 +54,7 @@ use lemmy_db_views_moderator::structs::{
   ModRemoveCommentView,
   ModRemoveCommunityView,
   ModRemovePostView,
+  ModShadowbanView,
   ModTransferCommunityView,
 };
 use serde::{Deserialize, Serialize};
@@ -59,6 +75,8 @@ pub struct Search {
   pub type_: Option<SearchType>,
   pub sort: Option<SortType>,
   pub listing_type: Option<ListingType>,
+  /// Only return communities in this category.
+  pub category_id: Option<CategoryId>,
   pub page: Option<i64>,
   pub limit: Option<i64>,
   pub auth: Option<Sensitive<String>>,
@@ -87,6 +105,227 @@ pub struct ResolveObject {
   pub auth: Option<Sensitive<String>>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Resolves many objects at once, eg to import subscriptions. Each query works like in
+/// ResolveObject.
+pub struct ResolveObjects {
+  pub q: Vec<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches a remote object from its instance again, even if the local copy isn't outdated yet.
+/// Exactly one of the ids should be given.
+///
+/// Admins and mods of the object's community can do this at any time, other users only if the
+/// object wasn't refreshed recently. The response is the same as for ResolveObject.
+pub struct RefreshObject {
+  pub community_id: Option<CommunityId>,
+  pub person_id: Option<PersonId>,
+  pub post_id: Option<PostId>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Replaces the keypair of a local person or community with a new one, and publishes the new
+/// public key to other instances. If no id is given, the key of the instance actor is rotated.
+pub struct RotateActorKeys {
+  pub person_id: Option<PersonId>,
+  pub community_id: Option<CommunityId>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for rotating an actor keypair.
+pub struct RotateActorKeysResponse {
+  pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Sends a previously sent activity once more to the given inbox, to debug federation with another
+/// instance. Only for admins, and not possible for private activities.
+pub struct ReplayActivity {
+  /// The activity id, eg `https://example.com/activities/create/...`
+  pub activity_id: String,
+  pub inbox: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for replaying an activity.
+pub struct ReplayActivityResponse {
+  pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Sends activities again which couldn't be delivered to an instance in the last week, for example
+/// after it was down for longer than failed deliveries are retried. Only for admins.
+pub struct RequeueFailedDeliveries {
+  /// The domain of the instance, eg `example.com`
+  pub domain: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for requeueing failed deliveries.
+pub struct RequeueFailedDeliveriesResponse {
+  /// The number of deliveries which are sent again.
+  pub requeued: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the delivery attempts of a sent activity. Only for admins.
+pub struct ListActivityDeliveries {
+  pub activity_id: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The delivery attempts of an activity, oldest first.
+pub struct ListActivityDeliveriesResponse {
+  pub deliveries: Vec<SentActivityDelivery>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the delivery statistics of the last three days for each remote instance, which is as
+/// long as failed deliveries are retried. Only for admins.
+pub struct ListInstanceDeliveries {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The delivery statistics of remote instances, those with the most failures first.
+pub struct ListInstanceDeliveriesResponse {
+  pub instances: Vec<InstanceDeliveryStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the rate limits of your site.
+pub struct GetRateLimits {
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Changes the rate limits of your site. Each bucket allows a number of actions per interval,
+/// given in seconds. The new limits are applied immediately.
+pub struct EditRateLimits {
+  pub message: Option<i32>,
+  pub message_per_second: Option<i32>,
+  pub post: Option<i32>,
+  pub post_per_second: Option<i32>,
+  pub register: Option<i32>,
+  pub register_per_second: Option<i32>,
+  pub image: Option<i32>,
+  pub image_per_second: Option<i32>,
+  pub comment: Option<i32>,
+  pub comment_per_second: Option<i32>,
+  pub search: Option<i32>,
+  pub search_per_second: Option<i32>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The rate limits of your site.
+pub struct RateLimitsResponse {
+  pub rate_limits: LocalSiteRateLimit,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Gives a local user individual rate limits, in percent of the site rate limits. Values above
+/// 100 raise the limits for trusted users and bots, lower values throttle problem users. Without
+/// a percent, the user gets the site rate limits again.
+///
+/// The limits apply to requests which pass the auth token in the query string or in the `jwt`
+/// cookie. These are counted for the user, separately from other requests from the same IP.
+pub struct SetRateLimitOverride {
+  pub person_id: PersonId,
+  pub limit_percent: Option<i32>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Lists the users with individual rate limits.
+pub struct ListRateLimitOverrides {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A user with individual rate limits.
+pub struct PersonRateLimitOverride {
+  pub person: Person,
+  pub rate_limit_override: RateLimitOverride,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The users with individual rate limits.
+pub struct ListRateLimitOverridesResponse {
+  pub overrides: Vec<PersonRateLimitOverride>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Sets the secret keys of hosted captcha services. An empty string removes the secret.
+pub struct SetCaptchaSecrets {
+  pub hcaptcha_secret: Option<Sensitive<String>>,
+  pub mcaptcha_secret: Option<Sensitive<String>>,
+  pub turnstile_secret: Option<Sensitive<String>>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Shows which captcha secrets are set, without revealing them.
+pub struct SetCaptchaSecretsResponse {
+  pub hcaptcha_secret_set: bool,
+  pub mcaptcha_secret_set: bool,
+  pub turnstile_secret_set: bool,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -100,6 +339,26 @@ pub struct ResolveObjectResponse {
   pub person: Option<PersonView>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The outcome of resolving one of the queries. Either the object or the error is given.
+pub struct ResolveObjectResult {
+  pub q: String,
+  pub object: Option<ResolveObjectResponse>,
+  /// Same as the error of a failed ResolveObject call, eg `couldnt_find_object`.
+  pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The results of a batched object fetch, in the same order as the queries.
+pub struct ResolveObjectsResponse {
+  pub results: Vec<ResolveObjectResult>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -136,6 +395,10 @@ pub struct GetModlogResponse {
   pub admin_purged_posts: Vec<AdminPurgePostView>,
   pub admin_purged_comments: Vec<AdminPurgeCommentView>,
   pub hidden_communities: Vec<ModHideCommunityView>,
+  /// Only returned to admins
+  pub shadowbanned: Vec<ModShadowbanView>,
+  /// Only returned to admins
+  pub admin_merged_persons: Vec<AdminMergePersonView>,
 }
 
 #[skip_serializing_none]
@@ -183,6 +446,17 @@ pub struct CreateSite {
   pub blocked_instances: Option<Vec<String>>,
   pub taglines: Option<Vec<String>>,
   pub registration_mode: Option<RegistrationMode>,
+  pub enable_vote_viewer: Option<bool>,
+  pub anonymize_outgoing_votes: Option<bool>,
+  pub private_instance_federation: Option<bool>,
+  pub check_breached_passwords: Option<bool>,
+  pub emoji_reactions_as_votes: Option<bool>,
+  pub users_can_create_invites: Option<bool>,
+  pub community_creation_min_account_age_days: Option<i32>,
+  pub community_creation_min_karma: Option<i32>,
+  pub community_creation_requires_approval: Option<bool>,
+  pub max_username_changes: Option<i32>,
+  pub username_reuse_cooldown_days: Option<i32>,
   pub auth: Sensitive<String>,
 }
 
@@ -257,11 +531,41 @@ pub struct EditSite {
   pub allowed_instances: Option<Vec<String>>,
   /// A list of blocked instances.
   pub blocked_instances: Option<Vec<String>>,
+  /// A list of email domains which can be used for signups. If none are set, all domains are
+  /// allowed. Subdomains are covered too.
+  pub allowed_email_domains: Option<Vec<String>>,
+  /// A list of email domains which can't be used for signups, including their subdomains.
+  pub blocked_email_domains: Option<Vec<String>>,
+  /// Email domains which aren't treated as disposable, even if they are in the list of disposable
+  /// email providers.
+  pub disposable_email_exceptions: Option<Vec<String>>,
   /// A list of taglines shown at the top of the front page.
   pub taglines: Option<Vec<String>>,
   pub registration_mode: Option<RegistrationMode>,
   /// Whether to email admins for new reports.
   pub reports_email_admins: Option<bool>,
+  /// Whether mods and admins can see who voted on posts and comments.
+  pub enable_vote_viewer: Option<bool>,
+  /// Federate votes of all local users through pseudonymous actors.
+  pub anonymize_outgoing_votes: Option<bool>,
+  /// Whether a private instance federates with the instances in the allowlist.
+  pub private_instance_federation: Option<bool>,
+  /// Reject passwords which are known from data breaches.
+  pub check_breached_passwords: Option<bool>,
+  /// Count 👍 and 👎 reactions from other platforms like Pleroma as votes.
+  pub emoji_reactions_as_votes: Option<bool>,
+  /// Whether users other than admins can create invite codes.
+  pub users_can_create_invites: Option<bool>,
+  /// Minimum account age in days for non-admins to create communities, 0 if disabled.
+  pub community_creation_min_account_age_days: Option<i32>,
+  /// Minimum site-wide karma for non-admins to create communities, 0 if disabled.
+  pub community_creation_min_karma: Option<i32>,
+  /// Whether communities created by non-admins have to be approved by an admin.
+  pub community_creation_requires_approval: Option<bool>,
+  /// How often local users can change their username, 0 if disabled.
+  pub max_username_changes: Option<i32>,
+  /// For how many days a former username can't be taken by other users.
+  pub username_reuse_cooldown_days: Option<i32>,
   pub auth: Sensitive<String>,
 }
 
@@ -297,8 +601,23 @@ pub struct GetSiteResponse {
   pub discussion_languages: Vec<LanguageId>,
   /// A list of taglines shown at the top of the front page.
   pub taglines: Vec<Tagline>,
+  /// A randomly chosen tagline, so that it rotates on every page load.
+  pub tagline: Option<Tagline>,
+  /// The announcement banners which are currently shown.
+  pub announcements: Vec<Announcement>,
   /// A list of custom emojis your site supports.
   pub custom_emojis: Vec<CustomEmojiView>,
+  /// The categories which communities can be assigned to.
+  pub categories: Vec<Category>,
+  /// The email domains which can be used for signups. Empty if all domains are allowed.
+  pub allowed_email_domains: Vec<String>,
+  /// The email domains which can't be used for signups. Only returned to admins.
+  pub blocked_email_domains: Option<Vec<String>>,
+  /// Email domains which aren't treated as disposable. Only returned to admins.
+  pub disposable_email_exceptions: Option<Vec<String>>,
+  /// Posts and comments deleted by their creator are overwritten after this many days, to be
+  /// mentioned in the privacy policy.
+  pub deleted_content_retention_days: u32,
 }
 
 #[skip_serializing_none]
@@ -327,6 +646,8 @@ pub struct GetFederatedInstancesResponse {
 pub struct MyUserInfo {
   pub local_user_view: LocalUserView,
   pub follows: Vec<CommunityFollowerView>,
+  /// New posts in followed communities since they were last visited.
+  pub community_unread_posts: Vec<CommunityUnreadPosts>,
   pub moderates: Vec<CommunityModeratorView>,
   pub community_blocks: Vec<CommunityBlockView>,
   pub person_blocks: Vec<PersonBlockView>,
@@ -351,6 +672,35 @@ pub struct FederatedInstances {
   pub blocked: Vec<Instance>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the known instances with their trust signals from Fediseer.
+pub struct ListInstanceTrust {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A known instance, and its trust signals if Fediseer knows about it.
+pub struct InstanceWithTrust {
+  pub instance: Instance,
+  pub trust: Option<InstanceTrust>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The known instances with their trust signals.
+pub struct ListInstanceTrustResponse {
+  pub instances: Vec<InstanceWithTrust>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -459,3 +809,65 @@ pub struct GetUnreadRegistrationApplicationCount {
 pub struct GetUnreadRegistrationApplicationCountResponse {
   pub registration_applications: i64,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Gets the daily snapshots of the site statistics, optionally limited to a time range.
+pub struct GetSiteStatsHistory {
+  /// A unix timestamp, snapshots from days before it are left out.
+  pub since: Option<i64>,
+  /// A unix timestamp, snapshots from days after it are left out.
+  pub until: Option<i64>,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The daily snapshots of the site statistics, oldest first.
+pub struct GetSiteStatsHistoryResponse {
+  pub history: Vec<SiteStatsHistory>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Gets operational statistics of the instance. Only admins can do this.
+pub struct GetAdminDashboard {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Operational statistics of the instance, for the admin dashboard.
+pub struct GetAdminDashboardResponse {
+  /// New local users for each of the last 30 days, oldest first.
+  pub signups: Vec<DailySignups>,
+  /// Daily snapshots of the site statistics for the last 30 days, oldest first.
+  pub stats_history: Vec<SiteStatsHistory>,
+  pub unresolved_post_reports: i64,
+  pub unresolved_comment_reports: i64,
+  pub unresolved_private_message_reports: i64,
+  pub unread_registration_applications: i64,
+  /// The number of activities sent to other instances in the last day.
+  pub activities_sent_day: i64,
+  /// The number of activities received from other instances in the last day.
+  pub activities_received_day: i64,
+  /// Attempts to deliver activities to remote inboxes in the last day.
+  pub deliveries_day: i64,
+  /// Delivery attempts in the last day which failed because the inbox couldn't be reached or
+  /// didn't accept the activity. Divide by `deliveries_day` for the failure rate.
+  pub failed_deliveries_day: i64,
+  /// Deliveries which are waiting in the federation queue to be retried. The queue itself only
+  /// lives in memory, so this is derived from the stored delivery attempts.
+  pub pending_delivery_retries: i64,
+  /// Local communities with the most active users in the last week.
+  pub top_communities: Vec<CommunityView>,
+  /// The number of images uploaded by local users.
+  pub local_images: i64,
+  /// The disk space used by the database, in bytes.
+  pub database_size: i64,
+}
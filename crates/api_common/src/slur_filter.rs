@@ -0,0 +1,66 @@
+use crate::sensitive::Sensitive;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, LanguageId, SlurFilterId},
+  source::slur_filter::SlurFilter,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Add a named slur filter, which applies on top of the site slur filter to posts and comments in
+/// the given language and/or community.
+pub struct CreateSlurFilter {
+  pub name: String,
+  /// A case insensitive regex.
+  pub pattern: String,
+  pub language_id: Option<LanguageId>,
+  pub community_id: Option<CommunityId>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delete a slur filter.
+pub struct DeleteSlurFilter {
+  pub id: SlurFilterId,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The response for deleting a slur filter.
+pub struct DeleteSlurFilterResponse {
+  pub id: SlurFilterId,
+  pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Fetches the slur filters.
+pub struct ListSlurFilters {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A response for a slur filter.
+pub struct SlurFilterResponse {
+  pub slur_filter: SlurFilter,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A list of slur filters.
+pub struct ListSlurFiltersResponse {
+  pub slur_filters: Vec<SlurFilter>,
+}
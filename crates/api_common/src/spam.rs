@@ -0,0 +1,405 @@
+use crate::context::LemmyContext;
+use async_trait::async_trait;
+use chrono::Duration;
+use lemmy_db_schema::{
+  source::{
+    comment::Comment,
+    comment_report::{CommentReport, CommentReportForm},
+    person::Person,
+    post::Post,
+    post_report::{PostReport, PostReportForm},
+    private_message::PrivateMessage,
+    private_message_report::{PrivateMessageReport, PrivateMessageReportForm},
+  },
+  traits::Reportable,
+  utils::{naive_now, DbPool},
+};
+use lemmy_db_views_actor::structs::PersonView;
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  settings::structs::SpamCheckConfig,
+};
+use serde::Deserialize;
+use tracing::warn;
+use url::Url;
+
+const STOP_FORUM_SPAM_URL: &str = "https://api.stopforumspam.org/api";
+
+/// Something a local user is about to create, with the parts that are checked for spam.
+pub enum SpamCheckInput<'a> {
+  Registration {
+    username: &'a str,
+    email: Option<&'a str>,
+  },
+  Post {
+    creator: &'a Person,
+    title: &'a str,
+    body: Option<&'a str>,
+  },
+  Comment {
+    creator: &'a Person,
+    content: &'a str,
+  },
+  PrivateMessage {
+    creator: &'a Person,
+    content: &'a str,
+  },
+}
+
+impl SpamCheckInput<'_> {
+  fn creator(&self) -> Option<&Person> {
+    match self {
+      SpamCheckInput::Registration { .. } => None,
+      SpamCheckInput::Post { creator, .. }
+      | SpamCheckInput::Comment { creator, .. }
+      | SpamCheckInput::PrivateMessage { creator, .. } => Some(creator),
+    }
+  }
+
+  fn texts(&self) -> Vec<&str> {
+    match self {
+      SpamCheckInput::Registration { .. } => vec![],
+      SpamCheckInput::Post { title, body, .. } => {
+        [Some(*title), *body].into_iter().flatten().collect()
+      }
+      SpamCheckInput::Comment { content, .. } | SpamCheckInput::PrivateMessage { content, .. } => {
+        vec![content]
+      }
+    }
+  }
+}
+
+/// A single check in the spam pipeline. The scores of all checks are added up, and compared with
+/// the thresholds from the config.
+#[async_trait]
+pub trait SpamCheck: Send + Sync {
+  /// Shown to admins when content is reported for review
+  fn name(&self) -> &'static str;
+
+  /// Returns 0 for content without any sign of spam, up to 100 for certain spam
+  async fn score(&self, input: &SpamCheckInput<'_>, context: &LemmyContext) -> LemmyResult<u32>;
+}
+
+/// Many links in little text
+pub struct LinkDensity;
+
+#[async_trait]
+impl SpamCheck for LinkDensity {
+  fn name(&self) -> &'static str {
+    "link density"
+  }
+
+  async fn score(&self, input: &SpamCheckInput<'_>, _context: &LemmyContext) -> LemmyResult<u32> {
+    Ok(link_density_score(&input.texts()))
+  }
+}
+
+fn link_density_score(texts: &[&str]) -> u32 {
+  let links: usize = texts
+    .iter()
+    .map(|t| t.matches("http://").count() + t.matches("https://").count())
+    .sum();
+  // A single link is normal for posts and comments
+  if links < 2 {
+    return 0;
+  }
+  let words: usize = texts.iter().map(|t| t.split_whitespace().count()).sum();
+  let percent = links * 100 / words.max(1);
+  u32::try_from(percent).unwrap_or(u32::MAX).min(60)
+}
+
+/// The same content posted again by the same person within a day
+pub struct DuplicateContent;
+
+#[async_trait]
+impl SpamCheck for DuplicateContent {
+  fn name(&self) -> &'static str {
+    "duplicate content"
+  }
+
+  async fn score(&self, input: &SpamCheckInput<'_>, context: &LemmyContext) -> LemmyResult<u32> {
+    let pool = &mut context.pool();
+    let since = naive_now() - Duration::days(1);
+    let duplicates = match input {
+      SpamCheckInput::Registration { .. } => 0,
+      SpamCheckInput::Post { creator, title, .. } => {
+        Post::count_duplicates_for_creator(pool, creator.id, title, since).await?
+      }
+      SpamCheckInput::Comment { creator, content } => {
+        Comment::count_duplicates_for_creator(pool, creator.id, content, since).await?
+      }
+      SpamCheckInput::PrivateMessage { creator, content } => {
+        PrivateMessage::count_duplicates_for_creator(pool, creator.id, content, since).await?
+      }
+    };
+    Ok(u32::try_from(duplicates.clamp(0, 2) * 30)?)
+  }
+}
+
+/// Accounts which were created very recently
+pub struct AccountAge;
+
+#[async_trait]
+impl SpamCheck for AccountAge {
+  fn name(&self) -> &'static str {
+    "account age"
+  }
+
+  async fn score(&self, input: &SpamCheckInput<'_>, _context: &LemmyContext) -> LemmyResult<u32> {
+    let Some(creator) = input.creator() else {
+      return Ok(0);
+    };
+    let age = naive_now() - creator.published;
+    Ok(if age < Duration::hours(1) {
+      30
+    } else if age < Duration::days(1) {
+      20
+    } else if age < Duration::weeks(1) {
+      10
+    } else {
+      0
+    })
+  }
+}
+
+/// Looks up the username and email of new users in the StopForumSpam database. This sends the
+/// data of every new user to an external service, so it needs to be enabled in the config.
+pub struct StopForumSpam;
+
+#[derive(Deserialize)]
+struct StopForumSpamResponse {
+  username: Option<StopForumSpamField>,
+  email: Option<StopForumSpamField>,
+}
+
+#[derive(Deserialize)]
+struct StopForumSpamField {
+  /// Percentage how likely the value belongs to a spammer, only present if it was reported
+  #[serde(default)]
+  confidence: f64,
+}
+
+#[async_trait]
+impl SpamCheck for StopForumSpam {
+  fn name(&self) -> &'static str {
+    "StopForumSpam"
+  }
+
+  async fn score(&self, input: &SpamCheckInput<'_>, context: &LemmyContext) -> LemmyResult<u32> {
+    let SpamCheckInput::Registration { username, email } = input else {
+      return Ok(0);
+    };
+    let mut params = vec![("json", ""), ("username", *username)];
+    if let Some(email) = email {
+      params.push(("email", email));
+    }
+    let url = Url::parse_with_params(STOP_FORUM_SPAM_URL, params)?;
+    let res: StopForumSpamResponse = context
+      .client()
+      .get(url.as_str())
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?;
+    Ok(stop_forum_spam_score(&res))
+  }
+}
+
+fn stop_forum_spam_score(res: &StopForumSpamResponse) -> u32 {
+  let confidence = [&res.username, &res.email]
+    .into_iter()
+    .flatten()
+    .map(|f| f.confidence)
+    .fold(0.0, f64::max);
+  confidence.round().clamp(0.0, 100.0) as u32
+}
+
+fn spam_checks(config: &SpamCheckConfig) -> Vec<Box<dyn SpamCheck>> {
+  let mut checks: Vec<Box<dyn SpamCheck>> = vec![
+    Box::new(LinkDensity),
+    Box::new(DuplicateContent),
+    Box::new(AccountAge),
+  ];
+  if config.stop_forum_spam {
+    checks.push(Box::new(StopForumSpam));
+  }
+  checks
+}
+
+/// Runs all spam checks which are enabled in the config. Content with a score above the reject
+/// threshold is rejected with an error. If the score is above the review threshold, the reason for
+/// a review is returned. Admins are never checked.
+pub async fn check_spam(
+  input: &SpamCheckInput<'_>,
+  context: &LemmyContext,
+) -> LemmyResult<Option<String>> {
+  let Some(config) = &context.settings().spam_check else {
+    return Ok(None);
+  };
+  if input.creator().is_some_and(|c| c.admin) {
+    return Ok(None);
+  }
+  let mut scores = vec![];
+  for check in spam_checks(config) {
+    // An unreachable external service shouldn't keep users from posting
+    match check.score(input, context).await {
+      Ok(score) => scores.push((check.name(), score)),
+      Err(e) => warn!("Spam check {} failed: {e}", check.name()),
+    }
+  }
+  spam_verdict(config, &scores)
+}
+
+fn spam_verdict(config: &SpamCheckConfig, scores: &[(&str, u32)]) -> LemmyResult<Option<String>> {
+  let total: u32 = scores.iter().map(|(_, s)| s).sum();
+  if total >= config.reject_threshold {
+    return Err(LemmyErrorType::SpamDetected.into());
+  }
+  if total < config.review_threshold {
+    return Ok(None);
+  }
+  let details = scores
+    .iter()
+    .filter(|(_, s)| *s > 0)
+    .map(|(name, s)| format!("{name}: {s}"))
+    .collect::<Vec<_>>()
+    .join(", ");
+  Ok(Some(format!("Spam check score {total} ({details})")))
+}
+
+/// Reports a post which needs a review after the spam check, in the name of the site owner.
+pub async fn report_spam_post(
+  post: &Post,
+  review: Option<String>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let (Some(reason), Some(owner)) = (review, site_owner(pool).await?) else {
+    return Ok(());
+  };
+  let form = PostReportForm {
+    creator_id: owner.id,
+    post_id: post.id,
+    original_post_name: post.name.clone(),
+    original_post_url: post.url.clone(),
+    original_post_body: post.body.clone(),
+    reason,
+    reason_id: None,
+    rule_id: None,
+  };
+  PostReport::report(pool, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateReport)?;
+  Ok(())
+}
+
+/// Reports a comment which needs a review after the spam check, in the name of the site owner.
+pub async fn report_spam_comment(
+  comment: &Comment,
+  review: Option<String>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let (Some(reason), Some(owner)) = (review, site_owner(pool).await?) else {
+    return Ok(());
+  };
+  let form = CommentReportForm {
+    creator_id: owner.id,
+    comment_id: comment.id,
+    original_comment_text: comment.content.clone(),
+    reason,
+    reason_id: None,
+    rule_id: None,
+  };
+  CommentReport::report(pool, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateReport)?;
+  Ok(())
+}
+
+/// Reports a private message which needs a review after the spam check, in the name of the site
+/// owner.
+pub async fn report_spam_private_message(
+  private_message: &PrivateMessage,
+  review: Option<String>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let (Some(reason), Some(owner)) = (review, site_owner(pool).await?) else {
+    return Ok(());
+  };
+  let form = PrivateMessageReportForm {
+    creator_id: owner.id,
+    private_message_id: private_message.id,
+    original_pm_text: private_message.content.clone(),
+    reason,
+    reason_id: None,
+  };
+  PrivateMessageReport::report(pool, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateReport)?;
+  Ok(())
+}
+
+async fn site_owner(pool: &mut DbPool<'_>) -> LemmyResult<Option<Person>> {
+  Ok(
+    PersonView::admins(pool)
+      .await?
+      .into_iter()
+      .next()
+      .map(|a| a.person),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+
+  #[test]
+  fn test_link_density_score() {
+    assert_eq!(
+      0,
+      link_density_score(&["Have a look at https://example.com"])
+    );
+    assert_eq!(
+      0,
+      link_density_score(&["A longer text which only mentions a single link: https://example.com"])
+    );
+    assert_eq!(
+      50,
+      link_density_score(&["Cheap pills", "https://spam.example https://spam.example/2"])
+    );
+    assert_eq!(
+      60,
+      link_density_score(&["https://a.example https://b.example"])
+    );
+    let text = "There are two links in this comment: http://example.com and \
+      https://example.org, but also quite a lot of other words which make up the text";
+    assert_eq!(8, link_density_score(&[text]));
+  }
+
+  #[test]
+  fn test_stop_forum_spam_score() {
+    let res: StopForumSpamResponse = serde_json::from_str(
+      r#"{"success":1,"username":{"frequency":0,"appears":0},
+      "email":{"lastseen":"2023-08-31 10:00:00","frequency":12,"appears":1,"confidence":87.6}}"#,
+    )
+    .unwrap();
+    assert_eq!(88, stop_forum_spam_score(&res));
+
+    let res: StopForumSpamResponse = serde_json::from_str(r#"{"success":1}"#).unwrap();
+    assert_eq!(0, stop_forum_spam_score(&res));
+  }
+
+  #[test]
+  fn test_spam_verdict() {
+    let config = SpamCheckConfig::default();
+    assert_eq!(None, spam_verdict(&config, &[("a", 20), ("b", 0)]).unwrap());
+    assert_eq!(
+      Some("Spam check score 60 (a: 30, c: 30)".to_string()),
+      spam_verdict(&config, &[("a", 30), ("b", 0), ("c", 30)]).unwrap()
+    );
+    assert!(spam_verdict(&config, &[("a", 60), ("b", 40)]).is_err());
+  }
+}
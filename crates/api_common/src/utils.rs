@@ -1,49 +1,105 @@
 use crate::{
+  community::BanFromCommunity,
   context::LemmyContext,
-  request::purge_image_from_pictrs,
+  request::{fetch_blocklist, purge_image_from_pictrs},
+  send_activity::{ActivityChannel, SendActivityData},
   sensitive::Sensitive,
   site::FederatedInstances,
 };
+use activitypub_federation::{config::Data, http_signatures::generate_actor_keypair};
+use actix_web::{http::header::USER_AGENT, HttpRequest};
 use anyhow::Context;
 use chrono::NaiveDateTime;
 use lemmy_db_schema::{
-  impls::person::is_banned,
-  newtypes::{CommunityId, DbUrl, LocalUserId, PersonId, PostId},
+  aggregates::structs::PersonAggregates,
+  impls::{actor_language::UNDETERMINED_ID, person::is_banned},
+  newtypes::{self, CommunityId, DbUrl, LocalUserId, LoginSessionId, PersonId, PostId},
   source::{
+    actor_language::CommunityLanguage,
+    blocked_url::BlockedUrl,
+    blocklist_subscription::BlocklistSubscription,
     comment::{Comment, CommentUpdateForm},
-    community::{Community, CommunityModerator, CommunityUpdateForm},
+    comment_report::{CommentReport, CommentReportForm},
+    community::{
+      Community,
+      CommunityFollower,
+      CommunityFollowerForm,
+      CommunityModerator,
+      CommunityPersonBan,
+      CommunityPersonBanForm,
+      CommunityUpdateForm,
+    },
+    community_application::CommunityApplication,
+    community_strike::{CommunityStrike, CommunityStrikeForm},
+    community_word_filter::CommunityWordFilter,
+    email_domain::EmailDomain,
     email_verification::{EmailVerification, EmailVerificationForm},
+    federation_blocklist::FederationBlockList,
     instance::Instance,
+    local_image::LocalImage,
     local_site::LocalSite,
     local_site_rate_limit::LocalSiteRateLimit,
+    local_user::{LocalUser, LocalUserUpdateForm},
+    login_history::{LoginHistory, LoginHistoryInsertForm},
+    login_session::{LoginSession, LoginSessionInsertForm},
+    magic_login_token::{MagicLoginToken, MagicLoginTokenForm},
+    moderator::{
+      ModBanFromCommunity,
+      ModBanFromCommunityForm,
+      ModRemoveComment,
+      ModRemoveCommentForm,
+      ModRemovePost,
+      ModRemovePostForm,
+    },
     password_reset_request::PasswordResetRequest,
     person::{Person, PersonUpdateForm},
     person_block::PersonBlock,
-    post::{Post, PostRead, PostReadForm},
+    post::{Post, PostRead, PostReadForm, PostUpdateForm},
+    post_report::{PostReport, PostReportForm},
+    rate_limit_override::RateLimitOverride,
     registration_application::RegistrationApplication,
+    site::{Site, SiteUpdateForm},
+    slur_filter::SlurFilter,
   },
-  traits::{Crud, Readable},
-  utils::DbPool,
+  traits::{Bannable, Crud, Followable, Readable, Reportable},
+  utils::{limit_and_offset, naive_now, DbPool},
+  AdminRole,
+  PostTypeRestriction,
   RegistrationMode,
+  WordFilterAction,
+};
+use lemmy_db_views::{
+  comment_view::CommentQuery,
+  structs::{CommentView, LocalUserView, ModQueueDigest},
 };
-use lemmy_db_views::{comment_view::CommentQuery, structs::LocalUserView};
 use lemmy_db_views_actor::structs::{
   CommunityModeratorView,
   CommunityPersonBanView,
   CommunityView,
+  PersonView,
 };
 use lemmy_utils::{
-  claims::Claims,
   email::{send_email, translations::Lang},
-  error::{LemmyError, LemmyErrorExt, LemmyErrorExt2, LemmyErrorType},
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
   location_info,
-  rate_limit::RateLimitConfig,
-  settings::structs::Settings,
-  utils::slurs::build_slur_regex,
+  rate_limit::{RateLimitCell, RateLimitConfig, UserRateLimit},
+  session::{generate_session_token, hash_session_token, login_session_expires},
+  settings::structs::{IncomingHtmlConfig, Settings},
+  utils::{
+    slurs::{build_slur_filter_regex, build_slur_regex, check_word_filters},
+    validation::build_and_check_regex,
+  },
 };
+use moka::future::Cache;
+use once_cell::sync::Lazy;
 use regex::Regex;
 use reqwest_middleware::ClientWithMiddleware;
 use rosetta_i18n::{Language, LanguageId};
+use std::{
+  collections::HashSet,
+  net::{IpAddr, SocketAddr},
+  time::Duration,
+};
 use tracing::warn;
 use url::{ParseError, Url};
 
@@ -70,7 +126,7 @@ pub async fn is_mod_or_admin_opt(
     if let Some(community_id) = community_id {
       is_mod_or_admin(pool, local_user_view.person.id, community_id).await
     } else {
-      is_admin(local_user_view)
+      check_admin_role(local_user_view, AdminRole::ContentAdmin)
     }
   } else {
     Err(LemmyErrorType::NotAModOrAdmin)?
@@ -84,6 +140,25 @@ pub fn is_admin(local_user_view: &LocalUserView) -> Result<(), LemmyError> {
   Ok(())
 }
 
+/// Checks that the user is an admin with the given role. Full admins have all roles.
+pub fn check_admin_role(local_user_view: &LocalUserView, role: AdminRole) -> LemmyResult<()> {
+  is_admin(local_user_view)?;
+  match local_user_view.local_user.admin_role {
+    Some(AdminRole::FullAdmin) => Ok(()),
+    Some(admin_role) if admin_role == role => Ok(()),
+    _ => Err(LemmyErrorType::MissingAdminRole)?,
+  }
+}
+
+/// Checks that an admin may take action against the target person. Only full admins can act
+/// against other admins.
+pub fn check_admin_target(local_user_view: &LocalUserView, target: &Person) -> LemmyResult<()> {
+  if target.admin && local_user_view.local_user.admin_role != Some(AdminRole::FullAdmin) {
+    Err(LemmyErrorType::MissingAdminRole)?;
+  }
+  Ok(())
+}
+
 pub fn is_top_mod(
   local_user_view: &LocalUserView,
   community_mods: &[CommunityModeratorView],
@@ -132,15 +207,40 @@ pub async fn mark_post_as_unread(
     .with_lemmy_type(LemmyErrorType::CouldntMarkPostAsRead)
 }
 
+/// Users of recently checked login tokens, keyed by token hash. Logging out removes the entry here,
+/// so in other processes a token may stay valid for this long after it was revoked.
+static LOGIN_SESSIONS: Lazy<Cache<String, LocalUserId>> = Lazy::new(|| {
+  Cache::builder()
+    .max_capacity(100_000)
+    .time_to_live(Duration::from_secs(30))
+    .support_invalidation_closures()
+    .build()
+});
+
+/// The user who is logged in with the given token.
+#[tracing::instrument(skip_all)]
+pub async fn local_user_id_from_jwt(
+  jwt: &str,
+  context: &LemmyContext,
+) -> Result<LocalUserId, LemmyError> {
+  let token_hash = hash_session_token(jwt);
+  if let Some(local_user_id) = LOGIN_SESSIONS.get(&token_hash) {
+    return Ok(local_user_id);
+  }
+  let expires = login_session_expires(context.settings());
+  let local_user_id = LoginSession::validate(&mut context.pool(), &token_hash, expires)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotLoggedIn)?;
+  LOGIN_SESSIONS.insert(token_hash, local_user_id).await;
+  Ok(local_user_id)
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn local_user_view_from_jwt(
   jwt: &str,
   context: &LemmyContext,
 ) -> Result<LocalUserView, LemmyError> {
-  let claims = Claims::decode(jwt, &context.secret().jwt_secret)
-    .with_lemmy_type(LemmyErrorType::NotLoggedIn)?
-    .claims;
-  let local_user_id = LocalUserId(claims.sub);
+  let local_user_id = local_user_id_from_jwt(jwt, context).await?;
   let local_user_view = LocalUserView::read(&mut context.pool(), local_user_id).await?;
   check_user_valid(
     local_user_view.person.banned,
@@ -148,8 +248,6 @@ pub async fn local_user_view_from_jwt(
     local_user_view.person.deleted,
   )?;
 
-  check_validator_time(&local_user_view.local_user.validator_time, &claims)?;
-
   Ok(local_user_view)
 }
 
@@ -161,17 +259,195 @@ pub async fn local_user_view_from_jwt_opt(
   local_user_view_from_jwt(jwt?, context).await.ok()
 }
 
-/// Checks if user's token was issued before user's password reset.
-pub fn check_validator_time(
-  validator_time: &NaiveDateTime,
-  claims: &Claims,
+/// Starts a new login session for the user, and returns the token to authenticate with.
+pub async fn create_login_session(
+  local_user_id: LocalUserId,
+  req: &HttpRequest,
+  context: &LemmyContext,
+) -> Result<Sensitive<String>, LemmyError> {
+  let token = generate_session_token()?;
+  let token_hash = hash_session_token(&token);
+  let form = LoginSessionInsertForm {
+    token_hash: token_hash.clone(),
+    local_user_id,
+    ip: req.connection_info().realip_remote_addr().map(String::from),
+    user_agent: req
+      .headers()
+      .get(USER_AGENT)
+      .and_then(|ua| ua.to_str().ok())
+      .map(String::from),
+    expires: login_session_expires(context.settings()),
+  };
+  let history_form = LoginHistoryInsertForm {
+    local_user_id,
+    ip_network: ip_network(form.ip.as_deref()),
+    device: device_name(form.user_agent.as_deref()),
+  };
+  LoginSession::create(&mut context.pool(), &form).await?;
+  LoginHistory::create(&mut context.pool(), &history_form).await?;
+
+  // The new token isn't known to the rate limiter yet, in case the user has an individual limit
+  if let Some(limit_percent) =
+    RateLimitOverride::read_limit_percent(&mut context.pool(), local_user_id).await?
+  {
+    let user_rate_limit = UserRateLimit {
+      local_user_id: local_user_id.0,
+      limit_percent,
+    };
+    context
+      .settings_updated_channel()
+      .set_user_rate_limit(token_hash, user_rate_limit);
+  }
+  Ok(token.into())
+}
+
+/// Ends the login session of the given token.
+pub async fn delete_login_session(jwt: &str, context: &LemmyContext) -> Result<(), LemmyError> {
+  let token_hash = hash_session_token(jwt);
+  LoginSession::delete(&mut context.pool(), &token_hash).await?;
+  LOGIN_SESSIONS.invalidate(&token_hash).await;
+  Ok(())
+}
+
+/// Ends all login sessions of the user, eg after a password change.
+pub async fn delete_all_login_sessions(
+  local_user_id: LocalUserId,
+  context: &LemmyContext,
 ) -> Result<(), LemmyError> {
-  let user_validation_time = validator_time.timestamp();
-  if user_validation_time > claims.iat {
-    Err(LemmyErrorType::NotLoggedIn)?
-  } else {
-    Ok(())
+  LoginSession::delete_for_user(&mut context.pool(), local_user_id).await?;
+  LOGIN_SESSIONS
+    .invalidate_entries_if(move |_, id| *id == local_user_id)
+    .map_err(|e| anyhow::anyhow!("{e}"))?;
+  Ok(())
+}
+
+/// Ends one of the user's login sessions by its id.
+pub async fn revoke_login_session(
+  login_session_id: LoginSessionId,
+  local_user_id: LocalUserId,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let session = LoginSession::revoke(&mut context.pool(), login_session_id, local_user_id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindLoginSession)?;
+  LOGIN_SESSIONS.invalidate(&session.token_hash).await;
+  Ok(())
+}
+
+/// Emails the user if the session of the given token comes from a device or network which they
+/// haven't logged in from recently, so that they can revoke it if the login wasn't them.
+pub async fn send_login_alert_email(
+  local_user_view: &LocalUserView,
+  jwt: &str,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let local_user = &local_user_view.local_user;
+  let (true, Some(email)) = (local_user.email_login_alerts, &local_user.email) else {
+    return Ok(());
+  };
+  let token_hash = hash_session_token(jwt);
+  let (new, others): (Vec<_>, Vec<_>) = LoginSession::list(&mut context.pool(), local_user.id)
+    .await?
+    .into_iter()
+    .partition(|s| s.token_hash == token_hash);
+  let Some(new) = new.first() else {
+    return Ok(());
+  };
+  let form = LoginHistoryInsertForm {
+    local_user_id: local_user.id,
+    ip_network: ip_network(new.ip.as_deref()),
+    device: device_name(new.user_agent.as_deref()),
+  };
+  // Sessions from before the login history was recorded still count as known
+  let is_known_device = LoginHistory::is_known(&mut context.pool(), &form, new.published).await?
+    || others.iter().any(|s| {
+      ip_network(s.ip.as_deref()) == form.ip_network
+        && device_name(s.user_agent.as_deref()) == form.device
+    });
+  if is_known_device {
+    return Ok(());
   }
+
+  let settings = context.settings();
+  let lang = get_interface_language_from_settings(local_user_view);
+  let subject = lang.login_alert_subject(&settings.hostname);
+  let body = lang.login_alert_body(
+    new.id.0,
+    new.ip.as_deref().unwrap_or("unknown"),
+    form
+      .device
+      .as_deref()
+      .or(new.user_agent.as_deref())
+      .unwrap_or("unknown"),
+    format!("{}/settings", settings.get_protocol_and_hostname()),
+  );
+  send_email(
+    &subject,
+    email,
+    &local_user_view.person.name,
+    &body,
+    settings,
+  )
+  .await
+}
+
+/// The network of an IP address, which is compared instead of the exact address because many
+/// connections change their address within the same network. This is the /24 for IPv4 and the
+/// /48 for IPv6.
+fn ip_network(ip: Option<&str>) -> Option<String> {
+  let ip = ip?;
+  let addr = ip
+    .parse::<SocketAddr>()
+    .map(|a| a.ip())
+    .or_else(|_| ip.parse::<IpAddr>())
+    .ok()?;
+  Some(match addr {
+    IpAddr::V4(v4) => {
+      let [a, b, c, _] = v4.octets();
+      format!("{a}.{b}.{c}.0/24")
+    }
+    IpAddr::V6(v6) => {
+      let [a, b, c, ..] = v6.segments();
+      format!("{a:x}:{b:x}:{c:x}::/48")
+    }
+  })
+}
+
+/// A coarse description of the device from its user agent, like "Firefox on Linux". Unlike the
+/// exact user agent this doesn't change with every browser update.
+fn device_name(user_agent: Option<&str>) -> Option<String> {
+  let user_agent = user_agent?;
+  let os = [
+    ("Windows", "Windows"),
+    ("Android", "Android"),
+    ("iPhone", "iOS"),
+    ("iPad", "iOS"),
+    ("Mac OS X", "macOS"),
+    ("CrOS", "ChromeOS"),
+    ("Linux", "Linux"),
+  ]
+  .into_iter()
+  .find(|(token, _)| user_agent.contains(token))
+  .map(|(_, os)| os);
+  let browser = [
+    ("Edg/", "Edge"),
+    ("OPR/", "Opera"),
+    ("Firefox/", "Firefox"),
+    ("Chrome/", "Chrome"),
+    ("Safari/", "Safari"),
+  ]
+  .into_iter()
+  .find(|(token, _)| user_agent.contains(token))
+  .map(|(_, browser)| browser.to_string())
+  .or_else(|| {
+    // Apps usually send their own product name, like "Jerboa/0.0.40"
+    let product = user_agent.split(['/', ' ']).next()?;
+    (!product.is_empty()).then(|| product.to_string())
+  })?;
+  Some(match os {
+    Some(os) => format!("{browser} on {os}"),
+    None => browser,
+  })
 }
 
 pub fn check_user_valid(
@@ -248,13 +524,410 @@ pub async fn check_person_block(
 }
 
 #[tracing::instrument(skip_all)]
-pub fn check_downvotes_enabled(score: i16, local_site: &LocalSite) -> Result<(), LemmyError> {
-  if score == -1 && !local_site.enable_downvotes {
+pub fn check_downvotes_enabled(
+  score: i16,
+  local_site: &LocalSite,
+  community: &Community,
+) -> Result<(), LemmyError> {
+  if score == -1 && (!local_site.enable_downvotes || !community.enable_downvotes) {
     Err(LemmyErrorType::DownvotesAreDisabled)?;
   }
   Ok(())
 }
 
+/// Checks if a post with the given url is allowed by the post type restriction of the community.
+pub fn check_community_post_type(community: &Community, url: Option<&Url>) -> LemmyResult<()> {
+  match (community.post_type_restriction, url) {
+    (PostTypeRestriction::TextOnly, Some(_)) => Err(LemmyErrorType::OnlyTextPostsInCommunity)?,
+    (PostTypeRestriction::LinkOnly, None) => Err(LemmyErrorType::OnlyLinkPostsInCommunity)?,
+    (PostTypeRestriction::NoImages, Some(url)) if is_image_url(url) => {
+      Err(LemmyErrorType::NoImagePostsInCommunity)?
+    }
+    _ => Ok(()),
+  }
+}
+
+/// Makes sure that content is in one of the languages of the community. Content without a language
+/// is only accepted if the community allows it.
+pub async fn check_community_language(
+  community: &Community,
+  language_id: Option<newtypes::LanguageId>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  match language_id {
+    None | Some(UNDETERMINED_ID) if !community.allow_undetermined_language => {
+      Err(LemmyErrorType::LanguageNotAllowed)?
+    }
+    None | Some(UNDETERMINED_ID) => Ok(()),
+    Some(language_id) => {
+      CommunityLanguage::is_allowed_community_language(pool, Some(language_id), community.id).await
+    }
+  }
+}
+
+/// Checks if a url is covered by the blocklist, either by its domain or by a url prefix. Domains
+/// also cover their subdomains, and url prefixes only match whole path segments.
+pub fn url_is_blocked(url: &Url, blocklist: &[BlockedUrl]) -> bool {
+  let Some(host) = url.host_str() else {
+    return false;
+  };
+  let host = host.to_lowercase();
+  let path = url.path();
+  blocklist.iter().any(|blocked| {
+    let (domain, prefix) = match blocked.url.find('/') {
+      Some(i) => blocked.url.split_at(i),
+      None => (blocked.url.as_str(), ""),
+    };
+    let domain_matches = host == domain || host.ends_with(&format!(".{domain}"));
+    let path_matches = prefix.is_empty()
+      || path == prefix
+      || path
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.starts_with('/'));
+    domain_matches && path_matches
+  })
+}
+
+/// Makes sure that a post doesn't link to a blocked domain or url.
+pub async fn check_url_not_blocked(url: Option<&Url>, pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  if let Some(url) = url {
+    if url_is_blocked(url, &BlockedUrl::get_all(pool).await?) {
+      Err(LemmyErrorType::BlockedUrl)?
+    }
+  }
+  Ok(())
+}
+
+/// Checks if the domain of an email address is one of the given domains, or a subdomain of them.
+pub fn email_domain_matches(email: &str, domains: &[String]) -> bool {
+  let Some((_, domain)) = email.rsplit_once('@') else {
+    return false;
+  };
+  let domain = domain.trim().to_lowercase();
+  domains
+    .iter()
+    .any(|d| domain == *d || domain.ends_with(&format!(".{d}")))
+}
+
+/// Makes sure that an email address can be used for signups. If an allowlist is set, signups
+/// without an email address are refused too.
+pub async fn check_email_domain_allowed(
+  email: Option<&str>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let allowed = EmailDomain::list(pool, true).await?;
+  let Some(email) = email else {
+    return if allowed.is_empty() {
+      Ok(())
+    } else {
+      Err(LemmyErrorType::EmailRequired)?
+    };
+  };
+  if !allowed.is_empty() && !email_domain_matches(email, &allowed) {
+    Err(LemmyErrorType::EmailDomainNotAllowed)?
+  }
+  if email_domain_matches(email, &EmailDomain::list(pool, false).await?) {
+    Err(LemmyErrorType::EmailDomainBlocked)?
+  }
+  Ok(())
+}
+
+const WORD_FILTER_REASON: &str = "Matched a community word filter";
+
+/// Checks content against the word filters of its community. Content matching a rejecting filter
+/// is refused, otherwise the most severe matching action is returned so it can be applied once the
+/// content is stored.
+pub async fn check_community_word_filters(
+  community_id: CommunityId,
+  texts: &[&str],
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<Option<WordFilterAction>> {
+  let word_filters = CommunityWordFilter::for_community(pool, community_id)
+    .await?
+    .into_iter()
+    .filter_map(|f| Some((build_and_check_regex(&Some(&f.pattern)).ok()??, f.action)))
+    .collect::<Vec<_>>();
+  match check_word_filters(texts, &word_filters) {
+    Some(WordFilterAction::Reject) => Err(LemmyErrorType::CommunityWordFilterMatch)?,
+    action => Ok(action),
+  }
+}
+
+/// Removes or reports a post which matched a community word filter, in the name of the site owner.
+pub async fn apply_post_word_filter_action(
+  post: &Post,
+  action: Option<WordFilterAction>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let Some(action) = action else {
+    return Ok(());
+  };
+  let Some(owner) = PersonView::admins(pool).await?.into_iter().next() else {
+    return Ok(());
+  };
+  if action == WordFilterAction::Remove {
+    remove_post_as(post, owner.person.id, WORD_FILTER_REASON, pool).await
+  } else {
+    report_post_as(post, owner.person.id, WORD_FILTER_REASON, pool).await
+  }
+}
+
+/// Removes or reports a comment which matched a community word filter, in the name of the site
+/// owner.
+pub async fn apply_comment_word_filter_action(
+  comment: &Comment,
+  action: Option<WordFilterAction>,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let Some(action) = action else {
+    return Ok(());
+  };
+  let Some(owner) = PersonView::admins(pool).await?.into_iter().next() else {
+    return Ok(());
+  };
+  if action == WordFilterAction::Remove {
+    remove_comment_as(comment, owner.person.id, WORD_FILTER_REASON, pool).await
+  } else {
+    report_comment_as(comment, owner.person.id, WORD_FILTER_REASON, pool).await
+  }
+}
+
+const HELD_FOR_REVIEW_REASON: &str =
+  "Held for review, the author doesn't meet the posting requirements of the community";
+
+/// Makes sure that non-mods meet the minimum account age and karma which the community requires
+/// for posting and commenting.
+pub async fn check_community_posting_thresholds(
+  community: &Community,
+  person: &Person,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let min_age = community.min_account_age_days;
+  let min_site_karma = community.min_site_karma;
+  let min_community_karma = community.min_community_karma;
+  if (min_age == 0 && min_site_karma == 0 && min_community_karma == 0)
+    || CommunityView::is_mod_or_admin(pool, person.id, community.id).await?
+  {
+    return Ok(());
+  }
+  let account_age = naive_now() - person.published;
+  if account_age < chrono::Duration::days(min_age.into()) {
+    Err(LemmyErrorType::AccountTooNewForCommunity(min_age))?;
+  }
+  if min_site_karma > 0 {
+    let counts = PersonAggregates::read(pool, person.id).await?;
+    if counts.post_score + counts.comment_score < min_site_karma.into() {
+      Err(LemmyErrorType::NotEnoughSiteKarma(min_site_karma))?;
+    }
+  }
+  if min_community_karma > 0 {
+    let score = PersonAggregates::community_score(pool, person.id, community.id).await?;
+    if score < min_community_karma.into() {
+      Err(LemmyErrorType::NotEnoughCommunityKarma(min_community_karma))?;
+    }
+  }
+  Ok(())
+}
+
+/// Makes sure that non-admins are allowed to create communities, and meet the minimum account age
+/// and karma which the site requires for it.
+pub async fn check_community_creation_allowed(
+  local_user_view: &LocalUserView,
+  local_site: &LocalSite,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let person = &local_user_view.person;
+  if person.admin {
+    return Ok(());
+  }
+  if local_site.community_creation_admin_only {
+    Err(LemmyErrorType::OnlyAdminsCanCreateCommunities)?;
+  }
+  let min_age = local_site.community_creation_min_account_age_days;
+  if naive_now() - person.published < chrono::Duration::days(min_age.into()) {
+    Err(LemmyErrorType::AccountTooNewToCreateCommunity(min_age))?;
+  }
+  let min_karma = local_site.community_creation_min_karma;
+  if min_karma > 0 {
+    let counts = PersonAggregates::read(pool, person.id).await?;
+    if counts.post_score + counts.comment_score < min_karma.into() {
+      Err(LemmyErrorType::NotEnoughKarmaToCreateCommunity(min_karma))?;
+    }
+  }
+  Ok(())
+}
+
+/// Whether the author of federated content doesn't meet the posting requirements of the community.
+pub async fn is_below_community_posting_thresholds(
+  community: &Community,
+  person: &Person,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<bool> {
+  match check_community_posting_thresholds(community, person, pool).await {
+    Ok(()) => Ok(false),
+    Err(e)
+      if matches!(
+        e.error_type,
+        LemmyErrorType::AccountTooNewForCommunity(_)
+          | LemmyErrorType::NotEnoughSiteKarma(_)
+          | LemmyErrorType::NotEnoughCommunityKarma(_)
+      ) =>
+    {
+      Ok(true)
+    }
+    Err(e) => Err(e),
+  }
+}
+
+/// Removes a federated post whose author doesn't meet the posting requirements of the community,
+/// and reports it so that mods can review and restore it.
+pub async fn hold_post_for_review(post: &Post, pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  let Some(owner) = PersonView::admins(pool).await?.into_iter().next() else {
+    return Ok(());
+  };
+  remove_post_as(post, owner.person.id, HELD_FOR_REVIEW_REASON, pool).await?;
+  report_post_as(post, owner.person.id, HELD_FOR_REVIEW_REASON, pool).await
+}
+
+/// Removes a federated comment whose author doesn't meet the posting requirements of the
+/// community, and reports it so that mods can review and restore it.
+pub async fn hold_comment_for_review(comment: &Comment, pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  let Some(owner) = PersonView::admins(pool).await?.into_iter().next() else {
+    return Ok(());
+  };
+  remove_comment_as(comment, owner.person.id, HELD_FOR_REVIEW_REASON, pool).await?;
+  report_comment_as(comment, owner.person.id, HELD_FOR_REVIEW_REASON, pool).await
+}
+
+async fn remove_post_as(
+  post: &Post,
+  mod_person_id: PersonId,
+  reason: &str,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let form = PostUpdateForm::builder().removed(Some(true)).build();
+  Post::update(pool, post.id, &form).await?;
+  let form = ModRemovePostForm {
+    mod_person_id,
+    post_id: post.id,
+    reason: Some(reason.to_string()),
+    removed: Some(true),
+  };
+  ModRemovePost::create(pool, &form).await?;
+  Ok(())
+}
+
+async fn report_post_as(
+  post: &Post,
+  creator_id: PersonId,
+  reason: &str,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let form = PostReportForm {
+    creator_id,
+    post_id: post.id,
+    original_post_name: post.name.clone(),
+    original_post_url: post.url.clone(),
+    original_post_body: post.body.clone(),
+    reason: reason.to_string(),
+    reason_id: None,
+    rule_id: None,
+  };
+  PostReport::report(pool, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateReport)?;
+  Ok(())
+}
+
+async fn remove_comment_as(
+  comment: &Comment,
+  mod_person_id: PersonId,
+  reason: &str,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let form = CommentUpdateForm::builder().removed(Some(true)).build();
+  Comment::update(pool, comment.id, &form).await?;
+  let form = ModRemoveCommentForm {
+    mod_person_id,
+    comment_id: comment.id,
+    reason: Some(reason.to_string()),
+    removed: Some(true),
+  };
+  ModRemoveComment::create(pool, &form).await?;
+  Ok(())
+}
+
+async fn report_comment_as(
+  comment: &Comment,
+  creator_id: PersonId,
+  reason: &str,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let form = CommentReportForm {
+    creator_id,
+    comment_id: comment.id,
+    original_comment_text: comment.content.clone(),
+    reason: reason.to_string(),
+    reason_id: None,
+    rule_id: None,
+  };
+  CommentReport::report(pool, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateReport)?;
+  Ok(())
+}
+
+/// Makes sure that non-mods wait long enough between posts in a community with slow mode.
+pub async fn check_post_slow_mode(
+  community: &Community,
+  person_id: PersonId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let interval = community.post_slow_mode_seconds;
+  if interval == 0 || CommunityView::is_mod_or_admin(pool, person_id, community.id).await? {
+    return Ok(());
+  }
+  let latest = Post::latest_published_for_creator(pool, person_id, community.id).await?;
+  check_slow_mode_elapsed(interval, latest)
+}
+
+/// Makes sure that non-mods wait long enough between comments in a community with slow mode.
+pub async fn check_comment_slow_mode(
+  community: &Community,
+  person_id: PersonId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  let interval = community.comment_slow_mode_seconds;
+  if interval == 0 || CommunityView::is_mod_or_admin(pool, person_id, community.id).await? {
+    return Ok(());
+  }
+  let latest = Comment::latest_published_for_creator(pool, person_id, community.id).await?;
+  check_slow_mode_elapsed(interval, latest)
+}
+
+fn check_slow_mode_elapsed(interval: i32, latest: Option<NaiveDateTime>) -> LemmyResult<()> {
+  if let Some(latest) = latest {
+    let next_allowed = latest + chrono::Duration::seconds(interval.into());
+    let remaining = (next_allowed - naive_now()).num_seconds();
+    if remaining > 0 {
+      Err(LemmyErrorType::SlowModeActive(remaining))?;
+    }
+  }
+  Ok(())
+}
+
+/// Guesses from the file extension if a url points directly to an image.
+fn is_image_url(url: &Url) -> bool {
+  const IMAGE_EXTENSIONS: [&str; 9] = [
+    "apng", "avif", "bmp", "gif", "jpeg", "jpg", "png", "svg", "webp",
+  ];
+  url
+    .path()
+    .rsplit_once('.')
+    .map(|(_, ext)| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+    .unwrap_or(false)
+}
+
 #[tracing::instrument(skip_all)]
 pub fn check_private_instance(
   local_user_view: &Option<LocalUserView>,
@@ -290,6 +963,20 @@ pub async fn build_federated_instances(
 }
 
 /// Checks the password length
+/// Returns the cursor for the page after a cursor-paginated comment tree fetch. A full page means
+/// there may be more comments, so the walk continues after the last one.
+pub fn next_comment_page_cursor(
+  comments: &[CommentView],
+  limit: Option<i64>,
+) -> Result<Option<String>, LemmyError> {
+  let (limit, _) = limit_and_offset(None, limit)?;
+  if comments.len() as i64 == limit {
+    Ok(comments.last().map(|c| c.comment.path.0.clone()))
+  } else {
+    Ok(None)
+  }
+}
+
 pub fn password_length_check(pass: &str) -> Result<(), LemmyError> {
   if !(10..=60).contains(&pass.chars().count()) {
     Err(LemmyErrorType::InvalidPassword)?
@@ -307,13 +994,36 @@ pub fn honeypot_check(honeypot: &Option<String>) -> Result<(), LemmyError> {
   }
 }
 
+/// Notifications which users can turn off individually
+#[derive(Clone, Copy, Debug)]
+pub enum NotificationType {
+  CommentReply,
+  PostReply,
+  Mention,
+  PrivateMessage,
+}
+
+/// Whether the user wants to be notified about this type of event
+fn notification_enabled(local_user: &LocalUser, notification_type: NotificationType) -> bool {
+  match notification_type {
+    NotificationType::CommentReply => local_user.notify_comment_replies,
+    NotificationType::PostReply => local_user.notify_post_replies,
+    NotificationType::Mention => local_user.notify_mentions,
+    NotificationType::PrivateMessage => local_user.notify_private_messages,
+  }
+}
+
 pub async fn send_email_to_user(
   local_user_view: &LocalUserView,
+  notification_type: NotificationType,
   subject: &str,
   body: &str,
   settings: &Settings,
 ) {
-  if local_user_view.person.banned || !local_user_view.local_user.send_notifications_to_email {
+  if local_user_view.person.banned
+    || !local_user_view.local_user.send_notifications_to_email
+    || !notification_enabled(&local_user_view.local_user, notification_type)
+  {
     return;
   }
 
@@ -354,6 +1064,35 @@ pub async fn send_password_reset_email(
   send_email(subject, email, &user.person.name, body, settings).await
 }
 
+/// How many minutes a login link sent by email stays valid
+const MAGIC_LINK_EXPIRY_MINUTES: i64 = 15;
+
+/// Emails the user a link which logs them in without their password.
+pub async fn send_magic_link_email(
+  user: &LocalUserView,
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let token = generate_session_token()?;
+  let form = MagicLoginTokenForm {
+    token_hash: hash_session_token(&token),
+    local_user_id: user.local_user.id,
+    expires: naive_now() + chrono::Duration::minutes(MAGIC_LINK_EXPIRY_MINUTES),
+  };
+  MagicLoginToken::create(pool, &form).await?;
+
+  let email = &user.local_user.email.clone().expect("email");
+  let lang = get_interface_language(user);
+  let subject = &lang.magic_link_subject(&settings.hostname);
+  let login_link = format!(
+    "{}/magic_login/{}",
+    settings.get_protocol_and_hostname(),
+    &token
+  );
+  let body = &lang.magic_link_body(login_link, MAGIC_LINK_EXPIRY_MINUTES);
+  send_email(subject, email, &user.person.name, body, settings).await
+}
+
 /// Send a verification email
 pub async fn send_verification_email(
   user: &LocalUserView,
@@ -417,10 +1156,47 @@ pub fn local_site_rate_limit_to_rate_limit_config(
   }
 }
 
+/// Passes the individual rate limits of users to the rate limiter, after they or the logins of
+/// these users were changed.
+pub async fn update_user_rate_limits(
+  pool: &mut DbPool<'_>,
+  rate_limit_cell: &RateLimitCell,
+) -> LemmyResult<()> {
+  let by_token_hash = RateLimitOverride::list_by_token_hash(pool)
+    .await?
+    .into_iter()
+    .map(|(token_hash, local_user_id, limit_percent)| {
+      let user_rate_limit = UserRateLimit {
+        local_user_id: local_user_id.0,
+        limit_percent,
+      };
+      (token_hash, user_rate_limit)
+    })
+    .collect();
+  rate_limit_cell.set_user_rate_limits(by_token_hash);
+  Ok(())
+}
+
 pub fn local_site_to_slur_regex(local_site: &LocalSite) -> Option<Regex> {
   build_slur_regex(local_site.slur_filter_regex.as_deref())
 }
 
+/// Builds the slur filter for posts and comments in the given language and community, which applies
+/// on top of the site slur filter. Filters are read from the database every time, so changes apply
+/// immediately.
+pub async fn slur_filter_regex(
+  language_id: Option<newtypes::LanguageId>,
+  community_id: CommunityId,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<Option<Regex>> {
+  let slur_filters = SlurFilter::for_content(pool, language_id, community_id).await?;
+  let patterns = slur_filters
+    .iter()
+    .map(|f| f.pattern.as_str())
+    .collect::<Vec<_>>();
+  Ok(build_slur_filter_regex(&patterns))
+}
+
 pub fn local_site_opt_to_slur_regex(local_site: &Option<LocalSite>) -> Option<Regex> {
   local_site
     .as_ref()
@@ -446,6 +1222,38 @@ pub async fn send_application_approved_email(
   send_email(&subject, email, &user.person.name, &body, settings).await
 }
 
+/// Lets the creator of a community application know whether it was approved or denied, if they
+/// have an email address.
+pub async fn send_community_application_email(
+  creator: &LocalUserView,
+  application: &CommunityApplication,
+  approved: bool,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let Some(email) = &creator.local_user.email else {
+    return Ok(());
+  };
+  let lang = get_interface_language(creator);
+  let community_name = &application.name;
+  let (subject, body) = if approved {
+    let community_link = format!(
+      "{}/c/{community_name}",
+      settings.get_protocol_and_hostname()
+    );
+    (
+      lang.community_application_approved_subject(community_name, &settings.hostname),
+      lang.community_application_approved_body(community_name, community_link),
+    )
+  } else {
+    let deny_reason = application.deny_reason.as_deref().unwrap_or_default();
+    (
+      lang.community_application_denied_subject(community_name, &settings.hostname),
+      lang.community_application_denied_body(community_name, deny_reason),
+    )
+  };
+  send_email(&subject, email, &creator.person.name, &body, settings).await
+}
+
 /// Send a new applicant email notification to all admins
 pub async fn send_new_applicant_email_to_admins(
   applicant_username: &str,
@@ -492,36 +1300,95 @@ pub async fn send_new_report_email_to_admins(
   Ok(())
 }
 
+/// Sends the mod queue digest to the mods and admins who enabled it, if anything was added to
+/// their queue since the last digest. Returns the number of sent emails.
+pub async fn send_mod_queue_digests(context: &LemmyContext) -> LemmyResult<usize> {
+  let settings = context.settings();
+  let recipients = LocalUserView::list_mod_queue_digest_recipients(&mut context.pool()).await?;
+  let reports_link = format!("{}/reports", settings.get_protocol_and_hostname());
+
+  let mut sent = 0;
+  for recipient in &recipients {
+    let now = naive_now();
+    // The first digest covers the last day
+    let since = recipient
+      .local_user
+      .mod_queue_digest_sent
+      .unwrap_or_else(|| now - chrono::Duration::days(1));
+    let digest = ModQueueDigest::read(
+      &mut context.pool(),
+      recipient.person.id,
+      recipient.person.admin,
+      since,
+      WORD_FILTER_REASON,
+    )
+    .await?;
+
+    if let (false, Some(email)) = (digest.is_empty(), &recipient.local_user.email) {
+      let lang = get_interface_language_from_settings(recipient);
+      let subject = lang.mod_queue_digest_subject(&settings.hostname);
+      let body = lang.mod_queue_digest_body(
+        digest.automod_holds,
+        digest.registration_applications,
+        digest.reports,
+        &reports_link,
+      );
+      match send_email(&subject, email, &recipient.person.name, &body, settings).await {
+        Ok(()) => sent += 1,
+        Err(e) => warn!("Failed to send mod queue digest: {e}"),
+      }
+    }
+
+    let form = LocalUserUpdateForm::builder()
+      .mod_queue_digest_sent(Some(Some(now)))
+      .build();
+    LocalUser::update(&mut context.pool(), recipient.local_user.id, &form).await?;
+  }
+  Ok(sent)
+}
+
 pub async fn check_registration_application(
   local_user_view: &LocalUserView,
   local_site: &LocalSite,
   pool: &mut DbPool<'_>,
 ) -> Result<(), LemmyError> {
-  if (local_site.registration_mode == RegistrationMode::RequireApplication
-    || local_site.registration_mode == RegistrationMode::Closed)
-    && !local_user_view.local_user.accepted_application
-    && !local_user_view.person.admin
-  {
-    // Fetch the registration, see if its denied
-    let local_user_id = local_user_view.local_user.id;
-    let registration = RegistrationApplication::find_by_local_user_id(pool, local_user_id).await?;
-    if let Some(deny_reason) = registration.deny_reason {
+  if local_user_view.local_user.accepted_application || local_user_view.person.admin {
+    return Ok(());
+  }
+  // Fetch the registration, see if its denied
+  let local_user_id = local_user_view.local_user.id;
+  let registration = RegistrationApplication::find_by_local_user_id(pool, local_user_id).await?;
+  match registration {
+    Some(RegistrationApplication {
+      deny_reason: Some(deny_reason),
+      ..
+    }) => {
       let lang = get_interface_language(local_user_view);
       let registration_denied_message = format!("{}: {}", lang.registration_denied(), deny_reason);
-      return Err(LemmyErrorType::RegistrationDenied(
+      Err(LemmyErrorType::RegistrationDenied(
         registration_denied_message,
-      ))?;
-    } else {
-      return Err(LemmyErrorType::RegistrationApplicationIsPending)?;
+      ))?
     }
+    Some(_) => Err(LemmyErrorType::RegistrationApplicationIsPending)?,
+    // With open registration, users are only held back if their signup was flagged for review.
+    // Users who registered before applications existed have none, and can always log in.
+    None
+      if local_site.registration_mode == RegistrationMode::RequireApplication
+        || local_site.registration_mode == RegistrationMode::Closed =>
+    {
+      Err(LemmyErrorType::RegistrationApplicationIsPending)?
+    }
+    None => Ok(()),
   }
-  Ok(())
 }
 
 pub fn check_private_instance_and_federation_enabled(
   local_site: &LocalSite,
 ) -> Result<(), LemmyError> {
-  if local_site.private_instance && local_site.federation_enabled {
+  if local_site.private_instance
+    && local_site.federation_enabled
+    && !local_site.private_instance_federation
+  {
     Err(LemmyErrorType::CantEnablePrivateInstanceAndFederationTogether)?;
   }
   Ok(())
@@ -573,6 +1440,52 @@ pub async fn purge_image_posts_for_community(
   Ok(())
 }
 
+/// Finds the aliases of images on the pictrs of this instance which are linked in the text.
+pub fn local_image_aliases(text: &str, settings: &Settings) -> Vec<String> {
+  let prefix = format!("{}/pictrs/image/", settings.get_protocol_and_hostname());
+  text
+    .match_indices(&prefix)
+    .filter_map(|(start, _)| {
+      let alias = text
+        .get(start + prefix.len()..)?
+        .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')))
+        .next()?;
+      (!alias.is_empty()).then(|| alias.to_string())
+    })
+    .collect()
+}
+
+/// Marks the local images which are used in a post, so that they aren't purged as orphans.
+pub async fn link_post_images(
+  post: &Post,
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let text = format!(
+    "{} {}",
+    post.url.as_ref().map(|u| u.as_str()).unwrap_or_default(),
+    post.body.as_deref().unwrap_or_default()
+  );
+  let aliases = local_image_aliases(&text, settings);
+  if !aliases.is_empty() {
+    LocalImage::link_to_post(pool, &aliases, post.id).await?;
+  }
+  Ok(())
+}
+
+/// Marks the local images which are used in a comment, so that they aren't purged as orphans.
+pub async fn link_comment_images(
+  comment: &Comment,
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let aliases = local_image_aliases(&comment.content, settings);
+  if !aliases.is_empty() {
+    LocalImage::link_to_comment(pool, &aliases, comment.id).await?;
+  }
+  Ok(())
+}
+
 pub async fn remove_user_data(
   banned_person_id: PersonId,
   pool: &mut DbPool<'_>,
@@ -687,6 +1600,79 @@ pub async fn remove_user_data_in_community(
   Ok(())
 }
 
+/// Records a strike against a user in a community. If the community has a strike threshold and the
+/// user reaches it, they are temporarily banned from the community.
+pub async fn add_community_strike(
+  form: &CommunityStrikeForm,
+  mod_person: &Person,
+  auth: &Sensitive<String>,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  CommunityStrike::create(&mut context.pool(), form).await?;
+
+  let community = Community::read(&mut context.pool(), form.community_id).await?;
+  if community.strike_ban_threshold <= 0 {
+    return Ok(());
+  }
+  let strikes =
+    CommunityStrike::count_for_person(&mut context.pool(), form.community_id, form.person_id)
+      .await?;
+  let already_banned =
+    CommunityPersonBanView::get(&mut context.pool(), form.person_id, form.community_id)
+      .await
+      .is_ok();
+  if strikes < i64::from(community.strike_ban_threshold) || already_banned {
+    return Ok(());
+  }
+
+  let expires = naive_now() + chrono::Duration::days(community.strike_ban_days.into());
+  let ban_form = CommunityPersonBanForm {
+    community_id: form.community_id,
+    person_id: form.person_id,
+    expires: Some(Some(expires)),
+  };
+  CommunityPersonBan::ban(&mut context.pool(), &ban_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CommunityUserAlreadyBanned)?;
+
+  let follower_form = CommunityFollowerForm {
+    community_id: form.community_id,
+    person_id: form.person_id,
+    pending: false,
+  };
+  CommunityFollower::unfollow(&mut context.pool(), &follower_form)
+    .await
+    .ok();
+
+  let reason = Some(format!("Reached {strikes} strikes"));
+  let mod_form = ModBanFromCommunityForm {
+    mod_person_id: mod_person.id,
+    other_person_id: form.person_id,
+    community_id: form.community_id,
+    reason: reason.clone(),
+    banned: Some(true),
+    expires: Some(expires),
+  };
+  ModBanFromCommunity::create(&mut context.pool(), &mod_form).await?;
+
+  let banned_person = Person::read(&mut context.pool(), form.person_id).await?;
+  let ban = BanFromCommunity {
+    community_id: form.community_id,
+    person_id: form.person_id,
+    ban: true,
+    remove_data: None,
+    reason,
+    expires: Some(expires.timestamp()),
+    strike: None,
+    auth: auth.clone(),
+  };
+  ActivityChannel::submit_activity(
+    SendActivityData::BanFromCommunity(mod_person.clone(), form.community_id, banned_person, ban),
+    context,
+  )
+  .await
+}
+
 pub async fn delete_user_account(
   person_id: PersonId,
   pool: &mut DbPool<'_>,
@@ -728,6 +1714,66 @@ pub async fn delete_user_account(
   Ok(())
 }
 
+/// Replaces the keypair of a local person, and publishes the new public key to other instances.
+/// The previous key is recorded by a database trigger, so that signatures which were made shortly
+/// before the rotation can still be verified.
+pub async fn rotate_person_keys(person: &Person, context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let keypair = generate_actor_keypair()?;
+  let form = PersonUpdateForm::builder()
+    .public_key(Some(keypair.public_key))
+    .private_key(Some(Some(keypair.private_key)))
+    .build();
+  let person = Person::update(&mut context.pool(), person.id, &form).await?;
+  ActivityChannel::submit_activity(SendActivityData::UpdatePerson(person), context).await
+}
+
+/// Replaces the keypair of a local community, and announces the new public key to its followers.
+pub async fn rotate_community_keys(
+  community: &Community,
+  actor: Person,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let keypair = generate_actor_keypair()?;
+  let form = CommunityUpdateForm::builder()
+    .public_key(Some(keypair.public_key))
+    .private_key(Some(Some(keypair.private_key)))
+    .build();
+  let community = Community::update(&mut context.pool(), community.id, &form).await?;
+  ActivityChannel::submit_activity(SendActivityData::UpdateCommunity(actor, community), context)
+    .await
+}
+
+/// Replaces the keypair of the instance actor, and publishes the new public key to other instances.
+pub async fn rotate_site_keys(site: &Site, context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let keypair = generate_actor_keypair()?;
+  let form = SiteUpdateForm::builder()
+    .public_key(Some(keypair.public_key))
+    .private_key(Some(Some(keypair.private_key)))
+    .build();
+  let site = Site::update(&mut context.pool(), site.id, &form).await?;
+  ActivityChannel::submit_activity(SendActivityData::UpdateSite(site), context).await
+}
+
+/// Fetches an external blocklist and syncs it into the federation blocklist. If that fails, the
+/// error is stored with the subscription so that admins can see it, and the blocklist stays as it
+/// was.
+pub async fn sync_blocklist_subscription(
+  subscription: &BlocklistSubscription,
+  context: &LemmyContext,
+) -> LemmyResult<BlocklistSubscription> {
+  let sync = async {
+    let url = Url::parse(&subscription.url)?;
+    let entries = fetch_blocklist(context.client(), &url).await?;
+    FederationBlockList::sync_subscription(&mut context.pool(), subscription.id, entries).await?;
+    Ok::<_, LemmyError>(())
+  };
+  let error = sync.await.err().map(|e| e.to_string());
+  if let Some(error) = &error {
+    warn!("Failed to sync blocklist {}: {error}", subscription.url);
+  }
+  Ok(BlocklistSubscription::update_sync_status(&mut context.pool(), subscription.id, error).await?)
+}
+
 pub enum EndpointType {
   Community,
   Person,
@@ -811,12 +1857,63 @@ pub fn sanitize_html_opt(data: &Option<String>) -> Option<String> {
   data.as_ref().map(|d| sanitize_html(d))
 }
 
+/// Removes the tags which aren't allowed in the config from incoming federated html, before it is
+/// converted to markdown. The text inside of removed tags is kept, except for scripts and styles.
+pub fn sanitize_incoming_html(html: &str, config: &IncomingHtmlConfig) -> String {
+  let clean_content_tags = ["script", "style"];
+  let tags: HashSet<&str> = config
+    .allowed_tags
+    .iter()
+    .map(String::as_str)
+    .filter(|t| !clean_content_tags.contains(t))
+    .collect();
+  ammonia::Builder::default()
+    .tags(tags)
+    .clean_content_tags(clean_content_tags.into())
+    .clean(html)
+    .to_string()
+}
+
 #[cfg(test)]
 mod tests {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
-  use crate::utils::{honeypot_check, password_length_check, sanitize_html};
+  use crate::utils::{
+    check_admin_role,
+    check_admin_target,
+    check_registration_application,
+    check_slow_mode_elapsed,
+    device_name,
+    email_domain_matches,
+    honeypot_check,
+    ip_network,
+    is_image_url,
+    password_length_check,
+    sanitize_html,
+    sanitize_incoming_html,
+    url_is_blocked,
+  };
+  use lemmy_db_schema::{
+    newtypes::BlockedUrlId,
+    source::{
+      blocked_url::BlockedUrl,
+      instance::Instance,
+      local_site::{LocalSite, LocalSiteInsertForm},
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+      registration_application::{RegistrationApplication, RegistrationApplicationInsertForm},
+      site::{Site, SiteInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+    AdminRole,
+    RegistrationMode,
+  };
+  use lemmy_db_views::structs::LocalUserView;
+  use lemmy_utils::{error::LemmyErrorType, settings::structs::IncomingHtmlConfig};
+  use serial_test::serial;
+  use url::Url;
 
   #[test]
   #[rustfmt::skip]
@@ -835,6 +1932,120 @@ mod tests {
     assert!(honeypot_check(&Some("message".to_string())).is_err());
   }
 
+  #[test]
+  fn test_ip_network() {
+    assert_eq!(
+      Some("192.0.2.0/24".to_string()),
+      ip_network(Some("192.0.2.17"))
+    );
+    assert_eq!(
+      ip_network(Some("192.0.2.17")),
+      ip_network(Some("192.0.2.200:443"))
+    );
+    assert_ne!(
+      ip_network(Some("192.0.2.17")),
+      ip_network(Some("192.0.3.17"))
+    );
+    assert_eq!(
+      Some("2001:db8:1::/48".to_string()),
+      ip_network(Some("2001:db8:1:2::1"))
+    );
+    assert_eq!(None, ip_network(Some("unknown")));
+    assert_eq!(None, ip_network(None));
+  }
+
+  #[test]
+  fn test_device_name() {
+    let firefox_linux = "Mozilla/5.0 (X11; Linux x86_64; rv:118.0) Gecko/20100101 Firefox/118.0";
+    let firefox_linux_updated =
+      "Mozilla/5.0 (X11; Linux x86_64; rv:119.0) Gecko/20100101 Firefox/119.0";
+    assert_eq!(
+      Some("Firefox on Linux".to_string()),
+      device_name(Some(firefox_linux))
+    );
+    assert_eq!(
+      device_name(Some(firefox_linux)),
+      device_name(Some(firefox_linux_updated))
+    );
+    let chrome_android =
+      "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) \
+      Chrome/118.0.0.0 Mobile Safari/537.36";
+    assert_eq!(
+      Some("Chrome on Android".to_string()),
+      device_name(Some(chrome_android))
+    );
+    let safari_iphone = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) \
+      AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1";
+    assert_eq!(
+      Some("Safari on iOS".to_string()),
+      device_name(Some(safari_iphone))
+    );
+    assert_eq!(
+      Some("Jerboa".to_string()),
+      device_name(Some("Jerboa/0.0.40"))
+    );
+    assert_eq!(None, device_name(Some("")));
+    assert_eq!(None, device_name(None));
+  }
+
+  #[test]
+  fn test_slow_mode_elapsed() {
+    assert!(check_slow_mode_elapsed(300, None).is_ok());
+    let long_ago = naive_now() - chrono::Duration::seconds(600);
+    assert!(check_slow_mode_elapsed(300, Some(long_ago)).is_ok());
+    let recently = naive_now() - chrono::Duration::seconds(100);
+    let err = check_slow_mode_elapsed(300, Some(recently)).unwrap_err();
+    assert!(
+      matches!(err.error_type, LemmyErrorType::SlowModeActive(s) if (195..=200).contains(&s))
+    );
+  }
+
+  #[test]
+  fn test_is_image_url() {
+    let image = Url::parse("https://example.com/pictrs/image/abc.JPG").unwrap();
+    assert!(is_image_url(&image));
+    let page = Url::parse("https://example.com/article.html").unwrap();
+    assert!(!is_image_url(&page));
+    let no_extension = Url::parse("https://example.com/post/1").unwrap();
+    assert!(!is_image_url(&no_extension));
+  }
+
+  #[test]
+  fn test_email_domain_matches() {
+    let domains = vec!["example.com".to_string(), "mail.example.org".to_string()];
+    assert!(email_domain_matches("user@example.com", &domains));
+    assert!(email_domain_matches("user@Eu.Example.com", &domains));
+    assert!(email_domain_matches("user@mail.example.org", &domains));
+    assert!(!email_domain_matches("user@example.org", &domains));
+    assert!(!email_domain_matches("user@notexample.com", &domains));
+    assert!(!email_domain_matches("example.com", &domains));
+  }
+
+  #[test]
+  fn test_url_is_blocked() {
+    let blocklist = ["spam.example", "files.example/malware"]
+      .iter()
+      .map(|u| BlockedUrl {
+        id: BlockedUrlId::default(),
+        url: u.to_string(),
+        published: naive_now(),
+        updated: None,
+      })
+      .collect::<Vec<_>>();
+    let blocked = |u: &str| url_is_blocked(&Url::parse(u).unwrap(), &blocklist);
+
+    assert!(blocked("https://spam.example/post"));
+    assert!(blocked("http://www.Spam.example"));
+    assert!(blocked("https://files.example/malware/setup.exe"));
+    assert!(blocked("https://cdn.files.example/malware"));
+    assert!(!blocked("https://files.example/docs"));
+    assert!(!blocked("https://files.example/malware-free"));
+    assert!(!blocked("https://notspam.example"));
+    assert!(!blocked("https://evil-spam.example"));
+    assert!(!blocked("https://spam.example.org"));
+    assert!(!blocked("https://example.com/?u=spam.example"));
+  }
+
   #[test]
   fn test_sanitize_html() {
     let sanitized = sanitize_html("<script>alert(1);</script> hello");
@@ -844,4 +2055,171 @@ mod tests {
     let sanitized = sanitize_html("Hello&nbsp;World");
     assert_eq!(sanitized, "Hello World");
   }
+
+  #[test]
+  fn test_sanitize_incoming_html() {
+    let config = IncomingHtmlConfig::default();
+    let sanitized = sanitize_incoming_html(
+      "<p><span class=\"h-card\"><a href=\"https://example.com/@user\" class=\"u-url mention\">@<span>user</span></a></span> hi</p><script>alert(1);</script>",
+      &config,
+    );
+    assert_eq!(
+      sanitized,
+      "<p><a href=\"https://example.com/@user\" rel=\"noopener noreferrer\">@user</a> hi</p>"
+    );
+    let sanitized = sanitize_incoming_html(
+      "<ul><li>one</li></ul><blockquote><pre><code>two</code></pre></blockquote><table><tr><td>three</td></tr></table>",
+      &config,
+    );
+    assert_eq!(
+      sanitized,
+      "<ul><li>one</li></ul><blockquote><pre><code>two</code></pre></blockquote>three"
+    );
+
+    let config = IncomingHtmlConfig {
+      allowed_tags: vec!["p".to_string(), "script".to_string()],
+    };
+    let sanitized = sanitize_incoming_html(
+      "<p><a href=\"https://example.com\">link</a></p><script>alert(1);</script>",
+      &config,
+    );
+    assert_eq!(sanitized, "<p>link</p>");
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_flagged_signup_cant_log_in_with_open_registration() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let site_form = SiteInsertForm::builder()
+      .name("test site".to_string())
+      .instance_id(instance.id)
+      .build();
+    let site = Site::create(pool, &site_form).await.unwrap();
+    let local_site_form = LocalSiteInsertForm::builder()
+      .site_id(site.id)
+      .registration_mode(Some(RegistrationMode::Open))
+      .build();
+    let local_site = LocalSite::create(pool, &local_site_form).await.unwrap();
+
+    let mut local_users = vec![];
+    for name in ["flagged_signup", "legacy_signup"] {
+      let person_form = PersonInsertForm::builder()
+        .name(name.into())
+        .public_key("pubkey".to_string())
+        .instance_id(instance.id)
+        .build();
+      let person = Person::create(pool, &person_form).await.unwrap();
+      // Not accepted, like users who were flagged by the spam check, or who registered before
+      // applications existed
+      let local_user_form = LocalUserInsertForm::builder()
+        .person_id(person.id)
+        .password_encrypted("nada".to_string())
+        .accepted_application(Some(false))
+        .build();
+      local_users.push(LocalUser::create(pool, &local_user_form).await.unwrap());
+    }
+    let application_form = RegistrationApplicationInsertForm {
+      local_user_id: local_users[0].id,
+      answer: "Flagged by spam check".to_string(),
+    };
+    RegistrationApplication::create(pool, &application_form)
+      .await
+      .unwrap();
+
+    let flagged = LocalUserView::read(pool, local_users[0].id).await.unwrap();
+    let err = check_registration_application(&flagged, &local_site, pool)
+      .await
+      .unwrap_err();
+    assert_eq!(
+      LemmyErrorType::RegistrationApplicationIsPending,
+      err.error_type
+    );
+
+    let legacy = LocalUserView::read(pool, local_users[1].id).await.unwrap();
+    assert!(check_registration_application(&legacy, &local_site, pool)
+      .await
+      .is_ok());
+
+    Site::delete(pool, site.id).await.unwrap();
+    Instance::delete(pool, instance.id).await.unwrap();
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_admin_role_checks() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("admin_role_checks".into())
+      .public_key("pubkey".to_string())
+      .instance_id(instance.id)
+      .build();
+    let person = Person::create(pool, &person_form).await.unwrap();
+    let local_user_form = LocalUserInsertForm::builder()
+      .person_id(person.id)
+      .password_encrypted("nada".to_string())
+      .build();
+    let local_user = LocalUser::create(pool, &local_user_form).await.unwrap();
+    let mut view = LocalUserView::read(pool, local_user.id).await.unwrap();
+    let mut target = person.clone();
+
+    let roles = [
+      AdminRole::UserAdmin,
+      AdminRole::ContentAdmin,
+      AdminRole::FederationAdmin,
+      AdminRole::FullAdmin,
+    ];
+
+    // Non-admins are rejected, even with a leftover role
+    for admin_role in [None, Some(AdminRole::FullAdmin)] {
+      view.person.admin = false;
+      view.local_user.admin_role = admin_role;
+      for role in roles {
+        let err = check_admin_role(&view, role).unwrap_err();
+        assert_eq!(LemmyErrorType::NotAnAdmin, err.error_type);
+      }
+    }
+
+    view.person.admin = true;
+    for admin_role in roles {
+      view.local_user.admin_role = Some(admin_role);
+      for role in roles {
+        let allowed = admin_role == AdminRole::FullAdmin || admin_role == role;
+        assert_eq!(
+          allowed,
+          check_admin_role(&view, role).is_ok(),
+          "{admin_role} checked for {role}"
+        );
+      }
+
+      // Only full admins can act against other admins
+      target.admin = false;
+      assert!(check_admin_target(&view, &target).is_ok());
+      target.admin = true;
+      assert_eq!(
+        admin_role == AdminRole::FullAdmin,
+        check_admin_target(&view, &target).is_ok()
+      );
+    }
+
+    // Admins without a role can't do anything
+    view.local_user.admin_role = None;
+    for role in roles {
+      let err = check_admin_role(&view, role).unwrap_err();
+      assert_eq!(LemmyErrorType::MissingAdminRole, err.error_type);
+    }
+    assert!(check_admin_target(&view, &target).is_err());
+
+    Person::delete(pool, person.id).await.unwrap();
+    Instance::delete(pool, instance.id).await.unwrap();
+  }
 }
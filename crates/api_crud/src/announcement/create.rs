@@ -0,0 +1,45 @@
+use crate::announcement::{check_announcement_message, check_announcement_times};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  announcement::{AnnouncementResponse, CreateAnnouncement},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{
+    announcement::{Announcement, AnnouncementInsertForm},
+    local_site::LocalSite,
+  },
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn create_announcement(
+  data: Json<CreateAnnouncement>,
+  context: Data<LemmyContext>,
+) -> Result<Json<AnnouncementResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  let message = check_announcement_message(&data.message, &local_site)?;
+  let (start_time, end_time) = check_announcement_times(data.start_time, data.end_time)?;
+
+  let form = AnnouncementInsertForm {
+    creator_id: local_user_view.person.id,
+    message,
+    severity: data.severity.unwrap_or_default(),
+    start_time,
+    end_time,
+  };
+  let announcement = Announcement::create(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateAnnouncement)?;
+
+  Ok(Json(AnnouncementResponse { announcement }))
+}
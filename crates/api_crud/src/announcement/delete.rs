@@ -0,0 +1,26 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  announcement::{DeleteAnnouncement, DeleteAnnouncementResponse},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{source::announcement::Announcement, traits::Crud, AdminRole};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_announcement(
+  data: Json<DeleteAnnouncement>,
+  context: Data<LemmyContext>,
+) -> Result<Json<DeleteAnnouncementResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  Announcement::delete(&mut context.pool(), data.id).await?;
+  Ok(Json(DeleteAnnouncementResponse {
+    id: data.id,
+    success: true,
+  }))
+}
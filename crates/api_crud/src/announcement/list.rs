@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  announcement::{ListAnnouncements, ListAnnouncementsResponse},
+  context::LemmyContext,
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::announcement::Announcement;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_announcements(
+  data: Query<ListAnnouncements>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListAnnouncementsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let announcements = Announcement::list(&mut context.pool(), data.page, data.limit).await?;
+
+  Ok(Json(ListAnnouncementsResponse { announcements }))
+}
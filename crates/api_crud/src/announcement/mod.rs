@@ -0,0 +1,36 @@
+use chrono::NaiveDateTime;
+use lemmy_api_common::utils::{local_site_to_slur_regex, sanitize_html};
+use lemmy_db_schema::{source::local_site::LocalSite, utils::naive_now};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{slurs::check_slurs, time::naive_from_unix, validation::is_valid_body_field},
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+/// Announcements are markdown, so they get the same checks as any other body field.
+fn check_announcement_message(message: &str, local_site: &LocalSite) -> LemmyResult<String> {
+  let message = message.trim();
+  if message.is_empty() {
+    Err(LemmyErrorType::InvalidBodyField)?;
+  }
+  check_slurs(message, &local_site_to_slur_regex(local_site))?;
+  is_valid_body_field(&Some(message.to_string()), false)?;
+  Ok(sanitize_html(message))
+}
+
+/// Converts the given unix timestamps, and makes sure that the announcement ends after it starts.
+fn check_announcement_times(
+  start_time: Option<i64>,
+  end_time: Option<i64>,
+) -> LemmyResult<(NaiveDateTime, Option<NaiveDateTime>)> {
+  let start_time = start_time.map(naive_from_unix).unwrap_or_else(naive_now);
+  let end_time = end_time.map(naive_from_unix);
+  if end_time.is_some_and(|e| e <= start_time) {
+    Err(LemmyErrorType::InvalidAnnouncementTimes)?;
+  }
+  Ok((start_time, end_time))
+}
@@ -0,0 +1,46 @@
+use crate::announcement::{check_announcement_message, check_announcement_times};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  announcement::{AnnouncementResponse, EditAnnouncement},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{
+    announcement::{Announcement, AnnouncementUpdateForm},
+    local_site::LocalSite,
+  },
+  traits::Crud,
+  utils::naive_now,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn update_announcement(
+  data: Json<EditAnnouncement>,
+  context: Data<LemmyContext>,
+) -> Result<Json<AnnouncementResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  let message = check_announcement_message(&data.message, &local_site)?;
+  let (start_time, end_time) = check_announcement_times(data.start_time, data.end_time)?;
+
+  let form = AnnouncementUpdateForm {
+    message,
+    severity: data.severity.unwrap_or_default(),
+    start_time,
+    end_time: Some(end_time),
+    updated: Some(naive_now()),
+  };
+  let announcement = Announcement::update(&mut context.pool(), data.id, &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateAnnouncement)?;
+
+  Ok(Json(AnnouncementResponse { announcement }))
+}
@@ -0,0 +1,26 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  blocked_url::{DeleteBlockedUrl, DeleteBlockedUrlResponse},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{source::blocked_url::BlockedUrl, traits::Crud, AdminRole};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_blocked_url(
+  data: Json<DeleteBlockedUrl>,
+  context: Data<LemmyContext>,
+) -> Result<Json<DeleteBlockedUrlResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  BlockedUrl::delete(&mut context.pool(), data.id).await?;
+  Ok(Json(DeleteBlockedUrlResponse {
+    id: data.id,
+    success: true,
+  }))
+}
@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  blocked_url::{ListBlockedUrls, ListBlockedUrlsResponse},
+  context::LemmyContext,
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::blocked_url::BlockedUrl;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_blocked_urls(
+  data: Query<ListBlockedUrls>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListBlockedUrlsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let blocked_urls = BlockedUrl::list(&mut context.pool(), data.page, data.limit).await?;
+
+  Ok(Json(ListBlockedUrlsResponse { blocked_urls }))
+}
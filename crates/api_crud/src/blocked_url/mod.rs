@@ -0,0 +1,46 @@
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use url::Url;
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+/// Blocked urls are stored without the scheme and trailing slash, as a bare lowercase domain or a
+/// domain followed by a path prefix.
+fn clean_blocked_url(blocked_url: &str) -> LemmyResult<String> {
+  let blocked_url = blocked_url.trim();
+  let without_scheme = blocked_url
+    .strip_prefix("https://")
+    .or_else(|| blocked_url.strip_prefix("http://"))
+    .unwrap_or(blocked_url);
+  let parsed = Url::parse(&format!("https://{without_scheme}"))
+    .map_err(|_| LemmyErrorType::InvalidBlockedUrl)?;
+  let host = parsed
+    .host_str()
+    .filter(|h| !h.is_empty())
+    .ok_or(LemmyErrorType::InvalidBlockedUrl)?;
+  Ok(format!("{host}{}", parsed.path().trim_end_matches('/')))
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::blocked_url::clean_blocked_url;
+
+  #[test]
+  fn test_clean_blocked_url() {
+    assert_eq!(
+      "spam.example",
+      clean_blocked_url(" https://Spam.example/ ").unwrap()
+    );
+    assert_eq!(
+      "files.example/malware",
+      clean_blocked_url("files.example/malware/").unwrap()
+    );
+    assert!(clean_blocked_url("").is_err());
+    assert!(clean_blocked_url("http://").is_err());
+  }
+}
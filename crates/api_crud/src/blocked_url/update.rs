@@ -0,0 +1,36 @@
+use crate::blocked_url::clean_blocked_url;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  blocked_url::{BlockedUrlResponse, EditBlockedUrl},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::blocked_url::{BlockedUrl, BlockedUrlForm},
+  traits::Crud,
+  utils::naive_now,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn update_blocked_url(
+  data: Json<EditBlockedUrl>,
+  context: Data<LemmyContext>,
+) -> Result<Json<BlockedUrlResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  let blocked_url_form = BlockedUrlForm {
+    url: clean_blocked_url(&data.url)?,
+    updated: Some(naive_now()),
+  };
+  let blocked_url = BlockedUrl::update(&mut context.pool(), data.id, &blocked_url_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateBlockedUrl)?;
+
+  Ok(Json(BlockedUrlResponse { blocked_url }))
+}
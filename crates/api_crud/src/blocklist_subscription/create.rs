@@ -0,0 +1,44 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  blocklist_subscription::{BlocklistSubscriptionResponse, CreateBlocklistSubscription},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt, sync_blocklist_subscription},
+};
+use lemmy_db_schema::{
+  source::blocklist_subscription::{BlocklistSubscription, BlocklistSubscriptionForm},
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+use url::Url;
+
+#[tracing::instrument(skip(context))]
+pub async fn create_blocklist_subscription(
+  data: Json<CreateBlocklistSubscription>,
+  context: Data<LemmyContext>,
+) -> Result<Json<BlocklistSubscriptionResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FederationAdmin)?;
+
+  let url = Url::parse(data.url.trim()).with_lemmy_type(LemmyErrorType::InvalidUrl)?;
+  if !["http", "https"].contains(&url.scheme()) {
+    return Err(LemmyErrorType::InvalidUrlScheme)?;
+  }
+  let form = BlocklistSubscriptionForm {
+    url: url.to_string(),
+  };
+  let blocklist_subscription = BlocklistSubscription::create(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateBlocklistSubscription)?;
+
+  // Sync right away, so that admins see if the list can be read
+  let blocklist_subscription =
+    sync_blocklist_subscription(&blocklist_subscription, &context).await?;
+
+  Ok(Json(BlocklistSubscriptionResponse {
+    blocklist_subscription,
+  }))
+}
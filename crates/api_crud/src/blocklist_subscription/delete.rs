@@ -0,0 +1,30 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  blocklist_subscription::{DeleteBlocklistSubscription, DeleteBlocklistSubscriptionResponse},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::blocklist_subscription::BlocklistSubscription,
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn delete_blocklist_subscription(
+  data: Json<DeleteBlocklistSubscription>,
+  context: Data<LemmyContext>,
+) -> Result<Json<DeleteBlocklistSubscriptionResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FederationAdmin)?;
+
+  BlocklistSubscription::delete(&mut context.pool(), data.id).await?;
+  Ok(Json(DeleteBlocklistSubscriptionResponse {
+    id: data.id,
+    success: true,
+  }))
+}
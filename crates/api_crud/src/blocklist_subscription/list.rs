@@ -0,0 +1,26 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  blocklist_subscription::{ListBlocklistSubscriptions, ListBlocklistSubscriptionsResponse},
+  context::LemmyContext,
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::blocklist_subscription::BlocklistSubscription;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_blocklist_subscriptions(
+  data: Query<ListBlocklistSubscriptions>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListBlocklistSubscriptionsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let blocklist_subscriptions = BlocklistSubscription::list(&mut context.pool()).await?;
+
+  Ok(Json(ListBlocklistSubscriptionsResponse {
+    blocklist_subscriptions,
+  }))
+}
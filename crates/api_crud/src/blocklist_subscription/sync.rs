@@ -0,0 +1,32 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  blocklist_subscription::{BlocklistSubscriptionResponse, SyncBlocklistSubscription},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt, sync_blocklist_subscription},
+};
+use lemmy_db_schema::{
+  source::blocklist_subscription::BlocklistSubscription,
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn sync_blocklist_subscription_now(
+  data: Json<SyncBlocklistSubscription>,
+  context: Data<LemmyContext>,
+) -> Result<Json<BlocklistSubscriptionResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::FederationAdmin)?;
+
+  let blocklist_subscription = BlocklistSubscription::read(&mut context.pool(), data.id).await?;
+  let blocklist_subscription =
+    sync_blocklist_subscription(&blocklist_subscription, &context).await?;
+
+  Ok(Json(BlocklistSubscriptionResponse {
+    blocklist_subscription,
+  }))
+}
@@ -0,0 +1,40 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  category::{CategoryResponse, CreateCategory},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt, sanitize_html, sanitize_html_opt},
+};
+use lemmy_db_schema::{
+  source::category::{Category, CategoryInsertForm},
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::{is_valid_body_field, is_valid_category_name},
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn create_category(
+  data: Json<CreateCategory>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CategoryResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  is_valid_category_name(&data.name)?;
+  is_valid_body_field(&data.description, false)?;
+
+  let category_form = CategoryInsertForm {
+    name: sanitize_html(&data.name),
+    description: sanitize_html_opt(&data.description).filter(|d| !d.is_empty()),
+  };
+  let category = Category::create(&mut context.pool(), &category_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateCategory)?;
+
+  Ok(Json(CategoryResponse { category }))
+}
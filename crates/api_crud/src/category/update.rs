@@ -0,0 +1,44 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  category::{CategoryResponse, EditCategory},
+  context::LemmyContext,
+  utils::{check_admin_role, local_user_view_from_jwt, sanitize_html, sanitize_html_opt},
+};
+use lemmy_db_schema::{
+  source::category::{Category, CategoryUpdateForm},
+  traits::Crud,
+  utils::{diesel_option_overwrite, naive_now},
+  AdminRole,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::{is_valid_body_field, is_valid_category_name},
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn update_category(
+  data: Json<EditCategory>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CategoryResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  if let Some(name) = &data.name {
+    is_valid_category_name(name)?;
+  }
+  is_valid_body_field(&data.description, false)?;
+
+  let category_form = CategoryUpdateForm {
+    name: data.name.as_deref().map(sanitize_html),
+    description: diesel_option_overwrite(sanitize_html_opt(&data.description)),
+    updated: Some(Some(naive_now())),
+  };
+  let category = Category::update(&mut context.pool(), data.id, &category_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateCategory)?;
+
+  Ok(Json(CategoryResponse { category }))
+}
@@ -1,37 +1,47 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_common::{
-  build_response::{build_comment_response, send_local_notifs},
+  build_response::{build_comment_response, send_community_mention_notifs, send_local_notifs},
   comment::{CommentResponse, CreateComment},
   context::LemmyContext,
+  language_detection::detect_language,
   send_activity::{ActivityChannel, SendActivityData},
+  spam::{check_spam, report_spam_comment, SpamCheckInput},
   utils::{
+    apply_comment_word_filter_action,
+    check_comment_slow_mode,
     check_community_ban,
     check_community_deleted_or_removed,
+    check_community_language,
+    check_community_posting_thresholds,
+    check_community_word_filters,
     check_post_deleted_or_removed,
     generate_local_apub_endpoint,
     get_post,
+    link_comment_images,
     local_site_to_slur_regex,
     local_user_view_from_jwt,
     sanitize_html,
+    slur_filter_regex,
     EndpointType,
   },
 };
 use lemmy_db_schema::{
   impls::actor_language::default_post_language,
   source::{
-    actor_language::CommunityLanguage,
     comment::{Comment, CommentInsertForm, CommentLike, CommentLikeForm, CommentUpdateForm},
     comment_reply::{CommentReply, CommentReplyUpdateForm},
+    community::Community,
     local_site::LocalSite,
     person_mention::{PersonMention, PersonMentionUpdateForm},
+    thread_subscription::{ThreadSubscription, ThreadSubscriptionForm},
   },
   traits::{Crud, Likeable},
 };
 use lemmy_utils::{
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
   utils::{
-    mention::scrape_text_for_mentions,
+    mention::{scrape_text_for_community_mentions, scrape_text_for_mentions},
     slurs::remove_slurs,
     validation::is_valid_body_field,
   },
@@ -68,6 +78,21 @@ pub async fn create_comment(
     return Err(LemmyErrorType::Locked)?;
   }
 
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  check_comment_slow_mode(&community, local_user_view.person.id, &mut context.pool()).await?;
+  check_community_posting_thresholds(&community, &local_user_view.person, &mut context.pool())
+    .await?;
+  let word_filter_action =
+    check_community_word_filters(community_id, &[&content], &mut context.pool()).await?;
+  let spam_review = check_spam(
+    &SpamCheckInput::Comment {
+      creator: &local_user_view.person,
+      content: &content,
+    },
+    &context,
+  )
+  .await?;
+
   // Fetch the parent, if it exists
   let parent_opt = if let Some(parent_id) = data.parent_id {
     Comment::read(&mut context.pool(), parent_id).await.ok()
@@ -84,25 +109,24 @@ pub async fn create_comment(
     check_comment_depth(parent)?;
   }
 
-  CommunityLanguage::is_allowed_community_language(
-    &mut context.pool(),
-    data.language_id,
-    community_id,
-  )
-  .await?;
-
-  // attempt to set default language if none was provided
+  // attempt to detect the language or set the default language if none was provided
   let language_id = match data.language_id {
     Some(lid) => Some(lid),
-    None => {
-      default_post_language(
-        &mut context.pool(),
-        community_id,
-        local_user_view.local_user.id,
-      )
-      .await?
-    }
+    None => match detect_language(&[&content], &community, &mut context.pool()).await? {
+      Some(lid) => Some(lid),
+      None => {
+        default_post_language(
+          &mut context.pool(),
+          community_id,
+          local_user_view.local_user.id,
+        )
+        .await?
+      }
+    },
   };
+  check_community_language(&community, language_id, &mut context.pool()).await?;
+  let slur_filter = slur_filter_regex(language_id, community_id, &mut context.pool()).await?;
+  let content = remove_slurs(&content, &slur_filter);
 
   let comment_form = CommentInsertForm::builder()
     .content(content.clone())
@@ -116,6 +140,10 @@ pub async fn create_comment(
   let inserted_comment = Comment::create(&mut context.pool(), &comment_form, parent_path.as_ref())
     .await
     .with_lemmy_type(LemmyErrorType::CouldntCreateComment)?;
+  apply_comment_word_filter_action(&inserted_comment, word_filter_action, &mut context.pool())
+    .await?;
+  report_spam_comment(&inserted_comment, spam_review, &mut context.pool()).await?;
+  link_comment_images(&inserted_comment, &mut context.pool(), context.settings()).await?;
 
   // Necessary to update the ap_id
   let inserted_comment_id = inserted_comment.id;
@@ -145,6 +173,27 @@ pub async fn create_comment(
     &context,
   )
   .await?;
+  send_community_mention_notifs(
+    scrape_text_for_community_mentions(&content),
+    &post,
+    Some(&updated_comment),
+    &local_user_view.person,
+    true,
+    &context,
+  )
+  .await?;
+
+  // Get notified about all replies below your own comment
+  if local_user_view.local_user.auto_subscribe_threads {
+    let subscription_form = ThreadSubscriptionForm {
+      person_id: local_user_view.person.id,
+      post_id: post.id,
+      comment_id: Some(inserted_comment.id),
+    };
+    ThreadSubscription::subscribe(&mut context.pool(), &subscription_form)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdateNotification)?;
+  }
 
   // You like your own comment by default
   let like_form = CommentLikeForm {
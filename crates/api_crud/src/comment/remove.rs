@@ -5,11 +5,18 @@ use lemmy_api_common::{
   comment::{CommentResponse, RemoveComment},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{check_community_ban, is_mod_or_admin, local_user_view_from_jwt},
+  utils::{
+    add_community_strike,
+    check_community_ban,
+    is_mod_or_admin,
+    local_user_view_from_jwt,
+    sanitize_html_opt,
+  },
 };
 use lemmy_db_schema::{
   source::{
     comment::{Comment, CommentUpdateForm},
+    community_strike::CommunityStrikeForm,
     moderator::{ModRemoveComment, ModRemoveCommentForm},
     post::Post,
   },
@@ -62,6 +69,18 @@ pub async fn remove_comment(
   };
   ModRemoveComment::create(&mut context.pool(), &form).await?;
 
+  if removed && data.strike.unwrap_or(false) {
+    let strike_form = CommunityStrikeForm {
+      community_id: orig_comment.community.id,
+      person_id: orig_comment.creator.id,
+      mod_person_id: local_user_view.person.id,
+      post_id: None,
+      comment_id: Some(comment_id),
+      reason: sanitize_html_opt(&data.reason),
+    };
+    add_community_strike(&strike_form, &local_user_view.person, &data.auth, &context).await?;
+  }
+
   let post_id = updated_comment.post_id;
   let post = Post::read(&mut context.pool(), post_id).await?;
   let recipient_ids = send_local_notifs(
@@ -1,20 +1,24 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_common::{
-  build_response::{build_comment_response, send_local_notifs},
+  build_response::{build_comment_response, send_community_mention_notifs, send_local_notifs},
   comment::{CommentResponse, EditComment},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
+    apply_comment_word_filter_action,
     check_community_ban,
+    check_community_language,
+    check_community_word_filters,
+    link_comment_images,
     local_site_to_slur_regex,
     local_user_view_from_jwt,
     sanitize_html_opt,
+    slur_filter_regex,
   },
 };
 use lemmy_db_schema::{
   source::{
-    actor_language::CommunityLanguage,
     comment::{Comment, CommentUpdateForm},
     local_site::LocalSite,
   },
@@ -25,7 +29,7 @@ use lemmy_db_views::structs::CommentView;
 use lemmy_utils::{
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
   utils::{
-    mention::scrape_text_for_mentions,
+    mention::{scrape_text_for_community_mentions, scrape_text_for_mentions},
     slurs::remove_slurs,
     validation::is_valid_body_field,
   },
@@ -55,20 +59,35 @@ pub async fn update_comment(
   }
 
   let language_id = data.language_id;
-  CommunityLanguage::is_allowed_community_language(
-    &mut context.pool(),
-    language_id,
+  if let Some(language_id) = language_id {
+    check_community_language(
+      &orig_comment.community,
+      Some(language_id),
+      &mut context.pool(),
+    )
+    .await?;
+  }
+
+  // Update the Content
+  let slur_regex = local_site_to_slur_regex(&local_site);
+  let slur_filter = slur_filter_regex(
+    language_id.or(Some(orig_comment.comment.language_id)),
     orig_comment.community.id,
+    &mut context.pool(),
   )
   .await?;
-
-  // Update the Content
   let content = data
     .content
     .as_ref()
-    .map(|c| remove_slurs(c, &local_site_to_slur_regex(&local_site)));
+    .map(|c| remove_slurs(&remove_slurs(c, &slur_regex), &slur_filter));
   is_valid_body_field(&content, false)?;
   let content = sanitize_html_opt(&content);
+  let word_filter_action = check_community_word_filters(
+    orig_comment.community.id,
+    &content.as_deref().into_iter().collect::<Vec<_>>(),
+    &mut context.pool(),
+  )
+  .await?;
 
   let comment_id = data.comment_id;
   let form = CommentUpdateForm::builder()
@@ -79,6 +98,9 @@ pub async fn update_comment(
   let updated_comment = Comment::update(&mut context.pool(), comment_id, &form)
     .await
     .with_lemmy_type(LemmyErrorType::CouldntUpdateComment)?;
+  apply_comment_word_filter_action(&updated_comment, word_filter_action, &mut context.pool())
+    .await?;
+  link_comment_images(&updated_comment, &mut context.pool(), context.settings()).await?;
 
   // Do the mentions / recipients
   let updated_comment_content = updated_comment.content.clone();
@@ -92,6 +114,15 @@ pub async fn update_comment(
     &context,
   )
   .await?;
+  send_community_mention_notifs(
+    scrape_text_for_community_mentions(&updated_comment_content),
+    &orig_comment.post,
+    Some(&updated_comment),
+    &local_user_view.person,
+    false,
+    &context,
+  )
+  .await?;
 
   ActivityChannel::submit_activity(
     SendActivityData::UpdateComment(updated_comment.clone()),
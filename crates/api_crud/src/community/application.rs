@@ -0,0 +1,137 @@
+use crate::community::create::{check_create_community, insert_community};
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  community::{
+    ApproveCommunityApplication,
+    CommunityApplicationResponse,
+    CreateCommunity,
+    ListCommunityApplications,
+    ListCommunityApplicationsResponse,
+  },
+  context::LemmyContext,
+  sensitive::Sensitive,
+  utils::{
+    check_admin_role,
+    check_community_creation_allowed,
+    local_user_view_from_jwt,
+    sanitize_html,
+    sanitize_html_opt,
+    send_community_application_email,
+  },
+};
+use lemmy_db_schema::{
+  source::community_application::{
+    CommunityApplication,
+    CommunityApplicationInsertForm,
+    CommunityApplicationUpdateForm,
+  },
+  AdminRole,
+};
+use lemmy_db_views::{
+  community_application_view::CommunityApplicationQuery,
+  structs::{CommunityApplicationView, LocalUserView, SiteView},
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+/// Applies for a new community, which is created once an admin approves it.
+#[tracing::instrument(skip(context))]
+pub async fn create_community_application(
+  data: Json<CreateCommunity>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityApplicationResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_site = &site_view.local_site;
+
+  check_community_creation_allowed(&local_user_view, local_site, &mut context.pool()).await?;
+  check_create_community(&data, local_site, &context).await?;
+
+  let form = CommunityApplicationInsertForm {
+    creator_id: local_user_view.person.id,
+    name: data.name.clone(),
+    title: sanitize_html(&data.title),
+    description: sanitize_html_opt(&data.description),
+    nsfw: data.nsfw.unwrap_or(false),
+  };
+  // The login token isn't stored with the request
+  let request = CreateCommunity {
+    auth: Sensitive::default(),
+    ..data.into_inner()
+  };
+  let application =
+    CommunityApplication::create(&mut context.pool(), &form, serde_json::to_value(request)?)
+      .await?;
+
+  let community_application =
+    CommunityApplicationView::read(&mut context.pool(), application.id).await?;
+  Ok(Json(CommunityApplicationResponse {
+    community_application,
+  }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn list_community_applications(
+  data: Query<ListCommunityApplications>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListCommunityApplicationsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Only admins can see the applications of others
+  let creator_id = (!local_user_view.person.admin).then_some(local_user_view.person.id);
+  let community_applications = CommunityApplicationQuery {
+    unread_only: data.unread_only,
+    creator_id,
+    page: data.page,
+    limit: data.limit,
+  }
+  .list(&mut context.pool())
+  .await?;
+
+  Ok(Json(ListCommunityApplicationsResponse {
+    community_applications,
+  }))
+}
+
+#[tracing::instrument(skip(context))]
+pub async fn approve_community_application(
+  data: Json<ApproveCommunityApplication>,
+  context: Data<LemmyContext>,
+) -> Result<Json<CommunityApplicationResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  let application = CommunityApplication::read(&mut context.pool(), data.id)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntFindCommunityApplication)?;
+  if application.admin_id.is_some() {
+    return Err(LemmyErrorType::CommunityApplicationAlreadyDecided)?;
+  }
+  let creator = LocalUserView::read_person(&mut context.pool(), application.creator_id).await?;
+
+  let community_id = if data.approve {
+    let request = CommunityApplication::read_request(&mut context.pool(), application.id).await?;
+    let request: CreateCommunity = serde_json::from_value(request)?;
+    // The site rules or other communities might have changed since the application
+    let site_view = SiteView::read_local(&mut context.pool()).await?;
+    check_create_community(&request, &site_view.local_site, &context).await?;
+    Some(insert_community(&request, &creator.person, &site_view, &context).await?)
+  } else {
+    None
+  };
+
+  let form = CommunityApplicationUpdateForm {
+    admin_id: Some(local_user_view.person.id),
+    deny_reason: data.deny_reason.clone().filter(|_| !data.approve),
+    community_id,
+  };
+  let application = CommunityApplication::update(&mut context.pool(), data.id, &form).await?;
+  send_community_application_email(&creator, &application, data.approve, context.settings())
+    .await?;
+
+  let community_application =
+    CommunityApplicationView::read(&mut context.pool(), application.id).await?;
+  Ok(Json(CommunityApplicationResponse {
+    community_application,
+  }))
+}
@@ -5,11 +5,11 @@ use lemmy_api_common::{
   community::{CommunityResponse, CreateCommunity},
   context::LemmyContext,
   utils::{
+    check_community_creation_allowed,
     generate_followers_url,
     generate_inbox_url,
     generate_local_apub_endpoint,
     generate_shared_inbox_url,
-    is_admin,
     local_site_to_slur_regex,
     local_user_view_from_jwt,
     sanitize_html,
@@ -18,8 +18,10 @@ use lemmy_api_common::{
   },
 };
 use lemmy_db_schema::{
+  newtypes::CommunityId,
   source::{
     actor_language::{CommunityLanguage, SiteLanguage},
+    category::CommunityCategory,
     community::{
       Community,
       CommunityFollower,
@@ -28,16 +30,25 @@ use lemmy_db_schema::{
       CommunityModerator,
       CommunityModeratorForm,
     },
+    local_site::LocalSite,
+    person::Person,
   },
   traits::{ApubActor, Crud, Followable, Joinable},
   utils::diesel_option_overwrite_to_url_create,
 };
 use lemmy_db_views::structs::SiteView;
 use lemmy_utils::{
-  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::{
     slurs::{check_slurs, check_slurs_opt},
-    validation::{is_valid_actor_name, is_valid_body_field},
+    validation::{
+      check_community_categories_count,
+      check_posting_thresholds,
+      check_slow_mode_interval,
+      check_strike_ban_settings,
+      is_valid_actor_name,
+      is_valid_body_field,
+    },
   },
 };
 
@@ -48,27 +59,47 @@ pub async fn create_community(
 ) -> Result<Json<CommunityResponse>, LemmyError> {
   let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
   let site_view = SiteView::read_local(&mut context.pool()).await?;
-  let local_site = site_view.local_site;
+  let local_site = &site_view.local_site;
 
-  if local_site.community_creation_admin_only && is_admin(&local_user_view).is_err() {
-    return Err(LemmyErrorType::OnlyAdminsCanCreateCommunities)?;
+  check_community_creation_allowed(&local_user_view, local_site, &mut context.pool()).await?;
+  if local_site.community_creation_requires_approval && !local_user_view.person.admin {
+    return Err(LemmyErrorType::CommunityCreationRequiresApproval)?;
   }
+  check_create_community(&data, local_site, &context).await?;
 
-  // Check to make sure the icon and banners are urls
-  let icon = diesel_option_overwrite_to_url_create(&data.icon)?;
-  let banner = diesel_option_overwrite_to_url_create(&data.banner)?;
+  let community_id = insert_community(&data, &local_user_view.person, &site_view, &context).await?;
 
-  let name = sanitize_html(&data.name);
-  let title = sanitize_html(&data.title);
-  let description = sanitize_html_opt(&data.description);
+  build_community_response(&context, local_user_view, community_id).await
+}
+
+/// Validates a new community, also before it is stored as an application.
+pub(crate) async fn check_create_community(
+  data: &CreateCommunity,
+  local_site: &LocalSite,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  // Check to make sure the icon and banners are urls
+  diesel_option_overwrite_to_url_create(&data.icon)?;
+  diesel_option_overwrite_to_url_create(&data.banner)?;
 
-  let slur_regex = local_site_to_slur_regex(&local_site);
-  check_slurs(&name, &slur_regex)?;
-  check_slurs(&title, &slur_regex)?;
-  check_slurs_opt(&description, &slur_regex)?;
+  let slur_regex = local_site_to_slur_regex(local_site);
+  check_slurs(&sanitize_html(&data.name), &slur_regex)?;
+  check_slurs(&sanitize_html(&data.title), &slur_regex)?;
+  check_slurs_opt(&sanitize_html_opt(&data.description), &slur_regex)?;
 
   is_valid_actor_name(&data.name, local_site.actor_name_max_length as usize)?;
   is_valid_body_field(&data.description, false)?;
+  check_slow_mode_interval(&data.post_slow_mode_seconds)?;
+  check_slow_mode_interval(&data.comment_slow_mode_seconds)?;
+  check_strike_ban_settings(&data.strike_ban_threshold, &data.strike_ban_days)?;
+  check_posting_thresholds(
+    &data.min_account_age_days,
+    &data.min_site_karma,
+    &data.min_community_karma,
+  )?;
+  if let Some(category_ids) = &data.category_ids {
+    check_community_categories_count(category_ids.len())?;
+  }
 
   // Double check for duplicate community actor_ids
   let community_actor_id = generate_local_apub_endpoint(
@@ -81,6 +112,27 @@ pub async fn create_community(
   if community_dupe.is_some() {
     return Err(LemmyErrorType::CommunityAlreadyExists)?;
   }
+  Ok(())
+}
+
+/// Creates a community which was checked with [check_create_community], with the creator as its
+/// moderator.
+pub(crate) async fn insert_community(
+  data: &CreateCommunity,
+  creator: &Person,
+  site_view: &SiteView,
+  context: &LemmyContext,
+) -> LemmyResult<CommunityId> {
+  let icon = diesel_option_overwrite_to_url_create(&data.icon)?;
+  let banner = diesel_option_overwrite_to_url_create(&data.banner)?;
+  let name = sanitize_html(&data.name);
+  let title = sanitize_html(&data.title);
+  let description = sanitize_html_opt(&data.description);
+  let community_actor_id = generate_local_apub_endpoint(
+    EndpointType::Community,
+    &data.name,
+    &context.settings().get_protocol_and_hostname(),
+  )?;
 
   // When you create a community, make sure the user becomes a moderator and a follower
   let keypair = generate_actor_keypair()?;
@@ -99,6 +151,19 @@ pub async fn create_community(
     .inbox_url(Some(generate_inbox_url(&community_actor_id)?))
     .shared_inbox_url(Some(generate_shared_inbox_url(&community_actor_id)?))
     .posting_restricted_to_mods(data.posting_restricted_to_mods)
+    .enable_downvotes(data.enable_downvotes)
+    .requires_follow_approval(data.requires_follow_approval)
+    .post_type_restriction(data.post_type_restriction)
+    .nsfw_only(data.nsfw_only)
+    .post_slow_mode_seconds(data.post_slow_mode_seconds)
+    .comment_slow_mode_seconds(data.comment_slow_mode_seconds)
+    .allow_undetermined_language(data.allow_undetermined_language)
+    .strike_ban_threshold(data.strike_ban_threshold)
+    .strike_ban_days(data.strike_ban_days)
+    .min_account_age_days(data.min_account_age_days)
+    .min_site_karma(data.min_site_karma)
+    .min_community_karma(data.min_community_karma)
+    .notify_mods_on_mention(data.notify_mods_on_mention)
     .instance_id(site_view.site.instance_id)
     .build();
 
@@ -109,7 +174,7 @@ pub async fn create_community(
   // The community creator becomes a moderator
   let community_moderator_form = CommunityModeratorForm {
     community_id: inserted_community.id,
-    person_id: local_user_view.person.id,
+    person_id: creator.id,
   };
 
   CommunityModerator::join(&mut context.pool(), &community_moderator_form)
@@ -119,7 +184,7 @@ pub async fn create_community(
   // Follow your own community
   let community_follower_form = CommunityFollowerForm {
     community_id: inserted_community.id,
-    person_id: local_user_view.person.id,
+    person_id: creator.id,
     pending: false,
   };
 
@@ -140,5 +205,9 @@ pub async fn create_community(
     CommunityLanguage::update(&mut context.pool(), languages, community_id).await?;
   }
 
-  build_community_response(&context, local_user_view, community_id).await
+  if let Some(category_ids) = data.category_ids.clone() {
+    CommunityCategory::update(&mut context.pool(), category_ids, community_id).await?;
+  }
+
+  Ok(community_id)
 }
@@ -1,3 +1,4 @@
+pub mod application;
 pub mod create;
 pub mod delete;
 pub mod list;
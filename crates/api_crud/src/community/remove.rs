@@ -5,7 +5,7 @@ use lemmy_api_common::{
   community::{CommunityResponse, RemoveCommunity},
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{is_admin, local_user_view_from_jwt},
+  utils::{check_admin_role, local_user_view_from_jwt},
 };
 use lemmy_db_schema::{
   source::{
@@ -13,6 +13,7 @@ use lemmy_db_schema::{
     moderator::{ModRemoveCommunity, ModRemoveCommunityForm},
   },
   traits::Crud,
+  AdminRole,
 };
 use lemmy_utils::{
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
@@ -27,7 +28,7 @@ pub async fn remove_community(
   let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
 
   // Verify its an admin (only an admin can remove a community)
-  is_admin(&local_user_view)?;
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
 
   // Do the remove
   let community_id = data.community_id;
@@ -11,6 +11,7 @@ use lemmy_db_schema::{
   newtypes::PersonId,
   source::{
     actor_language::{CommunityLanguage, SiteLanguage},
+    category::CommunityCategory,
     community::{Community, CommunityUpdateForm},
     local_site::LocalSite,
   },
@@ -20,7 +21,16 @@ use lemmy_db_schema::{
 use lemmy_db_views_actor::structs::CommunityModeratorView;
 use lemmy_utils::{
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
-  utils::{slurs::check_slurs_opt, validation::is_valid_body_field},
+  utils::{
+    slurs::check_slurs_opt,
+    validation::{
+      check_community_categories_count,
+      check_posting_thresholds,
+      check_slow_mode_interval,
+      check_strike_ban_settings,
+      is_valid_body_field,
+    },
+  },
 };
 
 #[tracing::instrument(skip(context))]
@@ -35,6 +45,14 @@ pub async fn update_community(
   check_slurs_opt(&data.title, &slur_regex)?;
   check_slurs_opt(&data.description, &slur_regex)?;
   is_valid_body_field(&data.description, false)?;
+  check_slow_mode_interval(&data.post_slow_mode_seconds)?;
+  check_slow_mode_interval(&data.comment_slow_mode_seconds)?;
+  check_strike_ban_settings(&data.strike_ban_threshold, &data.strike_ban_days)?;
+  check_posting_thresholds(
+    &data.min_account_age_days,
+    &data.min_site_karma,
+    &data.min_community_karma,
+  )?;
 
   let title = sanitize_html_opt(&data.title);
   let description = sanitize_html_opt(&data.description);
@@ -65,6 +83,11 @@ pub async fn update_community(
     CommunityLanguage::update(&mut context.pool(), languages, community_id).await?;
   }
 
+  if let Some(category_ids) = data.category_ids.clone() {
+    check_community_categories_count(category_ids.len())?;
+    CommunityCategory::update(&mut context.pool(), category_ids, community_id).await?;
+  }
+
   let community_form = CommunityUpdateForm::builder()
     .title(title)
     .description(description)
@@ -72,6 +95,19 @@ pub async fn update_community(
     .banner(banner)
     .nsfw(data.nsfw)
     .posting_restricted_to_mods(data.posting_restricted_to_mods)
+    .enable_downvotes(data.enable_downvotes)
+    .requires_follow_approval(data.requires_follow_approval)
+    .post_type_restriction(data.post_type_restriction)
+    .nsfw_only(data.nsfw_only)
+    .post_slow_mode_seconds(data.post_slow_mode_seconds)
+    .comment_slow_mode_seconds(data.comment_slow_mode_seconds)
+    .allow_undetermined_language(data.allow_undetermined_language)
+    .strike_ban_threshold(data.strike_ban_threshold)
+    .strike_ban_days(data.strike_ban_days)
+    .min_account_age_days(data.min_account_age_days)
+    .min_site_karma(data.min_site_karma)
+    .min_community_karma(data.min_community_karma)
+    .notify_mods_on_mention(data.notify_mods_on_mention)
     .updated(Some(Some(naive_now())))
     .build();
 
@@ -3,9 +3,9 @@ use actix_web::web::Json;
 use lemmy_api_common::{
   context::LemmyContext,
   custom_emoji::{DeleteCustomEmoji, DeleteCustomEmojiResponse},
-  utils::{is_admin, local_user_view_from_jwt},
+  utils::{check_admin_role, local_user_view_from_jwt},
 };
-use lemmy_db_schema::source::custom_emoji::CustomEmoji;
+use lemmy_db_schema::{source::custom_emoji::CustomEmoji, AdminRole};
 use lemmy_utils::error::LemmyError;
 
 #[tracing::instrument(skip(context))]
@@ -16,7 +16,7 @@ pub async fn delete_custom_emoji(
   let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
 
   // Make sure user is an admin
-  is_admin(&local_user_view)?;
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
   CustomEmoji::delete(&mut context.pool(), data.id).await?;
   Ok(Json(DeleteCustomEmojiResponse {
     id: data.id,
@@ -3,12 +3,15 @@ use actix_web::web::Json;
 use lemmy_api_common::{
   context::LemmyContext,
   custom_emoji::{CustomEmojiResponse, EditCustomEmoji},
-  utils::{is_admin, local_user_view_from_jwt, sanitize_html},
+  utils::{check_admin_role, local_user_view_from_jwt, sanitize_html},
 };
-use lemmy_db_schema::source::{
-  custom_emoji::{CustomEmoji, CustomEmojiUpdateForm},
-  custom_emoji_keyword::{CustomEmojiKeyword, CustomEmojiKeywordInsertForm},
-  local_site::LocalSite,
+use lemmy_db_schema::{
+  source::{
+    custom_emoji::{CustomEmoji, CustomEmojiUpdateForm},
+    custom_emoji_keyword::{CustomEmojiKeyword, CustomEmojiKeywordInsertForm},
+    local_site::LocalSite,
+  },
+  AdminRole,
 };
 use lemmy_db_views::structs::CustomEmojiView;
 use lemmy_utils::error::LemmyError;
@@ -22,7 +25,7 @@ pub async fn update_custom_emoji(
 
   let local_site = LocalSite::read(&mut context.pool()).await?;
   // Make sure user is an admin
-  is_admin(&local_user_view)?;
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
 
   let alt_text = sanitize_html(&data.alt_text);
   let category = sanitize_html(&data.category);
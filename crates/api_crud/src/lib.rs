@@ -1,7 +1,13 @@
+pub mod announcement;
+pub mod blocked_url;
+pub mod blocklist_subscription;
+pub mod category;
 pub mod comment;
 pub mod community;
 pub mod custom_emoji;
 pub mod post;
 pub mod private_message;
 pub mod site;
+pub mod slur_filter;
+pub mod tagline;
 pub mod user;
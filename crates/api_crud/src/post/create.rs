@@ -1,31 +1,47 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_common::{
-  build_response::build_post_response,
+  build_response::{
+    build_post_response,
+    send_community_mention_notifs,
+    send_community_post_notifs,
+    send_post_mention_notifs,
+  },
+  captcha::{check_captcha, CaptchaInput},
   context::LemmyContext,
+  language_detection::detect_language,
   post::{CreatePost, PostResponse},
-  request::fetch_site_data,
+  request::{fetch_audio_mime_type, fetch_site_data, is_site_data_missing},
   send_activity::{ActivityChannel, SendActivityData},
+  spam::{check_spam, report_spam_post, SpamCheckInput},
   utils::{
+    apply_post_word_filter_action,
     check_community_ban,
     check_community_deleted_or_removed,
+    check_community_language,
+    check_community_post_type,
+    check_community_posting_thresholds,
+    check_community_word_filters,
+    check_post_slow_mode,
+    check_url_not_blocked,
     generate_local_apub_endpoint,
     honeypot_check,
+    link_post_images,
     local_site_to_slur_regex,
     local_user_view_from_jwt,
     mark_post_as_read,
     sanitize_html,
     sanitize_html_opt,
+    slur_filter_regex,
     EndpointType,
   },
 };
 use lemmy_db_schema::{
   impls::actor_language::default_post_language,
   source::{
-    actor_language::CommunityLanguage,
     community::Community,
     local_site::LocalSite,
-    post::{Post, PostInsertForm, PostLike, PostLikeForm, PostUpdateForm},
+    post::{Post, PostInsertForm, PostLike, PostLikeForm, PostMetadataRefetch, PostUpdateForm},
   },
   traits::{Crud, Likeable},
 };
@@ -34,8 +50,15 @@ use lemmy_utils::{
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
   spawn_try_task,
   utils::{
+    mention::{scrape_text_for_community_mentions, scrape_text_for_mentions},
     slurs::{check_slurs, check_slurs_opt},
-    validation::{check_url_scheme, clean_url_params, is_valid_body_field, is_valid_post_title},
+    validation::{
+      check_audio_duration,
+      check_url_scheme,
+      clean_url_params,
+      is_valid_body_field,
+      is_valid_post_title,
+    },
   },
   SYNCHRONOUS_FEDERATION,
 };
@@ -55,6 +78,16 @@ pub async fn create_post(
   check_slurs(&data.name, &slur_regex)?;
   check_slurs_opt(&data.body, &slur_regex)?;
   honeypot_check(&data.honeypot)?;
+  if context.settings().captcha.require_for_posts && !local_user_view.person.admin {
+    check_captcha(
+      &CaptchaInput {
+        uuid: data.captcha_uuid.as_deref(),
+        answer: data.captcha_answer.as_deref(),
+      },
+      &context,
+    )
+    .await?;
+  }
 
   let data_url = data.url.as_ref();
   let url = data_url.map(clean_url_params).map(Into::into); // TODO no good way to handle a "clear"
@@ -62,6 +95,7 @@ pub async fn create_post(
   is_valid_post_title(&data.name)?;
   is_valid_body_field(&data.body, true)?;
   check_url_scheme(&data.url)?;
+  check_audio_duration(&data.audio_duration)?;
 
   check_community_ban(
     local_user_view.person.id,
@@ -85,40 +119,78 @@ pub async fn create_post(
       return Err(LemmyErrorType::OnlyModsCanPostInCommunity)?;
     }
   }
+  check_community_post_type(&community, data_url)?;
+  check_url_not_blocked(data_url, &mut context.pool()).await?;
+  check_post_slow_mode(&community, local_user_view.person.id, &mut context.pool()).await?;
+  check_community_posting_thresholds(&community, &local_user_view.person, &mut context.pool())
+    .await?;
+  let texts = [Some(data.name.as_str()), data.body.as_deref()];
+  let word_filter_action = check_community_word_filters(
+    community_id,
+    &texts.into_iter().flatten().collect::<Vec<_>>(),
+    &mut context.pool(),
+  )
+  .await?;
+  let nsfw = if community.nsfw_only {
+    Some(true)
+  } else {
+    data.nsfw
+  };
 
   // Fetch post links and pictrs cached image
   let (metadata_res, thumbnail_url) =
     fetch_site_data(context.client(), context.settings(), data_url, true).await;
+  let refetch_metadata = data_url.is_some() && is_site_data_missing(&metadata_res, &thumbnail_url);
   let (embed_title, embed_description, embed_video_url) = metadata_res
     .map(|u| (u.title, u.description, u.embed_video_url))
     .unwrap_or_default();
+  let audio_mime_type = match data_url {
+    Some(url) => fetch_audio_mime_type(context.client(), url).await,
+    None => None,
+  };
+  let audio_duration = audio_mime_type.as_ref().and(data.audio_duration);
 
   let name = sanitize_html(data.name.trim());
   let body = sanitize_html_opt(&data.body);
   let embed_title = sanitize_html_opt(&embed_title);
   let embed_description = sanitize_html_opt(&embed_description);
 
-  // Only need to check if language is allowed in case user set it explicitly. When using default
-  // language, it already only returns allowed languages.
-  CommunityLanguage::is_allowed_community_language(
-    &mut context.pool(),
-    data.language_id,
-    community_id,
+  let spam_review = check_spam(
+    &SpamCheckInput::Post {
+      creator: &local_user_view.person,
+      title: &name,
+      body: body.as_deref(),
+    },
+    &context,
   )
   .await?;
 
-  // attempt to set default language if none was provided
+  // attempt to detect the language or set the default language if none was provided
+  let detection_texts = [Some(data.name.as_str()), data.body.as_deref()];
   let language_id = match data.language_id {
     Some(lid) => Some(lid),
-    None => {
-      default_post_language(
-        &mut context.pool(),
-        community_id,
-        local_user_view.local_user.id,
-      )
-      .await?
-    }
+    None => match detect_language(
+      &detection_texts.into_iter().flatten().collect::<Vec<_>>(),
+      &community,
+      &mut context.pool(),
+    )
+    .await?
+    {
+      Some(lid) => Some(lid),
+      None => {
+        default_post_language(
+          &mut context.pool(),
+          community_id,
+          local_user_view.local_user.id,
+        )
+        .await?
+      }
+    },
   };
+  check_community_language(&community, language_id, &mut context.pool()).await?;
+  let slur_filter = slur_filter_regex(language_id, community_id, &mut context.pool()).await?;
+  check_slurs(&data.name, &slur_filter)?;
+  check_slurs_opt(&data.body, &slur_filter)?;
 
   let post_form = PostInsertForm::builder()
     .name(name)
@@ -126,18 +198,28 @@ pub async fn create_post(
     .body(body)
     .community_id(data.community_id)
     .creator_id(local_user_view.person.id)
-    .nsfw(data.nsfw)
+    .nsfw(nsfw)
     .embed_title(embed_title)
     .embed_description(embed_description)
     .embed_video_url(embed_video_url)
     .language_id(language_id)
     .thumbnail_url(thumbnail_url)
+    .audio_mime_type(audio_mime_type)
+    .audio_duration(audio_duration)
     .build();
 
   let inserted_post = Post::create(&mut context.pool(), &post_form)
     .await
     .with_lemmy_type(LemmyErrorType::CouldntCreatePost)?;
 
+  // Retry the link metadata later if the site didn't respond
+  if refetch_metadata {
+    PostMetadataRefetch::schedule(&mut context.pool(), inserted_post.id).await?;
+  }
+  apply_post_word_filter_action(&inserted_post, word_filter_action, &mut context.pool()).await?;
+  report_spam_post(&inserted_post, spam_review, &mut context.pool()).await?;
+  link_post_images(&inserted_post, &mut context.pool(), context.settings()).await?;
+
   let inserted_post_id = inserted_post.id;
   let protocol_and_hostname = context.settings().get_protocol_and_hostname();
   let apub_id = generate_local_apub_endpoint(
@@ -168,6 +250,36 @@ pub async fn create_post(
 
   ActivityChannel::submit_activity(SendActivityData::CreatePost(updated_post.clone()), &context)
     .await?;
+  send_community_post_notifs(&updated_post, &local_user_view.person, &context).await?;
+
+  // Scan the post body for user mentions, add those rows
+  let mentions = updated_post
+    .body
+    .as_deref()
+    .map(scrape_text_for_mentions)
+    .unwrap_or_default();
+  send_post_mention_notifs(
+    mentions,
+    &updated_post,
+    &local_user_view.person,
+    true,
+    &context,
+  )
+  .await?;
+  let community_mentions = updated_post
+    .body
+    .as_deref()
+    .map(scrape_text_for_community_mentions)
+    .unwrap_or_default();
+  send_community_mention_notifs(
+    community_mentions,
+    &updated_post,
+    None,
+    &local_user_view.person,
+    true,
+    &context,
+  )
+  .await?;
 
   // Mark the post as read
   mark_post_as_read(person_id, post_id, &mut context.pool()).await?;
@@ -5,10 +5,17 @@ use lemmy_api_common::{
   context::LemmyContext,
   post::{PostResponse, RemovePost},
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{check_community_ban, is_mod_or_admin, local_user_view_from_jwt},
+  utils::{
+    add_community_strike,
+    check_community_ban,
+    is_mod_or_admin,
+    local_user_view_from_jwt,
+    sanitize_html_opt,
+  },
 };
 use lemmy_db_schema::{
   source::{
+    community_strike::CommunityStrikeForm,
     moderator::{ModRemovePost, ModRemovePostForm},
     post::{Post, PostUpdateForm},
   },
@@ -60,6 +67,18 @@ pub async fn remove_post(
   };
   ModRemovePost::create(&mut context.pool(), &form).await?;
 
+  if removed && data.strike.unwrap_or(false) {
+    let strike_form = CommunityStrikeForm {
+      community_id: orig_post.community_id,
+      person_id: orig_post.creator_id,
+      mod_person_id: local_user_view.person.id,
+      post_id: Some(post_id),
+      comment_id: None,
+      reason: sanitize_html_opt(&data.reason),
+    };
+    add_community_strike(&strike_form, &local_user_view.person, &data.auth, &context).await?;
+  }
+
   let person_id = local_user_view.person.id;
   ActivityChannel::submit_activity(
     SendActivityData::RemovePost(post, local_user_view.person, data.0),
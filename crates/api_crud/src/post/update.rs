@@ -1,23 +1,30 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_common::{
-  build_response::build_post_response,
+  build_response::{build_post_response, send_community_mention_notifs, send_post_mention_notifs},
   context::LemmyContext,
   post::{EditPost, PostResponse},
-  request::fetch_site_data,
+  request::{fetch_audio_mime_type, fetch_site_data, is_site_data_missing},
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
+    apply_post_word_filter_action,
     check_community_ban,
+    check_community_language,
+    check_community_post_type,
+    check_community_word_filters,
+    check_url_not_blocked,
+    link_post_images,
     local_site_to_slur_regex,
     local_user_view_from_jwt,
     sanitize_html_opt,
+    slur_filter_regex,
   },
 };
 use lemmy_db_schema::{
   source::{
-    actor_language::CommunityLanguage,
+    community::Community,
     local_site::LocalSite,
-    post::{Post, PostUpdateForm},
+    post::{Post, PostMetadataRefetch, PostUpdateForm},
   },
   traits::Crud,
   utils::{diesel_option_overwrite, naive_now},
@@ -25,8 +32,15 @@ use lemmy_db_schema::{
 use lemmy_utils::{
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
   utils::{
+    mention::{scrape_text_for_community_mentions, scrape_text_for_mentions},
     slurs::check_slurs_opt,
-    validation::{check_url_scheme, clean_url_params, is_valid_body_field, is_valid_post_title},
+    validation::{
+      check_audio_duration,
+      check_url_scheme,
+      clean_url_params,
+      is_valid_body_field,
+      is_valid_post_title,
+    },
   },
 };
 use std::ops::Deref;
@@ -55,6 +69,7 @@ pub async fn update_post(
 
   is_valid_body_field(&data.body, true)?;
   check_url_scheme(&data.url)?;
+  check_audio_duration(&data.audio_duration)?;
 
   let post_id = data.post_id;
   let orig_post = Post::read(&mut context.pool(), post_id).await?;
@@ -71,13 +86,48 @@ pub async fn update_post(
     return Err(LemmyErrorType::NoPostEditAllowed)?;
   }
 
+  let community = Community::read(&mut context.pool(), orig_post.community_id).await?;
+  check_community_post_type(&community, data.url.as_ref())?;
+  check_url_not_blocked(data.url.as_ref(), &mut context.pool()).await?;
+  let texts = [data.name.as_deref(), data.body.as_deref()];
+  let word_filter_action = check_community_word_filters(
+    community.id,
+    &texts.into_iter().flatten().collect::<Vec<_>>(),
+    &mut context.pool(),
+  )
+  .await?;
+  let slur_filter = slur_filter_regex(
+    data.language_id.or(Some(orig_post.language_id)),
+    community.id,
+    &mut context.pool(),
+  )
+  .await?;
+  check_slurs_opt(&data.name, &slur_filter)?;
+  check_slurs_opt(&data.body, &slur_filter)?;
+  let nsfw = if community.nsfw_only {
+    Some(true)
+  } else {
+    data.nsfw
+  };
+
   // Fetch post links and Pictrs cached image
   let data_url = data.url.as_ref();
   let (metadata_res, thumbnail_url) =
     fetch_site_data(context.client(), context.settings(), data_url, true).await;
+  let metadata_missing = is_site_data_missing(&metadata_res, &thumbnail_url);
   let (embed_title, embed_description, embed_video_url) = metadata_res
     .map(|u| (Some(u.title), Some(u.description), Some(u.embed_video_url)))
     .unwrap_or_default();
+  // The audio type is only detected again if the link changed
+  let (audio_mime_type, audio_duration) = match data_url {
+    Some(url) => {
+      let mime_type = fetch_audio_mime_type(context.client(), url).await;
+      let duration = mime_type.as_ref().and(data.audio_duration);
+      (Some(mime_type), Some(duration))
+    }
+    None if orig_post.audio_mime_type.is_some() => (None, data.audio_duration.map(Some)),
+    None => (None, None),
+  };
 
   let name = sanitize_html_opt(&data.name);
   let body = sanitize_html_opt(&data.body);
@@ -85,24 +135,22 @@ pub async fn update_post(
   let embed_title = embed_title.map(|e| sanitize_html_opt(&e));
   let embed_description = embed_description.map(|e| sanitize_html_opt(&e));
 
-  let language_id = data.language_id;
-  CommunityLanguage::is_allowed_community_language(
-    &mut context.pool(),
-    language_id,
-    orig_post.community_id,
-  )
-  .await?;
+  if let Some(language_id) = data.language_id {
+    check_community_language(&community, Some(language_id), &mut context.pool()).await?;
+  }
 
   let post_form = PostUpdateForm::builder()
     .name(name)
     .url(url)
     .body(body)
-    .nsfw(data.nsfw)
+    .nsfw(nsfw)
     .embed_title(embed_title)
     .embed_description(embed_description)
     .embed_video_url(embed_video_url)
     .language_id(data.language_id)
     .thumbnail_url(Some(thumbnail_url))
+    .audio_mime_type(audio_mime_type)
+    .audio_duration(audio_duration)
     .updated(Some(Some(naive_now())))
     .build();
 
@@ -110,6 +158,46 @@ pub async fn update_post(
   let updated_post = Post::update(&mut context.pool(), post_id, &post_form)
     .await
     .with_lemmy_type(LemmyErrorType::CouldntUpdatePost)?;
+  apply_post_word_filter_action(&updated_post, word_filter_action, &mut context.pool()).await?;
+  link_post_images(&updated_post, &mut context.pool(), context.settings()).await?;
+
+  // Retry the link metadata later if the site didn't respond
+  if data_url.is_some() {
+    if metadata_missing {
+      PostMetadataRefetch::schedule(&mut context.pool(), post_id).await?;
+    } else {
+      PostMetadataRefetch::delete(&mut context.pool(), post_id).await?;
+    }
+  }
+
+  // Do the mentions
+  let mentions = updated_post
+    .body
+    .as_deref()
+    .map(scrape_text_for_mentions)
+    .unwrap_or_default();
+  send_post_mention_notifs(
+    mentions,
+    &updated_post,
+    &local_user_view.person,
+    false,
+    &context,
+  )
+  .await?;
+  let community_mentions = updated_post
+    .body
+    .as_deref()
+    .map(scrape_text_for_community_mentions)
+    .unwrap_or_default();
+  send_community_mention_notifs(
+    community_mentions,
+    &updated_post,
+    None,
+    &local_user_view.person,
+    false,
+    &context,
+  )
+  .await?;
 
   ActivityChannel::submit_activity(SendActivityData::UpdatePost(updated_post), &context).await?;
 
@@ -4,6 +4,7 @@ use lemmy_api_common::{
   context::LemmyContext,
   private_message::{CreatePrivateMessage, PrivateMessageResponse},
   send_activity::{ActivityChannel, SendActivityData},
+  spam::{check_spam, report_spam_private_message, SpamCheckInput},
   utils::{
     check_person_block,
     generate_local_apub_endpoint,
@@ -13,6 +14,7 @@ use lemmy_api_common::{
     sanitize_html,
     send_email_to_user,
     EndpointType,
+    NotificationType,
   },
 };
 use lemmy_db_schema::{
@@ -46,6 +48,14 @@ pub async fn create_private_message(
     &mut context.pool(),
   )
   .await?;
+  let spam_review = check_spam(
+    &SpamCheckInput::PrivateMessage {
+      creator: &local_user_view.person,
+      content: &content,
+    },
+    &context,
+  )
+  .await?;
 
   let private_message_form = PrivateMessageInsertForm::builder()
     .content(content.clone())
@@ -56,6 +66,7 @@ pub async fn create_private_message(
   let inserted_private_message = PrivateMessage::create(&mut context.pool(), &private_message_form)
     .await
     .with_lemmy_type(LemmyErrorType::CouldntCreatePrivateMessage)?;
+  report_spam_private_message(&inserted_private_message, spam_review, &mut context.pool()).await?;
 
   let inserted_private_message_id = inserted_private_message.id;
   let protocol_and_hostname = context.settings().get_protocol_and_hostname();
@@ -76,8 +87,13 @@ pub async fn create_private_message(
 
   let view = PrivateMessageView::read(&mut context.pool(), inserted_private_message.id).await?;
 
-  // Send email to the local recipient, if one exists
-  if view.recipient.local {
+  // Send email to the local recipient, if one exists. Messages of shadowbanned users aren't shown
+  // to the recipient, so they don't get an email either.
+  if view.recipient.local
+    && !view
+      .creator
+      .is_shadowbanned_for(view.private_message.published)
+  {
     let recipient_id = data.recipient_id;
     let local_recipient = LocalUserView::read_person(&mut context.pool(), recipient_id).await?;
     let lang = get_interface_language(&local_recipient);
@@ -85,6 +101,7 @@ pub async fn create_private_message(
     let sender_name = &local_user_view.person.name;
     send_email_to_user(
       &local_recipient,
+      NotificationType::PrivateMessage,
       &lang.notification_private_message_subject(sender_name),
       &lang.notification_private_message_body(inbox_link, &content, sender_name),
       context.settings(),
@@ -5,8 +5,8 @@ use lemmy_api_common::{
   context::LemmyContext,
   site::{CreateSite, SiteResponse},
   utils::{
+    check_admin_role,
     generate_site_inbox_url,
-    is_admin,
     local_site_rate_limit_to_rate_limit_config,
     local_user_view_from_jwt,
     sanitize_html,
@@ -23,6 +23,7 @@ use lemmy_db_schema::{
   },
   traits::Crud,
   utils::{diesel_option_overwrite, diesel_option_overwrite_to_url, naive_now},
+  AdminRole,
 };
 use lemmy_db_views::structs::SiteView;
 use lemmy_utils::{
@@ -31,7 +32,9 @@ use lemmy_utils::{
     slurs::{check_slurs, check_slurs_opt},
     validation::{
       build_and_check_regex,
+      check_posting_thresholds,
       check_site_visibility_valid,
+      check_username_change_settings,
       is_valid_body_field,
       site_description_length_check,
       site_name_length_check,
@@ -49,7 +52,7 @@ pub async fn create_site(
   let local_site = LocalSite::read(&mut context.pool()).await?;
 
   // Make sure user is an admin; other types of users should not create site data...
-  is_admin(&local_user_view)?;
+  check_admin_role(&local_user_view, AdminRole::FullAdmin)?;
 
   validate_create_payload(&local_site, &data)?;
 
@@ -102,6 +105,17 @@ pub async fn create_site(
     .federation_enabled(data.federation_enabled)
     .captcha_enabled(data.captcha_enabled)
     .captcha_difficulty(data.captcha_difficulty.clone())
+    .enable_vote_viewer(data.enable_vote_viewer)
+    .anonymize_outgoing_votes(data.anonymize_outgoing_votes)
+    .private_instance_federation(data.private_instance_federation)
+    .check_breached_passwords(data.check_breached_passwords)
+    .emoji_reactions_as_votes(data.emoji_reactions_as_votes)
+    .users_can_create_invites(data.users_can_create_invites)
+    .community_creation_min_account_age_days(data.community_creation_min_account_age_days)
+    .community_creation_min_karma(data.community_creation_min_karma)
+    .community_creation_requires_approval(data.community_creation_requires_approval)
+    .max_username_changes(data.max_username_changes)
+    .username_reuse_cooldown_days(data.username_reuse_cooldown_days)
     .build();
 
   LocalSite::update(&mut context.pool(), &local_site_form).await?;
@@ -169,13 +183,26 @@ fn validate_create_payload(local_site: &LocalSite, create_site: &CreateSite) ->
   check_site_visibility_valid(
     local_site.private_instance,
     local_site.federation_enabled,
+    local_site.private_instance_federation,
     &create_site.private_instance,
     &create_site.federation_enabled,
+    &create_site.private_instance_federation,
   )?;
 
   // Ensure that the sidebar has fewer than the max num characters...
   is_valid_body_field(&create_site.sidebar, false)?;
 
+  check_posting_thresholds(
+    &create_site.community_creation_min_account_age_days,
+    &create_site.community_creation_min_karma,
+    &None,
+  )?;
+
+  check_username_change_settings(
+    &create_site.max_username_changes,
+    &create_site.username_reuse_cooldown_days,
+  )?;
+
   application_question_check(
     &local_site.application_question,
     &create_site.application_question,
@@ -529,6 +556,17 @@ mod tests {
       updated: None,
       registration_mode: site_registration_mode,
       reports_email_admins: false,
+      enable_vote_viewer: false,
+      anonymize_outgoing_votes: false,
+      private_instance_federation: false,
+      check_breached_passwords: false,
+      emoji_reactions_as_votes: false,
+      users_can_create_invites: false,
+      community_creation_min_account_age_days: 0,
+      community_creation_min_karma: 0,
+      community_creation_requires_approval: false,
+      max_username_changes: 0,
+      username_reuse_cooldown_days: 90,
     }
   }
 
@@ -586,6 +624,17 @@ mod tests {
       blocked_instances: None,
       taglines: None,
       registration_mode: site_registration_mode,
+      enable_vote_viewer: None,
+      anonymize_outgoing_votes: None,
+      private_instance_federation: None,
+      check_breached_passwords: None,
+      emoji_reactions_as_votes: None,
+      users_can_create_invites: None,
+      community_creation_min_account_age_days: None,
+      community_creation_min_karma: None,
+      community_creation_requires_approval: None,
+      max_username_changes: None,
+      username_reuse_cooldown_days: None,
       auth: Default::default(),
     }
   }
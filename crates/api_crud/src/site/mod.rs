@@ -40,12 +40,37 @@ pub fn application_question_check(
   }
 }
 
+/// Normalizes a list of email domains for signups, and makes sure that they are valid.
+pub fn email_domains_check(domains: &Option<Vec<String>>) -> LemmyResult<Option<Vec<String>>> {
+  let Some(domains) = domains else {
+    return Ok(None);
+  };
+  let mut cleaned = Vec::with_capacity(domains.len());
+  for domain in domains {
+    let domain = domain.trim().trim_start_matches('@').to_lowercase();
+    if domain.is_empty()
+      || domain.starts_with('.')
+      || domain.contains(|c: char| c.is_whitespace() || c == '@' || c == '/')
+    {
+      Err(LemmyErrorType::InvalidEmailDomain)?
+    }
+    if !cleaned.contains(&domain) {
+      cleaned.push(domain);
+    }
+  }
+  Ok(Some(cleaned))
+}
+
 #[cfg(test)]
 mod tests {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
-  use crate::site::{application_question_check, site_default_post_listing_type_check};
+  use crate::site::{
+    application_question_check,
+    email_domains_check,
+    site_default_post_listing_type_check,
+  };
   use lemmy_db_schema::{ListingType, RegistrationMode};
 
   #[test]
@@ -93,4 +118,24 @@ mod tests {
       RegistrationMode::RequireApplication
     );
   }
+
+  #[test]
+  fn test_email_domains_check() {
+    assert_eq!(None, email_domains_check(&None).unwrap());
+    let domains = vec![
+      " Example.COM ".to_string(),
+      "@mail.example.org".to_string(),
+      "example.com".to_string(),
+    ];
+    assert_eq!(
+      Some(vec![
+        "example.com".to_string(),
+        "mail.example.org".to_string()
+      ]),
+      email_domains_check(&Some(domains)).unwrap()
+    );
+    assert!(email_domains_check(&Some(vec![String::new()])).is_err());
+    assert!(email_domains_check(&Some(vec!["user@example.com".to_string()])).is_err());
+    assert!(email_domains_check(&Some(vec!["example.com/path".to_string()])).is_err());
+  }
 }
@@ -3,15 +3,17 @@ use lemmy_api_common::{
   context::LemmyContext,
   sensitive::Sensitive,
   site::{GetSite, GetSiteResponse, MyUserInfo},
-  utils::{check_user_valid, check_validator_time},
+  utils::{check_user_valid, local_user_id_from_jwt},
 };
-use lemmy_db_schema::{
-  newtypes::LocalUserId,
-  source::{
-    actor_language::{LocalUserLanguage, SiteLanguage},
-    language::Language,
-    tagline::Tagline,
-  },
+use lemmy_db_schema::source::{
+  actor_language::{LocalUserLanguage, SiteLanguage},
+  announcement::Announcement,
+  category::Category,
+  community_last_seen::CommunityLastSeen,
+  disposable_email_domain::DisposableEmailDomain,
+  email_domain::EmailDomain,
+  language::Language,
+  tagline::Tagline,
 };
 use lemmy_db_views::structs::{CustomEmojiView, LocalUserView, SiteView};
 use lemmy_db_views_actor::structs::{
@@ -22,7 +24,6 @@ use lemmy_db_views_actor::structs::{
   PersonView,
 };
 use lemmy_utils::{
-  claims::Claims,
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
   version,
 };
@@ -47,6 +48,10 @@ pub async fn get_site(
       .await
       .with_lemmy_type(LemmyErrorType::SystemErrLogin)?;
 
+    let community_unread_posts = CommunityLastSeen::unread_posts(&mut context.pool(), person_id)
+      .await
+      .with_lemmy_type(LemmyErrorType::SystemErrLogin)?;
+
     let person_id = local_user_view.person.id;
     let community_blocks = CommunityBlockView::for_person(&mut context.pool(), person_id)
       .await
@@ -68,6 +73,7 @@ pub async fn get_site(
     Some(MyUserInfo {
       local_user_view,
       follows,
+      community_unread_posts,
       moderates,
       community_blocks,
       person_blocks,
@@ -80,8 +86,23 @@ pub async fn get_site(
   let all_languages = Language::read_all(&mut context.pool()).await?;
   let discussion_languages = SiteLanguage::read_local_raw(&mut context.pool()).await?;
   let taglines = Tagline::get_all(&mut context.pool(), site_view.local_site.id).await?;
+  let tagline = Tagline::get_random(&mut context.pool(), site_view.local_site.id).await?;
+  let announcements = Announcement::list_active(&mut context.pool()).await?;
   let custom_emojis =
     CustomEmojiView::get_all(&mut context.pool(), site_view.local_site.id).await?;
+  let categories = Category::get_all(&mut context.pool()).await?;
+  let allowed_email_domains = EmailDomain::list(&mut context.pool(), true).await?;
+  let is_admin = my_user
+    .as_ref()
+    .is_some_and(|u| u.local_user_view.person.admin);
+  let (blocked_email_domains, disposable_email_exceptions) = if is_admin {
+    (
+      Some(EmailDomain::list(&mut context.pool(), false).await?),
+      Some(DisposableEmailDomain::list_exceptions(&mut context.pool()).await?),
+    )
+  } else {
+    (None, None)
+  };
 
   Ok(Json(GetSiteResponse {
     site_view,
@@ -91,7 +112,14 @@ pub async fn get_site(
     all_languages,
     discussion_languages,
     taglines,
+    tagline,
+    announcements,
     custom_emojis,
+    categories,
+    allowed_email_domains,
+    blocked_email_domains,
+    disposable_email_exceptions,
+    deleted_content_retention_days: context.settings().deleted_content_retention_days,
   }))
 }
 
@@ -102,10 +130,7 @@ async fn local_user_settings_view_from_jwt_opt(
 ) -> Option<LocalUserView> {
   match jwt {
     Some(jwt) => {
-      let claims = Claims::decode(jwt.as_ref(), &context.secret().jwt_secret)
-        .ok()?
-        .claims;
-      let local_user_id = LocalUserId(claims.sub);
+      let local_user_id = local_user_id_from_jwt(jwt, context).await.ok()?;
       let local_user_view = LocalUserView::read(&mut context.pool(), local_user_id)
         .await
         .ok()?;
@@ -116,8 +141,6 @@ async fn local_user_settings_view_from_jwt_opt(
       )
       .ok()?;
 
-      check_validator_time(&local_user_view.local_user.validator_time, &claims).ok()?;
-
       Some(local_user_view)
     }
     None => None,
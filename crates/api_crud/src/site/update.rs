@@ -1,10 +1,14 @@
-use crate::site::{application_question_check, site_default_post_listing_type_check};
+use crate::site::{
+  application_question_check,
+  email_domains_check,
+  site_default_post_listing_type_check,
+};
 use actix_web::web::{Data, Json};
 use lemmy_api_common::{
   context::LemmyContext,
   site::{EditSite, SiteResponse},
   utils::{
-    is_admin,
+    check_admin_role,
     local_site_rate_limit_to_rate_limit_config,
     local_user_view_from_jwt,
     sanitize_html_opt,
@@ -13,6 +17,8 @@ use lemmy_api_common::{
 use lemmy_db_schema::{
   source::{
     actor_language::SiteLanguage,
+    disposable_email_domain::DisposableEmailDomain,
+    email_domain::EmailDomain,
     federation_allowlist::FederationAllowList,
     federation_blocklist::FederationBlockList,
     local_site::{LocalSite, LocalSiteUpdateForm},
@@ -23,6 +29,7 @@ use lemmy_db_schema::{
   },
   traits::Crud,
   utils::{diesel_option_overwrite, diesel_option_overwrite_to_url, naive_now},
+  AdminRole,
   RegistrationMode,
 };
 use lemmy_db_views::structs::SiteView;
@@ -32,7 +39,10 @@ use lemmy_utils::{
     slurs::check_slurs_opt,
     validation::{
       build_and_check_regex,
+      check_posting_thresholds,
+      check_rate_limit,
       check_site_visibility_valid,
+      check_username_change_settings,
       is_valid_body_field,
       site_description_length_check,
       site_name_length_check,
@@ -51,9 +61,12 @@ pub async fn update_site(
   let site = site_view.site;
 
   // Make sure user is an admin; other types of users should not update site data...
-  is_admin(&local_user_view)?;
+  check_admin_role(&local_user_view, AdminRole::FullAdmin)?;
 
   validate_update_payload(&local_site, &data)?;
+  let allowed_email_domains = email_domains_check(&data.allowed_email_domains)?;
+  let blocked_email_domains = email_domains_check(&data.blocked_email_domains)?;
+  let disposable_email_exceptions = email_domains_check(&data.disposable_email_exceptions)?;
 
   if let Some(discussion_languages) = data.discussion_languages.clone() {
     SiteLanguage::update(&mut context.pool(), discussion_languages.clone(), &site).await?;
@@ -102,6 +115,17 @@ pub async fn update_site(
     .captcha_enabled(data.captcha_enabled)
     .captcha_difficulty(data.captcha_difficulty.clone())
     .reports_email_admins(data.reports_email_admins)
+    .enable_vote_viewer(data.enable_vote_viewer)
+    .anonymize_outgoing_votes(data.anonymize_outgoing_votes)
+    .private_instance_federation(data.private_instance_federation)
+    .check_breached_passwords(data.check_breached_passwords)
+    .emoji_reactions_as_votes(data.emoji_reactions_as_votes)
+    .users_can_create_invites(data.users_can_create_invites)
+    .community_creation_min_account_age_days(data.community_creation_min_account_age_days)
+    .community_creation_min_karma(data.community_creation_min_karma)
+    .community_creation_requires_approval(data.community_creation_requires_approval)
+    .max_username_changes(data.max_username_changes)
+    .username_reuse_cooldown_days(data.username_reuse_cooldown_days)
     .build();
 
   let update_local_site = LocalSite::update(&mut context.pool(), &local_site_form)
@@ -133,6 +157,17 @@ pub async fn update_site(
   let blocked = data.blocked_instances.clone();
   FederationBlockList::replace(&mut context.pool(), blocked).await?;
 
+  // Replace the allowed and blocked email domains for signups
+  EmailDomain::replace(&mut context.pool(), true, allowed_email_domains)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateEmailDomains)?;
+  EmailDomain::replace(&mut context.pool(), false, blocked_email_domains)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateEmailDomains)?;
+  DisposableEmailDomain::replace_exceptions(&mut context.pool(), disposable_email_exceptions)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateEmailDomains)?;
+
   // TODO can't think of a better way to do this.
   // If the server suddenly requires email verification, or required applications, no old users
   // will be able to log in. It really only wants this to be a requirement for NEW signups.
@@ -204,13 +239,51 @@ fn validate_update_payload(local_site: &LocalSite, edit_site: &EditSite) -> Lemm
   check_site_visibility_valid(
     local_site.private_instance,
     local_site.federation_enabled,
+    local_site.private_instance_federation,
     &edit_site.private_instance,
     &edit_site.federation_enabled,
+    &edit_site.private_instance_federation,
   )?;
 
   // Ensure that the sidebar has fewer than the max num characters...
   is_valid_body_field(&edit_site.sidebar, false)?;
 
+  check_posting_thresholds(
+    &edit_site.community_creation_min_account_age_days,
+    &edit_site.community_creation_min_karma,
+    &None,
+  )?;
+
+  check_username_change_settings(
+    &edit_site.max_username_changes,
+    &edit_site.username_reuse_cooldown_days,
+  )?;
+
+  check_rate_limit(
+    &edit_site.rate_limit_message,
+    &edit_site.rate_limit_message_per_second,
+  )?;
+  check_rate_limit(
+    &edit_site.rate_limit_post,
+    &edit_site.rate_limit_post_per_second,
+  )?;
+  check_rate_limit(
+    &edit_site.rate_limit_register,
+    &edit_site.rate_limit_register_per_second,
+  )?;
+  check_rate_limit(
+    &edit_site.rate_limit_image,
+    &edit_site.rate_limit_image_per_second,
+  )?;
+  check_rate_limit(
+    &edit_site.rate_limit_comment,
+    &edit_site.rate_limit_comment_per_second,
+  )?;
+  check_rate_limit(
+    &edit_site.rate_limit_search,
+    &edit_site.rate_limit_search_per_second,
+  )?;
+
   application_question_check(
     &local_site.application_question,
     &edit_site.application_question,
@@ -527,6 +600,17 @@ mod tests {
       updated: None,
       registration_mode: site_registration_mode,
       reports_email_admins: false,
+      enable_vote_viewer: false,
+      anonymize_outgoing_votes: false,
+      private_instance_federation: false,
+      check_breached_passwords: false,
+      emoji_reactions_as_votes: false,
+      users_can_create_invites: false,
+      community_creation_min_account_age_days: 0,
+      community_creation_min_karma: 0,
+      community_creation_requires_approval: false,
+      max_username_changes: 0,
+      username_reuse_cooldown_days: 90,
     }
   }
 
@@ -582,9 +666,23 @@ mod tests {
       captcha_difficulty: None,
       allowed_instances: None,
       blocked_instances: None,
+      allowed_email_domains: None,
+      blocked_email_domains: None,
+      disposable_email_exceptions: None,
       taglines: None,
       registration_mode: site_registration_mode,
       reports_email_admins: None,
+      enable_vote_viewer: None,
+      anonymize_outgoing_votes: None,
+      private_instance_federation: None,
+      check_breached_passwords: None,
+      emoji_reactions_as_votes: None,
+      users_can_create_invites: None,
+      community_creation_min_account_age_days: None,
+      community_creation_min_karma: None,
+      community_creation_requires_approval: None,
+      max_username_changes: None,
+      username_reuse_cooldown_days: None,
       auth: Default::default(),
     }
   }
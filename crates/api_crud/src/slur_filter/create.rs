@@ -0,0 +1,44 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  slur_filter::{CreateSlurFilter, SlurFilterResponse},
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::slur_filter::{SlurFilter, SlurFilterForm},
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType},
+  utils::validation::build_and_check_regex,
+};
+
+#[tracing::instrument(skip(context))]
+pub async fn create_slur_filter(
+  data: Json<CreateSlurFilter>,
+  context: Data<LemmyContext>,
+) -> Result<Json<SlurFilterResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  let pattern = data.pattern.trim();
+  if build_and_check_regex(&Some(pattern))?.is_none() {
+    Err(LemmyErrorType::InvalidRegex)?
+  }
+
+  let form = SlurFilterForm {
+    name: data.name.trim().to_string(),
+    pattern: pattern.to_string(),
+    language_id: data.language_id,
+    community_id: data.community_id,
+  };
+  let slur_filter = SlurFilter::create(&mut context.pool(), &form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateSlurFilter)?;
+
+  Ok(Json(SlurFilterResponse { slur_filter }))
+}
@@ -0,0 +1,24 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  slur_filter::{ListSlurFilters, ListSlurFiltersResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::slur_filter::SlurFilter;
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_slur_filters(
+  data: Query<ListSlurFilters>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListSlurFiltersResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let slur_filters = SlurFilter::list(&mut context.pool()).await?;
+
+  Ok(Json(ListSlurFiltersResponse { slur_filters }))
+}
@@ -0,0 +1,42 @@
+use crate::tagline::check_tagline_content;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  tagline::{CreateTagline, TaglineResponse},
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{
+    local_site::LocalSite,
+    tagline::{Tagline, TaglineForm},
+  },
+  traits::Crud,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn create_tagline(
+  data: Json<CreateTagline>,
+  context: Data<LemmyContext>,
+) -> Result<Json<TaglineResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  let content = check_tagline_content(&data.content, &local_site)?;
+
+  let tagline_form = TaglineForm {
+    local_site_id: local_site.id,
+    content,
+    updated: None,
+  };
+  let tagline = Tagline::create(&mut context.pool(), &tagline_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntCreateTagline)?;
+
+  Ok(Json(TaglineResponse { tagline }))
+}
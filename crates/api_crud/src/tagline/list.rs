@@ -0,0 +1,25 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_common::{
+  context::LemmyContext,
+  tagline::{ListTaglines, ListTaglinesResponse},
+  utils::{is_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::source::{local_site::LocalSite, tagline::Tagline};
+use lemmy_utils::error::LemmyError;
+
+#[tracing::instrument(skip(context))]
+pub async fn list_taglines(
+  data: Query<ListTaglines>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ListTaglinesResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let taglines = Tagline::list(&mut context.pool(), local_site.id, data.page, data.limit).await?;
+
+  Ok(Json(ListTaglinesResponse { taglines }))
+}
@@ -0,0 +1,22 @@
+use lemmy_api_common::utils::{local_site_to_slur_regex, sanitize_html};
+use lemmy_db_schema::source::local_site::LocalSite;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{slurs::check_slurs, validation::is_valid_body_field},
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+/// Taglines are markdown, so they get the same checks as any other body field.
+fn check_tagline_content(content: &str, local_site: &LocalSite) -> LemmyResult<String> {
+  let content = content.trim();
+  if content.is_empty() {
+    Err(LemmyErrorType::InvalidBodyField)?;
+  }
+  check_slurs(content, &local_site_to_slur_regex(local_site))?;
+  is_valid_body_field(&Some(content.to_string()), false)?;
+  Ok(sanitize_html(content))
+}
@@ -0,0 +1,42 @@
+use crate::tagline::check_tagline_content;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  tagline::{EditTagline, TaglineResponse},
+  utils::{check_admin_role, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  source::{
+    local_site::LocalSite,
+    tagline::{Tagline, TaglineUpdateForm},
+  },
+  traits::Crud,
+  utils::naive_now,
+  AdminRole,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+#[tracing::instrument(skip(context))]
+pub async fn update_tagline(
+  data: Json<EditTagline>,
+  context: Data<LemmyContext>,
+) -> Result<Json<TaglineResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+
+  // Make sure user is an admin
+  check_admin_role(&local_user_view, AdminRole::ContentAdmin)?;
+
+  let content = check_tagline_content(&data.content, &local_site)?;
+
+  let tagline_form = TaglineUpdateForm {
+    content,
+    updated: Some(naive_now()),
+  };
+  let tagline = Tagline::update(&mut context.pool(), data.id, &tagline_form)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdateTagline)?;
+
+  Ok(Json(TaglineResponse { tagline }))
+}
@@ -1,9 +1,15 @@
 use activitypub_federation::{config::Data, http_signatures::generate_actor_keypair};
-use actix_web::web::Json;
+use actix_web::{web::Json, HttpRequest};
 use lemmy_api_common::{
+  breached_password::check_breached_password,
+  captcha::{check_captcha, CaptchaInput},
   context::LemmyContext,
+  disposable_email::check_disposable_email,
   person::{LoginResponse, Register},
+  spam::{check_spam, SpamCheckInput},
   utils::{
+    check_email_domain_allowed,
+    create_login_session,
     generate_inbox_url,
     generate_local_apub_endpoint,
     generate_shared_inbox_url,
@@ -19,17 +25,18 @@ use lemmy_api_common::{
 use lemmy_db_schema::{
   aggregates::structs::PersonAggregates,
   source::{
-    captcha_answer::{CaptchaAnswer, CheckCaptchaAnswer},
+    invite::{Invite, InviteUse},
     local_user::{LocalUser, LocalUserInsertForm},
     person::{Person, PersonInsertForm},
+    person_alias::PersonAlias,
     registration_application::{RegistrationApplication, RegistrationApplicationInsertForm},
   },
   traits::Crud,
+  AdminRole,
   RegistrationMode,
 };
 use lemmy_db_views::structs::{LocalUserView, SiteView};
 use lemmy_utils::{
-  claims::Claims,
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
   utils::{
     slurs::{check_slurs, check_slurs_opt},
@@ -40,6 +47,7 @@ use lemmy_utils::{
 #[tracing::instrument(skip(context))]
 pub async fn register(
   data: Json<Register>,
+  req: HttpRequest,
   context: Data<LemmyContext>,
 ) -> Result<Json<LoginResponse>, LemmyError> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
@@ -51,12 +59,24 @@ pub async fn register(
     return Err(LemmyErrorType::RegistrationClosed)?;
   }
 
+  let require_invite =
+    local_site.site_setup && local_site.registration_mode == RegistrationMode::Invite;
+  let invite_code = data.invite_code.as_deref().unwrap_or_default();
+  if require_invite {
+    Invite::read_valid(&mut context.pool(), invite_code)
+      .await
+      .with_lemmy_type(LemmyErrorType::InvalidInviteCode)?;
+  }
+
   password_length_check(&data.password)?;
   honeypot_check(&data.honeypot)?;
+  check_breached_password(&data.password, &local_site, &context).await?;
 
   if local_site.require_email_verification && data.email.is_none() {
     return Err(LemmyErrorType::EmailRequired)?;
   }
+  check_email_domain_allowed(data.email.as_deref(), &mut context.pool()).await?;
+  let disposable_email_review = check_disposable_email(data.email.as_deref(), &context).await?;
 
   if local_site.site_setup && require_registration_application && data.answer.is_none() {
     return Err(LemmyErrorType::RegistrationApplicationAnswerRequired)?;
@@ -68,22 +88,14 @@ pub async fn register(
   }
 
   if local_site.site_setup && local_site.captcha_enabled {
-    if let Some(captcha_uuid) = &data.captcha_uuid {
-      let uuid = uuid::Uuid::parse_str(captcha_uuid)?;
-      let check = CaptchaAnswer::check_captcha(
-        &mut context.pool(),
-        CheckCaptchaAnswer {
-          uuid,
-          answer: data.captcha_answer.clone().unwrap_or_default(),
-        },
-      )
-      .await?;
-      if !check {
-        return Err(LemmyErrorType::CaptchaIncorrect)?;
-      }
-    } else {
-      return Err(LemmyErrorType::CaptchaIncorrect)?;
-    }
+    check_captcha(
+      &CaptchaInput {
+        uuid: data.captcha_uuid.as_deref(),
+        answer: data.captcha_answer.as_deref(),
+      },
+      &context,
+    )
+    .await?;
   }
 
   let slur_regex = local_site_to_slur_regex(&local_site);
@@ -91,8 +103,34 @@ pub async fn register(
   check_slurs_opt(&data.answer, &slur_regex)?;
   let username = sanitize_html(&data.username);
 
+  let spam_review = check_spam(
+    &SpamCheckInput::Registration {
+      username: &data.username,
+      email: data.email.as_deref(),
+    },
+    &context,
+  )
+  .await?;
+  let review = match (spam_review, disposable_email_review) {
+    (Some(spam), Some(disposable)) => Some(format!("{spam}\n\n{disposable}")),
+    (spam, disposable) => spam.or(disposable),
+  };
+  // Users who look like spammers have to be approved by an admin, even with open registration
+  let require_registration_application =
+    require_registration_application || (local_site.site_setup && review.is_some());
+
   let actor_keypair = generate_actor_keypair()?;
   is_valid_actor_name(&data.username, local_site.actor_name_max_length as usize)?;
+  let reserved = PersonAlias::is_name_reserved(
+    &mut context.pool(),
+    &data.username,
+    None,
+    local_site.username_reuse_cooldown_days,
+  )
+  .await?;
+  if reserved {
+    return Err(LemmyErrorType::UsernameRecentlyUsed)?;
+  }
   let actor_id = generate_local_apub_endpoint(
     EndpointType::Person,
     &data.username,
@@ -120,33 +158,56 @@ pub async fn register(
     .instance_id(site_view.site.instance_id)
     .build();
 
-  // insert the person
-  let inserted_person = Person::create(&mut context.pool(), &person_form)
-    .await
-    .with_lemmy_type(LemmyErrorType::UserAlreadyExists)?;
-
   // Automatically set their application as accepted, if they created this with open registration.
   // Also fixes a bug which allows users to log in when registrations are changed to closed.
   let accepted_application = Some(!require_registration_application);
 
   // Create the local user
-  let local_user_form = LocalUserInsertForm::builder()
-    .person_id(inserted_person.id)
-    .email(data.email.as_deref().map(str::to_lowercase))
-    .password_encrypted(data.password.to_string())
-    .show_nsfw(Some(data.show_nsfw))
-    .accepted_application(accepted_application)
-    .default_listing_type(Some(local_site.default_post_listing_type))
-    .build();
+  let local_user_form = |person_id| {
+    LocalUserInsertForm::builder()
+      .person_id(person_id)
+      .email(data.email.as_deref().map(str::to_lowercase))
+      .password_encrypted(data.password.to_string())
+      .show_nsfw(Some(data.show_nsfw))
+      .accepted_application(accepted_application)
+      .default_listing_type(Some(local_site.default_post_listing_type))
+      // The first user becomes the admin of the site
+      .admin_role((!local_site.site_setup).then_some(AdminRole::FullAdmin))
+      .build()
+  };
 
-  let inserted_local_user = LocalUser::create(&mut context.pool(), &local_user_form).await?;
+  let (inserted_person, inserted_local_user) = if require_invite {
+    // The invite might have been used up by another registration in the meantime
+    InviteUse::register(
+      &mut context.pool(),
+      invite_code,
+      &person_form,
+      local_user_form,
+    )
+    .await
+    .with_lemmy_type(LemmyErrorType::UserAlreadyExists)?
+    .ok_or(LemmyErrorType::InvalidInviteCode)?
+  } else {
+    // insert the person
+    let inserted_person = Person::create(&mut context.pool(), &person_form)
+      .await
+      .with_lemmy_type(LemmyErrorType::UserAlreadyExists)?;
+    let inserted_local_user =
+      LocalUser::create(&mut context.pool(), &local_user_form(inserted_person.id)).await?;
+    (inserted_person, inserted_local_user)
+  };
 
   if local_site.site_setup && require_registration_application {
     // Create the registration application
+    // The answer is only missing if the application is required because of the spam or
+    // disposable email check. In that case, the admins get to see the result of the check instead.
+    let answer = match (data.answer.clone(), review) {
+      (Some(answer), Some(review)) => format!("{answer}\n\n{review}"),
+      (answer, review) => answer.or(review).unwrap_or_default(),
+    };
     let form = RegistrationApplicationInsertForm {
       local_user_id: inserted_local_user.id,
-      // We already made sure answer was not null above
-      answer: data.answer.clone().expect("must have an answer"),
+      answer,
     };
 
     RegistrationApplication::create(&mut context.pool(), &form).await?;
@@ -168,14 +229,7 @@ pub async fn register(
   if !local_site.site_setup
     || (!require_registration_application && !local_site.require_email_verification)
   {
-    login_response.jwt = Some(
-      Claims::jwt(
-        inserted_local_user.id.0,
-        &context.secret().jwt_secret,
-        &context.settings().hostname,
-      )?
-      .into(),
-    );
+    login_response.jwt = Some(create_login_session(inserted_local_user.id, &req, &context).await?);
   } else {
     if local_site.require_email_verification {
       let local_user_view = LocalUserView {
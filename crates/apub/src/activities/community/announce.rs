@@ -58,7 +58,7 @@ impl ActivityHandler for RawAnnouncableActivities {
     // if activity is in a community, send to followers
     let community = activity.community(data).await;
     if let Ok(community) = community {
-      if community.local {
+      if community.local && !is_removed_on_arrival(&activity, data).await {
         let actor_id = activity.actor().clone().into();
         verify_person_in_community(&actor_id, &community, data).await?;
         AnnounceActivity::send(self, &community, data).await?;
@@ -68,6 +68,29 @@ impl ActivityHandler for RawAnnouncableActivities {
   }
 }
 
+/// Posts and comments which were removed as soon as they were received, for example because they
+/// are held for review, are not announced to community followers.
+async fn is_removed_on_arrival(
+  activity: &AnnouncableActivities,
+  data: &Data<LemmyContext>,
+) -> bool {
+  match activity {
+    AnnouncableActivities::CreateOrUpdatePost(c) => c
+      .object
+      .id
+      .dereference_local(data)
+      .await
+      .is_ok_and(|p| p.removed),
+    AnnouncableActivities::CreateOrUpdateComment(c) => c
+      .object
+      .id
+      .dereference_local(data)
+      .await
+      .is_ok_and(|c| c.removed),
+    _ => false,
+  }
+}
+
 impl AnnounceActivity {
   pub(crate) fn new(
     object: RawAnnouncableActivities,
@@ -149,9 +172,18 @@ impl ActivityHandler for AnnounceActivity {
       return Err(LemmyErrorType::CannotReceivePage)?;
     }
 
-    // verify here in order to avoid fetching the object twice over http
-    object.verify(context).await?;
-    object.receive(context).await
+    // verify here in order to avoid fetching the object twice over http. The announced activity
+    // may have been received directly already, in that case there is nothing left to do.
+    match object.verify(context).await {
+      Err(LemmyError {
+        error_type: LemmyErrorType::DuplicateActivity,
+        ..
+      }) => Ok(()),
+      res => {
+        res?;
+        object.receive(context).await
+      }
+    }
   }
 }
 
@@ -140,14 +140,13 @@ impl ActivityHandler for CollectionAdd {
           // write mod log
           let actor = self.actor.dereference(context).await?;
           let form = ModAddCommunityForm {
-            mod_person_id: actor.id,
+            mod_person_id: Some(actor.id),
             other_person_id: new_mod.id,
             community_id: community.id,
             removed: Some(false),
           };
           ModAddCommunity::create(&mut context.pool(), &form).await?;
         }
-        // TODO: send websocket notification about added mod
       }
       CollectionType::Featured => {
         let post = ObjectId::<ApubPost>::from(self.object)
@@ -128,14 +128,12 @@ impl ActivityHandler for CollectionRemove {
         // write mod log
         let actor = self.actor.dereference(context).await?;
         let form = ModAddCommunityForm {
-          mod_person_id: actor.id,
+          mod_person_id: Some(actor.id),
           other_person_id: remove_mod.id,
           community_id: community.id,
           removed: Some(true),
         };
         ModAddCommunity::create(&mut context.pool(), &form).await?;
-
-        // TODO: send websocket notification about removed mod
       }
       CollectionType::Featured => {
         let post = ObjectId::<ApubPost>::from(self.object)
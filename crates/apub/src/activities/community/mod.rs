@@ -1,6 +1,7 @@
 use crate::{
   activities::send_lemmy_activity,
   activity_lists::AnnouncableActivities,
+  integrity_proof::sign_announcable,
   objects::{community::ApubCommunity, person::ApubPerson},
   protocol::activities::community::announce::AnnounceActivity,
 };
@@ -53,7 +54,8 @@ pub(crate) async fn send_activity_in_community(
 
   if community.local {
     // send directly to community followers
-    AnnounceActivity::send(activity.clone().try_into()?, community, context).await?;
+    let announcable = sign_announcable(activity.clone().try_into()?, context).await?;
+    AnnounceActivity::send(announcable, community, context).await?;
   } else {
     // send to the community, which will then forward to followers
     inboxes.push(community.shared_inbox_or_inbox());
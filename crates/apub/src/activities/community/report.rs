@@ -87,6 +87,8 @@ impl ActivityHandler for Report {
           original_post_name: post.name.clone(),
           original_post_url: post.url.clone(),
           reason: sanitize_html(&self.summary),
+          reason_id: None,
+          rule_id: None,
           original_post_body: post.body.clone(),
         };
         PostReport::report(&mut context.pool(), &report_form).await?;
@@ -97,6 +99,8 @@ impl ActivityHandler for Report {
           comment_id: comment.id,
           original_comment_text: comment.content.clone(),
           reason: sanitize_html(&self.summary),
+          reason_id: None,
+          rule_id: None,
         };
         CommentReport::report(&mut context.pool(), &report_form).await?;
       }
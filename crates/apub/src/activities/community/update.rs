@@ -9,7 +9,7 @@ use crate::{
   activity_lists::AnnouncableActivities,
   insert_received_activity,
   objects::{community::ApubCommunity, person::ApubPerson},
-  protocol::{activities::community::update::UpdateCommunity, InCommunity},
+  protocol::{activities::community::update::UpdateCommunity, objects::CategoryTag, InCommunity},
 };
 use activitypub_federation::{
   config::Data,
@@ -18,10 +18,16 @@ use activitypub_federation::{
 };
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::{
-  source::{community::Community, person::Person},
+  source::{
+    category::CommunityCategory,
+    community::Community,
+    community_rule::CommunityRule,
+    person::Person,
+  },
   traits::Crud,
 };
 use lemmy_utils::error::LemmyError;
+use tracing::debug;
 use url::Url;
 
 pub(crate) async fn send_update_community(
@@ -76,10 +82,26 @@ impl ActivityHandler for UpdateCommunity {
   #[tracing::instrument(skip_all)]
   async fn receive(self, context: &Data<Self::DataType>) -> Result<(), LemmyError> {
     let community = self.community(context).await?;
+    let moderators = self.object.attributed_to.clone();
+    let categories = CategoryTag::to_category_ids(&self.object.tag, &mut context.pool()).await?;
+    let rules = self.object.rule_forms(community.id);
 
     let community_update_form = self.object.into_update_form();
 
     Community::update(&mut context.pool(), community.id, &community_update_form).await?;
+    CommunityCategory::update(&mut context.pool(), categories, community.id).await?;
+    CommunityRule::replace(&mut context.pool(), community.id, rules).await?;
+
+    // Resync mods so that remote instances learn about ownership transfers
+    if let Some(moderators) = moderators {
+      if !community.local {
+        moderators
+          .dereference(&community, context)
+          .await
+          .map_err(|e| debug!("{}", e))
+          .ok();
+      }
+    }
     Ok(())
   }
 }
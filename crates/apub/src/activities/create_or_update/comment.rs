@@ -8,7 +8,7 @@ use crate::{
   },
   activity_lists::AnnouncableActivities,
   insert_received_activity,
-  mentions::MentionOrValue,
+  mentions::{local_community_mentions_from_tags, MentionOrValue},
   objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson},
   protocol::{
     activities::{create_or_update::note::CreateOrUpdateNote, CreateOrUpdateType},
@@ -23,7 +23,7 @@ use activitypub_federation::{
   traits::{ActivityHandler, Actor, Object},
 };
 use lemmy_api_common::{
-  build_response::send_local_notifs,
+  build_response::{send_community_mention_notifs, send_local_notifs},
   context::LemmyContext,
   utils::{check_post_deleted_or_removed, is_mod_or_admin},
 };
@@ -54,6 +54,10 @@ impl CreateOrUpdateNote {
     let post = Post::read(&mut context.pool(), post_id).await?;
     let community_id = post.community_id;
     let person: ApubPerson = Person::read(&mut context.pool(), person_id).await?.into();
+    // Content of shadowbanned users stays local
+    if person.is_shadowbanned_for(comment.published) {
+      return Ok(());
+    }
     let community: ApubCommunity = Community::read(&mut context.pool(), community_id)
       .await?
       .into();
@@ -143,6 +147,7 @@ impl ActivityHandler for CreateOrUpdateNote {
       }
     }
 
+    let community_mentions = local_community_mentions_from_tags(&self.object.tag, context).await;
     let comment = ApubComment::from_json(self.object, context).await?;
 
     // author likes their own comment by default
@@ -169,6 +174,17 @@ impl ActivityHandler for CreateOrUpdateNote {
     // TODO: for compatibility with other projects, it would be much better to read this from cc or tags
     let mentions = scrape_text_for_mentions(&comment.content);
     send_local_notifs(mentions, &comment.0, &actor, &post, do_send_email, context).await?;
+    if comment.creator_id == actor.id {
+      send_community_mention_notifs(
+        community_mentions,
+        &post,
+        Some(&comment.0),
+        &actor,
+        do_send_email,
+        context,
+      )
+      .await?;
+    }
     Ok(())
   }
 }
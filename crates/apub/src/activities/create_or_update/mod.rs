@@ -1,3 +1,5 @@
 pub mod comment;
+pub mod person;
 pub mod post;
 pub mod private_message;
+pub mod site;
@@ -0,0 +1,67 @@
+use crate::{
+  activities::{generate_activity_id, send_lemmy_activity, verify_is_public},
+  insert_received_activity,
+  objects::{instance::remote_instance_inboxes, person::ApubPerson},
+  protocol::activities::create_or_update::person::UpdatePerson,
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::{activity::UpdateType, public},
+  protocol::verification::verify_urls_match,
+  traits::{ActivityHandler, Actor, Object},
+};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::source::person::Person;
+use lemmy_utils::error::LemmyError;
+use url::Url;
+
+pub(crate) async fn send_update_person(
+  person: Person,
+  context: Data<LemmyContext>,
+) -> Result<(), LemmyError> {
+  let actor: ApubPerson = person.into();
+  let id = generate_activity_id(
+    UpdateType::Update,
+    &context.settings().get_protocol_and_hostname(),
+  )?;
+  let update = UpdatePerson {
+    actor: actor.id().into(),
+    to: vec![public()],
+    object: actor.clone().into_json(&context).await?,
+    kind: UpdateType::Update,
+    id: id.clone(),
+    cc: vec![],
+  };
+
+  let inboxes = remote_instance_inboxes(&mut context.pool()).await?;
+  send_lemmy_activity(&context, update, &actor, inboxes, true).await
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for UpdatePerson {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), LemmyError> {
+    insert_received_activity(&self.id, context).await?;
+    verify_is_public(&self.to, &self.cc)?;
+    verify_urls_match(self.actor.inner(), self.object.id.inner())?;
+    ApubPerson::verify(&self.object, self.actor.inner(), context).await?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(self, context: &Data<Self::DataType>) -> Result<(), LemmyError> {
+    ApubPerson::from_json(self.object, context).await?;
+    Ok(())
+  }
+}
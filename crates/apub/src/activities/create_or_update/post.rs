@@ -9,6 +9,7 @@ use crate::{
   },
   activity_lists::AnnouncableActivities,
   insert_received_activity,
+  mentions::{local_community_mentions_from_tags, local_mentions_from_tags, MentionOrValue},
   objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
   protocol::{
     activities::{create_or_update::page::CreateOrUpdatePage, CreateOrUpdateType},
@@ -17,11 +18,19 @@ use crate::{
 };
 use activitypub_federation::{
   config::Data,
+  fetch::object_id::ObjectId,
   kinds::public,
   protocol::verification::{verify_domains_match, verify_urls_match},
   traits::{ActivityHandler, Actor, Object},
 };
-use lemmy_api_common::context::LemmyContext;
+use lemmy_api_common::{
+  build_response::{
+    send_community_mention_notifs,
+    send_community_post_notifs,
+    send_post_mention_notifs,
+  },
+  context::LemmyContext,
+};
 use lemmy_db_schema::{
   aggregates::structs::PostAggregates,
   newtypes::PersonId,
@@ -47,11 +56,15 @@ impl CreateOrUpdatePage {
       kind.clone(),
       &context.settings().get_protocol_and_hostname(),
     )?;
+    let object = post.into_json(context).await?;
     Ok(CreateOrUpdatePage {
       actor: actor.id().into(),
       to: vec![public()],
-      object: post.into_json(context).await?,
-      cc: vec![community.id()],
+      cc: [community.id()]
+        .into_iter()
+        .chain(object.cc.clone())
+        .collect(),
+      object,
       kind,
       id: id.clone(),
       audience: Some(community.id().into()),
@@ -68,6 +81,10 @@ impl CreateOrUpdatePage {
     let post = ApubPost(post);
     let community_id = post.community_id;
     let person: ApubPerson = Person::read(&mut context.pool(), person_id).await?.into();
+    // Content of shadowbanned users stays local
+    if person.is_shadowbanned_for(post.published) {
+      return Ok(());
+    }
     let community: ApubCommunity = Community::read(&mut context.pool(), community_id)
       .await?
       .into();
@@ -75,12 +92,24 @@ impl CreateOrUpdatePage {
     let create_or_update =
       CreateOrUpdatePage::new(post, &person, &community, kind, &context).await?;
     let is_mod_action = create_or_update.object.is_mod_action(&context).await?;
+
+    // Mentioned persons may not follow the community
+    let mut inboxes = vec![];
+    for tag in &create_or_update.object.tag {
+      if let MentionOrValue::Mention(tag) = tag {
+        let person = ObjectId::<ApubPerson>::from(tag.href.clone())
+          .dereference(&context)
+          .await?;
+        inboxes.push(person.shared_inbox_or_inbox());
+      }
+    }
+
     let activity = AnnouncableActivities::CreateOrUpdatePost(create_or_update);
     send_activity_in_community(
       activity,
       &person,
       &community,
-      vec![],
+      inboxes,
       is_mod_action,
       &context,
     )
@@ -139,6 +168,8 @@ impl ActivityHandler for CreateOrUpdatePage {
 
   #[tracing::instrument(skip_all)]
   async fn receive(self, context: &Data<LemmyContext>) -> Result<(), LemmyError> {
+    let mentions = local_mentions_from_tags(&self.object.tag, context).await;
+    let community_mentions = local_community_mentions_from_tags(&self.object.tag, context).await;
     let post = ApubPost::from_json(self.object, context).await?;
 
     // author likes their own post by default
@@ -152,6 +183,24 @@ impl ActivityHandler for CreateOrUpdatePage {
     // Calculate initial hot_rank for post
     PostAggregates::update_hot_rank(&mut context.pool(), post.id).await?;
 
+    let do_send_email = self.kind == CreateOrUpdateType::Create;
+    let actor = self.actor.dereference(context).await?;
+    if do_send_email {
+      send_community_post_notifs(&post, &actor, context).await?;
+    }
+    // Only the author can mention persons, mods may update the post too
+    if post.creator_id == actor.id {
+      send_post_mention_notifs(mentions, &post, &actor, do_send_email, context).await?;
+      send_community_mention_notifs(
+        community_mentions,
+        &post,
+        None,
+        &actor,
+        do_send_email,
+        context,
+      )
+      .await?;
+    }
     Ok(())
   }
 }
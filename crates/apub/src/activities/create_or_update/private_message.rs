@@ -22,6 +22,13 @@ pub(crate) async fn send_create_or_update_pm(
   kind: CreateOrUpdateType,
   context: Data<LemmyContext>,
 ) -> Result<(), LemmyError> {
+  // Content of shadowbanned users stays local
+  if pm_view
+    .creator
+    .is_shadowbanned_for(pm_view.private_message.published)
+  {
+    return Ok(());
+  }
   let actor: ApubPerson = pm_view.creator.into();
   let recipient: ApubPerson = pm_view.recipient.into();
 
@@ -107,12 +107,17 @@ impl ActivityHandler for Follow {
         PersonFollower::follow(&mut context.pool(), &form).await?;
       }
       UserOrCommunity::Community(c) => {
+        // Restricted communities keep the follow pending until a mod approves it
+        let pending = c.local && c.requires_follow_approval;
         let form = CommunityFollowerForm {
           community_id: c.id,
           person_id: actor.id,
-          pending: false,
+          pending,
         };
         CommunityFollower::follow(&mut context.pool(), &form).await?;
+        if pending {
+          return Ok(());
+        }
       }
     }
 
@@ -1,14 +1,22 @@
 use crate::{
+  collections::community_outbox::backfill_outbox,
   objects::{community::ApubCommunity, person::ApubPerson},
-  protocol::activities::following::{follow::Follow, undo_follow::UndoFollow},
+  protocol::activities::following::{
+    accept::AcceptFollow,
+    follow::Follow,
+    reject::RejectFollow,
+    undo_follow::UndoFollow,
+  },
 };
 use activitypub_federation::config::Data;
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::source::{community::Community, person::Person};
-use lemmy_utils::error::LemmyError;
+use lemmy_db_views_actor::structs::CommunityFollowerView;
+use lemmy_utils::{error::LemmyError, spawn_try_task};
 
 pub mod accept;
 pub mod follow;
+pub mod reject;
 pub mod undo_follow;
 
 pub async fn send_follow_community(
@@ -17,11 +25,48 @@ pub async fn send_follow_community(
   follow: bool,
   context: &Data<LemmyContext>,
 ) -> Result<(), LemmyError> {
+  // Local follows are handled entirely by the api
+  if community.local {
+    return Ok(());
+  }
   let community: ApubCommunity = community.into();
   let actor: ApubPerson = person.into();
   if follow {
-    Follow::send(&actor, &community, context).await
+    // The api already stored the new follower, so it is the first one if there is only one
+    let first_local_follower =
+      CommunityFollowerView::count_local_followers(&mut context.pool(), community.id).await? == 1;
+    Follow::send(&actor, &community, context).await?;
+
+    let backfill_limit = context.settings().outbox_backfill_limit;
+    if first_local_follower && backfill_limit > 0 {
+      spawn_try_task(backfill_outbox(
+        community,
+        backfill_limit,
+        context.reset_request_count(),
+      ));
+    }
+    Ok(())
   } else {
     UndoFollow::send(&actor, &community, context).await
   }
 }
+
+/// Tells a remote follower that a mod approved or denied its follow request.
+pub async fn send_follow_decision(
+  community: Community,
+  follower: Person,
+  approved: bool,
+  context: &Data<LemmyContext>,
+) -> Result<(), LemmyError> {
+  if follower.local {
+    return Ok(());
+  }
+  let community: ApubCommunity = community.into();
+  let follower: ApubPerson = follower.into();
+  let follow = Follow::new(&follower, &community, context)?;
+  if approved {
+    AcceptFollow::send(follow, context).await
+  } else {
+    RejectFollow::send(follow, context).await
+  }
+}
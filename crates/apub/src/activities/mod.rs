@@ -1,4 +1,4 @@
-use self::following::send_follow_community;
+use self::following::{send_follow_community, send_follow_decision};
 use crate::{
   activities::{
     block::{send_ban_from_community, send_ban_from_site},
@@ -7,7 +7,11 @@ use crate::{
       lock_page::send_lock_post,
       update::send_update_community,
     },
-    create_or_update::private_message::send_create_or_update_pm,
+    create_or_update::{
+      person::send_update_person,
+      private_message::send_create_or_update_pm,
+      site::send_update_site,
+    },
     deletion::{
       delete_user::delete_user,
       send_apub_delete_in_community,
@@ -15,8 +19,11 @@ use crate::{
       send_apub_delete_private_message,
       DeletableObjects,
     },
+    reaction::send_reaction_activity,
+    replay::send_replay_activity,
     voting::send_like_activity,
   },
+  integrity_proof::WithProof,
   objects::{community::ApubCommunity, person::ApubPerson},
   protocol::activities::{
     community::report::Report,
@@ -65,6 +72,8 @@ pub mod community;
 pub mod create_or_update;
 pub mod deletion;
 pub mod following;
+pub mod reaction;
+pub(crate) mod replay;
 pub mod unfederated;
 pub mod voting;
 
@@ -216,6 +225,7 @@ where
   });
   info!("Sending activity {}", activity.id().to_string());
   let activity = WithContext::new(activity, CONTEXT.deref().clone());
+  let activity = WithProof::new(activity, &actor.id(), data).await?;
 
   let form = SentActivityForm {
     ap_id: activity.id().clone().into(),
@@ -297,9 +307,15 @@ pub async fn match_outgoing_activities(
       LikePostOrComment(object_id, person, community, score) => {
         send_like_activity(object_id, person, community, score, context).await
       }
+      ReactPostOrComment(object_id, person, community, emoji, add) => {
+        send_reaction_activity(object_id, person, community, emoji, add, context).await
+      }
       FollowCommunity(community, person, follow) => {
         send_follow_community(community, person, follow, &context).await
       }
+      ApproveCommunityFollower(community, follower, approved) => {
+        send_follow_decision(community, follower, approved, &context).await
+      }
       UpdateCommunity(actor, community) => send_update_community(community, actor, context).await,
       DeleteCommunity(actor, community, removed) => {
         let deletable = DeletableObjects::Community(community.clone().into());
@@ -334,9 +350,12 @@ pub async fn match_outgoing_activities(
         send_apub_delete_private_message(&person.into(), pm, deleted, context).await
       }
       DeleteUser(person) => delete_user(person, context).await,
+      UpdatePerson(person) => send_update_person(person, context).await,
+      UpdateSite(site) => send_update_site(site, context).await,
       CreateReport(url, actor, community, reason) => {
         Report::send(ObjectId::from(url), actor, community, reason, context).await
       }
+      ReplayActivity(activity, inboxes) => send_replay_activity(activity, inboxes, context).await,
     }
   };
   if *SYNCHRONOUS_FEDERATION {
@@ -0,0 +1,71 @@
+use crate::{
+  activities::{generate_activity_id, reaction::react_or_vote, verify_person_in_community},
+  insert_received_activity,
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::{
+    activities::reaction::emoji_react::{EmojiReact, EmojiReactType},
+    InCommunity,
+  },
+  PostOrComment,
+};
+use activitypub_federation::{
+  config::Data,
+  fetch::object_id::ObjectId,
+  traits::{ActivityHandler, Actor},
+};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_utils::{error::LemmyError, utils::validation::is_valid_emoji_reaction};
+use url::Url;
+
+impl EmojiReact {
+  pub(in crate::activities::reaction) fn new(
+    object_id: ObjectId<PostOrComment>,
+    actor: &ApubPerson,
+    community: &ApubCommunity,
+    emoji: String,
+    context: &Data<LemmyContext>,
+  ) -> Result<EmojiReact, LemmyError> {
+    Ok(EmojiReact {
+      actor: actor.id().into(),
+      object: object_id,
+      content: emoji,
+      kind: EmojiReactType::EmojiReact,
+      id: generate_activity_id(
+        EmojiReactType::EmojiReact,
+        &context.settings().get_protocol_and_hostname(),
+      )?,
+      audience: Some(community.id().into()),
+    })
+  }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for EmojiReact {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(&self, context: &Data<LemmyContext>) -> Result<(), LemmyError> {
+    insert_received_activity(&self.id, context).await?;
+    let community = self.community(context).await?;
+    verify_person_in_community(&self.actor, &community, context).await?;
+    is_valid_emoji_reaction(&self.content)?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(self, context: &Data<LemmyContext>) -> Result<(), LemmyError> {
+    let community = self.community(context).await?;
+    let actor = self.actor.dereference(context).await?;
+    let object = self.object.dereference(context).await?;
+    react_or_vote(object, actor, self.content, &community, context).await
+  }
+}
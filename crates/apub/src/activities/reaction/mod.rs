@@ -0,0 +1,159 @@
+use crate::{
+  activities::{
+    community::send_activity_in_community,
+    voting::{undo_vote_comment, undo_vote_post, vote_comment, vote_post},
+  },
+  activity_lists::AnnouncableActivities,
+  fetcher::post_or_comment::PostOrComment,
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::{
+    reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
+    voting::vote::VoteType,
+  },
+};
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::{
+  newtypes::DbUrl,
+  source::{
+    comment::{CommentReaction, CommentReactionForm},
+    community::Community,
+    local_site::LocalSite,
+    person::Person,
+    post::{PostReaction, PostReactionForm},
+  },
+  traits::Reactable,
+};
+use lemmy_utils::error::LemmyError;
+
+pub mod emoji_react;
+pub mod undo_emoji_react;
+
+pub(crate) async fn send_reaction_activity(
+  object_id: DbUrl,
+  actor: Person,
+  community: Community,
+  emoji: String,
+  add: bool,
+  context: Data<LemmyContext>,
+) -> Result<(), LemmyError> {
+  let object_id: ObjectId<PostOrComment> = object_id.into();
+  let actor: ApubPerson = actor.into();
+  let community: ApubCommunity = community.into();
+
+  let react = EmojiReact::new(object_id, &actor, &community, emoji, &context)?;
+  let activity = if add {
+    AnnouncableActivities::EmojiReact(react)
+  } else {
+    let undo = UndoEmojiReact::new(react, &actor, &community, &context)?;
+    AnnouncableActivities::UndoEmojiReact(undo)
+  };
+  send_activity_in_community(activity, &actor, &community, vec![], false, &context).await
+}
+
+/// Removes variation selectors and skin tones, so that for example 👍🏽 is treated like 👍.
+pub(crate) fn normalize_emoji(emoji: &str) -> String {
+  emoji
+    .chars()
+    .filter(|c| !matches!(c, '\u{FE0E}' | '\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}'))
+    .collect()
+}
+
+/// The vote which a reaction counts as, if the site counts 👍 and 👎 reactions as votes. Where
+/// downvotes are disabled, 👎 stays a reaction.
+async fn reaction_vote_type(
+  emoji: &str,
+  community: &ApubCommunity,
+  context: &Data<LemmyContext>,
+) -> Result<Option<VoteType>, LemmyError> {
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  if !local_site.emoji_reactions_as_votes {
+    return Ok(None);
+  }
+  let vote_type = match normalize_emoji(emoji).as_str() {
+    "👍" => Some(VoteType::Like),
+    "👎" if local_site.enable_downvotes && community.enable_downvotes => Some(VoteType::Dislike),
+    _ => None,
+  };
+  Ok(vote_type)
+}
+
+#[tracing::instrument(skip_all)]
+async fn react_or_vote(
+  object: PostOrComment,
+  actor: ApubPerson,
+  emoji: String,
+  community: &ApubCommunity,
+  context: &Data<LemmyContext>,
+) -> Result<(), LemmyError> {
+  let vote_type = reaction_vote_type(&emoji, community, context).await?;
+  match (vote_type, object) {
+    (Some(vote_type), PostOrComment::Post(p)) => vote_post(&vote_type, actor, &p, context).await,
+    (Some(vote_type), PostOrComment::Comment(c)) => {
+      vote_comment(&vote_type, actor, &c, context).await
+    }
+    (None, object) => react(object, actor, emoji, context).await,
+  }
+}
+
+#[tracing::instrument(skip_all)]
+async fn undo_react_or_vote(
+  object: PostOrComment,
+  actor: ApubPerson,
+  emoji: &str,
+  community: &ApubCommunity,
+  context: &Data<LemmyContext>,
+) -> Result<(), LemmyError> {
+  let vote_type = reaction_vote_type(emoji, community, context).await?;
+  match (vote_type, object) {
+    (Some(_), PostOrComment::Post(p)) => undo_vote_post(actor, &p, context).await,
+    (Some(_), PostOrComment::Comment(c)) => undo_vote_comment(actor, &c, context).await,
+    (None, object) => undo_react(object, actor, emoji, context).await,
+  }
+}
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn react(
+  object: PostOrComment,
+  actor: ApubPerson,
+  emoji: String,
+  context: &Data<LemmyContext>,
+) -> Result<(), LemmyError> {
+  match object {
+    PostOrComment::Post(p) => {
+      let form = PostReactionForm {
+        post_id: p.id,
+        person_id: actor.id,
+        emoji,
+      };
+      PostReaction::react(&mut context.pool(), &form).await?;
+    }
+    PostOrComment::Comment(c) => {
+      let form = CommentReactionForm {
+        comment_id: c.id,
+        person_id: actor.id,
+        emoji,
+      };
+      CommentReaction::react(&mut context.pool(), &form).await?;
+    }
+  }
+  Ok(())
+}
+
+#[tracing::instrument(skip_all)]
+pub(crate) async fn undo_react(
+  object: PostOrComment,
+  actor: ApubPerson,
+  emoji: &str,
+  context: &Data<LemmyContext>,
+) -> Result<(), LemmyError> {
+  match object {
+    PostOrComment::Post(p) => {
+      PostReaction::unreact(&mut context.pool(), actor.id, p.id, emoji).await?;
+    }
+    PostOrComment::Comment(c) => {
+      CommentReaction::unreact(&mut context.pool(), actor.id, c.id, emoji).await?;
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::{
+  activities::{generate_activity_id, reaction::undo_react_or_vote, verify_person_in_community},
+  insert_received_activity,
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::{
+    activities::reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
+    InCommunity,
+  },
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::activity::UndoType,
+  protocol::verification::verify_urls_match,
+  traits::{ActivityHandler, Actor},
+};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_utils::error::LemmyError;
+use url::Url;
+
+impl UndoEmojiReact {
+  pub(in crate::activities::reaction) fn new(
+    react: EmojiReact,
+    actor: &ApubPerson,
+    community: &ApubCommunity,
+    context: &Data<LemmyContext>,
+  ) -> Result<Self, LemmyError> {
+    Ok(UndoEmojiReact {
+      actor: actor.id().into(),
+      object: react,
+      kind: UndoType::Undo,
+      id: generate_activity_id(
+        UndoType::Undo,
+        &context.settings().get_protocol_and_hostname(),
+      )?,
+      audience: Some(community.id().into()),
+    })
+  }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for UndoEmojiReact {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(&self, context: &Data<LemmyContext>) -> Result<(), LemmyError> {
+    insert_received_activity(&self.id, context).await?;
+    let community = self.community(context).await?;
+    verify_person_in_community(&self.actor, &community, context).await?;
+    verify_urls_match(self.actor.inner(), self.object.actor.inner())?;
+    self.object.verify(context).await?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(self, context: &Data<LemmyContext>) -> Result<(), LemmyError> {
+    let community = self.community(context).await?;
+    let actor = self.actor.dereference(context).await?;
+    let object = self.object.object.dereference(context).await?;
+    undo_react_or_vote(object, actor, &self.object.content, &community, context).await
+  }
+}
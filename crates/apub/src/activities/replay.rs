@@ -0,0 +1,61 @@
+use crate::{fetcher::user_or_community::UserOrCommunity, objects::instance::ApubSite};
+use activitypub_federation::{
+  activity_queue::send_activity,
+  config::Data,
+  traits::{ActivityHandler, Object},
+};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::source::activity::SentActivity;
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use url::Url;
+
+/// An activity which was already sent before, and is sent again exactly as it was stored.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct StoredActivity {
+  id: Url,
+  actor: Url,
+  #[serde(flatten)]
+  other: Map<String, Value>,
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for StoredActivity {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    &self.actor
+  }
+
+  async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+    Err(LemmyErrorType::CouldntReplayActivity)?
+  }
+
+  async fn receive(self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+    Err(LemmyErrorType::CouldntReplayActivity)?
+  }
+}
+
+/// Sends a stored activity again to the given inboxes, signed by its original actor. This is meant
+/// for debugging federation with a specific instance, and for requeueing failed deliveries.
+pub(crate) async fn send_replay_activity(
+  activity: SentActivity,
+  inboxes: Vec<Url>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let activity: StoredActivity = serde_json::from_value(activity.data)?;
+  let actor_id = activity.actor.clone();
+  if let Some(actor) = UserOrCommunity::read_from_id(actor_id.clone(), &context).await? {
+    send_activity(activity, &actor, inboxes, &context).await
+  } else if let Some(site) = ApubSite::read_from_id(actor_id, &context).await? {
+    send_activity(activity, &site, inboxes, &context).await
+  } else {
+    Err(LemmyErrorType::CouldntReplayActivity)?
+  }
+}
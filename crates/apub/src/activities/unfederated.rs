@@ -31,7 +31,6 @@ use lemmy_api_common::{
     BannedPersonsResponse,
     BlockPerson,
     BlockPersonResponse,
-    ChangePassword,
     CommentReplyResponse,
     GetBannedPersons,
     GetCaptcha,
@@ -44,7 +43,6 @@ use lemmy_api_common::{
     GetReportCountResponse,
     GetUnreadCount,
     GetUnreadCountResponse,
-    Login,
     LoginResponse,
     MarkAllAsRead,
     MarkCommentReplyAsRead,
@@ -143,10 +141,6 @@ impl SendActivity for GetComment {
   type Response = CommentResponse;
 }
 
-impl SendActivity for Login {
-  type Response = LoginResponse;
-}
-
 impl SendActivity for GetCaptcha {
   type Response = GetCaptchaResponse;
 }
@@ -207,10 +201,6 @@ impl SendActivity for SaveUserSettings {
   type Response = LoginResponse;
 }
 
-impl SendActivity for ChangePassword {
-  type Response = LoginResponse;
-}
-
 impl SendActivity for GetReportCount {
   type Response = GetReportCountResponse;
 }
@@ -1,5 +1,5 @@
 use crate::{
-  activities::community::send_activity_in_community,
+  activities::{community::send_activity_in_community, reaction::normalize_emoji},
   activity_lists::AnnouncableActivities,
   fetcher::post_or_comment::PostOrComment,
   objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson, post::ApubPost},
@@ -8,19 +8,35 @@ use crate::{
     vote::{Vote, VoteType},
   },
 };
-use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
-use lemmy_api_common::context::LemmyContext;
+use activitypub_federation::{
+  config::Data,
+  fetch::object_id::ObjectId,
+  http_signatures::generate_actor_keypair,
+};
+use lemmy_api_common::{
+  context::LemmyContext,
+  utils::{
+    generate_inbox_url,
+    generate_local_apub_endpoint,
+    generate_shared_inbox_url,
+    EndpointType,
+  },
+};
 use lemmy_db_schema::{
   newtypes::DbUrl,
   source::{
     comment::{CommentLike, CommentLikeForm},
     community::Community,
-    person::Person,
+    local_site::LocalSite,
+    person::{Person, PersonInsertForm},
+    person_vote_pseudonym::{PersonVotePseudonym, PersonVotePseudonymForm},
     post::{PostLike, PostLikeForm},
   },
-  traits::Likeable,
+  traits::{Crud, Likeable},
 };
-use lemmy_utils::error::LemmyError;
+use lemmy_db_views::structs::LocalUserView;
+use lemmy_utils::{error::LemmyError, utils::validation::is_valid_emoji_reaction};
+use uuid::Uuid;
 
 pub mod undo_vote;
 pub mod vote;
@@ -33,7 +49,7 @@ pub(crate) async fn send_like_activity(
   context: Data<LemmyContext>,
 ) -> Result<(), LemmyError> {
   let object_id: ObjectId<PostOrComment> = object_id.try_into()?;
-  let actor: ApubPerson = actor.into();
+  let actor: ApubPerson = vote_actor(actor, &context).await?.into();
   let community: ApubCommunity = community.into();
 
   // score of 1 means upvote, -1 downvote, 0 undo a previous vote
@@ -50,8 +66,82 @@ pub(crate) async fn send_like_activity(
   }
 }
 
+/// Returns the actor which should federate a vote of the given person. If either the site or the
+/// user enabled vote anonymization, this is a pseudonymous local actor which is created on first
+/// use, so that remote instances can't tell which account voted. Local votes are always stored
+/// under the real person.
+async fn vote_actor(person: Person, context: &Data<LemmyContext>) -> Result<Person, LemmyError> {
+  let site_anonymize = LocalSite::read(&mut context.pool())
+    .await
+    .map(|l| l.anonymize_outgoing_votes)
+    .unwrap_or(false);
+  let user_anonymize = LocalUserView::read_person(&mut context.pool(), person.id)
+    .await
+    .map(|l| l.local_user.anonymize_outgoing_votes)
+    .unwrap_or(false);
+  if !person.local || !(site_anonymize || user_anonymize) {
+    return Ok(person);
+  }
+
+  if let Ok(existing) = PersonVotePseudonym::read(&mut context.pool(), person.id).await {
+    return Ok(Person::read(&mut context.pool(), existing.pseudonym_id).await?);
+  }
+
+  let suffix: String = Uuid::new_v4()
+    .simple()
+    .to_string()
+    .chars()
+    .take(14)
+    .collect();
+  let name = format!("voter_{suffix}");
+  let actor_id = generate_local_apub_endpoint(
+    EndpointType::Person,
+    &name,
+    &context.settings().get_protocol_and_hostname(),
+  )?;
+  let keypair = generate_actor_keypair()?;
+  let pseudonym_form = PersonInsertForm::builder()
+    .name(name)
+    .actor_id(Some(actor_id.clone()))
+    .private_key(Some(keypair.private_key))
+    .public_key(keypair.public_key)
+    .inbox_url(Some(generate_inbox_url(&actor_id)?))
+    .shared_inbox_url(Some(generate_shared_inbox_url(&actor_id)?))
+    .bot_account(Some(true))
+    .instance_id(person.instance_id)
+    .build();
+  let pseudonym = Person::create(&mut context.pool(), &pseudonym_form).await?;
+  let form = PersonVotePseudonymForm {
+    person_id: person.id,
+    pseudonym_id: pseudonym.id,
+  };
+  PersonVotePseudonym::create(&mut context.pool(), &form).await?;
+  Ok(pseudonym)
+}
+
+/// Votes of local pseudonyms are already counted under the real voter, so they must be ignored
+/// when a remote community announces them back to us.
+async fn is_local_vote_pseudonym(
+  actor: &ApubPerson,
+  context: &Data<LemmyContext>,
+) -> Result<bool, LemmyError> {
+  Ok(actor.local && PersonVotePseudonym::is_pseudonym(&mut context.pool(), actor.id).await?)
+}
+
+/// The emoji of a Misskey reaction, which is sent as a like with the emoji as content. Plain likes
+/// (👍 or ❤) are still counted as votes.
+fn misskey_reaction(vote: &Vote) -> Option<&str> {
+  let emoji = vote.content.as_deref()?;
+  let is_plain_like = matches!(normalize_emoji(emoji).as_str(), "👍" | "❤");
+  if vote.kind == VoteType::Like && !is_plain_like && is_valid_emoji_reaction(emoji).is_ok() {
+    Some(emoji)
+  } else {
+    None
+  }
+}
+
 #[tracing::instrument(skip_all)]
-async fn vote_comment(
+pub(crate) async fn vote_comment(
   vote_type: &VoteType,
   actor: ApubPerson,
   comment: &ApubComment,
@@ -71,7 +161,7 @@ async fn vote_comment(
 }
 
 #[tracing::instrument(skip_all)]
-async fn vote_post(
+pub(crate) async fn vote_post(
   vote_type: &VoteType,
   actor: ApubPerson,
   post: &ApubPost,
@@ -90,7 +180,7 @@ async fn vote_post(
 }
 
 #[tracing::instrument(skip_all)]
-async fn undo_vote_comment(
+pub(crate) async fn undo_vote_comment(
   actor: ApubPerson,
   comment: &ApubComment,
   context: &Data<LemmyContext>,
@@ -102,7 +192,7 @@ async fn undo_vote_comment(
 }
 
 #[tracing::instrument(skip_all)]
-async fn undo_vote_post(
+pub(crate) async fn undo_vote_post(
   actor: ApubPerson,
   post: &ApubPost,
   context: &Data<LemmyContext>,
@@ -112,3 +202,113 @@ async fn undo_vote_post(
   PostLike::remove(&mut context.pool(), person_id, post_id).await?;
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::{is_local_vote_pseudonym, misskey_reaction, vote_actor};
+  use crate::{
+    objects::tests::init_context,
+    protocol::{
+      activities::voting::vote::{Vote, VoteType},
+      tests::test_json,
+    },
+  };
+  use activitypub_federation::config::Data;
+  use lemmy_api_common::context::LemmyContext;
+  use lemmy_db_schema::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+  };
+  use serial_test::serial;
+
+  #[test]
+  fn test_misskey_reaction() {
+    let mut vote = test_json::<Vote>("assets/misskey/activities/like_note_reaction.json")
+      .unwrap()
+      .inner()
+      .clone();
+    assert_eq!(Some("🎉"), misskey_reaction(&vote));
+
+    // Plain likes stay votes, also with a skin tone or variation selector
+    vote.content = Some("👍🏽".to_string());
+    assert_eq!(None, misskey_reaction(&vote));
+    vote.content = Some("❤️".to_string());
+    assert_eq!(None, misskey_reaction(&vote));
+
+    vote.content = Some(":blobcat@.:".to_string());
+    assert_eq!(Some(":blobcat@.:"), misskey_reaction(&vote));
+    vote.kind = VoteType::Dislike;
+    assert_eq!(None, misskey_reaction(&vote));
+  }
+
+  async fn create_user(
+    name: &str,
+    anonymize: bool,
+    instance: &Instance,
+    context: &Data<LemmyContext>,
+  ) -> Person {
+    let person_form = PersonInsertForm::builder()
+      .name(name.into())
+      .public_key("pubkey".to_string())
+      .local(Some(true))
+      .instance_id(instance.id)
+      .build();
+    let person = Person::create(&mut context.pool(), &person_form)
+      .await
+      .unwrap();
+    let local_user_form = LocalUserInsertForm::builder()
+      .person_id(person.id)
+      .password_encrypted("123456".to_string())
+      .anonymize_outgoing_votes(Some(anonymize))
+      .build();
+    LocalUser::create(&mut context.pool(), &local_user_form)
+      .await
+      .unwrap();
+    person
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_vote_actor() {
+    let context = init_context().await;
+    let inserted_instance =
+      Instance::read_or_create(&mut context.pool(), "my_domain.tld".to_string())
+        .await
+        .unwrap();
+    let anonymous = create_user("anonymous_voter", true, &inserted_instance, &context).await;
+    let public = create_user("public_voter", false, &inserted_instance, &context).await;
+
+    // Votes of users who don't anonymize are sent with their own account
+    let actor = vote_actor(public.clone(), &context).await.unwrap();
+    assert_eq!(public.id, actor.id);
+
+    // Otherwise a pseudonym is created once and then reused
+    let pseudonym = vote_actor(anonymous.clone(), &context).await.unwrap();
+    assert_ne!(anonymous.id, pseudonym.id);
+    assert!(pseudonym.local);
+    assert!(pseudonym.bot_account);
+    let reused = vote_actor(anonymous.clone(), &context).await.unwrap();
+    assert_eq!(pseudonym.id, reused.id);
+    assert!(is_local_vote_pseudonym(&pseudonym.clone().into(), &context)
+      .await
+      .unwrap());
+    assert!(
+      !is_local_vote_pseudonym(&anonymous.clone().into(), &context)
+        .await
+        .unwrap()
+    );
+
+    let pool = &mut context.pool();
+    Person::delete(pool, anonymous.id).await.unwrap();
+    Person::delete(pool, public.id).await.unwrap();
+    Person::delete(pool, pseudonym.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
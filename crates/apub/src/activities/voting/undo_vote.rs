@@ -1,8 +1,9 @@
 use crate::{
   activities::{
     generate_activity_id,
+    reaction::undo_react,
     verify_person_in_community,
-    voting::{undo_vote_comment, undo_vote_post},
+    voting::{is_local_vote_pseudonym, misskey_reaction, undo_vote_comment, undo_vote_post},
   },
   insert_received_activity,
   objects::{community::ApubCommunity, person::ApubPerson},
@@ -68,7 +69,13 @@ impl ActivityHandler for UndoVote {
   #[tracing::instrument(skip_all)]
   async fn receive(self, context: &Data<LemmyContext>) -> Result<(), LemmyError> {
     let actor = self.actor.dereference(context).await?;
+    if is_local_vote_pseudonym(&actor, context).await? {
+      return Ok(());
+    }
     let object = self.object.object.dereference(context).await?;
+    if let Some(emoji) = misskey_reaction(&self.object) {
+      return undo_react(object, actor, emoji, context).await;
+    }
     match object {
       PostOrComment::Post(p) => undo_vote_post(actor, &p, context).await,
       PostOrComment::Comment(c) => undo_vote_comment(actor, &c, context).await,
@@ -1,8 +1,9 @@
 use crate::{
   activities::{
     generate_activity_id,
+    reaction::react,
     verify_person_in_community,
-    voting::{vote_comment, vote_post},
+    voting::{is_local_vote_pseudonym, misskey_reaction, vote_comment, vote_post},
   },
   insert_received_activity,
   objects::{community::ApubCommunity, person::ApubPerson},
@@ -37,6 +38,7 @@ impl Vote {
       kind: kind.clone(),
       id: generate_activity_id(kind, &context.settings().get_protocol_and_hostname())?,
       audience: Some(community.id().into()),
+      content: None,
     })
   }
 }
@@ -63,7 +65,7 @@ impl ActivityHandler for Vote {
       .await
       .map(|l| l.enable_downvotes)
       .unwrap_or(true);
-    if self.kind == VoteType::Dislike && !enable_downvotes {
+    if self.kind == VoteType::Dislike && !(enable_downvotes && community.enable_downvotes) {
       return Err(anyhow!("Downvotes disabled").into());
     }
     Ok(())
@@ -72,7 +74,13 @@ impl ActivityHandler for Vote {
   #[tracing::instrument(skip_all)]
   async fn receive(self, context: &Data<LemmyContext>) -> Result<(), LemmyError> {
     let actor = self.actor.dereference(context).await?;
+    if is_local_vote_pseudonym(&actor, context).await? {
+      return Ok(());
+    }
     let object = self.object.dereference(context).await?;
+    if let Some(emoji) = misskey_reaction(&self) {
+      return react(object, actor, emoji.to_string(), context).await;
+    }
     match object {
       PostOrComment::Post(p) => vote_post(&self.kind, actor, &p, context).await,
       PostOrComment::Comment(c) => vote_comment(&self.kind, actor, &c, context).await,
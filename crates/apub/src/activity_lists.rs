@@ -15,9 +15,17 @@ use crate::{
         chat_message::CreateOrUpdateChatMessage,
         note::CreateOrUpdateNote,
         page::CreateOrUpdatePage,
+        person::UpdatePerson,
+        site::UpdateSite,
       },
       deletion::{delete::Delete, delete_user::DeleteUser, undo_delete::UndoDelete},
-      following::{accept::AcceptFollow, follow::Follow, undo_follow::UndoFollow},
+      following::{
+        accept::AcceptFollow,
+        follow::Follow,
+        reject::RejectFollow,
+        undo_follow::UndoFollow,
+      },
+      reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
       voting::{undo_vote::UndoVote, vote::Vote},
     },
     objects::page::Page,
@@ -41,6 +49,7 @@ use url::Url;
 pub enum SharedInboxActivities {
   Follow(Follow),
   AcceptFollow(AcceptFollow),
+  RejectFollow(RejectFollow),
   UndoFollow(UndoFollow),
   CreateOrUpdatePrivateMessage(CreateOrUpdateChatMessage),
   Report(Report),
@@ -68,6 +77,7 @@ pub enum GroupInboxActivities {
 pub enum PersonInboxActivities {
   Follow(Follow),
   AcceptFollow(AcceptFollow),
+  RejectFollow(RejectFollow),
   UndoFollow(UndoFollow),
   CreateOrUpdatePrivateMessage(CreateOrUpdateChatMessage),
   Delete(Delete),
@@ -85,6 +95,8 @@ pub enum AnnouncableActivities {
   CreateOrUpdatePost(CreateOrUpdatePage),
   Vote(Vote),
   UndoVote(UndoVote),
+  EmojiReact(EmojiReact),
+  UndoEmojiReact(UndoEmojiReact),
   Delete(Delete),
   UndoDelete(UndoDelete),
   UpdateCommunity(UpdateCommunity),
@@ -106,6 +118,8 @@ pub enum SiteInboxActivities {
   BlockUser(BlockUser),
   UndoBlockUser(UndoBlockUser),
   DeleteUser(DeleteUser),
+  UpdatePerson(Box<UpdatePerson>),
+  UpdateSite(Box<UpdateSite>),
 }
 
 #[async_trait::async_trait]
@@ -118,6 +132,8 @@ impl InCommunity for AnnouncableActivities {
       CreateOrUpdatePost(a) => a.community(context).await,
       Vote(a) => a.community(context).await,
       UndoVote(a) => a.community(context).await,
+      EmojiReact(a) => a.community(context).await,
+      UndoEmojiReact(a) => a.community(context).await,
       Delete(a) => a.community(context).await,
       UndoDelete(a) => a.community(context).await,
       UpdateCommunity(a) => a.community(context).await,
@@ -173,5 +189,9 @@ mod tests {
       "assets/lemmy/activities/deletion/delete_user.json",
     )
     .unwrap();
+    test_parse_lemmy_item::<SiteInboxActivities>(
+      "assets/lemmy/activities/create_or_update/update_site.json",
+    )
+    .unwrap();
   }
 }
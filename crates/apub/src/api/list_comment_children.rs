@@ -0,0 +1,54 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use diesel_ltree::Ltree;
+use lemmy_api_common::{
+  comment::{GetCommentChildren, GetCommentsResponse},
+  context::LemmyContext,
+  utils::{check_private_instance, local_user_view_from_jwt_opt, next_comment_page_cursor},
+};
+use lemmy_db_schema::{
+  source::{comment::Comment, local_site::LocalSite},
+  traits::Crud,
+};
+use lemmy_db_views::comment_view::CommentQuery;
+use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
+
+/// Loads the next replies under a comment, walking its subtree in depth-first order.
+#[tracing::instrument(skip(context))]
+pub async fn list_comment_children(
+  data: Query<GetCommentChildren>,
+  context: Data<LemmyContext>,
+) -> Result<Json<GetCommentsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt_opt(data.auth.as_ref(), &context).await;
+  let local_site = LocalSite::read(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let parent = Comment::read(&mut context.pool(), data.parent_id).await?;
+  // Without a cursor, start right after the parent itself
+  let page_cursor = data
+    .page_cursor
+    .clone()
+    .map(Ltree)
+    .unwrap_or_else(|| parent.path.clone());
+  let limit = data.limit;
+
+  let comments = CommentQuery {
+    post_id: Some(parent.post_id),
+    parent_path: Some(parent.path),
+    page_cursor: Some(page_cursor),
+    max_depth: Some(data.max_depth.unwrap_or(1)),
+    local_user: local_user_view.as_ref(),
+    limit,
+    ..Default::default()
+  }
+  .list(&mut context.read_pool())
+  .await
+  .with_lemmy_type(LemmyErrorType::CouldntGetComments)?;
+
+  let next_page = next_comment_page_cursor(&comments, limit)?;
+  Ok(Json(GetCommentsResponse {
+    comments,
+    next_page,
+    new_comments: None,
+  }))
+}
@@ -5,12 +5,14 @@ use crate::{
 };
 use activitypub_federation::config::Data;
 use actix_web::web::{Json, Query};
+use diesel_ltree::Ltree;
 use lemmy_api_common::{
   comment::{GetComments, GetCommentsResponse},
   context::LemmyContext,
-  utils::{check_private_instance, local_user_view_from_jwt_opt},
+  utils::{check_private_instance, local_user_view_from_jwt_opt, next_comment_page_cursor},
 };
 use lemmy_db_schema::{
+  aggregates::structs::PersonPostAggregates,
   source::{comment::Comment, community::Community, local_site::LocalSite},
   traits::Crud,
 };
@@ -35,9 +37,14 @@ pub async fn list_comments(
   let sort = data.sort;
   let max_depth = data.max_depth;
   let saved_only = data.saved_only;
+  let reported_only = data.reported_only;
+  if reported_only.unwrap_or(false) && local_user_view.is_none() {
+    return Err(LemmyErrorType::NotLoggedIn)?;
+  }
   let page = data.page;
   let limit = data.limit;
   let parent_id = data.parent_id;
+  let page_cursor = data.page_cursor.clone().map(Ltree);
 
   let listing_type = Some(listing_type_with_default(
     data.type_,
@@ -54,22 +61,56 @@ pub async fn list_comments(
 
   let parent_path_cloned = parent_path.clone();
   let post_id = data.post_id;
+
+  // Opening a post with GetPost records the visit, so this is the visit before
+  let previous_visit = match (&local_user_view, post_id) {
+    (Some(local_user_view), Some(post_id)) => {
+      let person_id = local_user_view.person.id;
+      let previous_visit = PersonPostAggregates::read(&mut context.pool(), person_id, post_id)
+        .await
+        .ok()
+        .and_then(|a| a.previous_comments_seen);
+      Some((person_id, previous_visit))
+    }
+    _ => None,
+  };
   let comments = CommentQuery {
     listing_type,
     sort,
     max_depth,
     saved_only,
+    reported_only,
     community_id,
     parent_path: parent_path_cloned,
     post_id,
+    page_cursor,
     local_user: local_user_view.as_ref(),
     page,
     limit,
     ..Default::default()
   }
-  .list(&mut context.pool())
+  .list(&mut context.read_pool())
   .await
   .with_lemmy_type(LemmyErrorType::CouldntGetComments)?;
 
-  Ok(Json(GetCommentsResponse { comments }))
+  let next_page = if data.page_cursor.is_some() {
+    next_comment_page_cursor(&comments, limit)?
+  } else {
+    None
+  };
+
+  let new_comments = previous_visit.map(|(person_id, previous_visit)| {
+    comments
+      .iter()
+      .filter(|c| c.creator.id != person_id)
+      .filter(|c| previous_visit.is_some_and(|p| c.comment.published > p))
+      .map(|c| c.comment.id)
+      .collect()
+  });
+
+  Ok(Json(GetCommentsResponse {
+    comments,
+    next_page,
+    new_comments,
+  }))
 }
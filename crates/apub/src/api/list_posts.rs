@@ -10,7 +10,10 @@ use lemmy_api_common::{
   post::{GetPosts, GetPostsResponse},
   utils::{check_private_instance, local_user_view_from_jwt_opt},
 };
-use lemmy_db_schema::source::{community::Community, local_site::LocalSite};
+use lemmy_db_schema::{
+  source::{community::Community, community_last_seen::CommunityLastSeen, local_site::LocalSite},
+  SortType,
+};
 use lemmy_db_views::post_view::PostQuery;
 use lemmy_utils::error::{LemmyError, LemmyErrorExt, LemmyErrorType};
 
@@ -25,8 +28,22 @@ pub async fn list_posts(
   check_private_instance(&local_user_view, &local_site)?;
 
   let sort = data.sort;
+  // Trending is based on the growth of communities, there is no such ranking for posts
+  if sort == Some(SortType::Trending) {
+    Err(LemmyErrorType::InvalidSortType)?
+  }
 
   let page = data.page;
+  let page_after = if let Some(page_cursor) = &data.page_cursor {
+    Some(
+      page_cursor
+        .read(&mut context.pool())
+        .await
+        .with_lemmy_type(LemmyErrorType::CouldntGetPosts)?,
+    )
+  } else {
+    None
+  };
   let limit = data.limit;
   let community_id = if let Some(name) = &data.community_name {
     Some(resolve_actor_identifier::<ApubCommunity, Community>(name, &context, &None, true).await?)
@@ -37,6 +54,10 @@ pub async fn list_posts(
   let saved_only = data.saved_only;
 
   let moderator_view = data.moderator_view;
+  let reported_only = data.reported_only;
+  if reported_only.unwrap_or(false) && local_user_view.is_none() {
+    return Err(LemmyErrorType::NotLoggedIn)?;
+  }
 
   let listing_type = Some(listing_type_with_default(
     data.type_,
@@ -44,20 +65,30 @@ pub async fn list_posts(
     community_id,
   )?);
 
-  let posts = PostQuery {
+  let (posts, next_page) = PostQuery {
     local_user: local_user_view.as_ref(),
     listing_type,
     sort,
     community_id,
     saved_only,
     moderator_view,
+    reported_only,
     page,
+    page_after,
     limit,
     ..Default::default()
   }
-  .list(&mut context.pool())
+  .list_with_next_page(&mut context.read_pool())
   .await
   .with_lemmy_type(LemmyErrorType::CouldntGetPosts)?;
 
-  Ok(Json(GetPostsResponse { posts }))
+  // The newest posts of the community were shown, so they don't count as unread anymore
+  if let (Some(local_user_view), Some(community_id)) = (&local_user_view, community_id) {
+    if page.unwrap_or(1) <= 1 && data.page_cursor.is_none() {
+      CommunityLastSeen::mark_seen(&mut context.pool(), local_user_view.person.id, community_id)
+        .await?;
+    }
+  }
+
+  Ok(Json(GetPostsResponse { posts, next_page }))
 }
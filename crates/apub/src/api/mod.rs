@@ -1,11 +1,14 @@
 use lemmy_db_schema::{newtypes::CommunityId, source::local_site::LocalSite, ListingType};
 use lemmy_utils::error::LemmyError;
 
+pub mod list_comment_children;
 pub mod list_comments;
 pub mod list_posts;
 pub mod read_community;
 pub mod read_person;
+pub mod refresh_object;
 pub mod resolve_object;
+pub mod resolve_objects;
 pub mod search;
 
 /// Returns default listing type, depending if the query is for frontpage or community.
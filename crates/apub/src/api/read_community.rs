@@ -8,7 +8,9 @@ use lemmy_api_common::{
 };
 use lemmy_db_schema::source::{
   actor_language::CommunityLanguage,
-  community::Community,
+  category::CommunityCategory,
+  community::{Community, CommunityTransferRequest},
+  community_rule::CommunityRule,
   local_site::LocalSite,
   site::Site,
 };
@@ -75,11 +77,21 @@ pub async fn get_community(
 
   let community_id = community_view.community.id;
   let discussion_languages = CommunityLanguage::read(&mut context.pool(), community_id).await?;
+  let categories = CommunityCategory::read(&mut context.pool(), community_id).await?;
+  let rules = CommunityRule::for_community(&mut context.pool(), community_id).await?;
+
+  let pending_transfer = CommunityTransferRequest::read(&mut context.pool(), community_id)
+    .await
+    .ok()
+    .filter(|t| is_mod_or_admin || Some(t.recipient_id) == person_id);
 
   Ok(Json(GetCommunityResponse {
     community_view,
     site,
     moderators,
     discussion_languages,
+    categories,
+    rules,
+    pending_transfer,
   }))
 }
@@ -7,11 +7,12 @@ use lemmy_api_common::{
   utils::{check_private_instance, local_user_view_from_jwt_opt},
 };
 use lemmy_db_schema::{
+  aggregates::structs::CommunityPersonAggregates,
   source::{local_site::LocalSite, person::Person},
   utils::post_to_comment_sort_type,
 };
 use lemmy_db_views::{comment_view::CommentQuery, post_view::PostQuery};
-use lemmy_db_views_actor::structs::{CommunityModeratorView, PersonView};
+use lemmy_db_views_actor::structs::{CommunityModeratorView, CommunityView, PersonView};
 use lemmy_utils::error::{LemmyError, LemmyErrorExt2, LemmyErrorType};
 
 #[tracing::instrument(skip(context))]
@@ -91,11 +92,27 @@ pub async fn read_person(
   let moderates =
     CommunityModeratorView::for_person(&mut context.pool(), person_details_id).await?;
 
+  // Mods can see the reputation of the person within their community
+  let community_counts = match (community_id, &local_user_view) {
+    (Some(community_id), Some(local_user_view))
+      if CommunityView::is_mod_or_admin(
+        &mut context.pool(),
+        local_user_view.person.id,
+        community_id,
+      )
+      .await? =>
+    {
+      CommunityPersonAggregates::read(&mut context.pool(), community_id, person_details_id).await?
+    }
+    _ => None,
+  };
+
   // Return the jwt
   Ok(Json(GetPersonDetailsResponse {
     person_view,
     moderates,
     comments,
     posts,
+    community_counts,
   }))
 }
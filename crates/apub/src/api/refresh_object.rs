@@ -0,0 +1,116 @@
+use crate::{
+  fetcher::refetch_object,
+  objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
+};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{RefreshObject, ResolveObjectResponse},
+  utils::{is_admin, is_mod_or_admin, local_user_view_from_jwt},
+};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{community::Community, person::Person, post::Post},
+  traits::Crud,
+};
+use lemmy_db_views::structs::{LocalUserView, PostView};
+use lemmy_db_views_actor::structs::{CommunityView, PersonView};
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use std::time::Duration;
+use url::Url;
+
+/// How long users without mod rights have to wait before refreshing the same object again
+const REFRESH_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
+/// Objects which were refreshed recently, so that users can't flood remote instances with requests
+static RECENTLY_REFRESHED: Lazy<Cache<Url, ()>> = Lazy::new(|| {
+  Cache::builder()
+    .max_capacity(10_000)
+    .time_to_live(REFRESH_COOLDOWN)
+    .build()
+});
+
+#[tracing::instrument(skip(context))]
+pub async fn refresh_object(
+  data: Json<RefreshObject>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ResolveObjectResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  let person_id = Some(local_user_view.person.id);
+  let mut res = ResolveObjectResponse::default();
+
+  if let Some(community_id) = data.community_id {
+    let community: ApubCommunity = Community::read(&mut context.pool(), community_id)
+      .await?
+      .into();
+    let ap_id = community.actor_id.clone().into();
+    check_refresh_allowed(
+      &local_user_view,
+      &ap_id,
+      community.local,
+      Some(community.id),
+      &context,
+    )
+    .await?;
+    refetch_object(community, ap_id, &context).await?;
+    res.community =
+      Some(CommunityView::read(&mut context.pool(), community_id, person_id, None).await?);
+  } else if let Some(refreshed_person_id) = data.person_id {
+    let person: ApubPerson = Person::read(&mut context.pool(), refreshed_person_id)
+      .await?
+      .into();
+    let ap_id = person.actor_id.clone().into();
+    check_refresh_allowed(&local_user_view, &ap_id, person.local, None, &context).await?;
+    refetch_object(person, ap_id, &context).await?;
+    res.person = Some(PersonView::read(&mut context.pool(), refreshed_person_id).await?);
+  } else if let Some(post_id) = data.post_id {
+    let post: ApubPost = Post::read(&mut context.pool(), post_id).await?.into();
+    let ap_id = post.ap_id.clone().into();
+    check_refresh_allowed(
+      &local_user_view,
+      &ap_id,
+      post.local,
+      Some(post.community_id),
+      &context,
+    )
+    .await?;
+    refetch_object(post, ap_id, &context).await?;
+    res.post = Some(PostView::read(&mut context.pool(), post_id, person_id, None).await?);
+  } else {
+    return Err(LemmyErrorType::NoIdGiven)?;
+  }
+
+  Ok(Json(res))
+}
+
+/// Local objects are always up to date. Admins and mods of the community can refresh remote
+/// objects at any time, other users are limited by [REFRESH_COOLDOWN].
+async fn check_refresh_allowed(
+  local_user_view: &LocalUserView,
+  ap_id: &Url,
+  local: bool,
+  community_id: Option<CommunityId>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  if local {
+    return Err(LemmyErrorType::CantRefreshLocalObject)?;
+  }
+  let is_privileged = match community_id {
+    Some(community_id) => {
+      is_mod_or_admin(&mut context.pool(), local_user_view.person.id, community_id)
+        .await
+        .is_ok()
+    }
+    None => is_admin(local_user_view).is_ok(),
+  };
+  if !is_privileged {
+    if RECENTLY_REFRESHED.contains_key(ap_id) {
+      return Err(LemmyErrorType::RateLimitError)?;
+    }
+    RECENTLY_REFRESHED.insert(ap_id.clone(), ()).await;
+  }
+  Ok(())
+}
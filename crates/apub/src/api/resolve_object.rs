@@ -25,16 +25,25 @@ pub async fn resolve_object(
   let local_site = LocalSite::read(&mut context.pool()).await?;
   check_private_instance(&local_user_view, &local_site)?;
   let person_id = local_user_view.map(|v| v.person.id);
+  Ok(Json(resolve(&data.q, person_id, &context).await?))
+}
+
+/// Resolves a single query. Remote lookups are only done for authenticated users.
+pub(crate) async fn resolve(
+  q: &str,
+  person_id: Option<PersonId>,
+  context: &Data<LemmyContext>,
+) -> Result<ResolveObjectResponse, LemmyError> {
   // If we get a valid personId back we can safely assume that the user is authenticated,
   // if there's no personId then the JWT was missing or invalid.
   let is_authenticated = person_id.is_some();
 
   let res = if is_authenticated {
     // user is fully authenticated; allow remote lookups as well.
-    search_query_to_object_id(&data.q, &context).await
+    search_query_to_object_id(q, context).await
   } else {
     // user isn't authenticated only allow a local search.
-    search_query_to_object_id_local(&data.q, &context).await
+    search_query_to_object_id_local(q, context).await
   }
   .with_lemmy_type(LemmyErrorType::CouldntFindObject)?;
 
@@ -47,7 +56,7 @@ async fn convert_response(
   object: SearchableObjects,
   user_id: Option<PersonId>,
   pool: &mut DbPool<'_>,
-) -> Result<Json<ResolveObjectResponse>, LemmyError> {
+) -> Result<ResolveObjectResponse, LemmyError> {
   use SearchableObjects::*;
   let removed_or_deleted;
   let mut res = ResolveObjectResponse::default();
@@ -73,5 +82,5 @@ async fn convert_response(
   if removed_or_deleted {
     return Err(NotFound {}.into());
   }
-  Ok(Json(res))
+  Ok(res)
 }
@@ -0,0 +1,104 @@
+use crate::api::resolve_object::resolve;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use futures::{stream, StreamExt};
+use lemmy_api_common::{
+  context::LemmyContext,
+  site::{ResolveObjectResult, ResolveObjects, ResolveObjectsResponse},
+  utils::local_user_view_from_jwt,
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorType};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Semaphore;
+use url::Url;
+
+/// Maximum number of queries in a single request
+const MAX_RESOLVE_OBJECTS: usize = 50;
+/// Number of queries which are resolved at the same time
+const RESOLVE_CONCURRENCY: usize = 10;
+/// Number of queries which are resolved at the same time from a single instance, so that remote
+/// instances don't get flooded with requests
+const RESOLVE_CONCURRENCY_PER_INSTANCE: usize = 2;
+
+#[tracing::instrument(skip(context))]
+pub async fn resolve_objects(
+  data: Json<ResolveObjects>,
+  context: Data<LemmyContext>,
+) -> Result<Json<ResolveObjectsResponse>, LemmyError> {
+  let local_user_view = local_user_view_from_jwt(&data.auth, &context).await?;
+  if data.q.len() > MAX_RESOLVE_OBJECTS {
+    Err(LemmyErrorType::TooManyObjectsToResolve)?
+  }
+  let person_id = Some(local_user_view.person.id);
+
+  let mut instance_limits = HashMap::new();
+  let queries = data
+    .q
+    .iter()
+    .map(|q| {
+      let limit = instance_limits
+        .entry(query_domain(q))
+        .or_insert_with(|| Arc::new(Semaphore::new(RESOLVE_CONCURRENCY_PER_INSTANCE)))
+        .clone();
+      (q, limit)
+    })
+    .collect::<Vec<_>>();
+
+  let context = &context;
+  let results = stream::iter(queries)
+    .map(|(q, limit)| async move {
+      let _permit = limit.acquire().await;
+      match resolve(q, person_id, context).await {
+        Ok(object) => ResolveObjectResult {
+          q: q.clone(),
+          object: Some(object),
+          error: None,
+        },
+        Err(e) => ResolveObjectResult {
+          q: q.clone(),
+          object: None,
+          error: error_name(&e),
+        },
+      }
+    })
+    .buffered(RESOLVE_CONCURRENCY)
+    .collect()
+    .await;
+
+  Ok(Json(ResolveObjectsResponse { results }))
+}
+
+/// The instance which a query is resolved from, for urls as well as webfinger identifiers.
+fn query_domain(q: &str) -> String {
+  match Url::parse(q) {
+    Ok(url) => url.domain().unwrap_or_default().to_string(),
+    Err(_) => q.rsplit('@').next().unwrap_or_default().to_string(),
+  }
+}
+
+/// The name of the error, as in the `error` field of regular error responses.
+fn error_name(error: &LemmyError) -> Option<String> {
+  serde_json::to_value(&error.error_type)
+    .ok()?
+    .get("error")?
+    .as_str()
+    .map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_query_domain() {
+    assert_eq!("lemmy.ml", query_domain("https://lemmy.ml/post/123"));
+    assert_eq!("lemmy.ml", query_domain("!fediverse@lemmy.ml"));
+    assert_eq!("example.com", query_domain("@user@example.com"));
+  }
+
+  #[test]
+  fn test_error_name() {
+    let error = LemmyError::from(LemmyErrorType::CouldntFindObject);
+    assert_eq!(Some("couldnt_find_object".to_string()), error_name(&error));
+  }
+}
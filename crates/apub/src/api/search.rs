@@ -9,11 +9,13 @@ use lemmy_api_common::{
 use lemmy_db_schema::{
   source::{community::Community, local_site::LocalSite},
   utils::{post_to_comment_sort_type, post_to_person_sort_type},
+  ListingType,
   SearchType,
+  SortType,
 };
 use lemmy_db_views::{comment_view::CommentQuery, post_view::PostQuery};
 use lemmy_db_views_actor::{community_view::CommunityQuery, person_view::PersonQuery};
-use lemmy_utils::error::LemmyError;
+use lemmy_utils::error::{LemmyError, LemmyErrorType};
 
 #[tracing::instrument(skip(context))]
 pub async fn search(
@@ -38,8 +40,11 @@ pub async fn search(
   let page = data.page;
   let limit = data.limit;
   let sort = data.sort;
-  let listing_type = data.listing_type;
+  let category_id = data.category_id;
   let search_type = data.type_.unwrap_or(SearchType::All);
+  if sort == Some(SortType::Trending) && !matches!(search_type, SearchType::Communities) {
+    Err(LemmyErrorType::InvalidSortType)?
+  }
   let community_id = if let Some(name) = &data.community_name {
     Some(
       resolve_actor_identifier::<ApubCommunity, Community>(name, &context, &local_user_view, false)
@@ -49,6 +54,12 @@ pub async fn search(
   } else {
     data.community_id
   };
+  // Posts and comments of hidden communities are only found by their subscribers, unless
+  // searching within the community itself
+  let listing_type = match (data.listing_type, community_id) {
+    (None, None) => Some(ListingType::All),
+    (listing_type, _) => listing_type,
+  };
   let creator_id = data.creator_id;
   let local_user = local_user_view.as_ref().map(|l| l.local_user.clone());
   match search_type {
@@ -64,7 +75,7 @@ pub async fn search(
         limit: (limit),
         ..Default::default()
       }
-      .list(&mut context.pool())
+      .list(&mut context.read_pool())
       .await?;
     }
     SearchType::Comments => {
@@ -79,7 +90,7 @@ pub async fn search(
         limit: (limit),
         ..Default::default()
       }
-      .list(&mut context.pool())
+      .list(&mut context.read_pool())
       .await?;
     }
     SearchType::Communities => {
@@ -87,13 +98,14 @@ pub async fn search(
         sort: (sort),
         listing_type: (listing_type),
         search_term: (Some(q)),
+        category_id: (category_id),
         local_user: (local_user.as_ref()),
         is_mod_or_admin: (is_admin),
         page: (page),
         limit: (limit),
         ..Default::default()
       }
-      .list(&mut context.pool())
+      .list(&mut context.read_pool())
       .await?;
     }
     SearchType::Users => {
@@ -103,7 +115,7 @@ pub async fn search(
         page: (page),
         limit: (limit),
       }
-      .list(&mut context.pool())
+      .list(&mut context.read_pool())
       .await?;
     }
     SearchType::All => {
@@ -124,7 +136,7 @@ pub async fn search(
         limit: (limit),
         ..Default::default()
       }
-      .list(&mut context.pool())
+      .list(&mut context.read_pool())
       .await?;
 
       let q = data.q.clone();
@@ -140,7 +152,7 @@ pub async fn search(
         limit: (limit),
         ..Default::default()
       }
-      .list(&mut context.pool())
+      .list(&mut context.read_pool())
       .await?;
 
       let q = data.q.clone();
@@ -152,13 +164,14 @@ pub async fn search(
           sort: (sort),
           listing_type: (listing_type),
           search_term: (Some(q)),
+          category_id: (category_id),
           local_user: (local_user.as_ref()),
           is_mod_or_admin: (is_admin),
           page: (page),
           limit: (limit),
           ..Default::default()
         }
-        .list(&mut context.pool())
+        .list(&mut context.read_pool())
         .await?
       };
 
@@ -173,7 +186,7 @@ pub async fn search(
           page: (page),
           limit: (limit),
         }
-        .list(&mut context.pool())
+        .list(&mut context.read_pool())
         .await?
       };
     }
@@ -188,7 +201,7 @@ pub async fn search(
         limit: (limit),
         ..Default::default()
       }
-      .list(&mut context.pool())
+      .list(&mut context.read_pool())
       .await?;
     }
   };
@@ -11,8 +11,11 @@ use activitypub_federation::{
 };
 use lemmy_api_common::{context::LemmyContext, utils::generate_moderators_url};
 use lemmy_db_schema::{
-  source::community::{CommunityModerator, CommunityModeratorForm},
-  traits::Joinable,
+  source::{
+    community::{CommunityModerator, CommunityModeratorForm},
+    moderator::{ModAddCommunity, ModAddCommunityForm},
+  },
+  traits::{Crud, Joinable},
 };
 use lemmy_db_views_actor::structs::CommunityModeratorView;
 use lemmy_utils::error::LemmyError;
@@ -64,34 +67,49 @@ impl Collection for ApubCommunityModerators {
     let community_id = owner.id;
     let current_moderators =
       CommunityModeratorView::for_community(&mut data.pool(), community_id).await?;
-    // Remove old mods from database which arent in the moderators collection anymore
-    for mod_user in &current_moderators {
-      let mod_id = ObjectId::from(mod_user.moderator.actor_id.clone());
-      if !apub.ordered_items.contains(&mod_id) {
-        let community_moderator_form = CommunityModeratorForm {
-          community_id: mod_user.community.id,
-          person_id: mod_user.moderator.id,
-        };
-        CommunityModerator::leave(&mut data.pool(), &community_moderator_form).await?;
-      }
-    }
 
-    // Add new mods to database which have been added to moderators collection
+    // Resolve the collection in its original order, the first item is the top mod
+    let mut new_moderators = Vec::new();
     for mod_id in apub.ordered_items {
+      let known = current_moderators
+        .iter()
+        .find(|c| ObjectId::from(c.moderator.actor_id.clone()) == mod_id);
+      if let Some(known) = known {
+        new_moderators.push(known.moderator.id);
+        continue;
+      }
       // Ignore errors as mod accounts might be deleted or instances unavailable.
       let mod_user: Option<ApubPerson> = mod_id.dereference(data).await.ok();
       if let Some(mod_user) = mod_user {
-        if !current_moderators
-          .iter()
-          .map(|c| c.moderator.actor_id.clone())
-          .any(|x| x == mod_user.actor_id)
-        {
-          let community_moderator_form = CommunityModeratorForm {
-            community_id: owner.id,
-            person_id: mod_user.id,
-          };
-          CommunityModerator::join(&mut data.pool(), &community_moderator_form).await?;
-        }
+        new_moderators.push(mod_user.id);
+      }
+    }
+
+    let current_ids: Vec<_> = current_moderators.iter().map(|c| c.moderator.id).collect();
+    if current_ids != new_moderators {
+      // Mods are ordered by join date, so rewrite the whole list to pick up transfers
+      CommunityModerator::delete_for_community(&mut data.pool(), community_id).await?;
+      for person_id in &new_moderators {
+        let community_moderator_form = CommunityModeratorForm {
+          community_id,
+          person_id: *person_id,
+        };
+        CommunityModerator::join(&mut data.pool(), &community_moderator_form).await?;
+      }
+
+      // The remote instance doesn't tell who made the changes, so they are logged without a
+      // moderator. Nothing is logged when the community is first fetched, as those aren't changes.
+      let added = new_moderators.iter().filter(|p| !current_ids.contains(p));
+      let removed = current_ids.iter().filter(|p| !new_moderators.contains(p));
+      let changes = added.map(|p| (p, false)).chain(removed.map(|p| (p, true)));
+      for (person_id, removed) in changes.filter(|_| !current_ids.is_empty()) {
+        let form = ModAddCommunityForm {
+          mod_person_id: None,
+          other_person_id: *person_id,
+          community_id,
+          removed: Some(removed),
+        };
+        ModAddCommunity::create(&mut data.pool(), &form).await?;
       }
     }
 
@@ -123,6 +141,7 @@ mod tests {
     },
     traits::Crud,
   };
+  use lemmy_db_views_moderator::structs::{ModAddCommunityView, ModlogListParams};
   use serial_test::serial;
 
   #[tokio::test]
@@ -175,6 +194,62 @@ mod tests {
     assert_eq!(current_moderators.len(), 1);
     assert_eq!(current_moderators[0].moderator.id, new_mod.id);
 
+    // The changes are logged without a moderator, as the remote instance doesn't say who made them
+    let modlog_params = ModlogListParams {
+      community_id: Some(community_id),
+      mod_person_id: None,
+      other_person_id: None,
+      page: None,
+      limit: None,
+      hide_modlog_names: false,
+    };
+    let modlog = ModAddCommunityView::list(&mut context.pool(), modlog_params)
+      .await
+      .unwrap();
+    assert_eq!(modlog.len(), 2);
+    assert!(modlog
+      .iter()
+      .all(|m| m.mod_add_community.mod_person_id.is_none()));
+    let added = modlog
+      .iter()
+      .find(|m| !m.mod_add_community.removed)
+      .unwrap();
+    assert_eq!(added.modded_person.id, new_mod.id);
+
+    // A changed order in the collection is applied locally
+    CommunityModerator::join(&mut context.pool(), &community_moderator_form)
+      .await
+      .unwrap();
+    let mut json: GroupModerators =
+      file_to_json_object("assets/lemmy/collections/group_moderators.json").unwrap();
+    json
+      .ordered_items
+      .insert(0, ObjectId::from(old_mod.actor_id.clone()));
+    ApubCommunityModerators::from_json(json, &community, &context)
+      .await
+      .unwrap();
+    let current_moderators =
+      CommunityModeratorView::for_community(&mut context.pool(), community_id)
+        .await
+        .unwrap();
+    assert_eq!(current_moderators.len(), 2);
+    assert_eq!(current_moderators[0].moderator.id, old_mod.id);
+    assert_eq!(current_moderators[1].moderator.id, new_mod.id);
+
+    // Neither a changed order nor the first sync of a community is logged
+    CommunityModerator::delete_for_community(&mut context.pool(), community_id)
+      .await
+      .unwrap();
+    let json: GroupModerators =
+      file_to_json_object("assets/lemmy/collections/group_moderators.json").unwrap();
+    ApubCommunityModerators::from_json(json, &community, &context)
+      .await
+      .unwrap();
+    let modlog = ModAddCommunityView::list(&mut context.pool(), modlog_params)
+      .await
+      .unwrap();
+    assert_eq!(modlog.len(), 2);
+
     Person::delete(&mut context.pool(), old_mod.id)
       .await
       .unwrap();
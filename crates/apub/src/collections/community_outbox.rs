@@ -1,17 +1,24 @@
 use crate::{
   activity_lists::AnnouncableActivities,
-  objects::{community::ApubCommunity, post::ApubPost},
+  collections::collection_page_size,
+  objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
   protocol::{
     activities::{
       community::announce::AnnounceActivity,
       create_or_update::page::CreateOrUpdatePage,
       CreateOrUpdateType,
     },
-    collections::group_outbox::GroupOutbox,
+    collections::{
+      collection_page::{CollectionPage, CollectionPageRef},
+      group_outbox::GroupOutbox,
+      ordered_collection_page::OrderedCollectionPage,
+    },
+    objects::group::Group,
   },
 };
 use activitypub_federation::{
   config::Data,
+  fetch::fetch_object_http,
   kinds::collection::OrderedCollectionType,
   protocol::verification::verify_domains_match,
   traits::{ActivityHandler, Collection},
@@ -19,13 +26,25 @@ use activitypub_federation::{
 use futures::future::join_all;
 use lemmy_api_common::{context::LemmyContext, utils::generate_outbox_url};
 use lemmy_db_schema::{
+  newtypes::PostId,
   source::{person::Person, post::Post},
   traits::Crud,
   utils::FETCH_LIMIT_MAX,
 };
-use lemmy_utils::error::LemmyError;
+use lemmy_utils::error::{LemmyError, LemmyResult};
+use once_cell::sync::Lazy;
+use std::time::Duration;
+use tokio::{sync::Semaphore, time::sleep};
+use tracing::info;
 use url::Url;
 
+/// Number of community outboxes which are backfilled at the same time
+static BACKFILL_SLOTS: Lazy<Semaphore> = Lazy::new(|| Semaphore::new(2));
+
+/// Maximum number of outbox pages fetched for a backfill, and the delay between them
+const BACKFILL_MAX_PAGES: usize = 20;
+const BACKFILL_PAGE_DELAY: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Debug)]
 pub(crate) struct ApubCommunityOutbox(Vec<ApubPost>);
 
@@ -41,27 +60,19 @@ impl Collection for ApubCommunityOutbox {
     owner: &Self::Owner,
     data: &Data<Self::DataType>,
   ) -> Result<Self::Kind, LemmyError> {
-    let post_list: Vec<ApubPost> = Post::list_for_community(&mut data.pool(), owner.id)
+    let id = generate_outbox_url(&owner.actor_id)?.into();
+    let total_items = Post::count_for_community(&mut data.pool(), owner.id).await?;
+    let ordered_items = outbox_items(owner, None, collection_page_size(data), data)
       .await?
       .into_iter()
-      .map(Into::into)
+      .map(|(_, announce)| announce)
       .collect();
-    let mut ordered_items = vec![];
-    for post in post_list {
-      let person = Person::read(&mut data.pool(), post.creator_id)
-        .await?
-        .into();
-      let create =
-        CreateOrUpdatePage::new(post, &person, owner, CreateOrUpdateType::Create, data).await?;
-      let announcable = AnnouncableActivities::CreateOrUpdatePost(create);
-      let announce = AnnounceActivity::new(announcable.try_into()?, owner, data)?;
-      ordered_items.push(announce);
-    }
 
     Ok(GroupOutbox {
       r#type: OrderedCollectionType::OrderedCollection,
-      id: generate_outbox_url(&owner.actor_id)?.into(),
-      total_items: ordered_items.len() as i32,
+      first: Some(OrderedCollectionPage::<AnnounceActivity>::url(&id, None)),
+      id,
+      total_items: total_items as i32,
       ordered_items,
     })
   }
@@ -110,3 +121,108 @@ impl Collection for ApubCommunityOutbox {
     Ok(ApubCommunityOutbox(Vec::new()))
   }
 }
+
+impl ApubCommunityOutbox {
+  /// The page of the outbox which starts below `max_id`
+  pub(crate) async fn read_local_page(
+    owner: &ApubCommunity,
+    max_id: Option<i32>,
+    data: &Data<LemmyContext>,
+  ) -> LemmyResult<OrderedCollectionPage<AnnounceActivity>> {
+    let limit = collection_page_size(data);
+    let items = outbox_items(owner, max_id, limit, data).await?;
+    let collection = generate_outbox_url(&owner.actor_id)?.into();
+    Ok(OrderedCollectionPage::new(
+      &collection,
+      max_id,
+      items,
+      limit,
+    ))
+  }
+}
+
+/// Announced creates of the posts in a local community, newest first, with the post ids
+async fn outbox_items(
+  owner: &ApubCommunity,
+  max_id: Option<i32>,
+  limit: i64,
+  data: &Data<LemmyContext>,
+) -> LemmyResult<Vec<(i32, AnnounceActivity)>> {
+  let post_list: Vec<ApubPost> =
+    Post::list_for_community(&mut data.pool(), owner.id, max_id.map(PostId), limit)
+      .await?
+      .into_iter()
+      .map(Into::into)
+      .collect();
+  let mut items = vec![];
+  for post in post_list {
+    let post_id = post.id.0;
+    let person: ApubPerson = Person::read(&mut data.pool(), post.creator_id)
+      .await?
+      .into();
+    let create =
+      CreateOrUpdatePage::new(post, &person, owner, CreateOrUpdateType::Create, data).await?;
+    let announcable = AnnouncableActivities::CreateOrUpdatePost(create);
+    let announce = AnnounceActivity::new(announcable.try_into()?, owner, data)?;
+    items.push((post_id, announce));
+  }
+  Ok(items)
+}
+
+/// Fetches up to `limit` posts from the outbox of a remote community, following pagination. Used
+/// when a community gets its first local follower, so that it doesn't show up empty.
+pub(crate) async fn backfill_outbox(
+  community: ApubCommunity,
+  limit: usize,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let _permit = BACKFILL_SLOTS.acquire().await?;
+  let group: Group = fetch_object_http(community.actor_id.inner(), &context).await?;
+
+  let mut items = Vec::new();
+  let mut pages = 0;
+  let mut next = Some(CollectionPageRef::Url(group.outbox.into()));
+  while let Some(page) = next.take() {
+    if items.len() >= limit || pages >= BACKFILL_MAX_PAGES {
+      break;
+    }
+    let page = match page {
+      CollectionPageRef::Url(url) => {
+        if pages > 0 {
+          sleep(BACKFILL_PAGE_DELAY).await;
+        }
+        verify_domains_match(&url, community.actor_id.inner())?;
+        fetch_object_http::<_, CollectionPage>(&url, &context.reset_request_count()).await?
+      }
+      CollectionPageRef::Page(page) => *page,
+    };
+    pages += 1;
+    // Lemmy also embeds the newest items in a paginated outbox, which are repeated on the first
+    // page
+    match page.first {
+      Some(first) => next = Some(first),
+      None => {
+        items.extend(page.ordered_items);
+        next = page.next;
+      }
+    }
+  }
+  items.truncate(limit);
+
+  // Items which can't be parsed are skipped, as in the regular outbox fetch
+  let mut received = 0;
+  for item in items {
+    let Ok(activity) = serde_json::from_value::<AnnounceActivity>(item) else {
+      continue;
+    };
+    let context = context.reset_request_count();
+    if activity.verify(&context).await.is_ok() && activity.receive(&context).await.is_ok() {
+      received += 1;
+    }
+  }
+  info!(
+    "Backfilled {received} activities from outbox of {}",
+    community.actor_id
+  );
+  Ok(())
+}
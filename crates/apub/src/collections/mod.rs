@@ -1,3 +1,12 @@
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::utils::FETCH_LIMIT_MAX;
+
 pub(crate) mod community_featured;
 pub(crate) mod community_moderators;
 pub(crate) mod community_outbox;
+pub(crate) mod post_replies;
+
+/// Number of items per page of paginated collections, from the config
+pub(crate) fn collection_page_size(context: &LemmyContext) -> i64 {
+  i64::from(context.settings().collection_page_size).clamp(1, FETCH_LIMIT_MAX)
+}
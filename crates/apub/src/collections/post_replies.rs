@@ -0,0 +1,122 @@
+use crate::{
+  objects::comment::ApubComment,
+  protocol::{
+    collections::collection_page::{CollectionPage, CollectionPageRef},
+    objects::note::Note,
+  },
+};
+use activitypub_federation::{
+  config::Data,
+  fetch::{fetch_object_http, object_id::ObjectId},
+  traits::Object,
+};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_utils::error::LemmyResult;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::{HashSet, VecDeque};
+use tokio::sync::Semaphore;
+use tracing::info;
+use url::Url;
+
+/// Maximum number of comments fetched when backfilling the replies of a single post
+const BACKFILL_MAX_COMMENTS: usize = 100;
+/// How many levels of nested replies are followed
+const BACKFILL_MAX_DEPTH: usize = 3;
+/// Maximum number of pages fetched from a single replies collection
+const BACKFILL_MAX_PAGES: usize = 5;
+/// Maximum number of posts whose replies are backfilled at the same time
+const BACKFILL_MAX_CONCURRENT: usize = 10;
+
+static BACKFILL_SLOTS: Lazy<Semaphore> = Lazy::new(|| Semaphore::new(BACKFILL_MAX_CONCURRENT));
+
+/// Walks the replies collection of a remote post which was just fetched, so that existing comment
+/// threads show up instead of only comments which are created later on. When many posts are
+/// fetched at once, eg from a community outbox, only some of them are backfilled.
+pub(crate) async fn backfill_replies(
+  post_ap_id: Url,
+  replies: CollectionPageRef,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let Ok(_slot) = BACKFILL_SLOTS.try_acquire() else {
+    info!("Skipped backfilling comments for post {post_ap_id}, too many backfills running");
+    return Ok(());
+  };
+  let mut queue = VecDeque::from([(replies, 1)]);
+  let mut seen = HashSet::new();
+  let mut received = 0;
+  'collections: while let Some((replies, depth)) = queue.pop_front() {
+    for url in read_collection_item_ids(replies, &context).await {
+      if seen.len() >= BACKFILL_MAX_COMMENTS {
+        break 'collections;
+      }
+      if !seen.insert(url.clone()) {
+        continue;
+      }
+      // Comments which are already known, eg because they arrived in the inbox in the meantime,
+      // are not written again. We only need to fetch them if their replies are followed.
+      let context = context.reset_request_count();
+      let is_known = ObjectId::<ApubComment>::from(url.clone())
+        .dereference_local(&context)
+        .await
+        .is_ok();
+      if is_known && depth >= BACKFILL_MAX_DEPTH {
+        continue;
+      }
+      let Ok(mut note) = fetch_object_http::<_, Note>(&url, &context).await else {
+        continue;
+      };
+      let nested_replies = note.replies.take();
+      if !is_known {
+        if ApubComment::verify(&note, &url, &context).await.is_err()
+          || ApubComment::from_json(note, &context).await.is_err()
+        {
+          continue;
+        }
+        received += 1;
+      }
+      if let Some(nested_replies) = nested_replies.filter(|_| depth < BACKFILL_MAX_DEPTH) {
+        queue.push_back((nested_replies, depth + 1));
+      }
+    }
+  }
+  info!("Backfilled {received} comments for post {post_ap_id}");
+  Ok(())
+}
+
+/// Returns the ids of all items in a replies collection, following pagination. Items may be
+/// embedded objects or urls, either way they are fetched again for verification.
+async fn read_collection_item_ids(
+  collection: CollectionPageRef,
+  context: &Data<LemmyContext>,
+) -> Vec<Url> {
+  let mut ids = Vec::new();
+  let mut pages = 0;
+  let mut next = Some(collection);
+  while let Some(page) = next.take() {
+    if pages >= BACKFILL_MAX_PAGES {
+      break;
+    }
+    let page = match page {
+      CollectionPageRef::Url(url) => {
+        match fetch_object_http::<_, CollectionPage>(&url, &context.reset_request_count()).await {
+          Ok(page) => page,
+          Err(_) => break,
+        }
+      }
+      CollectionPageRef::Page(page) => *page,
+    };
+    pages += 1;
+    ids.extend(page.ordered_items.iter().filter_map(item_id));
+    next = page.first.or(page.next);
+  }
+  ids
+}
+
+fn item_id(item: &Value) -> Option<Url> {
+  let id = match item {
+    Value::Object(object) => object.get("id")?,
+    id => id,
+  };
+  id.as_str().and_then(|id| Url::parse(id).ok())
+}
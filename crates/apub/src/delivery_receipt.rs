@@ -0,0 +1,102 @@
+use activitypub_federation::FEDERATION_CONTENT_TYPE;
+use lemmy_db_schema::{
+  source::sent_activity_delivery::{SentActivityDelivery, SentActivityDeliveryForm},
+  utils::ActualDbPool,
+};
+use reqwest::{header::CONTENT_TYPE, Method, Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use serde::Deserialize;
+use task_local_extensions::Extensions;
+use tracing::warn;
+use url::Url;
+
+/// A reqwest middleware which stores the outcome of every activity delivery to a remote inbox,
+/// including retries. This way admins can check if and when an activity reached an instance.
+pub struct DeliveryReceipts(pub ActualDbPool);
+
+#[async_trait::async_trait]
+impl Middleware for DeliveryReceipts {
+  async fn handle(
+    &self,
+    req: Request,
+    extensions: &mut Extensions,
+    next: Next<'_>,
+  ) -> reqwest_middleware::Result<Response> {
+    let activity_id = delivered_activity_id(&req);
+    let inbox = req.url().clone();
+    let res = next.run(req, extensions).await;
+
+    if let Some(activity_id) = activity_id {
+      let form = SentActivityDeliveryForm {
+        ap_id: activity_id.into(),
+        inbox: inbox.into(),
+        status: res.as_ref().ok().map(|r| i32::from(r.status().as_u16())),
+      };
+      if let Err(e) = SentActivityDelivery::create(&mut (&self.0).into(), &form).await {
+        warn!("Failed to store activity delivery: {e}");
+      }
+    }
+    res
+  }
+}
+
+/// Returns the activity id if the request delivers an activity, meaning that it posts activity
+/// json.
+fn delivered_activity_id(req: &Request) -> Option<Url> {
+  #[derive(Deserialize)]
+  struct ActivityId {
+    id: Url,
+  }
+
+  if req.method() != Method::POST {
+    return None;
+  }
+  let content_type = req.headers().get(CONTENT_TYPE)?;
+  if content_type.as_bytes() != FEDERATION_CONTENT_TYPE.as_bytes() {
+    return None;
+  }
+  let body = req.body()?.as_bytes()?;
+  serde_json::from_slice::<ActivityId>(body)
+    .ok()
+    .map(|a| a.id)
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+
+  #[test]
+  fn test_delivered_activity_id() {
+    let client = reqwest::Client::new();
+    let activity = r#"{"id":"http://example.com/activities/like/1","type":"Like"}"#;
+
+    let delivery = client
+      .post("http://remote.example.com/inbox")
+      .header(CONTENT_TYPE, FEDERATION_CONTENT_TYPE)
+      .body(activity)
+      .build()
+      .unwrap();
+    assert_eq!(
+      Some(Url::parse("http://example.com/activities/like/1").unwrap()),
+      delivered_activity_id(&delivery)
+    );
+
+    let fetch = client
+      .get("http://remote.example.com/u/alice")
+      .header(CONTENT_TYPE, FEDERATION_CONTENT_TYPE)
+      .build()
+      .unwrap();
+    assert_eq!(None, delivered_activity_id(&fetch));
+
+    let json_post = client
+      .post("http://remote.example.com/api")
+      .header(CONTENT_TYPE, "application/json")
+      .body(activity)
+      .build()
+      .unwrap();
+    assert_eq!(None, delivered_activity_id(&json_post));
+  }
+}
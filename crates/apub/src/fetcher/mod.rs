@@ -1,6 +1,7 @@
 use activitypub_federation::{
   config::Data,
-  fetch::webfinger::webfinger_resolve_actor,
+  error::Error as FederationError,
+  fetch::{fetch_object_http, webfinger::webfinger_resolve_actor},
   traits::{Actor, Object},
 };
 use diesel::NotFound;
@@ -8,10 +9,12 @@ use itertools::Itertools;
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::traits::ApubActor;
 use lemmy_db_views::structs::LocalUserView;
-use lemmy_utils::error::LemmyError;
+use lemmy_utils::error::{LemmyError, LemmyResult};
+use url::Url;
 
 pub mod post_or_comment;
 pub mod search;
+pub mod signing_actor;
 pub mod user_or_community;
 
 /// Resolve actor identifier like `!news@example.com` to user or community object.
@@ -62,3 +65,25 @@ where
     )
   }
 }
+
+/// Fetches the object over http and updates the local copy, bypassing the usual refresh interval.
+/// If the object was deleted on its instance, it is marked as deleted locally.
+pub async fn refetch_object<Kind>(
+  object: Kind,
+  ap_id: Url,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()>
+where
+  Kind: Object<DataType = LemmyContext, Error = LemmyError> + Send + 'static,
+  for<'de2> <Kind as Object>::Kind: serde::Deserialize<'de2>,
+{
+  match fetch_object_http::<_, Kind::Kind>(&ap_id, context).await {
+    Err(FederationError::ObjectDeleted) => object.delete(context).await?,
+    res => {
+      let json = res?;
+      Kind::verify(&json, &ap_id, context).await?;
+      Kind::from_json(json, context).await?;
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,297 @@
+use crate::{
+  fetcher::user_or_community::{PersonOrGroup, UserOrCommunity},
+  objects::instance::ApubSite,
+  protocol::objects::instance::Instance,
+};
+use activitypub_federation::{
+  config::Data,
+  fetch::fetch_object_http,
+  traits::{Actor, Object},
+};
+use chrono::NaiveDateTime;
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::{source::actor_key_rotation::ActorKeyRotation, utils::naive_now};
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::{future::Future, time::Duration};
+use url::Url;
+
+/// How long public keys of remote actors are kept in memory
+const SIGNING_KEY_CACHE_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// Minimum time between two refetches of the same actor after a failed signature check, so that
+/// requests with invalid signatures can't be used to flood remote instances
+const SIGNING_KEY_REFETCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long the previous key of an actor is still accepted after a key rotation. Failed deliveries
+/// are retried for up to three days, and may still be signed with the old key.
+const KEY_ROTATION_GRACE_DAYS: i64 = 3;
+
+tokio::task_local! {
+  /// Set while an activity is checked against the previous key of its actor
+  static PREVIOUS_KEY: SigningActor;
+}
+
+/// Public keys of actors which recently sent activities, keyed by the key owner
+static SIGNING_KEYS: Lazy<Cache<Url, SigningActor>> = Lazy::new(|| {
+  Cache::builder()
+    .max_capacity(50_000)
+    .time_to_live(SIGNING_KEY_CACHE_DURATION)
+    .build()
+});
+
+static RECENTLY_REFETCHED: Lazy<Cache<Url, ()>> = Lazy::new(|| {
+  Cache::builder()
+    .max_capacity(10_000)
+    .time_to_live(SIGNING_KEY_REFETCH_INTERVAL)
+    .build()
+});
+
+/// Json of an actor which signs requests. Besides users and communities, this includes instance
+/// actors which sign fetches from private instances.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum SigningActorKind {
+  PersonOrGroup(PersonOrGroup),
+  Instance(Instance),
+}
+
+/// The actor which signed an incoming activity. Only its public key is needed to verify the http
+/// signature, so it is cached in memory instead of reading the whole actor for every activity.
+#[derive(Clone, Debug)]
+pub struct SigningActor {
+  id: Url,
+  public_key_pem: String,
+  last_refreshed_at: NaiveDateTime,
+}
+
+impl From<UserOrCommunity> for SigningActor {
+  fn from(actor: UserOrCommunity) -> Self {
+    let (id, public_key_pem, last_refreshed_at) = match actor {
+      UserOrCommunity::User(p) => (
+        p.actor_id.clone(),
+        p.public_key.clone(),
+        p.last_refreshed_at,
+      ),
+      UserOrCommunity::Community(c) => (
+        c.actor_id.clone(),
+        c.public_key.clone(),
+        c.last_refreshed_at,
+      ),
+    };
+    SigningActor {
+      id: id.into(),
+      public_key_pem,
+      last_refreshed_at,
+    }
+  }
+}
+
+impl From<ApubSite> for SigningActor {
+  fn from(site: ApubSite) -> Self {
+    SigningActor {
+      id: site.actor_id.clone().into(),
+      public_key_pem: site.public_key.clone(),
+      last_refreshed_at: site.last_refreshed_at,
+    }
+  }
+}
+
+impl SigningActor {
+  async fn cache<T: Into<SigningActor>>(actor: T) -> Self {
+    let actor = actor.into();
+    SIGNING_KEYS.insert(actor.id.clone(), actor.clone()).await;
+    actor
+  }
+
+  /// Reads the actor from the database, without going through the key cache.
+  async fn read_from_db(
+    actor_id: &Url,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<Option<SigningActor>> {
+    if let Some(actor) = UserOrCommunity::read_from_id(actor_id.clone(), context).await? {
+      return Ok(Some(actor.into()));
+    }
+    Ok(
+      ApubSite::read_from_id(actor_id.clone(), context)
+        .await?
+        .map(Into::into),
+    )
+  }
+
+  /// Drops the cached key of an actor after a failed signature check, and fetches the actor again
+  /// in case it rotated its key. Returns true if the key changed, so that the activity can be
+  /// checked once more.
+  pub(crate) async fn refetch_key(
+    actor_id: &Url,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<bool> {
+    let old_key = match SIGNING_KEYS.get(actor_id) {
+      Some(actor) => Some(actor.public_key_pem),
+      None => SigningActor::read_from_db(actor_id, context)
+        .await?
+        .map(|a| a.public_key_pem),
+    };
+    SIGNING_KEYS.invalidate(actor_id).await;
+    if old_key.is_none() || RECENTLY_REFETCHED.contains_key(actor_id) {
+      return Ok(false);
+    }
+    RECENTLY_REFETCHED.insert(actor_id.clone(), ()).await;
+
+    let json: SigningActorKind = fetch_object_http(actor_id, context).await?;
+    SigningActor::verify(&json, actor_id, context).await?;
+    let actor = SigningActor::from_json(json, context).await?;
+    Ok(old_key.as_ref() != Some(&actor.public_key_pem))
+  }
+
+  /// Returns the previous key of an actor, if it was rotated recently.
+  pub(crate) async fn previous_key(
+    actor_id: &Url,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<Option<SigningActor>> {
+    let since = naive_now() - chrono::Duration::days(KEY_ROTATION_GRACE_DAYS);
+    let rotation =
+      ActorKeyRotation::read_since(&mut context.pool(), &actor_id.clone().into(), since).await?;
+    Ok(rotation.map(|r| SigningActor {
+      id: actor_id.clone(),
+      public_key_pem: r.previous_public_key,
+      last_refreshed_at: naive_now(),
+    }))
+  }
+
+  /// Runs the signature check with the previous key of the actor, instead of the current one.
+  pub(crate) async fn with_previous_key<F: Future>(previous_key: SigningActor, f: F) -> F::Output {
+    PREVIOUS_KEY.scope(previous_key, f).await
+  }
+}
+
+#[async_trait::async_trait]
+impl Object for SigningActor {
+  type DataType = LemmyContext;
+  type Kind = SigningActorKind;
+  type Error = LemmyError;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    Some(self.last_refreshed_at)
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_id(
+    object_id: Url,
+    data: &Data<Self::DataType>,
+  ) -> Result<Option<Self>, LemmyError> {
+    let previous_key = PREVIOUS_KEY
+      .try_with(|a| (a.id == object_id).then(|| a.clone()))
+      .ok()
+      .flatten();
+    if let Some(actor) = previous_key.or(SIGNING_KEYS.get(&object_id)) {
+      return Ok(Some(actor));
+    }
+    Ok(match SigningActor::read_from_db(&object_id, data).await? {
+      Some(actor) => Some(SigningActor::cache(actor).await),
+      None => None,
+    })
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn delete(self, data: &Data<Self::DataType>) -> Result<(), LemmyError> {
+    SIGNING_KEYS.invalidate(&self.id).await;
+    if let Some(actor) = UserOrCommunity::read_from_id(self.id, data).await? {
+      actor.delete(data).await?;
+    }
+    Ok(())
+  }
+
+  async fn into_json(self, _data: &Data<Self::DataType>) -> Result<Self::Kind, LemmyError> {
+    Err(LemmyErrorType::Unknown("Signing actors are never sent as json".to_string()).into())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    apub: &Self::Kind,
+    expected_domain: &Url,
+    data: &Data<Self::DataType>,
+  ) -> Result<(), LemmyError> {
+    match apub {
+      SigningActorKind::PersonOrGroup(a) => UserOrCommunity::verify(a, expected_domain, data).await,
+      SigningActorKind::Instance(i) => ApubSite::verify(i, expected_domain, data).await,
+    }
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn from_json(apub: Self::Kind, data: &Data<Self::DataType>) -> Result<Self, LemmyError> {
+    Ok(match apub {
+      SigningActorKind::PersonOrGroup(a) => {
+        SigningActor::cache(UserOrCommunity::from_json(a, data).await?).await
+      }
+      SigningActorKind::Instance(i) => {
+        SigningActor::cache(ApubSite::from_json(i, data).await?).await
+      }
+    })
+  }
+}
+
+impl Actor for SigningActor {
+  fn id(&self) -> Url {
+    self.id.clone()
+  }
+
+  fn public_key_pem(&self) -> &str {
+    &self.public_key_pem
+  }
+
+  fn private_key_pem(&self) -> Option<String> {
+    None
+  }
+
+  // Signing actors are only used to verify incoming activities, and nothing is ever sent to them
+  fn inbox(&self) -> Url {
+    unimplemented!()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+  use crate::objects::{person::tests::parse_lemmy_person, tests::init_context};
+  use lemmy_db_schema::{
+    source::{person::Person, site::Site},
+    traits::Crud,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_signing_key_cache() {
+    let context = init_context().await;
+    let (person, site) = parse_lemmy_person(&context).await;
+    let actor_id: Url = person.actor_id.clone().into();
+
+    let actor = SigningActor::read_from_id(actor_id.clone(), &context)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(person.public_key, actor.public_key_pem());
+
+    // the key is served from memory, even after the actor is gone from the database
+    Person::delete(&mut context.pool(), person.id)
+      .await
+      .unwrap();
+    let cached = SigningActor::read_from_id(actor_id.clone(), &context)
+      .await
+      .unwrap();
+    assert!(cached.is_some());
+
+    SIGNING_KEYS.invalidate(&actor_id).await;
+    let cached = SigningActor::read_from_id(actor_id, &context)
+      .await
+      .unwrap();
+    assert!(cached.is_none());
+    Site::delete(&mut context.pool(), site.id).await.unwrap();
+  }
+}
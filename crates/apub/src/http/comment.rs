@@ -1,11 +1,21 @@
 use crate::{
-  http::{create_apub_response, create_apub_tombstone_response, err_object_not_local},
+  http::{
+    check_private_instance_fetch,
+    create_apub_tombstone_response,
+    create_cached_apub_response,
+    err_object_not_local,
+    ObjectVersion,
+  },
   objects::comment::ApubComment,
 };
 use activitypub_federation::{config::Data, traits::Object};
-use actix_web::{web::Path, HttpResponse};
+use actix_web::{web::Path, HttpRequest, HttpResponse};
 use lemmy_api_common::context::LemmyContext;
-use lemmy_db_schema::{newtypes::CommentId, source::comment::Comment, traits::Crud};
+use lemmy_db_schema::{
+  newtypes::CommentId,
+  source::{comment::Comment, person::Person},
+  traits::Crud,
+};
 use lemmy_utils::error::LemmyError;
 use serde::Deserialize;
 
@@ -19,16 +29,25 @@ pub(crate) struct CommentQuery {
 pub(crate) async fn get_apub_comment(
   info: Path<CommentQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let id = CommentId(info.comment_id.parse::<i32>()?);
   let comment: ApubComment = Comment::read(&mut context.pool(), id).await?.into();
   if !comment.local {
     return Err(err_object_not_local());
   }
 
-  if !comment.deleted && !comment.removed {
-    create_apub_response(&comment.into_json(&context).await?)
+  let creator = Person::read(&mut context.pool(), comment.creator_id).await?;
+  // Content of shadowbanned users isn't federated
+  if !comment.deleted && !comment.removed && !creator.is_shadowbanned_for(comment.published) {
+    let version = ObjectVersion::new(
+      serde_json::to_string(&comment.0)?,
+      comment.updated.unwrap_or(comment.published),
+    );
+    create_cached_apub_response(&request, version, comment.into_json(&context)).await
   } else {
-    create_apub_tombstone_response(comment.ap_id.clone())
+    let deleted = comment.updated.unwrap_or(comment.published);
+    create_apub_tombstone_response(comment.ap_id.clone(), "Note", deleted)
   }
 }
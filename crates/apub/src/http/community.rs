@@ -5,8 +5,17 @@ use crate::{
     community_moderators::ApubCommunityModerators,
     community_outbox::ApubCommunityOutbox,
   },
-  http::{create_apub_response, create_apub_tombstone_response},
-  objects::{community::ApubCommunity, person::ApubPerson},
+  fetcher::signing_actor::SigningActor,
+  http::{
+    check_private_instance_fetch,
+    create_apub_response,
+    create_apub_tombstone_response,
+    create_cached_apub_response,
+    receive_in_order,
+    CollectionPageQuery,
+    ObjectVersion,
+  },
+  objects::community::ApubCommunity,
   protocol::collections::group_followers::GroupFollowers,
 };
 use activitypub_federation::{
@@ -31,18 +40,24 @@ pub(crate) struct CommunityQuery {
 pub(crate) async fn get_apub_community_http(
   info: web::Path<CommunityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, true)
       .await?
       .into();
 
   if !community.deleted && !community.removed {
-    let apub = community.into_json(&context).await?;
-
-    create_apub_response(&apub)
+    // The public key isn't serialized, but is part of the json
+    let version = ObjectVersion::new(
+      (serde_json::to_string(&*community)?, &community.public_key),
+      community.updated.unwrap_or(community.published),
+    );
+    create_cached_apub_response(&request, version, community.into_json(&context)).await
   } else {
-    create_apub_tombstone_response(community.actor_id.clone())
+    let deleted = community.updated.unwrap_or(community.published);
+    create_apub_tombstone_response(community.actor_id.clone(), "Group", deleted)
   }
 }
 
@@ -53,29 +68,42 @@ pub async fn community_inbox(
   body: Bytes,
   data: Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
-  receive_activity::<WithContext<GroupInboxActivities>, ApubPerson, LemmyContext>(
-    request, body, &data,
-  )
+  receive_in_order(request, body, &data, |request, body| {
+    receive_activity::<WithContext<GroupInboxActivities>, SigningActor, LemmyContext>(
+      request, body, &data,
+    )
+  })
   .await
 }
 
-/// Returns an empty followers collection, only populating the size (for privacy).
+/// Returns the followers collection with the number of followers, or a page of follower actors.
 pub(crate) async fn get_apub_community_followers(
   info: web::Path<CommunityQuery>,
+  query: web::Query<CollectionPageQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let community =
     Community::read_from_name(&mut context.pool(), &info.community_name, false).await?;
-  let followers = GroupFollowers::new(community, &context).await?;
-  create_apub_response(&followers)
+  if query.page {
+    let page = GroupFollowers::page(community, query.max_id, &context).await?;
+    create_apub_response(&page)
+  } else {
+    let followers = GroupFollowers::new(community, &context).await?;
+    create_apub_response(&followers)
+  }
 }
 
-/// Returns the community outbox, which is populated by a maximum of 20 posts (but no other
-/// activites like votes or comments).
+/// Returns the community outbox, or one of its pages. It contains the creation of posts, but no
+/// other activites like votes or comments.
 pub(crate) async fn get_apub_community_outbox(
   info: web::Path<CommunityQuery>,
+  query: web::Query<CollectionPageQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, false)
       .await?
@@ -83,15 +111,22 @@ pub(crate) async fn get_apub_community_outbox(
   if community.deleted || community.removed {
     return Err(LemmyErrorType::Deleted)?;
   }
-  let outbox = ApubCommunityOutbox::read_local(&community, &context).await?;
-  create_apub_response(&outbox)
+  if query.page {
+    let page = ApubCommunityOutbox::read_local_page(&community, query.max_id, &context).await?;
+    create_apub_response(&page)
+  } else {
+    let outbox = ApubCommunityOutbox::read_local(&community, &context).await?;
+    create_apub_response(&outbox)
+  }
 }
 
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_community_moderators(
   info: web::Path<CommunityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, false)
       .await?
@@ -107,7 +142,9 @@ pub(crate) async fn get_apub_community_moderators(
 pub(crate) async fn get_apub_community_featured(
   info: web::Path<CommunityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, false)
       .await?
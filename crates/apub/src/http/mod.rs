@@ -1,22 +1,47 @@
 use crate::{
   activity_lists::SharedInboxActivities,
-  fetcher::user_or_community::UserOrCommunity,
+  check_apub_id_valid,
+  fetcher::signing_actor::SigningActor,
+  integrity_proof::verify_integrity_proofs,
+  local_site_data_cached,
   protocol::objects::tombstone::Tombstone,
   CONTEXT,
 };
 use activitypub_federation::{
-  actix_web::inbox::receive_activity,
+  actix_web::{inbox::receive_activity, signing_actor},
   config::Data,
-  protocol::context::WithContext,
+  error::Error as FederationError,
+  protocol::{context::WithContext, helpers::deserialize_skip_error},
+  traits::Actor,
   FEDERATION_CONTENT_TYPE,
 };
-use actix_web::{web, web::Bytes, HttpRequest, HttpResponse};
+use actix_web::{
+  http::header::{ETag, EntityTag, Header, HttpDate, IfNoneMatch, LastModified},
+  web,
+  web::Bytes,
+  HttpRequest,
+  HttpResponse,
+};
+use chrono::NaiveDateTime;
 use http::StatusCode;
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::source::activity::SentActivity;
-use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorType, LemmyResult},
+  settings::SETTINGS,
+};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::ops::Deref;
+use serde_json::Value;
+use std::{
+  collections::{hash_map::DefaultHasher, HashMap},
+  future::Future,
+  hash::{Hash, Hasher},
+  ops::Deref,
+  sync::{Arc, Mutex as StdMutex},
+  time::{Duration, UNIX_EPOCH},
+};
+use tokio::sync::{Mutex, Semaphore};
 use url::Url;
 
 mod comment;
@@ -31,8 +56,171 @@ pub async fn shared_inbox(
   body: Bytes,
   data: Data<LemmyContext>,
 ) -> LemmyResult<HttpResponse> {
-  receive_activity::<SharedInboxActivities, UserOrCommunity, LemmyContext>(request, body, &data)
+  receive_in_order(request, body, &data, |request, body| {
+    receive_activity::<SharedInboxActivities, SigningActor, LemmyContext>(request, body, &data)
+  })
+  .await
+}
+
+/// Limits the number of incoming activities which are processed at the same time
+static INBOX_WORKERS: Lazy<Semaphore> = Lazy::new(|| {
+  let permits = match SETTINGS.inbox_worker_count {
+    0 => Semaphore::MAX_PERMITS,
+    count => count,
+  };
+  Semaphore::new(permits)
+});
+
+/// One lock per activity origin, so that activities of the same origin are processed in order.
+/// Entries are removed by [OriginLock] once no activity holds or waits for them.
+static ORIGIN_LOCKS: Lazy<StdMutex<HashMap<Url, Arc<Mutex<()>>>>> = Lazy::new(Default::default);
+
+/// The lock of an activity origin, for as long as an activity holds or waits for it
+struct OriginLock {
+  origin: Url,
+  lock: Arc<Mutex<()>>,
+}
+
+impl OriginLock {
+  fn new(origin: Url) -> Self {
+    let lock = ORIGIN_LOCKS
+      .lock()
+      .expect("Failed to lock origin locks mutex")
+      .entry(origin.clone())
+      .or_default()
+      .clone();
+    OriginLock { origin, lock }
+  }
+}
+
+impl Drop for OriginLock {
+  fn drop(&mut self) {
+    let mut locks = ORIGIN_LOCKS
+      .lock()
+      .expect("Failed to lock origin locks mutex");
+    // Only the map and this activity reference the lock, so no other activity is waiting
+    if Arc::strong_count(&self.lock) == 2 {
+      locks.remove(&self.origin);
+    }
+  }
+}
+
+#[derive(Deserialize)]
+struct ActivityOrigin {
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  actor: Option<Url>,
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  audience: Option<Url>,
+}
+
+/// Activities are ordered by the community they belong to, or otherwise by their actor. This way
+/// eg a post is always created before it gets removed by a mod.
+fn activity_origin(body: &[u8]) -> Option<Url> {
+  let origin = serde_json::from_slice::<ActivityOrigin>(body).ok()?;
+  origin.audience.or(origin.actor)
+}
+
+/// Processes an incoming activity on the bounded inbox worker pool. Activities from the same
+/// origin wait for each other, all others are handled concurrently.
+///
+/// Integrity proofs are checked after the HTTP signature if the activity has any. If the signature
+/// is invalid, the actor may have rotated its key. In that case the activity is
+/// checked once more with the new key, or with the previous key during the grace period.
+pub(crate) async fn receive_in_order<R, F>(
+  request: HttpRequest,
+  body: Bytes,
+  data: &Data<LemmyContext>,
+  receive: R,
+) -> LemmyResult<HttpResponse>
+where
+  R: Fn(HttpRequest, Bytes) -> F,
+  F: Future<Output = LemmyResult<HttpResponse>>,
+{
+  let origin_lock = activity_origin(&body).map(OriginLock::new);
+  // Take the origin lock first, so that waiting activities don't block a worker
+  let _origin_guard = match &origin_lock {
+    Some(origin_lock) => Some(origin_lock.lock.lock().await),
+    None => None,
+  };
+  let _permit = INBOX_WORKERS.acquire().await?;
+  let receive = |request, body| verify_and_receive(request, body, data, &receive);
+
+  let res = match receive(request.clone(), body.clone()).await {
+    Err(e) if is_signature_invalid(&e) => {
+      let actor = serde_json::from_slice::<ActivityOrigin>(&body)
+        .ok()
+        .and_then(|o| o.actor);
+      match actor {
+        Some(actor) => retry_with_other_key(&actor, request, body, data, receive)
+          .await
+          .unwrap_or(Err(e)),
+        None => Err(e),
+      }
+    }
+    res => res,
+  };
+  accept_duplicate_activity(res)
+}
+
+/// Checks the HTTP signature before the integrity proofs, so that unsigned requests can't make
+/// the server fetch actors to check proofs.
+async fn verify_and_receive<R, F>(
+  request: HttpRequest,
+  body: Bytes,
+  data: &Data<LemmyContext>,
+  receive: &R,
+) -> LemmyResult<HttpResponse>
+where
+  R: Fn(HttpRequest, Bytes) -> F,
+  F: Future<Output = LemmyResult<HttpResponse>>,
+{
+  signing_actor::<SigningActor>(&request, Some(body.clone()), data).await?;
+  if let Ok(activity) = serde_json::from_slice::<Value>(&body) {
+    verify_integrity_proofs(&activity, data).await?;
+  }
+  receive(request, body).await
+}
+
+/// Checks the activity again, with the new key if the actor rotated its key in the meantime, or
+/// with its previous key if the rotation happened recently. Returns None if there is no other key.
+async fn retry_with_other_key<R, F>(
+  actor: &Url,
+  request: HttpRequest,
+  body: Bytes,
+  data: &Data<LemmyContext>,
+  receive: R,
+) -> Option<LemmyResult<HttpResponse>>
+where
+  R: Fn(HttpRequest, Bytes) -> F,
+  F: Future<Output = LemmyResult<HttpResponse>>,
+{
+  if SigningActor::refetch_key(actor, data)
     .await
+    .unwrap_or(false)
+  {
+    return Some(receive(request, body).await);
+  }
+  let previous_key = SigningActor::previous_key(actor, data).await.ok()??;
+  Some(SigningActor::with_previous_key(previous_key, receive(request, body)).await)
+}
+
+fn is_signature_invalid(err: &LemmyError) -> bool {
+  matches!(
+    err.inner.downcast_ref::<FederationError>(),
+    Some(FederationError::ActivitySignatureInvalid)
+  )
+}
+
+/// Activities which were already received are acknowledged as successful, so that the sender
+/// doesn't keep retrying them.
+fn accept_duplicate_activity(res: LemmyResult<HttpResponse>) -> LemmyResult<HttpResponse> {
+  match res {
+    Err(LemmyError {
+      error_type: LemmyErrorType::DuplicateActivity,
+      ..
+    }) => Ok(HttpResponse::Ok().finish()),
+    res => res,
+  }
 }
 
 /// Convert the data to json and turn it into an HTTP Response with the correct ActivityPub
@@ -52,8 +240,71 @@ where
   )
 }
 
-fn create_apub_tombstone_response<T: Into<Url>>(id: T) -> LemmyResult<HttpResponse> {
-  let tombstone = Tombstone::new(id.into());
+/// The version of a local object, for conditional requests by crawlers and remote instances.
+pub(crate) struct ObjectVersion {
+  etag: EntityTag,
+  last_modified: HttpDate,
+}
+
+impl ObjectVersion {
+  /// The etag is a hash of the database row instead of the timestamp, because some changes like
+  /// locking a post don't set the updated column.
+  fn new<T: Hash>(row: T, updated: NaiveDateTime) -> Self {
+    let mut hasher = DefaultHasher::new();
+    row.hash(&mut hasher);
+    let seconds = u64::try_from(updated.timestamp()).unwrap_or_default();
+    ObjectVersion {
+      etag: EntityTag::new_strong(format!("{:x}", hasher.finish())),
+      last_modified: HttpDate::from(UNIX_EPOCH + Duration::from_secs(seconds)),
+    }
+  }
+
+  /// Whether the client sent the etag of this version in If-None-Match
+  fn is_cached_by(&self, request: &HttpRequest) -> bool {
+    match IfNoneMatch::parse(request) {
+      Ok(IfNoneMatch::Any) => true,
+      Ok(IfNoneMatch::Items(etags)) => etags.iter().any(|e| e.weak_eq(&self.etag)),
+      Err(_) => false,
+    }
+  }
+}
+
+/// Like [create_apub_response], but responds with 304 Not Modified if the client already has this
+/// version of the object. The json is only generated when it is actually sent.
+async fn create_cached_apub_response<T, F>(
+  request: &HttpRequest,
+  version: ObjectVersion,
+  data: F,
+) -> LemmyResult<HttpResponse>
+where
+  T: Serialize,
+  F: Future<Output = LemmyResult<T>>,
+{
+  let cached = version.is_cached_by(request);
+  let mut response = if cached {
+    HttpResponse::NotModified()
+  } else {
+    HttpResponse::Ok()
+  };
+  response
+    .insert_header(ETag(version.etag))
+    .insert_header(LastModified(version.last_modified));
+  if cached {
+    return Ok(response.finish());
+  }
+
+  let json = serde_json::to_string_pretty(&WithContext::new(data.await?, CONTEXT.clone()))?;
+  Ok(response.content_type(FEDERATION_CONTENT_TYPE).body(json))
+}
+
+/// Responds with 410 Gone for a deleted or removed object, so that other instances can tell it apart
+/// from an object which never existed.
+fn create_apub_tombstone_response<T: Into<Url>>(
+  id: T,
+  former_type: &str,
+  deleted: NaiveDateTime,
+) -> LemmyResult<HttpResponse> {
+  let tombstone = Tombstone::for_deleted(id.into(), former_type, deleted);
   let json = serde_json::to_string_pretty(&WithContext::new(tombstone, CONTEXT.deref().clone()))?;
 
   Ok(
@@ -64,10 +315,38 @@ fn create_apub_tombstone_response<T: Into<Url>>(id: T) -> LemmyResult<HttpRespon
   )
 }
 
+/// Private instances only serve federated objects to the instances in their allowlist. The fetch
+/// has to be signed by an actor of an allowed instance, other instances get an error.
+pub(crate) async fn check_private_instance_fetch(
+  request: &HttpRequest,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let local_site_data = local_site_data_cached(&mut context.pool()).await?;
+  let private_instance = local_site_data
+    .local_site
+    .as_ref()
+    .map(|l| l.private_instance)
+    .unwrap_or(false);
+  if !private_instance {
+    return Ok(());
+  }
+
+  let actor = signing_actor::<SigningActor>(request, None, context).await?;
+  check_apub_id_valid(&actor.id(), &local_site_data)
+}
+
 fn err_object_not_local() -> LemmyError {
   LemmyErrorType::ObjectNotLocal.into()
 }
 
+/// Selects a page of a paginated collection. Without `page`, the collection itself is returned.
+#[derive(Deserialize)]
+pub(crate) struct CollectionPageQuery {
+  #[serde(default)]
+  page: bool,
+  max_id: Option<i32>,
+}
+
 #[derive(Deserialize)]
 pub struct ActivityQuery {
   type_: String,
@@ -78,8 +357,10 @@ pub struct ActivityQuery {
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_activity(
   info: web::Path<ActivityQuery>,
-  context: web::Data<LemmyContext>,
+  context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let settings = context.settings();
   let activity_id = Url::parse(&format!(
     "{}/activities/{}/{}",
@@ -97,3 +378,63 @@ pub(crate) async fn get_activity(
     create_apub_response(&activity.data)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+
+  #[test]
+  fn test_activity_origin() {
+    // Announces are ordered by the announcing community
+    let announce =
+      std::fs::read("assets/lemmy/activities/community/announce_create_page.json").unwrap();
+    assert_eq!(
+      Some(Url::parse("http://enterprise.lemmy.ml/c/main").unwrap()),
+      activity_origin(&announce)
+    );
+
+    // Other activities by their audience, falling back to the actor
+    let create =
+      std::fs::read("assets/lemmy/activities/create_or_update/create_note.json").unwrap();
+    assert_eq!(
+      Some(Url::parse("http://ds9.lemmy.ml/u/lemmy_alpha").unwrap()),
+      activity_origin(&create)
+    );
+    let follow = std::fs::read("assets/lemmy/activities/following/follow.json").unwrap();
+    assert_eq!(
+      Some(Url::parse("http://ds9.lemmy.ml/u/lemmy_alpha").unwrap()),
+      activity_origin(&follow)
+    );
+
+    assert_eq!(None, activity_origin(b"not json"));
+  }
+
+  #[test]
+  fn test_object_version() {
+    use actix_web::test::TestRequest;
+
+    let updated = chrono::NaiveDateTime::from_timestamp_opt(1_600_000_000, 0).unwrap();
+    let version = ObjectVersion::new("row", updated);
+    let etag = version.etag.to_string();
+
+    let request = TestRequest::default().to_http_request();
+    assert!(!version.is_cached_by(&request));
+    let request = TestRequest::default()
+      .insert_header(("If-None-Match", etag.as_str()))
+      .to_http_request();
+    assert!(version.is_cached_by(&request));
+    let request = TestRequest::default()
+      .insert_header(("If-None-Match", "\"other\", W/\"tags\""))
+      .to_http_request();
+    assert!(!version.is_cached_by(&request));
+
+    // A changed row gets a new etag, even with the same timestamp
+    assert_ne!(
+      version.etag,
+      ObjectVersion::new("changed row", updated).etag
+    );
+  }
+}
@@ -1,7 +1,14 @@
 use crate::{
   activity_lists::PersonInboxActivities,
-  fetcher::user_or_community::UserOrCommunity,
-  http::{create_apub_response, create_apub_tombstone_response},
+  fetcher::signing_actor::SigningActor,
+  http::{
+    check_private_instance_fetch,
+    create_apub_response,
+    create_apub_tombstone_response,
+    create_cached_apub_response,
+    receive_in_order,
+    ObjectVersion,
+  },
   objects::person::ApubPerson,
   protocol::collections::empty_outbox::EmptyOutbox,
 };
@@ -11,9 +18,12 @@ use activitypub_federation::{
   protocol::context::WithContext,
   traits::Object,
 };
-use actix_web::{web, web::Bytes, HttpRequest, HttpResponse};
+use actix_web::{http::header::LOCATION, web, web::Bytes, HttpRequest, HttpResponse};
 use lemmy_api_common::{context::LemmyContext, utils::generate_outbox_url};
-use lemmy_db_schema::{source::person::Person, traits::ApubActor};
+use lemmy_db_schema::{
+  source::{person::Person, person_alias::PersonAlias},
+  traits::ApubActor,
+};
 use lemmy_utils::error::LemmyError;
 use serde::Deserialize;
 
@@ -27,19 +37,42 @@ pub struct PersonQuery {
 pub(crate) async fn get_apub_person_http(
   info: web::Path<PersonQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let user_name = info.into_inner().user_name;
-  // TODO: this needs to be able to read deleted persons, so that it can send tombstones
-  let person: ApubPerson = Person::read_from_name(&mut context.pool(), &user_name, true)
-    .await?
-    .into();
+  let person: ApubPerson = match Person::read_from_name(&mut context.pool(), &user_name, true).await
+  {
+    Ok(person) => person.into(),
+    Err(e) => {
+      // The former actor id of a renamed user redirects to the current one
+      let Ok(person) = PersonAlias::read_person_from_name(&mut context.pool(), &user_name).await
+      else {
+        return Err(e.into());
+      };
+      return Ok(
+        HttpResponse::MovedPermanently()
+          .insert_header((LOCATION, person.actor_id.as_str()))
+          .finish(),
+      );
+    }
+  };
 
   if !person.deleted {
-    let apub = person.into_json(&context).await?;
-
-    create_apub_response(&apub)
+    // The public key isn't serialized, but is part of the json
+    let version = ObjectVersion::new(
+      (serde_json::to_string(&person.0)?, &person.public_key),
+      person.updated.unwrap_or(person.published),
+    );
+    create_cached_apub_response(&request, version, person.into_json(&context)).await
   } else {
-    create_apub_tombstone_response(person.actor_id.clone())
+    let former_type = if person.bot_account {
+      "Service"
+    } else {
+      "Person"
+    };
+    let deleted = person.updated.unwrap_or(person.published);
+    create_apub_tombstone_response(person.actor_id.clone(), former_type, deleted)
   }
 }
 
@@ -49,9 +82,11 @@ pub async fn person_inbox(
   body: Bytes,
   data: Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
-  receive_activity::<WithContext<PersonInboxActivities>, UserOrCommunity, LemmyContext>(
-    request, body, &data,
-  )
+  receive_in_order(request, body, &data, |request, body| {
+    receive_activity::<WithContext<PersonInboxActivities>, SigningActor, LemmyContext>(
+      request, body, &data,
+    )
+  })
   .await
 }
 
@@ -59,7 +94,9 @@ pub async fn person_inbox(
 pub(crate) async fn get_apub_person_outbox(
   info: web::Path<PersonQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let person = Person::read_from_name(&mut context.pool(), &info.user_name, false).await?;
   let outbox_id = generate_outbox_url(&person.actor_id)?.into();
   let outbox = EmptyOutbox::new(outbox_id)?;
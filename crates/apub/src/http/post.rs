@@ -1,11 +1,21 @@
 use crate::{
-  http::{create_apub_response, create_apub_tombstone_response, err_object_not_local},
+  http::{
+    check_private_instance_fetch,
+    create_apub_tombstone_response,
+    create_cached_apub_response,
+    err_object_not_local,
+    ObjectVersion,
+  },
   objects::post::ApubPost,
 };
 use activitypub_federation::{config::Data, traits::Object};
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use lemmy_api_common::context::LemmyContext;
-use lemmy_db_schema::{newtypes::PostId, source::post::Post, traits::Crud};
+use lemmy_db_schema::{
+  newtypes::PostId,
+  source::{person::Person, post::Post},
+  traits::Crud,
+};
 use lemmy_utils::error::LemmyError;
 use serde::Deserialize;
 
@@ -19,16 +29,25 @@ pub(crate) struct PostQuery {
 pub(crate) async fn get_apub_post(
   info: web::Path<PostQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let id = PostId(info.post_id.parse::<i32>()?);
   let post: ApubPost = Post::read(&mut context.pool(), id).await?.into();
   if !post.local {
     return Err(err_object_not_local());
   }
 
-  if !post.deleted && !post.removed {
-    create_apub_response(&post.into_json(&context).await?)
+  let creator = Person::read(&mut context.pool(), post.creator_id).await?;
+  // Content of shadowbanned users isn't federated
+  if !post.deleted && !post.removed && !creator.is_shadowbanned_for(post.published) {
+    let version = ObjectVersion::new(
+      serde_json::to_string(&post.0)?,
+      post.updated.unwrap_or(post.published),
+    );
+    create_cached_apub_response(&request, version, post.into_json(&context)).await
   } else {
-    create_apub_tombstone_response(post.ap_id.clone())
+    let deleted = post.updated.unwrap_or(post.published);
+    create_apub_tombstone_response(post.ap_id.clone(), "Page", deleted)
   }
 }
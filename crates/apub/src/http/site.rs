@@ -1,7 +1,8 @@
 use crate::{
   activity_lists::SiteInboxActivities,
-  http::create_apub_response,
-  objects::{instance::ApubSite, person::ApubPerson},
+  fetcher::signing_actor::SigningActor,
+  http::{check_private_instance_fetch, create_apub_response, receive_in_order},
+  objects::instance::ApubSite,
   protocol::collections::empty_outbox::EmptyOutbox,
 };
 use activitypub_federation::{
@@ -28,7 +29,9 @@ pub(crate) async fn get_apub_site_http(
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_site_outbox(
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> Result<HttpResponse, LemmyError> {
+  check_private_instance_fetch(&request, &context).await?;
   let outbox_id = format!(
     "{}/site_outbox",
     context.settings().get_protocol_and_hostname()
@@ -43,8 +46,10 @@ pub async fn get_apub_site_inbox(
   body: Bytes,
   data: Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
-  receive_activity::<WithContext<SiteInboxActivities>, ApubPerson, LemmyContext>(
-    request, body, &data,
-  )
+  receive_in_order(request, body, &data, |request, body| {
+    receive_activity::<WithContext<SiteInboxActivities>, SigningActor, LemmyContext>(
+      request, body, &data,
+    )
+  })
   .await
 }
@@ -0,0 +1,526 @@
+use crate::{
+  fetcher::{signing_actor::SigningActor, user_or_community::UserOrCommunity},
+  protocol::{
+    activities::community::announce::RawAnnouncableActivities,
+    integrity_proof::{DataIntegrityProof, Multikey},
+  },
+  CONTEXT,
+};
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId, traits::ActivityHandler};
+use chrono::{SecondsFormat, Utc};
+use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::{
+  newtypes::DbUrl,
+  source::actor_integrity_key::{ActorIntegrityKey, ActorIntegrityKeyForm},
+};
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use openssl::{
+  pkey::{Id, PKey},
+  sha::sha256,
+  sign::{Signer, Verifier},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::ops::Deref;
+use url::Url;
+
+const PROOF_TYPE: &str = "DataIntegrityProof";
+const CRYPTOSUITE: &str = "eddsa-jcs-2022";
+const PROOF_PURPOSE: &str = "assertionMethod";
+const MULTIKEY_TYPE: &str = "Multikey";
+/// Multicodec prefix of Ed25519 public keys
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// An outgoing activity together with the integrity proof of its actor
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct WithProof<T> {
+  #[serde(flatten)]
+  inner: T,
+  proof: Value,
+}
+
+impl<T: Serialize> WithProof<T> {
+  pub(crate) async fn new(
+    inner: T,
+    actor_id: &Url,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<Self> {
+    let key = local_integrity_key(actor_id, context).await?;
+    let proof = create_proof(&serde_json::to_value(&inner)?, &key)?;
+    Ok(WithProof { inner, proof })
+  }
+}
+
+#[async_trait::async_trait]
+impl<T> ActivityHandler for WithProof<T>
+where
+  T: ActivityHandler + Send + Sync,
+{
+  type DataType = T::DataType;
+  type Error = T::Error;
+
+  fn id(&self) -> &Url {
+    self.inner.id()
+  }
+
+  fn actor(&self) -> &Url {
+    self.inner.actor()
+  }
+
+  async fn verify(&self, data: &Data<Self::DataType>) -> Result<(), Self::Error> {
+    self.inner.verify(data).await
+  }
+
+  async fn receive(self, data: &Data<Self::DataType>) -> Result<(), Self::Error> {
+    self.inner.receive(data).await
+  }
+}
+
+/// Adds a proof by the actor to an activity which is sent inside of an Announce, so that the
+/// receivers can check that the community didn't change it.
+pub(crate) async fn sign_announcable(
+  mut activity: RawAnnouncableActivities,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<RawAnnouncableActivities> {
+  let key = local_integrity_key(&activity.actor, context).await?;
+  activity
+    .other
+    .insert("@context".to_string(), json!(CONTEXT.deref()));
+  let proof = create_proof(&serde_json::to_value(&activity)?, &key)?;
+  activity.other.insert("proof".to_string(), proof);
+  Ok(activity)
+}
+
+/// Returns the key of a local actor, generating it when it is first needed.
+async fn local_integrity_key(
+  actor_id: &Url,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<ActorIntegrityKey> {
+  if !is_local(actor_id, context)? {
+    return Err(LemmyErrorType::InvalidIntegrityProof)?;
+  }
+  let actor_id: DbUrl = actor_id.clone().into();
+  if let Some(key) = ActorIntegrityKey::read(&mut context.pool(), &actor_id).await? {
+    return Ok(key);
+  }
+  let keypair = PKey::generate_ed25519()?;
+  let form = ActorIntegrityKeyForm {
+    actor_id,
+    public_key: encode_multikey(&keypair.raw_public_key()?),
+    private_key: Some(String::from_utf8(keypair.private_key_to_pem_pkcs8()?)?),
+  };
+  Ok(ActorIntegrityKey::read_or_create(&mut context.pool(), &form).await?)
+}
+
+/// The keys which are published with an actor, so that others can verify its proofs. Remote actors
+/// are published with the key they sent, if any.
+pub(crate) async fn assertion_method(
+  actor_id: &DbUrl,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Vec<Multikey>> {
+  let key = if is_local(actor_id.inner(), context)? {
+    local_integrity_key(actor_id.inner(), context).await?
+  } else {
+    match ActorIntegrityKey::read(&mut context.pool(), actor_id).await? {
+      Some(key) if key.private_key.is_none() => key,
+      _ => return Ok(vec![]),
+    }
+  };
+  Ok(vec![Multikey {
+    id: key_id(actor_id.inner()),
+    kind: MULTIKEY_TYPE.to_string(),
+    controller: actor_id.clone().into(),
+    public_key_multibase: key.public_key,
+  }])
+}
+
+/// Stores the integrity key which a remote actor publishes, if it has one
+pub(crate) async fn store_assertion_method(
+  actor_id: &Url,
+  assertion_method: &[Multikey],
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let key = assertion_method.iter().find(|k| {
+    k.kind == MULTIKEY_TYPE
+      && &k.controller == actor_id
+      && decode_multikey(&k.public_key_multibase).is_ok()
+  });
+  let Some(key) = key else {
+    return Ok(());
+  };
+  // Never overwrite the keys of local actors
+  if is_local(actor_id, context)? {
+    return Ok(());
+  }
+  let actor_id: DbUrl = actor_id.clone().into();
+  let existing = ActorIntegrityKey::read(&mut context.pool(), &actor_id).await?;
+  if existing.map_or(true, |e| {
+    e.private_key.is_some() || e.public_key != key.public_key_multibase
+  }) {
+    let form = ActorIntegrityKeyForm {
+      actor_id,
+      public_key: key.public_key_multibase.clone(),
+      private_key: None,
+    };
+    ActorIntegrityKey::upsert(&mut context.pool(), &form).await?;
+  }
+  Ok(())
+}
+
+fn is_local(actor_id: &Url, context: &Data<LemmyContext>) -> LemmyResult<bool> {
+  Ok(actor_id.domain() == Some(&context.settings().get_hostname_without_port()?))
+}
+
+fn key_id(actor_id: &Url) -> Url {
+  let mut key_id = actor_id.clone();
+  key_id.set_fragment(Some("ed25519-key"));
+  key_id
+}
+
+/// Creates a proof with the eddsa-jcs-2022 cryptosuite. The proof options are hashed together with
+/// the object, so they can't be changed either.
+fn create_proof(object: &Value, key: &ActorIntegrityKey) -> LemmyResult<Value> {
+  let private_key = key
+    .private_key
+    .as_ref()
+    .ok_or(LemmyErrorType::InvalidIntegrityProof)?;
+  let mut proof = json!({
+    "type": PROOF_TYPE,
+    "cryptosuite": CRYPTOSUITE,
+    "verificationMethod": key_id(key.actor_id.inner()),
+    "proofPurpose": PROOF_PURPOSE,
+    "created": Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+  });
+  if let Some(context) = object.get("@context") {
+    proof["@context"] = context.clone();
+  }
+  let pkey = PKey::private_key_from_pem(private_key.as_bytes())?;
+  let signature = Signer::new_without_digest(&pkey)?.sign_oneshot_to_vec(&hash(object, &proof))?;
+  proof["proofValue"] = Value::String(format!("z{}", base58_encode(&signature)));
+  Ok(proof)
+}
+
+/// Checks the integrity proofs of an incoming activity, and of the activity inside of it if it is
+/// an Announce. Proofs are optional, but if there is one it has to be valid.
+pub(crate) async fn verify_integrity_proofs(
+  activity: &Value,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  verify_proof(activity, context).await?;
+  if activity.get("type").and_then(Value::as_str) == Some("Announce") {
+    if let Some(object) = activity.get("object").filter(|o| o.is_object()) {
+      verify_proof(object, context).await?;
+    }
+  }
+  Ok(())
+}
+
+async fn verify_proof(object: &Value, context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let Some(proof_json) = object.get("proof") else {
+    return Ok(());
+  };
+  let proof: DataIntegrityProof = serde_json::from_value(proof_json.clone())?;
+  // Other kinds of proofs can't be checked, so they are treated like a missing proof
+  if proof.kind != PROOF_TYPE || proof.cryptosuite != CRYPTOSUITE {
+    return Ok(());
+  }
+  let actor = object
+    .get("actor")
+    .and_then(Value::as_str)
+    .and_then(|a| Url::parse(a).ok())
+    .ok_or(LemmyErrorType::InvalidIntegrityProof)?;
+  let mut key_owner = proof.verification_method.clone();
+  key_owner.set_fragment(None);
+  if proof.proof_purpose != PROOF_PURPOSE || key_owner != actor {
+    return Err(LemmyErrorType::InvalidIntegrityProof)?;
+  }
+
+  let mut proof_options = proof_json.clone();
+  if let Some(p) = proof_options.as_object_mut() {
+    p.remove("proofValue");
+  }
+  // Activities inside of an Announce are often forwarded without their context. It is part of the
+  // signed data, so it is restored from the proof.
+  let mut object = object.clone();
+  if let (Some(o), Some(c)) = (object.as_object_mut(), proof_json.get("@context")) {
+    o.entry("@context").or_insert_with(|| c.clone());
+  }
+  let hash = hash(&object, &proof_options);
+  let signature = proof
+    .proof_value
+    .strip_prefix('z')
+    .and_then(base58_decode)
+    .ok_or(LemmyErrorType::InvalidIntegrityProof)?;
+
+  let key = remote_integrity_key(&actor, context).await?;
+  if is_signature_valid(&key, &hash, &signature)? {
+    return Ok(());
+  }
+  // The actor may have replaced its key since it was last fetched
+  SigningActor::refetch_key(&actor, context).await?;
+  let new_key = remote_integrity_key(&actor, context).await?;
+  if new_key != key && is_signature_valid(&new_key, &hash, &signature)? {
+    return Ok(());
+  }
+  Err(LemmyErrorType::InvalidIntegrityProof)?
+}
+
+/// Reads the public key of an actor, fetching the actor if necessary
+async fn remote_integrity_key(actor: &Url, context: &Data<LemmyContext>) -> LemmyResult<String> {
+  let actor_id: DbUrl = actor.clone().into();
+  if let Some(key) = ActorIntegrityKey::read(&mut context.pool(), &actor_id).await? {
+    return Ok(key.public_key);
+  }
+  let object_id: ObjectId<UserOrCommunity> = actor.clone().into();
+  if object_id.dereference_local(context).await.is_ok() {
+    // The actor may have added a key since it was last fetched
+    SigningActor::refetch_key(actor, context).await?;
+  } else {
+    object_id.dereference(context).await?;
+  }
+  let key = ActorIntegrityKey::read(&mut context.pool(), &actor_id).await?;
+  Ok(key.ok_or(LemmyErrorType::InvalidIntegrityProof)?.public_key)
+}
+
+fn is_signature_valid(public_key: &str, hash: &[u8], signature: &[u8]) -> LemmyResult<bool> {
+  let public_key = decode_multikey(public_key)?;
+  let pkey = PKey::public_key_from_raw_bytes(&public_key, Id::ED25519)?;
+  let valid = Verifier::new_without_digest(&pkey)?.verify_oneshot(signature, hash)?;
+  Ok(valid)
+}
+
+/// Hashes the proof options and the object without its proof, as the input for the signature
+fn hash(object: &Value, proof_options: &Value) -> Vec<u8> {
+  let mut object = object.clone();
+  if let Some(o) = object.as_object_mut() {
+    o.remove("proof");
+  }
+  let mut hash = sha256(canonicalize(proof_options).as_bytes()).to_vec();
+  hash.extend(sha256(canonicalize(&object).as_bytes()));
+  hash
+}
+
+/// Serializes json with sorted keys and without whitespace, as defined by the JSON
+/// canonicalization scheme (RFC 8785)
+fn canonicalize(value: &Value) -> String {
+  match value {
+    Value::Object(map) => {
+      let mut entries: Vec<_> = map.iter().collect();
+      entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+      let entries: Vec<_> = entries
+        .into_iter()
+        .map(|(k, v)| format!("{}:{}", Value::String(k.clone()), canonicalize(v)))
+        .collect();
+      format!("{{{}}}", entries.join(","))
+    }
+    Value::Array(values) => {
+      let values: Vec<_> = values.iter().map(canonicalize).collect();
+      format!("[{}]", values.join(","))
+    }
+    value => value.to_string(),
+  }
+}
+
+fn encode_multikey(public_key: &[u8]) -> String {
+  let mut bytes = ED25519_MULTICODEC.to_vec();
+  bytes.extend_from_slice(public_key);
+  format!("z{}", base58_encode(&bytes))
+}
+
+fn decode_multikey(multikey: &str) -> Result<Vec<u8>, LemmyError> {
+  multikey
+    .strip_prefix('z')
+    .and_then(base58_decode)
+    .and_then(|b| b.strip_prefix(&ED25519_MULTICODEC).map(<[u8]>::to_vec))
+    .filter(|k| k.len() == 32)
+    .ok_or(LemmyErrorType::InvalidIntegrityProof.into())
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+  let mut digits: Vec<u8> = Vec::new();
+  for byte in bytes {
+    let mut carry = u32::from(*byte);
+    for digit in digits.iter_mut() {
+      carry += u32::from(*digit) << 8;
+      *digit = (carry % 58) as u8;
+      carry /= 58;
+    }
+    while carry > 0 {
+      digits.push((carry % 58) as u8);
+      carry /= 58;
+    }
+  }
+  let leading_zeros = bytes.iter().take_while(|b| **b == 0).count();
+  std::iter::repeat('1')
+    .take(leading_zeros)
+    .chain(
+      digits
+        .iter()
+        .rev()
+        .map(|d| char::from(BASE58_ALPHABET[usize::from(*d)])),
+    )
+    .collect()
+}
+
+fn base58_decode(encoded: &str) -> Option<Vec<u8>> {
+  let mut bytes: Vec<u8> = Vec::new();
+  for c in encoded.bytes() {
+    let mut carry = BASE58_ALPHABET.iter().position(|a| *a == c)? as u32;
+    for byte in bytes.iter_mut() {
+      carry += u32::from(*byte) * 58;
+      *byte = (carry & 0xff) as u8;
+      carry >>= 8;
+    }
+    while carry > 0 {
+      bytes.push((carry & 0xff) as u8);
+      carry >>= 8;
+    }
+  }
+  let leading_zeros = encoded.bytes().take_while(|b| *b == b'1').count();
+  Some(
+    std::iter::repeat(0)
+      .take(leading_zeros)
+      .chain(bytes.into_iter().rev())
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+  use crate::objects::tests::init_context;
+  use lemmy_db_schema::utils::naive_now;
+  use serial_test::serial;
+
+  #[test]
+  fn test_base58() {
+    assert_eq!("", base58_encode(&[]));
+    assert_eq!("1112", base58_encode(&[0, 0, 0, 1]));
+    assert_eq!("StV1DL6CwTryKyV", base58_encode(b"hello world"));
+    assert_eq!(
+      b"hello world".to_vec(),
+      base58_decode("StV1DL6CwTryKyV").unwrap()
+    );
+    assert_eq!(vec![0, 0, 0, 1], base58_decode("1112").unwrap());
+    assert!(base58_decode("0OIl").is_none());
+  }
+
+  #[test]
+  fn test_canonicalize() {
+    let value = json!({"b": [1, "x\n"], "a": {"d": null, "c": true}, "€": 1, "\u{10000}": 2});
+    assert_eq!(
+      r#"{"a":{"c":true,"d":null},"b":[1,"x\n"],"€":1,"𐀀":2}"#,
+      canonicalize(&value)
+    );
+  }
+
+  #[test]
+  fn test_create_and_verify_proof() {
+    let keypair = PKey::generate_ed25519().unwrap();
+    let public_key = encode_multikey(&keypair.raw_public_key().unwrap());
+    assert!(public_key.starts_with("z6Mk"));
+    let key = ActorIntegrityKey {
+      id: 1,
+      actor_id: Url::parse("https://lemmy.example/u/alice").unwrap().into(),
+      public_key: public_key.clone(),
+      private_key: Some(String::from_utf8(keypair.private_key_to_pem_pkcs8().unwrap()).unwrap()),
+      published: naive_now(),
+      updated: None,
+    };
+    let mut activity = json!({
+      "@context": "https://www.w3.org/ns/activitystreams",
+      "actor": "https://lemmy.example/u/alice",
+      "type": "Like",
+      "object": "https://lemmy.example/post/1",
+    });
+    let proof = create_proof(&activity, &key).unwrap();
+    assert_eq!(
+      "https://lemmy.example/u/alice#ed25519-key",
+      proof["verificationMethod"]
+    );
+    activity["proof"] = proof.clone();
+
+    let mut options = proof.clone();
+    options.as_object_mut().unwrap().remove("proofValue");
+    let signature = base58_decode(
+      proof["proofValue"]
+        .as_str()
+        .unwrap()
+        .strip_prefix('z')
+        .unwrap(),
+    )
+    .unwrap();
+    let hash_value = hash(&activity, &options);
+    assert!(is_signature_valid(&public_key, &hash_value, &signature).unwrap());
+
+    // any change to the activity invalidates the proof
+    activity["object"] = json!("https://lemmy.example/post/2");
+    let hash_value = hash(&activity, &options);
+    assert!(!is_signature_valid(&public_key, &hash_value, &signature).unwrap());
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_remote_actor_keys() {
+    let context = init_context().await;
+    let remote: DbUrl = Url::parse("https://integrity-remote.example/u/bob")
+      .unwrap()
+      .into();
+    // a key with private part, as it was wrongly generated for remote actors before
+    let generated = ActorIntegrityKeyForm {
+      actor_id: remote.clone(),
+      public_key: "z6MkGenerated".to_string(),
+      private_key: Some("generated".to_string()),
+    };
+    ActorIntegrityKey::upsert(&mut context.pool(), &generated)
+      .await
+      .unwrap();
+    assert!(assertion_method(&remote, &context)
+      .await
+      .unwrap()
+      .is_empty());
+
+    let keypair = PKey::generate_ed25519().unwrap();
+    let published = Multikey {
+      id: key_id(remote.inner()),
+      kind: MULTIKEY_TYPE.to_string(),
+      controller: remote.clone().into(),
+      public_key_multibase: encode_multikey(&keypair.raw_public_key().unwrap()),
+    };
+    store_assertion_method(remote.inner(), &[published.clone()], &context)
+      .await
+      .unwrap();
+    let stored = ActorIntegrityKey::read(&mut context.pool(), &remote)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(published.public_key_multibase, stored.public_key);
+    assert!(stored.private_key.is_none());
+    assert_eq!(
+      vec![published.clone()],
+      assertion_method(&remote, &context).await.unwrap()
+    );
+
+    // local actors get their own key, which is never replaced by a published one
+    let local: DbUrl = Url::parse(&format!(
+      "http://{}/u/integrity_local",
+      context.settings().hostname
+    ))
+    .unwrap()
+    .into();
+    let local_key = assertion_method(&local, &context).await.unwrap();
+    assert_eq!(1, local_key.len());
+    let published = Multikey {
+      controller: local.clone().into(),
+      ..published
+    };
+    store_assertion_method(local.inner(), &[published], &context)
+      .await
+      .unwrap();
+    assert_eq!(local_key, assertion_method(&local, &context).await.unwrap());
+  }
+}
@@ -1,6 +1,7 @@
 use crate::fetcher::post_or_comment::PostOrComment;
 use activitypub_federation::config::{Data, UrlVerifier};
 use async_trait::async_trait;
+use diesel::result::{DatabaseErrorKind, Error::DatabaseError};
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::{
   source::{activity::ReceivedActivity, instance::Instance, local_site::LocalSite},
@@ -16,8 +17,10 @@ pub mod activities;
 pub(crate) mod activity_lists;
 pub mod api;
 pub(crate) mod collections;
+pub mod delivery_receipt;
 pub mod fetcher;
 pub mod http;
+pub(crate) mod integrity_proof;
 pub(crate) mod mentions;
 pub mod objects;
 pub mod protocol;
@@ -28,6 +31,9 @@ pub const FEDERATION_HTTP_FETCH_LIMIT: u32 = 50;
 /// cache these values for a short time, which will already make a huge difference and ensures that
 /// changes take effect quickly.
 const BLOCKLIST_CACHE_DURATION: Duration = Duration::from_secs(60);
+/// Number of recently received activity ids which are kept in memory for deduplication
+const RECEIVED_ACTIVITY_CACHE_SIZE: u64 = 100_000;
+const RECEIVED_ACTIVITY_CACHE_DURATION: Duration = Duration::from_secs(60 * 60);
 
 static CONTEXT: Lazy<Vec<serde_json::Value>> = Lazy::new(|| {
   serde_json::from_str(include_str!("../assets/lemmy/context.json")).expect("parse context")
@@ -66,7 +72,7 @@ impl UrlVerifier for VerifyUrlData {
 /// In particular, it checks for:
 /// - federation being enabled (if its disabled, only local URLs are allowed)
 /// - the correct scheme (either http or https)
-/// - URL being in the allowlist (if it is active)
+/// - URL being in the allowlist (if it is active, or if this is a private instance)
 /// - URL not being in the blocklist (if it is active)
 #[tracing::instrument(skip(local_site_data))]
 fn check_apub_id_valid(apub_id: &Url, local_site_data: &LocalSiteData) -> Result<(), LemmyError> {
@@ -89,8 +95,14 @@ fn check_apub_id_valid(apub_id: &Url, local_site_data: &LocalSiteData) -> Result
     Err(LemmyErrorType::DomainBlocked(domain.clone()))?;
   }
 
-  // Only check this if there are instances in the allowlist
-  if !local_site_data.allowed_instances.is_empty()
+  // Private instances only federate with explicitly trusted instances, otherwise only check this
+  // if there are instances in the allowlist
+  let private_instance = local_site_data
+    .local_site
+    .as_ref()
+    .map(|l| l.private_instance)
+    .unwrap_or(false);
+  if (private_instance || !local_site_data.allowed_instances.is_empty())
     && !local_site_data
       .allowed_instances
       .iter()
@@ -184,14 +196,36 @@ pub(crate) async fn check_apub_id_valid_with_strictness(
 /// Store received activities in the database.
 ///
 /// This ensures that the same activity doesnt get received and processed more than once, which
-/// would be a waste of resources.
+/// would be a waste of resources. The same activity often arrives multiple times in a short
+/// period, eg directly and wrapped in an announce, so recently seen ids are also kept in memory
+/// to skip the database roundtrip.
 #[tracing::instrument(skip(data))]
 async fn insert_received_activity(
   ap_id: &Url,
   data: &Data<LemmyContext>,
 ) -> Result<(), LemmyError> {
-  ReceivedActivity::create(&mut data.pool(), &ap_id.clone().into()).await?;
-  Ok(())
+  static RECENTLY_RECEIVED: Lazy<Cache<Url, ()>> = Lazy::new(|| {
+    Cache::builder()
+      .max_capacity(RECEIVED_ACTIVITY_CACHE_SIZE)
+      .time_to_live(RECEIVED_ACTIVITY_CACHE_DURATION)
+      .build()
+  });
+  if RECENTLY_RECEIVED.contains_key(ap_id) {
+    return Err(LemmyErrorType::DuplicateActivity)?;
+  }
+  // Only cache ids which are stored, otherwise a failed insert would make the activity get rejected
+  // as a duplicate when it is retried
+  match ReceivedActivity::create(&mut data.pool(), &ap_id.clone().into()).await {
+    Ok(_) => {
+      RECENTLY_RECEIVED.insert(ap_id.clone(), ()).await;
+      Ok(())
+    }
+    Err(DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+      RECENTLY_RECEIVED.insert(ap_id.clone(), ()).await;
+      Err(LemmyErrorType::DuplicateActivity)?
+    }
+    Err(e) => Err(e.into()),
+  }
 }
 
 #[async_trait::async_trait]
@@ -206,3 +240,30 @@ pub trait SendActivity: Sync {
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+  use crate::objects::tests::init_context;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_insert_received_activity_duplicate() {
+    let context = init_context().await;
+    let ap_id = Url::parse("http://example.com/activity/9157").unwrap();
+
+    insert_received_activity(&ap_id, &context).await.unwrap();
+    let res = insert_received_activity(&ap_id, &context).await;
+    assert!(matches!(
+      res,
+      Err(LemmyError {
+        error_type: LemmyErrorType::DuplicateActivity,
+        ..
+      })
+    ));
+  }
+}
@@ -7,15 +7,36 @@ use activitypub_federation::{
 };
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::{
-  source::{comment::Comment, person::Person, post::Post},
-  traits::Crud,
+  source::{comment::Comment, community::Community, person::Person, post::Post},
+  traits::{ApubActor, Crud},
   utils::DbPool,
 };
-use lemmy_utils::{error::LemmyError, utils::mention::scrape_text_for_mentions};
+use lemmy_utils::{
+  error::LemmyError,
+  utils::mention::{scrape_text_for_community_mentions, scrape_text_for_mentions, MentionData},
+};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::time::Duration;
 use url::Url;
 
+/// How many unknown communities may be fetched for the mentions in a single post or comment
+const MAX_COMMUNITY_MENTION_FETCHES: usize = 3;
+
+/// How long to wait before trying to fetch the same unknown community again
+const COMMUNITY_MENTION_FETCH_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+
+/// Mentioned communities which were fetched recently, so that repeatedly mentioning a community
+/// which doesn't exist doesn't flood its instance with requests
+static RECENTLY_FETCHED_COMMUNITY_MENTIONS: Lazy<Cache<String, ()>> = Lazy::new(|| {
+  Cache::builder()
+    .max_capacity(10_000)
+    .time_to_live(COMMUNITY_MENTION_FETCH_COOLDOWN)
+    .build()
+});
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum MentionOrValue {
@@ -60,32 +81,156 @@ pub async fn collect_non_local_mentions(
   };
   let mut tags = vec![parent_creator_tag];
 
-  // Get the person IDs for any mentions
-  let mentions = scrape_text_for_mentions(&comment.content)
+  for mention_tag in non_local_mention_tags(&comment.content, context).await {
+    addressed_ccs.push(mention_tag.href.clone());
+    tags.push(mention_tag);
+  }
+  tags.append(&mut community_mention_tags(&comment.content, context).await);
+
+  let tags = tags.into_iter().map(MentionOrValue::Mention).collect();
+  Ok(MentionsAndAddresses {
+    ccs: addressed_ccs,
+    tags,
+  })
+}
+
+/// Same as [collect_non_local_mentions], for the body of a post. There is no parent to address.
+#[tracing::instrument(skip(body, context))]
+pub async fn collect_non_local_post_mentions(
+  body: Option<&str>,
+  context: &Data<LemmyContext>,
+) -> MentionsAndAddresses {
+  let Some(body) = body else {
+    return MentionsAndAddresses {
+      ccs: vec![],
+      tags: vec![],
+    };
+  };
+  let mut tags = non_local_mention_tags(body, context).await;
+  let ccs = tags.iter().map(|t| t.href.clone()).collect();
+  tags.append(&mut community_mention_tags(body, context).await);
+  MentionsAndAddresses {
+    ccs,
+    tags: tags.into_iter().map(MentionOrValue::Mention).collect(),
+  }
+}
+
+/// Resolves the remote persons mentioned in the given text, and builds mention tags for them.
+/// Mentions which can't be resolved are skipped.
+async fn non_local_mention_tags(content: &str, context: &Data<LemmyContext>) -> Vec<Mention> {
+  let mentions = scrape_text_for_mentions(content)
     .into_iter()
     // Filter only the non-local ones
     .filter(|m| !m.is_local(&context.settings().hostname));
 
+  let mut tags = vec![];
   for mention in mentions {
     let identifier = format!("{}@{}", mention.name, mention.domain);
     let person = webfinger_resolve_actor::<LemmyContext, ApubPerson>(&identifier, context).await;
     if let Ok(person) = person {
-      addressed_ccs.push(person.actor_id.to_string().parse()?);
-
-      let mention_tag = Mention {
+      tags.push(Mention {
         href: person.id(),
         name: Some(mention.full_name()),
         kind: MentionType::Mention,
-      };
-      tags.push(mention_tag);
+      });
     }
   }
+  tags
+}
 
-  let tags = tags.into_iter().map(MentionOrValue::Mention).collect();
-  Ok(MentionsAndAddresses {
-    ccs: addressed_ccs,
-    tags,
-  })
+/// Builds mention tags for the communities mentioned in the given text, so that other instances
+/// can link them. Unknown remote communities are fetched, within the limits of
+/// [MAX_COMMUNITY_MENTION_FETCHES] and [COMMUNITY_MENTION_FETCH_COOLDOWN]. Communities are not
+/// addressed, as that would make the post appear in them.
+async fn community_mention_tags(content: &str, context: &Data<LemmyContext>) -> Vec<Mention> {
+  let mut tags = vec![];
+  let mut fetches = 0;
+  for mention in scrape_text_for_community_mentions(content) {
+    let community = if mention.is_local(&context.settings().hostname) {
+      Community::read_from_name(&mut context.pool(), &mention.name, false).await
+    } else {
+      Community::read_from_name_and_domain(&mut context.pool(), &mention.name, &mention.domain)
+        .await
+    };
+    let community = match community {
+      Ok(c) => Some(ApubCommunity::from(c)),
+      Err(_) if !mention.is_local(&context.settings().hostname) => {
+        let identifier = format!("{}@{}", mention.name, mention.domain);
+        if fetches >= MAX_COMMUNITY_MENTION_FETCHES
+          || RECENTLY_FETCHED_COMMUNITY_MENTIONS.contains_key(&identifier)
+        {
+          None
+        } else {
+          fetches += 1;
+          RECENTLY_FETCHED_COMMUNITY_MENTIONS
+            .insert(identifier.clone(), ())
+            .await;
+          webfinger_resolve_actor::<LemmyContext, ApubCommunity>(&identifier, context)
+            .await
+            .ok()
+        }
+      }
+      Err(_) => None,
+    };
+    if let Some(community) = community {
+      tags.push(Mention {
+        href: community.actor_id.clone().into(),
+        name: Some(format!("!{}@{}", mention.name, mention.domain)),
+        kind: MentionType::Mention,
+      });
+    }
+  }
+  tags
+}
+
+/// Returns the local communities mentioned by the given tags, as sent by other instances.
+pub(crate) async fn local_community_mentions_from_tags(
+  tags: &[MentionOrValue],
+  context: &Data<LemmyContext>,
+) -> Vec<MentionData> {
+  let mut mentions = vec![];
+  for tag in tags {
+    let MentionOrValue::Mention(tag) = tag else {
+      continue;
+    };
+    let community = ObjectId::<ApubCommunity>::from(tag.href.clone())
+      .dereference_local(context)
+      .await;
+    if let Ok(community) = community {
+      if community.local {
+        mentions.push(MentionData {
+          name: community.name.clone(),
+          domain: context.settings().hostname.clone(),
+        });
+      }
+    }
+  }
+  mentions
+}
+
+/// Returns the local persons mentioned by the given tags, as sent by other instances.
+pub(crate) async fn local_mentions_from_tags(
+  tags: &[MentionOrValue],
+  context: &Data<LemmyContext>,
+) -> Vec<MentionData> {
+  let mut mentions = vec![];
+  for tag in tags {
+    let MentionOrValue::Mention(tag) = tag else {
+      continue;
+    };
+    let person = ObjectId::<ApubPerson>::from(tag.href.clone())
+      .dereference_local(context)
+      .await;
+    if let Ok(person) = person {
+      if person.local {
+        mentions.push(MentionData {
+          name: person.name.clone(),
+          domain: context.settings().hostname.clone(),
+        });
+      }
+    }
+  }
+  mentions
 }
 
 /// Returns the apub ID of the person this comment is responding to. Meaning, in case this is a
@@ -105,3 +250,43 @@ async fn get_comment_parent_creator(
   };
   Ok(Person::read(pool, parent_creator_id).await?.into())
 }
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+  use crate::objects::{
+    community::tests::parse_lemmy_community,
+    instance::tests::parse_lemmy_instance,
+    tests::init_context,
+  };
+  use lemmy_db_schema::source::site::Site;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_mention_tags() {
+    let context = init_context().await;
+    let site = parse_lemmy_instance(&context).await;
+    let community = parse_lemmy_community(&context).await;
+
+    // A community which was looked up recently is not fetched again
+    RECENTLY_FETCHED_COMMUNITY_MENTIONS
+      .insert("missing@example.com".to_string(), ())
+      .await;
+    let text = "See !main@enterprise.lemmy.ml and !missing@example.com, not !missing@lemmy-alpha";
+    let tags = community_mention_tags(text, &context).await;
+
+    assert_eq!(1, tags.len());
+    assert_eq!(community.actor_id.inner(), &tags[0].href);
+    assert_eq!(Some("!main@enterprise.lemmy.ml".to_string()), tags[0].name);
+    assert_eq!(0, context.request_count());
+
+    Community::delete(&mut context.pool(), community.id)
+      .await
+      .unwrap();
+    Site::delete(&mut context.pool(), site.id).await.unwrap();
+  }
+}
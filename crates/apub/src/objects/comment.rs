@@ -18,7 +18,17 @@ use activitypub_federation::{
 use chrono::NaiveDateTime;
 use lemmy_api_common::{
   context::LemmyContext,
-  utils::{local_site_opt_to_slur_regex, sanitize_html},
+  language_detection::detect_language,
+  utils::{
+    apply_comment_word_filter_action,
+    check_community_language,
+    check_community_word_filters,
+    hold_comment_for_review,
+    is_below_community_posting_thresholds,
+    local_site_opt_to_slur_regex,
+    sanitize_html,
+    slur_filter_regex,
+  },
 };
 use lemmy_db_schema::{
   source::{
@@ -119,6 +129,7 @@ impl Object for ApubComment {
       distinguished: Some(self.distinguished),
       language,
       audience: Some(community.actor_id.into()),
+      replies: None,
     };
 
     Ok(note)
@@ -159,8 +170,23 @@ impl Object for ApubComment {
     let slur_regex = &local_site_opt_to_slur_regex(&local_site);
     let content = remove_slurs(&content, slur_regex);
     let content = sanitize_html(&content);
+    let community = Community::read(&mut context.pool(), post.community_id).await?;
     let language_id =
-      LanguageTag::to_language_id_single(note.language, &mut context.pool()).await?;
+      match LanguageTag::to_language_id_single(note.language, &mut context.pool()).await? {
+        Some(lid) => Some(lid),
+        None => detect_language(&[&content], &community, &mut context.pool()).await?,
+      };
+    // Only local communities are checked, remote ones are responsible for their own content
+    if community.local {
+      check_community_language(&community, language_id, &mut context.pool()).await?;
+    }
+    let slur_filter = slur_filter_regex(language_id, community.id, &mut context.pool()).await?;
+    let content = remove_slurs(&content, &slur_filter);
+
+    // only apply community word filters once, when the comment is first received
+    let is_new = note.id.dereference_local(context).await.is_err();
+    let word_filter_action =
+      check_community_word_filters(community.id, &[&content], &mut context.pool()).await?;
 
     let form = CommentInsertForm {
       creator_id: creator.id,
@@ -177,6 +203,17 @@ impl Object for ApubComment {
     };
     let parent_comment_path = parent_comment.map(|t| t.0.path);
     let comment = Comment::create(&mut context.pool(), &form, parent_comment_path.as_ref()).await?;
+    if is_new {
+      // remote comments in local communities by users below the posting requirements are held
+      // for review
+      let held = community.local
+        && is_below_community_posting_thresholds(&community, &creator, &mut context.pool()).await?;
+      if held {
+        hold_comment_for_review(&comment, &mut context.pool()).await?;
+      } else {
+        apply_comment_word_filter_action(&comment, word_filter_action, &mut context.pool()).await?;
+      }
+    }
     Ok(comment.into())
   }
 }
@@ -190,6 +227,7 @@ pub(crate) mod tests {
   use crate::{
     objects::{
       community::{tests::parse_lemmy_community, ApubCommunity},
+      html_to_markdown::html_to_markdown,
       instance::ApubSite,
       person::{tests::parse_lemmy_person, ApubPerson},
       post::ApubPost,
@@ -198,7 +236,6 @@ pub(crate) mod tests {
     protocol::tests::file_to_json_object,
   };
   use assert_json_diff::assert_json_include;
-  use html2md::parse_html;
   use lemmy_db_schema::source::site::Site;
   use serial_test::serial;
 
@@ -291,7 +328,17 @@ pub(crate) mod tests {
   #[tokio::test]
   #[serial]
   async fn test_html_to_markdown_sanitize() {
-    let parsed = parse_html("<script></script><b>hello</b>");
+    let parsed = html_to_markdown("<script></script><b>hello</b>");
     assert_eq!(parsed, "**hello**");
   }
+
+  #[test]
+  fn test_read_incoming_html() {
+    let content = read_from_string_or_source(
+      "<p>hello<br><span>world</span></p><script>alert(1)</script><ul><li>item</li></ul>",
+      &None,
+      &None,
+    );
+    assert_eq!(content, "hello  \nworld\n\n* item");
+  }
 }
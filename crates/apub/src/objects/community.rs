@@ -1,15 +1,23 @@
 use crate::{
   check_apub_id_valid,
+  collections::community_moderators::ApubCommunityModerators,
+  integrity_proof::{assertion_method, store_assertion_method},
   local_site_data_cached,
   objects::instance::fetch_instance_actor_for_object,
   protocol::{
-    objects::{group::Group, Endpoints, LanguageTag},
+    objects::{
+      group::{Group, GroupRule},
+      CategoryTag,
+      Endpoints,
+      LanguageTag,
+    },
     ImageObject,
     Source,
   },
 };
 use activitypub_federation::{
   config::Data,
+  fetch::collection_id::CollectionId,
   kinds::actor::GroupType,
   traits::{Actor, Object},
 };
@@ -21,13 +29,15 @@ use lemmy_api_common::{
 use lemmy_db_schema::{
   source::{
     actor_language::CommunityLanguage,
+    category::CommunityCategory,
     community::{Community, CommunityUpdateForm},
+    community_rule::CommunityRule,
   },
   traits::{ApubActor, Crud},
 };
 use lemmy_db_views_actor::structs::CommunityFollowerView;
 use lemmy_utils::{
-  error::LemmyError,
+  error::{LemmyError, LemmyResult},
   utils::{markdown::markdown_to_html, time::convert_datetime},
 };
 use std::ops::Deref;
@@ -84,6 +94,15 @@ impl Object for ApubCommunity {
     let community_id = self.id;
     let langs = CommunityLanguage::read(&mut data.pool(), community_id).await?;
     let language = LanguageTag::new_multiple(langs, &mut data.pool()).await?;
+    let tag = CategoryTag::new_multiple(community_id, &mut data.pool()).await?;
+    let rules = CommunityRule::for_community(&mut data.pool(), community_id)
+      .await?
+      .into_iter()
+      .map(|r| GroupRule {
+        name: r.title,
+        content: r.description,
+      })
+      .collect();
 
     let group = Group {
       kind: GroupType::Group,
@@ -103,10 +122,18 @@ impl Object for ApubCommunity {
         shared_inbox: s.into(),
       }),
       public_key: self.public_key(),
+      assertion_method: assertion_method(&self.actor_id, data).await?,
       language,
+      tag,
+      rules,
       published: Some(convert_datetime(self.published)),
       updated: self.updated.map(convert_datetime),
       posting_restricted_to_mods: Some(self.posting_restricted_to_mods),
+      enable_downvotes: Some(self.enable_downvotes),
+      manually_approves_followers: Some(self.requires_follow_approval),
+      post_type_restriction: Some(self.post_type_restriction),
+      nsfw_only: Some(self.nsfw_only),
+      hidden: Some(self.hidden),
       attributed_to: Some(generate_moderators_url(&self.actor_id)?.into()),
     };
     Ok(group)
@@ -131,10 +158,19 @@ impl Object for ApubCommunity {
 
     let form = Group::into_insert_form(group.clone(), instance_id);
     let languages =
-      LanguageTag::to_language_id_multiple(group.language, &mut context.pool()).await?;
+      LanguageTag::to_language_id_multiple(group.language.clone(), &mut context.pool()).await?;
+    let categories = CategoryTag::to_category_ids(&group.tag, &mut context.pool()).await?;
 
     let community = Community::create(&mut context.pool(), &form).await?;
+    store_assertion_method(community.actor_id.inner(), &group.assertion_method, context).await?;
     CommunityLanguage::update(&mut context.pool(), languages, community.id).await?;
+    CommunityCategory::update(&mut context.pool(), categories, community.id).await?;
+    CommunityRule::replace(
+      &mut context.pool(),
+      community.id,
+      group.rule_forms(community.id),
+    )
+    .await?;
 
     let community: ApubCommunity = community.into();
 
@@ -179,6 +215,17 @@ impl Actor for ApubCommunity {
 }
 
 impl ApubCommunity {
+  /// Fetches the moderators collection of a remote community and updates the local moderator list
+  /// to match it.
+  pub async fn fetch_moderators(&self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    if let Some(moderators_url) = &self.moderators_url {
+      CollectionId::<ApubCommunityModerators>::from(moderators_url.clone())
+        .dereference(self, context)
+        .await?;
+    }
+    Ok(())
+  }
+
   /// For a given community, returns the inboxes of all followers.
   #[tracing::instrument(skip_all)]
   pub(crate) async fn get_follower_inboxes(
@@ -212,7 +259,6 @@ pub(crate) mod tests {
     objects::{instance::tests::parse_lemmy_instance, tests::init_context},
     protocol::tests::file_to_json_object,
   };
-  use activitypub_federation::fetch::collection_id::CollectionId;
   use lemmy_db_schema::{source::site::Site, traits::Crud};
   use serial_test::serial;
 
@@ -243,6 +289,14 @@ pub(crate) mod tests {
     assert_eq!(community.title, "Ten Forward");
     assert!(!community.local);
     assert_eq!(community.description.as_ref().unwrap().len(), 132);
+    assert!(community.enable_downvotes);
+
+    // Disabling downvotes is federated with the group
+    let mut json = community.clone().into_json(&context).await.unwrap();
+    json.enable_downvotes = Some(false);
+    json.attributed_to = None;
+    let community = ApubCommunity::from_json(json, &context).await.unwrap();
+    assert!(!community.enable_downvotes);
 
     Community::delete(&mut context.pool(), community.id)
       .await
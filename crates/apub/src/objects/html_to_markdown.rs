@@ -0,0 +1,484 @@
+//! Conversion of incoming html, mainly from microblogging software like Mastodon and Pleroma, to
+//! the markdown which is stored for posts, comments and profiles.
+//!
+//! Mentions are written with the full handle (`@name@domain`), so that they still refer to the
+//! right user when read on another instance. Paragraphs and line breaks are kept, and characters
+//! in the text which would otherwise be interpreted as markdown are escaped.
+
+use html5ever::{parse_document, tendril::TendrilSink, ParseOpts};
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use url::Url;
+
+static LINE_START_REGEX: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"^(#{1,6}(\s|$)|>|[-+](\s|$)|=+\s*$|-+\s*$)").expect("compile line start regex")
+});
+static ORDERED_LIST_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"^(\d+)([.)])(\s|$)").expect("compile ordered list regex"));
+static BLANK_LINES_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"\n{3,}").expect("compile blank lines regex"));
+
+pub(crate) fn html_to_markdown(html: &str) -> String {
+  let dom = parse_document(RcDom::default(), ParseOpts::default()).one(html);
+  let markdown = blocks(&dom.document).join("\n\n");
+  BLANK_LINES_REGEX
+    .replace_all(&markdown, "\n\n")
+    .trim()
+    .to_string()
+}
+
+/// Converts the children of a block element to a list of markdown blocks. Consecutive inline
+/// children are collected into a paragraph.
+fn blocks(node: &Handle) -> Vec<String> {
+  let mut blocks = vec![];
+  let mut paragraph = String::new();
+  for child in node.children.borrow().iter() {
+    match element_name(child) {
+      Some(name) if is_block(&name) => {
+        push_paragraph(&mut blocks, &mut paragraph);
+        blocks.extend(block(child, &name));
+      }
+      _ => paragraph.push_str(&inline(child)),
+    }
+  }
+  push_paragraph(&mut blocks, &mut paragraph);
+  blocks
+}
+
+fn block(node: &Handle, name: &str) -> Vec<String> {
+  match name {
+    "head" | "script" | "style" => vec![],
+    "p" => {
+      let mut paragraph = inline_children(node);
+      let mut blocks = vec![];
+      push_paragraph(&mut blocks, &mut paragraph);
+      blocks
+    }
+    "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+      let level = name.trim_start_matches('h').parse().unwrap_or(1);
+      let text = collapse_lines(&inline_children(node));
+      vec![format!("{} {}", "#".repeat(level), text)]
+    }
+    "pre" => vec![code_block(node)],
+    "blockquote" => {
+      let quoted = blocks(node).join("\n\n");
+      if quoted.is_empty() {
+        return vec![];
+      }
+      let quoted = quoted
+        .lines()
+        .map(|l| {
+          if l.is_empty() {
+            ">".to_string()
+          } else {
+            format!("> {l}")
+          }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+      vec![quoted]
+    }
+    "ul" | "ol" => vec![list(node, name == "ol")],
+    "hr" => vec!["---".to_string()],
+    _ => blocks(node),
+  }
+}
+
+fn list(node: &Handle, ordered: bool) -> String {
+  let mut number: usize = attr(node, "start")
+    .and_then(|s| s.parse().ok())
+    .unwrap_or(1);
+  let mut items = vec![];
+  for child in node.children.borrow().iter() {
+    if element_name(child).as_deref() != Some("li") {
+      continue;
+    }
+    let marker = if ordered {
+      format!("{number}. ")
+    } else {
+      "* ".to_string()
+    };
+    number += 1;
+    let indent = " ".repeat(marker.len());
+    let content = blocks(child).join("\n\n");
+    let item = content
+      .lines()
+      .enumerate()
+      .map(|(i, l)| match (i, l.is_empty()) {
+        (0, _) => format!("{marker}{l}"),
+        (_, true) => String::new(),
+        _ => format!("{indent}{l}"),
+      })
+      .collect::<Vec<_>>()
+      .join("\n");
+    items.push(if item.is_empty() {
+      marker.trim_end().to_string()
+    } else {
+      item
+    });
+  }
+  items.join("\n")
+}
+
+fn code_block(node: &Handle) -> String {
+  let code = text_content(node);
+  let code = code.strip_suffix('\n').unwrap_or(&code);
+  let language = node
+    .children
+    .borrow()
+    .iter()
+    .find(|c| element_name(c).as_deref() == Some("code"))
+    .and_then(|c| attr(c, "class"))
+    .and_then(|class| {
+      class
+        .split_whitespace()
+        .find_map(|c| c.strip_prefix("language-").map(ToString::to_string))
+    })
+    .unwrap_or_default();
+  let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+  format!("{fence}{language}\n{code}\n{fence}")
+}
+
+fn inline_children(node: &Handle) -> String {
+  node.children.borrow().iter().map(inline).collect()
+}
+
+fn inline(node: &Handle) -> String {
+  match &node.data {
+    NodeData::Text { contents } => escape(&collapse_whitespace(&contents.borrow())),
+    NodeData::Element { .. } => {
+      let name = element_name(node).unwrap_or_default();
+      match name.as_str() {
+        "script" | "style" => String::new(),
+        "br" => "  \n".to_string(),
+        "a" => link(node),
+        "img" => {
+          let src = attr(node, "src").unwrap_or_default();
+          let alt = escape(&attr(node, "alt").unwrap_or_default());
+          if src.is_empty() {
+            alt
+          } else {
+            format!("![{alt}]({src})")
+          }
+        }
+        "em" | "i" => wrap(&inline_children(node), "*"),
+        "strong" | "b" => wrap(&inline_children(node), "**"),
+        "del" | "s" | "strike" => wrap(&inline_children(node), "~~"),
+        "code" => code_span(&text_content(node)),
+        _ if is_block(&name) => format!(" {} ", blocks(node).join(" ")),
+        _ => inline_children(node),
+      }
+    }
+    _ => String::new(),
+  }
+}
+
+/// Links to users, which Mastodon and Pleroma render as `@name`, are written with the domain of
+/// the user.
+fn link(node: &Handle) -> String {
+  let text = inline_children(node).trim().to_string();
+  let Some(href) = attr(node, "href") else {
+    return text;
+  };
+  let class = attr(node, "class").unwrap_or_default();
+  let is_mention = class.split_whitespace().any(|c| c == "mention")
+    && !class.split_whitespace().any(|c| c == "hashtag");
+  if is_mention && text.starts_with('@') && !text.trim_start_matches('@').contains('@') {
+    if let Some(domain) = Url::parse(&href).ok().as_ref().and_then(Url::host_str) {
+      return format!("[{text}@{domain}]({href})");
+    }
+  }
+  if text.is_empty() || text_content(node).trim() == href {
+    return href;
+  }
+  format!("[{text}]({href})")
+}
+
+/// Wraps inline text in emphasis markers, keeping surrounding whitespace outside of them.
+fn wrap(text: &str, marker: &str) -> String {
+  let trimmed = text.trim();
+  if trimmed.is_empty() {
+    return text.to_string();
+  }
+  let leading = text.strip_suffix(text.trim_start()).unwrap_or_default();
+  let trailing = text.strip_prefix(text.trim_end()).unwrap_or_default();
+  format!("{leading}{marker}{trimmed}{marker}{trailing}")
+}
+
+fn code_span(code: &str) -> String {
+  let fence = "`".repeat(longest_run(code, '`') + 1);
+  if code.starts_with('`') || code.ends_with('`') {
+    format!("{fence} {code} {fence}")
+  } else {
+    format!("{fence}{code}{fence}")
+  }
+}
+
+/// Finishes a paragraph: leading whitespace is removed from each line, markdown syntax at the start
+/// of lines is escaped, and lines which consist only of whitespace separate paragraphs.
+fn push_paragraph(blocks: &mut Vec<String>, paragraph: &mut String) {
+  let lines: Vec<String> = paragraph
+    .split('\n')
+    .map(|l| escape_line_start(l.trim_start()))
+    .collect();
+  let mut current: Vec<&str> = vec![];
+  for line in &lines {
+    if line.trim().is_empty() {
+      push_lines(blocks, &mut current);
+    } else {
+      current.push(line);
+    }
+  }
+  push_lines(blocks, &mut current);
+  paragraph.clear();
+}
+
+fn push_lines(blocks: &mut Vec<String>, lines: &mut Vec<&str>) {
+  if let Some(last) = lines.pop() {
+    lines.push(last.trim_end());
+    blocks.push(lines.join("\n"));
+  }
+  lines.clear();
+}
+
+fn collapse_lines(text: &str) -> String {
+  text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+  let mut collapsed = String::with_capacity(text.len());
+  let mut previous_whitespace = false;
+  for c in text.chars() {
+    if c.is_ascii_whitespace() {
+      if !previous_whitespace {
+        collapsed.push(' ');
+      }
+      previous_whitespace = true;
+    } else {
+      collapsed.push(c);
+      previous_whitespace = false;
+    }
+  }
+  collapsed
+}
+
+/// Escapes characters which would otherwise start markdown syntax inside of a line. Underscores
+/// within words are left alone, as they can't start emphasis.
+fn escape(text: &str) -> String {
+  let chars: Vec<char> = text.chars().collect();
+  let mut escaped = String::with_capacity(text.len());
+  for (i, c) in chars.iter().enumerate() {
+    let previous = i.checked_sub(1).and_then(|p| chars.get(p));
+    let next = chars.get(i + 1);
+    let needs_escape = match c {
+      '\\' | '*' | '`' | '[' | ']' | '<' => true,
+      // superscript and strikethrough need to enclose text
+      '^' => [previous, next]
+        .iter()
+        .all(|c| c.is_some_and(|c| !c.is_whitespace() && c != &'^')),
+      '~' => previous == Some(&'~') || next == Some(&'~'),
+      '_' => {
+        !previous.is_some_and(|p| p.is_alphanumeric()) || !next.is_some_and(|n| n.is_alphanumeric())
+      }
+      _ => false,
+    };
+    if needs_escape {
+      escaped.push('\\');
+    }
+    escaped.push(*c);
+  }
+  escaped
+}
+
+/// Escapes markdown syntax which is only valid at the start of a line, like headings and lists.
+fn escape_line_start(line: &str) -> String {
+  if LINE_START_REGEX.is_match(line) {
+    format!("\\{line}")
+  } else {
+    ORDERED_LIST_REGEX.replace(line, "$1\\$2$3").to_string()
+  }
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+  text
+    .split(|x| x != c)
+    .map(str::len)
+    .max()
+    .unwrap_or_default()
+}
+
+fn text_content(node: &Handle) -> String {
+  match &node.data {
+    NodeData::Text { contents } => contents.borrow().to_string(),
+    _ => node.children.borrow().iter().map(text_content).collect(),
+  }
+}
+
+fn element_name(node: &Handle) -> Option<String> {
+  match &node.data {
+    NodeData::Element { name, .. } => Some(name.local.to_string()),
+    _ => None,
+  }
+}
+
+fn attr(node: &Handle, attr_name: &str) -> Option<String> {
+  match &node.data {
+    NodeData::Element { attrs, .. } => attrs
+      .borrow()
+      .iter()
+      .find(|a| &*a.name.local == attr_name)
+      .map(|a| a.value.to_string()),
+    _ => None,
+  }
+}
+
+fn is_block(name: &str) -> bool {
+  matches!(
+    name,
+    "html"
+      | "head"
+      | "body"
+      | "p"
+      | "div"
+      | "section"
+      | "article"
+      | "header"
+      | "footer"
+      | "main"
+      | "aside"
+      | "nav"
+      | "figure"
+      | "figcaption"
+      | "h1"
+      | "h2"
+      | "h3"
+      | "h4"
+      | "h5"
+      | "h6"
+      | "pre"
+      | "blockquote"
+      | "ul"
+      | "ol"
+      | "li"
+      | "hr"
+      | "table"
+      | "thead"
+      | "tbody"
+      | "tr"
+      | "td"
+      | "th"
+      | "dl"
+      | "dt"
+      | "dd"
+      | "details"
+      | "summary"
+      | "script"
+      | "style"
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use super::*;
+  use crate::protocol::{
+    objects::{note::Note, person::Person},
+    tests::file_to_json_object,
+  };
+
+  fn note_content(path: &str) -> String {
+    html_to_markdown(&file_to_json_object::<Note>(path).unwrap().content)
+  }
+
+  #[test]
+  fn test_notes() {
+    let corpus = [
+      (
+        "assets/mastodon/objects/note.json",
+        "[@retiolus@mamot.fr](https://mamot.fr/@retiolus) i have never been disappointed by a thinkpad. if you want to save money, get a model from a few years ago, there isnt a huge difference anyway.",
+      ),
+      (
+        "assets/mastodon/objects/note_2.json",
+        "[@rust@lemmy.ml](https://lemmy.ml/c/rust) [@some_user@fosstodon.org](https://fosstodon.org/@some_user) The release notes are at https://blog.rust-lang.org/2023/09/19/Rust-1.72.1.html\n\n\
+        Highlights:  \n\\- faster builds  \n\\- 2 \\* 3 = 6 in const fns\n\n\
+        1\\. not a list  \n#not_a_heading\n\n\
+        [#rust_lang](https://mastodon.social/tags/rust_lang) [#programming](https://mastodon.social/tags/programming)",
+      ),
+      (
+        "assets/pleroma/objects/note.json",
+        "[@popolon@pleroma.popolon.org](https://pleroma.popolon.org/users/popolon) Have what?",
+      ),
+      (
+        "assets/pleroma/objects/note_2.json",
+        "[@popolon@pleroma.popolon.org](https://pleroma.popolon.org/users/popolon) it works with **both** versions:\n\n\
+        > does it build on musl?\n\n\
+        * glibc\n* musl, with `CFLAGS=-O2`\n\n\
+        ```sh\n./configure --prefix=/usr\nmake check\n```\n\n\
+        See [the repo](https://hacktivis.me/git/badwolf/)\n\n\
+        Federation-bots: [#nobot](https://queer.hacktivis.me/tag/nobot)",
+      ),
+      (
+        "assets/friendica/objects/note_1.json",
+        "[@jakob@lemmy.schuerz.at](https://lemmy.schuerz.at/u/jakob) test",
+      ),
+      ("assets/gnusocial/objects/note.json", "yay ^^"),
+    ];
+    for (path, expected) in corpus {
+      assert_eq!(note_content(path), expected, "Converting {path}");
+    }
+  }
+
+  #[test]
+  fn test_profile() {
+    let person = file_to_json_object::<Person>("assets/pleroma/objects/person.json").unwrap();
+    let bio = html_to_markdown(&person.summary.unwrap());
+    let lines: Vec<_> = bio.lines().collect();
+
+    assert_eq!(lines[0], "\\---  ");
+    assert_eq!(lines[1], "Website: https://hacktivis.me/  ");
+    assert_eq!(lines[4], "");
+    assert_eq!(
+      lines[6],
+      "Timezone: Let's say Mars, I have a non-24h cycle  "
+    );
+    assert_eq!(
+      lines[17],
+      "Just because computer bad: X5O!P%@AP\\[4\\\\PZX54(P\\^)7CC)7}$EICAR-STANDARD-ANTIVIRUS-TEST-FILE!$H+H\\*"
+    );
+    assert_eq!(
+      lines.last(),
+      Some(&"Federation-bots: [#nobot](https://queer.hacktivis.me/tag/nobot)")
+    );
+  }
+
+  #[test]
+  fn test_html_to_markdown() {
+    let tests = [
+      (
+        "<p>one</p>\n<p>two<br>three<br><br>four</p>",
+        "one\n\ntwo  \nthree\n\nfour",
+      ),
+      (
+        "<p>_emphasis_ and snake_case</p>",
+        "\\_emphasis\\_ and snake_case",
+      ),
+      ("<p><em> spaced </em>text</p>", "*spaced* text"),
+      (
+        "<ol start=\"3\"><li>three</li><li><p>four</p><p>more</p></li></ol>",
+        "3. three\n4. four\n\n   more",
+      ),
+      ("<p>with <code>`tick`</code></p>", "with `` `tick` ``"),
+      (
+        "<p><a href=\"https://example.com\">https://example.com</a></p>",
+        "https://example.com",
+      ),
+      ("<h2>Title <em>here</em></h2>", "## Title *here*"),
+    ];
+    for (html, expected) in tests {
+      assert_eq!(html_to_markdown(html), expected, "Converting {html}");
+    }
+  }
+}
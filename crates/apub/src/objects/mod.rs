@@ -1,12 +1,16 @@
-use crate::protocol::Source;
+use crate::{objects::html_to_markdown::html_to_markdown, protocol::Source};
 use activitypub_federation::protocol::values::MediaTypeMarkdownOrHtml;
 use anyhow::anyhow;
-use html2md::parse_html;
-use lemmy_utils::{error::LemmyError, settings::structs::Settings};
+use lemmy_api_common::utils::sanitize_incoming_html;
+use lemmy_utils::{
+  error::LemmyError,
+  settings::{structs::Settings, SETTINGS},
+};
 use url::Url;
 
 pub mod comment;
 pub mod community;
+pub(crate) mod html_to_markdown;
 pub mod instance;
 pub mod person;
 pub mod post;
@@ -25,7 +29,7 @@ pub(crate) fn read_from_string_or_source(
     content.to_string()
   } else {
     // otherwise, convert content html to markdown
-    parse_html(content)
+    html_to_markdown(&sanitize_incoming_html(content, &SETTINGS.incoming_html))
   }
 }
 
@@ -98,7 +102,9 @@ pub(crate) mod tests {
     let client = ClientBuilder::new(client).with(BlockedMiddleware).build();
     let secret = Secret {
       id: 0,
-      jwt_secret: String::new(),
+      hcaptcha_secret: None,
+      mcaptcha_secret: None,
+      turnstile_secret: None,
     };
 
     let rate_limit_config = RateLimitConfig::builder().build();
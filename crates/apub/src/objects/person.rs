@@ -1,5 +1,6 @@
 use crate::{
   check_apub_id_valid_with_strictness,
+  integrity_proof::{assertion_method, store_assertion_method},
   local_site_data_cached,
   objects::{instance::fetch_instance_actor_for_object, read_from_string_or_source_opt},
   protocol::{
@@ -22,12 +23,15 @@ use lemmy_api_common::{
   utils::{generate_outbox_url, local_site_opt_to_slur_regex, sanitize_html, sanitize_html_opt},
 };
 use lemmy_db_schema::{
-  source::person::{Person as DbPerson, PersonInsertForm, PersonUpdateForm},
+  source::{
+    person::{Person as DbPerson, PersonInsertForm, PersonUpdateForm},
+    person_alias::PersonAlias,
+  },
   traits::{ApubActor, Crud},
   utils::naive_now,
 };
 use lemmy_utils::{
-  error::LemmyError,
+  error::{LemmyError, LemmyResult},
   utils::{
     markdown::markdown_to_html,
     slurs::{check_slurs, check_slurs_opt},
@@ -83,7 +87,7 @@ impl Object for ApubPerson {
   }
 
   #[tracing::instrument(skip_all)]
-  async fn into_json(self, _context: &Data<Self::DataType>) -> Result<Person, LemmyError> {
+  async fn into_json(self, context: &Data<Self::DataType>) -> Result<Person, LemmyError> {
     let kind = if self.bot_account {
       UserTypes::Service
     } else {
@@ -105,7 +109,13 @@ impl Object for ApubPerson {
       endpoints: self.shared_inbox_url.clone().map(|s| Endpoints {
         shared_inbox: s.into(),
       }),
+      also_known_as: PersonAlias::list_for_person(&mut context.pool(), self.id)
+        .await?
+        .into_iter()
+        .map(|a| a.actor_id.into())
+        .collect(),
       public_key: self.public_key(),
+      assertion_method: assertion_method(&self.actor_id, context).await?,
       updated: self.updated.map(convert_datetime),
       inbox: self.inbox_url.clone().into(),
     };
@@ -137,6 +147,8 @@ impl Object for ApubPerson {
     context: &Data<Self::DataType>,
   ) -> Result<ApubPerson, LemmyError> {
     let instance_id = fetch_instance_actor_for_object(&person.id, context).await?;
+    move_renamed_person(&person, context).await?;
+    let assertion_methods = person.assertion_method;
 
     let name = sanitize_html(&person.preferred_username);
     let display_name = sanitize_html_opt(&person.name);
@@ -171,11 +183,38 @@ impl Object for ApubPerson {
       instance_id,
     };
     let person = DbPerson::upsert(&mut context.pool(), &person_form).await?;
+    store_assertion_method(person.actor_id.inner(), &assertion_methods, context).await?;
 
     Ok(person.into())
   }
 }
 
+/// If a remote user changed their username, their actor id changes as well. Move the existing
+/// account to the new id, instead of creating a second one.
+async fn move_renamed_person(person: &Person, context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let actor_id = person.id.clone().into();
+  if DbPerson::read_from_apub_id(&mut context.pool(), &actor_id)
+    .await?
+    .is_some()
+  {
+    return Ok(());
+  }
+  for former_id in &person.also_known_as {
+    // Only the same instance can claim that an account was renamed
+    if former_id.domain() != person.id.inner().domain() {
+      continue;
+    }
+    let former =
+      DbPerson::read_from_apub_id(&mut context.pool(), &former_id.clone().into()).await?;
+    if let Some(former) = former.filter(|p| !p.local) {
+      let form = PersonUpdateForm::builder().actor_id(Some(actor_id)).build();
+      DbPerson::update(&mut context.pool(), former.id, &form).await?;
+      break;
+    }
+  }
+  Ok(())
+}
+
 impl Actor for ApubPerson {
   fn id(&self) -> Url {
     self.actor_id.inner().clone()
@@ -258,11 +297,33 @@ pub(crate) mod tests {
     assert_eq!(person.name, "lanodan");
     assert!(!person.local);
     assert_eq!(context.request_count(), 0);
-    assert_eq!(person.bio.as_ref().unwrap().len(), 873);
+    assert_eq!(person.bio.as_ref().unwrap().len(), 766);
 
     cleanup((person, site), &context).await;
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_parse_renamed_person() {
+    let context = init_context().await;
+    let (person, site) = parse_lemmy_person(&context).await;
+
+    // The same account with a new name, which mentions the old id
+    let mut json: Person = file_to_json_object("assets/lemmy/objects/person.json").unwrap();
+    let url = Url::parse("https://enterprise.lemmy.ml/u/jeanluc").unwrap();
+    json.also_known_as = vec![json.id.inner().clone()];
+    json.id = url.clone().into();
+    json.preferred_username = "jeanluc".to_string();
+    ApubPerson::verify(&json, &url, &context).await.unwrap();
+    let renamed = ApubPerson::from_json(json, &context).await.unwrap();
+
+    assert_eq!(person.id, renamed.id);
+    assert_eq!(renamed.actor_id, url.into());
+    assert_eq!(renamed.name, "jeanluc");
+
+    cleanup((renamed, site), &context).await;
+  }
+
   async fn cleanup(data: (ApubPerson, ApubSite), context: &LemmyContext) {
     DbPerson::delete(&mut context.pool(), data.0.id)
       .await
@@ -1,8 +1,14 @@
 use crate::{
   activities::{verify_is_public, verify_person_in_community},
   check_apub_id_valid_with_strictness,
+  collections::post_replies::backfill_replies,
   local_site_data_cached,
-  objects::{read_from_string_or_source_opt, verify_is_remote_object},
+  mentions::collect_non_local_post_mentions,
+  objects::{
+    html_to_markdown::html_to_markdown,
+    read_from_string_or_source_opt,
+    verify_is_remote_object,
+  },
   protocol::{
     objects::{
       page::{Attachment, AttributedTo, Page, PageType},
@@ -21,31 +27,42 @@ use activitypub_federation::{
 };
 use anyhow::anyhow;
 use chrono::NaiveDateTime;
-use html2md::parse_html;
 use lemmy_api_common::{
   context::LemmyContext,
-  request::fetch_site_data,
+  language_detection::detect_language,
+  request::{fetch_site_data, is_site_data_missing},
   utils::{
+    apply_post_word_filter_action,
+    check_community_language,
+    check_community_post_type,
+    check_community_word_filters,
+    hold_post_for_review,
+    is_below_community_posting_thresholds,
     is_mod_or_admin,
     local_site_opt_to_sensitive,
     local_site_opt_to_slur_regex,
     sanitize_html,
     sanitize_html_opt,
+    slur_filter_regex,
+    url_is_blocked,
   },
 };
 use lemmy_db_schema::{
   self,
   source::{
+    blocked_url::BlockedUrl,
     community::Community,
     local_site::LocalSite,
-    moderator::{ModLockPost, ModLockPostForm},
+    moderator::{ModLockPost, ModLockPostForm, ModRemovePost, ModRemovePostForm},
     person::Person,
-    post::{Post, PostInsertForm, PostUpdateForm},
+    post::{Post, PostInsertForm, PostMetadataRefetch, PostUpdateForm},
   },
   traits::Crud,
 };
+use lemmy_db_views_actor::structs::PersonView;
 use lemmy_utils::{
   error::LemmyError,
+  spawn_try_task,
   utils::{
     markdown::markdown_to_html,
     slurs::{check_slurs_opt, remove_slurs},
@@ -113,18 +130,28 @@ impl Object for ApubPost {
     let community_id = self.community_id;
     let community = Community::read(&mut context.pool(), community_id).await?;
     let language = LanguageTag::new_single(self.language_id, &mut context.pool()).await?;
+    let attachment = match (&self.url, &self.audio_mime_type) {
+      (Some(url), Some(media_type)) => vec![Attachment::new_audio(
+        url.clone(),
+        media_type.clone(),
+        self.audio_duration,
+      )],
+      _ => self.url.clone().map(Attachment::new).into_iter().collect(),
+    };
+
+    let maa = collect_non_local_post_mentions(self.body.as_deref(), context).await;
 
     let page = Page {
       kind: PageType::Page,
       id: self.ap_id.clone().into(),
       attributed_to: AttributedTo::Lemmy(creator.actor_id.into()),
       to: vec![community.actor_id.clone().into(), public()],
-      cc: vec![],
+      cc: maa.ccs,
       name: Some(self.name.clone()),
       content: self.body.as_ref().map(|b| markdown_to_html(b)),
       media_type: Some(MediaTypeMarkdownOrHtml::Html),
       source: self.body.clone().map(Source::new),
-      attachment: self.url.clone().map(Attachment::new).into_iter().collect(),
+      attachment,
       image: self.thumbnail_url.clone().map(ImageObject::new),
       comments_enabled: Some(!self.locked),
       sensitive: Some(self.nsfw),
@@ -132,7 +159,11 @@ impl Object for ApubPost {
       published: Some(convert_datetime(self.published)),
       updated: self.updated.map(convert_datetime),
       audience: Some(community.actor_id.into()),
+      tag: maa.tags,
       in_reply_to: None,
+      replies: None,
+      url: vec![],
+      duration: None,
     };
     Ok(page)
   }
@@ -178,7 +209,7 @@ impl Object for ApubPost {
           .content
           .clone()
           .as_ref()
-          .and_then(|c| parse_html(c).lines().next().map(ToString::to_string))
+          .and_then(|c| html_to_markdown(c).lines().next().map(ToString::to_string))
       })
       .ok_or_else(|| anyhow!("Object must have name or content"))?;
     if name.chars().count() > MAX_TITLE_LENGTH {
@@ -187,11 +218,20 @@ impl Object for ApubPost {
 
     // read existing, local post if any (for generating mod log)
     let old_post = page.id.dereference_local(context).await;
+    let blocklist = BlockedUrl::get_all(&mut context.pool()).await?;
 
-    let form = if !page.is_mod_action(context).await? {
+    let is_mod_action = page.is_mod_action(context).await?;
+    let (form, refetch_metadata, word_filter_action) = if !is_mod_action {
+      let audio = page.audio();
+      let canonical_url = page.canonical_url();
       let first_attachment = page.attachment.into_iter().map(Attachment::url).next();
       let url = if first_attachment.is_some() {
         first_attachment
+      } else if canonical_url.is_some() {
+        // long-form articles from blogs link to their original page
+        canonical_url
+      } else if let Some(audio) = &audio {
+        Some(audio.url.clone())
       } else if page.kind == PageType::Video {
         // we cant display videos directly, so insert a link to external video page
         Some(page.id.inner().clone())
@@ -199,6 +239,7 @@ impl Object for ApubPost {
         None
       };
       check_url_scheme(&url)?;
+      let url_blocked = url.as_ref().is_some_and(|u| url_is_blocked(u, &blocklist));
 
       let local_site = LocalSite::read(&mut context.pool()).await.ok();
       let allow_sensitive = local_site_opt_to_sensitive(&local_site);
@@ -206,10 +247,11 @@ impl Object for ApubPost {
       let include_image = allow_sensitive || !page_is_sensitive;
 
       // Only fetch metadata if the post has a url and was not seen previously. We dont want to
-      // waste resources by fetching metadata for the same post multiple times.
-      // Additionally, only fetch image if content is not sensitive or is allowed on local site.
+      // waste resources by fetching metadata for the same post multiple times, or contact
+      // blocked sites. Additionally, only fetch image if content is not sensitive or is allowed
+      // on local site.
       let (metadata_res, thumbnail) = match &url {
-        Some(url) if old_post.is_err() => {
+        Some(url) if old_post.is_err() && !url_blocked => {
           fetch_site_data(
             context.client(),
             context.settings(),
@@ -222,6 +264,10 @@ impl Object for ApubPost {
       };
       // If no image was included with metadata, use post image instead when available.
       let thumbnail_url = thumbnail.or_else(|| page.image.map(|i| i.url.into()));
+      let refetch_metadata = url.is_some()
+        && old_post.is_err()
+        && !url_blocked
+        && is_site_data_missing(&metadata_res, &thumbnail_url);
 
       let (embed_title, embed_description, embed_video_url) = metadata_res
         .map(|u| (u.title, u.description, u.embed_video_url))
@@ -232,24 +278,55 @@ impl Object for ApubPost {
         read_from_string_or_source_opt(&page.content, &page.media_type, &page.source)
           .map(|s| remove_slurs(&s, slur_regex));
       let language_id =
-        LanguageTag::to_language_id_single(page.language, &mut context.pool()).await?;
+        match LanguageTag::to_language_id_single(page.language, &mut context.pool()).await? {
+          Some(lid) => Some(lid),
+          None => {
+            let texts = [Some(name.as_str()), body_slurs_removed.as_deref()];
+            let texts = texts.into_iter().flatten().collect::<Vec<_>>();
+            detect_language(&texts, &community, &mut context.pool()).await?
+          }
+        };
+      // Only local communities are checked, remote ones are responsible for their own content
+      if community.local {
+        check_community_language(&community, language_id, &mut context.pool()).await?;
+      }
+      let slur_filter = slur_filter_regex(language_id, community.id, &mut context.pool()).await?;
+      let name = remove_slurs(&name, &slur_filter);
+      let body_slurs_removed = body_slurs_removed.map(|b| remove_slurs(&b, &slur_filter));
+
+      // Posts which break the community post type restriction or link to a blocked url are kept,
+      // but removed
+      let removed = (url_blocked || check_community_post_type(&community, url.as_ref()).is_err())
+        .then_some(true);
+      let nsfw = if community.nsfw_only {
+        Some(true)
+      } else {
+        page.sensitive
+      };
 
       let name = sanitize_html(&name);
+      let texts = [Some(name.as_str()), body_slurs_removed.as_deref()];
+      let word_filter_action = check_community_word_filters(
+        community.id,
+        &texts.into_iter().flatten().collect::<Vec<_>>(),
+        &mut context.pool(),
+      )
+      .await?;
       let embed_title = sanitize_html_opt(&embed_title);
       let embed_description = sanitize_html_opt(&embed_description);
 
-      PostInsertForm {
+      let form = PostInsertForm {
         name,
         url: url.map(Into::into),
         body: body_slurs_removed,
         creator_id: creator.id,
         community_id: community.id,
-        removed: None,
+        removed,
         locked: page.comments_enabled.map(|e| !e),
         published: page.published.map(|u| u.naive_local()),
         updated: page.updated.map(|u| u.naive_local()),
         deleted: Some(false),
-        nsfw: page.sensitive,
+        nsfw,
         embed_title,
         embed_description,
         embed_video_url,
@@ -259,21 +336,72 @@ impl Object for ApubPost {
         language_id,
         featured_community: None,
         featured_local: None,
-      }
+        audio_mime_type: audio.as_ref().map(|a| a.media_type.clone()),
+        audio_duration: audio.and_then(|a| a.duration),
+      };
+      (form, refetch_metadata, word_filter_action)
     } else {
       // if is mod action, only update locked/stickied fields, nothing else
-      PostInsertForm::builder()
+      let form = PostInsertForm::builder()
         .name(name)
         .creator_id(creator.id)
         .community_id(community.id)
         .ap_id(Some(page.id.clone().into()))
         .locked(page.comments_enabled.map(|e| !e))
         .updated(page.updated.map(|u| u.naive_local()))
-        .build()
+        .build();
+      (form, false, None)
     };
 
     let post = Post::create(&mut context.pool(), &form).await?;
 
+    // retry the link metadata later if the site didn't respond
+    if refetch_metadata {
+      PostMetadataRefetch::schedule(&mut context.pool(), post.id).await?;
+    }
+
+    // write mod log entry for the removal of posts linking to a blocked url, in the name of the
+    // site owner
+    let was_removed = old_post.as_ref().is_ok_and(|p| p.removed);
+    let links_blocked_url = post
+      .url
+      .as_ref()
+      .is_some_and(|u| url_is_blocked(u, &blocklist));
+    if post.removed && !was_removed && links_blocked_url {
+      if let Some(owner) = PersonView::admins(&mut context.pool()).await?.first() {
+        let form = ModRemovePostForm {
+          mod_person_id: owner.person.id,
+          post_id: post.id,
+          reason: Some("Links to a blocked url".to_string()),
+          removed: Some(true),
+        };
+        ModRemovePost::create(&mut context.pool(), &form).await?;
+      }
+    }
+
+    // only apply community word filters and posting requirements once, when the post is first
+    // received. Remote posts into local communities by users below the requirements are held for
+    // review.
+    if old_post.is_err() {
+      let held = community.local
+        && !is_mod_action
+        && is_below_community_posting_thresholds(&community, &creator, &mut context.pool()).await?;
+      if held {
+        hold_post_for_review(&post, &mut context.pool()).await?;
+      } else {
+        apply_post_word_filter_action(&post, word_filter_action, &mut context.pool()).await?;
+      }
+    }
+
+    // fetch existing comments of posts which are seen for the first time
+    if let Some(replies) = page.replies.filter(|_| old_post.is_err() && !is_mod_action) {
+      spawn_try_task(backfill_replies(
+        post.ap_id.clone().into(),
+        replies,
+        context.reset_request_count(),
+      ));
+    }
+
     // write mod log entry for lock
     if Page::is_locked_changed(&old_post, &page.comments_enabled) {
       let form = ModLockPostForm {
@@ -321,7 +449,7 @@ mod tests {
     assert_eq!(post.ap_id, url.into());
     assert_eq!(post.name, "Post title");
     assert!(post.body.is_some());
-    assert_eq!(post.body.as_ref().unwrap().len(), 45);
+    assert_eq!(post.body.as_ref().unwrap().len(), 76);
     assert!(!post.locked);
     assert!(!post.featured_community);
     assert_eq!(context.request_count(), 0);
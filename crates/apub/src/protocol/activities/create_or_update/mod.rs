@@ -1,6 +1,8 @@
 pub mod chat_message;
 pub mod note;
 pub mod page;
+pub mod person;
+pub mod site;
 
 #[cfg(test)]
 mod tests {
@@ -12,6 +14,8 @@ mod tests {
       chat_message::CreateOrUpdateChatMessage,
       note::CreateOrUpdateNote,
       page::CreateOrUpdatePage,
+      person::UpdatePerson,
+      site::UpdateSite,
     },
     tests::test_parse_lemmy_item,
   };
@@ -34,5 +38,13 @@ mod tests {
       "assets/lemmy/activities/create_or_update/create_private_message.json",
     )
     .unwrap();
+    test_parse_lemmy_item::<UpdatePerson>(
+      "assets/lemmy/activities/create_or_update/update_person.json",
+    )
+    .unwrap();
+    test_parse_lemmy_item::<UpdateSite>(
+      "assets/lemmy/activities/create_or_update/update_site.json",
+    )
+    .unwrap();
   }
 }
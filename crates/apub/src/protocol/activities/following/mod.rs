@@ -1,5 +1,6 @@
 pub(crate) mod accept;
 pub mod follow;
+pub(crate) mod reject;
 pub mod undo_follow;
 
 #[cfg(test)]
@@ -8,7 +9,12 @@ mod tests {
   #![allow(clippy::indexing_slicing)]
 
   use crate::protocol::{
-    activities::following::{accept::AcceptFollow, follow::Follow, undo_follow::UndoFollow},
+    activities::following::{
+      accept::AcceptFollow,
+      follow::Follow,
+      reject::RejectFollow,
+      undo_follow::UndoFollow,
+    },
     tests::test_parse_lemmy_item,
   };
 
@@ -16,6 +22,7 @@ mod tests {
   fn test_parse_lemmy_accept_follow() {
     test_parse_lemmy_item::<Follow>("assets/lemmy/activities/following/follow.json").unwrap();
     test_parse_lemmy_item::<AcceptFollow>("assets/lemmy/activities/following/accept.json").unwrap();
+    test_parse_lemmy_item::<RejectFollow>("assets/lemmy/activities/following/reject.json").unwrap();
     test_parse_lemmy_item::<UndoFollow>("assets/lemmy/activities/following/undo_follow.json")
       .unwrap();
   }
@@ -6,6 +6,7 @@ pub mod community;
 pub mod create_or_update;
 pub mod deletion;
 pub mod following;
+pub mod reaction;
 pub mod voting;
 
 #[derive(Clone, Debug, Display, Deserialize, Serialize, PartialEq, Eq)]
@@ -25,6 +26,7 @@ mod tests {
       create_or_update::{note::CreateOrUpdateNote, page::CreateOrUpdatePage},
       deletion::delete::Delete,
       following::{follow::Follow, undo_follow::UndoFollow},
+      reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
       voting::{undo_vote::UndoVote, vote::Vote},
     },
     tests::test_json,
@@ -40,6 +42,8 @@ mod tests {
     test_json::<CreateOrUpdateNote>("assets/pleroma/activities/create_note.json").unwrap();
     test_json::<Delete>("assets/pleroma/activities/delete.json").unwrap();
     test_json::<Follow>("assets/pleroma/activities/follow.json").unwrap();
+    test_json::<EmojiReact>("assets/pleroma/activities/emoji_react.json").unwrap();
+    test_json::<UndoEmojiReact>("assets/pleroma/activities/undo_emoji_react.json").unwrap();
   }
 
   #[test]
@@ -50,6 +54,22 @@ mod tests {
     test_json::<UndoFollow>("assets/mastodon/activities/undo_follow.json").unwrap();
     test_json::<Vote>("assets/mastodon/activities/like_page.json").unwrap();
     test_json::<UndoVote>("assets/mastodon/activities/undo_like_page.json").unwrap();
+    // Poll votes are notes with only a name. Lemmy has no polls, so they must not turn into
+    // comments.
+    assert!(
+      test_json::<CreateOrUpdateNote>("assets/mastodon/activities/create_poll_vote.json").is_err()
+    );
+  }
+
+  #[test]
+  fn test_parse_misskey_activities() {
+    let reaction = test_json::<Vote>("assets/misskey/activities/like_note_reaction.json").unwrap();
+    assert_eq!(Some("🎉".to_string()), reaction.inner().content);
+  }
+
+  #[test]
+  fn test_parse_plume_activities() {
+    test_json::<CreateOrUpdatePage>("assets/plume/activities/create_article.json").unwrap();
   }
 
   #[test]
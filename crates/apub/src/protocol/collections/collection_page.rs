@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+/// A collection or collection page of any software, which may be paginated. Used for outboxes
+/// and reply collections. Items are kept as json, because they may contain objects which Lemmy
+/// can't parse, or only urls.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionPage {
+  #[serde(default, alias = "items")]
+  pub(crate) ordered_items: Vec<Value>,
+  pub(crate) first: Option<CollectionPageRef>,
+  pub(crate) next: Option<CollectionPageRef>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CollectionPageRef {
+  Url(Url),
+  Page(Box<CollectionPage>),
+}
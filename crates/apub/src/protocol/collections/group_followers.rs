@@ -1,4 +1,8 @@
-use activitypub_federation::kinds::collection::CollectionType;
+use crate::{
+  collections::collection_page_size,
+  protocol::collections::ordered_collection_page::OrderedCollectionPage,
+};
+use activitypub_federation::kinds::collection::OrderedCollectionType;
 use lemmy_api_common::{context::LemmyContext, utils::generate_followers_url};
 use lemmy_db_schema::source::community::Community;
 use lemmy_db_views_actor::structs::CommunityFollowerView;
@@ -6,13 +10,15 @@ use lemmy_utils::error::LemmyError;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+/// The followers of a community. Only the number of followers is included, the actors themselves
+/// are listed in pages starting from `first`.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct GroupFollowers {
   id: Url,
-  r#type: CollectionType,
+  r#type: OrderedCollectionType,
   total_items: i32,
-  items: Vec<()>,
+  first: Url,
 }
 
 impl GroupFollowers {
@@ -23,12 +29,39 @@ impl GroupFollowers {
     let community_id = community.id;
     let community_followers =
       CommunityFollowerView::count_community_followers(&mut context.pool(), community_id).await?;
+    let id: Url = generate_followers_url(&community.actor_id)?.into();
 
     Ok(GroupFollowers {
-      id: generate_followers_url(&community.actor_id)?.into(),
-      r#type: CollectionType::Collection,
+      first: OrderedCollectionPage::<Url>::url(&id, None),
+      id,
+      r#type: OrderedCollectionType::OrderedCollection,
       total_items: community_followers as i32,
-      items: vec![],
     })
   }
+
+  /// The page of followers which starts below `max_id`
+  pub(crate) async fn page(
+    community: Community,
+    max_id: Option<i32>,
+    context: &LemmyContext,
+  ) -> Result<OrderedCollectionPage<Url>, LemmyError> {
+    let limit = collection_page_size(context);
+    let followers = CommunityFollowerView::list_follower_actor_ids(
+      &mut context.pool(),
+      community.id,
+      max_id,
+      limit,
+    )
+    .await?
+    .into_iter()
+    .map(|(id, actor_id)| (id, actor_id.into()))
+    .collect();
+    let collection = generate_followers_url(&community.actor_id)?.into();
+    Ok(OrderedCollectionPage::new(
+      &collection,
+      max_id,
+      followers,
+      limit,
+    ))
+  }
 }
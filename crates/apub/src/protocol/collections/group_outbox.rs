@@ -1,8 +1,12 @@
 use crate::protocol::activities::community::announce::AnnounceActivity;
 use activitypub_federation::kinds::collection::OrderedCollectionType;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use url::Url;
 
+/// The outbox of a community. It contains the newest posts, for older Lemmy versions which don't
+/// paginate, and links to the first page from where all posts can be walked.
+#[skip_serializing_none]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupOutbox {
@@ -10,4 +14,5 @@ pub struct GroupOutbox {
   pub(crate) id: Url,
   pub(crate) total_items: i32,
   pub(crate) ordered_items: Vec<AnnounceActivity>,
+  pub(crate) first: Option<Url>,
 }
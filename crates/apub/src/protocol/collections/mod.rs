@@ -1,8 +1,10 @@
+pub(crate) mod collection_page;
 pub(crate) mod empty_outbox;
 pub(crate) mod group_featured;
 pub(crate) mod group_followers;
 pub(crate) mod group_moderators;
 pub(crate) mod group_outbox;
+pub(crate) mod ordered_collection_page;
 
 #[cfg(test)]
 mod tests {
@@ -11,19 +13,27 @@ mod tests {
 
   use crate::protocol::{
     collections::{
+      collection_page::{CollectionPage, CollectionPageRef},
       empty_outbox::EmptyOutbox,
       group_featured::GroupFeatured,
       group_followers::GroupFollowers,
       group_moderators::GroupModerators,
       group_outbox::GroupOutbox,
+      ordered_collection_page::OrderedCollectionPage,
     },
-    tests::{test_json, test_parse_lemmy_item},
+    objects::note::Note,
+    tests::{file_to_json_object, test_json, test_parse_lemmy_item},
   };
+  use url::Url;
 
   #[test]
   fn test_parse_lemmy_collections() {
     test_parse_lemmy_item::<GroupFollowers>("assets/lemmy/collections/group_followers.json")
       .unwrap();
+    test_parse_lemmy_item::<OrderedCollectionPage<Url>>(
+      "assets/lemmy/collections/group_followers_page.json",
+    )
+    .unwrap();
     let outbox =
       test_parse_lemmy_item::<GroupOutbox>("assets/lemmy/collections/group_outbox.json").unwrap();
     assert_eq!(outbox.ordered_items.len() as i32, outbox.total_items);
@@ -34,8 +44,61 @@ mod tests {
     test_parse_lemmy_item::<EmptyOutbox>("assets/lemmy/collections/person_outbox.json").unwrap();
   }
 
+  #[test]
+  fn test_ordered_collection_page() {
+    let collection = Url::parse("https://enterprise.lemmy.ml/c/main/outbox").unwrap();
+    let page = OrderedCollectionPage::new(&collection, Some(10), vec![(9, "a"), (7, "b")], 2);
+    assert_eq!(
+      "https://enterprise.lemmy.ml/c/main/outbox?page=true&max_id=10",
+      page.id.as_str()
+    );
+    assert_eq!(vec!["a", "b"], page.ordered_items);
+    assert_eq!(
+      Some("https://enterprise.lemmy.ml/c/main/outbox?page=true&max_id=7"),
+      page.next.as_ref().map(Url::as_str)
+    );
+
+    // A page which isn't full is the last one
+    let page = OrderedCollectionPage::new(&collection, Some(7), vec![(3, "c")], 2);
+    assert!(page.next.is_none());
+  }
+
   #[test]
   fn test_parse_mastodon_collections() {
     test_json::<GroupFeatured>("assets/mastodon/collections/featured.json").unwrap();
   }
+
+  #[test]
+  fn test_parse_outbox_pages() {
+    // Lemmy includes the newest items in the outbox itself, and links the first page
+    let outbox =
+      file_to_json_object::<CollectionPage>("assets/lemmy/collections/group_outbox.json").unwrap();
+    assert_eq!(2, outbox.ordered_items.len());
+    assert!(matches!(outbox.first, Some(CollectionPageRef::Url(_))));
+
+    // Mastodon paginates, and only links the first page
+    let outbox =
+      file_to_json_object::<CollectionPage>("assets/mastodon/collections/outbox.json").unwrap();
+    assert!(outbox.ordered_items.is_empty());
+    assert!(matches!(outbox.first, Some(CollectionPageRef::Url(_))));
+    let page =
+      file_to_json_object::<CollectionPage>("assets/mastodon/collections/outbox_page.json")
+        .unwrap();
+    assert_eq!(2, page.ordered_items.len());
+    assert!(matches!(page.next, Some(CollectionPageRef::Url(_))));
+  }
+
+  #[test]
+  fn test_parse_replies_collection() {
+    // Mastodon embeds the first page of replies, which links to the next one
+    let note = test_json::<Note>("assets/mastodon/objects/note.json").unwrap();
+    let Some(CollectionPageRef::Page(replies)) = note.inner().replies.clone() else {
+      panic!("replies collection should be embedded");
+    };
+    let Some(CollectionPageRef::Page(first)) = replies.first else {
+      panic!("first page should be embedded");
+    };
+    assert!(first.ordered_items.is_empty());
+    assert!(matches!(first.next, Some(CollectionPageRef::Url(_))));
+  }
 }
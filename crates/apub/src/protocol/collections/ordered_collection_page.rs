@@ -0,0 +1,52 @@
+use activitypub_federation::kinds::collection::OrderedCollectionPageType;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// A page of a collection served by Lemmy, which is linked as `first` from the collection itself.
+/// Each page links to the next one, until the last page.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OrderedCollectionPage<T> {
+  pub(crate) r#type: OrderedCollectionPageType,
+  pub(crate) id: Url,
+  pub(crate) part_of: Url,
+  pub(crate) ordered_items: Vec<T>,
+  pub(crate) next: Option<Url>,
+}
+
+impl<T> OrderedCollectionPage<T> {
+  /// Url of the page which starts below `max_id`, or of the first page
+  pub(crate) fn url(collection: &Url, max_id: Option<i32>) -> Url {
+    let mut url = collection.clone();
+    url.query_pairs_mut().append_pair("page", "true");
+    if let Some(max_id) = max_id {
+      url
+        .query_pairs_mut()
+        .append_pair("max_id", &max_id.to_string());
+    }
+    url
+  }
+
+  /// Builds a page from items sorted by descending keys. Only links the next page if this one is
+  /// full, the last page is the first which has less than `limit` items.
+  pub(crate) fn new(
+    collection: &Url,
+    max_id: Option<i32>,
+    items: Vec<(i32, T)>,
+    limit: i64,
+  ) -> Self {
+    let next = match items.last() {
+      Some((key, _)) if items.len() as i64 >= limit => Some(Self::url(collection, Some(*key))),
+      _ => None,
+    };
+    OrderedCollectionPage {
+      r#type: OrderedCollectionPageType::OrderedCollectionPage,
+      id: Self::url(collection, max_id),
+      part_of: collection.clone(),
+      ordered_items: items.into_iter().map(|(_, item)| item).collect(),
+      next,
+    }
+  }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Proof that an object was created by its actor, independent of the http signature of the
+/// request which delivered it (FEP-8b32)
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DataIntegrityProof {
+  #[serde(rename = "type")]
+  pub(crate) kind: String,
+  pub(crate) cryptosuite: String,
+  pub(crate) verification_method: Url,
+  pub(crate) proof_purpose: String,
+  pub(crate) proof_value: String,
+}
+
+/// Public key for integrity proofs, published as `assertionMethod` of an actor
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Multikey {
+  pub(crate) id: Url,
+  #[serde(rename = "type")]
+  pub(crate) kind: String,
+  pub(crate) controller: Url,
+  pub(crate) public_key_multibase: String,
+}
@@ -14,6 +14,7 @@ use url::Url;
 
 pub mod activities;
 pub(crate) mod collections;
+pub(crate) mod integrity_proof;
 pub(crate) mod objects;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
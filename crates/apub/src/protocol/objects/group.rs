@@ -8,7 +8,8 @@ use crate::{
   local_site_data_cached,
   objects::{community::ApubCommunity, read_from_string_or_source_opt},
   protocol::{
-    objects::{Endpoints, LanguageTag},
+    integrity_proof::Multikey,
+    objects::{CategoryTagOrValue, Endpoints, LanguageTag},
     ImageObject,
     Source,
   },
@@ -28,9 +29,13 @@ use lemmy_api_common::{
   utils::{local_site_opt_to_slur_regex, sanitize_html, sanitize_html_opt},
 };
 use lemmy_db_schema::{
-  newtypes::InstanceId,
-  source::community::{CommunityInsertForm, CommunityUpdateForm},
+  newtypes::{CommunityId, InstanceId},
+  source::{
+    community::{CommunityInsertForm, CommunityUpdateForm},
+    community_rule::CommunityRuleForm,
+  },
   utils::naive_now,
+  PostTypeRestriction,
 };
 use lemmy_utils::{
   error::LemmyError,
@@ -53,6 +58,9 @@ pub struct Group {
   pub(crate) inbox: Url,
   pub(crate) followers: Url,
   pub(crate) public_key: PublicKey,
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) assertion_method: Vec<Multikey>,
 
   /// title
   pub(crate) name: Option<String>,
@@ -68,15 +76,38 @@ pub struct Group {
   pub(crate) attributed_to: Option<CollectionId<ApubCommunityModerators>>,
   // lemmy extension
   pub(crate) posting_restricted_to_mods: Option<bool>,
+  // lemmy extension
+  pub(crate) enable_downvotes: Option<bool>,
+  pub(crate) manually_approves_followers: Option<bool>,
+  // lemmy extension
+  pub(crate) post_type_restriction: Option<PostTypeRestriction>,
+  // lemmy extension
+  pub(crate) nsfw_only: Option<bool>,
+  // lemmy extension
+  pub(crate) hidden: Option<bool>,
   pub(crate) outbox: CollectionId<ApubCommunityOutbox>,
   pub(crate) endpoints: Option<Endpoints>,
   pub(crate) featured: Option<CollectionId<ApubCommunityFeatured>>,
   #[serde(default)]
   pub(crate) language: Vec<LanguageTag>,
+  #[serde(default)]
+  pub(crate) tag: Vec<CategoryTagOrValue>,
+  // lemmy extension
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) rules: Vec<GroupRule>,
   pub(crate) published: Option<DateTime<FixedOffset>>,
   pub(crate) updated: Option<DateTime<FixedOffset>>,
 }
 
+/// A community rule, in the order in which it is shown.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct GroupRule {
+  pub(crate) name: String,
+  pub(crate) content: Option<String>,
+}
+
 impl Group {
   pub(crate) async fn verify(
     &self,
@@ -93,9 +124,27 @@ impl Group {
     check_slurs_opt(&self.name, slur_regex)?;
     let description = read_from_string_or_source_opt(&self.summary, &None, &self.source);
     check_slurs_opt(&description, slur_regex)?;
+    for rule in &self.rules {
+      check_slurs(&rule.name, slur_regex)?;
+      check_slurs_opt(&rule.content, slur_regex)?;
+    }
     Ok(())
   }
 
+  pub(crate) fn rule_forms(&self, community_id: CommunityId) -> Vec<CommunityRuleForm> {
+    self
+      .rules
+      .iter()
+      .zip(0..)
+      .map(|(rule, position)| CommunityRuleForm {
+        community_id,
+        position,
+        title: sanitize_html(&rule.name),
+        description: sanitize_html_opt(&rule.content),
+      })
+      .collect()
+  }
+
   pub(crate) fn into_insert_form(self, instance_id: InstanceId) -> CommunityInsertForm {
     let name = sanitize_html(&self.preferred_username);
     let title = sanitize_html(&self.name.unwrap_or(self.preferred_username));
@@ -114,7 +163,7 @@ impl Group {
       actor_id: Some(self.id.into()),
       local: Some(false),
       private_key: None,
-      hidden: None,
+      hidden: self.hidden,
       public_key: self.public_key.public_key_pem,
       last_refreshed_at: Some(naive_now()),
       icon: self.icon.map(|i| i.url.into()),
@@ -126,6 +175,19 @@ impl Group {
       posting_restricted_to_mods: self.posting_restricted_to_mods,
       instance_id,
       featured_url: self.featured.map(Into::into),
+      enable_downvotes: self.enable_downvotes,
+      requires_follow_approval: self.manually_approves_followers,
+      post_type_restriction: self.post_type_restriction,
+      nsfw_only: self.nsfw_only,
+      post_slow_mode_seconds: None,
+      comment_slow_mode_seconds: None,
+      allow_undetermined_language: None,
+      strike_ban_threshold: None,
+      strike_ban_days: None,
+      min_account_age_days: None,
+      min_site_karma: None,
+      min_community_karma: None,
+      notify_mods_on_mention: None,
     }
   }
 
@@ -145,7 +207,7 @@ impl Group {
       actor_id: Some(self.id.into()),
       local: None,
       private_key: None,
-      hidden: None,
+      hidden: self.hidden,
       public_key: Some(self.public_key.public_key_pem),
       last_refreshed_at: Some(naive_now()),
       icon: Some(self.icon.map(|i| i.url.into())),
@@ -156,6 +218,19 @@ impl Group {
       moderators_url: self.attributed_to.map(Into::into),
       posting_restricted_to_mods: self.posting_restricted_to_mods,
       featured_url: self.featured.map(Into::into),
+      enable_downvotes: self.enable_downvotes,
+      requires_follow_approval: self.manually_approves_followers,
+      post_type_restriction: self.post_type_restriction,
+      nsfw_only: self.nsfw_only,
+      post_slow_mode_seconds: None,
+      comment_slow_mode_seconds: None,
+      allow_undetermined_language: None,
+      strike_ban_threshold: None,
+      strike_ban_days: None,
+      min_account_age_days: None,
+      min_site_karma: None,
+      min_community_karma: None,
+      notify_mods_on_mention: None,
     }
   }
 }
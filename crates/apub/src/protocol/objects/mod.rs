@@ -1,11 +1,15 @@
 use lemmy_db_schema::{
   impls::actor_language::UNDETERMINED_ID,
-  newtypes::LanguageId,
-  source::language::Language,
+  newtypes::{CategoryId, CommunityId, LanguageId},
+  source::{
+    category::{Category, CommunityCategory},
+    language::Language,
+  },
   utils::DbPool,
 };
-use lemmy_utils::error::LemmyError;
+use lemmy_utils::{error::LemmyError, utils::validation::COMMUNITY_CATEGORIES_MAX};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use url::Url;
 
 pub(crate) mod chat_message;
@@ -93,6 +97,74 @@ impl LanguageTag {
   }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub(crate) enum HashtagType {
+  Hashtag,
+}
+
+/// A community category, federated as hashtag so that other platforms can also use it for
+/// discovery
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct CategoryTag {
+  #[serde(rename = "type")]
+  pub(crate) kind: HashtagType,
+  pub(crate) name: String,
+}
+
+/// Other tags are kept, so that the object can still be parsed
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(crate) enum CategoryTagOrValue {
+  Category(CategoryTag),
+  Value(Value),
+}
+
+impl CategoryTag {
+  pub(crate) async fn new_multiple(
+    community_id: CommunityId,
+    pool: &mut DbPool<'_>,
+  ) -> Result<Vec<CategoryTagOrValue>, LemmyError> {
+    let categories = CommunityCategory::read(pool, community_id).await?;
+    Ok(
+      categories
+        .into_iter()
+        .map(|c| {
+          CategoryTagOrValue::Category(CategoryTag {
+            kind: HashtagType::Hashtag,
+            name: format!("#{}", c.name),
+          })
+        })
+        .collect(),
+    )
+  }
+
+  /// Categories are defined separately by each instance, so tags are matched by name with the
+  /// local categories. Unknown tags are ignored.
+  pub(crate) async fn to_category_ids(
+    tags: &[CategoryTagOrValue],
+    pool: &mut DbPool<'_>,
+  ) -> Result<Vec<CategoryId>, LemmyError> {
+    let names: Vec<String> = tags
+      .iter()
+      .filter_map(|t| match t {
+        CategoryTagOrValue::Category(c) => Some(c.name.trim_start_matches('#').to_string()),
+        CategoryTagOrValue::Value(_) => None,
+      })
+      .collect();
+    if names.is_empty() {
+      return Ok(vec![]);
+    }
+    let categories = Category::read_by_names(pool, &names).await?;
+    Ok(
+      categories
+        .into_iter()
+        .map(|c| c.id)
+        .take(COMMUNITY_CATEGORIES_MAX)
+        .collect(),
+    )
+  }
+}
+
 #[cfg(test)]
 mod tests {
   #![allow(clippy::unwrap_used)]
@@ -126,6 +198,7 @@ mod tests {
   fn test_parse_objects_pleroma() {
     test_json::<Person>("assets/pleroma/objects/person.json").unwrap();
     test_json::<Note>("assets/pleroma/objects/note.json").unwrap();
+    test_json::<Note>("assets/pleroma/objects/note_2.json").unwrap();
     test_json::<ChatMessage>("assets/pleroma/objects/chat_message.json").unwrap();
   }
 
@@ -139,6 +212,7 @@ mod tests {
   fn test_parse_objects_mastodon() {
     test_json::<Person>("assets/mastodon/objects/person.json").unwrap();
     test_json::<Note>("assets/mastodon/objects/note.json").unwrap();
+    test_json::<Note>("assets/mastodon/objects/note_2.json").unwrap();
     test_json::<Page>("assets/mastodon/objects/page.json").unwrap();
   }
 
@@ -183,4 +257,14 @@ mod tests {
     test_json::<Page>("assets/mobilizon/objects/event.json").unwrap();
     test_json::<Person>("assets/mobilizon/objects/person.json").unwrap();
   }
+
+  #[test]
+  fn test_parse_object_plume() {
+    test_json::<Page>("assets/plume/objects/article.json").unwrap();
+  }
+
+  #[test]
+  fn test_parse_object_writefreely() {
+    test_json::<Page>("assets/writefreely/objects/article.json").unwrap();
+  }
 }
@@ -3,7 +3,12 @@ use crate::{
   fetcher::post_or_comment::PostOrComment,
   mentions::MentionOrValue,
   objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson, post::ApubPost},
-  protocol::{objects::LanguageTag, InCommunity, Source},
+  protocol::{
+    collections::collection_page::CollectionPageRef,
+    objects::LanguageTag,
+    InCommunity,
+    Source,
+  },
 };
 use activitypub_federation::{
   config::Data,
@@ -51,6 +56,9 @@ pub struct Note {
   pub(crate) distinguished: Option<bool>,
   pub(crate) language: Option<LanguageTag>,
   pub(crate) audience: Option<ObjectId<ApubCommunity>>,
+  /// Only read from other software, used to backfill existing comments
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) replies: Option<CollectionPageRef>,
 }
 
 impl Note {
@@ -1,15 +1,22 @@
 use crate::{
   activities::verify_community_matches,
   fetcher::user_or_community::{PersonOrGroupType, UserOrCommunity},
+  mentions::MentionOrValue,
   objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
-  protocol::{objects::LanguageTag, ImageObject, InCommunity, Source},
+  protocol::{
+    collections::collection_page::CollectionPageRef,
+    objects::LanguageTag,
+    ImageObject,
+    InCommunity,
+    Source,
+  },
 };
 use activitypub_federation::{
   config::Data,
   fetch::object_id::ObjectId,
   kinds::{
     link::LinkType,
-    object::{DocumentType, ImageType},
+    object::{AudioType, DocumentType, ImageType},
   },
   protocol::{
     helpers::{deserialize_one_or_many, deserialize_skip_error},
@@ -26,6 +33,8 @@ use serde::{de::Error, Deserialize, Deserializer, Serialize};
 use serde_with::skip_serializing_none;
 use url::Url;
 
+const MAX_MEDIA_TYPE_LENGTH: usize = 255;
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub enum PageType {
   Page,
@@ -33,6 +42,7 @@ pub enum PageType {
   Note,
   Video,
   Event,
+  Audio,
 }
 
 #[skip_serializing_none]
@@ -67,6 +77,53 @@ pub struct Page {
   pub(crate) updated: Option<DateTime<FixedOffset>>,
   pub(crate) language: Option<LanguageTag>,
   pub(crate) audience: Option<ObjectId<ApubCommunity>>,
+  #[serde(default)]
+  pub(crate) tag: Vec<MentionOrValue>,
+  /// Only read from other software, used to backfill existing comments
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) replies: Option<CollectionPageRef>,
+  /// Only read from other software. Funkwhale links the files of `Audio` objects here instead of
+  /// attaching them, while Plume and WriteFreely use it for the canonical url of an `Article`.
+  #[serde(
+    deserialize_with = "deserialize_page_url",
+    default,
+    skip_serializing_if = "Vec::is_empty"
+  )]
+  pub(crate) url: Vec<PageUrl>,
+  /// Only read from other software
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) duration: Option<AudioDuration>,
+}
+
+/// Durations are usually in ISO 8601 format, but some software sends the number of seconds
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(crate) enum AudioDuration {
+  Iso8601(String),
+  Seconds(f64),
+}
+
+impl AudioDuration {
+  fn seconds(&self) -> Option<i32> {
+    match self {
+      AudioDuration::Iso8601(d) => parse_duration(d),
+      AudioDuration::Seconds(s) => Some(s.round() as i32),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MediaLink {
+  pub(crate) href: Url,
+  pub(crate) media_type: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub(crate) enum PageUrl {
+  Link(MediaLink),
+  Url(Url),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -90,6 +147,21 @@ pub(crate) struct Document {
   #[serde(rename = "type")]
   pub(crate) kind: DocumentType,
   pub(crate) url: Url,
+  /// Mastodon sends audio files as documents, with an audio media type
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) media_type: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Audio {
+  #[serde(rename = "type")]
+  pub(crate) kind: AudioType,
+  pub(crate) url: Url,
+  pub(crate) media_type: Option<String>,
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) duration: Option<AudioDuration>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -98,6 +170,16 @@ pub(crate) enum Attachment {
   Link(Link),
   Image(Image),
   Document(Document),
+  Audio(Audio),
+}
+
+/// The audio file of an audio post
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PageAudio {
+  pub(crate) url: Url,
+  pub(crate) media_type: String,
+  /// In seconds
+  pub(crate) duration: Option<i32>,
 }
 
 impl Attachment {
@@ -109,6 +191,7 @@ impl Attachment {
       Attachment::Image(i) => i.url,
       // sent by mobilizon
       Attachment::Document(d) => d.url,
+      Attachment::Audio(a) => a.url,
     }
   }
 }
@@ -118,6 +201,8 @@ impl Attachment {
 pub(crate) enum AttributedTo {
   Lemmy(ObjectId<ApubPerson>),
   Peertube([AttributedToPeertube; 2]),
+  /// Plume lists the author first, followed by the blog
+  Plume(Vec<Url>),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -154,9 +239,64 @@ impl Page {
     false
   }
 
+  /// The audio file of the post, from an audio attachment, or linked from an `Audio` object as
+  /// sent by Funkwhale. Missing or invalid media types are assumed to be mp3.
+  pub(crate) fn audio(&self) -> Option<PageAudio> {
+    let is_audio = |media_type: &Option<String>| {
+      media_type
+        .as_deref()
+        .is_some_and(|m| m.starts_with("audio/") && m.len() <= MAX_MEDIA_TYPE_LENGTH)
+    };
+    let (url, media_type, duration) = match self.attachment.first() {
+      Some(Attachment::Audio(a)) => (&a.url, &a.media_type, &a.duration),
+      Some(Attachment::Document(d)) if is_audio(&d.media_type) => {
+        (&d.url, &d.media_type, &self.duration)
+      }
+      None if self.kind == PageType::Audio => {
+        let link = self
+          .url
+          .iter()
+          .filter_map(|u| match u {
+            PageUrl::Link(l) => Some(l),
+            PageUrl::Url(_) => None,
+          })
+          .find(|l| is_audio(&l.media_type))?;
+        (&link.href, &link.media_type, &self.duration)
+      }
+      _ => return None,
+    };
+    Some(PageAudio {
+      url: url.clone(),
+      media_type: media_type
+        .clone()
+        .filter(|_| is_audio(media_type))
+        .unwrap_or_else(|| "audio/mpeg".to_string()),
+      duration: duration.as_ref().and_then(AudioDuration::seconds),
+    })
+  }
+
+  /// The canonical url of an `Article`, as sent by Plume and WriteFreely. It points to the html
+  /// version of the article, so it is used as post link.
+  pub(crate) fn canonical_url(&self) -> Option<Url> {
+    if self.kind != PageType::Article {
+      return None;
+    }
+    self.url.iter().find_map(|u| match u {
+      PageUrl::Url(u) => Some(u.clone()),
+      PageUrl::Link(l) if l.media_type.as_deref().unwrap_or("text/html") == "text/html" => {
+        Some(l.href.clone())
+      }
+      PageUrl::Link(_) => None,
+    })
+  }
+
   pub(crate) fn creator(&self) -> Result<ObjectId<ApubPerson>, LemmyError> {
     match &self.attributed_to {
       AttributedTo::Lemmy(l) => Ok(l.clone()),
+      AttributedTo::Plume(p) => p
+        .first()
+        .map(|a| ObjectId::<ApubPerson>::from(a.clone()))
+        .ok_or_else(|| LemmyErrorType::PageDoesNotSpecifyCreator.into()),
       AttributedTo::Peertube(p) => p
         .iter()
         .find(|a| a.kind == PersonOrGroupType::Person)
@@ -173,6 +313,36 @@ impl Attachment {
       r#type: Default::default(),
     })
   }
+
+  pub(crate) fn new_audio(url: DbUrl, media_type: String, duration: Option<i32>) -> Attachment {
+    Attachment::Audio(Audio {
+      kind: Default::default(),
+      url: url.into(),
+      media_type: Some(media_type),
+      duration: duration.map(|d| AudioDuration::Iso8601(format!("PT{d}S"))),
+    })
+  }
+}
+
+/// Parses an ISO 8601 duration like `PT1H2M3S` into seconds. Days and longer units aren't used for
+/// audio, so they aren't supported.
+fn parse_duration(duration: &str) -> Option<i32> {
+  let mut rest = duration.strip_prefix("PT")?;
+  let mut seconds = 0.0;
+  while !rest.is_empty() {
+    let end = rest.find(|c: char| c.is_ascii_alphabetic())?;
+    let value: f64 = rest.get(..end)?.parse().ok()?;
+    let unit = match rest.get(end..=end)? {
+      "H" => 3600.0,
+      "M" => 60.0,
+      "S" => 1.0,
+      _ => return None,
+    };
+    seconds += value * unit;
+    rest = rest.get(end + 1..)?;
+  }
+  // Rounding avoids off by one errors for fractional durations
+  Some(seconds.round() as i32)
 }
 
 // Used for community outbox, so that it can be compatible with Pleroma/Mastodon.
@@ -200,17 +370,12 @@ impl InCommunity for Page {
   async fn community(&self, context: &Data<LemmyContext>) -> Result<ApubCommunity, LemmyError> {
     let community = match &self.attributed_to {
       AttributedTo::Lemmy(_) => {
-        let mut iter = self.to.iter().merge(self.cc.iter());
-        loop {
-          if let Some(cid) = iter.next() {
-            let cid = ObjectId::from(cid.clone());
-            if let Ok(c) = cid.dereference(context).await {
-              break c;
-            }
-          } else {
-            return Err(LemmyErrorType::NoCommunityFoundInCc)?;
-          }
-        }
+        community_from_urls(self.to.iter().merge(self.cc.iter()), context).await?
+      }
+      // Plume blogs are groups like Peertube channels, fall back to addressed communities
+      AttributedTo::Plume(p) => {
+        let addressed = p.iter().skip(1).chain(self.to.iter().merge(self.cc.iter()));
+        community_from_urls(addressed, context).await?
       }
       AttributedTo::Peertube(p) => {
         p.iter()
@@ -228,6 +393,29 @@ impl InCommunity for Page {
   }
 }
 
+/// Returns the first of the given urls which is a community.
+async fn community_from_urls(
+  urls: impl Iterator<Item = &Url> + Send,
+  context: &Data<LemmyContext>,
+) -> Result<ApubCommunity, LemmyError> {
+  for cid in urls {
+    let cid = ObjectId::<ApubCommunity>::from(cid.clone());
+    if let Ok(c) = cid.dereference(context).await {
+      return Ok(c);
+    }
+  }
+  Err(LemmyErrorType::NoCommunityFoundInCc)?
+}
+
+/// Accepts a single url or link as well as a list of them. Invalid values are ignored.
+fn deserialize_page_url<'de, D>(deserializer: D) -> Result<Vec<PageUrl>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let value = serde_json::Value::deserialize(deserializer)?;
+  Ok(deserialize_one_or_many(value).unwrap_or_default())
+}
+
 /// Only allows deserialization if the field is missing or null. If it is present, throws an error.
 pub fn deserialize_not_present<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
@@ -245,10 +433,61 @@ mod tests {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
-  use crate::protocol::{objects::page::Page, tests::test_parse_lemmy_item};
+  use crate::protocol::{
+    objects::page::{parse_duration, Page, PageAudio},
+    tests::{test_json, test_parse_lemmy_item},
+  };
+  use url::Url;
 
   #[test]
   fn test_not_parsing_note_as_page() {
     assert!(test_parse_lemmy_item::<Page>("assets/lemmy/objects/note.json").is_err());
   }
+
+  #[test]
+  fn test_parse_audio() {
+    let page = test_json::<Page>("assets/funkwhale/objects/audio.json").unwrap();
+    let expected = PageAudio {
+      url: Url::parse("https://tanukitunes.com/api/v1/listen/4b2e3f/?upload=8c1d&download=false")
+        .unwrap(),
+      media_type: "audio/mpeg".to_string(),
+      duration: Some(1843),
+    };
+    assert_eq!(Some(expected), page.inner().audio());
+
+    let page = test_parse_lemmy_item::<Page>("assets/lemmy/objects/page.json").unwrap();
+    assert!(page.audio().is_none());
+  }
+
+  #[test]
+  fn test_parse_article() {
+    let page = test_json::<Page>("assets/plume/objects/article.json").unwrap();
+    let page = page.inner();
+    assert_eq!(
+      Url::parse("https://fediverse.blog/@/alice/").unwrap(),
+      *page.creator().unwrap().inner()
+    );
+    assert_eq!(
+      Some(Url::parse("https://fediverse.blog/~/AliceWrites/how-plume-works/").unwrap()),
+      page.canonical_url()
+    );
+
+    let page = test_json::<Page>("assets/writefreely/objects/article.json").unwrap();
+    assert_eq!(
+      Some(Url::parse("https://write.as/bob/notes-on-federation").unwrap()),
+      page.inner().canonical_url()
+    );
+
+    let page = test_parse_lemmy_item::<Page>("assets/lemmy/objects/page.json").unwrap();
+    assert!(page.canonical_url().is_none());
+  }
+
+  #[test]
+  fn test_parse_duration() {
+    assert_eq!(Some(3723), parse_duration("PT1H2M3S"));
+    assert_eq!(Some(213), parse_duration("PT213S"));
+    assert_eq!(Some(201), parse_duration("PT3M20.6S"));
+    assert_eq!(None, parse_duration("P1D"));
+    assert_eq!(None, parse_duration("PT5X"));
+  }
 }
@@ -1,6 +1,6 @@
 use crate::{
   objects::person::ApubPerson,
-  protocol::{objects::Endpoints, ImageObject, Source},
+  protocol::{integrity_proof::Multikey, objects::Endpoints, ImageObject, Source},
 };
 use activitypub_federation::{
   fetch::object_id::ObjectId,
@@ -31,6 +31,9 @@ pub struct Person {
   /// mandatory field in activitypub, lemmy currently serves an empty outbox
   pub(crate) outbox: Url,
   pub(crate) public_key: PublicKey,
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) assertion_method: Vec<Multikey>,
 
   /// displayname
   pub(crate) name: Option<String>,
@@ -43,6 +46,10 @@ pub struct Person {
   pub(crate) image: Option<ImageObject>,
   pub(crate) matrix_user_id: Option<String>,
   pub(crate) endpoints: Option<Endpoints>,
+  /// Former ids of the user, from before they changed their username
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) also_known_as: Vec<Url>,
   pub(crate) published: Option<DateTime<FixedOffset>>,
   pub(crate) updated: Option<DateTime<FixedOffset>>,
 }
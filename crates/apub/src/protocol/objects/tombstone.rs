@@ -1,5 +1,7 @@
 use crate::protocol::Id;
 use activitypub_federation::kinds::object::TombstoneType;
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use lemmy_utils::utils::time::convert_datetime;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use url::Url;
@@ -11,6 +13,9 @@ pub struct Tombstone {
   pub(crate) id: Url,
   #[serde(rename = "type")]
   pub(crate) kind: TombstoneType,
+  /// The type of the object before it was deleted, eg `Page` or `Note`
+  pub(crate) former_type: Option<String>,
+  pub(crate) deleted: Option<DateTime<FixedOffset>>,
 }
 
 impl Tombstone {
@@ -18,6 +23,17 @@ impl Tombstone {
     Tombstone {
       id,
       kind: TombstoneType::Tombstone,
+      former_type: None,
+      deleted: None,
+    }
+  }
+
+  /// Replaces a local object which was deleted or removed, when it is fetched
+  pub(crate) fn for_deleted(id: Url, former_type: &str, deleted: NaiveDateTime) -> Tombstone {
+    Tombstone {
+      former_type: Some(former_type.to_string()),
+      deleted: Some(convert_datetime(deleted)),
+      ..Tombstone::new(id)
     }
   }
 }
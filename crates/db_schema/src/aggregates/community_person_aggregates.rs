@@ -0,0 +1,98 @@
+use crate::{
+  aggregates::structs::CommunityPersonAggregates,
+  newtypes::{CommunityId, PersonId},
+  schema::community_person_aggregates,
+  utils::{get_conn, DbPool},
+};
+use diesel::{result::Error, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl CommunityPersonAggregates {
+  /// The scores of a person in a community, if they were calculated yet.
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    person_id: PersonId,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_person_aggregates::table
+      .filter(community_person_aggregates::community_id.eq(community_id))
+      .filter(community_person_aggregates::person_id.eq(person_id))
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    aggregates::structs::CommunityPersonAggregates,
+    schema::community_person_aggregates,
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, get_conn},
+  };
+  use diesel::{dsl::insert_into, ExpressionMethods};
+  use diesel_async::RunQueryDsl;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_read() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("community_agg_person".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("community_agg_community".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &community_form).await.unwrap();
+
+    let read = CommunityPersonAggregates::read(pool, inserted_community.id, inserted_person.id)
+      .await
+      .unwrap();
+    assert!(read.is_none());
+
+    insert_into(community_person_aggregates::table)
+      .values((
+        community_person_aggregates::community_id.eq(inserted_community.id),
+        community_person_aggregates::person_id.eq(inserted_person.id),
+        community_person_aggregates::post_score.eq(5),
+        community_person_aggregates::comment_score.eq(-2),
+      ))
+      .execute(&mut get_conn(pool).await.unwrap())
+      .await
+      .unwrap();
+    let read = CommunityPersonAggregates::read(pool, inserted_community.id, inserted_person.id)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(5, read.post_score);
+    assert_eq!(-2, read.comment_score);
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -3,6 +3,8 @@ pub mod comment_aggregates;
 #[cfg(feature = "full")]
 pub mod community_aggregates;
 #[cfg(feature = "full")]
+pub mod community_person_aggregates;
+#[cfg(feature = "full")]
 pub mod person_aggregates;
 #[cfg(feature = "full")]
 pub mod person_post_aggregates;
@@ -10,4 +12,6 @@ pub mod person_post_aggregates;
 pub mod post_aggregates;
 #[cfg(feature = "full")]
 pub mod site_aggregates;
+#[cfg(feature = "full")]
+pub mod site_stats_history;
 pub mod structs;
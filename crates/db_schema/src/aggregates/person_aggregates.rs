@@ -1,10 +1,10 @@
 use crate::{
   aggregates::structs::PersonAggregates,
-  newtypes::PersonId,
-  schema::person_aggregates,
+  newtypes::{CommunityId, PersonId},
+  schema::{comment, comment_aggregates, person_aggregates, post, post_aggregates},
   utils::{get_conn, DbPool},
 };
-use diesel::{result::Error, ExpressionMethods, QueryDsl};
+use diesel::{dsl::sql, result::Error, sql_types::BigInt, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 
 impl PersonAggregates {
@@ -15,6 +15,34 @@ impl PersonAggregates {
       .first::<Self>(conn)
       .await
   }
+
+  /// The combined score of the posts and comments which the person wrote in a community.
+  pub async fn community_score(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    community_id: CommunityId,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let post_score = post_aggregates::table
+      .inner_join(post::table)
+      .filter(post::creator_id.eq(person_id))
+      .filter(post::community_id.eq(community_id))
+      .select(sql::<BigInt>(
+        "coalesce(sum(post_aggregates.score), 0)::bigint",
+      ))
+      .first::<i64>(conn)
+      .await?;
+    let comment_score = comment_aggregates::table
+      .inner_join(comment::table.inner_join(post::table))
+      .filter(comment::creator_id.eq(person_id))
+      .filter(post::community_id.eq(community_id))
+      .select(sql::<BigInt>(
+        "coalesce(sum(comment_aggregates.score), 0)::bigint",
+      ))
+      .first::<i64>(conn)
+      .await?;
+    Ok(post_score + comment_score)
+  }
 }
 
 #[cfg(test)]
@@ -132,6 +160,11 @@ mod tests {
     assert_eq!(1, person_aggregates_before_delete.post_score);
     assert_eq!(2, person_aggregates_before_delete.comment_count);
     assert_eq!(2, person_aggregates_before_delete.comment_score);
+    let community_score =
+      PersonAggregates::community_score(pool, inserted_person.id, inserted_community.id)
+        .await
+        .unwrap();
+    assert_eq!(3, community_score);
 
     // Remove a post like
     PostLike::remove(pool, inserted_person.id, inserted_post.id)
@@ -2,23 +2,36 @@ use crate::{
   aggregates::structs::{PersonPostAggregates, PersonPostAggregatesForm},
   diesel::BoolExpressionMethods,
   newtypes::{PersonId, PostId},
-  schema::person_post_aggregates::dsl::{person_id, person_post_aggregates, post_id},
-  utils::{get_conn, DbPool},
+  schema::person_post_aggregates::dsl::{
+    comments_seen,
+    person_id,
+    person_post_aggregates,
+    post_id,
+    previous_comments_seen,
+  },
+  utils::{get_conn, naive_now, DbPool},
 };
 use diesel::{insert_into, result::Error, ExpressionMethods, QueryDsl};
 use diesel_async::RunQueryDsl;
 
 impl PersonPostAggregates {
+  /// Records that the person opened the post, together with the number of comments they have
+  /// read. The time of their visit before is kept in previous_comments_seen.
   pub async fn upsert(
     pool: &mut DbPool<'_>,
     form: &PersonPostAggregatesForm,
   ) -> Result<Self, Error> {
     let conn = &mut get_conn(pool).await?;
+    let now = naive_now();
     insert_into(person_post_aggregates)
-      .values(form)
+      .values((form, comments_seen.eq(now)))
       .on_conflict((person_id, post_id))
       .do_update()
-      .set(form)
+      .set((
+        form,
+        previous_comments_seen.eq(comments_seen),
+        comments_seen.eq(now),
+      ))
       .get_result::<Self>(conn)
       .await
   }
@@ -34,3 +47,74 @@ impl PersonPostAggregates {
       .await
   }
 }
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    aggregates::structs::{PersonPostAggregates, PersonPostAggregatesForm},
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_comments_seen() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("comments_seen_person".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("comments_seen_community".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &community_form).await.unwrap();
+    let post_form = PostInsertForm::builder()
+      .name("A test post".into())
+      .creator_id(inserted_person.id)
+      .community_id(inserted_community.id)
+      .build();
+    let inserted_post = Post::create(pool, &post_form).await.unwrap();
+
+    let form = PersonPostAggregatesForm {
+      person_id: inserted_person.id,
+      post_id: inserted_post.id,
+      ..Default::default()
+    };
+    // No previous visit the first time
+    let first_visit = PersonPostAggregates::upsert(pool, &form).await.unwrap();
+    assert!(first_visit.previous_comments_seen.is_none());
+    assert!(first_visit.comments_seen.is_some());
+
+    let second_visit = PersonPostAggregates::upsert(pool, &form).await.unwrap();
+    assert_eq!(
+      first_visit.comments_seen,
+      second_visit.previous_comments_seen
+    );
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -0,0 +1,112 @@
+use crate::{
+  aggregates::structs::SiteStatsHistory,
+  schema::site_stats_history,
+  utils::{get_conn, DbPool},
+};
+use chrono::NaiveDate;
+use diesel::{result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+/// The maximum number of daily snapshots returned at once, about a year
+const SITE_STATS_HISTORY_LIMIT: i64 = 366;
+
+impl SiteStatsHistory {
+  /// Lists the snapshots between the given days, oldest first. Without a start day, only the most
+  /// recent [SITE_STATS_HISTORY_LIMIT] snapshots are returned.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = site_stats_history::table.into_boxed();
+    if let Some(since) = since {
+      query = query.filter(site_stats_history::day.ge(since));
+    }
+    if let Some(until) = until {
+      query = query.filter(site_stats_history::day.le(until));
+    }
+    let mut history = query
+      .order_by(site_stats_history::day.desc())
+      .limit(SITE_STATS_HISTORY_LIMIT)
+      .load::<Self>(conn)
+      .await?;
+    history.reverse();
+    Ok(history)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    aggregates::structs::SiteStatsHistory,
+    schema::site_stats_history,
+    source::{
+      instance::Instance,
+      site::{Site, SiteInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, get_conn},
+  };
+  use chrono::NaiveDate;
+  use diesel::{dsl::insert_into, ExpressionMethods};
+  use diesel_async::RunQueryDsl;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let site_form = SiteInsertForm::builder()
+      .name("test_site".into())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_site = Site::create(pool, &site_form).await.unwrap();
+
+    let days: Vec<NaiveDate> = (1..=3)
+      .map(|d| NaiveDate::from_ymd_opt(2023, 8, d).unwrap())
+      .collect();
+    for (i, day) in days.iter().enumerate() {
+      insert_into(site_stats_history::table)
+        .values((
+          site_stats_history::site_id.eq(inserted_site.id),
+          site_stats_history::day.eq(day),
+          site_stats_history::users.eq(i as i64 + 1),
+        ))
+        .execute(&mut get_conn(pool).await.unwrap())
+        .await
+        .unwrap();
+    }
+
+    let history = SiteStatsHistory::list(pool, None, None).await.unwrap();
+    assert_eq!(3, history.len());
+    assert_eq!(days[0], history[0].day);
+    assert_eq!(3, history[2].users);
+
+    let history = SiteStatsHistory::list(pool, Some(days[1]), None)
+      .await
+      .unwrap();
+    assert_eq!(2, history.len());
+    assert_eq!(days[1], history[0].day);
+
+    let history = SiteStatsHistory::list(pool, Some(days[0]), Some(days[1]))
+      .await
+      .unwrap();
+    assert_eq!(2, history.len());
+    assert_eq!(2, history[1].users);
+
+    // Snapshots are removed together with their site
+    Site::delete(pool, inserted_site.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+    let history = SiteStatsHistory::list(pool, None, None).await.unwrap();
+    assert!(history.is_empty());
+  }
+}
@@ -3,10 +3,12 @@ use crate::newtypes::{CommentId, CommunityId, PersonId, PostId, SiteId};
 use crate::schema::{
   comment_aggregates,
   community_aggregates,
+  community_person_aggregates,
   person_aggregates,
   person_post_aggregates,
   post_aggregates,
   site_aggregates,
+  site_stats_history,
 };
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "full")]
@@ -56,6 +58,31 @@ pub struct CommunityAggregates {
   /// The number of users with any activity in the last year.
   pub users_active_half_year: i64,
   pub hot_rank: i32,
+  /// The number of subscribers at the last trending update.
+  pub subscribers_day_ago: i64,
+  /// How fast the community is growing and getting more active, relative to its size.
+  pub trending_rank: i32,
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Associations, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = community_person_aggregates))]
+#[cfg_attr(
+  feature = "full",
+  diesel(belongs_to(crate::source::community::Community))
+)]
+#[cfg_attr(feature = "full", ts(export))]
+/// Aggregate data for a person within a community, recalculated hourly.
+pub struct CommunityPersonAggregates {
+  pub id: i32,
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  /// The combined score of their posts in the community.
+  pub post_score: i64,
+  /// The combined score of their comments in the community.
+  pub comment_score: i64,
+  /// When the scores were last recalculated.
+  pub updated: chrono::NaiveDateTime,
 }
 
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone, Default)]
@@ -116,6 +143,10 @@ pub struct PersonPostAggregates {
   /// This is updated to the current post comment count every time they view a post.
   pub read_comments: i64,
   pub published: chrono::NaiveDateTime,
+  /// When they last viewed the post.
+  pub comments_seen: Option<chrono::NaiveDateTime>,
+  /// When they viewed the post before that.
+  pub previous_comments_seen: Option<chrono::NaiveDateTime>,
 }
 
 #[derive(Clone, Default)]
@@ -150,3 +181,24 @@ pub struct SiteAggregates {
   /// The number of users with any activity in the last half year.
   pub users_active_half_year: i64,
 }
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Associations, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = site_stats_history))]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::site::Site)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A daily snapshot of the aggregate data for a site.
+pub struct SiteStatsHistory {
+  pub id: i32,
+  pub site_id: SiteId,
+  /// The day on which the snapshot was taken.
+  pub day: chrono::NaiveDate,
+  pub users: i64,
+  pub posts: i64,
+  pub comments: i64,
+  pub communities: i64,
+  pub users_active_day: i64,
+  pub users_active_week: i64,
+  pub users_active_month: i64,
+  pub users_active_half_year: i64,
+}
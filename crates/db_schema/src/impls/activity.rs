@@ -4,8 +4,9 @@ use crate::{
   source::activity::{ReceivedActivity, SentActivity, SentActivityForm},
   utils::{get_conn, DbPool},
 };
+use chrono::NaiveDateTime;
 use diesel::{
-  dsl::insert_into,
+  dsl::{count_star, insert_into},
   result::{DatabaseErrorKind, Error, Error::DatabaseError},
   ExpressionMethods,
   QueryDsl,
@@ -30,6 +31,30 @@ impl SentActivity {
       .first::<Self>(conn)
       .await
   }
+
+  /// Reads all stored activities with the given ids
+  pub async fn read_from_apub_ids(
+    pool: &mut DbPool<'_>,
+    object_ids: &[DbUrl],
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::sent_activity::dsl::{ap_id, sent_activity};
+    let conn = &mut get_conn(pool).await?;
+    sent_activity
+      .filter(ap_id.eq_any(object_ids))
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Counts the activities which were sent after the given time
+  pub async fn count_since(pool: &mut DbPool<'_>, since: NaiveDateTime) -> Result<i64, Error> {
+    use crate::schema::sent_activity::dsl::{published, sent_activity};
+    let conn = &mut get_conn(pool).await?;
+    sent_activity
+      .filter(published.gt(since))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await
+  }
 }
 
 impl ReceivedActivity {
@@ -54,6 +79,17 @@ impl ReceivedActivity {
       ))
     }
   }
+
+  /// Counts the activities which were received after the given time
+  pub async fn count_since(pool: &mut DbPool<'_>, since: NaiveDateTime) -> Result<i64, Error> {
+    use crate::schema::received_activity::dsl::{published, received_activity};
+    let conn = &mut get_conn(pool).await?;
+    received_activity
+      .filter(published.gt(since))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await
+  }
 }
 
 #[cfg(test)]
@@ -62,7 +98,7 @@ mod tests {
   #![allow(clippy::indexing_slicing)]
 
   use super::*;
-  use crate::utils::build_db_pool_for_tests;
+  use crate::utils::{build_db_pool_for_tests, naive_now};
   use serde_json::json;
   use serial_test::serial;
   use url::Url;
@@ -82,6 +118,10 @@ mod tests {
 
     let res = ReceivedActivity::create(pool, &ap_id).await;
     assert!(res.is_err());
+
+    let since = naive_now() - chrono::Duration::hours(1);
+    let count = ReceivedActivity::count_since(pool, since).await.unwrap();
+    assert!(count >= 1);
   }
 
   #[tokio::test]
@@ -110,5 +150,15 @@ mod tests {
     assert_eq!(res.ap_id, ap_id);
     assert_eq!(res.data, data);
     assert_eq!(res.sensitive, sensitive);
+
+    let res = SentActivity::read_from_apub_ids(pool, &[ap_id.clone()])
+      .await
+      .unwrap();
+    assert_eq!(1, res.len());
+    assert_eq!(ap_id, res[0].ap_id);
+
+    let since = naive_now() - chrono::Duration::hours(1);
+    let count = SentActivity::count_since(pool, since).await.unwrap();
+    assert!(count >= 1);
   }
 }
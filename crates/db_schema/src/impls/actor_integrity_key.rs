@@ -0,0 +1,95 @@
+use crate::{
+  newtypes::DbUrl,
+  schema::actor_integrity_key,
+  source::actor_integrity_key::{ActorIntegrityKey, ActorIntegrityKeyForm},
+  utils::{get_conn, naive_now, DbPool},
+};
+use diesel::{insert_into, result::Error, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl ActorIntegrityKey {
+  pub async fn read(pool: &mut DbPool<'_>, actor_id: &DbUrl) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    actor_integrity_key::table
+      .filter(actor_integrity_key::actor_id.eq(actor_id))
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+
+  /// Stores the key of an actor, replacing any previous key.
+  pub async fn upsert(pool: &mut DbPool<'_>, form: &ActorIntegrityKeyForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(actor_integrity_key::table)
+      .values(form)
+      .on_conflict(actor_integrity_key::actor_id)
+      .do_update()
+      .set((form, actor_integrity_key::updated.eq(naive_now())))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Stores the key unless the actor has one already, and returns the stored key. Used for local
+  /// actors, whose key may be generated by concurrent requests.
+  pub async fn read_or_create(
+    pool: &mut DbPool<'_>,
+    form: &ActorIntegrityKeyForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(actor_integrity_key::table)
+      .values(form)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await?;
+    actor_integrity_key::table
+      .filter(actor_integrity_key::actor_id.eq(&form.actor_id))
+      .first::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::actor_integrity_key::{ActorIntegrityKey, ActorIntegrityKeyForm},
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_read_or_create() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let actor_id = Url::parse("https://integrity.example/u/alice")
+      .unwrap()
+      .into();
+
+    let form = ActorIntegrityKeyForm {
+      actor_id,
+      public_key: "z6MkFirst".to_string(),
+      private_key: Some("first".to_string()),
+    };
+    let created = ActorIntegrityKey::read_or_create(pool, &form)
+      .await
+      .unwrap();
+    let second_form = ActorIntegrityKeyForm {
+      public_key: "z6MkSecond".to_string(),
+      ..form.clone()
+    };
+    let existing = ActorIntegrityKey::read_or_create(pool, &second_form)
+      .await
+      .unwrap();
+    assert_eq!(created, existing);
+
+    let updated = ActorIntegrityKey::upsert(pool, &second_form).await.unwrap();
+    assert_eq!("z6MkSecond", updated.public_key);
+    assert!(updated.updated.is_some());
+    let read = ActorIntegrityKey::read(pool, &form.actor_id).await.unwrap();
+    assert_eq!(Some(updated), read);
+  }
+}
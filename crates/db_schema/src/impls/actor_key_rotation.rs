@@ -0,0 +1,102 @@
+use crate::{
+  newtypes::DbUrl,
+  schema::actor_key_rotation::dsl::{actor_id, actor_key_rotation, rotated_at},
+  source::actor_key_rotation::ActorKeyRotation,
+  utils::{get_conn, DbPool},
+};
+use chrono::NaiveDateTime;
+use diesel::{result::Error, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl ActorKeyRotation {
+  /// Returns the previous key of the actor, if it was rotated after the given time.
+  pub async fn read_since(
+    pool: &mut DbPool<'_>,
+    for_actor_id: &DbUrl,
+    since: NaiveDateTime,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    actor_key_rotation
+      .filter(actor_id.eq(for_actor_id))
+      .filter(rotated_at.gt(since))
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      actor_key_rotation::ActorKeyRotation,
+      instance::Instance,
+      person::{Person, PersonInsertForm, PersonUpdateForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_key_rotation_trigger() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("key_rotator".into())
+      .public_key("old_key".into())
+      .instance_id(inserted_instance.id)
+      .build();
+    let person = Person::create(pool, &person_form).await.unwrap();
+    let before_rotation = naive_now() - Duration::seconds(1);
+    assert!(
+      ActorKeyRotation::read_since(pool, &person.actor_id, before_rotation)
+        .await
+        .unwrap()
+        .is_none()
+    );
+
+    // Updating other fields doesn't count as rotation
+    let form = PersonUpdateForm::builder()
+      .display_name(Some(Some("Rotator".into())))
+      .build();
+    Person::update(pool, person.id, &form).await.unwrap();
+    assert!(
+      ActorKeyRotation::read_since(pool, &person.actor_id, before_rotation)
+        .await
+        .unwrap()
+        .is_none()
+    );
+
+    let form = PersonUpdateForm::builder()
+      .public_key(Some("new_key".into()))
+      .build();
+    Person::update(pool, person.id, &form).await.unwrap();
+    let rotation = ActorKeyRotation::read_since(pool, &person.actor_id, before_rotation)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!("old_key", rotation.previous_public_key);
+
+    // Rotations before the grace period are ignored
+    let after_rotation = naive_now() + Duration::seconds(1);
+    assert!(
+      ActorKeyRotation::read_since(pool, &person.actor_id, after_rotation)
+        .await
+        .unwrap()
+        .is_none()
+    );
+
+    Person::delete(pool, person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -25,7 +25,7 @@ use diesel::{
   ExpressionMethods,
   QueryDsl,
 };
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
 use lemmy_utils::error::{LemmyError, LemmyErrorType};
 use tokio::sync::OnceCell;
 
@@ -44,8 +44,7 @@ impl LocalUserLanguage {
     let conn = &mut get_conn(pool).await?;
 
     conn
-      .build_transaction()
-      .run(|conn| {
+      .transaction(|conn| {
         Box::pin(async move {
           let langs = local_user_language
             .filter(local_user_id.eq(for_local_user_id))
@@ -86,9 +85,9 @@ impl LocalUserLanguage {
       lang_ids.push(UNDETERMINED_ID);
     }
 
+    // Not a transaction builder, so that local users can be created within a transaction
     conn
-      .build_transaction()
-      .run(|conn| {
+      .transaction(|conn| {
         Box::pin(async move {
           use crate::schema::local_user_language::dsl::{local_user_id, local_user_language};
           // Clear the current user languages
@@ -0,0 +1,149 @@
+use crate::{
+  newtypes::AnnouncementId,
+  schema::announcement::dsl::{announcement, end_time, start_time},
+  source::announcement::{Announcement, AnnouncementInsertForm, AnnouncementUpdateForm},
+  traits::Crud,
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+use diesel::{
+  dsl::{insert_into, now},
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for Announcement {
+  type InsertForm = AnnouncementInsertForm;
+  type UpdateForm = AnnouncementUpdateForm;
+  type IdType = AnnouncementId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(announcement)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    announcement_id: AnnouncementId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(announcement.find(announcement_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl Announcement {
+  /// All announcements, including past and scheduled ones, newest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    announcement
+      .order(start_time.desc())
+      .limit(limit)
+      .offset(offset)
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  /// The announcements which should be shown right now.
+  pub async fn list_active(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    announcement
+      .filter(start_time.le(now))
+      .filter(end_time.is_null().or(end_time.gt(now)))
+      .order(start_time.desc())
+      .get_results::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      announcement::{Announcement, AnnouncementInsertForm, AnnouncementUpdateForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+    AnnouncementSeverity,
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_announcement() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("announcement_admin".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+
+    let form = |message: &str, start, end| AnnouncementInsertForm {
+      creator_id: inserted_person.id,
+      message: message.to_string(),
+      severity: AnnouncementSeverity::Warning,
+      start_time: start,
+      end_time: end,
+    };
+    let hour = Duration::hours(1);
+    let current = Announcement::create(pool, &form("current", naive_now() - hour, None))
+      .await
+      .unwrap();
+    let scheduled = Announcement::create(pool, &form("scheduled", naive_now() + hour, None))
+      .await
+      .unwrap();
+    let past = form("past", naive_now() - hour * 2, Some(naive_now() - hour));
+    let past = Announcement::create(pool, &past).await.unwrap();
+
+    let active = Announcement::list_active(pool).await.unwrap();
+    assert_eq!(vec![current.clone()], active);
+    let all = Announcement::list(pool, None, None).await.unwrap();
+    assert_eq!(vec![scheduled.clone(), current.clone(), past.clone()], all);
+
+    // Ending the current announcement hides it
+    let update_form = AnnouncementUpdateForm {
+      message: "ended".to_string(),
+      severity: AnnouncementSeverity::Info,
+      start_time: current.start_time,
+      end_time: Some(Some(naive_now() - Duration::minutes(1))),
+      updated: Some(naive_now()),
+    };
+    let updated = Announcement::update(pool, current.id, &update_form)
+      .await
+      .unwrap();
+    assert_eq!("ended", updated.message);
+    assert!(Announcement::list_active(pool).await.unwrap().is_empty());
+
+    let num_deleted = Announcement::delete(pool, scheduled.id).await.unwrap();
+    assert_eq!(1, num_deleted);
+
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
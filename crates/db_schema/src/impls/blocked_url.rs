@@ -0,0 +1,106 @@
+use crate::{
+  newtypes::BlockedUrlId,
+  schema::blocked_url::dsl::{blocked_url, url},
+  source::blocked_url::{BlockedUrl, BlockedUrlForm},
+  traits::Crud,
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+use diesel::{insert_into, result::Error, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for BlockedUrl {
+  type InsertForm = BlockedUrlForm;
+  type UpdateForm = BlockedUrlForm;
+  type IdType = BlockedUrlId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(blocked_url)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    blocked_url_id: BlockedUrlId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(blocked_url.find(blocked_url_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl BlockedUrl {
+  pub async fn get_all(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    blocked_url.get_results::<Self>(conn).await
+  }
+
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    blocked_url
+      .order(url)
+      .limit(limit)
+      .offset(offset)
+      .get_results::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::blocked_url::{BlockedUrl, BlockedUrlForm},
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crud() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let form = BlockedUrlForm {
+      url: "spam.example".to_string(),
+      updated: None,
+    };
+    let inserted_blocked_url = BlockedUrl::create(pool, &form).await.unwrap();
+    let duplicate = BlockedUrl::create(pool, &form).await;
+
+    let update_form = BlockedUrlForm {
+      url: "malware.example/downloads".to_string(),
+      updated: Some(naive_now()),
+    };
+    let updated_blocked_url = BlockedUrl::update(pool, inserted_blocked_url.id, &update_form)
+      .await
+      .unwrap();
+
+    let listed = BlockedUrl::list(pool, None, None).await.unwrap();
+    let num_deleted = BlockedUrl::delete(pool, inserted_blocked_url.id)
+      .await
+      .unwrap();
+    let all_after_delete = BlockedUrl::get_all(pool).await.unwrap();
+
+    assert!(duplicate.is_err());
+    assert_eq!("malware.example/downloads", updated_blocked_url.url);
+    assert!(updated_blocked_url.updated.is_some());
+    assert_eq!(vec![updated_blocked_url], listed);
+    assert_eq!(1, num_deleted);
+    assert!(all_after_delete.is_empty());
+  }
+}
@@ -0,0 +1,191 @@
+use crate::{
+  newtypes::BlocklistSubscriptionId,
+  schema::blocklist_subscription,
+  source::{
+    blocklist_subscription::{BlocklistSubscription, BlocklistSubscriptionForm},
+    federation_blocklist::FederationBlockList,
+  },
+  traits::Crud,
+  utils::{get_conn, naive_now, DbPool},
+};
+use diesel::{insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+#[async_trait]
+impl Crud for BlocklistSubscription {
+  type InsertForm = BlocklistSubscriptionForm;
+  type UpdateForm = BlocklistSubscriptionForm;
+  type IdType = BlocklistSubscriptionId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(blocklist_subscription::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    subscription_id: BlocklistSubscriptionId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(blocklist_subscription::table.find(subscription_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Also removes the blocklist entries of the subscription. Pinned entries are kept as local
+  /// entries.
+  async fn delete(
+    pool: &mut DbPool<'_>,
+    subscription_id: BlocklistSubscriptionId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .transaction(|conn| {
+        Box::pin(async move {
+          FederationBlockList::clear_subscription(conn, subscription_id).await?;
+          diesel::delete(blocklist_subscription::table.find(subscription_id))
+            .execute(conn)
+            .await
+        })
+      })
+      .await
+  }
+}
+
+impl BlocklistSubscription {
+  pub async fn list(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    blocklist_subscription::table
+      .order_by(blocklist_subscription::url)
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  /// Records the outcome of a sync. The sync time is only updated if it succeeded.
+  pub async fn update_sync_status(
+    pool: &mut DbPool<'_>,
+    subscription_id: BlocklistSubscriptionId,
+    error: Option<String>,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let query = diesel::update(blocklist_subscription::table.find(subscription_id));
+    match error {
+      None => {
+        query
+          .set((
+            blocklist_subscription::last_synced_at.eq(naive_now()),
+            blocklist_subscription::last_error.eq(None::<String>),
+          ))
+          .get_result::<Self>(conn)
+          .await
+      }
+      Some(error) => {
+        query
+          .set(blocklist_subscription::last_error.eq(error))
+          .get_result::<Self>(conn)
+          .await
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      blocklist_subscription::{BlocklistEntry, BlocklistSubscription, BlocklistSubscriptionForm},
+      federation_blocklist::FederationBlockList,
+      instance::Instance,
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  fn entry(domain: &str, reason: Option<&str>) -> BlocklistEntry {
+    BlocklistEntry {
+      domain: domain.to_string(),
+      reason: reason.map(ToString::to_string),
+    }
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_sync_subscription() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let form = BlocklistSubscriptionForm {
+      url: "https://blocklist.example/domains.csv".to_string(),
+    };
+    let subscription = BlocklistSubscription::create(pool, &form).await.unwrap();
+    FederationBlockList::replace(pool, Some(vec!["local.example".to_string()]))
+      .await
+      .unwrap();
+
+    let entries = vec![
+      entry("spam.example", Some("spam")),
+      entry("pinned.example", None),
+      entry("local.example", Some("also listed")),
+    ];
+    FederationBlockList::sync_subscription(pool, subscription.id, entries)
+      .await
+      .unwrap();
+    let blocklist = FederationBlockList::list(pool, None, None).await.unwrap();
+    assert_eq!(3, blocklist.len());
+    let (spam, _) = blocklist
+      .iter()
+      .find(|(_, i)| i.domain == "spam.example")
+      .unwrap();
+    assert_eq!(Some(subscription.id), spam.subscription_id);
+    assert_eq!(Some("spam".to_string()), spam.reason);
+    // the local entry keeps its provenance
+    let (local, _) = blocklist
+      .iter()
+      .find(|(_, i)| i.domain == "local.example")
+      .unwrap();
+    assert_eq!(None, local.subscription_id);
+    let (pinned, _) = blocklist
+      .iter()
+      .find(|(_, i)| i.domain == "pinned.example")
+      .unwrap();
+    FederationBlockList::set_pinned(pool, pinned.instance_id, true)
+      .await
+      .unwrap();
+
+    // entries which disappear from the source are removed, unless they are pinned
+    let entries = vec![entry("spam.example", Some("spam and abuse"))];
+    FederationBlockList::sync_subscription(pool, subscription.id, entries)
+      .await
+      .unwrap();
+    let blocklist = FederationBlockList::list(pool, None, None).await.unwrap();
+    let domains: Vec<_> = blocklist.iter().map(|(_, i)| i.domain.as_str()).collect();
+    assert_eq!(
+      vec!["local.example", "pinned.example", "spam.example"],
+      domains
+    );
+    assert_eq!(Some("spam and abuse".to_string()), blocklist[2].0.reason);
+
+    // unsubscribing keeps pinned entries, as local entries
+    BlocklistSubscription::delete(pool, subscription.id)
+      .await
+      .unwrap();
+    let blocklist = FederationBlockList::list(pool, None, None).await.unwrap();
+    let domains: Vec<_> = blocklist.iter().map(|(_, i)| i.domain.as_str()).collect();
+    assert_eq!(vec!["local.example", "pinned.example"], domains);
+    assert_eq!(None, blocklist[1].0.subscription_id);
+
+    FederationBlockList::replace(pool, Some(vec![]))
+      .await
+      .unwrap();
+    Instance::delete_all(pool).await.unwrap();
+  }
+}
@@ -0,0 +1,232 @@
+use crate::{
+  newtypes::{CategoryId, CommunityId},
+  schema::{category, community_category},
+  source::category::{
+    Category,
+    CategoryInsertForm,
+    CategoryUpdateForm,
+    CommunityCategory,
+    CommunityCategoryForm,
+  },
+  traits::Crud,
+  utils::{functions::lower, get_conn, DbPool},
+};
+use diesel::{
+  dsl::{delete, insert_into},
+  result::Error,
+  ExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+
+#[async_trait]
+impl Crud for Category {
+  type InsertForm = CategoryInsertForm;
+  type UpdateForm = CategoryUpdateForm;
+  type IdType = CategoryId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(category::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    category_id: CategoryId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(category::table.find(category_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl Category {
+  pub async fn get_all(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    category::table
+      .order(category::name)
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  /// Finds the categories with the given names, ignoring case. Unknown names are skipped.
+  pub async fn read_by_names(pool: &mut DbPool<'_>, names: &[String]) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let names: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+    category::table
+      .filter(lower(category::name).eq_any(names))
+      .order(category::name)
+      .get_results::<Self>(conn)
+      .await
+  }
+}
+
+impl CommunityCategory {
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+  ) -> Result<Vec<Category>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_category::table
+      .inner_join(category::table)
+      .filter(community_category::community_id.eq(for_community_id))
+      .order(category::name)
+      .select(category::all_columns)
+      .get_results::<Category>(conn)
+      .await
+  }
+
+  /// Replaces the categories of a community.
+  pub async fn update(
+    pool: &mut DbPool<'_>,
+    mut category_ids: Vec<CategoryId>,
+    for_community_id: CommunityId,
+  ) -> Result<(), Error> {
+    category_ids.sort_by_key(|c| c.0);
+    category_ids.dedup();
+    let forms: Vec<_> = category_ids
+      .into_iter()
+      .map(|category_id| CommunityCategoryForm {
+        community_id: for_community_id,
+        category_id,
+      })
+      .collect();
+
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .transaction::<_, Error, _>(|conn| {
+        Box::pin(async move {
+          delete(
+            community_category::table.filter(community_category::community_id.eq(for_community_id)),
+          )
+          .execute(conn)
+          .await?;
+          insert_into(community_category::table)
+            .values(forms)
+            .execute(conn)
+            .await?;
+          Ok(())
+        })
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      category::{Category, CategoryInsertForm, CategoryUpdateForm, CommunityCategory},
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_categories() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let new_community = CommunityInsertForm::builder()
+      .name("categorized".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let science = Category::create(
+      pool,
+      &CategoryInsertForm {
+        name: "Science".to_string(),
+        description: None,
+      },
+    )
+    .await
+    .unwrap();
+    let games = Category::create(
+      pool,
+      &CategoryInsertForm {
+        name: "Games".to_string(),
+        description: Some("Video and board games".to_string()),
+      },
+    )
+    .await
+    .unwrap();
+    let duplicate = Category::create(
+      pool,
+      &CategoryInsertForm {
+        name: "Games".to_string(),
+        description: None,
+      },
+    )
+    .await;
+
+    let update_form = CategoryUpdateForm {
+      description: Some(None),
+      updated: Some(Some(naive_now())),
+      ..Default::default()
+    };
+    let games = Category::update(pool, games.id, &update_form)
+      .await
+      .unwrap();
+
+    CommunityCategory::update(
+      pool,
+      vec![science.id, games.id, games.id],
+      inserted_community.id,
+    )
+    .await
+    .unwrap();
+    let assigned = CommunityCategory::read(pool, inserted_community.id)
+      .await
+      .unwrap();
+
+    CommunityCategory::update(pool, vec![science.id], inserted_community.id)
+      .await
+      .unwrap();
+    let reassigned = CommunityCategory::read(pool, inserted_community.id)
+      .await
+      .unwrap();
+
+    let by_name = Category::read_by_names(pool, &["science".to_string(), "music".to_string()])
+      .await
+      .unwrap();
+
+    // deleting a category also removes it from communities
+    Category::delete(pool, science.id).await.unwrap();
+    let after_delete = CommunityCategory::read(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Category::delete(pool, games.id).await.unwrap();
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert!(duplicate.is_err());
+    assert!(games.description.is_none());
+    assert!(games.updated.is_some());
+    assert_eq!(vec![games, science.clone()], assigned);
+    assert_eq!(vec![science.clone()], reassigned);
+    assert_eq!(vec![science], by_name);
+    assert!(after_delete.is_empty());
+  }
+}
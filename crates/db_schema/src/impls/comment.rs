@@ -1,20 +1,36 @@
 use crate::{
-  newtypes::{CommentId, DbUrl, PersonId},
-  schema::comment::dsl::{ap_id, comment, content, creator_id, deleted, path, removed, updated},
+  newtypes::{CommentId, CommunityId, DbUrl, PersonId},
+  schema::{
+    comment::dsl::{
+      ap_id,
+      comment,
+      content,
+      creator_id,
+      deleted,
+      path,
+      published,
+      removed,
+      updated,
+    },
+    person,
+    post,
+  },
   source::comment::{
     Comment,
     CommentInsertForm,
     CommentLike,
     CommentLikeForm,
+    CommentReaction,
+    CommentReactionForm,
     CommentSaved,
     CommentSavedForm,
     CommentUpdateForm,
   },
-  traits::{Crud, Likeable, Saveable},
+  traits::{Crud, Likeable, Reactable, Saveable},
   utils::{get_conn, naive_now, DbPool, DELETED_REPLACEMENT_TEXT},
 };
 use diesel::{
-  dsl::{insert_into, sql_query},
+  dsl::{count_star, insert_into, sql_query},
   result::Error,
   ExpressionMethods,
   QueryDsl,
@@ -52,6 +68,84 @@ impl Comment {
       .await
   }
 
+  /// When the person last commented in the community, used for slow mode.
+  pub async fn latest_published_for_creator(
+    pool: &mut DbPool<'_>,
+    for_creator_id: PersonId,
+    for_community_id: CommunityId,
+  ) -> Result<Option<chrono::NaiveDateTime>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    comment
+      .inner_join(post::table)
+      .filter(creator_id.eq(for_creator_id))
+      .filter(post::community_id.eq(for_community_id))
+      .select(diesel::dsl::max(published))
+      .first::<Option<chrono::NaiveDateTime>>(conn)
+      .await
+  }
+
+  /// All comments in the community including removed and deleted ones, together with the actor id
+  /// of their creator and the activitypub id of their post, for community archives. Comments are returned in batches ordered by id, so
+  /// that parents come before their replies. The next batch starts above the id of the last
+  /// comment.
+  pub async fn list_for_archive(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+    min_id: Option<CommentId>,
+    limit: i64,
+  ) -> Result<Vec<(Self, DbUrl, DbUrl)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = comment
+      .inner_join(post::table)
+      .inner_join(person::table)
+      .filter(post::community_id.eq(for_community_id))
+      .select((
+        crate::schema::comment::all_columns,
+        person::actor_id,
+        post::ap_id,
+      ))
+      .into_boxed();
+    if let Some(min_id) = min_id {
+      query = query.filter(crate::schema::comment::id.gt(min_id));
+    }
+    query
+      .order_by(crate::schema::comment::id.asc())
+      .limit(limit)
+      .load::<(Self, DbUrl, DbUrl)>(conn)
+      .await
+  }
+
+  /// The activitypub ids of the given comments.
+  pub async fn list_ap_ids(
+    pool: &mut DbPool<'_>,
+    comment_ids: &[CommentId],
+  ) -> Result<Vec<(CommentId, DbUrl)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    comment
+      .filter(crate::schema::comment::id.eq_any(comment_ids))
+      .select((crate::schema::comment::id, ap_id))
+      .load::<(CommentId, DbUrl)>(conn)
+      .await
+  }
+
+  /// How many comments the person wrote since the given time with the same content, used to
+  /// detect spam.
+  pub async fn count_duplicates_for_creator(
+    pool: &mut DbPool<'_>,
+    for_creator_id: PersonId,
+    for_content: &str,
+    since: chrono::NaiveDateTime,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    comment
+      .filter(creator_id.eq(for_creator_id))
+      .filter(content.eq(for_content))
+      .filter(published.gt(since))
+      .count()
+      .get_result::<i64>(conn)
+      .await
+  }
+
   pub async fn create(
     pool: &mut DbPool<'_>,
     comment_form: &CommentInsertForm,
@@ -149,6 +243,19 @@ where ca.comment_id = c.id"
       None
     }
   }
+
+  /// The ids of all comments above this one, starting with the top level comment
+  pub fn ancestor_ids(&self) -> Vec<CommentId> {
+    let mut ids: Vec<CommentId> = self
+      .path
+      .0
+      .split('.')
+      .skip(1) // The first is always 0
+      .filter_map(|p| p.parse::<i32>().map(CommentId).ok())
+      .collect();
+    ids.pop();
+    ids
+  }
 }
 
 #[async_trait]
@@ -207,6 +314,73 @@ impl Likeable for CommentLike {
   }
 }
 
+#[async_trait]
+impl Reactable for CommentReaction {
+  type Form = CommentReactionForm;
+  type IdType = CommentId;
+  async fn react(pool: &mut DbPool<'_>, form: &CommentReactionForm) -> Result<Self, Error> {
+    use crate::schema::comment_reaction::dsl::{comment_id, comment_reaction, emoji, person_id};
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_reaction)
+      .values(form)
+      .on_conflict((comment_id, person_id, emoji))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+  async fn unreact(
+    pool: &mut DbPool<'_>,
+    person_id_: PersonId,
+    comment_id_: CommentId,
+    emoji_: &str,
+  ) -> Result<usize, Error> {
+    use crate::schema::comment_reaction::dsl::{comment_id, comment_reaction, emoji, person_id};
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      comment_reaction
+        .filter(comment_id.eq(comment_id_))
+        .filter(person_id.eq(person_id_))
+        .filter(emoji.eq(emoji_)),
+    )
+    .execute(conn)
+    .await
+  }
+}
+
+impl CommentReaction {
+  /// Counts the reactions for each emoji on the given comments.
+  pub async fn counts(
+    pool: &mut DbPool<'_>,
+    comment_ids: &[CommentId],
+  ) -> Result<Vec<(CommentId, String, i64)>, Error> {
+    use crate::schema::comment_reaction::dsl::{comment_id, comment_reaction, emoji};
+    let conn = &mut get_conn(pool).await?;
+    comment_reaction
+      .filter(comment_id.eq_any(comment_ids))
+      .group_by((comment_id, emoji))
+      .select((comment_id, emoji, count_star()))
+      .load::<(CommentId, String, i64)>(conn)
+      .await
+  }
+
+  /// The emojis a person reacted with on the given comments.
+  pub async fn for_person(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+    comment_ids: &[CommentId],
+  ) -> Result<Vec<(CommentId, String)>, Error> {
+    use crate::schema::comment_reaction::dsl::{comment_id, comment_reaction, emoji, person_id};
+    let conn = &mut get_conn(pool).await?;
+    comment_reaction
+      .filter(comment_id.eq_any(comment_ids))
+      .filter(person_id.eq(for_person_id))
+      .select((comment_id, emoji))
+      .load::<(CommentId, String)>(conn)
+      .await
+  }
+}
+
 #[async_trait]
 impl Saveable for CommentSaved {
   type Form = CommentSavedForm;
@@ -1,6 +1,6 @@
 use crate::{
   newtypes::{CommunityId, DbUrl, PersonId},
-  schema::{community, instance},
+  schema::{community, community_transfer_request, instance},
   source::{
     actor_language::CommunityLanguage,
     community::{
@@ -12,11 +12,13 @@ use crate::{
       CommunityModeratorForm,
       CommunityPersonBan,
       CommunityPersonBanForm,
+      CommunityTransferRequest,
+      CommunityTransferRequestForm,
       CommunityUpdateForm,
     },
   },
   traits::{ApubActor, Bannable, Crud, Followable, Joinable},
-  utils::{functions::lower, get_conn, DbPool},
+  utils::{functions::lower, get_conn, naive_now, DbPool},
   SubscribedType,
 };
 use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
@@ -264,6 +266,41 @@ impl Followable for CommunityFollower {
   }
 }
 
+impl CommunityTransferRequest {
+  /// Creates the transfer request, replacing any earlier one for the same community.
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &CommunityTransferRequestForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_transfer_request::table)
+      .values(form)
+      .on_conflict(community_transfer_request::community_id)
+      .do_update()
+      .set((form, community_transfer_request::published.eq(naive_now())))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, for_community_id: CommunityId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_transfer_request::table
+      .find(for_community_id)
+      .first::<Self>(conn)
+      .await
+  }
+
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(community_transfer_request::table.find(for_community_id))
+      .execute(conn)
+      .await
+  }
+}
+
 #[async_trait]
 impl ApubActor for Community {
   async fn read_from_apub_id(
@@ -331,6 +368,8 @@ mod tests {
         CommunityModeratorForm,
         CommunityPersonBan,
         CommunityPersonBanForm,
+        CommunityTransferRequest,
+        CommunityTransferRequestForm,
         CommunityUpdateForm,
       },
       instance::Instance,
@@ -338,6 +377,7 @@ mod tests {
     },
     traits::{Bannable, Crud, Followable, Joinable},
     utils::build_db_pool_for_tests,
+    PostTypeRestriction,
   };
   use serial_test::serial;
 
@@ -393,6 +433,19 @@ mod tests {
       hidden: false,
       posting_restricted_to_mods: false,
       instance_id: inserted_instance.id,
+      enable_downvotes: true,
+      requires_follow_approval: false,
+      post_type_restriction: PostTypeRestriction::Any,
+      nsfw_only: false,
+      post_slow_mode_seconds: 0,
+      comment_slow_mode_seconds: 0,
+      allow_undetermined_language: true,
+      strike_ban_threshold: 0,
+      strike_ban_days: 7,
+      min_account_age_days: 0,
+      min_site_karma: 0,
+      min_community_karma: 0,
+      notify_mods_on_mention: false,
     };
 
     let community_follower_form = CommunityFollowerForm {
@@ -447,6 +500,22 @@ mod tests {
       expires: None,
     };
 
+    let community_transfer_request_form = CommunityTransferRequestForm {
+      community_id: inserted_community.id,
+      requester_id: inserted_person.id,
+      recipient_id: inserted_person.id,
+    };
+    let inserted_transfer_request =
+      CommunityTransferRequest::create(pool, &community_transfer_request_form)
+        .await
+        .unwrap();
+    let read_transfer_request = CommunityTransferRequest::read(pool, inserted_community.id)
+      .await
+      .unwrap();
+    let deleted_transfer_request = CommunityTransferRequest::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+
     let read_community = Community::read(pool, inserted_community.id).await.unwrap();
 
     let update_community_form = CommunityUpdateForm::builder()
@@ -476,6 +545,8 @@ mod tests {
     assert_eq!(expected_community, updated_community);
     assert_eq!(expected_community_follower, inserted_community_follower);
     assert_eq!(expected_community_moderator, inserted_community_moderator);
+    assert_eq!(inserted_transfer_request, read_transfer_request);
+    assert_eq!(1, deleted_transfer_request);
     assert_eq!(expected_community_person_ban, inserted_community_person_ban);
     assert_eq!(1, ignored_community);
     assert_eq!(1, left_community);
@@ -0,0 +1,189 @@
+use crate::{
+  newtypes::CommunityApplicationId,
+  schema::community_application::dsl::{
+    admin_id,
+    community_application,
+    community_id,
+    creator_id,
+    deny_reason,
+    description,
+    form,
+    id,
+    name,
+    nsfw,
+    published,
+    title,
+  },
+  source::community_application::{
+    CommunityApplication,
+    CommunityApplicationInsertForm,
+    CommunityApplicationUpdateForm,
+  },
+  utils::{get_conn, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use serde_json::Value;
+
+/// Everything except the creation request, which is only loaded for approving the application.
+#[allow(clippy::type_complexity)]
+pub const COLUMNS: (
+  id,
+  creator_id,
+  name,
+  title,
+  description,
+  nsfw,
+  admin_id,
+  deny_reason,
+  community_id,
+  published,
+) = (
+  id,
+  creator_id,
+  name,
+  title,
+  description,
+  nsfw,
+  admin_id,
+  deny_reason,
+  community_id,
+  published,
+);
+
+impl CommunityApplication {
+  /// Stores an application, with the community creation request which is used once it is
+  /// approved.
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    insert_form: &CommunityApplicationInsertForm,
+    request: Value,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_application)
+      .values((insert_form, form.eq(request)))
+      .returning(COLUMNS)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    application_id: CommunityApplicationId,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_application
+      .find(application_id)
+      .select(COLUMNS)
+      .first::<Self>(conn)
+      .await
+  }
+
+  /// The community creation request of the application.
+  pub async fn read_request(
+    pool: &mut DbPool<'_>,
+    application_id: CommunityApplicationId,
+  ) -> Result<Value, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_application
+      .find(application_id)
+      .select(form)
+      .first::<Value>(conn)
+      .await
+  }
+
+  pub async fn update(
+    pool: &mut DbPool<'_>,
+    application_id: CommunityApplicationId,
+    update_form: &CommunityApplicationUpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_application.find(application_id))
+      .set(update_form)
+      .returning(COLUMNS)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community_application::{
+        CommunityApplication,
+        CommunityApplicationInsertForm,
+        CommunityApplicationUpdateForm,
+      },
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serde_json::json;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_application() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("community_applicant".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let creator = Person::create(pool, &person_form).await.unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("community_application_admin".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let admin = Person::create(pool, &person_form).await.unwrap();
+
+    let form = CommunityApplicationInsertForm {
+      creator_id: creator.id,
+      name: "applied".to_string(),
+      title: "An applied community".to_string(),
+      description: None,
+      nsfw: false,
+    };
+    let request = json!({"name": "applied", "title": "An applied community"});
+    let created = CommunityApplication::create(pool, &form, request.clone())
+      .await
+      .unwrap();
+    assert!(created.admin_id.is_none());
+    assert_eq!(
+      request,
+      CommunityApplication::read_request(pool, created.id)
+        .await
+        .unwrap()
+    );
+
+    let form = CommunityApplicationUpdateForm {
+      admin_id: Some(admin.id),
+      deny_reason: Some("duplicate".to_string()),
+      community_id: None,
+    };
+    let denied = CommunityApplication::update(pool, created.id, &form)
+      .await
+      .unwrap();
+    assert_eq!(Some(admin.id), denied.admin_id);
+    assert_eq!(
+      denied,
+      CommunityApplication::read(pool, created.id).await.unwrap()
+    );
+
+    Person::delete(pool, creator.id).await.unwrap();
+    Person::delete(pool, admin.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -0,0 +1,207 @@
+use crate::{
+  newtypes::{CommunityExportId, CommunityId},
+  schema::{
+    community_export::dsl::{community_export, community_id, error, finished},
+    community_export_chunk,
+  },
+  source::community_export::{CommunityExport, CommunityExportForm},
+  utils::{get_conn, naive_now, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl CommunityExport {
+  pub async fn create(pool: &mut DbPool<'_>, form: &CommunityExportForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_export)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, export_id: CommunityExportId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_export.find(export_id).first::<Self>(conn).await
+  }
+
+  /// The export of the community which is currently being generated, if any.
+  pub async fn read_pending(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_export
+      .filter(community_id.eq(for_community_id))
+      .filter(finished.is_null())
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+
+  /// Appends the next piece of the archive. Chunks are numbered from 0.
+  pub async fn add_chunk(
+    pool: &mut DbPool<'_>,
+    export_id: CommunityExportId,
+    seq: i32,
+    data: &str,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_export_chunk::table)
+      .values((
+        community_export_chunk::export_id.eq(export_id),
+        community_export_chunk::seq.eq(seq),
+        community_export_chunk::data.eq(data),
+      ))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// A piece of the archive, or `None` after the last one.
+  pub async fn read_chunk(
+    pool: &mut DbPool<'_>,
+    export_id: CommunityExportId,
+    seq: i32,
+  ) -> Result<Option<String>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_export_chunk::table
+      .find((export_id, seq))
+      .select(community_export_chunk::data)
+      .first::<String>(conn)
+      .await
+      .optional()
+  }
+
+  pub async fn finish(pool: &mut DbPool<'_>, export_id: CommunityExportId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_export.find(export_id))
+      .set(finished.eq(naive_now()))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Marks the export as failed, and deletes the parts of the archive which were already written.
+  pub async fn fail(
+    pool: &mut DbPool<'_>,
+    export_id: CommunityExportId,
+    reason: String,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      community_export_chunk::table.filter(community_export_chunk::export_id.eq(export_id)),
+    )
+    .execute(conn)
+    .await?;
+    diesel::update(community_export.find(export_id))
+      .set((error.eq(reason), finished.eq(naive_now())))
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      community_export::{CommunityExport, CommunityExportForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_export() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("exporter".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let person = Person::create(pool, &person_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("exported".to_string())
+      .title("exported".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let community = Community::create(pool, &community_form).await.unwrap();
+
+    let form = CommunityExportForm {
+      community_id: community.id,
+      creator_id: person.id,
+    };
+    let export = CommunityExport::create(pool, &form).await.unwrap();
+    assert_eq!(None, export.finished);
+    let pending = CommunityExport::read_pending(pool, community.id)
+      .await
+      .unwrap();
+    assert_eq!(Some(export.clone()), pending);
+    assert_eq!(
+      None,
+      CommunityExport::read_chunk(pool, export.id, 0)
+        .await
+        .unwrap()
+    );
+
+    CommunityExport::add_chunk(pool, export.id, 0, r#"{"version":"#)
+      .await
+      .unwrap();
+    CommunityExport::add_chunk(pool, export.id, 1, "1}")
+      .await
+      .unwrap();
+    let finished = CommunityExport::finish(pool, export.id).await.unwrap();
+    assert!(finished.finished.is_some());
+    assert_eq!(None, finished.error);
+    assert_eq!(
+      finished,
+      CommunityExport::read(pool, export.id).await.unwrap()
+    );
+    let mut archive = String::new();
+    let mut seq = 0;
+    while let Some(chunk) = CommunityExport::read_chunk(pool, export.id, seq)
+      .await
+      .unwrap()
+    {
+      archive.push_str(&chunk);
+      seq += 1;
+    }
+    assert_eq!(r#"{"version":1}"#, archive);
+    assert!(CommunityExport::read_pending(pool, community.id)
+      .await
+      .unwrap()
+      .is_none());
+
+    let failed = CommunityExport::create(pool, &form).await.unwrap();
+    CommunityExport::add_chunk(pool, failed.id, 0, "{")
+      .await
+      .unwrap();
+    let failed = CommunityExport::fail(pool, failed.id, "broken".to_string())
+      .await
+      .unwrap();
+    assert_eq!(Some("broken".to_string()), failed.error);
+    assert!(failed.finished.is_some());
+    assert_eq!(
+      None,
+      CommunityExport::read_chunk(pool, failed.id, 0)
+        .await
+        .unwrap()
+    );
+
+    Community::delete(pool, community.id).await.unwrap();
+    Person::delete(pool, person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -0,0 +1,219 @@
+use crate::{
+  newtypes::{CommunityId, CommunityImportId, DbUrl},
+  schema::{
+    community_import::dsl::{
+      comments_imported,
+      community_id,
+      community_import,
+      error,
+      finished,
+      id,
+      posts_imported,
+      skipped,
+      updated,
+    },
+    community_import_object,
+  },
+  source::community_import::{CommunityImport, CommunityImportForm, CommunityImportObject},
+  utils::{get_conn, naive_now, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl CommunityImport {
+  pub async fn create(pool: &mut DbPool<'_>, form: &CommunityImportForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_import)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, import_id: CommunityImportId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_import.find(import_id).first::<Self>(conn).await
+  }
+
+  /// Adds to the counts of the import after a batch was processed.
+  pub async fn add_progress(
+    pool: &mut DbPool<'_>,
+    import_id: CommunityImportId,
+    posts: i32,
+    comments: i32,
+    skipped_objects: i32,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_import.find(import_id))
+      .set((
+        posts_imported.eq(posts_imported + posts),
+        comments_imported.eq(comments_imported + comments),
+        skipped.eq(skipped + skipped_objects),
+        updated.eq(naive_now()),
+      ))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// The latest import into the community, if it was created by an import.
+  pub async fn read_latest_for_community(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_import
+      .filter(community_id.eq(for_community_id))
+      .order_by(id.desc())
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+
+  /// Records a post or comment which was imported. If another import created it at the same time,
+  /// the existing record is kept.
+  pub async fn add_object(
+    pool: &mut DbPool<'_>,
+    object: &CommunityImportObject,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_import_object::table)
+      .values(object)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// The post or comment which was created for the given activitypub id of an archive, together
+  /// with the community it was imported into.
+  pub async fn read_object(
+    pool: &mut DbPool<'_>,
+    source_ap_id: &DbUrl,
+  ) -> Result<Option<(CommunityImportObject, CommunityId)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_import_object::table
+      .inner_join(community_import)
+      .filter(community_import_object::source_ap_id.eq(source_ap_id.as_str()))
+      .select((community_import_object::all_columns, community_id))
+      .first::<(CommunityImportObject, CommunityId)>(conn)
+      .await
+      .optional()
+  }
+
+  pub async fn finish(
+    pool: &mut DbPool<'_>,
+    import_id: CommunityImportId,
+    reason: Option<String>,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_import.find(import_id))
+      .set((error.eq(reason), finished.eq(naive_now())))
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    newtypes::DbUrl,
+    source::{
+      community::{Community, CommunityInsertForm},
+      community_import::{CommunityImport, CommunityImportForm, CommunityImportObject},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_import() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("importer".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let person = Person::create(pool, &person_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("imported".to_string())
+      .title("imported".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let community = Community::create(pool, &community_form).await.unwrap();
+
+    let form = CommunityImportForm {
+      community_id: community.id,
+      creator_id: person.id,
+    };
+    let import = CommunityImport::create(pool, &form).await.unwrap();
+    assert_eq!(0, import.posts_imported);
+    assert_eq!(None, import.finished);
+
+    CommunityImport::add_progress(pool, import.id, 2, 5, 1)
+      .await
+      .unwrap();
+    let import = CommunityImport::add_progress(pool, import.id, 1, 0, 0)
+      .await
+      .unwrap();
+    assert_eq!(3, import.posts_imported);
+    assert_eq!(5, import.comments_imported);
+    assert_eq!(1, import.skipped);
+    assert!(import.updated.is_some());
+
+    let ap_id: DbUrl = Url::parse("https://example.com/post/1").unwrap().into();
+    assert_eq!(
+      None,
+      CommunityImport::read_object(pool, &ap_id).await.unwrap()
+    );
+    let object = CommunityImportObject {
+      source_ap_id: ap_id.to_string(),
+      import_id: import.id,
+      post_id: None,
+      comment_id: None,
+    };
+    CommunityImport::add_object(pool, &object).await.unwrap();
+    let second_import = CommunityImport::create(pool, &form).await.unwrap();
+    let duplicate = CommunityImportObject {
+      import_id: second_import.id,
+      ..object.clone()
+    };
+    CommunityImport::add_object(pool, &duplicate).await.unwrap();
+    assert_eq!(
+      Some((object, community.id)),
+      CommunityImport::read_object(pool, &ap_id).await.unwrap()
+    );
+    assert_eq!(
+      Some(second_import.clone()),
+      CommunityImport::read_latest_for_community(pool, community.id)
+        .await
+        .unwrap()
+    );
+
+    let finished = CommunityImport::finish(pool, import.id, None)
+      .await
+      .unwrap();
+    assert!(finished.finished.is_some());
+    assert_eq!(None, finished.error);
+    assert_eq!(
+      finished,
+      CommunityImport::read(pool, import.id).await.unwrap()
+    );
+
+    Community::delete(pool, community.id).await.unwrap();
+    Person::delete(pool, person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
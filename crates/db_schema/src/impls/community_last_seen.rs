@@ -0,0 +1,158 @@
+use crate::{
+  newtypes::{CommunityId, PersonId},
+  schema::{community_follower, community_last_seen},
+  source::community_last_seen::{CommunityLastSeen, CommunityLastSeenForm, CommunityUnreadPosts},
+  utils::{functions::community_unread_posts, get_conn, naive_now, DbPool},
+};
+use diesel::{
+  dsl::insert_into,
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  JoinOnDsl,
+  NullableExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl CommunityLastSeen {
+  /// Marks all current posts of the community as seen by the person.
+  pub async fn mark_seen(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+    for_community_id: CommunityId,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let form = CommunityLastSeenForm {
+      person_id: for_person_id,
+      community_id: for_community_id,
+      last_seen: naive_now(),
+    };
+    insert_into(community_last_seen::table)
+      .values(&form)
+      .on_conflict((
+        community_last_seen::person_id,
+        community_last_seen::community_id,
+      ))
+      .do_update()
+      .set(&form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Unread post counts for all communities which the person follows and has visited before.
+  pub async fn unread_posts(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+  ) -> Result<Vec<CommunityUnreadPosts>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_last_seen::table
+      .inner_join(
+        community_follower::table.on(
+          community_follower::community_id
+            .eq(community_last_seen::community_id)
+            .and(community_follower::person_id.eq(community_last_seen::person_id)),
+        ),
+      )
+      .filter(community_last_seen::person_id.eq(for_person_id))
+      .select((
+        community_last_seen::community_id,
+        community_unread_posts(
+          community_last_seen::person_id.nullable(),
+          community_last_seen::community_id,
+        ),
+      ))
+      .load::<CommunityUnreadPosts>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityFollower, CommunityFollowerForm, CommunityInsertForm},
+      community_last_seen::{CommunityLastSeen, CommunityUnreadPosts},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::{Crud, Followable},
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_unread_posts() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("last_seen_person".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("last_seen_community".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &community_form).await.unwrap();
+    let post_form = PostInsertForm::builder()
+      .name("A test post".into())
+      .creator_id(inserted_person.id)
+      .community_id(inserted_community.id)
+      .build();
+
+    // Only visited communities which are followed are counted
+    CommunityLastSeen::mark_seen(pool, inserted_person.id, inserted_community.id)
+      .await
+      .unwrap();
+    Post::create(pool, &post_form).await.unwrap();
+    let unread = CommunityLastSeen::unread_posts(pool, inserted_person.id)
+      .await
+      .unwrap();
+    assert!(unread.is_empty());
+
+    let follower_form = CommunityFollowerForm {
+      community_id: inserted_community.id,
+      person_id: inserted_person.id,
+      pending: false,
+    };
+    CommunityFollower::follow(pool, &follower_form)
+      .await
+      .unwrap();
+    let unread = CommunityLastSeen::unread_posts(pool, inserted_person.id)
+      .await
+      .unwrap();
+    let expected = CommunityUnreadPosts {
+      community_id: inserted_community.id,
+      unread_posts: 1,
+    };
+    assert_eq!(vec![expected], unread);
+
+    // Visiting again resets the count
+    CommunityLastSeen::mark_seen(pool, inserted_person.id, inserted_community.id)
+      .await
+      .unwrap();
+    let unread = CommunityLastSeen::unread_posts(pool, inserted_person.id)
+      .await
+      .unwrap();
+    assert_eq!(0, unread[0].unread_posts);
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
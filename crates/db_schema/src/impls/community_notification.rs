@@ -0,0 +1,347 @@
+use crate::{
+  newtypes::{CommunityId, PersonId, PostNotificationId},
+  schema::{
+    community,
+    community_follower,
+    community_notification,
+    person,
+    post,
+    post_notification,
+  },
+  source::{
+    community::Community,
+    community_notification::{
+      CommunityNotification,
+      CommunityNotificationForm,
+      PostNotification,
+      PostNotificationInsertForm,
+    },
+    person::Person,
+    post::Post,
+  },
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+use chrono::{Duration, NaiveDateTime};
+use diesel::{
+  dsl::{exists, insert_into},
+  result::Error,
+  ExpressionMethods,
+  JoinOnDsl,
+  OptionalExtension,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl CommunityNotification {
+  /// Subscribes to new posts in a community, or changes the interval of an existing subscription
+  pub async fn subscribe(
+    pool: &mut DbPool<'_>,
+    form: &CommunityNotificationForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_notification::table)
+      .values(form)
+      .on_conflict((
+        community_notification::person_id,
+        community_notification::community_id,
+      ))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn unsubscribe(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    community_id: CommunityId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      community_notification::table
+        .filter(community_notification::person_id.eq(person_id))
+        .filter(community_notification::community_id.eq(community_id)),
+    )
+    .execute(conn)
+    .await
+  }
+
+  /// Subscriptions for a community whose users still follow it, and weren't notified within their
+  /// interval
+  pub async fn list_due(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    now: NaiveDateTime,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let follows = community_follower::table
+      .filter(community_follower::person_id.eq(community_notification::person_id))
+      .filter(community_follower::community_id.eq(community_notification::community_id))
+      .filter(community_follower::pending.eq(false));
+    let subscriptions = community_notification::table
+      .filter(community_notification::community_id.eq(community_id))
+      .filter(exists(follows))
+      .load::<Self>(conn)
+      .await?;
+    Ok(
+      subscriptions
+        .into_iter()
+        .filter(|s| s.is_due(now))
+        .collect(),
+    )
+  }
+
+  fn is_due(&self, now: NaiveDateTime) -> bool {
+    match self.last_notified {
+      Some(last) => last + Duration::minutes(self.min_interval_minutes.into()) <= now,
+      None => true,
+    }
+  }
+
+  pub async fn mark_notified(
+    pool: &mut DbPool<'_>,
+    id: i32,
+    now: NaiveDateTime,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_notification::table.find(id))
+      .set(community_notification::last_notified.eq(now))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+}
+
+impl PostNotification {
+  /// Returns None if the user was already notified about the post
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &PostNotificationInsertForm,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_notification::table)
+      .values(form)
+      .on_conflict_do_nothing()
+      .get_result::<Self>(conn)
+      .await
+      .optional()
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, id: PostNotificationId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post_notification::table.find(id).first::<Self>(conn).await
+  }
+
+  pub async fn mark_as_read(
+    pool: &mut DbPool<'_>,
+    id: PostNotificationId,
+    read: bool,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(post_notification::table.find(id))
+      .set(post_notification::read.eq(read))
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn mark_all_as_read(
+    pool: &mut DbPool<'_>,
+    recipient_id: PersonId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      post_notification::table
+        .filter(post_notification::recipient_id.eq(recipient_id))
+        .filter(post_notification::read.eq(false)),
+    )
+    .set(post_notification::read.eq(true))
+    .execute(conn)
+    .await
+  }
+
+  pub async fn count_unread(pool: &mut DbPool<'_>, recipient_id: PersonId) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post_notification::table
+      .filter(post_notification::recipient_id.eq(recipient_id))
+      .filter(post_notification::read.eq(false))
+      .count()
+      .get_result(conn)
+      .await
+  }
+
+  /// Lists the notifications of a user, newest first, together with the post, its community and
+  /// its creator
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    recipient_id: PersonId,
+    unread_only: bool,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<(Self, Post, Community, Person)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    let mut query = post_notification::table
+      .inner_join(post::table)
+      .inner_join(community::table.on(post::community_id.eq(community::id)))
+      .inner_join(person::table.on(post::creator_id.eq(person::id)))
+      .filter(post_notification::recipient_id.eq(recipient_id))
+      .select((
+        post_notification::all_columns,
+        post::all_columns,
+        community::all_columns,
+        person::all_columns,
+      ))
+      .into_boxed();
+    if unread_only {
+      query = query.filter(post_notification::read.eq(false));
+    }
+    query
+      .order_by(post_notification::published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityFollower, CommunityFollowerForm, CommunityInsertForm},
+      community_notification::{
+        CommunityNotification,
+        CommunityNotificationForm,
+        PostNotification,
+        PostNotificationInsertForm,
+      },
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::{Crud, Followable},
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_post_notifications() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("notified_person".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("notified_community".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &community_form).await.unwrap();
+    let post_form = PostInsertForm::builder()
+      .name("A test post".into())
+      .creator_id(inserted_person.id)
+      .community_id(inserted_community.id)
+      .build();
+    let inserted_post = Post::create(pool, &post_form).await.unwrap();
+
+    let form = CommunityNotificationForm {
+      person_id: inserted_person.id,
+      community_id: inserted_community.id,
+      min_interval_minutes: 60,
+    };
+    let subscription = CommunityNotification::subscribe(pool, &form).await.unwrap();
+
+    // Only followers are notified
+    let now = naive_now();
+    let due = CommunityNotification::list_due(pool, inserted_community.id, now)
+      .await
+      .unwrap();
+    assert!(due.is_empty());
+    let follower_form = CommunityFollowerForm {
+      community_id: inserted_community.id,
+      person_id: inserted_person.id,
+      pending: false,
+    };
+    CommunityFollower::follow(pool, &follower_form)
+      .await
+      .unwrap();
+    let due = CommunityNotification::list_due(pool, inserted_community.id, now)
+      .await
+      .unwrap();
+    assert_eq!(1, due.len());
+
+    // Not again until the interval passed
+    CommunityNotification::mark_notified(pool, subscription.id, now)
+      .await
+      .unwrap();
+    let due = CommunityNotification::list_due(pool, inserted_community.id, now)
+      .await
+      .unwrap();
+    assert!(due.is_empty());
+    let due =
+      CommunityNotification::list_due(pool, inserted_community.id, now + Duration::minutes(60))
+        .await
+        .unwrap();
+    assert_eq!(1, due.len());
+
+    let notification_form = PostNotificationInsertForm {
+      recipient_id: inserted_person.id,
+      post_id: inserted_post.id,
+    };
+    let created = PostNotification::create(pool, &notification_form)
+      .await
+      .unwrap();
+    assert!(created.is_some());
+    let duplicate = PostNotification::create(pool, &notification_form)
+      .await
+      .unwrap();
+    assert!(duplicate.is_none());
+    assert_eq!(
+      1,
+      PostNotification::count_unread(pool, inserted_person.id)
+        .await
+        .unwrap()
+    );
+    let list = PostNotification::list(pool, inserted_person.id, true, None, None)
+      .await
+      .unwrap();
+    assert_eq!(1, list.len());
+    assert_eq!(inserted_post.id, list[0].1.id);
+
+    PostNotification::mark_all_as_read(pool, inserted_person.id)
+      .await
+      .unwrap();
+    let list = PostNotification::list(pool, inserted_person.id, true, None, None)
+      .await
+      .unwrap();
+    assert!(list.is_empty());
+
+    CommunityNotification::unsubscribe(pool, inserted_person.id, inserted_community.id)
+      .await
+      .unwrap();
+    let due =
+      CommunityNotification::list_due(pool, inserted_community.id, now + Duration::minutes(60))
+        .await
+        .unwrap();
+    assert!(due.is_empty());
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
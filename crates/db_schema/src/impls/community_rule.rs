@@ -0,0 +1,174 @@
+use crate::{
+  newtypes::{CommunityId, CommunityRuleId},
+  schema::community_rule::dsl::{community_id, community_rule, id, position},
+  source::community_rule::{CommunityRule, CommunityRuleForm},
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for CommunityRule {
+  type InsertForm = CommunityRuleForm;
+  type UpdateForm = CommunityRuleForm;
+  type IdType = CommunityRuleId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_rule)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    rule_id: CommunityRuleId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_rule.find(rule_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl CommunityRule {
+  pub async fn for_community(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_rule
+      .filter(community_id.eq(for_community_id))
+      .order((position, id))
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  /// Replaces the rules of a community, as received from its instance. Existing rules are updated
+  /// in place, so that reports keep pointing to the rule at the same position.
+  pub async fn replace(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+    forms: Vec<CommunityRuleForm>,
+  ) -> Result<(), Error> {
+    let existing = Self::for_community(pool, for_community_id).await?;
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let mut existing = existing.into_iter();
+          for form in forms {
+            match existing.next() {
+              Some(rule) => {
+                diesel::update(community_rule.find(rule.id))
+                  .set(&form)
+                  .execute(conn)
+                  .await?;
+              }
+              None => {
+                insert_into(community_rule)
+                  .values(&form)
+                  .execute(conn)
+                  .await?;
+              }
+            }
+          }
+          let removed: Vec<CommunityRuleId> = existing.map(|r| r.id).collect();
+          diesel::delete(community_rule.filter(id.eq_any(removed)))
+            .execute(conn)
+            .await?;
+          Ok(())
+        }) as _
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      community_rule::{CommunityRule, CommunityRuleForm},
+      instance::Instance,
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crud() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let new_community = CommunityInsertForm::builder()
+      .name("community_rule".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let form = CommunityRuleForm {
+      community_id: inserted_community.id,
+      position: 1,
+      title: "No spam".to_string(),
+      description: None,
+    };
+    let second_rule = CommunityRule::create(pool, &form).await.unwrap();
+    let first_rule = CommunityRule::create(
+      pool,
+      &CommunityRuleForm {
+        position: 0,
+        title: "Be nice".to_string(),
+        description: Some("Treat others with respect".to_string()),
+        ..form.clone()
+      },
+    )
+    .await
+    .unwrap();
+
+    let rules = CommunityRule::for_community(pool, inserted_community.id)
+      .await
+      .unwrap();
+
+    // Replacing keeps the first rule and removes the second one
+    let replacement = CommunityRuleForm {
+      position: 0,
+      title: "Be kind".to_string(),
+      description: None,
+      ..form
+    };
+    CommunityRule::replace(pool, inserted_community.id, vec![replacement])
+      .await
+      .unwrap();
+    let rules_after_replace = CommunityRule::for_community(pool, inserted_community.id)
+      .await
+      .unwrap();
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert_eq!(vec![first_rule.clone(), second_rule], rules);
+    assert_eq!(1, rules_after_replace.len());
+    assert_eq!(first_rule.id, rules_after_replace[0].id);
+    assert_eq!("Be kind", rules_after_replace[0].title);
+    assert_eq!(None, rules_after_replace[0].description);
+  }
+}
@@ -0,0 +1,106 @@
+use crate::{
+  newtypes::{CommunityId, PersonId},
+  schema::community_snooze::dsl::{community_id, community_snooze, person_id},
+  source::community_snooze::{CommunitySnooze, CommunitySnoozeForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl CommunitySnooze {
+  /// Hides the community from the person's feeds until the given time, or moves the existing
+  /// snooze to that time.
+  pub async fn snooze(pool: &mut DbPool<'_>, form: &CommunitySnoozeForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_snooze)
+      .values(form)
+      .on_conflict((person_id, community_id))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn unsnooze(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+    for_community_id: CommunityId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      community_snooze
+        .filter(person_id.eq(for_person_id))
+        .filter(community_id.eq(for_community_id)),
+    )
+    .execute(conn)
+    .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      community_snooze::{CommunitySnooze, CommunitySnoozeForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_snooze() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("snoozer".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let person = Person::create(pool, &person_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("snoozed".to_string())
+      .title("snoozed".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let community = Community::create(pool, &community_form).await.unwrap();
+
+    let form = CommunitySnoozeForm {
+      person_id: person.id,
+      community_id: community.id,
+      expires: naive_now() + Duration::days(1),
+    };
+    let snooze = CommunitySnooze::snooze(pool, &form).await.unwrap();
+
+    // Snoozing again moves the existing snooze
+    let form = CommunitySnoozeForm {
+      expires: naive_now() - Duration::days(1),
+      ..form
+    };
+    let moved = CommunitySnooze::snooze(pool, &form).await.unwrap();
+    assert_eq!(snooze.id, moved.id);
+    assert!(moved.expires < snooze.expires);
+
+    let unsnoozed = CommunitySnooze::unsnooze(pool, person.id, community.id)
+      .await
+      .unwrap();
+    assert_eq!(1, unsnoozed);
+
+    Community::delete(pool, community.id).await.unwrap();
+    Person::delete(pool, person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
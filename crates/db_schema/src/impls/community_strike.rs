@@ -0,0 +1,172 @@
+use crate::{
+  newtypes::{CommunityId, CommunityStrikeId, PersonId},
+  schema::community_strike::dsl::{community_id, community_strike, id, person_id},
+  source::community_strike::{CommunityStrike, CommunityStrikeForm},
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{dsl::count_star, insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for CommunityStrike {
+  type InsertForm = CommunityStrikeForm;
+  type UpdateForm = CommunityStrikeForm;
+  type IdType = CommunityStrikeId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_strike)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    strike_id: CommunityStrikeId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_strike.find(strike_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl CommunityStrike {
+  /// The strikes of a user in a community, newest first.
+  pub async fn list_for_person(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+    for_person_id: PersonId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_strike
+      .filter(community_id.eq(for_community_id))
+      .filter(person_id.eq(for_person_id))
+      .order(id.desc())
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  pub async fn count_for_person(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+    for_person_id: PersonId,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_strike
+      .filter(community_id.eq(for_community_id))
+      .filter(person_id.eq(for_person_id))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      community_strike::{CommunityStrike, CommunityStrikeForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crud() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let new_mod = PersonInsertForm::builder()
+      .name("strike_mod".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_mod = Person::create(pool, &new_mod).await.unwrap();
+
+    let new_person = PersonInsertForm::builder()
+      .name("strike_person".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &new_person).await.unwrap();
+
+    let new_community = CommunityInsertForm::builder()
+      .name("strike_community".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let new_post = PostInsertForm::builder()
+      .name("A post with a strike".into())
+      .creator_id(inserted_person.id)
+      .community_id(inserted_community.id)
+      .build();
+    let inserted_post = Post::create(pool, &new_post).await.unwrap();
+
+    let form = CommunityStrikeForm {
+      community_id: inserted_community.id,
+      person_id: inserted_person.id,
+      mod_person_id: inserted_mod.id,
+      post_id: Some(inserted_post.id),
+      comment_id: None,
+      reason: Some("spam".to_string()),
+    };
+    let first_strike = CommunityStrike::create(pool, &form).await.unwrap();
+    let second_strike = CommunityStrike::create(
+      pool,
+      &CommunityStrikeForm {
+        post_id: None,
+        reason: None,
+        ..form
+      },
+    )
+    .await
+    .unwrap();
+
+    let strikes = CommunityStrike::list_for_person(pool, inserted_community.id, inserted_person.id)
+      .await
+      .unwrap();
+    let count = CommunityStrike::count_for_person(pool, inserted_community.id, inserted_person.id)
+      .await
+      .unwrap();
+    let mod_count = CommunityStrike::count_for_person(pool, inserted_community.id, inserted_mod.id)
+      .await
+      .unwrap();
+
+    // The strike is kept when the post is deleted
+    Post::delete(pool, inserted_post.id).await.unwrap();
+    let first_strike_after_delete = CommunityStrike::read(pool, first_strike.id).await.unwrap();
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Person::delete(pool, inserted_mod.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert_eq!(vec![second_strike, first_strike], strikes);
+    assert_eq!(2, count);
+    assert_eq!(0, mod_count);
+    assert_eq!(None, first_strike_after_delete.post_id);
+  }
+}
@@ -0,0 +1,125 @@
+use crate::{
+  newtypes::{CommunityId, CommunityWordFilterId},
+  schema::community_word_filter::dsl::{community_id, community_word_filter, id},
+  source::community_word_filter::{CommunityWordFilter, CommunityWordFilterForm},
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for CommunityWordFilter {
+  type InsertForm = CommunityWordFilterForm;
+  type UpdateForm = CommunityWordFilterForm;
+  type IdType = CommunityWordFilterId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_word_filter)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    word_filter_id: CommunityWordFilterId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_word_filter.find(word_filter_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl CommunityWordFilter {
+  pub async fn for_community(
+    pool: &mut DbPool<'_>,
+    for_community_id: CommunityId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_word_filter
+      .filter(community_id.eq(for_community_id))
+      .order(id)
+      .get_results::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      community_word_filter::{CommunityWordFilter, CommunityWordFilterForm},
+      instance::Instance,
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+    WordFilterAction,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crud() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let new_community = CommunityInsertForm::builder()
+      .name("word_filter".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let form = CommunityWordFilterForm {
+      community_id: inserted_community.id,
+      pattern: r"\bbuy now\b".to_string(),
+      action: WordFilterAction::Report,
+    };
+    let inserted_filter = CommunityWordFilter::create(pool, &form).await.unwrap();
+    let duplicate = CommunityWordFilter::create(pool, &form).await;
+    let second_filter = CommunityWordFilter::create(
+      pool,
+      &CommunityWordFilterForm {
+        pattern: "crypto".to_string(),
+        action: WordFilterAction::Reject,
+        ..form
+      },
+    )
+    .await
+    .unwrap();
+
+    let filters = CommunityWordFilter::for_community(pool, inserted_community.id)
+      .await
+      .unwrap();
+    let num_deleted = CommunityWordFilter::delete(pool, inserted_filter.id)
+      .await
+      .unwrap();
+    let filters_after_delete = CommunityWordFilter::for_community(pool, inserted_community.id)
+      .await
+      .unwrap();
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert!(duplicate.is_err());
+    assert_eq!(vec![inserted_filter, second_filter.clone()], filters);
+    assert_eq!(1, num_deleted);
+    assert_eq!(vec![second_filter], filters_after_delete);
+  }
+}
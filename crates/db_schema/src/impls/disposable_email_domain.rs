@@ -0,0 +1,167 @@
+use crate::{
+  schema::disposable_email_domain,
+  source::disposable_email_domain::{DisposableEmailDomain, DisposableEmailDomainForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+/// Rows are inserted in batches, to stay below the maximum number of query parameters
+const INSERT_BATCH_SIZE: usize = 10_000;
+
+impl DisposableEmailDomain {
+  /// Replaces the fetched list of disposable domains. Domains with an exception are kept as they
+  /// are.
+  pub async fn replace_fetched(pool: &mut DbPool<'_>, domains: Vec<String>) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          diesel::delete(
+            disposable_email_domain::table.filter(disposable_email_domain::exception.eq(false)),
+          )
+          .execute(conn)
+          .await?;
+
+          let forms = domains
+            .into_iter()
+            .map(|domain| DisposableEmailDomainForm {
+              domain,
+              exception: false,
+            })
+            .collect::<Vec<_>>();
+          for batch in forms.chunks(INSERT_BATCH_SIZE) {
+            insert_into(disposable_email_domain::table)
+              .values(batch)
+              .on_conflict_do_nothing()
+              .execute(conn)
+              .await?;
+          }
+          Ok(())
+        }) as _
+      })
+      .await
+  }
+
+  /// Replaces the domains which admins don't consider disposable.
+  pub async fn replace_exceptions(
+    pool: &mut DbPool<'_>,
+    list_opt: Option<Vec<String>>,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let Some(list) = list_opt else {
+      return Ok(());
+    };
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          diesel::delete(
+            disposable_email_domain::table.filter(
+              disposable_email_domain::exception
+                .eq(true)
+                .or(disposable_email_domain::domain.eq_any(&list)),
+            ),
+          )
+          .execute(conn)
+          .await?;
+
+          let forms = list
+            .into_iter()
+            .map(|domain| DisposableEmailDomainForm {
+              domain,
+              exception: true,
+            })
+            .collect::<Vec<_>>();
+          insert_into(disposable_email_domain::table)
+            .values(forms)
+            .execute(conn)
+            .await?;
+          Ok(())
+        }) as _
+      })
+      .await
+  }
+
+  /// The stored entries for any of the given domains.
+  pub async fn read_for_domains(
+    pool: &mut DbPool<'_>,
+    domains: &[String],
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    disposable_email_domain::table
+      .filter(disposable_email_domain::domain.eq_any(domains))
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// The domains which admins don't consider disposable.
+  pub async fn list_exceptions(pool: &mut DbPool<'_>) -> Result<Vec<String>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    disposable_email_domain::table
+      .filter(disposable_email_domain::exception.eq(true))
+      .select(disposable_email_domain::domain)
+      .order_by(disposable_email_domain::domain)
+      .load::<String>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::disposable_email_domain::DisposableEmailDomain,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_replace() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let fetched = vec!["trash.example".to_string(), "temp.example".to_string()];
+    DisposableEmailDomain::replace_fetched(pool, fetched)
+      .await
+      .unwrap();
+    let domains = ["temp.example".to_string(), "other.example".to_string()];
+    let found = DisposableEmailDomain::read_for_domains(pool, &domains)
+      .await
+      .unwrap();
+    assert_eq!(1, found.len());
+    assert!(!found[0].exception);
+
+    // An exception replaces the fetched entry, and survives the next fetch
+    DisposableEmailDomain::replace_exceptions(pool, Some(vec!["temp.example".to_string()]))
+      .await
+      .unwrap();
+    DisposableEmailDomain::replace_fetched(pool, vec!["temp.example".to_string()])
+      .await
+      .unwrap();
+    let found = DisposableEmailDomain::read_for_domains(pool, &domains)
+      .await
+      .unwrap();
+    assert_eq!(1, found.len());
+    assert!(found[0].exception);
+    assert_eq!(
+      vec!["temp.example".to_string()],
+      DisposableEmailDomain::list_exceptions(pool).await.unwrap()
+    );
+
+    DisposableEmailDomain::replace_exceptions(pool, Some(vec![]))
+      .await
+      .unwrap();
+    DisposableEmailDomain::replace_fetched(pool, vec![])
+      .await
+      .unwrap();
+    assert!(DisposableEmailDomain::read_for_domains(pool, &domains)
+      .await
+      .unwrap()
+      .is_empty());
+  }
+}
@@ -0,0 +1,98 @@
+use crate::{
+  schema::email_domain,
+  source::email_domain::{EmailDomain, EmailDomainForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl EmailDomain {
+  /// Replaces either the allowed or the blocked domains with the given list.
+  pub async fn replace(
+    pool: &mut DbPool<'_>,
+    allowed: bool,
+    list_opt: Option<Vec<String>>,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let Some(list) = list_opt else {
+      return Ok(());
+    };
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          diesel::delete(email_domain::table.filter(email_domain::allowed.eq(allowed)))
+            .execute(conn)
+            .await?;
+
+          let forms = list
+            .into_iter()
+            .map(|domain| EmailDomainForm { domain, allowed })
+            .collect::<Vec<_>>();
+          insert_into(email_domain::table)
+            .values(forms)
+            .execute(conn)
+            .await?;
+          Ok(())
+        }) as _
+      })
+      .await
+  }
+
+  /// The allowed or the blocked domains.
+  pub async fn list(pool: &mut DbPool<'_>, allowed: bool) -> Result<Vec<String>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    email_domain::table
+      .filter(email_domain::allowed.eq(allowed))
+      .select(email_domain::domain)
+      .order_by(email_domain::domain)
+      .load::<String>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{source::email_domain::EmailDomain, utils::build_db_pool_for_tests};
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_replace() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let allowed = vec!["example.com".to_string(), "example.org".to_string()];
+    EmailDomain::replace(pool, true, Some(allowed.clone()))
+      .await
+      .unwrap();
+    EmailDomain::replace(pool, false, Some(vec!["spam.example".to_string()]))
+      .await
+      .unwrap();
+    assert_eq!(allowed, EmailDomain::list(pool, true).await.unwrap());
+    assert_eq!(
+      vec!["spam.example".to_string()],
+      EmailDomain::list(pool, false).await.unwrap()
+    );
+
+    // Not passing a list leaves it as is, and replacing one list keeps the other
+    EmailDomain::replace(pool, true, None).await.unwrap();
+    assert_eq!(allowed, EmailDomain::list(pool, true).await.unwrap());
+    EmailDomain::replace(pool, true, Some(vec![]))
+      .await
+      .unwrap();
+    assert!(EmailDomain::list(pool, true).await.unwrap().is_empty());
+    assert_eq!(1, EmailDomain::list(pool, false).await.unwrap().len());
+
+    // A domain can't be both allowed and blocked
+    let conflict = EmailDomain::replace(pool, true, Some(vec!["spam.example".to_string()])).await;
+    assert!(conflict.is_err());
+
+    EmailDomain::replace(pool, false, Some(vec![]))
+      .await
+      .unwrap();
+  }
+}
@@ -1,15 +1,19 @@
 use crate::{
-  schema::federation_blocklist,
+  newtypes::{BlocklistSubscriptionId, InstanceId},
+  schema::{federation_blocklist, instance},
   source::{
+    blocklist_subscription::BlocklistEntry,
     federation_blocklist::{FederationBlockList, FederationBlockListForm},
     instance::Instance,
   },
-  utils::{get_conn, DbPool},
+  utils::{get_conn, limit_and_offset, naive_now, DbPool},
 };
-use diesel::{dsl::insert_into, result::Error};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, PgExpressionMethods, QueryDsl};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 
 impl FederationBlockList {
+  /// Sets the blocklist to the given domains. Entries which are already present keep their
+  /// subscription and reason, new ones are added as local entries.
   pub async fn replace(pool: &mut DbPool<'_>, list_opt: Option<Vec<String>>) -> Result<(), Error> {
     let conn = &mut get_conn(pool).await?;
     conn
@@ -17,21 +21,24 @@ impl FederationBlockList {
       .run(|conn| {
         Box::pin(async move {
           if let Some(list) = list_opt {
-            Self::clear(conn).await?;
-
+            let mut instance_ids = Vec::new();
             for domain in list {
               // Upsert all of these as instances
               let instance = Instance::read_or_create(&mut conn.into(), domain).await?;
+              instance_ids.push(instance.id);
 
               let form = FederationBlockListForm {
                 instance_id: instance.id,
-                updated: None,
+                ..Default::default()
               };
-              insert_into(federation_blocklist::table)
-                .values(form)
-                .get_result::<Self>(conn)
-                .await?;
+              Self::insert(conn, &form).await?;
             }
+            diesel::delete(
+              federation_blocklist::table
+                .filter(federation_blocklist::instance_id.ne_all(instance_ids)),
+            )
+            .execute(conn)
+            .await?;
             Ok(())
           } else {
             Ok(())
@@ -41,8 +48,114 @@ impl FederationBlockList {
       .await
   }
 
-  async fn clear(conn: &mut AsyncPgConnection) -> Result<usize, Error> {
-    diesel::delete(federation_blocklist::table)
+  /// Updates the entries of a blocklist subscription to the given list. Domains which are blocked
+  /// already are left alone, and entries which are missing from the list are removed unless they
+  /// are pinned.
+  pub async fn sync_subscription(
+    pool: &mut DbPool<'_>,
+    subscription_id: BlocklistSubscriptionId,
+    entries: Vec<BlocklistEntry>,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let mut instance_ids = Vec::new();
+          for entry in entries {
+            let instance = Instance::read_or_create(&mut conn.into(), entry.domain).await?;
+            instance_ids.push(instance.id);
+
+            let form = FederationBlockListForm {
+              instance_id: instance.id,
+              subscription_id: Some(subscription_id),
+              reason: entry.reason.clone(),
+              ..Default::default()
+            };
+            Self::insert(conn, &form).await?;
+            diesel::update(
+              federation_blocklist::table
+                .filter(federation_blocklist::instance_id.eq(instance.id))
+                .filter(federation_blocklist::subscription_id.eq(subscription_id))
+                .filter(federation_blocklist::reason.is_distinct_from(&entry.reason)),
+            )
+            .set((
+              federation_blocklist::reason.eq(&entry.reason),
+              federation_blocklist::updated.eq(naive_now()),
+            ))
+            .execute(conn)
+            .await?;
+          }
+          diesel::delete(
+            federation_blocklist::table
+              .filter(federation_blocklist::subscription_id.eq(subscription_id))
+              .filter(federation_blocklist::pinned.eq(false))
+              .filter(federation_blocklist::instance_id.ne_all(instance_ids)),
+          )
+          .execute(conn)
+          .await?;
+          Ok(())
+        }) as _
+      })
+      .await
+  }
+
+  /// Removes the entries of a blocklist subscription, except for the pinned ones.
+  pub(crate) async fn clear_subscription(
+    conn: &mut AsyncPgConnection,
+    subscription_id: BlocklistSubscriptionId,
+  ) -> Result<usize, Error> {
+    diesel::delete(
+      federation_blocklist::table
+        .filter(federation_blocklist::subscription_id.eq(subscription_id))
+        .filter(federation_blocklist::pinned.eq(false)),
+    )
+    .execute(conn)
+    .await
+  }
+
+  pub async fn set_pinned(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    pinned: bool,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      federation_blocklist::table.filter(federation_blocklist::instance_id.eq(instance_id)),
+    )
+    .set((
+      federation_blocklist::pinned.eq(pinned),
+      federation_blocklist::updated.eq(naive_now()),
+    ))
+    .get_result::<Self>(conn)
+    .await
+  }
+
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<(Self, Instance)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    federation_blocklist::table
+      .inner_join(instance::table)
+      .order_by(instance::domain)
+      .limit(limit)
+      .offset(offset)
+      .get_results(conn)
+      .await
+  }
+
+  /// Domains which are blocked already keep their entry
+  async fn insert(
+    conn: &mut AsyncPgConnection,
+    form: &FederationBlockListForm,
+  ) -> Result<usize, Error> {
+    insert_into(federation_blocklist::table)
+      .values(form)
+      .on_conflict(federation_blocklist::instance_id)
+      .do_nothing()
       .execute(conn)
       .await
   }
@@ -0,0 +1,112 @@
+use crate::{
+  schema::{instance, instance_trust},
+  source::{
+    instance::Instance,
+    instance_trust::{InstanceTrust, InstanceTrustForm},
+  },
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+/// Rows per insert statement, to stay below the bind parameter limit of postgres
+const INSERT_CHUNK_SIZE: usize = 1000;
+
+impl InstanceTrust {
+  /// Replaces all trust signals with the latest ones
+  pub async fn replace_all(
+    pool: &mut DbPool<'_>,
+    forms: Vec<InstanceTrustForm>,
+  ) -> Result<(), Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          diesel::delete(instance_trust::table).execute(conn).await?;
+          for chunk in forms.chunks(INSERT_CHUNK_SIZE) {
+            insert_into(instance_trust::table)
+              .values(chunk)
+              .execute(conn)
+              .await?;
+          }
+          Ok(())
+        }) as _
+      })
+      .await
+  }
+
+  /// Lists known instances together with their trust signals, if there are any
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<(Instance, Option<Self>)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    instance::table
+      .left_join(instance_trust::table)
+      .order_by(instance::domain)
+      .limit(limit)
+      .offset(offset)
+      .get_results(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      instance_trust::{InstanceTrust, InstanceTrustForm},
+    },
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_replace_all() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let trusted = Instance::read_or_create(pool, "trusted.example".to_string())
+      .await
+      .unwrap();
+    let spam = Instance::read_or_create(pool, "spam.example".to_string())
+      .await
+      .unwrap();
+    let forms = vec![
+      InstanceTrustForm {
+        instance_id: trusted.id,
+        guarantor: Some("fediseer.com".to_string()),
+        endorsements: 3,
+        ..Default::default()
+      },
+      InstanceTrustForm {
+        instance_id: spam.id,
+        censures: 2,
+        censure_reasons: Some("spam".to_string()),
+        ..Default::default()
+      },
+    ];
+    InstanceTrust::replace_all(pool, forms).await.unwrap();
+    let list = InstanceTrust::list(pool, None, None).await.unwrap();
+    assert_eq!(2, list.len());
+    assert_eq!("spam.example", list[0].0.domain);
+    assert_eq!(2, list[0].1.as_ref().unwrap().censures);
+    assert_eq!(3, list[1].1.as_ref().unwrap().endorsements);
+
+    // instances without signals are still listed
+    InstanceTrust::replace_all(pool, vec![]).await.unwrap();
+    let list = InstanceTrust::list(pool, None, None).await.unwrap();
+    assert_eq!(2, list.len());
+    assert!(list.iter().all(|(_, trust)| trust.is_none()));
+
+    Instance::delete_all(pool).await.unwrap();
+  }
+}
@@ -0,0 +1,236 @@
+use crate::{
+  newtypes::{InviteId, PersonId},
+  schema::{
+    invite::dsl::{code, expires, invite, max_uses, uses},
+    invite_use,
+  },
+  source::{
+    invite::{Invite, InviteInsertForm, InviteUse, InviteUseForm},
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+  },
+  traits::Crud,
+  utils::{get_conn, naive_now, DbPool},
+};
+use diesel::{
+  dsl::{insert_into, now},
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl Invite {
+  pub async fn create(pool: &mut DbPool<'_>, form: &InviteInsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(invite)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, invite_id: InviteId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    invite.find(invite_id).first::<Self>(conn).await
+  }
+
+  /// Reads the invite with the given code, if it isn't expired or used up.
+  pub async fn read_valid(pool: &mut DbPool<'_>, invite_code: &str) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    invite
+      .filter(code.eq(invite_code))
+      .filter(uses.lt(max_uses))
+      .filter(expires.is_null().or(expires.gt(now)))
+      .first::<Self>(conn)
+      .await
+  }
+
+  /// Counts a registration against the invite with the given code. Fails with `NotFound` if the
+  /// invite is expired or used up, also when another registration used it up concurrently.
+  pub async fn claim(pool: &mut DbPool<'_>, invite_code: &str) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      invite
+        .filter(code.eq(invite_code))
+        .filter(uses.lt(max_uses))
+        .filter(expires.is_null().or(expires.gt(now))),
+    )
+    .set(uses.eq(uses + 1))
+    .get_result::<Self>(conn)
+    .await
+  }
+
+  /// Makes the invite unusable by letting it expire now.
+  pub async fn revoke(pool: &mut DbPool<'_>, invite_id: InviteId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(invite.find(invite_id))
+      .set(expires.eq(naive_now()))
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl InviteUse {
+  pub async fn create(pool: &mut DbPool<'_>, form: &InviteUseForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(invite_use::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Registers a new user with the invite code in a single transaction: The registration is
+  /// counted against the invite, and the person and local user are created. Returns `None` without
+  /// creating anything if the invite is expired or used up, also when another registration used it
+  /// up concurrently.
+  pub async fn register<F>(
+    pool: &mut DbPool<'_>,
+    invite_code: &str,
+    person_form: &PersonInsertForm,
+    local_user_form: F,
+  ) -> Result<Option<(Person, LocalUser)>, Error>
+  where
+    F: FnOnce(PersonId) -> LocalUserInsertForm + Send,
+  {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          let Some(invite_) = Invite::claim(&mut conn.into(), invite_code)
+            .await
+            .optional()?
+          else {
+            return Ok(None);
+          };
+          let person = Person::create(&mut conn.into(), person_form).await?;
+          let local_user = LocalUser::create(&mut conn.into(), &local_user_form(person.id)).await?;
+          let form = InviteUseForm {
+            invite_id: invite_.id,
+            person_id: person.id,
+          };
+          InviteUse::create(&mut conn.into(), &form).await?;
+          Ok(Some((person, local_user)))
+        }) as _
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      invite::{Invite, InviteInsertForm, InviteUse, InviteUseForm},
+      local_user::LocalUserInsertForm,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_invite() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("invite_creator".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let creator = Person::create(pool, &person_form).await.unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("invitee".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let invitee = Person::create(pool, &person_form).await.unwrap();
+
+    let form = InviteInsertForm {
+      code: "invite_code".to_string(),
+      creator_id: creator.id,
+      max_uses: 2,
+      expires: Some(naive_now() + Duration::days(1)),
+    };
+    let created = Invite::create(pool, &form).await.unwrap();
+    assert_eq!(0, created.uses);
+    assert!(Invite::read_valid(pool, "invite_code").await.is_ok());
+    assert!(Invite::read_valid(pool, "other_code").await.is_err());
+
+    // The invite can only be used as often as allowed
+    assert_eq!(1, Invite::claim(pool, "invite_code").await.unwrap().uses);
+    assert_eq!(2, Invite::claim(pool, "invite_code").await.unwrap().uses);
+    assert!(Invite::claim(pool, "invite_code").await.is_err());
+    assert!(Invite::read_valid(pool, "invite_code").await.is_err());
+
+    let form = InviteUseForm {
+      invite_id: created.id,
+      person_id: invitee.id,
+    };
+    let invite_use = InviteUse::create(pool, &form).await.unwrap();
+    assert_eq!(created.id, invite_use.invite_id);
+
+    // A revoked invite can't be used anymore
+    let form = InviteInsertForm {
+      code: "unlimited_code".to_string(),
+      creator_id: creator.id,
+      max_uses: 100,
+      expires: None,
+    };
+    let unlimited = Invite::create(pool, &form).await.unwrap();
+    assert!(Invite::read_valid(pool, "unlimited_code").await.is_ok());
+    let revoked = Invite::revoke(pool, unlimited.id).await.unwrap();
+    assert!(revoked.expires.is_some());
+    assert!(Invite::claim(pool, "unlimited_code").await.is_err());
+    assert_eq!(revoked, Invite::read(pool, unlimited.id).await.unwrap());
+
+    // Registering with a used up invite doesn't create the user
+    let person_form = PersonInsertForm::builder()
+      .name("invited_user".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let local_user_form = |person_id| {
+      LocalUserInsertForm::builder()
+        .person_id(person_id)
+        .password_encrypted("password".to_string())
+        .build()
+    };
+    let registered = InviteUse::register(pool, "invite_code", &person_form, local_user_form)
+      .await
+      .unwrap();
+    assert_eq!(None, registered);
+    let form = InviteInsertForm {
+      code: "register_code".to_string(),
+      creator_id: creator.id,
+      max_uses: 1,
+      expires: None,
+    };
+    Invite::create(pool, &form).await.unwrap();
+    let (person, local_user) =
+      InviteUse::register(pool, "register_code", &person_form, local_user_form)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(person.id, local_user.person_id);
+    assert!(Invite::read_valid(pool, "register_code").await.is_err());
+
+    Person::delete(pool, person.id).await.unwrap();
+    Person::delete(pool, creator.id).await.unwrap();
+    Person::delete(pool, invitee.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
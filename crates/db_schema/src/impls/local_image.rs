@@ -0,0 +1,200 @@
+use crate::{
+  newtypes::{CommentId, PersonId, PostId},
+  schema::local_image::dsl::{
+    comment_id,
+    local_image,
+    person_id,
+    pictrs_alias,
+    post_id,
+    published,
+  },
+  source::local_image::{LocalImage, LocalImageForm},
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+use diesel::{dsl::count_star, insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl LocalImage {
+  pub async fn create(pool: &mut DbPool<'_>, form: &LocalImageForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(local_image)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Number of images the person has uploaded, used for the upload quota.
+  pub async fn count(pool: &mut DbPool<'_>) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    local_image.select(count_star()).first::<i64>(conn).await
+  }
+
+  pub async fn count_for_person(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    local_image
+      .filter(person_id.eq(for_person_id))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await
+  }
+
+  pub async fn list_for_person(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    local_image
+      .filter(person_id.eq(for_person_id))
+      .order(published.desc())
+      .limit(limit)
+      .offset(offset)
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  /// Marks the images as used by the post. Images which are already in use somewhere else keep
+  /// their existing link.
+  pub async fn link_to_post(
+    pool: &mut DbPool<'_>,
+    aliases: &[String],
+    for_post_id: PostId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      local_image
+        .filter(pictrs_alias.eq_any(aliases))
+        .filter(post_id.is_null())
+        .filter(comment_id.is_null()),
+    )
+    .set(post_id.eq(for_post_id))
+    .execute(conn)
+    .await
+  }
+
+  /// Marks the images as used by the comment. Images which are already in use somewhere else keep
+  /// their existing link.
+  pub async fn link_to_comment(
+    pool: &mut DbPool<'_>,
+    aliases: &[String],
+    for_comment_id: CommentId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      local_image
+        .filter(pictrs_alias.eq_any(aliases))
+        .filter(post_id.is_null())
+        .filter(comment_id.is_null()),
+    )
+    .set(comment_id.eq(for_comment_id))
+    .execute(conn)
+    .await
+  }
+
+  pub async fn delete_by_alias(pool: &mut DbPool<'_>, alias: &str) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(local_image.filter(pictrs_alias.eq(alias)))
+      .execute(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      local_image::{LocalImage, LocalImageForm},
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_link_and_list() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let new_person = PersonInsertForm::builder()
+      .name("image_uploader".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &new_person).await.unwrap();
+
+    let new_community = CommunityInsertForm::builder()
+      .name("image_community".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let new_post = PostInsertForm::builder()
+      .name("A test post".into())
+      .creator_id(inserted_person.id)
+      .community_id(inserted_community.id)
+      .build();
+    let inserted_post = Post::create(pool, &new_post).await.unwrap();
+
+    for alias in ["used.png", "unused.png"] {
+      let form = LocalImageForm::builder()
+        .person_id(inserted_person.id)
+        .pictrs_alias(alias.to_string())
+        .pictrs_delete_token("token".to_string())
+        .build();
+      LocalImage::create(pool, &form).await.unwrap();
+    }
+
+    let linked = LocalImage::link_to_post(
+      pool,
+      &["used.png".to_string(), "other.png".to_string()],
+      inserted_post.id,
+    )
+    .await
+    .unwrap();
+    let count = LocalImage::count_for_person(pool, inserted_person.id)
+      .await
+      .unwrap();
+    let listed = LocalImage::list_for_person(pool, inserted_person.id, None, None)
+      .await
+      .unwrap();
+    let deleted = LocalImage::delete_by_alias(pool, "unused.png")
+      .await
+      .unwrap();
+
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert_eq!(1, linked);
+    assert_eq!(2, count);
+    let used = listed
+      .iter()
+      .find(|i| i.pictrs_alias == "used.png")
+      .unwrap();
+    assert_eq!(Some(inserted_post.id), used.post_id);
+    let unused = listed
+      .iter()
+      .find(|i| i.pictrs_alias == "unused.png")
+      .unwrap();
+    assert_eq!(None, unused.post_id);
+    assert_eq!(1, deleted);
+  }
+}
@@ -10,14 +10,21 @@ use crate::{
   },
   source::{
     actor_language::{LocalUserLanguage, SiteLanguage},
-    local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
+    local_user::{DailySignups, LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
   },
   traits::Crud,
   utils::{get_conn, naive_now, DbPool},
 };
-use bcrypt::{hash, DEFAULT_COST};
-use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel::{
+  dsl::{count_star, insert_into},
+  result::Error,
+  sql_types::Integer,
+  ExpressionMethods,
+  QueryDsl,
+  TextExpressionMethods,
+};
 use diesel_async::RunQueryDsl;
+use lemmy_utils::password::{hash_password, LEGACY_HASH_PREFIX};
 
 impl LocalUser {
   pub async fn update_password(
@@ -26,7 +33,7 @@ impl LocalUser {
     new_password: &str,
   ) -> Result<Self, Error> {
     let conn = &mut get_conn(pool).await?;
-    let password_hash = hash(new_password, DEFAULT_COST).expect("Couldn't hash password");
+    let password_hash = hash_password(new_password).expect("Couldn't hash password");
 
     diesel::update(local_user.find(local_user_id))
       .set((
@@ -37,6 +44,17 @@ impl LocalUser {
       .await
   }
 
+  /// Number of users whose password is still stored as a bcrypt hash, because they didn't log in
+  /// since the switch to Argon2id.
+  pub async fn count_legacy_password_hashes(pool: &mut DbPool<'_>) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    local_user
+      .filter(password_encrypted.like(format!("{LEGACY_HASH_PREFIX}%")))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await
+  }
+
   pub async fn set_all_users_email_verified(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
     diesel::update(local_user)
@@ -62,6 +80,26 @@ impl LocalUser {
       .get_result(conn)
       .await
   }
+
+  /// Counts the new local users for each of the last `days` days, oldest first. Days without any
+  /// signups are included with a count of zero.
+  pub async fn signups_per_day(
+    pool: &mut DbPool<'_>,
+    days: i32,
+  ) -> Result<Vec<DailySignups>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::sql_query(
+      "SELECT d::date AS day, count(p.id) AS signups
+       FROM generate_series(current_date - ($1 - 1), current_date, interval '1 day') d
+       LEFT JOIN (person p INNER JOIN local_user lu ON lu.person_id = p.id)
+         ON p.published::date = d::date
+       GROUP BY d
+       ORDER BY d",
+    )
+    .bind::<Integer, _>(days)
+    .load::<DailySignups>(conn)
+    .await
+  }
 }
 
 #[async_trait]
@@ -73,8 +111,7 @@ impl Crud for LocalUser {
   async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
     let conn = &mut get_conn(pool).await?;
     let mut form_with_encrypted_password = form.clone();
-    let password_hash =
-      hash(&form.password_encrypted, DEFAULT_COST).expect("Couldn't hash password");
+    let password_hash = hash_password(&form.password_encrypted).expect("Couldn't hash password");
     form_with_encrypted_password.password_encrypted = password_hash;
 
     let local_user_ = insert_into(local_user)
@@ -106,3 +143,52 @@ impl Crud for LocalUser {
       .await
   }
 }
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_signups_per_day() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let before = LocalUser::signups_per_day(pool, 7).await.unwrap();
+    assert_eq!(7, before.len());
+    assert!(before[0].day < before[6].day);
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("signup".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let local_user_form = LocalUserInsertForm::builder()
+      .person_id(inserted_person.id)
+      .password_encrypted("123456".to_string())
+      .build();
+    LocalUser::create(pool, &local_user_form).await.unwrap();
+
+    let after = LocalUser::signups_per_day(pool, 7).await.unwrap();
+    assert_eq!(before[6].signups + 1, after[6].signups);
+
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
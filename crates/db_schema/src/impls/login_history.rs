@@ -0,0 +1,103 @@
+use crate::{
+  schema::login_history::dsl::{device, ip_network, local_user_id, login_history, published},
+  source::login_history::{LoginHistory, LoginHistoryInsertForm},
+  utils::{get_conn, DbPool},
+};
+use chrono::NaiveDateTime;
+use diesel::{
+  dsl::{exists, insert_into},
+  result::Error,
+  ExpressionMethods,
+  PgExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl LoginHistory {
+  pub async fn create(pool: &mut DbPool<'_>, form: &LoginHistoryInsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(login_history)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Whether the user logged in from the same network and device before the given time.
+  pub async fn is_known(
+    pool: &mut DbPool<'_>,
+    form: &LoginHistoryInsertForm,
+    before: NaiveDateTime,
+  ) -> Result<bool, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let query = login_history
+      .filter(local_user_id.eq(form.local_user_id))
+      .filter(ip_network.is_not_distinct_from(&form.ip_network))
+      .filter(device.is_not_distinct_from(&form.device))
+      .filter(published.lt(before));
+    diesel::select(exists(query)).get_result(conn).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      login_history::{LoginHistory, LoginHistoryInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_is_known() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("login_history_user".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let local_user_form = LocalUserInsertForm::builder()
+      .person_id(inserted_person.id)
+      .password_encrypted("123456".to_string())
+      .build();
+    let inserted_local_user = LocalUser::create(pool, &local_user_form).await.unwrap();
+
+    let form = LoginHistoryInsertForm {
+      local_user_id: inserted_local_user.id,
+      ip_network: Some("192.0.2.0/24".to_string()),
+      device: None,
+    };
+    let login = LoginHistory::create(pool, &form).await.unwrap();
+    let later = login.published + Duration::seconds(1);
+    assert!(LoginHistory::is_known(pool, &form, later).await.unwrap());
+    // The login itself doesn't count
+    assert!(!LoginHistory::is_known(pool, &form, login.published)
+      .await
+      .unwrap());
+
+    let other_device = LoginHistoryInsertForm {
+      device: Some("Firefox on Linux".to_string()),
+      ..form
+    };
+    assert!(!LoginHistory::is_known(pool, &other_device, naive_now())
+      .await
+      .unwrap());
+
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
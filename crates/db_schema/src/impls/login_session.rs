@@ -0,0 +1,196 @@
+use crate::{
+  newtypes::{LocalUserId, LoginSessionId},
+  schema::login_session::dsl::{expires, id, local_user_id, login_session, published, token_hash},
+  source::login_session::{LoginSession, LoginSessionInsertForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{
+  dsl::{insert_into, now},
+  result::Error,
+  ExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl LoginSession {
+  pub async fn create(pool: &mut DbPool<'_>, form: &LoginSessionInsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(login_session)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// The user who is logged in with the token of the given hash. As the session is in use, it is
+  /// extended until the given time.
+  pub async fn validate(
+    pool: &mut DbPool<'_>,
+    hash: &str,
+    new_expires: chrono::NaiveDateTime,
+  ) -> Result<LocalUserId, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      login_session
+        .filter(token_hash.eq(hash))
+        .filter(expires.gt(now)),
+    )
+    .set(expires.eq(new_expires))
+    .returning(local_user_id)
+    .get_result::<LocalUserId>(conn)
+    .await
+  }
+
+  /// All active logins of a user, newest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    login_session
+      .filter(local_user_id.eq(for_local_user_id))
+      .filter(expires.gt(now))
+      .order_by(published.desc())
+      .load::<Self>(conn)
+      .await
+  }
+
+  pub async fn delete(pool: &mut DbPool<'_>, hash: &str) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(login_session.filter(token_hash.eq(hash)))
+      .execute(conn)
+      .await
+  }
+
+  /// Ends one of the user's sessions, eg after being alerted about a login from a new device.
+  pub async fn revoke(
+    pool: &mut DbPool<'_>,
+    login_session_id: LoginSessionId,
+    for_local_user_id: LocalUserId,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      login_session
+        .filter(id.eq(login_session_id))
+        .filter(local_user_id.eq(for_local_user_id)),
+    )
+    .get_result::<Self>(conn)
+    .await
+  }
+
+  /// Logs the user out everywhere.
+  pub async fn delete_for_user(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(login_session.filter(local_user_id.eq(for_local_user_id)))
+      .execute(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    newtypes::LocalUserId,
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      login_session::{LoginSession, LoginSessionInsertForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_login_session() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("session_user".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let local_user_form = LocalUserInsertForm::builder()
+      .person_id(inserted_person.id)
+      .password_encrypted("123456".to_string())
+      .build();
+    let inserted_local_user = LocalUser::create(pool, &local_user_form).await.unwrap();
+
+    let form = |hash: &str| LoginSessionInsertForm {
+      token_hash: hash.to_string(),
+      local_user_id: inserted_local_user.id,
+      ip: Some("127.0.0.1".to_string()),
+      user_agent: None,
+      expires: naive_now() + Duration::days(1),
+    };
+    let extended = naive_now() + Duration::days(2);
+    LoginSession::create(pool, &form("first")).await.unwrap();
+    let revoked = LoginSession::create(pool, &form("revoked")).await.unwrap();
+    let second = LoginSession::create(pool, &form("second")).await.unwrap();
+
+    let local_user_id = LoginSession::validate(pool, "first", extended)
+      .await
+      .unwrap();
+    assert_eq!(inserted_local_user.id, local_user_id);
+    assert!(LoginSession::validate(pool, "unknown", extended)
+      .await
+      .is_err());
+
+    // Expired sessions can't be used anymore
+    let expired = LoginSessionInsertForm {
+      expires: naive_now() - Duration::days(1),
+      ..form("expired")
+    };
+    LoginSession::create(pool, &expired).await.unwrap();
+    assert!(LoginSession::validate(pool, "expired", extended)
+      .await
+      .is_err());
+
+    // Logging out only ends that session
+    assert_eq!(1, LoginSession::delete(pool, "first").await.unwrap());
+    assert!(LoginSession::validate(pool, "first", extended)
+      .await
+      .is_err());
+    let sessions = LoginSession::list(pool, inserted_local_user.id)
+      .await
+      .unwrap();
+    assert_eq!(vec![second.clone(), revoked.clone()], sessions);
+
+    // Sessions can only be revoked by their own user
+    let other_user_id = LocalUserId(inserted_local_user.id.0 + 1);
+    assert!(LoginSession::revoke(pool, revoked.id, other_user_id)
+      .await
+      .is_err());
+    let deleted = LoginSession::revoke(pool, revoked.id, inserted_local_user.id)
+      .await
+      .unwrap();
+    assert_eq!(revoked, deleted);
+    assert!(LoginSession::validate(pool, "revoked", extended)
+      .await
+      .is_err());
+
+    let deleted = LoginSession::delete_for_user(pool, inserted_local_user.id)
+      .await
+      .unwrap();
+    assert_eq!(2, deleted);
+    assert!(LoginSession::validate(pool, "second", extended)
+      .await
+      .is_err());
+
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -0,0 +1,126 @@
+use crate::{
+  newtypes::LocalUserId,
+  schema::magic_login_token::dsl::{
+    expires,
+    local_user_id,
+    magic_login_token,
+    published,
+    token_hash,
+  },
+  source::magic_login_token::{MagicLoginToken, MagicLoginTokenForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{
+  dsl::{insert_into, now, IntervalDsl},
+  result::Error,
+  ExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl MagicLoginToken {
+  pub async fn create(pool: &mut DbPool<'_>, form: &MagicLoginTokenForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(magic_login_token)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// Uses up the token of the given hash, and returns the user it logs in. Fails if the token is
+  /// unknown, expired or was already used.
+  pub async fn redeem(pool: &mut DbPool<'_>, hash: &str) -> Result<LocalUserId, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      magic_login_token
+        .filter(token_hash.eq(hash))
+        .filter(expires.gt(now)),
+    )
+    .returning(local_user_id)
+    .get_result::<LocalUserId>(conn)
+    .await
+  }
+
+  /// The number of login links sent to the user within the last hour.
+  pub async fn get_recent_count(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    magic_login_token
+      .filter(local_user_id.eq(for_local_user_id))
+      .filter(published.gt(now - 1.hours()))
+      .count()
+      .get_result(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      magic_login_token::{MagicLoginToken, MagicLoginTokenForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_magic_login_token() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("magic_link_user".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let local_user_form = LocalUserInsertForm::builder()
+      .person_id(inserted_person.id)
+      .password_encrypted("123456".to_string())
+      .build();
+    let inserted_local_user = LocalUser::create(pool, &local_user_form).await.unwrap();
+
+    let form = |hash: &str, expires| MagicLoginTokenForm {
+      token_hash: hash.to_string(),
+      local_user_id: inserted_local_user.id,
+      expires,
+    };
+    let in_a_while = naive_now() + Duration::minutes(15);
+    MagicLoginToken::create(pool, &form("valid", in_a_while))
+      .await
+      .unwrap();
+    let expired = naive_now() - Duration::minutes(1);
+    MagicLoginToken::create(pool, &form("expired", expired))
+      .await
+      .unwrap();
+    let count = MagicLoginToken::get_recent_count(pool, inserted_local_user.id)
+      .await
+      .unwrap();
+    assert_eq!(2, count);
+
+    // Tokens can only be used once, and not after they expire
+    let local_user_id = MagicLoginToken::redeem(pool, "valid").await.unwrap();
+    assert_eq!(inserted_local_user.id, local_user_id);
+    assert!(MagicLoginToken::redeem(pool, "valid").await.is_err());
+    assert!(MagicLoginToken::redeem(pool, "expired").await.is_err());
+    assert!(MagicLoginToken::redeem(pool, "unknown").await.is_err());
+
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
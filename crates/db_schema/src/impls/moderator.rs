@@ -1,5 +1,7 @@
 use crate::{
   source::moderator::{
+    AdminMergePerson,
+    AdminMergePersonForm,
     AdminPurgeComment,
     AdminPurgeCommentForm,
     AdminPurgeCommunity,
@@ -28,6 +30,8 @@ use crate::{
     ModRemoveCommunityForm,
     ModRemovePost,
     ModRemovePostForm,
+    ModShadowban,
+    ModShadowbanForm,
     ModTransferCommunity,
     ModTransferCommunityForm,
   },
@@ -236,6 +240,35 @@ impl Crud for ModBan {
   }
 }
 
+#[async_trait]
+impl Crud for ModShadowban {
+  type InsertForm = ModShadowbanForm;
+  type UpdateForm = ModShadowbanForm;
+  type IdType = i32;
+
+  async fn create(pool: &mut DbPool<'_>, form: &ModShadowbanForm) -> Result<Self, Error> {
+    use crate::schema::mod_shadowban::dsl::mod_shadowban;
+    let conn = &mut get_conn(pool).await?;
+    insert_into(mod_shadowban)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    from_id: i32,
+    form: &ModShadowbanForm,
+  ) -> Result<Self, Error> {
+    use crate::schema::mod_shadowban::dsl::mod_shadowban;
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(mod_shadowban.find(from_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
 #[async_trait]
 impl Crud for ModHideCommunity {
   type InsertForm = ModHideCommunityForm;
@@ -348,6 +381,35 @@ impl Crud for ModAdd {
   }
 }
 
+#[async_trait]
+impl Crud for AdminMergePerson {
+  type InsertForm = AdminMergePersonForm;
+  type UpdateForm = AdminMergePersonForm;
+  type IdType = i32;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    use crate::schema::admin_merge_person::dsl::admin_merge_person;
+    let conn = &mut get_conn(pool).await?;
+    insert_into(admin_merge_person)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    from_id: i32,
+    form: &Self::InsertForm,
+  ) -> Result<Self, Error> {
+    use crate::schema::admin_merge_person::dsl::admin_merge_person;
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(admin_merge_person.find(from_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
 #[async_trait]
 impl Crud for AdminPurgePerson {
   type InsertForm = AdminPurgePersonForm;
@@ -724,7 +786,7 @@ mod tests {
     // mod add community
 
     let mod_add_community_form = ModAddCommunityForm {
-      mod_person_id: inserted_mod.id,
+      mod_person_id: Some(inserted_mod.id),
       other_person_id: inserted_person.id,
       community_id: inserted_community.id,
       removed: None,
@@ -738,7 +800,7 @@ mod tests {
     let expected_mod_add_community = ModAddCommunity {
       id: inserted_mod_add_community.id,
       community_id: inserted_community.id,
-      mod_person_id: inserted_mod.id,
+      mod_person_id: Some(inserted_mod.id),
       other_person_id: inserted_person.id,
       removed: false,
       when_: inserted_mod_add_community.when_,
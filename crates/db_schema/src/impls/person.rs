@@ -1,6 +1,18 @@
 use crate::{
-  newtypes::{CommunityId, DbUrl, PersonId},
-  schema::{instance, local_user, person, person_follower},
+  newtypes::{CommentId, CommunityId, DbUrl, PersonId, PostId},
+  schema::{
+    comment,
+    comment_like,
+    community_follower,
+    instance,
+    local_user,
+    person,
+    person_aggregates,
+    person_follower,
+    post,
+    post_aggregates,
+    post_like,
+  },
   source::person::{
     Person,
     PersonFollower,
@@ -11,7 +23,13 @@ use crate::{
   traits::{ApubActor, Crud, Followable},
   utils::{functions::lower, get_conn, naive_now, DbPool},
 };
-use diesel::{dsl::insert_into, result::Error, ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel::{
+  dsl::{delete, insert_into},
+  result::Error,
+  ExpressionMethods,
+  JoinOnDsl,
+  QueryDsl,
+};
 use diesel_async::RunQueryDsl;
 
 #[async_trait]
@@ -87,6 +105,132 @@ impl Person {
       .get_result::<Self>(conn)
       .await
   }
+
+  /// Moves the posts, comments, community follows and votes of a local person to another person,
+  /// and disables the account. Follows and votes which the other person already has are dropped.
+  /// This only changes the local database, nothing is sent to other instances.
+  pub async fn merge_into(
+    pool: &mut DbPool<'_>,
+    source_id: PersonId,
+    target_id: PersonId,
+  ) -> Result<Person, Error> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .build_transaction()
+      .run(|conn| {
+        Box::pin(async move {
+          diesel::update(post::table.filter(post::creator_id.eq(source_id)))
+            .set(post::creator_id.eq(target_id))
+            .execute(conn)
+            .await?;
+          diesel::update(post_aggregates::table.filter(post_aggregates::creator_id.eq(source_id)))
+            .set(post_aggregates::creator_id.eq(target_id))
+            .execute(conn)
+            .await?;
+          diesel::update(comment::table.filter(comment::creator_id.eq(source_id)))
+            .set(comment::creator_id.eq(target_id))
+            .execute(conn)
+            .await?;
+
+          let target_follows = community_follower::table
+            .filter(community_follower::person_id.eq(target_id))
+            .select(community_follower::community_id)
+            .load::<CommunityId>(conn)
+            .await?;
+          diesel::update(
+            community_follower::table
+              .filter(community_follower::person_id.eq(source_id))
+              .filter(community_follower::community_id.ne_all(target_follows)),
+          )
+          .set(community_follower::person_id.eq(target_id))
+          .execute(conn)
+          .await?;
+          delete(community_follower::table.filter(community_follower::person_id.eq(source_id)))
+            .execute(conn)
+            .await?;
+
+          let target_post_likes = post_like::table
+            .filter(post_like::person_id.eq(target_id))
+            .select(post_like::post_id)
+            .load::<PostId>(conn)
+            .await?;
+          diesel::update(
+            post_like::table
+              .filter(post_like::person_id.eq(source_id))
+              .filter(post_like::post_id.ne_all(target_post_likes)),
+          )
+          .set(post_like::person_id.eq(target_id))
+          .execute(conn)
+          .await?;
+          delete(post_like::table.filter(post_like::person_id.eq(source_id)))
+            .execute(conn)
+            .await?;
+
+          let target_comment_likes = comment_like::table
+            .filter(comment_like::person_id.eq(target_id))
+            .select(comment_like::comment_id)
+            .load::<CommentId>(conn)
+            .await?;
+          diesel::update(
+            comment_like::table
+              .filter(comment_like::person_id.eq(source_id))
+              .filter(comment_like::comment_id.ne_all(target_comment_likes)),
+          )
+          .set(comment_like::person_id.eq(target_id))
+          .execute(conn)
+          .await?;
+          delete(comment_like::table.filter(comment_like::person_id.eq(source_id)))
+            .execute(conn)
+            .await?;
+
+          // The triggers only count new content, so move the counts of the moved content as well
+          let (post_count, post_score, comment_count, comment_score) = person_aggregates::table
+            .filter(person_aggregates::person_id.eq(source_id))
+            .select((
+              person_aggregates::post_count,
+              person_aggregates::post_score,
+              person_aggregates::comment_count,
+              person_aggregates::comment_score,
+            ))
+            .first::<(i64, i64, i64, i64)>(conn)
+            .await?;
+          diesel::update(
+            person_aggregates::table.filter(person_aggregates::person_id.eq(target_id)),
+          )
+          .set((
+            person_aggregates::post_count.eq(person_aggregates::post_count + post_count),
+            person_aggregates::post_score.eq(person_aggregates::post_score + post_score),
+            person_aggregates::comment_count.eq(person_aggregates::comment_count + comment_count),
+            person_aggregates::comment_score.eq(person_aggregates::comment_score + comment_score),
+          ))
+          .execute(conn)
+          .await?;
+          diesel::update(
+            person_aggregates::table.filter(person_aggregates::person_id.eq(source_id)),
+          )
+          .set((
+            person_aggregates::post_count.eq(0),
+            person_aggregates::post_score.eq(0),
+            person_aggregates::comment_count.eq(0),
+            person_aggregates::comment_score.eq(0),
+          ))
+          .execute(conn)
+          .await?;
+
+          diesel::update(person::table.find(source_id))
+            .set((person::deleted.eq(true), person::updated.eq(naive_now())))
+            .get_result::<Self>(conn)
+            .await
+        }) as _
+      })
+      .await
+  }
+
+  /// Whether content published at the given time is hidden by a shadowban. Content from before
+  /// the shadowban stays visible.
+  pub fn is_shadowbanned_for(&self, published: chrono::NaiveDateTime) -> bool {
+    self.shadowbanned_at.is_some_and(|s| published >= s)
+  }
 }
 
 pub fn is_banned(banned_: bool, expires: Option<chrono::NaiveDateTime>) -> bool {
@@ -199,11 +343,20 @@ mod tests {
   #![allow(clippy::indexing_slicing)]
 
   use crate::{
+    aggregates::structs::{
+      CommentAggregates,
+      CommunityAggregates,
+      PersonAggregates,
+      PostAggregates,
+    },
     source::{
+      comment::{Comment, CommentInsertForm, CommentLike, CommentLikeForm},
+      community::{Community, CommunityFollower, CommunityFollowerForm, CommunityInsertForm},
       instance::Instance,
       person::{Person, PersonFollower, PersonFollowerForm, PersonInsertForm, PersonUpdateForm},
+      post::{Post, PostInsertForm, PostLike, PostLikeForm},
     },
-    traits::{Crud, Followable},
+    traits::{Crud, Followable, Likeable},
     utils::build_db_pool_for_tests,
   };
   use serial_test::serial;
@@ -248,6 +401,7 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      shadowbanned_at: None,
       instance_id: inserted_instance.id,
     };
 
@@ -309,4 +463,106 @@ mod tests {
     let unfollow = PersonFollower::unfollow(pool, &follow_form).await.unwrap();
     assert_eq!(1, unfollow);
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_merge_into() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("merge_source".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let source = Person::create(pool, &person_form).await.unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("merge_target".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let target = Person::create(pool, &person_form).await.unwrap();
+
+    let community_form = CommunityInsertForm::builder()
+      .name("merge_community".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let community = Community::create(pool, &community_form).await.unwrap();
+    let post_form = PostInsertForm::builder()
+      .name("A merged post".into())
+      .creator_id(source.id)
+      .community_id(community.id)
+      .build();
+    let post = Post::create(pool, &post_form).await.unwrap();
+    let comment_form = CommentInsertForm::builder()
+      .content("A merged comment".into())
+      .creator_id(source.id)
+      .post_id(post.id)
+      .build();
+    let comment = Comment::create(pool, &comment_form, None).await.unwrap();
+
+    // Both accounts follow the community and voted on the post, only the source on the comment
+    for person_id in [source.id, target.id] {
+      let follower_form = CommunityFollowerForm {
+        community_id: community.id,
+        person_id,
+        pending: false,
+      };
+      CommunityFollower::follow(pool, &follower_form)
+        .await
+        .unwrap();
+      let like_form = PostLikeForm {
+        post_id: post.id,
+        person_id,
+        score: 1,
+      };
+      PostLike::like(pool, &like_form).await.unwrap();
+    }
+    let like_form = CommentLikeForm {
+      person_id: source.id,
+      comment_id: comment.id,
+      post_id: post.id,
+      score: 1,
+    };
+    CommentLike::like(pool, &like_form).await.unwrap();
+
+    let merged = Person::merge_into(pool, source.id, target.id)
+      .await
+      .unwrap();
+    assert!(merged.deleted);
+    assert_eq!(
+      target.id,
+      Post::read(pool, post.id).await.unwrap().creator_id
+    );
+    assert_eq!(
+      target.id,
+      Comment::read(pool, comment.id).await.unwrap().creator_id
+    );
+
+    // The duplicate vote is dropped, the other one is moved
+    let post_aggregates = PostAggregates::read(pool, post.id).await.unwrap();
+    assert_eq!(1, post_aggregates.score);
+    assert_eq!(target.id, post_aggregates.creator_id);
+    let comment_aggregates = CommentAggregates::read(pool, comment.id).await.unwrap();
+    assert_eq!(1, comment_aggregates.score);
+    let community_aggregates = CommunityAggregates::read(pool, community.id).await.unwrap();
+    assert_eq!(1, community_aggregates.subscribers);
+
+    let target_aggregates = PersonAggregates::read(pool, target.id).await.unwrap();
+    assert_eq!(1, target_aggregates.post_count);
+    assert_eq!(1, target_aggregates.comment_count);
+    assert_eq!(1, target_aggregates.comment_score);
+    let source_aggregates = PersonAggregates::read(pool, source.id).await.unwrap();
+    assert_eq!(0, source_aggregates.post_count);
+
+    Community::delete(pool, community.id).await.unwrap();
+    Person::delete(pool, source.id).await.unwrap();
+    Person::delete(pool, target.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
 }
@@ -0,0 +1,163 @@
+use crate::{
+  newtypes::PersonId,
+  schema::{person, person_alias},
+  source::{
+    person::Person,
+    person_alias::{PersonAlias, PersonAliasForm},
+  },
+  utils::{functions::lower, get_conn, naive_now, DbPool},
+};
+use chrono::Duration;
+use diesel::{
+  dsl::{exists, insert_into, select},
+  result::Error,
+  ExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl PersonAlias {
+  pub async fn create(pool: &mut DbPool<'_>, form: &PersonAliasForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(person_alias::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// The former names of a person, oldest first.
+  pub async fn list_for_person(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    person_alias::table
+      .filter(person_alias::person_id.eq(for_person_id))
+      .order_by(person_alias::published.asc())
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Reads the local person who most recently used the given name.
+  pub async fn read_person_from_name(
+    pool: &mut DbPool<'_>,
+    from_name: &str,
+  ) -> Result<Person, Error> {
+    let conn = &mut get_conn(pool).await?;
+    person_alias::table
+      .inner_join(person::table)
+      .filter(lower(person_alias::name).eq(from_name.to_lowercase()))
+      .filter(person::local.eq(true))
+      .filter(person::deleted.eq(false))
+      .order_by(person_alias::published.desc())
+      .select(person::all_columns)
+      .first::<Person>(conn)
+      .await
+  }
+
+  /// Whether another person gave up the name within the last `cooldown_days`, so that it can't be
+  /// taken yet.
+  pub async fn is_name_reserved(
+    pool: &mut DbPool<'_>,
+    name: &str,
+    for_person_id: Option<PersonId>,
+    cooldown_days: i32,
+  ) -> Result<bool, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = person_alias::table
+      .filter(lower(person_alias::name).eq(name.to_lowercase()))
+      .filter(person_alias::published.gt(naive_now() - Duration::days(cooldown_days.into())))
+      .into_boxed();
+    if let Some(for_person_id) = for_person_id {
+      query = query.filter(person_alias::person_id.ne(for_person_id));
+    }
+    select(exists(query)).get_result(conn).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      person_alias::{PersonAlias, PersonAliasForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_person_alias() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("renamed_person".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .local(Some(true))
+      .build();
+    let person = Person::create(pool, &person_form).await.unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("other_person".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let other = Person::create(pool, &person_form).await.unwrap();
+
+    let form = PersonAliasForm {
+      person_id: person.id,
+      name: "Former_Name".to_string(),
+      actor_id: Url::parse("https://my_domain.tld/u/Former_Name")
+        .unwrap()
+        .into(),
+    };
+    let alias = PersonAlias::create(pool, &form).await.unwrap();
+
+    assert_eq!(
+      vec![alias],
+      PersonAlias::list_for_person(pool, person.id).await.unwrap()
+    );
+    assert_eq!(
+      person,
+      PersonAlias::read_person_from_name(pool, "former_name")
+        .await
+        .unwrap()
+    );
+    assert!(PersonAlias::read_person_from_name(pool, "unused_name")
+      .await
+      .is_err());
+
+    // The name is reserved for others, but not for the person who used it
+    assert!(
+      PersonAlias::is_name_reserved(pool, "former_name", Some(other.id), 30)
+        .await
+        .unwrap()
+    );
+    assert!(PersonAlias::is_name_reserved(pool, "former_name", None, 30)
+      .await
+      .unwrap());
+    assert!(
+      !PersonAlias::is_name_reserved(pool, "former_name", Some(person.id), 30)
+        .await
+        .unwrap()
+    );
+    assert!(!PersonAlias::is_name_reserved(pool, "former_name", None, 0)
+      .await
+      .unwrap());
+
+    Person::delete(pool, person.id).await.unwrap();
+    Person::delete(pool, other.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -1,11 +1,17 @@
 use crate::{
   newtypes::PersonId,
-  schema::person_block::dsl::{person_block, person_id, target_id},
+  schema::person_block::dsl::{expires, person_block, person_id, target_id},
   source::person_block::{PersonBlock, PersonBlockForm},
   traits::Blockable,
   utils::{get_conn, DbPool},
 };
-use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel::{
+  dsl::{insert_into, now},
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  QueryDsl,
+};
 use diesel_async::RunQueryDsl;
 
 impl PersonBlock {
@@ -18,6 +24,7 @@ impl PersonBlock {
     person_block
       .filter(person_id.eq(for_person_id))
       .filter(target_id.eq(for_recipient_id))
+      .filter(expires.is_null().or(expires.gt(now)))
       .first::<Self>(conn)
       .await
   }
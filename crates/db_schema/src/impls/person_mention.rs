@@ -1,6 +1,6 @@
 use crate::{
   newtypes::{CommentId, PersonId, PersonMentionId},
-  schema::person_mention::dsl::{comment_id, person_mention, read, recipient_id},
+  schema::person_mention::dsl::{comment_id, person_mention, post_id, read, recipient_id},
   source::person_mention::{PersonMention, PersonMentionInsertForm, PersonMentionUpdateForm},
   traits::Crud,
   utils::{get_conn, DbPool},
@@ -21,13 +21,22 @@ impl Crud for PersonMention {
     let conn = &mut get_conn(pool).await?;
     // since the return here isnt utilized, we dont need to do an update
     // but get_result doesnt return the existing row here
-    insert_into(person_mention)
-      .values(person_mention_form)
-      .on_conflict((recipient_id, comment_id))
-      .do_update()
-      .set(person_mention_form)
-      .get_result::<Self>(conn)
-      .await
+    let insert = insert_into(person_mention).values(person_mention_form);
+    if person_mention_form.post_id.is_some() {
+      insert
+        .on_conflict((recipient_id, post_id))
+        .do_update()
+        .set(person_mention_form)
+        .get_result::<Self>(conn)
+        .await
+    } else {
+      insert
+        .on_conflict((recipient_id, comment_id))
+        .do_update()
+        .set(person_mention_form)
+        .get_result::<Self>(conn)
+        .await
+    }
   }
 
   async fn update(
@@ -145,7 +154,8 @@ mod tests {
 
     let person_mention_form = PersonMentionInsertForm {
       recipient_id: inserted_recipient.id,
-      comment_id: inserted_comment.id,
+      comment_id: Some(inserted_comment.id),
+      post_id: None,
       read: None,
     };
 
@@ -159,6 +169,7 @@ mod tests {
       comment_id: inserted_mention.comment_id,
       read: false,
       published: inserted_mention.published,
+      post_id: None,
     };
 
     let read_mention = PersonMention::read(pool, inserted_mention.id)
@@ -170,6 +181,21 @@ mod tests {
       PersonMention::update(pool, inserted_mention.id, &person_mention_update_form)
         .await
         .unwrap();
+
+    // Mentioning the same person in the same post again only updates the existing row
+    let post_mention_form = PersonMentionInsertForm {
+      recipient_id: inserted_recipient.id,
+      comment_id: None,
+      post_id: Some(inserted_post.id),
+      read: None,
+    };
+    let post_mention = PersonMention::create(pool, &post_mention_form)
+      .await
+      .unwrap();
+    let post_mention_again = PersonMention::create(pool, &post_mention_form)
+      .await
+      .unwrap();
+
     Comment::delete(pool, inserted_comment.id).await.unwrap();
     Post::delete(pool, inserted_post.id).await.unwrap();
     Community::delete(pool, inserted_community.id)
@@ -182,5 +208,8 @@ mod tests {
     assert_eq!(expected_mention, read_mention);
     assert_eq!(expected_mention, inserted_mention);
     assert_eq!(expected_mention, updated_mention);
+    assert_eq!(post_mention.id, post_mention_again.id);
+    assert_eq!(Some(inserted_post.id), post_mention.post_id);
+    assert_eq!(None, post_mention.comment_id);
   }
 }
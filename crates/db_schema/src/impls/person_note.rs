@@ -0,0 +1,135 @@
+use crate::{
+  newtypes::PersonId,
+  schema::person_note::dsl::{person_id, person_note, target_id},
+  source::person_note::{PersonNote, PersonNoteForm},
+  utils::{get_conn, naive_now, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl PersonNote {
+  /// Creates the note of a person about another person, or replaces it.
+  pub async fn set(pool: &mut DbPool<'_>, form: &PersonNoteForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let update_form = PersonNoteForm {
+      updated: Some(naive_now()),
+      ..form.clone()
+    };
+    insert_into(person_note)
+      .values(form)
+      .on_conflict((person_id, target_id))
+      .do_update()
+      .set(&update_form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+    for_target_id: PersonId,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      person_note
+        .filter(person_id.eq(for_person_id))
+        .filter(target_id.eq(for_target_id)),
+    )
+    .execute(conn)
+    .await
+  }
+
+  /// The notes which a person wrote about any of the given people.
+  pub async fn for_targets(
+    pool: &mut DbPool<'_>,
+    for_person_id: PersonId,
+    target_ids: &[PersonId],
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    person_note
+      .filter(person_id.eq(for_person_id))
+      .filter(target_id.eq_any(target_ids))
+      .load::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      person_note::{PersonNote, PersonNoteForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_person_note() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("note_writer".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let writer = Person::create(pool, &person_form).await.unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("note_target".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let target = Person::create(pool, &person_form).await.unwrap();
+
+    let form = PersonNoteForm {
+      person_id: writer.id,
+      target_id: target.id,
+      note: "helpful".to_string(),
+      color: Some("#00ff00".to_string()),
+      updated: None,
+    };
+    let created = PersonNote::set(pool, &form).await.unwrap();
+    assert!(created.updated.is_none());
+
+    // Setting it again replaces the note
+    let form = PersonNoteForm {
+      note: "argued with me".to_string(),
+      color: None,
+      ..form
+    };
+    let replaced = PersonNote::set(pool, &form).await.unwrap();
+    assert_eq!(created.id, replaced.id);
+    assert!(replaced.color.is_none());
+    assert!(replaced.updated.is_some());
+
+    // Notes are only returned to their writer
+    let notes = PersonNote::for_targets(pool, writer.id, &[target.id, writer.id])
+      .await
+      .unwrap();
+    assert_eq!(vec![replaced], notes);
+    let notes = PersonNote::for_targets(pool, target.id, &[writer.id])
+      .await
+      .unwrap();
+    assert!(notes.is_empty());
+
+    let deleted = PersonNote::delete(pool, writer.id, target.id)
+      .await
+      .unwrap();
+    assert_eq!(1, deleted);
+
+    Person::delete(pool, writer.id).await.unwrap();
+    Person::delete(pool, target.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
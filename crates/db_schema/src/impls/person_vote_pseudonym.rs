@@ -0,0 +1,113 @@
+use crate::{
+  newtypes::PersonId,
+  schema::person_vote_pseudonym::dsl::{person_id, person_vote_pseudonym, pseudonym_id},
+  source::person_vote_pseudonym::{PersonVotePseudonym, PersonVotePseudonymForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{
+  dsl::{exists, insert_into, select},
+  result::Error,
+  ExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl PersonVotePseudonym {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &PersonVotePseudonymForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(person_vote_pseudonym)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, for_person_id: PersonId) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    person_vote_pseudonym
+      .filter(person_id.eq(for_person_id))
+      .first::<Self>(conn)
+      .await
+  }
+
+  /// Whether the given person is a vote pseudonym for some local user.
+  pub async fn is_pseudonym(pool: &mut DbPool<'_>, for_person_id: PersonId) -> Result<bool, Error> {
+    let conn = &mut get_conn(pool).await?;
+    select(exists(
+      person_vote_pseudonym.filter(pseudonym_id.eq(for_person_id)),
+    ))
+    .get_result(conn)
+    .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      person_vote_pseudonym::{PersonVotePseudonym, PersonVotePseudonymForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crud() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let person_form = PersonInsertForm::builder()
+      .name("terry_pseudonym".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+
+    let pseudonym_form = PersonInsertForm::builder()
+      .name("voter_pseudonym".into())
+      .public_key("pubkey".to_string())
+      .bot_account(Some(true))
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_pseudonym = Person::create(pool, &pseudonym_form).await.unwrap();
+
+    let form = PersonVotePseudonymForm {
+      person_id: inserted_person.id,
+      pseudonym_id: inserted_pseudonym.id,
+    };
+    let inserted = PersonVotePseudonym::create(pool, &form).await.unwrap();
+    let read = PersonVotePseudonym::read(pool, inserted_person.id)
+      .await
+      .unwrap();
+    let pseudonym_is_pseudonym = PersonVotePseudonym::is_pseudonym(pool, inserted_pseudonym.id)
+      .await
+      .unwrap();
+    let person_is_pseudonym = PersonVotePseudonym::is_pseudonym(pool, inserted_person.id)
+      .await
+      .unwrap();
+
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    let read_after_delete = PersonVotePseudonym::read(pool, inserted_person.id).await;
+    Person::delete(pool, inserted_pseudonym.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert_eq!(inserted, read);
+    assert_eq!(inserted_pseudonym.id, read.pseudonym_id);
+    assert!(pseudonym_is_pseudonym);
+    assert!(!person_is_pseudonym);
+    assert!(read_after_delete.is_err());
+  }
+}
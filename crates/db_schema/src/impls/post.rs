@@ -1,36 +1,52 @@
 use crate::{
   newtypes::{CommunityId, DbUrl, PersonId, PostId},
-  schema::post::dsl::{
-    ap_id,
-    body,
-    community_id,
-    creator_id,
-    deleted,
-    featured_community,
-    name,
-    post,
-    published,
-    removed,
-    thumbnail_url,
-    updated,
-    url,
+  schema::{
+    person,
+    post::dsl::{
+      ap_id,
+      body,
+      community_id,
+      creator_id,
+      deleted,
+      featured_community,
+      id,
+      name,
+      post,
+      published,
+      removed,
+      thumbnail_url,
+      updated,
+      url,
+    },
   },
   source::post::{
     Post,
     PostInsertForm,
     PostLike,
     PostLikeForm,
+    PostMetadataRefetch,
+    PostMetadataRefetchForm,
+    PostReaction,
+    PostReactionForm,
     PostRead,
     PostReadForm,
     PostSaved,
     PostSavedForm,
     PostUpdateForm,
   },
-  traits::{Crud, Likeable, Readable, Saveable},
+  traits::{Crud, Likeable, Reactable, Readable, Saveable},
   utils::{get_conn, naive_now, DbPool, DELETED_REPLACEMENT_TEXT, FETCH_LIMIT_MAX},
 };
 use ::url::Url;
-use diesel::{dsl::insert_into, result::Error, ExpressionMethods, QueryDsl, TextExpressionMethods};
+use diesel::{
+  dsl::{count_star, insert_into},
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+  TextExpressionMethods,
+};
 use diesel_async::RunQueryDsl;
 
 #[async_trait]
@@ -64,22 +80,121 @@ impl Crud for Post {
 }
 
 impl Post {
+  /// A page of the posts in a community which are federated in its outbox, newest first. Posts of
+  /// shadowbanned users are left out. The next page starts below the id of the last post.
   pub async fn list_for_community(
     pool: &mut DbPool<'_>,
     the_community_id: CommunityId,
+    max_id: Option<PostId>,
+    limit: i64,
   ) -> Result<Vec<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
-    post
+    let mut query = post
+      .inner_join(person::table)
       .filter(community_id.eq(the_community_id))
       .filter(deleted.eq(false))
       .filter(removed.eq(false))
-      .then_order_by(featured_community.desc())
-      .then_order_by(published.desc())
-      .limit(FETCH_LIMIT_MAX)
+      .filter(
+        person::shadowbanned_at
+          .is_null()
+          .or(published.nullable().lt(person::shadowbanned_at)),
+      )
+      .select(crate::schema::post::all_columns)
+      .into_boxed();
+    if let Some(max_id) = max_id {
+      query = query.filter(id.lt(max_id));
+    }
+    query
+      .order_by(id.desc())
+      .limit(limit)
       .load::<Self>(conn)
       .await
   }
 
+  /// Number of posts which [Post::list_for_community] returns over all pages
+  pub async fn count_for_community(
+    pool: &mut DbPool<'_>,
+    the_community_id: CommunityId,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post
+      .inner_join(person::table)
+      .filter(community_id.eq(the_community_id))
+      .filter(deleted.eq(false))
+      .filter(removed.eq(false))
+      .filter(
+        person::shadowbanned_at
+          .is_null()
+          .or(published.nullable().lt(person::shadowbanned_at)),
+      )
+      .select(count_star())
+      .first::<i64>(conn)
+      .await
+  }
+
+  /// All posts in the community including removed and deleted ones, together with the actor id of
+  /// their creator, for community archives. Posts are returned in batches ordered by id, the next
+  /// batch starts above the id of the last post.
+  pub async fn list_for_archive(
+    pool: &mut DbPool<'_>,
+    the_community_id: CommunityId,
+    min_id: Option<PostId>,
+    limit: i64,
+  ) -> Result<Vec<(Self, DbUrl)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = post
+      .inner_join(person::table)
+      .filter(community_id.eq(the_community_id))
+      .select((crate::schema::post::all_columns, person::actor_id))
+      .into_boxed();
+    if let Some(min_id) = min_id {
+      query = query.filter(id.gt(min_id));
+    }
+    query
+      .order_by(id.asc())
+      .limit(limit)
+      .load::<(Self, DbUrl)>(conn)
+      .await
+  }
+
+  /// Whether vote counts should currently be hidden from non-mods.
+  pub fn in_contest_mode(&self) -> bool {
+    self.contest_mode_until.is_some_and(|u| u > naive_now())
+  }
+
+  /// When the person last posted in the community, used for slow mode.
+  pub async fn latest_published_for_creator(
+    pool: &mut DbPool<'_>,
+    for_creator_id: PersonId,
+    the_community_id: CommunityId,
+  ) -> Result<Option<chrono::NaiveDateTime>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post
+      .filter(creator_id.eq(for_creator_id))
+      .filter(community_id.eq(the_community_id))
+      .select(diesel::dsl::max(published))
+      .first::<Option<chrono::NaiveDateTime>>(conn)
+      .await
+  }
+
+  /// How many posts the person created since the given time with the same title, used to detect
+  /// spam.
+  pub async fn count_duplicates_for_creator(
+    pool: &mut DbPool<'_>,
+    for_creator_id: PersonId,
+    title: &str,
+    since: chrono::NaiveDateTime,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    post
+      .filter(creator_id.eq(for_creator_id))
+      .filter(name.eq(title))
+      .filter(published.gt(since))
+      .count()
+      .get_result::<i64>(conn)
+      .await
+  }
+
   pub async fn list_featured_for_community(
     pool: &mut DbPool<'_>,
     the_community_id: CommunityId,
@@ -257,6 +372,73 @@ impl Likeable for PostLike {
   }
 }
 
+#[async_trait]
+impl Reactable for PostReaction {
+  type Form = PostReactionForm;
+  type IdType = PostId;
+  async fn react(pool: &mut DbPool<'_>, form: &PostReactionForm) -> Result<Self, Error> {
+    use crate::schema::post_reaction::dsl::{emoji, person_id, post_id, post_reaction};
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_reaction)
+      .values(form)
+      .on_conflict((post_id, person_id, emoji))
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+  async fn unreact(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    post_id: PostId,
+    emoji: &str,
+  ) -> Result<usize, Error> {
+    use crate::schema::post_reaction::dsl;
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      dsl::post_reaction
+        .filter(dsl::post_id.eq(post_id))
+        .filter(dsl::person_id.eq(person_id))
+        .filter(dsl::emoji.eq(emoji)),
+    )
+    .execute(conn)
+    .await
+  }
+}
+
+impl PostReaction {
+  /// Counts the reactions for each emoji on the given posts.
+  pub async fn counts(
+    pool: &mut DbPool<'_>,
+    post_ids: &[PostId],
+  ) -> Result<Vec<(PostId, String, i64)>, Error> {
+    use crate::schema::post_reaction::dsl;
+    let conn = &mut get_conn(pool).await?;
+    dsl::post_reaction
+      .filter(dsl::post_id.eq_any(post_ids))
+      .group_by((dsl::post_id, dsl::emoji))
+      .select((dsl::post_id, dsl::emoji, count_star()))
+      .load::<(PostId, String, i64)>(conn)
+      .await
+  }
+
+  /// The emojis a person reacted with on the given posts.
+  pub async fn for_person(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    post_ids: &[PostId],
+  ) -> Result<Vec<(PostId, String)>, Error> {
+    use crate::schema::post_reaction::dsl;
+    let conn = &mut get_conn(pool).await?;
+    dsl::post_reaction
+      .filter(dsl::post_id.eq_any(post_ids))
+      .filter(dsl::person_id.eq(person_id))
+      .select((dsl::post_id, dsl::emoji))
+      .load::<(PostId, String)>(conn)
+      .await
+  }
+}
+
 #[async_trait]
 impl Saveable for PostSaved {
   type Form = PostSavedForm;
@@ -318,6 +500,42 @@ impl Readable for PostRead {
   }
 }
 
+impl PostMetadataRefetch {
+  /// After this many failed retries the post is given up on.
+  pub const MAX_ATTEMPTS: i32 = 6;
+
+  /// When to retry after the given number of failed attempts, doubling the delay from one hour.
+  pub fn next_attempt_after(attempts: i32) -> chrono::NaiveDateTime {
+    naive_now() + chrono::Duration::hours(1 << attempts.clamp(0, Self::MAX_ATTEMPTS))
+  }
+
+  /// Marks the post for a later metadata refetch, restarting the backoff if it was already marked.
+  pub async fn schedule(pool: &mut DbPool<'_>, for_post_id: PostId) -> Result<Self, Error> {
+    use crate::schema::post_metadata_refetch::dsl::{post_id, post_metadata_refetch};
+    let conn = &mut get_conn(pool).await?;
+    let form = PostMetadataRefetchForm {
+      post_id: for_post_id,
+      attempts: 0,
+      next_attempt: Self::next_attempt_after(0),
+    };
+    insert_into(post_metadata_refetch)
+      .values(&form)
+      .on_conflict(post_id)
+      .do_update()
+      .set(&form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn delete(pool: &mut DbPool<'_>, for_post_id: PostId) -> Result<usize, Error> {
+    use crate::schema::post_metadata_refetch::dsl::post_metadata_refetch;
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(post_metadata_refetch.find(for_post_id))
+      .execute(conn)
+      .await
+  }
+}
+
 #[cfg(test)]
 mod tests {
   #![allow(clippy::unwrap_used)]
@@ -333,6 +551,9 @@ mod tests {
         PostInsertForm,
         PostLike,
         PostLikeForm,
+        PostMetadataRefetch,
+        PostReaction,
+        PostReactionForm,
         PostRead,
         PostReadForm,
         PostSaved,
@@ -340,7 +561,7 @@ mod tests {
         PostUpdateForm,
       },
     },
-    traits::{Crud, Likeable, Readable, Saveable},
+    traits::{Crud, Likeable, Reactable, Readable, Saveable},
     utils::build_db_pool_for_tests,
   };
   use serial_test::serial;
@@ -402,6 +623,9 @@ mod tests {
       language_id: Default::default(),
       featured_community: false,
       featured_local: false,
+      contest_mode_until: None,
+      audio_mime_type: None,
+      audio_duration: None,
     };
 
     // Post Like
@@ -421,6 +645,36 @@ mod tests {
       score: 1,
     };
 
+    // Post Reaction
+    let post_reaction_form = PostReactionForm {
+      post_id: inserted_post.id,
+      person_id: inserted_person.id,
+      emoji: "🎉".into(),
+    };
+
+    let inserted_post_reaction = PostReaction::react(pool, &post_reaction_form)
+      .await
+      .unwrap();
+    // Reacting twice with the same emoji is a no-op
+    PostReaction::react(pool, &post_reaction_form)
+      .await
+      .unwrap();
+
+    let expected_post_reaction = PostReaction {
+      id: inserted_post_reaction.id,
+      post_id: inserted_post.id,
+      person_id: inserted_person.id,
+      emoji: "🎉".into(),
+      published: inserted_post_reaction.published,
+    };
+
+    let reaction_counts = PostReaction::counts(pool, &[inserted_post.id])
+      .await
+      .unwrap();
+    let my_reactions = PostReaction::for_person(pool, inserted_person.id, &[inserted_post.id])
+      .await
+      .unwrap();
+
     // Post Save
     let post_saved_form = PostSavedForm {
       post_id: inserted_post.id,
@@ -451,7 +705,19 @@ mod tests {
       published: inserted_post_read.published,
     };
 
+    // Metadata refetch
+    let scheduled_refetch = PostMetadataRefetch::schedule(pool, inserted_post.id)
+      .await
+      .unwrap();
+    let rescheduled_refetch = PostMetadataRefetch::schedule(pool, inserted_post.id)
+      .await
+      .unwrap();
+
     let read_post = Post::read(pool, inserted_post.id).await.unwrap();
+    let latest_published =
+      Post::latest_published_for_creator(pool, inserted_person.id, inserted_community.id)
+        .await
+        .unwrap();
 
     let new_post_update = PostUpdateForm::builder()
       .name(Some("A test post".into()))
@@ -460,13 +726,42 @@ mod tests {
       .await
       .unwrap();
 
+    // The outbox is paginated by descending post id
+    let second_post = Post::create(pool, &new_post).await.unwrap();
+    let first_page = Post::list_for_community(pool, inserted_community.id, None, 1)
+      .await
+      .unwrap();
+    let second_page =
+      Post::list_for_community(pool, inserted_community.id, Some(second_post.id), 1)
+        .await
+        .unwrap();
+    let outbox_count = Post::count_for_community(pool, inserted_community.id)
+      .await
+      .unwrap();
+    assert_eq!(
+      vec![second_post.id],
+      first_page.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+    assert_eq!(
+      vec![inserted_post.id],
+      second_page.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+    assert_eq!(2, outbox_count);
+    Post::delete(pool, second_post.id).await.unwrap();
+
     let like_removed = PostLike::remove(pool, inserted_person.id, inserted_post.id)
       .await
       .unwrap();
+    let reaction_removed = PostReaction::unreact(pool, inserted_person.id, inserted_post.id, "🎉")
+      .await
+      .unwrap();
     let saved_removed = PostSaved::unsave(pool, &post_saved_form).await.unwrap();
     let read_removed = PostRead::mark_as_unread(pool, &post_read_form)
       .await
       .unwrap();
+    let refetch_removed = PostMetadataRefetch::delete(pool, inserted_post.id)
+      .await
+      .unwrap();
     let num_deleted = Post::delete(pool, inserted_post.id).await.unwrap();
     Community::delete(pool, inserted_community.id)
       .await
@@ -475,14 +770,38 @@ mod tests {
     Instance::delete(pool, inserted_instance.id).await.unwrap();
 
     assert_eq!(expected_post, read_post);
+    assert_eq!(Some(inserted_post.published), latest_published);
     assert_eq!(expected_post, inserted_post);
     assert_eq!(expected_post, updated_post);
     assert_eq!(expected_post_like, inserted_post_like);
     assert_eq!(expected_post_saved, inserted_post_saved);
     assert_eq!(expected_post_read, inserted_post_read);
+    assert_eq!(expected_post_reaction, inserted_post_reaction);
+    assert_eq!(
+      vec![(inserted_post.id, "🎉".to_string(), 1)],
+      reaction_counts
+    );
+    assert_eq!(vec![(inserted_post.id, "🎉".to_string())], my_reactions);
     assert_eq!(1, like_removed);
+    assert_eq!(1, reaction_removed);
     assert_eq!(1, saved_removed);
     assert_eq!(1, read_removed);
+    assert_eq!(0, scheduled_refetch.attempts);
+    assert_eq!(0, rescheduled_refetch.attempts);
+    assert!(rescheduled_refetch.next_attempt > read_post.published);
+    assert_eq!(1, refetch_removed);
     assert_eq!(1, num_deleted);
   }
+
+  #[test]
+  fn test_metadata_refetch_backoff() {
+    let first = PostMetadataRefetch::next_attempt_after(0);
+    let second = PostMetadataRefetch::next_attempt_after(1);
+    let last = PostMetadataRefetch::next_attempt_after(PostMetadataRefetch::MAX_ATTEMPTS);
+    let capped = PostMetadataRefetch::next_attempt_after(100);
+
+    assert!((second - first).num_minutes() >= 59);
+    assert!(last > second);
+    assert!((capped - last).num_seconds() < 60);
+  }
 }
@@ -1,6 +1,14 @@
 use crate::{
   newtypes::{DbUrl, PersonId, PrivateMessageId},
-  schema::private_message::dsl::{ap_id, private_message, read, recipient_id},
+  schema::private_message::dsl::{
+    ap_id,
+    content,
+    creator_id,
+    private_message,
+    published,
+    read,
+    recipient_id,
+  },
   source::private_message::{PrivateMessage, PrivateMessageInsertForm, PrivateMessageUpdateForm},
   traits::Crud,
   utils::{get_conn, DbPool},
@@ -56,6 +64,24 @@ impl PrivateMessage {
     .await
   }
 
+  /// How many private messages the person sent since the given time with the same content, used
+  /// to detect spam.
+  pub async fn count_duplicates_for_creator(
+    pool: &mut DbPool<'_>,
+    for_creator_id: PersonId,
+    for_content: &str,
+    since: chrono::NaiveDateTime,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    private_message
+      .filter(creator_id.eq(for_creator_id))
+      .filter(content.eq(for_content))
+      .filter(published.gt(since))
+      .count()
+      .get_result::<i64>(conn)
+      .await
+  }
+
   pub async fn read_from_apub_id(
     pool: &mut DbPool<'_>,
     object_id: Url,
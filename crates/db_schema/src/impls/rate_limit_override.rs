@@ -0,0 +1,171 @@
+use crate::{
+  newtypes::LocalUserId,
+  schema::{local_user, login_session, person, rate_limit_override},
+  source::{
+    person::Person,
+    rate_limit_override::{RateLimitOverride, RateLimitOverrideForm},
+  },
+  utils::{get_conn, DbPool},
+};
+use diesel::{
+  dsl::{insert_into, now},
+  result::Error,
+  ExpressionMethods,
+  JoinOnDsl,
+  OptionalExtension,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+
+impl RateLimitOverride {
+  /// Sets the rate limits of a user, replacing a previous override
+  pub async fn set(pool: &mut DbPool<'_>, form: &RateLimitOverrideForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(rate_limit_override::table)
+      .values(form)
+      .on_conflict(rate_limit_override::local_user_id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  pub async fn remove(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      rate_limit_override::table.filter(rate_limit_override::local_user_id.eq(local_user_id)),
+    )
+    .execute(conn)
+    .await
+  }
+
+  /// The rate limit of a user in percent of the site rate limits, if they have an override
+  pub async fn read_limit_percent(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> Result<Option<i32>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    rate_limit_override::table
+      .filter(rate_limit_override::local_user_id.eq(local_user_id))
+      .select(rate_limit_override::limit_percent)
+      .first(conn)
+      .await
+      .optional()
+  }
+
+  /// Lists all overrides together with the affected users
+  pub async fn list(pool: &mut DbPool<'_>) -> Result<Vec<(Self, Person)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    rate_limit_override::table
+      .inner_join(local_user::table.inner_join(person::table))
+      .select((rate_limit_override::all_columns, person::all_columns))
+      .order_by(person::name)
+      .load(conn)
+      .await
+  }
+
+  /// The hashes of all login tokens of users with overrides, with the user and their limit.
+  pub async fn list_by_token_hash(
+    pool: &mut DbPool<'_>,
+  ) -> Result<Vec<(String, LocalUserId, i32)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    rate_limit_override::table
+      .inner_join(
+        login_session::table
+          .on(login_session::local_user_id.eq(rate_limit_override::local_user_id)),
+      )
+      .filter(login_session::expires.gt(now))
+      .select((
+        login_session::token_hash,
+        rate_limit_override::local_user_id,
+        rate_limit_override::limit_percent,
+      ))
+      .load(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      local_user::{LocalUser, LocalUserInsertForm},
+      login_session::{LoginSession, LoginSessionInsertForm},
+      person::{Person, PersonInsertForm},
+      rate_limit_override::{RateLimitOverride, RateLimitOverrideForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use chrono::Duration;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_set_and_remove() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("rate_limit_bot".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let local_user_form = LocalUserInsertForm::builder()
+      .person_id(inserted_person.id)
+      .password_encrypted("123456".to_string())
+      .build();
+    let inserted_local_user = LocalUser::create(pool, &local_user_form).await.unwrap();
+
+    let mut form = RateLimitOverrideForm {
+      local_user_id: inserted_local_user.id,
+      limit_percent: 500,
+    };
+    RateLimitOverride::set(pool, &form).await.unwrap();
+    form.limit_percent = 50;
+    RateLimitOverride::set(pool, &form).await.unwrap();
+
+    let list = RateLimitOverride::list(pool).await.unwrap();
+    assert_eq!(1, list.len());
+    assert_eq!(50, list[0].0.limit_percent);
+    assert_eq!(inserted_person.id, list[0].1.id);
+
+    // The rate limiter recognizes the user by their login tokens
+    let session_form = LoginSessionInsertForm {
+      token_hash: "hash".to_string(),
+      local_user_id: inserted_local_user.id,
+      ip: None,
+      user_agent: None,
+      expires: naive_now() + Duration::days(1),
+    };
+    LoginSession::create(pool, &session_form).await.unwrap();
+    let by_token_hash = RateLimitOverride::list_by_token_hash(pool).await.unwrap();
+    assert_eq!(
+      vec![("hash".to_string(), inserted_local_user.id, 50)],
+      by_token_hash
+    );
+    assert_eq!(
+      Some(50),
+      RateLimitOverride::read_limit_percent(pool, inserted_local_user.id)
+        .await
+        .unwrap()
+    );
+
+    let removed = RateLimitOverride::remove(pool, inserted_local_user.id)
+      .await
+      .unwrap();
+    assert_eq!(1, removed);
+    assert!(RateLimitOverride::list(pool).await.unwrap().is_empty());
+
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
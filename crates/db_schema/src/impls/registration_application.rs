@@ -9,7 +9,7 @@ use crate::{
   traits::Crud,
   utils::{get_conn, DbPool},
 };
-use diesel::{insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel::{insert_into, result::Error, ExpressionMethods, OptionalExtension, QueryDsl};
 use diesel_async::RunQueryDsl;
 
 #[async_trait]
@@ -43,11 +43,12 @@ impl RegistrationApplication {
   pub async fn find_by_local_user_id(
     pool: &mut DbPool<'_>,
     local_user_id_: LocalUserId,
-  ) -> Result<Self, Error> {
+  ) -> Result<Option<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
     registration_application
       .filter(local_user_id.eq(local_user_id_))
       .first::<Self>(conn)
       .await
+      .optional()
   }
 }
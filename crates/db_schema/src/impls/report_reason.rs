@@ -0,0 +1,124 @@
+use crate::{
+  newtypes::{CommunityId, ReportReasonId},
+  schema::report_reason::dsl::{community_id, id, report_reason},
+  source::report_reason::{ReportReason, ReportReasonForm},
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error, BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for ReportReason {
+  type InsertForm = ReportReasonForm;
+  type UpdateForm = ReportReasonForm;
+  type IdType = ReportReasonId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(report_reason)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    report_reason_id: ReportReasonId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(report_reason.find(report_reason_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl ReportReason {
+  /// Lists the site wide reasons, and those of the community if one is given.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    for_community_id: Option<CommunityId>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    report_reason
+      .filter(community_id.is_null().or(community_id.eq(for_community_id)))
+      .order(id)
+      .get_results::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      report_reason::{ReportReason, ReportReasonForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crud() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let new_community = CommunityInsertForm::builder()
+      .name("report_reason".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let site_reasons = ReportReason::list(pool, None).await.unwrap();
+    let form = ReportReasonForm {
+      community_id: Some(inserted_community.id),
+      name: "Off topic".to_string(),
+    };
+    let inserted_reason = ReportReason::create(pool, &form).await.unwrap();
+    let duplicate = ReportReason::create(pool, &form).await;
+
+    let community_reasons = ReportReason::list(pool, Some(inserted_community.id))
+      .await
+      .unwrap();
+    let site_reasons_after_create = ReportReason::list(pool, None).await.unwrap();
+    let num_deleted = ReportReason::delete(pool, inserted_reason.id)
+      .await
+      .unwrap();
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    let site_reason_names = site_reasons
+      .iter()
+      .map(|r| r.name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec!["Spam", "Harassment", "Illegal content"],
+      site_reason_names
+    );
+    assert!(duplicate.is_err());
+    assert_eq!(
+      [site_reasons.clone(), vec![inserted_reason]].concat(),
+      community_reasons
+    );
+    assert_eq!(site_reasons, site_reasons_after_create);
+    assert_eq!(1, num_deleted);
+  }
+}
@@ -0,0 +1,92 @@
+use crate::{
+  schema::scheduled_job::dsl::{name, scheduled_job},
+  source::scheduled_job::{ScheduledJob, ScheduledJobInsertForm, ScheduledJobUpdateForm},
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl ScheduledJob {
+  /// Adds the job if it doesn't exist yet. Existing jobs keep their schedule, as it may have been
+  /// changed by an admin.
+  pub async fn register(
+    pool: &mut DbPool<'_>,
+    form: &ScheduledJobInsertForm,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(scheduled_job)
+      .values(form)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, job_name: &str) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    scheduled_job.find(job_name).first::<Self>(conn).await
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    scheduled_job.order(name).load::<Self>(conn).await
+  }
+
+  pub async fn update(
+    pool: &mut DbPool<'_>,
+    job_name: &str,
+    form: &ScheduledJobUpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(scheduled_job.find(job_name))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::scheduled_job::{ScheduledJob, ScheduledJobInsertForm, ScheduledJobUpdateForm},
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_register_and_update() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let form = ScheduledJobInsertForm {
+      name: "test_job".to_string(),
+      interval_seconds: 60,
+    };
+    let registered = ScheduledJob::register(pool, &form).await.unwrap();
+
+    let update_form = ScheduledJobUpdateForm {
+      interval_seconds: Some(120),
+      enabled: Some(false),
+      ..Default::default()
+    };
+    let updated = ScheduledJob::update(pool, "test_job", &update_form)
+      .await
+      .unwrap();
+
+    // Registering again must not reset the changed schedule
+    let registered_again = ScheduledJob::register(pool, &form).await.unwrap();
+    let read = ScheduledJob::read(pool, "test_job").await.unwrap();
+    let listed = ScheduledJob::list(pool).await.unwrap();
+
+    assert_eq!(1, registered);
+    assert_eq!(0, registered_again);
+    assert_eq!(120, updated.interval_seconds);
+    assert!(!updated.enabled);
+    assert_eq!(updated, read);
+    assert!(listed.contains(&read));
+    assert_eq!(0, read.run_count);
+  }
+}
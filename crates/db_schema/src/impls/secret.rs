@@ -1,6 +1,6 @@
 use crate::{
   schema::secret::dsl::secret,
-  source::secret::Secret,
+  source::secret::{Secret, SecretUpdateForm},
   utils::{get_conn, DbPool},
 };
 use diesel::result::Error;
@@ -13,8 +13,65 @@ impl Secret {
     Self::read_secrets(pool).await
   }
 
-  async fn read_secrets(pool: &mut DbPool<'_>) -> Result<Secret, Error> {
+  /// Reads the current secrets, for those which can be changed while Lemmy is running.
+  pub async fn read_secrets(pool: &mut DbPool<'_>) -> Result<Secret, Error> {
     let conn = &mut get_conn(pool).await?;
     secret.first::<Secret>(conn).await
   }
+
+  pub async fn update(pool: &mut DbPool<'_>, form: &SecretUpdateForm) -> Result<Secret, Error> {
+    // Diesel refuses to build an update without any changes
+    if form.hcaptcha_secret.is_none()
+      && form.mcaptcha_secret.is_none()
+      && form.turnstile_secret.is_none()
+    {
+      return Self::read_secrets(pool).await;
+    }
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(secret)
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::secret::{Secret, SecretUpdateForm},
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_update() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let original = Secret::read_secrets(pool).await.unwrap();
+
+    // Nothing to change
+    let unchanged = Secret::update(pool, &SecretUpdateForm::default())
+      .await
+      .unwrap();
+    assert_eq!(original.hcaptcha_secret, unchanged.hcaptcha_secret);
+    assert_eq!(original.turnstile_secret, unchanged.turnstile_secret);
+
+    let form = SecretUpdateForm {
+      turnstile_secret: Some(Some("turnstile".to_string())),
+      ..Default::default()
+    };
+    let updated = Secret::update(pool, &form).await.unwrap();
+    assert_eq!(Some("turnstile".to_string()), updated.turnstile_secret);
+    assert_eq!(original.hcaptcha_secret, updated.hcaptcha_secret);
+
+    let form = SecretUpdateForm {
+      turnstile_secret: Some(original.turnstile_secret),
+      ..Default::default()
+    };
+    Secret::update(pool, &form).await.unwrap();
+  }
 }
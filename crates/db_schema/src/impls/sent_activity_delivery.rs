@@ -0,0 +1,273 @@
+use crate::{
+  newtypes::DbUrl,
+  schema::sent_activity_delivery::dsl::{
+    ap_id,
+    id,
+    inbox,
+    published,
+    sent_activity_delivery,
+    status,
+  },
+  source::sent_activity_delivery::{
+    InstanceDeliveryStats,
+    SentActivityDelivery,
+    SentActivityDeliveryForm,
+  },
+  utils::{fuzzy_search, get_conn, DbPool},
+};
+use chrono::NaiveDateTime;
+use diesel::{
+  dsl::{count_star, sql},
+  insert_into,
+  result::Error,
+  sql_types::{BigInt, Bool, Timestamp},
+  BoolExpressionMethods,
+  ExpressionMethods,
+  QueryDsl,
+  TextExpressionMethods,
+};
+use diesel_async::RunQueryDsl;
+
+impl SentActivityDelivery {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &SentActivityDeliveryForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(sent_activity_delivery)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  /// All delivery attempts of an activity, oldest first.
+  pub async fn list_for_activity(
+    pool: &mut DbPool<'_>,
+    activity_id: &DbUrl,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    sent_activity_delivery
+      .filter(ap_id.eq(activity_id))
+      .order(id)
+      .load::<Self>(conn)
+      .await
+  }
+
+  /// Counts the delivery attempts after the given time, and how many of them failed because the
+  /// inbox couldn't be reached or didn't accept the activity.
+  pub async fn count_since(
+    pool: &mut DbPool<'_>,
+    since: NaiveDateTime,
+  ) -> Result<(i64, i64), Error> {
+    let conn = &mut get_conn(pool).await?;
+    let attempts = sent_activity_delivery
+      .filter(published.gt(since))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await?;
+    let failures = sent_activity_delivery
+      .filter(published.gt(since))
+      .filter(status.is_null().or(status.ge(400)))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await?;
+    Ok((attempts, failures))
+  }
+
+  /// Counts the deliveries since the given time which are still waiting to be retried. The
+  /// federation queue retries a delivery as long as the inbox is unreachable or returns a server
+  /// error, so these are the ones where no attempt got any other response.
+  pub async fn count_pending_retries(
+    pool: &mut DbPool<'_>,
+    since: NaiveDateTime,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::select(
+      sql::<BigInt>(
+        "(SELECT count(*) FROM (SELECT 1 FROM sent_activity_delivery WHERE published > ",
+      )
+      .bind::<Timestamp, _>(since)
+      .sql(" GROUP BY ap_id, inbox HAVING bool_and(status IS NULL OR status >= 500)) AS pending)"),
+    )
+    .get_result::<i64>(conn)
+    .await
+  }
+
+  /// Deliveries since the given time to inboxes on the given domain, which didn't succeed in any
+  /// attempt. Returns the activity ids with the inboxes, most recent first.
+  pub async fn list_failed_for_domain(
+    pool: &mut DbPool<'_>,
+    domain: &str,
+    since: NaiveDateTime,
+    limit: i64,
+  ) -> Result<Vec<(DbUrl, DbUrl)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    // Matches both `https://example.com/inbox` and `https://example.com/u/alice/inbox`
+    let inbox_pattern = fuzzy_search(&format!("://{domain}/"));
+    sent_activity_delivery
+      .filter(published.gt(since))
+      .filter(inbox.like(inbox_pattern))
+      .group_by((ap_id, inbox))
+      .having(sql::<Bool>("bool_and(status IS NULL OR status >= 400)"))
+      .select((ap_id, inbox))
+      .order_by(diesel::dsl::max(id).desc())
+      .limit(limit)
+      .load::<(DbUrl, DbUrl)>(conn)
+      .await
+  }
+
+  /// Delivery statistics since the given time for each remote instance, grouped by the domain of
+  /// the inbox. Instances with the most failures come first.
+  pub async fn stats_per_instance(
+    pool: &mut DbPool<'_>,
+    since: NaiveDateTime,
+  ) -> Result<Vec<InstanceDeliveryStats>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::sql_query(
+      "WITH delivery AS (
+         SELECT substring(inbox FROM '://([^/]+)') AS domain, ap_id, inbox, status, published
+         FROM sent_activity_delivery
+         WHERE published > $1
+       ), pending AS (
+         SELECT domain, count(*) AS pending_retries
+         FROM (
+           SELECT domain FROM delivery
+           GROUP BY domain, ap_id, inbox
+           HAVING bool_and(status IS NULL OR status >= 500)
+         ) AS p
+         GROUP BY domain
+       )
+       SELECT domain,
+         count(*) AS attempts,
+         count(*) FILTER (WHERE status IS NULL OR status >= 400) AS failures,
+         coalesce(max(pending_retries), 0) AS pending_retries,
+         max(published) FILTER (WHERE status < 400) AS last_success,
+         max(published) FILTER (WHERE status IS NULL OR status >= 400) AS last_failure
+       FROM delivery
+       LEFT JOIN pending USING (domain)
+       GROUP BY domain
+       ORDER BY failures DESC, domain",
+    )
+    .bind::<Timestamp, _>(since)
+    .load::<InstanceDeliveryStats>(conn)
+    .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    newtypes::DbUrl,
+    source::sent_activity_delivery::{SentActivityDelivery, SentActivityDeliveryForm},
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_for_activity() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let activity_id: DbUrl = Url::parse("http://example.com/activities/create/delivery-test")
+      .unwrap()
+      .into();
+    let inbox: Url = Url::parse("http://remote.example.com/inbox").unwrap();
+    let failed = SentActivityDeliveryForm {
+      ap_id: activity_id.clone(),
+      inbox: inbox.clone().into(),
+      status: None,
+    };
+    let delivered = SentActivityDeliveryForm {
+      status: Some(200),
+      ..failed.clone()
+    };
+    SentActivityDelivery::create(pool, &failed).await.unwrap();
+    SentActivityDelivery::create(pool, &delivered)
+      .await
+      .unwrap();
+
+    let deliveries = SentActivityDelivery::list_for_activity(pool, &activity_id)
+      .await
+      .unwrap();
+    assert_eq!(2, deliveries.len());
+    assert_eq!(None, deliveries[0].status);
+    assert_eq!(Some(200), deliveries[1].status);
+    assert_eq!(inbox, *deliveries[1].inbox);
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_delivery_counts() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let since = naive_now();
+
+    let form = |activity: &str, inbox: &str, status| SentActivityDeliveryForm {
+      ap_id: Url::parse(activity).unwrap().into(),
+      inbox: Url::parse(inbox).unwrap().into(),
+      status,
+    };
+    let activity = "http://example.com/activities/create/delivery-count-test";
+    let deliveries = [
+      // Unreachable, then delivered
+      form(activity, "http://a.example.com/inbox", None),
+      form(activity, "http://a.example.com/inbox", Some(202)),
+      // Rejected, which isn't retried
+      form(activity, "http://b.example.com/inbox", Some(403)),
+      // Still failing
+      form(activity, "http://c.example.com/inbox", None),
+      form(activity, "http://c.example.com/inbox", Some(502)),
+    ];
+    for delivery in &deliveries {
+      SentActivityDelivery::create(pool, delivery).await.unwrap();
+    }
+
+    let (attempts, failures) = SentActivityDelivery::count_since(pool, since)
+      .await
+      .unwrap();
+    assert_eq!(5, attempts);
+    assert_eq!(4, failures);
+    let pending = SentActivityDelivery::count_pending_retries(pool, since)
+      .await
+      .unwrap();
+    assert_eq!(1, pending);
+
+    let stats = SentActivityDelivery::stats_per_instance(pool, since)
+      .await
+      .unwrap();
+    assert_eq!(3, stats.len());
+    let c = &stats[0];
+    assert_eq!("c.example.com", c.domain);
+    assert_eq!((2, 2, 1), (c.attempts, c.failures, c.pending_retries));
+    assert_eq!(None, c.last_success);
+    assert!(c.last_failure.is_some());
+    let a = &stats[1];
+    assert_eq!("a.example.com", a.domain);
+    assert_eq!((2, 1, 0), (a.attempts, a.failures, a.pending_retries));
+    assert!(a.last_success.is_some());
+    assert_eq!("b.example.com", stats[2].domain);
+    assert_eq!(0, stats[2].pending_retries);
+
+    // Rejected and unreachable deliveries can be requeued, but not those which succeeded later
+    for (domain, expected) in [
+      ("a.example.com", 0),
+      ("b.example.com", 1),
+      ("c.example.com", 1),
+    ] {
+      let failed = SentActivityDelivery::list_failed_for_domain(pool, domain, since, 10)
+        .await
+        .unwrap();
+      assert_eq!(expected, failed.len(), "{domain}");
+    }
+    let failed = SentActivityDelivery::list_failed_for_domain(pool, "c.example.com", since, 10)
+      .await
+      .unwrap();
+    assert_eq!(deliveries[3].ap_id, failed[0].0);
+    assert_eq!(deliveries[3].inbox, failed[0].1);
+  }
+}
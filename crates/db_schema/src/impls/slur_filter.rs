@@ -0,0 +1,154 @@
+use crate::{
+  newtypes::{CommunityId, LanguageId, SlurFilterId},
+  schema::slur_filter::dsl::{community_id, language_id, name, slur_filter},
+  source::slur_filter::{SlurFilter, SlurFilterForm},
+  traits::Crud,
+  utils::{get_conn, DbPool},
+};
+use diesel::{insert_into, result::Error, BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[async_trait]
+impl Crud for SlurFilter {
+  type InsertForm = SlurFilterForm;
+  type UpdateForm = SlurFilterForm;
+  type IdType = SlurFilterId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(slur_filter)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    slur_filter_id: SlurFilterId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(slur_filter.find(slur_filter_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
+impl SlurFilter {
+  pub async fn list(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    slur_filter.order(name).get_results::<Self>(conn).await
+  }
+
+  /// The filters which apply to content with the given language in the given community. Filters
+  /// without a language or community apply to any.
+  pub async fn for_content(
+    pool: &mut DbPool<'_>,
+    for_language_id: Option<LanguageId>,
+    for_community_id: CommunityId,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    slur_filter
+      .filter(language_id.is_null().or(language_id.eq(for_language_id)))
+      .filter(community_id.is_null().or(community_id.eq(for_community_id)))
+      .order(name)
+      .get_results::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      language::Language,
+      slur_filter::{SlurFilter, SlurFilterForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crud() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let new_community = CommunityInsertForm::builder()
+      .name("slur_filter".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let english = Language::read_id_from_code(pool, Some("en"))
+      .await
+      .unwrap()
+      .unwrap();
+    let german = Language::read_id_from_code(pool, Some("de"))
+      .await
+      .unwrap()
+      .unwrap();
+
+    let german_form = SlurFilterForm {
+      name: "german".to_string(),
+      pattern: "schimpfwort".to_string(),
+      language_id: Some(german),
+      community_id: None,
+    };
+    let german_filter = SlurFilter::create(pool, &german_form).await.unwrap();
+    let duplicate = SlurFilter::create(pool, &german_form).await;
+    let community_filter = SlurFilter::create(
+      pool,
+      &SlurFilterForm {
+        name: "community".to_string(),
+        pattern: "offtopic".to_string(),
+        language_id: None,
+        community_id: Some(inserted_community.id),
+      },
+    )
+    .await
+    .unwrap();
+
+    let for_english = SlurFilter::for_content(pool, Some(english), inserted_community.id)
+      .await
+      .unwrap();
+    let for_german = SlurFilter::for_content(pool, Some(german), inserted_community.id)
+      .await
+      .unwrap();
+    let for_undetermined = SlurFilter::for_content(pool, None, inserted_community.id)
+      .await
+      .unwrap();
+    let listed = SlurFilter::list(pool).await.unwrap();
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    let after_community_delete = SlurFilter::list(pool).await.unwrap();
+    let num_deleted = SlurFilter::delete(pool, german_filter.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert!(duplicate.is_err());
+    assert_eq!(vec![community_filter.clone()], for_english);
+    assert_eq!(
+      vec![community_filter.clone(), german_filter.clone()],
+      for_german
+    );
+    assert_eq!(vec![community_filter.clone()], for_undetermined);
+    assert_eq!(vec![community_filter, german_filter.clone()], listed);
+    assert_eq!(vec![german_filter], after_community_delete);
+    assert_eq!(1, num_deleted);
+  }
+}
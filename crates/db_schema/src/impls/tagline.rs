@@ -1,12 +1,40 @@
 use crate::{
-  newtypes::LocalSiteId,
-  schema::tagline::dsl::{local_site_id, tagline},
-  source::tagline::{Tagline, TaglineForm},
-  utils::{get_conn, DbPool},
+  newtypes::{LocalSiteId, TaglineId},
+  schema::tagline::dsl::{local_site_id, published, tagline},
+  source::tagline::{Tagline, TaglineForm, TaglineUpdateForm},
+  traits::Crud,
+  utils::{functions::random, get_conn, limit_and_offset, DbPool},
 };
-use diesel::{insert_into, result::Error, ExpressionMethods, QueryDsl};
+use diesel::{insert_into, result::Error, ExpressionMethods, OptionalExtension, QueryDsl};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 
+#[async_trait]
+impl Crud for Tagline {
+  type InsertForm = TaglineForm;
+  type UpdateForm = TaglineUpdateForm;
+  type IdType = TaglineId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(tagline)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    tagline_id: TaglineId,
+    form: &Self::UpdateForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(tagline.find(tagline_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+  }
+}
+
 impl Tagline {
   pub async fn replace(
     pool: &mut DbPool<'_>,
@@ -55,4 +83,114 @@ impl Tagline {
       .get_results::<Self>(conn)
       .await
   }
+
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    for_local_site_id: LocalSiteId,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    tagline
+      .filter(local_site_id.eq(for_local_site_id))
+      .order(published.desc())
+      .limit(limit)
+      .offset(offset)
+      .get_results::<Self>(conn)
+      .await
+  }
+
+  /// Picks a single random tagline, so that the front page can rotate through them.
+  pub async fn get_random(
+    pool: &mut DbPool<'_>,
+    for_local_site_id: LocalSiteId,
+  ) -> Result<Option<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    tagline
+      .filter(local_site_id.eq(for_local_site_id))
+      .order(random())
+      .first::<Self>(conn)
+      .await
+      .optional()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      instance::Instance,
+      local_site::{LocalSite, LocalSiteInsertForm},
+      site::{Site, SiteInsertForm},
+      tagline::{Tagline, TaglineForm, TaglineUpdateForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crud() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let site_form = SiteInsertForm::builder()
+      .name("test_site".into())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_site = Site::create(pool, &site_form).await.unwrap();
+
+    let local_site_form = LocalSiteInsertForm::builder()
+      .site_id(inserted_site.id)
+      .build();
+    let inserted_local_site = LocalSite::create(pool, &local_site_form).await.unwrap();
+
+    let form = TaglineForm {
+      local_site_id: inserted_local_site.id,
+      content: "**hello** world".to_string(),
+      updated: None,
+    };
+    let inserted_tagline = Tagline::create(pool, &form).await.unwrap();
+
+    let random_tagline = Tagline::get_random(pool, inserted_local_site.id)
+      .await
+      .unwrap();
+
+    let update_form = TaglineUpdateForm {
+      content: "goodbye".to_string(),
+      updated: Some(naive_now()),
+    };
+    let updated_tagline = Tagline::update(pool, inserted_tagline.id, &update_form)
+      .await
+      .unwrap();
+
+    let listed_taglines = Tagline::list(pool, inserted_local_site.id, None, None)
+      .await
+      .unwrap();
+
+    let num_deleted = Tagline::delete(pool, inserted_tagline.id).await.unwrap();
+    let random_after_delete = Tagline::get_random(pool, inserted_local_site.id)
+      .await
+      .unwrap();
+
+    Site::delete(pool, inserted_site.id).await.unwrap();
+    LocalSite::delete(pool).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert_eq!(Some(inserted_tagline.clone()), random_tagline);
+    assert_eq!("goodbye", updated_tagline.content);
+    assert!(updated_tagline.updated.is_some());
+    assert_eq!(vec![updated_tagline], listed_taglines);
+    assert_eq!(1, num_deleted);
+    assert_eq!(None, random_after_delete);
+  }
 }
@@ -0,0 +1,202 @@
+use crate::{
+  newtypes::{CommentId, PersonId, PostId},
+  schema::thread_subscription,
+  source::{
+    comment::Comment,
+    thread_subscription::{ThreadSubscription, ThreadSubscriptionForm},
+  },
+  utils::{get_conn, DbPool},
+};
+use diesel::{dsl::insert_into, result::Error, BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+impl ThreadSubscription {
+  /// Subscribes to a post or comment thread. Subscribing twice returns the existing subscription.
+  pub async fn subscribe(
+    pool: &mut DbPool<'_>,
+    form: &ThreadSubscriptionForm,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(thread_subscription::table)
+      .values(form)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await?;
+    let query = thread_subscription::table
+      .filter(thread_subscription::person_id.eq(form.person_id))
+      .filter(thread_subscription::post_id.eq(form.post_id))
+      .into_boxed();
+    let query = match form.comment_id {
+      Some(comment_id) => query.filter(thread_subscription::comment_id.eq(comment_id)),
+      None => query.filter(thread_subscription::comment_id.is_null()),
+    };
+    query.first::<Self>(conn).await
+  }
+
+  pub async fn unsubscribe(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    post_id: PostId,
+    comment_id: Option<CommentId>,
+  ) -> Result<usize, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let query = diesel::delete(thread_subscription::table)
+      .filter(thread_subscription::person_id.eq(person_id))
+      .filter(thread_subscription::post_id.eq(post_id))
+      .into_boxed();
+    let query = match comment_id {
+      Some(comment_id) => query.filter(thread_subscription::comment_id.eq(comment_id)),
+      None => query.filter(thread_subscription::comment_id.is_null()),
+    };
+    query.execute(conn).await
+  }
+
+  /// Subscriptions to the post of a new comment, or to any of the comments above it
+  pub async fn list_for_comment(
+    pool: &mut DbPool<'_>,
+    comment: &Comment,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    thread_subscription::table
+      .filter(thread_subscription::post_id.eq(comment.post_id))
+      .filter(
+        thread_subscription::comment_id
+          .is_null()
+          .or(thread_subscription::comment_id.eq_any(comment.ancestor_ids())),
+      )
+      .load::<Self>(conn)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    source::{
+      comment::{Comment, CommentInsertForm},
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+      thread_subscription::{ThreadSubscription, ThreadSubscriptionForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_thread_subscriptions() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("thread_subscriber".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &person_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("thread_community".into())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &community_form).await.unwrap();
+    let post_form = PostInsertForm::builder()
+      .name("A test post".into())
+      .creator_id(inserted_person.id)
+      .community_id(inserted_community.id)
+      .build();
+    let inserted_post = Post::create(pool, &post_form).await.unwrap();
+
+    let comment_form = CommentInsertForm::builder()
+      .content("A test comment".into())
+      .creator_id(inserted_person.id)
+      .post_id(inserted_post.id)
+      .build();
+    let top_comment = Comment::create(pool, &comment_form, None).await.unwrap();
+    let child_comment = Comment::create(pool, &comment_form, Some(&top_comment.path))
+      .await
+      .unwrap();
+    let grandchild_comment = Comment::create(pool, &comment_form, Some(&child_comment.path))
+      .await
+      .unwrap();
+    let other_comment = Comment::create(pool, &comment_form, None).await.unwrap();
+    assert_eq!(
+      vec![top_comment.id, child_comment.id],
+      grandchild_comment.ancestor_ids()
+    );
+
+    // Subscribe to the subtree of the top comment, twice
+    let form = ThreadSubscriptionForm {
+      person_id: inserted_person.id,
+      post_id: inserted_post.id,
+      comment_id: Some(top_comment.id),
+    };
+    let subscription = ThreadSubscription::subscribe(pool, &form).await.unwrap();
+    let duplicate = ThreadSubscription::subscribe(pool, &form).await.unwrap();
+    assert_eq!(subscription, duplicate);
+
+    // Deeper replies match, other comments and the subscribed comment itself don't
+    let matches = ThreadSubscription::list_for_comment(pool, &grandchild_comment)
+      .await
+      .unwrap();
+    assert_eq!(vec![subscription], matches);
+    let matches = ThreadSubscription::list_for_comment(pool, &other_comment)
+      .await
+      .unwrap();
+    assert!(matches.is_empty());
+    let matches = ThreadSubscription::list_for_comment(pool, &top_comment)
+      .await
+      .unwrap();
+    assert!(matches.is_empty());
+
+    // A subscription to the post matches all comments
+    let post_form = ThreadSubscriptionForm {
+      comment_id: None,
+      ..form.clone()
+    };
+    ThreadSubscription::subscribe(pool, &post_form)
+      .await
+      .unwrap();
+    ThreadSubscription::subscribe(pool, &post_form)
+      .await
+      .unwrap();
+    let matches = ThreadSubscription::list_for_comment(pool, &other_comment)
+      .await
+      .unwrap();
+    assert_eq!(1, matches.len());
+
+    let removed = ThreadSubscription::unsubscribe(pool, inserted_person.id, inserted_post.id, None)
+      .await
+      .unwrap();
+    assert_eq!(1, removed);
+    let removed = ThreadSubscription::unsubscribe(
+      pool,
+      inserted_person.id,
+      inserted_post.id,
+      Some(top_comment.id),
+    )
+    .await
+    .unwrap();
+    assert_eq!(1, removed);
+    let matches = ThreadSubscription::list_for_comment(pool, &grandchild_comment)
+      .await
+      .unwrap();
+    assert!(matches.is_empty());
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -72,6 +72,7 @@ pub enum SortType {
   TopSixMonths,
   TopNineMonths,
   Controversial,
+  Trending,
 }
 
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy)]
@@ -133,6 +134,89 @@ pub enum RegistrationMode {
   RequireApplication,
   /// Open to all.
   Open,
+  /// Open to those with a valid invite code.
+  Invite,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::PostTypeRestrictionEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// The kinds of posts which are allowed in a community.
+pub enum PostTypeRestriction {
+  /// All posts are allowed.
+  Any,
+  /// Only posts without a url.
+  TextOnly,
+  /// Only posts with a url.
+  LinkOnly,
+  /// Links are allowed, but not if they point to an image.
+  NoImages,
+}
+
+#[derive(
+  EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::WordFilterActionEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// What happens to content which matches a community word filter, from least to most severe.
+pub enum WordFilterAction {
+  /// The content is published, and reported to the community mods.
+  Report,
+  /// The content is published, but removed.
+  Remove,
+  /// The content is rejected.
+  Reject,
+}
+
+#[derive(
+  EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq,
+)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::AdminRoleEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// What an admin is allowed to do. Only has an effect for admins.
+pub enum AdminRole {
+  /// Manages users: bans, shadowbans, account merges and registration applications.
+  UserAdmin,
+  /// Manages content: purges, featured posts, reports, emojis, taglines and filters.
+  ContentAdmin,
+  /// Manages federation: blocklists, instance trust, activity deliveries and keys.
+  FederationAdmin,
+  /// Can do everything, including editing the site and adding admins.
+  #[default]
+  FullAdmin,
+}
+
+#[derive(
+  EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq,
+)]
+#[cfg_attr(feature = "full", derive(DbEnum, TS))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::AnnouncementSeverityEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "full", ts(export))]
+/// How an announcement banner is styled.
+pub enum AnnouncementSeverity {
+  #[default]
+  Info,
+  Warning,
+  Critical,
 }
 
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy)]
@@ -175,6 +259,8 @@ pub enum ModlogActionType {
   ModAdd,
   ModBan,
   ModHideCommunity,
+  ModShadowban,
+  AdminMergePerson,
   AdminPurgePerson,
   AdminPurgeCommunity,
   AdminPurgePost,
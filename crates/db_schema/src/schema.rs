@@ -1,10 +1,22 @@
 // @generated automatically by Diesel CLI.
 
 pub mod sql_types {
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "admin_role_enum"))]
+    pub struct AdminRoleEnum;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "announcement_severity_enum"))]
+    pub struct AnnouncementSeverityEnum;
+
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "listing_type_enum"))]
     pub struct ListingTypeEnum;
 
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "post_type_restriction_enum"))]
+    pub struct PostTypeRestrictionEnum;
+
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "registration_mode_enum"))]
     pub struct RegistrationModeEnum;
@@ -12,6 +24,43 @@ pub mod sql_types {
     #[derive(diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "sort_type_enum"))]
     pub struct SortTypeEnum;
+
+    #[derive(diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "word_filter_action_enum"))]
+    pub struct WordFilterActionEnum;
+}
+
+diesel::table! {
+    actor_integrity_key (id) {
+        id -> Int4,
+        #[max_length = 255]
+        actor_id -> Varchar,
+        public_key -> Text,
+        private_key -> Nullable<Text>,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    actor_key_rotation (id) {
+        id -> Int4,
+        #[max_length = 255]
+        actor_id -> Varchar,
+        previous_public_key -> Text,
+        rotated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    admin_merge_person (id) {
+        id -> Int4,
+        admin_person_id -> Int4,
+        source_person_id -> Int4,
+        target_person_id -> Int4,
+        reason -> Nullable<Text>,
+        when_ -> Timestamp,
+    }
 }
 
 diesel::table! {
@@ -52,6 +101,41 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AnnouncementSeverityEnum;
+
+    announcement (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        message -> Text,
+        severity -> AnnouncementSeverityEnum,
+        start_time -> Timestamp,
+        end_time -> Nullable<Timestamp>,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    blocked_url (id) {
+        id -> Int4,
+        url -> Text,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    blocklist_subscription (id) {
+        id -> Int4,
+        url -> Text,
+        published -> Timestamp,
+        last_synced_at -> Nullable<Timestamp>,
+        last_error -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     captcha_answer (id) {
         id -> Int4,
@@ -61,6 +145,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    category (id) {
+        id -> Int4,
+        #[max_length = 50]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use diesel_ltree::sql_types::Ltree;
@@ -108,6 +203,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    comment_reaction (id) {
+        id -> Int4,
+        comment_id -> Int4,
+        person_id -> Int4,
+        #[max_length = 100]
+        emoji -> Varchar,
+        published -> Timestamp,
+    }
+}
+
 diesel::table! {
     comment_reply (id) {
         id -> Int4,
@@ -129,6 +235,8 @@ diesel::table! {
         resolver_id -> Nullable<Int4>,
         published -> Timestamp,
         updated -> Nullable<Timestamp>,
+        reason_id -> Nullable<Int4>,
+        rule_id -> Nullable<Int4>,
     }
 }
 
@@ -142,6 +250,9 @@ diesel::table! {
 }
 
 diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::PostTypeRestrictionEnum;
+
     community (id) {
         id -> Int4,
         #[max_length = 255]
@@ -175,6 +286,19 @@ diesel::table! {
         moderators_url -> Nullable<Varchar>,
         #[max_length = 255]
         featured_url -> Nullable<Varchar>,
+        enable_downvotes -> Bool,
+        requires_follow_approval -> Bool,
+        post_type_restriction -> PostTypeRestrictionEnum,
+        nsfw_only -> Bool,
+        post_slow_mode_seconds -> Int4,
+        comment_slow_mode_seconds -> Int4,
+        allow_undetermined_language -> Bool,
+        strike_ban_threshold -> Int4,
+        strike_ban_days -> Int4,
+        min_account_age_days -> Int4,
+        min_site_karma -> Int4,
+        min_community_karma -> Int4,
+        notify_mods_on_mention -> Bool,
     }
 }
 
@@ -191,6 +315,26 @@ diesel::table! {
         users_active_month -> Int8,
         users_active_half_year -> Int8,
         hot_rank -> Int4,
+        subscribers_day_ago -> Int8,
+        trending_rank -> Int4,
+    }
+}
+
+diesel::table! {
+    community_application (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        title -> Varchar,
+        description -> Nullable<Text>,
+        nsfw -> Bool,
+        form -> Jsonb,
+        admin_id -> Nullable<Int4>,
+        deny_reason -> Nullable<Text>,
+        community_id -> Nullable<Int4>,
+        published -> Timestamp,
     }
 }
 
@@ -203,6 +347,33 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_category (id) {
+        id -> Int4,
+        community_id -> Int4,
+        category_id -> Int4,
+    }
+}
+
+diesel::table! {
+    community_export (id) {
+        id -> Int4,
+        community_id -> Int4,
+        creator_id -> Int4,
+        error -> Nullable<Text>,
+        published -> Timestamp,
+        finished -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    community_export_chunk (export_id, seq) {
+        export_id -> Int4,
+        seq -> Int4,
+        data -> Text,
+    }
+}
+
 diesel::table! {
     community_follower (id) {
         id -> Int4,
@@ -213,6 +384,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_import (id) {
+        id -> Int4,
+        community_id -> Int4,
+        creator_id -> Int4,
+        posts_imported -> Int4,
+        comments_imported -> Int4,
+        skipped -> Int4,
+        error -> Nullable<Text>,
+        published -> Timestamp,
+        finished -> Nullable<Timestamp>,
+        updated -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    community_import_object (source_ap_id) {
+        source_ap_id -> Text,
+        import_id -> Int4,
+        post_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+    }
+}
+
 diesel::table! {
     community_language (id) {
         id -> Int4,
@@ -221,6 +416,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_last_seen (person_id, community_id) {
+        person_id -> Int4,
+        community_id -> Int4,
+        last_seen -> Timestamp,
+    }
+}
+
 diesel::table! {
     community_moderator (id) {
         id -> Int4,
@@ -230,6 +433,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_notification (id) {
+        id -> Int4,
+        person_id -> Int4,
+        community_id -> Int4,
+        min_interval_minutes -> Int4,
+        last_notified -> Nullable<Timestamp>,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    community_person_aggregates (id) {
+        id -> Int4,
+        community_id -> Int4,
+        person_id -> Int4,
+        post_score -> Int8,
+        comment_score -> Int8,
+        updated -> Timestamp,
+    }
+}
+
 diesel::table! {
     community_person_ban (id) {
         id -> Int4,
@@ -240,6 +465,62 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    community_rule (id) {
+        id -> Int4,
+        community_id -> Int4,
+        position -> Int4,
+        title -> Text,
+        description -> Nullable<Text>,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    community_snooze (id) {
+        id -> Int4,
+        person_id -> Int4,
+        community_id -> Int4,
+        expires -> Timestamp,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    community_strike (id) {
+        id -> Int4,
+        community_id -> Int4,
+        person_id -> Int4,
+        mod_person_id -> Int4,
+        post_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+        reason -> Nullable<Text>,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    community_transfer_request (community_id) {
+        community_id -> Int4,
+        requester_id -> Int4,
+        recipient_id -> Int4,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WordFilterActionEnum;
+
+    community_word_filter (id) {
+        id -> Int4,
+        community_id -> Int4,
+        pattern -> Text,
+        action -> WordFilterActionEnum,
+        published -> Timestamp,
+    }
+}
+
 diesel::table! {
     custom_emoji (id) {
         id -> Int4,
@@ -263,6 +544,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    disposable_email_domain (id) {
+        id -> Int4,
+        domain -> Text,
+        exception -> Bool,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    email_domain (id) {
+        id -> Int4,
+        domain -> Text,
+        allowed -> Bool,
+        published -> Timestamp,
+    }
+}
+
 diesel::table! {
     email_verification (id) {
         id -> Int4,
@@ -288,6 +587,9 @@ diesel::table! {
         instance_id -> Int4,
         published -> Timestamp,
         updated -> Nullable<Timestamp>,
+        subscription_id -> Nullable<Int4>,
+        reason -> Nullable<Text>,
+        pinned -> Bool,
     }
 }
 
@@ -302,6 +604,40 @@ diesel::table! {
         software -> Nullable<Varchar>,
         #[max_length = 255]
         version -> Nullable<Varchar>,
+        open_registrations -> Nullable<Bool>,
+    }
+}
+
+diesel::table! {
+    instance_trust (id) {
+        id -> Int4,
+        instance_id -> Int4,
+        guarantor -> Nullable<Text>,
+        endorsements -> Int4,
+        censures -> Int4,
+        censure_reasons -> Nullable<Text>,
+        updated -> Timestamp,
+    }
+}
+
+diesel::table! {
+    invite (id) {
+        id -> Int4,
+        code -> Text,
+        creator_id -> Int4,
+        max_uses -> Int4,
+        uses -> Int4,
+        expires -> Nullable<Timestamp>,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    invite_use (id) {
+        id -> Int4,
+        invite_id -> Int4,
+        person_id -> Int4,
+        published -> Timestamp,
     }
 }
 
@@ -314,6 +650,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    local_image (id) {
+        id -> Int4,
+        person_id -> Int4,
+        pictrs_alias -> Text,
+        pictrs_delete_token -> Text,
+        post_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+        published -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::ListingTypeEnum;
@@ -344,6 +692,17 @@ diesel::table! {
         updated -> Nullable<Timestamp>,
         registration_mode -> RegistrationModeEnum,
         reports_email_admins -> Bool,
+        enable_vote_viewer -> Bool,
+        anonymize_outgoing_votes -> Bool,
+        private_instance_federation -> Bool,
+        check_breached_passwords -> Bool,
+        emoji_reactions_as_votes -> Bool,
+        users_can_create_invites -> Bool,
+        community_creation_min_account_age_days -> Int4,
+        community_creation_min_karma -> Int4,
+        community_creation_requires_approval -> Bool,
+        max_username_changes -> Int4,
+        username_reuse_cooldown_days -> Int4,
     }
 }
 
@@ -372,6 +731,7 @@ diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::SortTypeEnum;
     use super::sql_types::ListingTypeEnum;
+    use super::sql_types::AdminRoleEnum;
 
     local_user (id) {
         id -> Int4,
@@ -399,6 +759,16 @@ diesel::table! {
         blur_nsfw -> Bool,
         auto_expand -> Bool,
         infinite_scroll_enabled -> Bool,
+        anonymize_outgoing_votes -> Bool,
+        notify_comment_replies -> Bool,
+        notify_post_replies -> Bool,
+        notify_mentions -> Bool,
+        notify_private_messages -> Bool,
+        auto_subscribe_threads -> Bool,
+        email_mod_queue_digest -> Bool,
+        mod_queue_digest_sent -> Nullable<Timestamp>,
+        email_login_alerts -> Bool,
+        admin_role -> Nullable<AdminRoleEnum>,
     }
 }
 
@@ -410,6 +780,38 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    login_history (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        ip_network -> Nullable<Text>,
+        device -> Nullable<Text>,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    login_session (id) {
+        id -> Int4,
+        token_hash -> Text,
+        local_user_id -> Int4,
+        ip -> Nullable<Text>,
+        user_agent -> Nullable<Text>,
+        published -> Timestamp,
+        expires -> Timestamp,
+    }
+}
+
+diesel::table! {
+    magic_login_token (id) {
+        id -> Int4,
+        token_hash -> Text,
+        local_user_id -> Int4,
+        published -> Timestamp,
+        expires -> Timestamp,
+    }
+}
+
 diesel::table! {
     mod_add (id) {
         id -> Int4,
@@ -423,7 +825,7 @@ diesel::table! {
 diesel::table! {
     mod_add_community (id) {
         id -> Int4,
-        mod_person_id -> Int4,
+        mod_person_id -> Nullable<Int4>,
         other_person_id -> Int4,
         community_id -> Int4,
         removed -> Bool,
@@ -522,6 +924,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    mod_shadowban (id) {
+        id -> Int4,
+        mod_person_id -> Int4,
+        other_person_id -> Int4,
+        reason -> Nullable<Text>,
+        shadowbanned -> Bool,
+        when_ -> Timestamp,
+    }
+}
+
 diesel::table! {
     mod_transfer_community (id) {
         id -> Int4,
@@ -570,6 +983,7 @@ diesel::table! {
         bot_account -> Bool,
         ban_expires -> Nullable<Timestamp>,
         instance_id -> Int4,
+        shadowbanned_at -> Nullable<Timestamp>,
     }
 }
 
@@ -584,6 +998,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    person_alias (id) {
+        id -> Int4,
+        person_id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        actor_id -> Varchar,
+        published -> Timestamp,
+    }
+}
+
 diesel::table! {
     person_ban (id) {
         id -> Int4,
@@ -598,6 +1024,7 @@ diesel::table! {
         person_id -> Int4,
         target_id -> Int4,
         published -> Timestamp,
+        expires -> Nullable<Timestamp>,
     }
 }
 
@@ -615,9 +1042,22 @@ diesel::table! {
     person_mention (id) {
         id -> Int4,
         recipient_id -> Int4,
-        comment_id -> Int4,
+        comment_id -> Nullable<Int4>,
         read -> Bool,
         published -> Timestamp,
+        post_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    person_note (id) {
+        id -> Int4,
+        person_id -> Int4,
+        target_id -> Int4,
+        note -> Text,
+        color -> Nullable<Text>,
+        published -> Timestamp,
+        updated -> Nullable<Timestamp>,
     }
 }
 
@@ -628,6 +1068,16 @@ diesel::table! {
         post_id -> Int4,
         read_comments -> Int8,
         published -> Timestamp,
+        comments_seen -> Nullable<Timestamp>,
+        previous_comments_seen -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    person_vote_pseudonym (person_id) {
+        person_id -> Int4,
+        pseudonym_id -> Int4,
+        published -> Timestamp,
     }
 }
 
@@ -657,6 +1107,10 @@ diesel::table! {
         language_id -> Int4,
         featured_community -> Bool,
         featured_local -> Bool,
+        contest_mode_until -> Nullable<Timestamp>,
+        #[max_length = 255]
+        audio_mime_type -> Nullable<Varchar>,
+        audio_duration -> Nullable<Int4>,
     }
 }
 
@@ -691,6 +1145,35 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_metadata_refetch (post_id) {
+        post_id -> Int4,
+        attempts -> Int4,
+        next_attempt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    post_notification (id) {
+        id -> Int4,
+        recipient_id -> Int4,
+        post_id -> Int4,
+        read -> Bool,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    post_reaction (id) {
+        id -> Int4,
+        post_id -> Int4,
+        person_id -> Int4,
+        #[max_length = 100]
+        emoji -> Varchar,
+        published -> Timestamp,
+    }
+}
+
 diesel::table! {
     post_read (id) {
         id -> Int4,
@@ -714,6 +1197,8 @@ diesel::table! {
         resolver_id -> Nullable<Int4>,
         published -> Timestamp,
         updated -> Nullable<Timestamp>,
+        reason_id -> Nullable<Int4>,
+        rule_id -> Nullable<Int4>,
     }
 }
 
@@ -753,6 +1238,16 @@ diesel::table! {
         resolver_id -> Nullable<Int4>,
         published -> Timestamp,
         updated -> Nullable<Timestamp>,
+        reason_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    rate_limit_override (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        limit_percent -> Int4,
+        published -> Timestamp,
     }
 }
 
@@ -775,10 +1270,37 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    report_reason (id) {
+        id -> Int4,
+        community_id -> Nullable<Int4>,
+        name -> Text,
+        published -> Timestamp,
+    }
+}
+
+diesel::table! {
+    scheduled_job (name) {
+        name -> Text,
+        interval_seconds -> Int4,
+        enabled -> Bool,
+        next_run -> Timestamp,
+        locked_until -> Nullable<Timestamp>,
+        last_started -> Nullable<Timestamp>,
+        last_finished -> Nullable<Timestamp>,
+        last_duration_ms -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        run_count -> Int4,
+        failure_count -> Int4,
+    }
+}
+
 diesel::table! {
     secret (id) {
         id -> Int4,
-        jwt_secret -> Varchar,
+        hcaptcha_secret -> Nullable<Text>,
+        mcaptcha_secret -> Nullable<Text>,
+        turnstile_secret -> Nullable<Text>,
     }
 }
 
@@ -792,6 +1314,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sent_activity_delivery (id) {
+        id -> Int8,
+        ap_id -> Text,
+        inbox -> Text,
+        status -> Nullable<Int4>,
+        published -> Timestamp,
+    }
+}
+
 diesel::table! {
     site (id) {
         id -> Int4,
@@ -838,6 +1370,33 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    site_stats_history (id) {
+        id -> Int4,
+        site_id -> Int4,
+        day -> Date,
+        users -> Int8,
+        posts -> Int8,
+        comments -> Int8,
+        communities -> Int8,
+        users_active_day -> Int8,
+        users_active_week -> Int8,
+        users_active_month -> Int8,
+        users_active_half_year -> Int8,
+    }
+}
+
+diesel::table! {
+    slur_filter (id) {
+        id -> Int4,
+        name -> Text,
+        pattern -> Text,
+        language_id -> Nullable<Int4>,
+        community_id -> Nullable<Int4>,
+        published -> Timestamp,
+    }
+}
+
 diesel::table! {
     tagline (id) {
         id -> Int4,
@@ -848,12 +1407,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    thread_subscription (id) {
+        id -> Int4,
+        person_id -> Int4,
+        post_id -> Int4,
+        comment_id -> Nullable<Int4>,
+        published -> Timestamp,
+    }
+}
+
 diesel::joinable!(admin_purge_comment -> person (admin_person_id));
 diesel::joinable!(admin_purge_comment -> post (post_id));
 diesel::joinable!(admin_purge_community -> person (admin_person_id));
 diesel::joinable!(admin_purge_person -> person (admin_person_id));
 diesel::joinable!(admin_purge_post -> community (community_id));
 diesel::joinable!(admin_purge_post -> person (admin_person_id));
+diesel::joinable!(announcement -> person (creator_id));
 diesel::joinable!(comment -> language (language_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(comment -> post (post_id));
@@ -861,33 +1431,73 @@ diesel::joinable!(comment_aggregates -> comment (comment_id));
 diesel::joinable!(comment_like -> comment (comment_id));
 diesel::joinable!(comment_like -> person (person_id));
 diesel::joinable!(comment_like -> post (post_id));
+diesel::joinable!(comment_reaction -> comment (comment_id));
+diesel::joinable!(comment_reaction -> person (person_id));
 diesel::joinable!(comment_reply -> comment (comment_id));
 diesel::joinable!(comment_reply -> person (recipient_id));
 diesel::joinable!(comment_report -> comment (comment_id));
+diesel::joinable!(comment_report -> community_rule (rule_id));
+diesel::joinable!(comment_report -> report_reason (reason_id));
 diesel::joinable!(comment_saved -> comment (comment_id));
 diesel::joinable!(comment_saved -> person (person_id));
 diesel::joinable!(community -> instance (instance_id));
 diesel::joinable!(community_aggregates -> community (community_id));
+diesel::joinable!(community_application -> community (community_id));
 diesel::joinable!(community_block -> community (community_id));
 diesel::joinable!(community_block -> person (person_id));
+diesel::joinable!(community_category -> category (category_id));
+diesel::joinable!(community_category -> community (community_id));
+diesel::joinable!(community_export -> community (community_id));
+diesel::joinable!(community_export -> person (creator_id));
+diesel::joinable!(community_export_chunk -> community_export (export_id));
 diesel::joinable!(community_follower -> community (community_id));
 diesel::joinable!(community_follower -> person (person_id));
+diesel::joinable!(community_import -> community (community_id));
+diesel::joinable!(community_import -> person (creator_id));
+diesel::joinable!(community_import_object -> comment (comment_id));
+diesel::joinable!(community_import_object -> community_import (import_id));
+diesel::joinable!(community_import_object -> post (post_id));
 diesel::joinable!(community_language -> community (community_id));
 diesel::joinable!(community_language -> language (language_id));
+diesel::joinable!(community_last_seen -> community (community_id));
+diesel::joinable!(community_last_seen -> person (person_id));
 diesel::joinable!(community_moderator -> community (community_id));
 diesel::joinable!(community_moderator -> person (person_id));
+diesel::joinable!(community_notification -> community (community_id));
+diesel::joinable!(community_notification -> person (person_id));
+diesel::joinable!(community_person_aggregates -> community (community_id));
+diesel::joinable!(community_person_aggregates -> person (person_id));
 diesel::joinable!(community_person_ban -> community (community_id));
 diesel::joinable!(community_person_ban -> person (person_id));
+diesel::joinable!(community_rule -> community (community_id));
+diesel::joinable!(community_snooze -> community (community_id));
+diesel::joinable!(community_snooze -> person (person_id));
+diesel::joinable!(community_strike -> comment (comment_id));
+diesel::joinable!(community_strike -> community (community_id));
+diesel::joinable!(community_strike -> post (post_id));
+diesel::joinable!(community_transfer_request -> community (community_id));
+diesel::joinable!(community_word_filter -> community (community_id));
 diesel::joinable!(custom_emoji -> local_site (local_site_id));
 diesel::joinable!(custom_emoji_keyword -> custom_emoji (custom_emoji_id));
 diesel::joinable!(email_verification -> local_user (local_user_id));
 diesel::joinable!(federation_allowlist -> instance (instance_id));
+diesel::joinable!(federation_blocklist -> blocklist_subscription (subscription_id));
 diesel::joinable!(federation_blocklist -> instance (instance_id));
+diesel::joinable!(instance_trust -> instance (instance_id));
+diesel::joinable!(invite -> person (creator_id));
+diesel::joinable!(invite_use -> invite (invite_id));
+diesel::joinable!(invite_use -> person (person_id));
+diesel::joinable!(local_image -> comment (comment_id));
+diesel::joinable!(local_image -> person (person_id));
+diesel::joinable!(local_image -> post (post_id));
 diesel::joinable!(local_site -> site (site_id));
 diesel::joinable!(local_site_rate_limit -> local_site (local_site_id));
 diesel::joinable!(local_user -> person (person_id));
 diesel::joinable!(local_user_language -> language (language_id));
 diesel::joinable!(local_user_language -> local_user (local_user_id));
+diesel::joinable!(login_history -> local_user (local_user_id));
+diesel::joinable!(login_session -> local_user (local_user_id));
+diesel::joinable!(magic_login_token -> local_user (local_user_id));
 diesel::joinable!(mod_add_community -> community (community_id));
 diesel::joinable!(mod_ban_from_community -> community (community_id));
 diesel::joinable!(mod_feature_post -> person (mod_person_id));
@@ -906,11 +1516,14 @@ diesel::joinable!(mod_transfer_community -> community (community_id));
 diesel::joinable!(password_reset_request -> local_user (local_user_id));
 diesel::joinable!(person -> instance (instance_id));
 diesel::joinable!(person_aggregates -> person (person_id));
+diesel::joinable!(person_alias -> person (person_id));
 diesel::joinable!(person_ban -> person (person_id));
 diesel::joinable!(person_mention -> comment (comment_id));
 diesel::joinable!(person_mention -> person (recipient_id));
+diesel::joinable!(person_mention -> post (post_id));
 diesel::joinable!(person_post_aggregates -> person (person_id));
 diesel::joinable!(person_post_aggregates -> post (post_id));
+diesel::joinable!(person_vote_pseudonym -> person (person_id));
 diesel::joinable!(post -> community (community_id));
 diesel::joinable!(post -> language (language_id));
 diesel::joinable!(post -> person (creator_id));
@@ -919,50 +1532,97 @@ diesel::joinable!(post_aggregates -> person (creator_id));
 diesel::joinable!(post_aggregates -> post (post_id));
 diesel::joinable!(post_like -> person (person_id));
 diesel::joinable!(post_like -> post (post_id));
+diesel::joinable!(post_metadata_refetch -> post (post_id));
+diesel::joinable!(post_notification -> person (recipient_id));
+diesel::joinable!(post_notification -> post (post_id));
+diesel::joinable!(post_reaction -> person (person_id));
+diesel::joinable!(post_reaction -> post (post_id));
 diesel::joinable!(post_read -> person (person_id));
 diesel::joinable!(post_read -> post (post_id));
+diesel::joinable!(post_report -> community_rule (rule_id));
 diesel::joinable!(post_report -> post (post_id));
+diesel::joinable!(post_report -> report_reason (reason_id));
 diesel::joinable!(post_saved -> person (person_id));
 diesel::joinable!(post_saved -> post (post_id));
 diesel::joinable!(private_message_report -> private_message (private_message_id));
+diesel::joinable!(private_message_report -> report_reason (reason_id));
+diesel::joinable!(rate_limit_override -> local_user (local_user_id));
 diesel::joinable!(registration_application -> local_user (local_user_id));
 diesel::joinable!(registration_application -> person (admin_id));
+diesel::joinable!(report_reason -> community (community_id));
 diesel::joinable!(site -> instance (instance_id));
 diesel::joinable!(site_aggregates -> site (site_id));
 diesel::joinable!(site_language -> language (language_id));
 diesel::joinable!(site_language -> site (site_id));
+diesel::joinable!(site_stats_history -> site (site_id));
+diesel::joinable!(slur_filter -> community (community_id));
+diesel::joinable!(slur_filter -> language (language_id));
 diesel::joinable!(tagline -> local_site (local_site_id));
+diesel::joinable!(thread_subscription -> comment (comment_id));
+diesel::joinable!(thread_subscription -> person (person_id));
+diesel::joinable!(thread_subscription -> post (post_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    actor_integrity_key,
+    actor_key_rotation,
+    admin_merge_person,
     admin_purge_comment,
     admin_purge_community,
     admin_purge_person,
     admin_purge_post,
+    announcement,
+    blocked_url,
+    blocklist_subscription,
     captcha_answer,
+    category,
     comment,
     comment_aggregates,
     comment_like,
+    comment_reaction,
     comment_reply,
     comment_report,
     comment_saved,
     community,
     community_aggregates,
+    community_application,
     community_block,
+    community_category,
+    community_export,
+    community_export_chunk,
     community_follower,
+    community_import,
+    community_import_object,
     community_language,
+    community_last_seen,
     community_moderator,
+    community_notification,
+    community_person_aggregates,
     community_person_ban,
+    community_rule,
+    community_snooze,
+    community_strike,
+    community_transfer_request,
+    community_word_filter,
     custom_emoji,
     custom_emoji_keyword,
+    disposable_email_domain,
+    email_domain,
     email_verification,
     federation_allowlist,
     federation_blocklist,
     instance,
+    instance_trust,
+    invite,
+    invite_use,
     language,
+    local_image,
     local_site,
     local_site_rate_limit,
     local_user,
     local_user_language,
+    login_history,
+    login_session,
+    magic_login_token,
     mod_add,
     mod_add_community,
     mod_ban,
@@ -973,29 +1633,43 @@ diesel::allow_tables_to_appear_in_same_query!(
     mod_remove_comment,
     mod_remove_community,
     mod_remove_post,
+    mod_shadowban,
     mod_transfer_community,
     password_reset_request,
     person,
     person_aggregates,
+    person_alias,
     person_ban,
     person_block,
     person_follower,
     person_mention,
+    person_note,
     person_post_aggregates,
+    person_vote_pseudonym,
     post,
     post_aggregates,
     post_like,
+    post_metadata_refetch,
+    post_notification,
+    post_reaction,
     post_read,
     post_report,
     post_saved,
     private_message,
     private_message_report,
+    rate_limit_override,
     received_activity,
     registration_application,
+    report_reason,
+    scheduled_job,
     secret,
     sent_activity,
+    sent_activity_delivery,
     site,
     site_aggregates,
     site_language,
+    site_stats_history,
+    slur_filter,
     tagline,
+    thread_subscription,
 );
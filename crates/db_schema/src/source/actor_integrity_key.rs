@@ -0,0 +1,22 @@
+use crate::{newtypes::DbUrl, schema::actor_integrity_key};
+
+/// Ed25519 key of an actor for object integrity proofs (FEP-8b32). The public key is multibase
+/// encoded as in the `assertionMethod` of the actor, the private key is only known for local actors.
+#[derive(PartialEq, Eq, Debug, Clone, Queryable)]
+pub struct ActorIntegrityKey {
+  pub id: i32,
+  pub actor_id: DbUrl,
+  pub public_key: String,
+  pub private_key: Option<String>,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone, Insertable, AsChangeset)]
+#[diesel(table_name = actor_integrity_key)]
+#[diesel(treat_none_as_null = true)]
+pub struct ActorIntegrityKeyForm {
+  pub actor_id: DbUrl,
+  pub public_key: String,
+  pub private_key: Option<String>,
+}
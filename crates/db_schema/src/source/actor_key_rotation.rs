@@ -0,0 +1,11 @@
+use crate::newtypes::DbUrl;
+
+/// The previous public key of an actor, written by a database trigger whenever the key of a
+/// person, community or site changes.
+#[derive(PartialEq, Eq, Debug, Queryable)]
+pub struct ActorKeyRotation {
+  pub id: i32,
+  pub actor_id: DbUrl,
+  pub previous_public_key: String,
+  pub rotated_at: chrono::NaiveDateTime,
+}
@@ -0,0 +1,53 @@
+#[cfg(feature = "full")]
+use crate::schema::announcement;
+use crate::{
+  newtypes::{AnnouncementId, PersonId},
+  AnnouncementSeverity,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = announcement))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A banner shown across the whole site, eg to warn about maintenance.
+pub struct Announcement {
+  pub id: AnnouncementId,
+  /// The admin who created the announcement.
+  pub creator_id: PersonId,
+  /// The message, in markdown.
+  pub message: String,
+  pub severity: AnnouncementSeverity,
+  /// When the announcement starts being shown.
+  pub start_time: chrono::NaiveDateTime,
+  /// When the announcement stops being shown. It is shown indefinitely if this is empty.
+  pub end_time: Option<chrono::NaiveDateTime>,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = announcement))]
+pub struct AnnouncementInsertForm {
+  pub creator_id: PersonId,
+  pub message: String,
+  pub severity: AnnouncementSeverity,
+  pub start_time: chrono::NaiveDateTime,
+  pub end_time: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = announcement))]
+pub struct AnnouncementUpdateForm {
+  pub message: String,
+  pub severity: AnnouncementSeverity,
+  pub start_time: chrono::NaiveDateTime,
+  pub end_time: Option<Option<chrono::NaiveDateTime>>,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
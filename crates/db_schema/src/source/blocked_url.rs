@@ -0,0 +1,29 @@
+use crate::newtypes::BlockedUrlId;
+#[cfg(feature = "full")]
+use crate::schema::blocked_url;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = blocked_url))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A domain or url prefix which can't be linked in posts.
+pub struct BlockedUrl {
+  pub id: BlockedUrlId,
+  /// Either a bare domain, which also blocks its subdomains, or a url prefix without the scheme.
+  pub url: String,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = blocked_url))]
+pub struct BlockedUrlForm {
+  pub url: String,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
@@ -0,0 +1,38 @@
+use crate::newtypes::BlocklistSubscriptionId;
+#[cfg(feature = "full")]
+use crate::schema::blocklist_subscription;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = blocklist_subscription))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An external list of instances to defederate from, which is synced into the federation blocklist.
+pub struct BlocklistSubscription {
+  pub id: BlocklistSubscriptionId,
+  /// Returns the blocked domains, either as plain text with one domain per line, as a Mastodon
+  /// domain block csv export, or as json.
+  pub url: String,
+  pub published: chrono::NaiveDateTime,
+  pub last_synced_at: Option<chrono::NaiveDateTime>,
+  /// Why the last sync failed, if it did.
+  pub last_error: Option<String>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = blocklist_subscription))]
+pub struct BlocklistSubscriptionForm {
+  pub url: String,
+}
+
+/// A blocked domain as given by a blocklist subscription.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlocklistEntry {
+  pub domain: String,
+  pub reason: Option<String>,
+}
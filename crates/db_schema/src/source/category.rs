@@ -0,0 +1,56 @@
+use crate::newtypes::{CategoryId, CommunityId};
+#[cfg(feature = "full")]
+use crate::schema::{category, community_category};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = category))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A topic defined by the admins, which communities can assign to themselves.
+pub struct Category {
+  pub id: CategoryId,
+  pub name: String,
+  pub description: Option<String>,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = category))]
+pub struct CategoryInsertForm {
+  pub name: String,
+  pub description: Option<String>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = category))]
+pub struct CategoryUpdateForm {
+  pub name: Option<String>,
+  pub description: Option<Option<String>>,
+  pub updated: Option<Option<chrono::NaiveDateTime>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_category))]
+pub struct CommunityCategory {
+  #[serde(skip)]
+  pub id: i32,
+  pub community_id: CommunityId,
+  pub category_id: CategoryId,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_category))]
+pub struct CommunityCategoryForm {
+  pub community_id: CommunityId,
+  pub category_id: CategoryId,
+}
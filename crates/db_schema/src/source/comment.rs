@@ -2,7 +2,7 @@
 use crate::newtypes::LtreeDef;
 use crate::newtypes::{CommentId, DbUrl, LanguageId, PersonId, PostId};
 #[cfg(feature = "full")]
-use crate::schema::{comment, comment_like, comment_saved};
+use crate::schema::{comment, comment_like, comment_reaction, comment_saved};
 #[cfg(feature = "full")]
 use diesel_ltree::Ltree;
 use serde::{Deserialize, Serialize};
@@ -105,6 +105,28 @@ pub struct CommentLikeForm {
   pub score: i16,
 }
 
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Identifiable, Queryable, Associations))]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::comment::Comment)))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_reaction))]
+/// An emoji reaction on a comment.
+pub struct CommentReaction {
+  pub id: i32,
+  pub comment_id: CommentId,
+  pub person_id: PersonId,
+  pub emoji: String,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_reaction))]
+pub struct CommentReactionForm {
+  pub comment_id: CommentId,
+  pub person_id: PersonId,
+  pub emoji: String,
+}
+
 #[derive(PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "full", derive(Identifiable, Queryable, Associations))]
 #[cfg_attr(feature = "full", diesel(belongs_to(crate::source::comment::Comment)))]
@@ -1,4 +1,4 @@
-use crate::newtypes::{CommentId, CommentReportId, PersonId};
+use crate::newtypes::{CommentId, CommentReportId, CommunityRuleId, PersonId, ReportReasonId};
 #[cfg(feature = "full")]
 use crate::schema::comment_report;
 use serde::{Deserialize, Serialize};
@@ -23,6 +23,10 @@ pub struct CommentReport {
   pub resolver_id: Option<PersonId>,
   pub published: chrono::NaiveDateTime,
   pub updated: Option<chrono::NaiveDateTime>,
+  /// The listed reason which was picked for the report, if any.
+  pub reason_id: Option<ReportReasonId>,
+  /// The community rule which the content breaks, if any.
+  pub rule_id: Option<CommunityRuleId>,
 }
 
 #[derive(Clone)]
@@ -33,4 +37,6 @@ pub struct CommentReportForm {
   pub comment_id: CommentId,
   pub original_comment_text: String,
   pub reason: String,
+  pub reason_id: Option<ReportReasonId>,
+  pub rule_id: Option<CommunityRuleId>,
 }
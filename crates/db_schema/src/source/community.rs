@@ -1,8 +1,15 @@
 #[cfg(feature = "full")]
-use crate::schema::{community, community_follower, community_moderator, community_person_ban};
+use crate::schema::{
+  community,
+  community_follower,
+  community_moderator,
+  community_person_ban,
+  community_transfer_request,
+};
 use crate::{
   newtypes::{CommunityId, DbUrl, InstanceId, PersonId},
   source::placeholder_apub_url,
+  PostTypeRestriction,
 };
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -62,6 +69,32 @@ pub struct Community {
   /// Url where featured posts collection is served over Activitypub
   #[serde(skip)]
   pub featured_url: Option<DbUrl>,
+  /// Whether downvotes are allowed in this community.
+  pub enable_downvotes: bool,
+  /// Whether new followers need to be approved by a mod.
+  pub requires_follow_approval: bool,
+  /// Which kinds of posts are allowed in the community.
+  pub post_type_restriction: PostTypeRestriction,
+  /// Whether all posts in the community are marked as NSFW.
+  pub nsfw_only: bool,
+  /// Minimum number of seconds between posts of a user, 0 if disabled.
+  pub post_slow_mode_seconds: i32,
+  /// Minimum number of seconds between comments of a user, 0 if disabled.
+  pub comment_slow_mode_seconds: i32,
+  /// Whether content without a language is allowed in the community.
+  pub allow_undetermined_language: bool,
+  /// Number of strikes after which a user is temporarily banned, 0 if disabled.
+  pub strike_ban_threshold: i32,
+  /// Length of the temporary ban in days.
+  pub strike_ban_days: i32,
+  /// Minimum age of accounts which post or comment in the community, 0 if disabled.
+  pub min_account_age_days: i32,
+  /// Minimum site-wide karma of users who post or comment in the community, 0 if disabled.
+  pub min_site_karma: i32,
+  /// Minimum karma within the community of users who post or comment in it, 0 if disabled.
+  pub min_community_karma: i32,
+  /// Whether the mods are notified when the community is mentioned in a post or comment.
+  pub notify_mods_on_mention: bool,
 }
 
 #[derive(Debug, Clone, TypedBuilder)]
@@ -95,6 +128,19 @@ pub struct CommunityInsertForm {
   pub posting_restricted_to_mods: Option<bool>,
   #[builder(!default)]
   pub instance_id: InstanceId,
+  pub enable_downvotes: Option<bool>,
+  pub requires_follow_approval: Option<bool>,
+  pub post_type_restriction: Option<PostTypeRestriction>,
+  pub nsfw_only: Option<bool>,
+  pub post_slow_mode_seconds: Option<i32>,
+  pub comment_slow_mode_seconds: Option<i32>,
+  pub allow_undetermined_language: Option<bool>,
+  pub strike_ban_threshold: Option<i32>,
+  pub strike_ban_days: Option<i32>,
+  pub min_account_age_days: Option<i32>,
+  pub min_site_karma: Option<i32>,
+  pub min_community_karma: Option<i32>,
+  pub notify_mods_on_mention: Option<bool>,
 }
 
 #[derive(Debug, Clone, TypedBuilder)]
@@ -123,6 +169,19 @@ pub struct CommunityUpdateForm {
   pub featured_url: Option<DbUrl>,
   pub hidden: Option<bool>,
   pub posting_restricted_to_mods: Option<bool>,
+  pub enable_downvotes: Option<bool>,
+  pub requires_follow_approval: Option<bool>,
+  pub post_type_restriction: Option<PostTypeRestriction>,
+  pub nsfw_only: Option<bool>,
+  pub post_slow_mode_seconds: Option<i32>,
+  pub comment_slow_mode_seconds: Option<i32>,
+  pub allow_undetermined_language: Option<bool>,
+  pub strike_ban_threshold: Option<i32>,
+  pub strike_ban_days: Option<i32>,
+  pub min_account_age_days: Option<i32>,
+  pub min_site_karma: Option<i32>,
+  pub min_community_karma: Option<i32>,
+  pub notify_mods_on_mention: Option<bool>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -194,3 +253,29 @@ pub struct CommunityFollowerForm {
   pub person_id: PersonId,
   pub pending: bool,
 }
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Identifiable, Queryable, Associations, TS))]
+#[cfg_attr(
+  feature = "full",
+  diesel(belongs_to(crate::source::community::Community))
+)]
+#[cfg_attr(feature = "full", diesel(table_name = community_transfer_request))]
+#[cfg_attr(feature = "full", diesel(primary_key(community_id)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A community transfer which is waiting for the new owner to accept it.
+pub struct CommunityTransferRequest {
+  pub community_id: CommunityId,
+  pub requester_id: PersonId,
+  pub recipient_id: PersonId,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_transfer_request))]
+pub struct CommunityTransferRequestForm {
+  pub community_id: CommunityId,
+  pub requester_id: PersonId,
+  pub recipient_id: PersonId,
+}
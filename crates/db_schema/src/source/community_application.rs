@@ -0,0 +1,48 @@
+use crate::newtypes::{CommunityApplicationId, CommunityId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::community_application;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = community_application))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A new community which has to be approved by an admin before it is created.
+pub struct CommunityApplication {
+  pub id: CommunityApplicationId,
+  pub creator_id: PersonId,
+  pub name: String,
+  pub title: String,
+  pub description: Option<String>,
+  pub nsfw: bool,
+  /// The admin who approved or denied the application.
+  pub admin_id: Option<PersonId>,
+  pub deny_reason: Option<String>,
+  /// The community which was created when the application was approved.
+  pub community_id: Option<CommunityId>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_application))]
+pub struct CommunityApplicationInsertForm {
+  pub creator_id: PersonId,
+  pub name: String,
+  pub title: String,
+  pub description: Option<String>,
+  pub nsfw: bool,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_application))]
+pub struct CommunityApplicationUpdateForm {
+  pub admin_id: Option<PersonId>,
+  pub deny_reason: Option<String>,
+  pub community_id: Option<CommunityId>,
+}
@@ -0,0 +1,34 @@
+use crate::newtypes::{CommunityExportId, CommunityId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::community_export;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = community_export))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An archive of a community which is generated in the background. The archive itself is only
+/// returned by the download endpoint.
+pub struct CommunityExport {
+  pub id: CommunityExportId,
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  /// Why the archive couldn't be generated.
+  pub error: Option<String>,
+  pub published: chrono::NaiveDateTime,
+  /// When the archive was generated or failed. It can be downloaded once this is set without an
+  /// error.
+  pub finished: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_export))]
+pub struct CommunityExportForm {
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+}
@@ -0,0 +1,49 @@
+use crate::newtypes::{CommentId, CommunityId, CommunityImportId, PersonId, PostId};
+#[cfg(feature = "full")]
+use crate::schema::{community_import, community_import_object};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = community_import))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The import of a community archive into a new local community, which runs in the background.
+pub struct CommunityImport {
+  pub id: CommunityImportId,
+  /// The community which was created for the import.
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  pub posts_imported: i32,
+  pub comments_imported: i32,
+  /// Duplicates, and posts or comments which aren't valid on this instance.
+  pub skipped: i32,
+  /// Why the import was aborted.
+  pub error: Option<String>,
+  pub published: chrono::NaiveDateTime,
+  pub finished: Option<chrono::NaiveDateTime>,
+  /// When the import last made progress.
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_import))]
+pub struct CommunityImportForm {
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_import_object))]
+/// A post or comment which was created by an import, by its activitypub id in the archive.
+pub struct CommunityImportObject {
+  pub source_ap_id: String,
+  pub import_id: CommunityImportId,
+  pub post_id: Option<PostId>,
+  pub comment_id: Option<CommentId>,
+}
@@ -0,0 +1,38 @@
+use crate::newtypes::{CommunityId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::community_last_seen;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Associations, Identifiable))]
+#[cfg_attr(
+  feature = "full",
+  diesel(belongs_to(crate::source::community::Community))
+)]
+#[cfg_attr(feature = "full", diesel(table_name = community_last_seen))]
+#[cfg_attr(feature = "full", diesel(primary_key(person_id, community_id)))]
+/// When a person last viewed the posts of a community.
+pub struct CommunityLastSeen {
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+  pub last_seen: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_last_seen))]
+pub struct CommunityLastSeenForm {
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+  pub last_seen: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The number of new posts in a followed community since the last visit.
+pub struct CommunityUnreadPosts {
+  pub community_id: CommunityId,
+  pub unread_posts: i64,
+}
@@ -0,0 +1,52 @@
+use crate::newtypes::{CommunityId, PersonId, PostId, PostNotificationId};
+#[cfg(feature = "full")]
+use crate::schema::{community_notification, post_notification};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = community_notification))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A user who wants to be notified about new posts in a community.
+pub struct CommunityNotification {
+  pub id: i32,
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+  /// At most one notification is sent per interval.
+  pub min_interval_minutes: i32,
+  pub last_notified: Option<chrono::NaiveDateTime>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_notification))]
+pub struct CommunityNotificationForm {
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+  pub min_interval_minutes: i32,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = post_notification))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A notification about a new post in a community.
+pub struct PostNotification {
+  pub id: PostNotificationId,
+  pub recipient_id: PersonId,
+  pub post_id: PostId,
+  pub read: bool,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_notification))]
+pub struct PostNotificationInsertForm {
+  pub recipient_id: PersonId,
+  pub post_id: PostId,
+}
@@ -0,0 +1,38 @@
+use crate::newtypes::{CommunityId, CommunityRuleId};
+#[cfg(feature = "full")]
+use crate::schema::community_rule;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Associations, Identifiable, TS))]
+#[cfg_attr(
+  feature = "full",
+  diesel(belongs_to(crate::source::community::Community))
+)]
+#[cfg_attr(feature = "full", diesel(table_name = community_rule))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A rule of a community, which reports can refer to.
+pub struct CommunityRule {
+  pub id: CommunityRuleId,
+  pub community_id: CommunityId,
+  /// Rules are shown in ascending order of their position.
+  pub position: i32,
+  pub title: String,
+  pub description: Option<String>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_rule))]
+#[cfg_attr(feature = "full", diesel(treat_none_as_null = true))]
+pub struct CommunityRuleForm {
+  pub community_id: CommunityId,
+  pub position: i32,
+  pub title: String,
+  pub description: Option<String>,
+}
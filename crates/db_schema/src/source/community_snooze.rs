@@ -0,0 +1,29 @@
+use crate::newtypes::{CommunityId, CommunitySnoozeId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::community_snooze;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = community_snooze))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A community which a user hid from their feeds for a while, without unsubscribing.
+pub struct CommunitySnooze {
+  pub id: CommunitySnoozeId,
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+  /// The community shows up in feeds again after this time.
+  pub expires: chrono::NaiveDateTime,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_snooze))]
+pub struct CommunitySnoozeForm {
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+  pub expires: chrono::NaiveDateTime,
+}
@@ -0,0 +1,38 @@
+use crate::newtypes::{CommentId, CommunityId, CommunityStrikeId, PersonId, PostId};
+#[cfg(feature = "full")]
+use crate::schema::community_strike;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = community_strike))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A strike which a mod gave a user in a community, when removing their content or banning them.
+pub struct CommunityStrike {
+  pub id: CommunityStrikeId,
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  pub mod_person_id: PersonId,
+  /// The removed post, if the strike was given for one.
+  pub post_id: Option<PostId>,
+  /// The removed comment, if the strike was given for one.
+  pub comment_id: Option<CommentId>,
+  pub reason: Option<String>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_strike))]
+pub struct CommunityStrikeForm {
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  pub mod_person_id: PersonId,
+  pub post_id: Option<PostId>,
+  pub comment_id: Option<CommentId>,
+  pub reason: Option<String>,
+}
@@ -0,0 +1,37 @@
+#[cfg(feature = "full")]
+use crate::schema::community_word_filter;
+use crate::{
+  newtypes::{CommunityId, CommunityWordFilterId},
+  WordFilterAction,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Associations, Identifiable, TS))]
+#[cfg_attr(
+  feature = "full",
+  diesel(belongs_to(crate::source::community::Community))
+)]
+#[cfg_attr(feature = "full", diesel(table_name = community_word_filter))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A regex which mods apply to posts and comments in their community, in addition to the site
+/// slur filter.
+pub struct CommunityWordFilter {
+  pub id: CommunityWordFilterId,
+  pub community_id: CommunityId,
+  /// A case insensitive regex.
+  pub pattern: String,
+  pub action: WordFilterAction,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_word_filter))]
+pub struct CommunityWordFilterForm {
+  pub community_id: CommunityId,
+  pub pattern: String,
+  pub action: WordFilterAction,
+}
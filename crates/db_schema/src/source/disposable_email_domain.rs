@@ -0,0 +1,24 @@
+#[cfg(feature = "full")]
+use crate::schema::disposable_email_domain;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = disposable_email_domain))]
+/// A domain of a disposable email provider, or one which admins don't consider disposable.
+pub struct DisposableEmailDomain {
+  pub id: i32,
+  pub domain: String,
+  /// Whether admins made an exception for the domain, so that it isn't treated as disposable.
+  pub exception: bool,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = disposable_email_domain))]
+pub struct DisposableEmailDomainForm {
+  pub domain: String,
+  pub exception: bool,
+}
@@ -0,0 +1,24 @@
+#[cfg(feature = "full")]
+use crate::schema::email_domain;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = email_domain))]
+/// An email domain which is allowed or blocked for signups.
+pub struct EmailDomain {
+  pub id: i32,
+  /// A bare domain, which also covers its subdomains.
+  pub domain: String,
+  pub allowed: bool,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = email_domain))]
+pub struct EmailDomainForm {
+  pub domain: String,
+  pub allowed: bool,
+}
@@ -1,21 +1,32 @@
-use crate::newtypes::InstanceId;
+use crate::newtypes::{BlocklistSubscriptionId, InstanceId};
 #[cfg(feature = "full")]
 use crate::schema::federation_blocklist;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use std::fmt::Debug;
+#[cfg(feature = "full")]
+use ts_rs::TS;
 
+#[skip_serializing_none]
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
-#[cfg_attr(feature = "full", derive(Queryable, Associations, Identifiable))]
+#[cfg_attr(feature = "full", derive(Queryable, Associations, Identifiable, TS))]
 #[cfg_attr(
   feature = "full",
   diesel(belongs_to(crate::source::instance::Instance))
 )]
 #[cfg_attr(feature = "full", diesel(table_name = federation_blocklist))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An instance which this site doesn't federate with.
 pub struct FederationBlockList {
   pub id: i32,
   pub instance_id: InstanceId,
   pub published: chrono::NaiveDateTime,
   pub updated: Option<chrono::NaiveDateTime>,
+  /// The subscription which added this entry, or none if it was added by an admin.
+  pub subscription_id: Option<BlocklistSubscriptionId>,
+  pub reason: Option<String>,
+  /// Pinned entries are never removed by a blocklist sync.
+  pub pinned: bool,
 }
 
 #[derive(Clone, Default)]
@@ -24,4 +35,6 @@ pub struct FederationBlockList {
 pub struct FederationBlockListForm {
   pub instance_id: InstanceId,
   pub updated: Option<chrono::NaiveDateTime>,
+  pub subscription_id: Option<BlocklistSubscriptionId>,
+  pub reason: Option<String>,
 }
@@ -21,6 +21,8 @@ pub struct Instance {
   pub updated: Option<chrono::NaiveDateTime>,
   pub software: Option<String>,
   pub version: Option<String>,
+  /// Whether the instance accepts new users, as reported in its nodeinfo.
+  pub open_registrations: Option<bool>,
 }
 
 #[derive(Clone, TypedBuilder)]
@@ -32,5 +34,6 @@ pub struct InstanceForm {
   pub domain: String,
   pub software: Option<String>,
   pub version: Option<String>,
+  pub open_registrations: Option<bool>,
   pub updated: Option<chrono::NaiveDateTime>,
 }
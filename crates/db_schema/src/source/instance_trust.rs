@@ -0,0 +1,36 @@
+use crate::newtypes::InstanceId;
+#[cfg(feature = "full")]
+use crate::schema::instance_trust;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = instance_trust))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Trust signals for a federated instance, as reported by Fediseer.
+pub struct InstanceTrust {
+  pub id: i32,
+  pub instance_id: InstanceId,
+  /// The instance which guarantees that this one isn't run by spammers, if any.
+  pub guarantor: Option<String>,
+  pub endorsements: i32,
+  /// The number of trusted instances which censured this one.
+  pub censures: i32,
+  pub censure_reasons: Option<String>,
+  pub updated: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = instance_trust))]
+pub struct InstanceTrustForm {
+  pub instance_id: InstanceId,
+  pub guarantor: Option<String>,
+  pub endorsements: i32,
+  pub censures: i32,
+  pub censure_reasons: Option<String>,
+}
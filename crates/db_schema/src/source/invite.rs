@@ -0,0 +1,55 @@
+use crate::newtypes::{InviteId, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::{invite, invite_use};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = invite))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An invite code, which is required to register when the site is in invite mode.
+pub struct Invite {
+  pub id: InviteId,
+  pub code: String,
+  pub creator_id: PersonId,
+  /// How many users can register with the code.
+  pub max_uses: i32,
+  /// How many users have registered with the code so far.
+  pub uses: i32,
+  pub expires: Option<chrono::NaiveDateTime>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = invite))]
+pub struct InviteInsertForm {
+  pub code: String,
+  pub creator_id: PersonId,
+  pub max_uses: i32,
+  pub expires: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = invite_use))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A user who registered with an invite code.
+pub struct InviteUse {
+  pub id: i32,
+  pub invite_id: InviteId,
+  pub person_id: PersonId,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = invite_use))]
+pub struct InviteUseForm {
+  pub invite_id: InviteId,
+  pub person_id: PersonId,
+}
@@ -0,0 +1,41 @@
+use crate::newtypes::{CommentId, LocalImageId, PersonId, PostId};
+#[cfg(feature = "full")]
+use crate::schema::local_image;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+use typed_builder::TypedBuilder;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = local_image))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An image uploaded to pictrs by a local user.
+pub struct LocalImage {
+  pub id: LocalImageId,
+  pub person_id: PersonId,
+  pub pictrs_alias: String,
+  pub pictrs_delete_token: String,
+  /// The post which uses this image, if any.
+  pub post_id: Option<PostId>,
+  /// The comment which uses this image, if any.
+  pub comment_id: Option<CommentId>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, TypedBuilder)]
+#[builder(field_defaults(default))]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = local_image))]
+pub struct LocalImageForm {
+  #[builder(!default)]
+  pub person_id: PersonId,
+  #[builder(!default)]
+  pub pictrs_alias: String,
+  #[builder(!default)]
+  pub pictrs_delete_token: String,
+  pub post_id: Option<PostId>,
+  pub comment_id: Option<CommentId>,
+}
@@ -59,6 +59,29 @@ pub struct LocalSite {
   pub registration_mode: RegistrationMode,
   /// Whether to email admins on new reports.
   pub reports_email_admins: bool,
+  /// Whether mods and admins can see who voted on posts and comments.
+  pub enable_vote_viewer: bool,
+  /// Federate votes of all local users through pseudonymous actors.
+  pub anonymize_outgoing_votes: bool,
+  /// Whether a private instance federates with the instances in the allowlist. Those have to sign
+  /// their fetches, and no other instances are federated with.
+  pub private_instance_federation: bool,
+  /// Reject passwords which are known from data breaches.
+  pub check_breached_passwords: bool,
+  /// Count 👍 and 👎 reactions from other platforms like Pleroma as votes.
+  pub emoji_reactions_as_votes: bool,
+  /// Whether users other than admins can create invite codes.
+  pub users_can_create_invites: bool,
+  /// Minimum account age in days for non-admins to create communities, 0 if disabled.
+  pub community_creation_min_account_age_days: i32,
+  /// Minimum site-wide karma for non-admins to create communities, 0 if disabled.
+  pub community_creation_min_karma: i32,
+  /// Whether communities created by non-admins have to be approved by an admin.
+  pub community_creation_requires_approval: bool,
+  /// How often local users can change their username, 0 if disabled.
+  pub max_username_changes: i32,
+  /// For how many days a former username can't be taken by other users.
+  pub username_reuse_cooldown_days: i32,
 }
 
 #[derive(Clone, TypedBuilder)]
@@ -87,6 +110,17 @@ pub struct LocalSiteInsertForm {
   pub captcha_difficulty: Option<String>,
   pub registration_mode: Option<RegistrationMode>,
   pub reports_email_admins: Option<bool>,
+  pub enable_vote_viewer: Option<bool>,
+  pub anonymize_outgoing_votes: Option<bool>,
+  pub private_instance_federation: Option<bool>,
+  pub check_breached_passwords: Option<bool>,
+  pub emoji_reactions_as_votes: Option<bool>,
+  pub users_can_create_invites: Option<bool>,
+  pub community_creation_min_account_age_days: Option<i32>,
+  pub community_creation_min_karma: Option<i32>,
+  pub community_creation_requires_approval: Option<bool>,
+  pub max_username_changes: Option<i32>,
+  pub username_reuse_cooldown_days: Option<i32>,
 }
 
 #[derive(Clone, TypedBuilder)]
@@ -113,5 +147,16 @@ pub struct LocalSiteUpdateForm {
   pub captcha_difficulty: Option<String>,
   pub registration_mode: Option<RegistrationMode>,
   pub reports_email_admins: Option<bool>,
+  pub enable_vote_viewer: Option<bool>,
+  pub anonymize_outgoing_votes: Option<bool>,
+  pub private_instance_federation: Option<bool>,
+  pub check_breached_passwords: Option<bool>,
+  pub emoji_reactions_as_votes: Option<bool>,
+  pub users_can_create_invites: Option<bool>,
+  pub community_creation_min_account_age_days: Option<i32>,
+  pub community_creation_min_karma: Option<i32>,
+  pub community_creation_requires_approval: Option<bool>,
+  pub max_username_changes: Option<i32>,
+  pub username_reuse_cooldown_days: Option<i32>,
   pub updated: Option<Option<chrono::NaiveDateTime>>,
 }
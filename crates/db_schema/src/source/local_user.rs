@@ -2,6 +2,7 @@
 use crate::schema::local_user;
 use crate::{
   newtypes::{LocalUserId, PersonId},
+  AdminRole,
   ListingType,
   SortType,
 };
@@ -57,6 +58,26 @@ pub struct LocalUser {
   pub auto_expand: bool,
   /// Whether infinite scroll is enabled.
   pub infinite_scroll_enabled: bool,
+  /// Federate your votes through a pseudonymous actor instead of your account.
+  pub anonymize_outgoing_votes: bool,
+  /// Send notifications for replies to your comments.
+  pub notify_comment_replies: bool,
+  /// Send notifications for comments on your posts.
+  pub notify_post_replies: bool,
+  /// Send notifications when you are mentioned.
+  pub notify_mentions: bool,
+  /// Send notifications for private messages.
+  pub notify_private_messages: bool,
+  /// Subscribe to the replies below your own comments.
+  pub auto_subscribe_threads: bool,
+  /// Send moderators and admins a regular email summary of their mod queue.
+  pub email_mod_queue_digest: bool,
+  /// When the last mod queue digest was sent.
+  pub mod_queue_digest_sent: Option<chrono::NaiveDateTime>,
+  /// Send an email when someone logs in from a new device or network.
+  pub email_login_alerts: bool,
+  /// What the user can do as an admin. Only set for admins.
+  pub admin_role: Option<AdminRole>,
 }
 
 #[derive(Clone, TypedBuilder)]
@@ -88,6 +109,15 @@ pub struct LocalUserInsertForm {
   pub blur_nsfw: Option<bool>,
   pub auto_expand: Option<bool>,
   pub infinite_scroll_enabled: Option<bool>,
+  pub anonymize_outgoing_votes: Option<bool>,
+  pub notify_comment_replies: Option<bool>,
+  pub notify_post_replies: Option<bool>,
+  pub notify_mentions: Option<bool>,
+  pub notify_private_messages: Option<bool>,
+  pub auto_subscribe_threads: Option<bool>,
+  pub email_mod_queue_digest: Option<bool>,
+  pub email_login_alerts: Option<bool>,
+  pub admin_role: Option<AdminRole>,
 }
 
 #[derive(Clone, TypedBuilder)]
@@ -116,4 +146,25 @@ pub struct LocalUserUpdateForm {
   pub blur_nsfw: Option<bool>,
   pub auto_expand: Option<bool>,
   pub infinite_scroll_enabled: Option<bool>,
+  pub anonymize_outgoing_votes: Option<bool>,
+  pub notify_comment_replies: Option<bool>,
+  pub notify_post_replies: Option<bool>,
+  pub notify_mentions: Option<bool>,
+  pub notify_private_messages: Option<bool>,
+  pub auto_subscribe_threads: Option<bool>,
+  pub email_mod_queue_digest: Option<bool>,
+  pub mod_queue_digest_sent: Option<Option<chrono::NaiveDateTime>>,
+  pub email_login_alerts: Option<bool>,
+  pub admin_role: Option<Option<AdminRole>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(QueryableByName, TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The number of local users who signed up on a given day.
+pub struct DailySignups {
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Date))]
+  pub day: chrono::NaiveDate,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub signups: i64,
 }
@@ -0,0 +1,25 @@
+use crate::newtypes::LocalUserId;
+#[cfg(feature = "full")]
+use crate::schema::login_history;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = login_history))]
+/// A past login of a local user, with the network and device it came from.
+pub struct LoginHistory {
+  pub id: i32,
+  pub local_user_id: LocalUserId,
+  /// The network of the IP address, eg `192.0.2.0/24`.
+  pub ip_network: Option<String>,
+  /// The browser or app and operating system, eg `Firefox on Linux`.
+  pub device: Option<String>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = login_history))]
+pub struct LoginHistoryInsertForm {
+  pub local_user_id: LocalUserId,
+  pub ip_network: Option<String>,
+  pub device: Option<String>,
+}
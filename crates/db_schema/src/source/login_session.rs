@@ -0,0 +1,39 @@
+use crate::newtypes::{LocalUserId, LoginSessionId};
+#[cfg(feature = "full")]
+use crate::schema::login_session;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = login_session))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A login of a local user, which stays valid until it is logged out or isn't used for a while.
+pub struct LoginSession {
+  pub id: LoginSessionId,
+  /// Hash of the token which the client authenticates with. Never sent out.
+  #[serde(skip)]
+  #[cfg_attr(feature = "full", ts(skip))]
+  pub token_hash: String,
+  pub local_user_id: LocalUserId,
+  /// The IP address which logged in.
+  pub ip: Option<String>,
+  /// The user agent which logged in.
+  pub user_agent: Option<String>,
+  pub published: chrono::NaiveDateTime,
+  /// When the session ends, unless it is used again before.
+  pub expires: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = login_session))]
+pub struct LoginSessionInsertForm {
+  pub token_hash: String,
+  pub local_user_id: LocalUserId,
+  pub ip: Option<String>,
+  pub user_agent: Option<String>,
+  pub expires: chrono::NaiveDateTime,
+}
@@ -0,0 +1,24 @@
+use crate::newtypes::LocalUserId;
+#[cfg(feature = "full")]
+use crate::schema::magic_login_token;
+
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = magic_login_token))]
+/// A single use token for logging in via a link sent by email.
+pub struct MagicLoginToken {
+  pub id: i32,
+  /// Hash of the token from the link.
+  pub token_hash: String,
+  pub local_user_id: LocalUserId,
+  pub published: chrono::NaiveDateTime,
+  pub expires: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = magic_login_token))]
+pub struct MagicLoginTokenForm {
+  pub token_hash: String,
+  pub local_user_id: LocalUserId,
+  pub expires: chrono::NaiveDateTime,
+}
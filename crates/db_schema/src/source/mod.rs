@@ -3,36 +3,70 @@ use url::Url;
 
 #[cfg(feature = "full")]
 pub mod activity;
+#[cfg(feature = "full")]
+pub mod actor_integrity_key;
+#[cfg(feature = "full")]
+pub mod actor_key_rotation;
 pub mod actor_language;
+pub mod announcement;
+pub mod blocked_url;
+pub mod blocklist_subscription;
 pub mod captcha_answer;
+pub mod category;
 pub mod comment;
 pub mod comment_reply;
 pub mod comment_report;
 pub mod community;
+pub mod community_application;
 pub mod community_block;
+pub mod community_export;
+pub mod community_import;
+pub mod community_last_seen;
+pub mod community_notification;
+pub mod community_rule;
+pub mod community_snooze;
+pub mod community_strike;
+pub mod community_word_filter;
 pub mod custom_emoji;
 pub mod custom_emoji_keyword;
+pub mod disposable_email_domain;
+pub mod email_domain;
 pub mod email_verification;
 pub mod federation_allowlist;
 pub mod federation_blocklist;
 pub mod instance;
+pub mod instance_trust;
+pub mod invite;
 pub mod language;
+pub mod local_image;
 pub mod local_site;
 pub mod local_site_rate_limit;
 pub mod local_user;
+pub mod login_history;
+pub mod login_session;
+pub mod magic_login_token;
 pub mod moderator;
 pub mod password_reset_request;
 pub mod person;
+pub mod person_alias;
 pub mod person_block;
 pub mod person_mention;
+pub mod person_note;
+pub mod person_vote_pseudonym;
 pub mod post;
 pub mod post_report;
 pub mod private_message;
 pub mod private_message_report;
+pub mod rate_limit_override;
 pub mod registration_application;
+pub mod report_reason;
+pub mod scheduled_job;
 pub mod secret;
+pub mod sent_activity_delivery;
 pub mod site;
+pub mod slur_filter;
 pub mod tagline;
+pub mod thread_subscription;
 
 /// Default value for columns like [community::Community.inbox_url] which are marked as serde(skip).
 ///
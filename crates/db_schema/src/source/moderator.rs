@@ -1,6 +1,7 @@
 use crate::newtypes::{CommentId, CommunityId, PersonId, PostId};
 #[cfg(feature = "full")]
 use crate::schema::{
+  admin_merge_person,
   admin_purge_comment,
   admin_purge_community,
   admin_purge_person,
@@ -15,6 +16,7 @@ use crate::schema::{
   mod_remove_comment,
   mod_remove_community,
   mod_remove_post,
+  mod_shadowban,
   mod_transfer_community,
 };
 use serde::{Deserialize, Serialize};
@@ -218,6 +220,30 @@ pub struct ModBanForm {
   pub expires: Option<chrono::NaiveDateTime>,
 }
 
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_shadowban))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When someone is shadowbanned. Only visible to admins.
+pub struct ModShadowban {
+  pub id: i32,
+  pub mod_person_id: PersonId,
+  pub other_person_id: PersonId,
+  pub reason: Option<String>,
+  pub shadowbanned: bool,
+  pub when_: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = mod_shadowban))]
+pub struct ModShadowbanForm {
+  pub mod_person_id: PersonId,
+  pub other_person_id: PersonId,
+  pub reason: Option<String>,
+  pub shadowbanned: bool,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
 #[cfg_attr(feature = "full", diesel(table_name = mod_add_community))]
@@ -225,7 +251,9 @@ pub struct ModBanForm {
 /// When someone is added as a community moderator.
 pub struct ModAddCommunity {
   pub id: i32,
-  pub mod_person_id: PersonId,
+  /// Empty if the change was synced from the moderators of a remote community, which don't say who
+  /// made it. The community itself is the actor then.
+  pub mod_person_id: Option<PersonId>,
   pub other_person_id: PersonId,
   pub community_id: CommunityId,
   pub removed: bool,
@@ -235,7 +263,7 @@ pub struct ModAddCommunity {
 #[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
 #[cfg_attr(feature = "full", diesel(table_name = mod_add_community))]
 pub struct ModAddCommunityForm {
-  pub mod_person_id: PersonId,
+  pub mod_person_id: Option<PersonId>,
   pub other_person_id: PersonId,
   pub community_id: CommunityId,
   pub removed: Option<bool>,
@@ -283,6 +311,30 @@ pub struct ModAddForm {
   pub removed: Option<bool>,
 }
 
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = admin_merge_person))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When an admin merges a local account into another one. Only visible to admins.
+pub struct AdminMergePerson {
+  pub id: i32,
+  pub admin_person_id: PersonId,
+  pub source_person_id: PersonId,
+  pub target_person_id: PersonId,
+  pub reason: Option<String>,
+  pub when_: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = admin_merge_person))]
+pub struct AdminMergePersonForm {
+  pub admin_person_id: PersonId,
+  pub source_person_id: PersonId,
+  pub target_person_id: PersonId,
+  pub reason: Option<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
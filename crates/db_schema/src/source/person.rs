@@ -56,6 +56,9 @@ pub struct Person {
   /// When their ban, if it exists, expires, if at all.
   pub ban_expires: Option<chrono::NaiveDateTime>,
   pub instance_id: InstanceId,
+  /// Since when the person is shadowbanned. Never serialized, so that the person can't tell.
+  #[serde(skip)]
+  pub shadowbanned_at: Option<chrono::NaiveDateTime>,
 }
 
 #[derive(Clone, TypedBuilder)]
@@ -94,6 +97,7 @@ pub struct PersonInsertForm {
 #[cfg_attr(feature = "full", diesel(table_name = person))]
 #[builder(field_defaults(default))]
 pub struct PersonUpdateForm {
+  pub name: Option<String>,
   pub display_name: Option<Option<String>>,
   pub avatar: Option<Option<DbUrl>>,
   pub banned: Option<bool>,
@@ -112,6 +116,7 @@ pub struct PersonUpdateForm {
   pub admin: Option<bool>,
   pub bot_account: Option<bool>,
   pub ban_expires: Option<Option<chrono::NaiveDateTime>>,
+  pub shadowbanned_at: Option<Option<chrono::NaiveDateTime>>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -0,0 +1,28 @@
+use crate::newtypes::{DbUrl, PersonId};
+#[cfg(feature = "full")]
+use crate::schema::person_alias;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = person_alias))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A former name of a local user. Requests for the old name are redirected to the user.
+pub struct PersonAlias {
+  pub id: i32,
+  pub person_id: PersonId,
+  pub name: String,
+  pub actor_id: DbUrl,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = person_alias))]
+pub struct PersonAliasForm {
+  pub person_id: PersonId,
+  pub name: String,
+  pub actor_id: DbUrl,
+}
@@ -12,6 +12,8 @@ pub struct PersonBlock {
   pub person_id: PersonId,
   pub target_id: PersonId,
   pub published: chrono::NaiveDateTime,
+  /// The block is lifted automatically after this time.
+  pub expires: Option<chrono::NaiveDateTime>,
 }
 
 #[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
@@ -19,4 +21,5 @@ pub struct PersonBlock {
 pub struct PersonBlockForm {
   pub person_id: PersonId,
   pub target_id: PersonId,
+  pub expires: Option<Option<chrono::NaiveDateTime>>,
 }
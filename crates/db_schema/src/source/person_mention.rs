@@ -1,4 +1,4 @@
-use crate::newtypes::{CommentId, PersonId, PersonMentionId};
+use crate::newtypes::{CommentId, PersonId, PersonMentionId, PostId};
 #[cfg(feature = "full")]
 use crate::schema::person_mention;
 use serde::{Deserialize, Serialize};
@@ -10,20 +10,22 @@ use ts_rs::TS;
 #[cfg_attr(feature = "full", diesel(belongs_to(crate::source::comment::Comment)))]
 #[cfg_attr(feature = "full", diesel(table_name = person_mention))]
 #[cfg_attr(feature = "full", ts(export))]
-/// A person mention.
+/// A person mention, in either a comment or a post.
 pub struct PersonMention {
   pub id: PersonMentionId,
   pub recipient_id: PersonId,
-  pub comment_id: CommentId,
+  pub comment_id: Option<CommentId>,
   pub read: bool,
   pub published: chrono::NaiveDateTime,
+  pub post_id: Option<PostId>,
 }
 
 #[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
 #[cfg_attr(feature = "full", diesel(table_name = person_mention))]
 pub struct PersonMentionInsertForm {
   pub recipient_id: PersonId,
-  pub comment_id: CommentId,
+  pub comment_id: Option<CommentId>,
+  pub post_id: Option<PostId>,
   pub read: Option<bool>,
 }
 
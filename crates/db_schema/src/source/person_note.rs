@@ -0,0 +1,38 @@
+use crate::newtypes::{PersonId, PersonNoteId};
+#[cfg(feature = "full")]
+use crate::schema::person_note;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = person_note))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A private note which a user attached to another user.
+pub struct PersonNote {
+  pub id: PersonNoteId,
+  /// The user who wrote the note.
+  pub person_id: PersonId,
+  /// The user who the note is about.
+  pub target_id: PersonId,
+  pub note: String,
+  /// A hex color like `#ff8800`, to show the note as a colored tag.
+  pub color: Option<String>,
+  pub published: chrono::NaiveDateTime,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = person_note))]
+#[cfg_attr(feature = "full", diesel(treat_none_as_null = true))]
+pub struct PersonNoteForm {
+  pub person_id: PersonId,
+  pub target_id: PersonId,
+  pub note: String,
+  pub color: Option<String>,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
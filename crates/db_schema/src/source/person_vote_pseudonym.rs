@@ -0,0 +1,21 @@
+use crate::newtypes::PersonId;
+#[cfg(feature = "full")]
+use crate::schema::person_vote_pseudonym;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable))]
+#[cfg_attr(feature = "full", diesel(table_name = person_vote_pseudonym))]
+/// Links a local person to the pseudonymous actor used to federate their votes.
+pub struct PersonVotePseudonym {
+  pub person_id: PersonId,
+  pub pseudonym_id: PersonId,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = person_vote_pseudonym))]
+pub struct PersonVotePseudonymForm {
+  pub person_id: PersonId,
+  pub pseudonym_id: PersonId,
+}
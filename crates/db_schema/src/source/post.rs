@@ -1,6 +1,6 @@
 use crate::newtypes::{CommunityId, DbUrl, LanguageId, PersonId, PostId};
 #[cfg(feature = "full")]
-use crate::schema::{post, post_like, post_read, post_saved};
+use crate::schema::{post, post_like, post_metadata_refetch, post_reaction, post_read, post_saved};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
@@ -53,6 +53,12 @@ pub struct Post {
   pub featured_community: bool,
   /// Whether the post is featured to its site.
   pub featured_local: bool,
+  /// Until when vote counts are hidden and comments are shuffled for non-mods.
+  pub contest_mode_until: Option<chrono::NaiveDateTime>,
+  /// The mime type of the audio file which the url links to, if this is an audio post.
+  pub audio_mime_type: Option<String>,
+  /// The length of the audio in seconds.
+  pub audio_duration: Option<i32>,
 }
 
 #[derive(Debug, Clone, TypedBuilder)]
@@ -83,6 +89,8 @@ pub struct PostInsertForm {
   pub language_id: Option<LanguageId>,
   pub featured_community: Option<bool>,
   pub featured_local: Option<bool>,
+  pub audio_mime_type: Option<String>,
+  pub audio_duration: Option<i32>,
 }
 
 #[derive(Debug, Clone, TypedBuilder)]
@@ -108,6 +116,9 @@ pub struct PostUpdateForm {
   pub language_id: Option<LanguageId>,
   pub featured_community: Option<bool>,
   pub featured_local: Option<bool>,
+  pub contest_mode_until: Option<Option<chrono::NaiveDateTime>>,
+  pub audio_mime_type: Option<Option<String>>,
+  pub audio_duration: Option<Option<i32>>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -131,6 +142,28 @@ pub struct PostLikeForm {
   pub score: i16,
 }
 
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Identifiable, Queryable, Associations))]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::post::Post)))]
+#[cfg_attr(feature = "full", diesel(table_name = post_reaction))]
+/// An emoji reaction on a post.
+pub struct PostReaction {
+  pub id: i32,
+  pub post_id: PostId,
+  pub person_id: PersonId,
+  pub emoji: String,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_reaction))]
+pub struct PostReactionForm {
+  pub post_id: PostId,
+  pub person_id: PersonId,
+  pub emoji: String,
+}
+
 #[derive(PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "full", derive(Identifiable, Queryable, Associations))]
 #[cfg_attr(feature = "full", diesel(belongs_to(crate::source::post::Post)))]
@@ -166,3 +199,23 @@ pub struct PostReadForm {
   pub post_id: PostId,
   pub person_id: PersonId,
 }
+
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Identifiable, Queryable, Associations))]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::post::Post)))]
+#[cfg_attr(feature = "full", diesel(table_name = post_metadata_refetch))]
+#[cfg_attr(feature = "full", diesel(primary_key(post_id)))]
+/// A post whose link metadata couldn't be fetched, which is retried on a backoff schedule.
+pub struct PostMetadataRefetch {
+  pub post_id: PostId,
+  pub attempts: i32,
+  pub next_attempt: chrono::NaiveDateTime,
+}
+
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = post_metadata_refetch))]
+pub struct PostMetadataRefetchForm {
+  pub post_id: PostId,
+  pub attempts: i32,
+  pub next_attempt: chrono::NaiveDateTime,
+}
@@ -1,4 +1,4 @@
-use crate::newtypes::{DbUrl, PersonId, PostId, PostReportId};
+use crate::newtypes::{CommunityRuleId, DbUrl, PersonId, PostId, PostReportId, ReportReasonId};
 #[cfg(feature = "full")]
 use crate::schema::post_report;
 use serde::{Deserialize, Serialize};
@@ -28,6 +28,10 @@ pub struct PostReport {
   pub resolver_id: Option<PersonId>,
   pub published: chrono::NaiveDateTime,
   pub updated: Option<chrono::NaiveDateTime>,
+  /// The listed reason which was picked for the report, if any.
+  pub reason_id: Option<ReportReasonId>,
+  /// The community rule which the content breaks, if any.
+  pub rule_id: Option<CommunityRuleId>,
 }
 
 #[derive(Clone)]
@@ -40,4 +44,6 @@ pub struct PostReportForm {
   pub original_post_url: Option<DbUrl>,
   pub original_post_body: Option<String>,
   pub reason: String,
+  pub reason_id: Option<ReportReasonId>,
+  pub rule_id: Option<CommunityRuleId>,
 }
@@ -1,4 +1,4 @@
-use crate::newtypes::{PersonId, PrivateMessageId, PrivateMessageReportId};
+use crate::newtypes::{PersonId, PrivateMessageId, PrivateMessageReportId, ReportReasonId};
 #[cfg(feature = "full")]
 use crate::schema::private_message_report;
 use serde::{Deserialize, Serialize};
@@ -27,6 +27,8 @@ pub struct PrivateMessageReport {
   pub resolver_id: Option<PersonId>,
   pub published: chrono::NaiveDateTime,
   pub updated: Option<chrono::NaiveDateTime>,
+  /// The listed reason which was picked for the report, if any.
+  pub reason_id: Option<ReportReasonId>,
 }
 
 #[derive(Clone)]
@@ -37,4 +39,5 @@ pub struct PrivateMessageReportForm {
   pub private_message_id: PrivateMessageId,
   pub original_pm_text: String,
   pub reason: String,
+  pub reason_id: Option<ReportReasonId>,
 }
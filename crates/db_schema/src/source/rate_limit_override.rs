@@ -0,0 +1,27 @@
+use crate::newtypes::LocalUserId;
+#[cfg(feature = "full")]
+use crate::schema::rate_limit_override;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = rate_limit_override))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Individual rate limits for a user, which replace those of the site.
+pub struct RateLimitOverride {
+  pub id: i32,
+  pub local_user_id: LocalUserId,
+  /// The rate limits of the user, in percent of the site rate limits.
+  pub limit_percent: i32,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = rate_limit_override))]
+pub struct RateLimitOverrideForm {
+  pub local_user_id: LocalUserId,
+  pub limit_percent: i32,
+}
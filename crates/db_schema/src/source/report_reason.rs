@@ -0,0 +1,29 @@
+use crate::newtypes::{CommunityId, ReportReasonId};
+#[cfg(feature = "full")]
+use crate::schema::report_reason;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = report_reason))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A reason which can be picked when reporting content.
+pub struct ReportReason {
+  pub id: ReportReasonId,
+  /// Only set for reasons defined by the mods of a community. Others apply to the whole site.
+  pub community_id: Option<CommunityId>,
+  pub name: String,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = report_reason))]
+pub struct ReportReasonForm {
+  pub community_id: Option<CommunityId>,
+  pub name: String,
+}
@@ -0,0 +1,47 @@
+#[cfg(feature = "full")]
+use crate::schema::scheduled_job;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = scheduled_job))]
+#[cfg_attr(feature = "full", diesel(primary_key(name)))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A background job of the scheduler, with its schedule and the outcome of its last run.
+pub struct ScheduledJob {
+  pub name: String,
+  pub interval_seconds: i32,
+  pub enabled: bool,
+  pub next_run: chrono::NaiveDateTime,
+  /// Set while a process is running the job. Expires so that the job isn't blocked forever if
+  /// that process dies.
+  pub locked_until: Option<chrono::NaiveDateTime>,
+  pub last_started: Option<chrono::NaiveDateTime>,
+  pub last_finished: Option<chrono::NaiveDateTime>,
+  pub last_duration_ms: Option<i32>,
+  /// The error of the last run, if it failed.
+  pub last_error: Option<String>,
+  pub run_count: i32,
+  pub failure_count: i32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = scheduled_job))]
+pub struct ScheduledJobInsertForm {
+  pub name: String,
+  pub interval_seconds: i32,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = scheduled_job))]
+pub struct ScheduledJobUpdateForm {
+  pub interval_seconds: Option<i32>,
+  pub enabled: Option<bool>,
+  pub next_run: Option<chrono::NaiveDateTime>,
+}
@@ -6,5 +6,17 @@ use crate::schema::secret;
 #[cfg_attr(feature = "full", diesel(table_name = secret))]
 pub struct Secret {
   pub id: i32,
-  pub jwt_secret: String,
+  /// Secret keys for hosted captcha services
+  pub hcaptcha_secret: Option<String>,
+  pub mcaptcha_secret: Option<String>,
+  pub turnstile_secret: Option<String>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = secret))]
+pub struct SecretUpdateForm {
+  pub hcaptcha_secret: Option<Option<String>>,
+  pub mcaptcha_secret: Option<Option<String>>,
+  pub turnstile_secret: Option<Option<String>>,
 }
@@ -0,0 +1,60 @@
+use crate::newtypes::DbUrl;
+#[cfg(feature = "full")]
+use crate::schema::sent_activity_delivery;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = sent_activity_delivery))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An attempt to deliver a sent activity to an inbox.
+pub struct SentActivityDelivery {
+  pub id: i64,
+  /// The id of the delivered activity.
+  pub ap_id: DbUrl,
+  pub inbox: DbUrl,
+  /// The HTTP status of the response, or none if the inbox couldn't be reached.
+  pub status: Option<i32>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = sent_activity_delivery))]
+pub struct SentActivityDeliveryForm {
+  pub ap_id: DbUrl,
+  pub inbox: DbUrl,
+  pub status: Option<i32>,
+}
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(QueryableByName, TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// Delivery statistics for the inboxes of a remote instance.
+pub struct InstanceDeliveryStats {
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Text))]
+  pub domain: String,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub attempts: i64,
+  /// Attempts where the inbox couldn't be reached or didn't accept the activity.
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub failures: i64,
+  /// Deliveries which the federation queue is still retrying.
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub pending_retries: i64,
+  #[cfg_attr(
+    feature = "full",
+    diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)
+  )]
+  pub last_success: Option<chrono::NaiveDateTime>,
+  #[cfg_attr(
+    feature = "full",
+    diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)
+  )]
+  pub last_failure: Option<chrono::NaiveDateTime>,
+}
@@ -0,0 +1,35 @@
+use crate::newtypes::{CommunityId, LanguageId, SlurFilterId};
+#[cfg(feature = "full")]
+use crate::schema::slur_filter;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = slur_filter))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A named slur filter, which applies on top of the site slur filter.
+pub struct SlurFilter {
+  pub id: SlurFilterId,
+  pub name: String,
+  /// A case insensitive regex.
+  pub pattern: String,
+  /// If set, the filter only applies to content in this language.
+  pub language_id: Option<LanguageId>,
+  /// If set, the filter only applies to content in this community.
+  pub community_id: Option<CommunityId>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = slur_filter))]
+pub struct SlurFilterForm {
+  pub name: String,
+  pub pattern: String,
+  pub language_id: Option<LanguageId>,
+  pub community_id: Option<CommunityId>,
+}
@@ -1,4 +1,4 @@
-use crate::newtypes::LocalSiteId;
+use crate::newtypes::{LocalSiteId, TaglineId};
 #[cfg(feature = "full")]
 use crate::schema::tagline;
 use serde::{Deserialize, Serialize};
@@ -17,7 +17,7 @@ use ts_rs::TS;
 #[cfg_attr(feature = "full", ts(export))]
 /// A tagline, shown at the top of your site.
 pub struct Tagline {
-  pub id: i32,
+  pub id: TaglineId,
   pub local_site_id: LocalSiteId,
   pub content: String,
   pub published: chrono::NaiveDateTime,
@@ -32,3 +32,11 @@ pub struct TaglineForm {
   pub content: String,
   pub updated: Option<chrono::NaiveDateTime>,
 }
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = tagline))]
+pub struct TaglineUpdateForm {
+  pub content: String,
+  pub updated: Option<chrono::NaiveDateTime>,
+}
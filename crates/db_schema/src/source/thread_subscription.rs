@@ -0,0 +1,31 @@
+use crate::newtypes::{CommentId, PersonId, PostId};
+#[cfg(feature = "full")]
+use crate::schema::thread_subscription;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use ts_rs::TS;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Identifiable, TS))]
+#[cfg_attr(feature = "full", diesel(table_name = thread_subscription))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A user who wants to be notified about all new comments in a post, or below a comment.
+pub struct ThreadSubscription {
+  pub id: i32,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  /// If empty, the subscription is for the whole post.
+  pub comment_id: Option<CommentId>,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = thread_subscription))]
+pub struct ThreadSubscriptionForm {
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub comment_id: Option<CommentId>,
+}
@@ -107,6 +107,23 @@ pub trait Likeable {
     Self: Sized;
 }
 
+#[async_trait]
+pub trait Reactable {
+  type Form;
+  type IdType;
+  async fn react(pool: &mut DbPool<'_>, form: &Self::Form) -> Result<Self, Error>
+  where
+    Self: Sized;
+  async fn unreact(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    item_id: Self::IdType,
+    emoji: &str,
+  ) -> Result<usize, Error>
+  where
+    Self: Sized;
+}
+
 #[async_trait]
 pub trait Bannable {
   type Form;
@@ -9,14 +9,18 @@ use crate::{
 };
 use activitypub_federation::{fetch::object_id::ObjectId, traits::Object};
 use chrono::NaiveDateTime;
-use deadpool::Runtime;
+use deadpool::{
+  managed::{Hook, HookError, HookErrorCause, PoolError},
+  Runtime,
+};
 use diesel::{
   backend::Backend,
   deserialize::FromSql,
   pg::Pg,
   result::{ConnectionError, ConnectionResult, Error as DieselError, Error::QueryBuilderError},
   serialize::{Output, ToSql},
-  sql_types::Text,
+  sql_query,
+  sql_types::{BigInt, Text},
   PgConnection,
 };
 use diesel_async::{
@@ -25,12 +29,13 @@ use diesel_async::{
     deadpool::{Object as PooledConnection, Pool},
     AsyncDieselConnectionManager,
   },
+  RunQueryDsl,
 };
 use diesel_migrations::EmbeddedMigrations;
 use futures_util::{future::BoxFuture, Future, FutureExt};
 use lemmy_utils::{
   error::{LemmyError, LemmyErrorExt, LemmyErrorType},
-  settings::structs::Settings,
+  settings::structs::{DatabaseConfig, DatabasePoolConfig, Settings},
 };
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -42,15 +47,18 @@ use std::{
   env,
   env::VarError,
   ops::{Deref, DerefMut},
-  sync::Arc,
-  time::{Duration, SystemTime},
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+  },
+  time::{Duration, Instant, SystemTime},
 };
 use tracing::{error, info};
 use url::Url;
 
 const FETCH_LIMIT_DEFAULT: i64 = 10;
 pub const FETCH_LIMIT_MAX: i64 = 50;
-const POOL_TIMEOUT: Option<Duration> = Some(Duration::from_secs(5));
+const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub type ActualDbPool = Pool<AsyncPgConnection>;
 
@@ -63,23 +71,67 @@ pub enum DbPool<'a> {
 }
 
 pub enum DbConn<'a> {
-  Pool(PooledConnection<AsyncPgConnection>),
+  Pool(PooledConnection<AsyncPgConnection>, CheckoutTimer),
   Conn(&'a mut AsyncPgConnection),
 }
 
+/// Usage counters of all connection pools in this process, exposed as prometheus metrics.
+#[derive(Default)]
+pub struct DbPoolStats {
+  /// Number of connections taken from a pool
+  pub checkouts: AtomicU64,
+  /// Total time spent waiting for a connection from a pool
+  pub checkout_wait_micros: AtomicU64,
+  /// Number of times no connection could be obtained before the timeout
+  pub checkout_timeouts: AtomicU64,
+  /// Total time that connections were checked out before being returned to a pool. Besides the
+  /// statements executed with them, this includes any work done in between.
+  pub checked_out_micros: AtomicU64,
+}
+
+pub static DB_POOL_STATS: Lazy<DbPoolStats> = Lazy::new(DbPoolStats::default);
+
+fn add_elapsed_micros(counter: &AtomicU64, since: Instant) {
+  let micros = u64::try_from(since.elapsed().as_micros()).unwrap_or(u64::MAX);
+  counter.fetch_add(micros, Ordering::Relaxed);
+}
+
 pub async fn get_conn<'a, 'b: 'a>(pool: &'a mut DbPool<'b>) -> Result<DbConn<'a>, DieselError> {
   Ok(match pool {
-    DbPool::Pool(pool) => DbConn::Pool(pool.get().await.map_err(|e| QueryBuilderError(e.into()))?),
+    DbPool::Pool(pool) => {
+      let start = Instant::now();
+      let conn = pool.get().await;
+      add_elapsed_micros(&DB_POOL_STATS.checkout_wait_micros, start);
+      let conn = conn.map_err(|e| {
+        if let PoolError::Timeout(_) = e {
+          DB_POOL_STATS
+            .checkout_timeouts
+            .fetch_add(1, Ordering::Relaxed);
+        }
+        QueryBuilderError(e.into())
+      })?;
+      DB_POOL_STATS.checkouts.fetch_add(1, Ordering::Relaxed);
+      DbConn::Pool(conn, CheckoutTimer(Instant::now()))
+    }
     DbPool::Conn(conn) => DbConn::Conn(conn),
   })
 }
 
+/// Records how long a pooled connection was checked out once it is returned.
+pub struct CheckoutTimer(Instant);
+
+impl Drop for CheckoutTimer {
+  fn drop(&mut self) {
+    add_elapsed_micros(&DB_POOL_STATS.checked_out_micros, self.0);
+  }
+}
+
 impl<'a> Deref for DbConn<'a> {
   type Target = AsyncPgConnection;
 
   fn deref(&self) -> &Self::Target {
     match self {
-      DbConn::Pool(conn) => conn.deref(),
+      DbConn::Pool(conn, _) => conn.deref(),
       DbConn::Conn(conn) => conn.deref(),
     }
   }
@@ -88,7 +140,7 @@ impl<'a> Deref for DbConn<'a> {
 impl<'a> DerefMut for DbConn<'a> {
   fn deref_mut(&mut self) -> &mut Self::Target {
     match self {
-      DbConn::Pool(conn) => conn.deref_mut(),
+      DbConn::Pool(conn, _) => conn.deref_mut(),
       DbConn::Conn(conn) => conn.deref_mut(),
     }
   }
@@ -149,6 +201,16 @@ macro_rules! try_join_with_pool {
   }};
 }
 
+/// The disk space used by the database, in bytes
+pub async fn database_size(pool: &mut DbPool<'_>) -> Result<i64, DieselError> {
+  let conn = &mut get_conn(pool).await?;
+  diesel::select(diesel::dsl::sql::<BigInt>(
+    "pg_database_size(current_database())",
+  ))
+  .get_result::<i64>(conn)
+  .await
+}
+
 pub fn get_database_url_from_env() -> Result<String, VarError> {
   env::var("LEMMY_DATABASE_URL")
 }
@@ -242,30 +304,86 @@ async fn build_db_pool_settings_opt(
   settings: Option<&Settings>,
 ) -> Result<ActualDbPool, LemmyError> {
   let db_url = get_database_url(settings);
-  let pool_size = settings.map(|s| s.database.pool_size).unwrap_or(5);
+  let options = settings
+    .map(|s| PoolOptions::new(&s.database, None))
+    .unwrap_or_default();
+  let pool = build_pool_for_url(&db_url, &options)?;
+
+  // If there's no settings, that means its a unit test, and migrations need to be run
+  if settings.is_none() {
+    run_migrations(&db_url);
+  }
+
+  Ok(pool)
+}
+
+/// Tuning values for a single connection pool.
+struct PoolOptions {
+  pool_size: usize,
+  connection_timeout: Duration,
+  statement_timeout: Option<Duration>,
+}
+
+impl Default for PoolOptions {
+  fn default() -> Self {
+    PoolOptions {
+      pool_size: 5,
+      connection_timeout: DEFAULT_CONNECTION_TIMEOUT,
+      statement_timeout: None,
+    }
+  }
+}
+
+impl PoolOptions {
+  /// Takes values from the workload specific config if given, otherwise from the main config.
+  fn new(config: &DatabaseConfig, workload: Option<&DatabasePoolConfig>) -> Self {
+    let connection_timeout_seconds = workload
+      .and_then(|w| w.connection_timeout_seconds)
+      .unwrap_or(config.connection_timeout_seconds);
+    PoolOptions {
+      pool_size: workload
+        .and_then(|w| w.pool_size)
+        .unwrap_or(config.pool_size),
+      connection_timeout: Duration::from_secs(connection_timeout_seconds),
+      statement_timeout: workload
+        .and_then(|w| w.statement_timeout_seconds)
+        .or(config.statement_timeout_seconds)
+        .map(Duration::from_secs),
+    }
+  }
+}
+
+fn build_pool_for_url(db_url: &str, options: &PoolOptions) -> Result<ActualDbPool, LemmyError> {
   // We only support TLS with sslmode=require currently
   let tls_enabled = db_url.contains("sslmode=require");
   let manager = if tls_enabled {
     // diesel-async does not support any TLS connections out of the box, so we need to manually
     // provide a setup function which handles creating the connection
-    AsyncDieselConnectionManager::<AsyncPgConnection>::new_with_setup(&db_url, establish_connection)
+    AsyncDieselConnectionManager::<AsyncPgConnection>::new_with_setup(db_url, establish_connection)
   } else {
-    AsyncDieselConnectionManager::<AsyncPgConnection>::new(&db_url)
+    AsyncDieselConnectionManager::<AsyncPgConnection>::new(db_url)
   };
-  let pool = Pool::builder(manager)
-    .max_size(pool_size)
-    .wait_timeout(POOL_TIMEOUT)
-    .create_timeout(POOL_TIMEOUT)
-    .recycle_timeout(POOL_TIMEOUT)
-    .runtime(Runtime::Tokio1)
-    .build()?;
-
-  // If there's no settings, that means its a unit test, and migrations need to be run
-  if settings.is_none() {
-    run_migrations(&db_url);
+  let timeout = Some(options.connection_timeout);
+  let mut builder = Pool::builder(manager)
+    .max_size(options.pool_size)
+    .wait_timeout(timeout)
+    .create_timeout(timeout)
+    .recycle_timeout(timeout)
+    .runtime(Runtime::Tokio1);
+  if let Some(statement_timeout) = options.statement_timeout {
+    let query = format!("SET statement_timeout = {}", statement_timeout.as_millis());
+    builder = builder.post_create(Hook::async_fn(move |conn: &mut AsyncPgConnection, _| {
+      let query = query.clone();
+      Box::pin(async move {
+        sql_query(query)
+          .execute(conn)
+          .await
+          .map_err(|e| HookError::Abort(HookErrorCause::Message(e.to_string())))?;
+        Ok(())
+      })
+    }));
   }
-
-  Ok(pool)
+  Ok(builder.build()?)
 }
 
 fn establish_connection(config: &str) -> BoxFuture<ConnectionResult<AsyncPgConnection>> {
@@ -323,6 +441,43 @@ pub async fn build_db_pool(settings: &Settings) -> Result<ActualDbPool, LemmyErr
   build_db_pool_settings_opt(Some(settings)).await
 }
 
+/// Builds one pool per configured read replica. Migrations are never run against replicas, they
+/// receive schema changes through replication.
+pub fn build_db_replica_pools(settings: &Settings) -> Result<Vec<ActualDbPool>, LemmyError> {
+  settings
+    .database
+    .read_replicas
+    .iter()
+    .map(|db_url| build_pool_for_url(db_url, &PoolOptions::new(&settings.database, None)))
+    .collect()
+}
+
+/// Builds the dedicated pool for federation if one is configured. Otherwise federation uses the
+/// main pool.
+pub fn build_federation_db_pool(settings: &Settings) -> Result<Option<ActualDbPool>, LemmyError> {
+  settings
+    .database
+    .federation
+    .as_ref()
+    .map(|federation| {
+      let db_url = get_database_url(Some(settings));
+      build_pool_for_url(
+        &db_url,
+        &PoolOptions::new(&settings.database, Some(federation)),
+      )
+    })
+    .transpose()
+}
+
+/// Statement applied to each new connection of scheduled tasks, if a timeout is configured.
+pub fn scheduler_statement_timeout_query(settings: &Settings) -> Option<String> {
+  settings
+    .database
+    .scheduler_statement_timeout_seconds
+    .or(settings.database.statement_timeout_seconds)
+    .map(|s| format!("SET statement_timeout = {}", s * 1000))
+}
+
 pub async fn build_db_pool_for_tests() -> ActualDbPool {
   build_db_pool_settings_opt(None)
     .await
@@ -346,7 +501,7 @@ pub fn naive_now() -> NaiveDateTime {
 
 pub fn post_to_comment_sort_type(sort: SortType) -> CommentSortType {
   match sort {
-    SortType::Active | SortType::Hot => CommentSortType::Hot,
+    SortType::Active | SortType::Hot | SortType::Trending => CommentSortType::Hot,
     SortType::New | SortType::NewComments | SortType::MostComments => CommentSortType::New,
     SortType::Old => CommentSortType::Old,
     SortType::Controversial => CommentSortType::Controversial,
@@ -380,7 +535,7 @@ static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
 });
 
 pub mod functions {
-  use diesel::sql_types::{BigInt, Text, Timestamp};
+  use diesel::sql_types::{BigInt, Integer, Nullable, Text, Timestamp};
 
   sql_function! {
     fn hot_rank(score: BigInt, time: Timestamp) -> Integer;
@@ -390,7 +545,13 @@ pub mod functions {
     fn controversy_rank(upvotes: BigInt, downvotes: BigInt, score: BigInt) -> Double;
   }
 
+  sql_function! {
+    fn community_unread_posts(person_id: Nullable<Integer>, community_id: Integer) -> BigInt;
+  }
+
   sql_function!(fn lower(x: Text) -> Text);
+
+  sql_function!(fn random() -> Double);
 }
 
 pub const DELETED_REPLACEMENT_TEXT: &str = "*Permanently Deleted*";
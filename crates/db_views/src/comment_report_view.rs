@@ -13,7 +13,7 @@ use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   aggregates::structs::CommentAggregates,
   aliases,
-  newtypes::{CommentReportId, CommunityId, PersonId},
+  newtypes::{CommentReportId, CommunityId, PersonId, ReportReasonId},
   schema::{
     comment,
     comment_aggregates,
@@ -117,6 +117,10 @@ fn queries<'a>() -> Queries<
       query = query.filter(comment_report::resolved.eq(false));
     }
 
+    if let Some(reason_id) = options.reason_id {
+      query = query.filter(comment_report::reason_id.eq(reason_id));
+    }
+
     let (limit, offset) = limit_and_offset(options.page, options.limit)?;
 
     query = query
@@ -199,6 +203,48 @@ impl CommentReportView {
         .await
     }
   }
+
+  /// Returns the current unresolved comment report counts for the communities you mod, grouped by
+  /// their listed reason. Reports without a listed reason are left out.
+  pub async fn get_report_count_by_reason(
+    pool: &mut DbPool<'_>,
+    my_person_id: PersonId,
+    admin: bool,
+    community_id: Option<CommunityId>,
+  ) -> Result<Vec<(ReportReasonId, i64)>, Error> {
+    use diesel::dsl::count;
+
+    let conn = &mut get_conn(pool).await?;
+
+    let mut query = comment_report::table
+      .inner_join(comment::table)
+      .inner_join(post::table.on(comment::post_id.eq(post::id)))
+      .filter(comment_report::resolved.eq(false))
+      .filter(comment_report::reason_id.is_not_null())
+      .group_by(comment_report::reason_id)
+      .select((
+        comment_report::reason_id.assume_not_null(),
+        count(comment_report::id),
+      ))
+      .into_boxed();
+
+    if let Some(community_id) = community_id {
+      query = query.filter(post::community_id.eq(community_id))
+    }
+
+    // If its not an admin, get only the ones you mod
+    if !admin {
+      query = query.filter(
+        post::community_id.eq_any(
+          community_moderator::table
+            .filter(community_moderator::person_id.eq(my_person_id))
+            .select(community_moderator::community_id),
+        ),
+      );
+    }
+
+    query.load::<(ReportReasonId, i64)>(conn).await
+  }
 }
 
 #[derive(Default)]
@@ -207,6 +253,7 @@ pub struct CommentReportQuery {
   pub page: Option<i64>,
   pub limit: Option<i64>,
   pub unresolved_only: Option<bool>,
+  pub reason_id: Option<ReportReasonId>,
 }
 
 impl CommentReportQuery {
@@ -267,6 +314,7 @@ mod tests {
     },
     traits::{Crud, Joinable, Reportable},
     utils::build_db_pool_for_tests,
+    PostTypeRestriction,
   };
   use serial_test::serial;
 
@@ -346,6 +394,8 @@ mod tests {
       comment_id: inserted_comment.id,
       original_comment_text: "this was it at time of creation".into(),
       reason: "from sara".into(),
+      reason_id: None,
+      rule_id: None,
     };
 
     let inserted_sara_report = CommentReport::report(pool, &sara_report_form)
@@ -358,6 +408,8 @@ mod tests {
       comment_id: inserted_comment.id,
       original_comment_text: "this was it at time of creation".into(),
       reason: "from jessica".into(),
+      reason_id: None,
+      rule_id: None,
     };
 
     let inserted_jessica_report = CommentReport::report(pool, &jessica_report_form)
@@ -391,6 +443,19 @@ mod tests {
         banner: None,
         hidden: false,
         posting_restricted_to_mods: false,
+        enable_downvotes: true,
+        requires_follow_approval: false,
+        post_type_restriction: PostTypeRestriction::Any,
+        nsfw_only: false,
+        post_slow_mode_seconds: 0,
+        comment_slow_mode_seconds: 0,
+        allow_undetermined_language: true,
+        strike_ban_threshold: 0,
+        strike_ban_days: 7,
+        min_account_age_days: 0,
+        min_site_karma: 0,
+        min_community_karma: 0,
+        notify_mods_on_mention: false,
         published: inserted_community.published,
         private_key: inserted_community.private_key,
         public_key: inserted_community.public_key,
@@ -421,6 +486,7 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        shadowbanned_at: None,
         instance_id: inserted_instance.id,
         private_key: inserted_jessica.private_key,
         public_key: inserted_jessica.public_key,
@@ -445,6 +511,7 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        shadowbanned_at: None,
         instance_id: inserted_instance.id,
         private_key: inserted_timmy.private_key.clone(),
         public_key: inserted_timmy.public_key.clone(),
@@ -489,6 +556,7 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      shadowbanned_at: None,
       instance_id: inserted_instance.id,
       private_key: inserted_sara.private_key,
       public_key: inserted_sara.public_key,
@@ -558,6 +626,7 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      shadowbanned_at: None,
       instance_id: inserted_instance.id,
     });
 
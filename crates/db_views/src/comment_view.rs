@@ -1,7 +1,9 @@
-use crate::structs::{CommentView, LocalUserView};
+use crate::structs::{CommentView, LocalUserView, ReactionCount};
 use diesel::{
+  dsl::{exists, now, sql},
   pg::Pg,
   result::Error,
+  sql_types::Text,
   BoolExpressionMethods,
   ExpressionMethods,
   JoinOnDsl,
@@ -18,10 +20,12 @@ use lemmy_db_schema::{
     comment,
     comment_aggregates,
     comment_like,
+    comment_report,
     comment_saved,
     community,
     community_block,
     community_follower,
+    community_moderator,
     community_person_ban,
     local_user_language,
     person,
@@ -29,13 +33,14 @@ use lemmy_db_schema::{
     post,
   },
   source::{
-    comment::{Comment, CommentSaved},
-    community::{Community, CommunityFollower, CommunityPersonBan},
+    comment::{Comment, CommentReaction, CommentSaved},
+    community::{Community, CommunityFollower, CommunityModerator, CommunityPersonBan},
     person::Person,
     person_block::PersonBlock,
+    person_note::PersonNote,
     post::Post,
   },
-  traits::JoinView,
+  traits::{Crud, JoinView},
   utils::{fuzzy_search, limit_and_offset, DbConn, DbPool, ListFn, Queries, ReadFn},
   CommentSortType,
   ListingType,
@@ -56,7 +61,7 @@ type CommentViewTuple = (
 
 fn queries<'a>() -> Queries<
   impl ReadFn<'a, CommentView, (CommentId, Option<PersonId>)>,
-  impl ListFn<'a, CommentView, CommentQuery<'a>>,
+  impl ListFn<'a, CommentView, (CommentQuery<'a>, bool)>,
 > {
   let all_joins = |query: comment::BoxedQuery<'a, Pg>, my_person_id: Option<PersonId>| {
     // The left join below will return None in this case
@@ -91,7 +96,12 @@ fn queries<'a>() -> Queries<
         person_block::table.on(
           comment::creator_id
             .eq(person_block::target_id)
-            .and(person_block::person_id.eq(person_id_join)),
+            .and(person_block::person_id.eq(person_id_join))
+            .and(
+              person_block::expires
+                .is_null()
+                .or(person_block::expires.gt(now)),
+            ),
         ),
       )
       .left_join(
@@ -124,7 +134,7 @@ fn queries<'a>() -> Queries<
       .await
   };
 
-  let list = move |mut conn: DbConn<'a>, options: CommentQuery<'a>| async move {
+  let list = move |mut conn: DbConn<'a>, (options, shuffle): (CommentQuery<'a>, bool)| async move {
     let person_id = options.local_user.map(|l| l.person.id);
     let local_user_id = options.local_user.map(|l| l.local_user.id);
 
@@ -195,6 +205,23 @@ fn queries<'a>() -> Queries<
       query = query.filter(comment_saved::comment_id.is_not_null());
     }
 
+    let reported_only = options.reported_only.unwrap_or(false);
+    if reported_only {
+      // Only comments with open reports, in communities the user moderates. Admins see all of them.
+      query = query.filter(exists(
+        comment_report::table
+          .filter(comment_report::comment_id.eq(comment::id))
+          .filter(comment_report::resolved.eq(false)),
+      ));
+      if !options.local_user.map(|l| l.person.admin).unwrap_or(false) {
+        query = query.filter(exists(
+          community_moderator::table
+            .filter(community_moderator::community_id.eq(post::community_id))
+            .filter(community_moderator::person_id.eq(person_id_join)),
+        ));
+      }
+    }
+
     let is_creator = options.creator_id == options.local_user.map(|l| l.person.id);
     // only show deleted comments to creator
     if !is_creator {
@@ -207,6 +234,21 @@ fn queries<'a>() -> Queries<
       query = query.filter(comment::removed.eq(false));
     }
 
+    // comments of shadowbanned users are only visible to themselves, mods and admins
+    if !is_admin {
+      query = query.filter(
+        person::shadowbanned_at
+          .is_null()
+          .or(comment::published.nullable().lt(person::shadowbanned_at))
+          .or(comment::creator_id.eq(person_id_join))
+          .or(exists(
+            community_moderator::table
+              .filter(community_moderator::community_id.eq(post::community_id))
+              .filter(community_moderator::person_id.eq(person_id_join)),
+          )),
+      );
+    }
+
     if !options
       .local_user
       .map(|l| l.local_user.show_bot_accounts)
@@ -223,11 +265,13 @@ fn queries<'a>() -> Queries<
       if options.post_id.is_none() {
         query = query.filter(community_block::person_id.is_null());
       }
-      query = query.filter(person_block::person_id.is_null());
+      if !reported_only {
+        query = query.filter(person_block::person_id.is_null());
+      }
     }
 
     // A Max depth given means its a tree fetch
-    let (limit, offset) = if let Some(max_depth) = options.max_depth {
+    if let Some(max_depth) = options.max_depth {
       let depth_limit = if let Some(parent_path) = options.parent_path.as_ref() {
         parent_path.0.split('.').count() as i32 + max_depth
         // Add one because of root "0"
@@ -236,7 +280,16 @@ fn queries<'a>() -> Queries<
       };
 
       query = query.filter(nlevel(comment::path).le(depth_limit));
+    }
 
+    let (limit, offset) = if let Some(page_cursor) = options.page_cursor.as_ref() {
+      // Cursor pagination walks the tree depth-first, continuing after the comment with the
+      // given path. This keeps deep threads cheap to page through, and respects the limit.
+      query = query
+        .filter(comment::path.gt(page_cursor))
+        .order_by(comment::path.asc());
+      limit_and_offset(None, options.limit)?
+    } else if options.max_depth.is_some() {
       // only order if filtering by a post id, or parent_path. DOS potential otherwise and max_depth + !post_id isn't used anyways (afaik)
       if options.post_id.is_some() || options.parent_path.is_some() {
         // Always order by the parent path first
@@ -259,17 +312,35 @@ fn queries<'a>() -> Queries<
       limit_and_offset(options.page, options.limit)?
     };
 
-    query = match options.sort.unwrap_or(CommentSortType::Hot) {
-      CommentSortType::Hot => query
-        .then_order_by(comment_aggregates::hot_rank.desc())
-        .then_order_by(comment_aggregates::score.desc()),
-      CommentSortType::Controversial => {
-        query.then_order_by(comment_aggregates::controversy_rank.desc())
-      }
-      CommentSortType::New => query.then_order_by(comment::published.desc()),
-      CommentSortType::Old => query.then_order_by(comment::published.asc()),
-      CommentSortType::Top => query.order_by(comment_aggregates::score.desc()),
-    };
+    // Outside of the post itself, the position of a comment in a listing by votes would reveal
+    // the hidden score of a contest
+    let sort = options.sort.unwrap_or(CommentSortType::Hot);
+    let is_score_sort = matches!(sort, CommentSortType::Top | CommentSortType::Controversial);
+    if !is_admin && options.post_id.is_none() && is_score_sort {
+      query = query.filter(
+        post::contest_mode_until
+          .is_null()
+          .or(post::contest_mode_until.le(now)),
+      );
+    }
+
+    // Paths are unique, so a cursor fetch doesn't need any further ordering
+    if options.page_cursor.is_none() && shuffle {
+      // A stable pseudo-random order, so that pages don't overlap
+      query = query.then_order_by(sql::<Text>("md5(comment.id::text)"));
+    } else if options.page_cursor.is_none() {
+      query = match sort {
+        CommentSortType::Hot => query
+          .then_order_by(comment_aggregates::hot_rank.desc())
+          .then_order_by(comment_aggregates::score.desc()),
+        CommentSortType::Controversial => {
+          query.then_order_by(comment_aggregates::controversy_rank.desc())
+        }
+        CommentSortType::New => query.then_order_by(comment::published.desc()),
+        CommentSortType::Old => query.then_order_by(comment::published.asc()),
+        CommentSortType::Top => query.order_by(comment_aggregates::score.desc()),
+      };
+    }
 
     // Note: deleted and removed comments are done on the front side
     query
@@ -282,6 +353,16 @@ fn queries<'a>() -> Queries<
   Queries::new(read, list)
 }
 
+async fn moderated_communities(
+  pool: &mut DbPool<'_>,
+  my_person_id: Option<PersonId>,
+) -> Result<Vec<CommunityId>, Error> {
+  match my_person_id {
+    Some(person_id) => CommunityModerator::get_person_moderated_communities(pool, person_id).await,
+    None => Ok(vec![]),
+  }
+}
+
 impl CommentView {
   pub async fn read(
     pool: &mut DbPool<'_>,
@@ -294,8 +375,79 @@ impl CommentView {
     if my_person_id.is_some() && res.my_vote.is_none() {
       res.my_vote = Some(0);
     }
+    Self::add_reactions(pool, std::slice::from_mut(&mut res), my_person_id).await?;
+    Self::add_creator_notes(pool, std::slice::from_mut(&mut res), my_person_id).await?;
+    Self::hide_contest_scores(pool, std::slice::from_mut(&mut res), my_person_id).await?;
     Ok(res)
   }
+
+  /// Hides the vote counts of comments on posts in contest mode, except in communities the person
+  /// moderates.
+  async fn hide_contest_scores(
+    pool: &mut DbPool<'_>,
+    comments: &mut [CommentView],
+    my_person_id: Option<PersonId>,
+  ) -> Result<(), Error> {
+    if !comments.iter().any(|c| c.post.in_contest_mode()) {
+      return Ok(());
+    }
+    let moderated = moderated_communities(pool, my_person_id).await?;
+    for comment_view in comments
+      .iter_mut()
+      .filter(|c| c.post.in_contest_mode() && !moderated.contains(&c.community.id))
+    {
+      comment_view.counts.score = 0;
+      comment_view.counts.upvotes = 0;
+      comment_view.counts.downvotes = 0;
+    }
+    Ok(())
+  }
+
+  /// Fills in the emoji reaction counts. These are loaded separately, as joining them in the
+  /// main query would multiply its rows.
+  async fn add_reactions(
+    pool: &mut DbPool<'_>,
+    comments: &mut [CommentView],
+    my_person_id: Option<PersonId>,
+  ) -> Result<(), Error> {
+    let comment_ids: Vec<CommentId> = comments.iter().map(|c| c.comment.id).collect();
+    let counts = CommentReaction::counts(pool, &comment_ids).await?;
+    if counts.is_empty() {
+      return Ok(());
+    }
+    let my_reactions = match my_person_id {
+      Some(person_id) => CommentReaction::for_person(pool, person_id, &comment_ids).await?,
+      None => vec![],
+    };
+
+    let mut reactions = ReactionCount::group(counts, my_reactions);
+    for comment_view in comments {
+      comment_view.reactions = reactions
+        .remove(&comment_view.comment.id)
+        .unwrap_or_default();
+    }
+    Ok(())
+  }
+
+  /// Fills in the private notes of the person about the comment creators.
+  async fn add_creator_notes(
+    pool: &mut DbPool<'_>,
+    comments: &mut [CommentView],
+    my_person_id: Option<PersonId>,
+  ) -> Result<(), Error> {
+    let Some(my_person_id) = my_person_id else {
+      return Ok(());
+    };
+    let creator_ids: Vec<PersonId> = comments.iter().map(|c| c.creator.id).collect();
+    let notes = PersonNote::for_targets(pool, my_person_id, &creator_ids).await?;
+    for comment_view in comments {
+      comment_view.creator_note = notes
+        .iter()
+        .find(|n| n.target_id == comment_view.creator.id)
+        .cloned();
+    }
+    Ok(())
+  }
 }
 
 #[derive(Default)]
@@ -305,10 +457,14 @@ pub struct CommentQuery<'a> {
   pub community_id: Option<CommunityId>,
   pub post_id: Option<PostId>,
   pub parent_path: Option<Ltree>,
+  /// Continue a depth-first walk of the comment tree after this path.
+  pub page_cursor: Option<Ltree>,
   pub creator_id: Option<PersonId>,
   pub local_user: Option<&'a LocalUserView>,
   pub search_term: Option<String>,
   pub saved_only: Option<bool>,
+  /// Only comments with open reports, in communities the user moderates.
+  pub reported_only: Option<bool>,
   pub is_profile_view: bool,
   pub page: Option<i64>,
   pub limit: Option<i64>,
@@ -317,7 +473,28 @@ pub struct CommentQuery<'a> {
 
 impl<'a> CommentQuery<'a> {
   pub async fn list(self, pool: &mut DbPool<'_>) -> Result<Vec<CommentView>, Error> {
-    queries().list(pool, self).await
+    let my_person_id = self.local_user.map(|l| l.person.id);
+    let is_admin = self.local_user.map(|l| l.person.admin).unwrap_or(false);
+
+    // Comments of a post in contest mode are shuffled, so that the order doesn't reveal scores
+    let shuffle = match self.post_id {
+      Some(post_id) if !is_admin => {
+        let post = Post::read(pool, post_id).await?;
+        post.in_contest_mode()
+          && !moderated_communities(pool, my_person_id)
+            .await?
+            .contains(&post.community_id)
+      }
+      _ => false,
+    };
+
+    let mut comments = queries().list(pool, (self, shuffle)).await?;
+    CommentView::add_reactions(pool, &mut comments, my_person_id).await?;
+    CommentView::add_creator_notes(pool, &mut comments, my_person_id).await?;
+    if !is_admin {
+      CommentView::hide_contest_scores(pool, &mut comments, my_person_id).await?;
+    }
+    Ok(comments)
   }
 }
 
@@ -335,6 +512,8 @@ impl JoinView for CommentView {
       saved: a.7.is_some(),
       creator_blocked: a.8.is_some(),
       my_vote: a.9,
+      reactions: vec![],
+      creator_note: None,
     }
   }
 }
@@ -358,6 +537,7 @@ mod tests {
     },
     structs::LocalUserView,
   };
+  use diesel_ltree::Ltree;
   use lemmy_db_schema::{
     aggregates::structs::CommentAggregates,
     impls::actor_language::UNDETERMINED_ID,
@@ -375,6 +555,7 @@ mod tests {
     },
     traits::{Blockable, Crud, Likeable},
     utils::build_db_pool_for_tests,
+    PostTypeRestriction,
     SubscribedType,
   };
   use serial_test::serial;
@@ -513,6 +694,7 @@ mod tests {
     let timmy_blocks_sara_form = PersonBlockForm {
       person_id: inserted_person.id,
       target_id: inserted_person_2.id,
+      expires: None,
     };
 
     let inserted_block = PersonBlock::block(pool, &timmy_blocks_sara_form)
@@ -524,6 +706,7 @@ mod tests {
       person_id: inserted_person.id,
       target_id: inserted_person_2.id,
       published: inserted_block.published,
+      expires: None,
     };
     assert_eq!(expected_block, inserted_block);
 
@@ -688,6 +871,76 @@ mod tests {
     cleanup(data, pool).await;
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_comment_tree_cursor() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await;
+
+    // Walk the whole post two comments at a time, starting from the root
+    let mut page_cursor = Some(Ltree("0".into()));
+    let mut contents = vec![];
+    while let Some(cursor) = page_cursor {
+      let page = CommentQuery {
+        post_id: (Some(data.inserted_post.id)),
+        page_cursor: (Some(cursor)),
+        limit: (Some(2)),
+        ..Default::default()
+      }
+      .list(pool)
+      .await
+      .unwrap();
+      assert!(page.len() <= 2);
+      page_cursor = page.last().map(|c| c.comment.path.clone());
+      contents.extend(page.into_iter().map(|c| c.comment.content));
+    }
+
+    // Depth-first, with each comment directly followed by its replies
+    assert_eq!(
+      vec![
+        "Comment 0",
+        "Comment 1, A test blocked comment",
+        "Comment 3",
+        "Comment 4",
+        "Comment 5",
+        "Comment 2"
+      ],
+      contents
+    );
+
+    // Only the direct children of comment 1, continuing after the first one
+    let child_path = data.inserted_comment_1.path.clone();
+    let first_child = CommentQuery {
+      post_id: (Some(data.inserted_post.id)),
+      parent_path: (Some(child_path.clone())),
+      page_cursor: (Some(child_path.clone())),
+      max_depth: (Some(1)),
+      limit: (Some(1)),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+    let next_children = CommentQuery {
+      post_id: (Some(data.inserted_post.id)),
+      parent_path: (Some(child_path)),
+      page_cursor: (Some(first_child[0].comment.path.clone())),
+      max_depth: (Some(1)),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+
+    assert_eq!(1, first_child.len());
+    assert_eq!("Comment 3", first_child[0].comment.content);
+    assert_eq!(1, next_children.len());
+    assert_eq!("Comment 4", next_children[0].comment.content);
+
+    cleanup(data, pool).await;
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_languages() {
@@ -787,6 +1040,8 @@ mod tests {
     CommentView {
       creator_banned_from_community: false,
       my_vote: None,
+      reactions: vec![],
+      creator_note: None,
       subscribed: SubscribedType::NotSubscribed,
       saved: false,
       creator_blocked: false,
@@ -824,6 +1079,7 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        shadowbanned_at: None,
         instance_id: data.inserted_instance.id,
         private_key: data.local_user_view.person.private_key.clone(),
         public_key: data.local_user_view.person.public_key.clone(),
@@ -851,6 +1107,9 @@ mod tests {
         language_id: Default::default(),
         featured_community: false,
         featured_local: false,
+        contest_mode_until: None,
+        audio_mime_type: None,
+        audio_duration: None,
       },
       community: Community {
         id: data.inserted_community.id,
@@ -867,6 +1126,19 @@ mod tests {
         banner: None,
         hidden: false,
         posting_restricted_to_mods: false,
+        enable_downvotes: true,
+        requires_follow_approval: false,
+        post_type_restriction: PostTypeRestriction::Any,
+        nsfw_only: false,
+        post_slow_mode_seconds: 0,
+        comment_slow_mode_seconds: 0,
+        allow_undetermined_language: true,
+        strike_ban_threshold: 0,
+        strike_ban_days: 7,
+        min_account_age_days: 0,
+        min_site_karma: 0,
+        min_community_karma: 0,
+        notify_mods_on_mention: false,
         published: data.inserted_community.published,
         instance_id: data.inserted_instance.id,
         private_key: data.inserted_community.private_key.clone(),
@@ -0,0 +1,203 @@
+use crate::structs::CommunityApplicationView;
+use diesel::{result::Error, ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  aliases,
+  impls::community_application::COLUMNS,
+  newtypes::{CommunityApplicationId, PersonId},
+  schema::{community_application, person},
+  source::{community_application::CommunityApplication, person::Person},
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+
+type CommunityApplicationViewTuple = (CommunityApplication, Person, Option<Person>);
+
+impl CommunityApplicationView {
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    application_id: CommunityApplicationId,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let res = community_application::table
+      .find(application_id)
+      .inner_join(person::table.on(community_application::creator_id.eq(person::id)))
+      .left_join(
+        aliases::person1
+          .on(community_application::admin_id.eq(aliases::person1.field(person::id).nullable())),
+      )
+      .select((
+        COLUMNS,
+        person::all_columns,
+        aliases::person1.fields(person::all_columns).nullable(),
+      ))
+      .first::<CommunityApplicationViewTuple>(conn)
+      .await?;
+    Ok(Self::from_tuple(res))
+  }
+
+  fn from_tuple((community_application, creator, admin): CommunityApplicationViewTuple) -> Self {
+    CommunityApplicationView {
+      community_application,
+      creator,
+      admin,
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct CommunityApplicationQuery {
+  /// Only shows the applications which weren't approved or denied yet.
+  pub unread_only: Option<bool>,
+  pub creator_id: Option<PersonId>,
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+}
+
+impl CommunityApplicationQuery {
+  pub async fn list(self, pool: &mut DbPool<'_>) -> Result<Vec<CommunityApplicationView>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(self.page, self.limit)?;
+
+    let mut query = community_application::table
+      .inner_join(person::table.on(community_application::creator_id.eq(person::id)))
+      .left_join(
+        aliases::person1
+          .on(community_application::admin_id.eq(aliases::person1.field(person::id).nullable())),
+      )
+      .select((
+        COLUMNS,
+        person::all_columns,
+        aliases::person1.fields(person::all_columns).nullable(),
+      ))
+      .into_boxed();
+    if self.unread_only.unwrap_or(false) {
+      query = query.filter(community_application::admin_id.is_null());
+    }
+    if let Some(creator_id) = self.creator_id {
+      query = query.filter(community_application::creator_id.eq(creator_id));
+    }
+
+    let res = query
+      .order_by(community_application::published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<CommunityApplicationViewTuple>(conn)
+      .await?;
+    Ok(
+      res
+        .into_iter()
+        .map(CommunityApplicationView::from_tuple)
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    community_application_view::CommunityApplicationQuery,
+    structs::CommunityApplicationView,
+  };
+  use lemmy_db_schema::{
+    source::{
+      community_application::{
+        CommunityApplication,
+        CommunityApplicationInsertForm,
+        CommunityApplicationUpdateForm,
+      },
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serde_json::json;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_application_view() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("applicant_cav".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let creator = Person::create(pool, &person_form).await.unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("admin_cav".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let admin = Person::create(pool, &person_form).await.unwrap();
+
+    let form = CommunityApplicationInsertForm {
+      creator_id: creator.id,
+      name: "applied_cav".to_string(),
+      title: "nada".to_string(),
+      description: Some("A community to be approved".to_string()),
+      nsfw: false,
+    };
+    let application = CommunityApplication::create(pool, &form, json!({}))
+      .await
+      .unwrap();
+
+    let read = CommunityApplicationView::read(pool, application.id)
+      .await
+      .unwrap();
+    let expected = CommunityApplicationView {
+      community_application: application.clone(),
+      creator: creator.clone(),
+      admin: None,
+    };
+    assert_eq!(expected, read);
+
+    let unread = CommunityApplicationQuery {
+      unread_only: Some(true),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+    assert_eq!(vec![expected], unread);
+
+    let form = CommunityApplicationUpdateForm {
+      admin_id: Some(admin.id),
+      deny_reason: Some("no".to_string()),
+      community_id: None,
+    };
+    CommunityApplication::update(pool, application.id, &form)
+      .await
+      .unwrap();
+
+    let unread = CommunityApplicationQuery {
+      unread_only: Some(true),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+    assert!(unread.is_empty());
+    let own = CommunityApplicationQuery {
+      creator_id: Some(creator.id),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+    assert_eq!(1, own.len());
+    assert_eq!(Some(admin.clone()), own[0].admin);
+
+    Person::delete(pool, creator.id).await.unwrap();
+    Person::delete(pool, admin.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -0,0 +1,182 @@
+use crate::structs::{InviteUseView, InviteView};
+use diesel::{result::Error, ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  aliases,
+  newtypes::PersonId,
+  schema::{invite, invite_use, person},
+  source::{
+    invite::{Invite, InviteUse},
+    person::Person,
+  },
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+
+impl InviteView {
+  /// Lists the newest invites, optionally only those created by the given person.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    creator_id: Option<PersonId>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+
+    let mut query = invite::table
+      .inner_join(person::table)
+      .select((invite::all_columns, person::all_columns))
+      .into_boxed();
+    if let Some(creator_id) = creator_id {
+      query = query.filter(invite::creator_id.eq(creator_id));
+    }
+    let res = query
+      .order_by(invite::published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<(Invite, Person)>(conn)
+      .await?;
+
+    Ok(
+      res
+        .into_iter()
+        .map(|(invite, creator)| InviteView { invite, creator })
+        .collect(),
+    )
+  }
+}
+
+impl InviteUseView {
+  /// Lists the newest registrations with an invite, optionally only those invited by the given
+  /// person.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    inviter_id: Option<PersonId>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+
+    let mut query = invite_use::table
+      .inner_join(invite::table)
+      .inner_join(person::table.on(invite::creator_id.eq(person::id)))
+      .inner_join(aliases::person1.on(invite_use::person_id.eq(aliases::person1.field(person::id))))
+      .select((
+        invite_use::all_columns,
+        invite::all_columns,
+        person::all_columns,
+        aliases::person1.fields(person::all_columns),
+      ))
+      .into_boxed();
+    if let Some(inviter_id) = inviter_id {
+      query = query.filter(invite::creator_id.eq(inviter_id));
+    }
+    let res = query
+      .order_by(invite_use::published.desc())
+      .limit(limit)
+      .offset(offset)
+      .load::<(InviteUse, Invite, Person, Person)>(conn)
+      .await?;
+
+    Ok(
+      res
+        .into_iter()
+        .map(|(invite_use, invite, inviter, invitee)| InviteUseView {
+          invite_use,
+          invite,
+          inviter,
+          invitee,
+        })
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::structs::{InviteUseView, InviteView};
+  use lemmy_db_schema::{
+    source::{
+      instance::Instance,
+      invite::{Invite, InviteInsertForm, InviteUse, InviteUseForm},
+      person::{Person, PersonInsertForm},
+    },
+    traits::Crud,
+    utils::build_db_pool_for_tests,
+  };
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_invite_views() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("inviter_iv".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inviter = Person::create(pool, &person_form).await.unwrap();
+    let person_form = PersonInsertForm::builder()
+      .name("invitee_iv".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let invitee = Person::create(pool, &person_form).await.unwrap();
+
+    let form = InviteInsertForm {
+      code: "invite_code_iv".to_string(),
+      creator_id: inviter.id,
+      max_uses: 1,
+      expires: None,
+    };
+    let invite = Invite::create(pool, &form).await.unwrap();
+    let form = InviteUseForm {
+      invite_id: invite.id,
+      person_id: invitee.id,
+    };
+    let invite_use = InviteUse::create(pool, &form).await.unwrap();
+
+    let invites = InviteView::list(pool, Some(inviter.id), None, None)
+      .await
+      .unwrap();
+    assert_eq!(
+      vec![InviteView {
+        invite: invite.clone(),
+        creator: inviter.clone(),
+      }],
+      invites
+    );
+    let invites = InviteView::list(pool, Some(invitee.id), None, None)
+      .await
+      .unwrap();
+    assert!(invites.is_empty());
+
+    let uses = InviteUseView::list(pool, None, None, None).await.unwrap();
+    assert_eq!(
+      vec![InviteUseView {
+        invite_use,
+        invite,
+        inviter: inviter.clone(),
+        invitee: invitee.clone(),
+      }],
+      uses
+    );
+    let uses = InviteUseView::list(pool, Some(invitee.id), None, None)
+      .await
+      .unwrap();
+    assert!(uses.is_empty());
+
+    Person::delete(pool, inviter.id).await.unwrap();
+    Person::delete(pool, invitee.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -6,10 +6,16 @@ pub mod comment_report_view;
 #[cfg(feature = "full")]
 pub mod comment_view;
 #[cfg(feature = "full")]
+pub mod community_application_view;
+#[cfg(feature = "full")]
 pub mod custom_emoji_view;
 #[cfg(feature = "full")]
+pub mod invite_view;
+#[cfg(feature = "full")]
 pub mod local_user_view;
 #[cfg(feature = "full")]
+pub mod mod_queue_digest;
+#[cfg(feature = "full")]
 pub mod post_report_view;
 #[cfg(feature = "full")]
 pub mod post_view;
@@ -18,7 +24,11 @@ pub mod private_message_report_view;
 #[cfg(feature = "full")]
 pub mod private_message_view;
 #[cfg(feature = "full")]
+pub mod reaction_count;
+#[cfg(feature = "full")]
 pub mod registration_application_view;
 #[cfg(feature = "full")]
 pub mod site_view;
 pub mod structs;
+#[cfg(feature = "full")]
+pub mod vote_view;
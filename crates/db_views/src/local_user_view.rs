@@ -1,10 +1,17 @@
 use crate::structs::LocalUserView;
-use diesel::{result::Error, BoolExpressionMethods, ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel::{
+  dsl::exists,
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  JoinOnDsl,
+  QueryDsl,
+};
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   aggregates::structs::PersonAggregates,
   newtypes::{LocalUserId, PersonId},
-  schema::{local_user, person, person_aggregates},
+  schema::{community_moderator, local_user, person, person_aggregates},
   source::{local_user::LocalUser, person::Person},
   traits::JoinView,
   utils::{functions::lower, DbConn, DbPool, ListFn, Queries, ReadFn},
@@ -22,6 +29,7 @@ enum ReadBy<'a> {
 
 enum ListMode {
   AdminsWithEmails,
+  ModQueueDigestRecipients,
 }
 
 fn queries<'a>(
@@ -69,6 +77,20 @@ fn queries<'a>(
           .load::<LocalUserViewTuple>(&mut conn)
           .await
       }
+      ListMode::ModQueueDigestRecipients => {
+        let moderates = community_moderator::table
+          .filter(community_moderator::person_id.eq(person::id))
+          .select(community_moderator::id);
+        local_user::table
+          .filter(local_user::email.is_not_null())
+          .filter(local_user::email_mod_queue_digest.eq(true))
+          .inner_join(person::table)
+          .filter(person::admin.eq(true).or(exists(moderates)))
+          .inner_join(person_aggregates::table.on(person::id.eq(person_aggregates::person_id)))
+          .select(selection)
+          .load::<LocalUserViewTuple>(&mut conn)
+          .await
+      }
     }
   };
 
@@ -104,6 +126,13 @@ impl LocalUserView {
   pub async fn list_admins_with_emails(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
     queries().list(pool, ListMode::AdminsWithEmails).await
   }
+
+  /// Admins and moderators with an email address, who want to receive the mod queue digest
+  pub async fn list_mod_queue_digest_recipients(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    queries()
+      .list(pool, ListMode::ModQueueDigestRecipients)
+      .await
+  }
 }
 
 impl JoinView for LocalUserView {
@@ -0,0 +1,221 @@
+use crate::structs::ModQueueDigest;
+use chrono::NaiveDateTime;
+use diesel::{dsl::count_star, result::Error, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  newtypes::PersonId,
+  schema::{
+    comment,
+    comment_report,
+    community_moderator,
+    mod_remove_comment,
+    mod_remove_post,
+    post,
+    post_report,
+    registration_application,
+  },
+  utils::{get_conn, DbPool},
+};
+
+impl ModQueueDigest {
+  /// Counts the items which were added to the mod queue of a person since the given time. Admins
+  /// see the whole queue, mods only the items in their communities.
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    admin: bool,
+    since: NaiveDateTime,
+    automod_reason: &str,
+  ) -> Result<Self, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let moderated = community_moderator::table
+      .filter(community_moderator::person_id.eq(person_id))
+      .select(community_moderator::community_id);
+
+    let mut post_reports = post_report::table
+      .inner_join(post::table)
+      .filter(post_report::resolved.eq(false))
+      .filter(post_report::published.gt(since))
+      .into_boxed();
+    let mut comment_reports = comment_report::table
+      .inner_join(comment::table.inner_join(post::table))
+      .filter(comment_report::resolved.eq(false))
+      .filter(comment_report::published.gt(since))
+      .into_boxed();
+    let mut removed_posts = mod_remove_post::table
+      .inner_join(post::table)
+      .filter(mod_remove_post::reason.eq(automod_reason))
+      .filter(mod_remove_post::removed.eq(true))
+      .filter(mod_remove_post::when_.gt(since))
+      .filter(post::removed.eq(true))
+      .into_boxed();
+    let mut removed_comments = mod_remove_comment::table
+      .inner_join(comment::table.inner_join(post::table))
+      .filter(mod_remove_comment::reason.eq(automod_reason))
+      .filter(mod_remove_comment::removed.eq(true))
+      .filter(mod_remove_comment::when_.gt(since))
+      .filter(comment::removed.eq(true))
+      .into_boxed();
+    if !admin {
+      post_reports = post_reports.filter(post::community_id.eq_any(moderated));
+      comment_reports = comment_reports.filter(post::community_id.eq_any(moderated));
+      removed_posts = removed_posts.filter(post::community_id.eq_any(moderated));
+      removed_comments = removed_comments.filter(post::community_id.eq_any(moderated));
+    }
+
+    let reports = post_reports.select(count_star()).first::<i64>(conn).await?
+      + comment_reports
+        .select(count_star())
+        .first::<i64>(conn)
+        .await?;
+    let automod_holds = removed_posts
+      .select(count_star())
+      .first::<i64>(conn)
+      .await?
+      + removed_comments
+        .select(count_star())
+        .first::<i64>(conn)
+        .await?;
+    let registration_applications = if admin {
+      registration_application::table
+        .filter(registration_application::admin_id.is_null())
+        .filter(registration_application::published.gt(since))
+        .select(count_star())
+        .first::<i64>(conn)
+        .await?
+    } else {
+      0
+    };
+
+    Ok(ModQueueDigest {
+      reports,
+      registration_applications,
+      automod_holds,
+    })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.reports == 0 && self.registration_applications == 0 && self.automod_holds == 0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::structs::ModQueueDigest;
+  use chrono::Duration;
+  use lemmy_db_schema::{
+    source::{
+      community::{Community, CommunityInsertForm, CommunityModerator, CommunityModeratorForm},
+      instance::Instance,
+      moderator::{ModRemovePost, ModRemovePostForm},
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm, PostUpdateForm},
+      post_report::{PostReport, PostReportForm},
+    },
+    traits::{Crud, Joinable, Reportable},
+    utils::{build_db_pool_for_tests, naive_now},
+  };
+  use serial_test::serial;
+
+  const AUTOMOD_REASON: &str = "Matched a word filter";
+
+  #[tokio::test]
+  #[serial]
+  async fn test_mod_queue_digest() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let since = naive_now() - Duration::minutes(1);
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let timmy_form = PersonInsertForm::builder()
+      .name("timmy_mqd".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_timmy = Person::create(pool, &timmy_form).await.unwrap();
+    let sara_form = PersonInsertForm::builder()
+      .name("sara_mqd".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_sara = Person::create(pool, &sara_form).await.unwrap();
+    let community_form = CommunityInsertForm::builder()
+      .name("test_community_mqd".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &community_form).await.unwrap();
+    let moderator_form = CommunityModeratorForm {
+      community_id: inserted_community.id,
+      person_id: inserted_timmy.id,
+    };
+    CommunityModerator::join(pool, &moderator_form)
+      .await
+      .unwrap();
+
+    let post_form = PostInsertForm::builder()
+      .name("A test post mqd".into())
+      .creator_id(inserted_sara.id)
+      .community_id(inserted_community.id)
+      .build();
+    let reported_post = Post::create(pool, &post_form).await.unwrap();
+    let held_post = Post::create(pool, &post_form).await.unwrap();
+
+    let report_form = PostReportForm {
+      creator_id: inserted_sara.id,
+      post_id: reported_post.id,
+      original_post_name: reported_post.name.clone(),
+      original_post_url: None,
+      original_post_body: None,
+      reason: "from sara".into(),
+      reason_id: None,
+      rule_id: None,
+    };
+    PostReport::report(pool, &report_form).await.unwrap();
+
+    let removed_form = PostUpdateForm::builder().removed(Some(true)).build();
+    Post::update(pool, held_post.id, &removed_form)
+      .await
+      .unwrap();
+    let mod_remove_form = ModRemovePostForm {
+      mod_person_id: inserted_timmy.id,
+      post_id: held_post.id,
+      reason: Some(AUTOMOD_REASON.to_string()),
+      removed: Some(true),
+    };
+    ModRemovePost::create(pool, &mod_remove_form).await.unwrap();
+
+    // Timmy mods the community and sees both items
+    let digest = ModQueueDigest::read(pool, inserted_timmy.id, false, since, AUTOMOD_REASON)
+      .await
+      .unwrap();
+    assert_eq!(1, digest.reports);
+    assert_eq!(1, digest.automod_holds);
+    assert_eq!(0, digest.registration_applications);
+
+    // Sara doesn't mod it
+    let digest = ModQueueDigest::read(pool, inserted_sara.id, false, since, AUTOMOD_REASON)
+      .await
+      .unwrap();
+    assert!(digest.is_empty());
+
+    // Nothing new since the last digest
+    let digest = ModQueueDigest::read(pool, inserted_timmy.id, false, naive_now(), AUTOMOD_REASON)
+      .await
+      .unwrap();
+    assert!(digest.is_empty());
+
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Person::delete(pool, inserted_timmy.id).await.unwrap();
+    Person::delete(pool, inserted_sara.id).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+  }
+}
@@ -12,7 +12,7 @@ use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   aggregates::structs::PostAggregates,
   aliases,
-  newtypes::{CommunityId, PersonId, PostReportId},
+  newtypes::{CommunityId, PersonId, PostReportId, ReportReasonId},
   schema::{
     community,
     community_moderator,
@@ -107,6 +107,10 @@ fn queries<'a>() -> Queries<
       query = query.filter(post_report::resolved.eq(false));
     }
 
+    if let Some(reason_id) = options.reason_id {
+      query = query.filter(post_report::reason_id.eq(reason_id));
+    }
+
     let (limit, offset) = limit_and_offset(options.page, options.limit)?;
 
     query = query
@@ -184,6 +188,45 @@ impl PostReportView {
         .await
     }
   }
+
+  /// Returns the current unresolved post report counts for the communities you mod, grouped by
+  /// their listed reason. Reports without a listed reason are left out.
+  pub async fn get_report_count_by_reason(
+    pool: &mut DbPool<'_>,
+    my_person_id: PersonId,
+    admin: bool,
+    community_id: Option<CommunityId>,
+  ) -> Result<Vec<(ReportReasonId, i64)>, Error> {
+    use diesel::dsl::count;
+    let conn = &mut get_conn(pool).await?;
+    let mut query = post_report::table
+      .inner_join(post::table)
+      .filter(post_report::resolved.eq(false))
+      .filter(post_report::reason_id.is_not_null())
+      .group_by(post_report::reason_id)
+      .select((
+        post_report::reason_id.assume_not_null(),
+        count(post_report::id),
+      ))
+      .into_boxed();
+
+    if let Some(community_id) = community_id {
+      query = query.filter(post::community_id.eq(community_id))
+    }
+
+    // If its not an admin, get only the ones you mod
+    if !admin {
+      query = query.filter(
+        post::community_id.eq_any(
+          community_moderator::table
+            .filter(community_moderator::person_id.eq(my_person_id))
+            .select(community_moderator::community_id),
+        ),
+      );
+    }
+
+    query.load::<(ReportReasonId, i64)>(conn).await
+  }
 }
 
 #[derive(Default)]
@@ -192,6 +235,7 @@ pub struct PostReportQuery {
   pub page: Option<i64>,
   pub limit: Option<i64>,
   pub unresolved_only: Option<bool>,
+  pub reason_id: Option<ReportReasonId>,
 }
 
 impl PostReportQuery {
@@ -235,9 +279,11 @@ mod tests {
       person::{Person, PersonInsertForm},
       post::{Post, PostInsertForm},
       post_report::{PostReport, PostReportForm},
+      report_reason::ReportReason,
     },
     traits::{Crud, Joinable, Reportable},
     utils::build_db_pool_for_tests,
+    PostTypeRestriction,
   };
   use serial_test::serial;
 
@@ -311,11 +357,14 @@ mod tests {
       original_post_url: None,
       original_post_body: None,
       reason: "from sara".into(),
+      reason_id: None,
+      rule_id: None,
     };
 
     let inserted_sara_report = PostReport::report(pool, &sara_report_form).await.unwrap();
 
-    // jessica reports
+    // jessica reports with a listed reason
+    let spam_reason = ReportReason::list(pool, None).await.unwrap()[0].clone();
     let jessica_report_form = PostReportForm {
       creator_id: inserted_jessica.id,
       post_id: inserted_post.id,
@@ -323,6 +372,8 @@ mod tests {
       original_post_url: None,
       original_post_body: None,
       reason: "from jessica".into(),
+      reason_id: Some(spam_reason.id),
+      rule_id: None,
     };
 
     let inserted_jessica_report = PostReport::report(pool, &jessica_report_form)
@@ -353,6 +404,19 @@ mod tests {
         banner: None,
         hidden: false,
         posting_restricted_to_mods: false,
+        enable_downvotes: true,
+        requires_follow_approval: false,
+        post_type_restriction: PostTypeRestriction::Any,
+        nsfw_only: false,
+        post_slow_mode_seconds: 0,
+        comment_slow_mode_seconds: 0,
+        allow_undetermined_language: true,
+        strike_ban_threshold: 0,
+        strike_ban_days: 7,
+        min_account_age_days: 0,
+        min_site_karma: 0,
+        min_community_karma: 0,
+        notify_mods_on_mention: false,
         published: inserted_community.published,
         instance_id: inserted_instance.id,
         private_key: inserted_community.private_key.clone(),
@@ -383,6 +447,7 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        shadowbanned_at: None,
         instance_id: inserted_instance.id,
         private_key: inserted_jessica.private_key,
         public_key: inserted_jessica.public_key,
@@ -407,6 +472,7 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        shadowbanned_at: None,
         instance_id: inserted_instance.id,
         private_key: inserted_timmy.private_key.clone(),
         public_key: inserted_timmy.public_key.clone(),
@@ -459,6 +525,7 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      shadowbanned_at: None,
       instance_id: inserted_instance.id,
       private_key: inserted_sara.private_key,
       public_key: inserted_sara.public_key,
@@ -484,6 +551,23 @@ mod tests {
       .await
       .unwrap();
     assert_eq!(2, report_count);
+    let report_count_by_reason =
+      PostReportView::get_report_count_by_reason(pool, inserted_timmy.id, false, None)
+        .await
+        .unwrap();
+    assert_eq!(vec![(spam_reason.id, 1)], report_count_by_reason);
+
+    let reports_with_reason = PostReportQuery {
+      reason_id: Some(spam_reason.id),
+      ..Default::default()
+    }
+    .list(pool, &inserted_timmy)
+    .await
+    .unwrap();
+    assert_eq!(
+      vec![expected_jessica_report_view.clone()],
+      reports_with_reason
+    );
 
     // Try to resolve the report
     PostReport::resolve(pool, inserted_jessica_report.id, inserted_timmy.id)
@@ -523,6 +607,7 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      shadowbanned_at: None,
       instance_id: inserted_instance.id,
       private_key: inserted_timmy.private_key.clone(),
       public_key: inserted_timmy.public_key.clone(),
@@ -1,9 +1,9 @@
-use crate::structs::{LocalUserView, PostView};
+use crate::structs::{LocalUserView, PaginationCursor, PostView, ReactionCount};
 use diesel::{
   debug_query,
-  dsl::{now, IntervalDsl},
+  dsl::{exists, now, sql, IntervalDsl},
   pg::Pg,
-  result::Error,
+  result::Error::{self, QueryBuilderError},
   sql_function,
   sql_types,
   BoolExpressionMethods,
@@ -16,6 +16,7 @@ use diesel::{
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   aggregates::structs::PostAggregates,
+  aliases,
   newtypes::{CommunityId, LocalUserId, PersonId, PostId},
   schema::{
     community,
@@ -23,6 +24,7 @@ use lemmy_db_schema::{
     community_follower,
     community_moderator,
     community_person_ban,
+    community_snooze,
     local_user_language,
     person,
     person_block,
@@ -31,13 +33,15 @@ use lemmy_db_schema::{
     post_aggregates,
     post_like,
     post_read,
+    post_report,
     post_saved,
   },
   source::{
-    community::{Community, CommunityFollower, CommunityPersonBan},
+    community::{Community, CommunityFollower, CommunityModerator, CommunityPersonBan},
     person::Person,
     person_block::PersonBlock,
-    post::{Post, PostRead, PostSaved},
+    person_note::PersonNote,
+    post::{Post, PostReaction, PostRead, PostSaved},
   },
   traits::JoinView,
   utils::{fuzzy_search, limit_and_offset, DbConn, DbPool, ListFn, Queries, ReadFn},
@@ -64,7 +68,7 @@ sql_function!(fn coalesce(x: sql_types::Nullable<sql_types::BigInt>, y: sql_type
 
 fn queries<'a>() -> Queries<
   impl ReadFn<'a, PostView, (PostId, Option<PersonId>, Option<bool>)>,
-  impl ListFn<'a, PostView, PostQuery<'a>>,
+  impl ListFn<'a, PostView, (PostQuery<'a>, bool)>,
 > {
   let all_joins = |query: post_aggregates::BoxedQuery<'a, Pg>, my_person_id: Option<PersonId>| {
     // The left join below will return None in this case
@@ -113,7 +117,12 @@ fn queries<'a>() -> Queries<
         person_block::table.on(
           post_aggregates::creator_id
             .eq(person_block::target_id)
-            .and(person_block::person_id.eq(person_id_join)),
+            .and(person_block::person_id.eq(person_id_join))
+            .and(
+              person_block::expires
+                .is_null()
+                .or(person_block::expires.gt(now)),
+            ),
         ),
       )
       .left_join(
@@ -181,13 +190,26 @@ fn queries<'a>() -> Queries<
           post::deleted
             .eq(false)
             .or(post::creator_id.eq(person_id_join)),
+        )
+        // posts of shadowbanned users are only visible to themselves, mods and admins
+        .filter(
+          person::shadowbanned_at
+            .is_null()
+            .or(post::published.nullable().lt(person::shadowbanned_at))
+            .or(post::creator_id.eq(person_id_join))
+            .or(community_moderator::person_id.is_not_null())
+            .or(exists(
+              aliases::person1
+                .filter(aliases::person1.field(person::id).eq(person_id_join))
+                .filter(aliases::person1.field(person::admin)),
+            )),
         );
     }
 
     query.first::<PostViewTuple>(&mut conn).await
   };
 
-  let list = move |mut conn: DbConn<'a>, options: PostQuery<'a>| async move {
+  let list = move |mut conn: DbConn<'a>, (options, look_ahead): (PostQuery<'a>, bool)| async move {
     let person_id = options.local_user.map(|l| l.person.id);
     let local_user_id = options.local_user.map(|l| l.local_user.id);
 
@@ -203,6 +225,14 @@ fn queries<'a>() -> Queries<
             .and(community_block::person_id.eq(person_id_join)),
         ),
       )
+      .left_join(
+        community_snooze::table.on(
+          post_aggregates::community_id
+            .eq(community_snooze::community_id)
+            .and(community_snooze::person_id.eq(person_id_join))
+            .and(community_snooze::expires.gt(now)),
+        ),
+      )
       .left_join(
         local_user_language::table.on(
           post::language_id
@@ -228,6 +258,17 @@ fn queries<'a>() -> Queries<
         .filter(post::removed.eq(false));
     }
 
+    // posts of shadowbanned users are only visible to themselves, mods and admins
+    if !is_admin {
+      query = query.filter(
+        person::shadowbanned_at
+          .is_null()
+          .or(post::published.nullable().lt(person::shadowbanned_at))
+          .or(post::creator_id.eq(person_id_join))
+          .or(community_moderator::person_id.is_not_null()),
+      );
+    }
+
     if options.community_id.is_none() {
       query = query.then_order_by(post_aggregates::featured_local.desc());
     } else if let Some(community_id) = options.community_id {
@@ -297,15 +338,29 @@ fn queries<'a>() -> Queries<
       query = query.filter(post_saved::post_id.is_not_null());
     }
 
+    let reported_only = options.reported_only.unwrap_or(false);
+    if reported_only {
+      // Only posts with open reports, in communities the user moderates. Admins see all of them.
+      query = query.filter(exists(
+        post_report::table
+          .filter(post_report::post_id.eq(post_aggregates::post_id))
+          .filter(post_report::resolved.eq(false)),
+      ));
+      if !is_admin {
+        query = query.filter(community_moderator::person_id.is_not_null());
+      }
+    }
+
     if options.moderator_view.unwrap_or(false) {
       query = query.filter(community_moderator::person_id.is_not_null());
     }
     // Only hide the read posts, if the saved_only is false. Otherwise ppl with the hide_read
     // setting wont be able to see saved posts.
-    else if !options
-      .local_user
-      .map(|l| l.local_user.show_read_posts)
-      .unwrap_or(true)
+    else if !reported_only
+      && !options
+        .local_user
+        .map(|l| l.local_user.show_read_posts)
+        .unwrap_or(true)
     {
       // Do not hide read posts when it is a user profile view
       if !options.is_profile_view {
@@ -319,16 +374,37 @@ fn queries<'a>() -> Queries<
 
       // Don't show blocked communities or persons
       query = query.filter(community_block::person_id.is_null());
-      if !options.moderator_view.unwrap_or(false) {
+      // Snoozed communities are only hidden from feeds, not when viewing the community itself
+      if options.community_id.is_none() {
+        query = query.filter(community_snooze::person_id.is_null());
+      }
+      if !options.moderator_view.unwrap_or(false) && !reported_only {
         query = query.filter(person_block::person_id.is_null());
       }
     }
 
-    query = match options.sort.unwrap_or(SortType::Hot) {
+    let sort = options.sort.unwrap_or(SortType::Hot);
+    if sort == SortType::Trending {
+      return Err(QueryBuilderError(
+        "Trending sort only applies to communities".into(),
+      ));
+    }
+
+    // The position of a post in contest mode in a listing by score would reveal its hidden score
+    if !is_admin && is_score_sort(sort) {
+      query = query.filter(
+        post::contest_mode_until
+          .is_null()
+          .or(post::contest_mode_until.le(now)),
+      );
+    }
+
+    query = match sort {
       SortType::Active => query
         .then_order_by(post_aggregates::hot_rank_active.desc())
         .then_order_by(post_aggregates::published.desc()),
-      SortType::Hot => query
+      // Trending is rejected above
+      SortType::Hot | SortType::Trending => query
         .then_order_by(post_aggregates::hot_rank.desc())
         .then_order_by(post_aggregates::published.desc()),
       SortType::Controversial => query.then_order_by(post_aggregates::controversy_rank.desc()),
@@ -383,8 +459,24 @@ fn queries<'a>() -> Queries<
         .then_order_by(post_aggregates::published.desc()),
     };
 
-    let (limit, offset) = limit_and_offset(options.page, options.limit)?;
+    // The post id breaks ties, so that every post has a unique position for keyset pagination
+    query = if matches!(options.sort, Some(SortType::Old)) {
+      query.then_order_by(post_aggregates::post_id.asc())
+    } else {
+      query.then_order_by(post_aggregates::post_id.desc())
+    };
 
+    let (limit, offset) = if let Some(page_after) = options.page_after {
+      let sort = options.sort.unwrap_or(SortType::Hot);
+      let filter = page_after.filter(sort, options.community_id.is_some());
+      query = query.filter(sql::<sql_types::Bool>(&filter));
+      limit_and_offset(None, options.limit)?
+    } else {
+      limit_and_offset(options.page, options.limit)?
+    };
+
+    // One more post than requested shows if there is another page
+    let limit = if look_ahead { limit + 1 } else { limit };
     query = query.limit(limit).offset(offset);
 
     debug!("Post View Query: {:?}", debug_query::<Pg, _>(&query));
@@ -412,8 +504,188 @@ impl PostView {
       res.my_vote = Some(0)
     };
 
+    Self::add_reactions(pool, std::slice::from_mut(&mut res), my_person_id).await?;
+    Self::add_creator_notes(pool, std::slice::from_mut(&mut res), my_person_id).await?;
+    if !is_mod_or_admin.unwrap_or(false) {
+      Self::hide_contest_scores(pool, std::slice::from_mut(&mut res), my_person_id).await?;
+    }
+
     Ok(res)
   }
+
+  /// Hides the vote counts of posts in contest mode, except in communities the person moderates.
+  async fn hide_contest_scores(
+    pool: &mut DbPool<'_>,
+    posts: &mut [PostView],
+    my_person_id: Option<PersonId>,
+  ) -> Result<(), Error> {
+    if !posts.iter().any(|p| p.post.in_contest_mode()) {
+      return Ok(());
+    }
+    let moderated = match my_person_id {
+      Some(person_id) => {
+        CommunityModerator::get_person_moderated_communities(pool, person_id).await?
+      }
+      None => vec![],
+    };
+    for post_view in posts
+      .iter_mut()
+      .filter(|p| p.post.in_contest_mode() && !moderated.contains(&p.community.id))
+    {
+      post_view.counts.score = 0;
+      post_view.counts.upvotes = 0;
+      post_view.counts.downvotes = 0;
+    }
+    Ok(())
+  }
+
+  /// Fills in the emoji reaction counts. These are loaded separately, as joining them in the
+  /// main query would multiply its rows.
+  async fn add_reactions(
+    pool: &mut DbPool<'_>,
+    posts: &mut [PostView],
+    my_person_id: Option<PersonId>,
+  ) -> Result<(), Error> {
+    let post_ids: Vec<PostId> = posts.iter().map(|p| p.post.id).collect();
+    let counts = PostReaction::counts(pool, &post_ids).await?;
+    if counts.is_empty() {
+      return Ok(());
+    }
+    let my_reactions = match my_person_id {
+      Some(person_id) => PostReaction::for_person(pool, person_id, &post_ids).await?,
+      None => vec![],
+    };
+
+    let mut reactions = ReactionCount::group(counts, my_reactions);
+    for post_view in posts {
+      post_view.reactions = reactions.remove(&post_view.post.id).unwrap_or_default();
+    }
+    Ok(())
+  }
+
+  /// Fills in the private notes of the person about the post creators.
+  async fn add_creator_notes(
+    pool: &mut DbPool<'_>,
+    posts: &mut [PostView],
+    my_person_id: Option<PersonId>,
+  ) -> Result<(), Error> {
+    let Some(my_person_id) = my_person_id else {
+      return Ok(());
+    };
+    let creator_ids: Vec<PersonId> = posts.iter().map(|p| p.creator.id).collect();
+    let notes = PersonNote::for_targets(pool, my_person_id, &creator_ids).await?;
+    for post_view in posts {
+      post_view.creator_note = notes
+        .iter()
+        .find(|n| n.target_id == post_view.creator.id)
+        .cloned();
+    }
+    Ok(())
+  }
+}
+
+/// Whether the sort orders posts by their votes.
+fn is_score_sort(sort: SortType) -> bool {
+  matches!(
+    sort,
+    SortType::Controversial
+      | SortType::TopAll
+      | SortType::TopYear
+      | SortType::TopMonth
+      | SortType::TopWeek
+      | SortType::TopDay
+      | SortType::TopHour
+      | SortType::TopSixHour
+      | SortType::TopTwelveHour
+      | SortType::TopThreeMonths
+      | SortType::TopSixMonths
+      | SortType::TopNineMonths
+  )
+}
+
+/// The sort values of the last post on a page, which the next page continues after.
+pub struct PaginationCursorData(PostAggregates);
+
+impl PaginationCursor {
+  /// Creates a cursor that continues a listing after the given post.
+  pub fn after_post(view: &PostView) -> PaginationCursor {
+    PaginationCursor(format!("P{:x}", view.counts.post_id.0))
+  }
+
+  pub async fn read(&self, pool: &mut DbPool<'_>) -> Result<PaginationCursorData, Error> {
+    let post_id = self
+      .0
+      .strip_prefix('P')
+      .and_then(|id| i32::from_str_radix(id, 16).ok())
+      .ok_or_else(|| QueryBuilderError("Invalid pagination cursor".into()))?;
+    let counts = PostAggregates::read(pool, PostId(post_id)).await?;
+    Ok(PaginationCursorData(counts))
+  }
+}
+
+impl PaginationCursorData {
+  /// Builds the condition for rows which come after the cursor in the listing order. Columns
+  /// which are sorted in the same direction are compared as a row, so that the matching listing
+  /// index can be used. The values all come from the database, so they are safe to inline.
+  fn filter(&self, sort: SortType, in_community: bool) -> String {
+    let a = &self.0;
+    let (featured_column, featured) = if in_community {
+      ("featured_community", a.featured_community)
+    } else {
+      ("featured_local", a.featured_local)
+    };
+    let published = format!("'{}'::timestamp", a.published);
+    let (columns, values) = match sort {
+      SortType::Old => {
+        return format!(
+          "(post_aggregates.{featured_column} < {featured} OR \
+           (post_aggregates.{featured_column} = {featured} AND \
+           (post_aggregates.published, post_aggregates.post_id) > ({published}, {})))",
+          a.post_id.0
+        )
+      }
+      SortType::Active => (
+        "hot_rank_active, post_aggregates.published",
+        format!("{}, {published}", a.hot_rank_active),
+      ),
+      SortType::Hot | SortType::Trending => (
+        "hot_rank, post_aggregates.published",
+        format!("{}, {published}", a.hot_rank),
+      ),
+      SortType::Controversial => (
+        "controversy_rank",
+        format!("'{}'::float8", a.controversy_rank),
+      ),
+      SortType::New => ("published", published),
+      SortType::NewComments => (
+        "newest_comment_time",
+        format!("'{}'::timestamp", a.newest_comment_time),
+      ),
+      SortType::MostComments => (
+        "comments, post_aggregates.published",
+        format!("{}, {published}", a.comments),
+      ),
+      SortType::TopAll
+      | SortType::TopYear
+      | SortType::TopMonth
+      | SortType::TopWeek
+      | SortType::TopDay
+      | SortType::TopHour
+      | SortType::TopSixHour
+      | SortType::TopTwelveHour
+      | SortType::TopThreeMonths
+      | SortType::TopSixMonths
+      | SortType::TopNineMonths => (
+        "score, post_aggregates.published",
+        format!("{}, {published}", a.score),
+      ),
+    };
+    format!(
+      "(post_aggregates.{featured_column}, post_aggregates.{columns}, post_aggregates.post_id) \
+       < ({featured}, {values}, {})",
+      a.post_id.0
+    )
+  }
 }
 
 #[derive(Default)]
@@ -427,14 +699,51 @@ pub struct PostQuery<'a> {
   pub url_search: Option<String>,
   pub saved_only: Option<bool>,
   pub moderator_view: Option<bool>,
+  /// Only posts with open reports, in communities the user moderates.
+  pub reported_only: Option<bool>,
   pub is_profile_view: bool,
   pub page: Option<i64>,
+  /// Continue the listing after this cursor, instead of using `page`.
+  pub page_after: Option<PaginationCursorData>,
   pub limit: Option<i64>,
 }
 
 impl<'a> PostQuery<'a> {
   pub async fn list(self, pool: &mut DbPool<'_>) -> Result<Vec<PostView>, Error> {
-    queries().list(pool, self).await
+    Ok(self.list_page(pool, false).await?.0)
+  }
+
+  /// Lists the posts, and a cursor for the next page if there are more posts after them.
+  pub async fn list_with_next_page(
+    self,
+    pool: &mut DbPool<'_>,
+  ) -> Result<(Vec<PostView>, Option<PaginationCursor>), Error> {
+    let (posts, has_next_page) = self.list_page(pool, true).await?;
+    let next_page = if has_next_page {
+      posts.last().map(PaginationCursor::after_post)
+    } else {
+      None
+    };
+    Ok((posts, next_page))
+  }
+
+  async fn list_page(
+    self,
+    pool: &mut DbPool<'_>,
+    look_ahead: bool,
+  ) -> Result<(Vec<PostView>, bool), Error> {
+    let my_person_id = self.local_user.map(|l| l.person.id);
+    let is_admin = self.local_user.map(|l| l.person.admin).unwrap_or(false);
+    let (limit, _) = limit_and_offset(None, self.limit)?;
+    let mut posts = queries().list(pool, (self, look_ahead)).await?;
+    let has_next_page = posts.len() > limit as usize;
+    posts.truncate(limit as usize);
+    PostView::add_reactions(pool, &mut posts, my_person_id).await?;
+    PostView::add_creator_notes(pool, &mut posts, my_person_id).await?;
+    if !is_admin {
+      PostView::hide_contest_scores(pool, &mut posts, my_person_id).await?;
+    }
+    Ok((posts, has_next_page))
   }
 }
 
@@ -453,6 +762,8 @@ impl JoinView for PostView {
       creator_blocked: a.8.is_some(),
       my_vote: a.9,
       unread_comments: a.10,
+      reactions: vec![],
+      creator_note: None,
     }
   }
 }
@@ -464,25 +775,37 @@ mod tests {
 
   use crate::{
     post_view::{PostQuery, PostView},
-    structs::LocalUserView,
+    structs::{LocalUserView, PaginationCursor, ReactionCount},
   };
+  use chrono::Duration;
   use lemmy_db_schema::{
     aggregates::structs::PostAggregates,
     impls::actor_language::UNDETERMINED_ID,
-    newtypes::LanguageId,
+    newtypes::{CommunityId, LanguageId, PostId},
     source::{
       actor_language::LocalUserLanguage,
-      community::{Community, CommunityInsertForm},
+      community::{Community, CommunityInsertForm, CommunityModerator, CommunityModeratorForm},
       community_block::{CommunityBlock, CommunityBlockForm},
+      community_snooze::{CommunitySnooze, CommunitySnoozeForm},
       instance::Instance,
       language::Language,
       local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
-      person::{Person, PersonInsertForm},
+      person::{Person, PersonInsertForm, PersonUpdateForm},
       person_block::{PersonBlock, PersonBlockForm},
-      post::{Post, PostInsertForm, PostLike, PostLikeForm, PostUpdateForm},
+      post::{
+        Post,
+        PostInsertForm,
+        PostLike,
+        PostLikeForm,
+        PostReaction,
+        PostReactionForm,
+        PostUpdateForm,
+      },
+      post_report::{PostReport, PostReportForm},
     },
-    traits::{Blockable, Crud, Likeable},
-    utils::{build_db_pool_for_tests, DbPool},
+    traits::{Blockable, Crud, Joinable, Likeable, Reactable, Reportable},
+    utils::{build_db_pool_for_tests, naive_now, DbPool},
+    PostTypeRestriction,
     SortType,
     SubscribedType,
   };
@@ -558,6 +881,7 @@ mod tests {
     let person_block = PersonBlockForm {
       person_id: inserted_person.id,
       target_id: inserted_blocked_person.id,
+      expires: None,
     };
 
     PersonBlock::block(pool, &person_block).await.unwrap();
@@ -704,6 +1028,124 @@ mod tests {
     cleanup(data, pool).await;
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn post_listing_page_cursor() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await;
+
+    // Feature one post, so that the cursor also has to skip past the featured ones
+    let form = PostUpdateForm::builder()
+      .featured_community(Some(true))
+      .build();
+    Post::update(pool, data.inserted_post.id, &form)
+      .await
+      .unwrap();
+
+    for sort in [
+      SortType::New,
+      SortType::Old,
+      SortType::Hot,
+      SortType::TopAll,
+      SortType::Controversial,
+    ] {
+      let all_post_ids: Vec<PostId> = PostQuery {
+        sort: (Some(sort)),
+        community_id: (Some(data.inserted_community.id)),
+        ..Default::default()
+      }
+      .list(pool)
+      .await
+      .unwrap()
+      .into_iter()
+      .map(|p| p.post.id)
+      .collect();
+
+      // Walk the listing one post at a time, until there is no next page
+      let mut paged_post_ids = vec![];
+      let mut page_after = None;
+      loop {
+        let (page, next_page) = PostQuery {
+          sort: (Some(sort)),
+          community_id: (Some(data.inserted_community.id)),
+          page_after,
+          limit: (Some(1)),
+          ..Default::default()
+        }
+        .list_with_next_page(pool)
+        .await
+        .unwrap();
+        assert_eq!(1, page.len());
+        paged_post_ids.push(page[0].post.id);
+        match next_page {
+          Some(next_page) => page_after = Some(next_page.read(pool).await.unwrap()),
+          None => break,
+        }
+      }
+
+      assert_eq!(3, all_post_ids.len());
+      assert_eq!(data.inserted_post.id, all_post_ids[0]);
+      assert_eq!(all_post_ids, paged_post_ids, "{sort}");
+    }
+
+    let invalid_cursor = PaginationCursor("nonsense".into()).read(pool).await;
+    assert!(invalid_cursor.is_err());
+
+    cleanup(data, pool).await;
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn post_listing_reported_only() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await;
+
+    let report_form = PostReportForm {
+      creator_id: data.inserted_bot.id,
+      post_id: data.inserted_post.id,
+      original_post_name: data.inserted_post.name.clone(),
+      original_post_url: None,
+      original_post_body: None,
+      reason: "spam".into(),
+      reason_id: None,
+      rule_id: None,
+    };
+    let inserted_report = PostReport::report(pool, &report_form).await.unwrap();
+
+    let reported_query = || PostQuery {
+      sort: (Some(SortType::New)),
+      local_user: (Some(&data.local_user_view)),
+      reported_only: (Some(true)),
+      ..Default::default()
+    };
+
+    // Not a mod of the community yet
+    let not_mod_listing = reported_query().list(pool).await.unwrap();
+
+    let moderator_form = CommunityModeratorForm {
+      community_id: data.inserted_community.id,
+      person_id: data.local_user_view.person.id,
+    };
+    CommunityModerator::join(pool, &moderator_form)
+      .await
+      .unwrap();
+    let mod_listing = reported_query().list(pool).await.unwrap();
+
+    PostReport::resolve(pool, inserted_report.id, data.local_user_view.person.id)
+      .await
+      .unwrap();
+    let resolved_listing = reported_query().list(pool).await.unwrap();
+
+    assert!(not_mod_listing.is_empty());
+    assert_eq!(1, mod_listing.len());
+    assert_eq!(data.inserted_post.id, mod_listing[0].post.id);
+    assert!(resolved_listing.is_empty());
+
+    cleanup(data, pool).await;
+  }
+
   #[tokio::test]
   #[serial]
   async fn post_listing_block_community() {
@@ -735,6 +1177,87 @@ mod tests {
     cleanup(data, pool).await;
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn post_listing_snooze_community() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await;
+
+    let query = |community_id: Option<CommunityId>| PostQuery {
+      sort: (Some(SortType::New)),
+      community_id,
+      local_user: (Some(&data.local_user_view)),
+      ..Default::default()
+    };
+    let community_posts = |posts: Vec<PostView>| {
+      posts
+        .into_iter()
+        .filter(|p| p.community.id == data.inserted_community.id)
+        .count()
+    };
+
+    let mut snooze = CommunitySnoozeForm {
+      person_id: data.local_user_view.person.id,
+      community_id: data.inserted_community.id,
+      expires: naive_now() + chrono::Duration::days(1),
+    };
+    CommunitySnooze::snooze(pool, &snooze).await.unwrap();
+
+    // Hidden from the feed, but still shown in the community itself
+    let listing = query(Some(data.inserted_community.id))
+      .list(pool)
+      .await
+      .unwrap();
+    let in_community = community_posts(listing);
+    assert!(in_community > 0);
+    let listing = query(None).list(pool).await.unwrap();
+    assert_eq!(0, community_posts(listing));
+
+    // Once the snooze runs out, the posts show up in the feed again
+    snooze.expires = naive_now() - chrono::Duration::days(1);
+    CommunitySnooze::snooze(pool, &snooze).await.unwrap();
+    let listing = query(None).list(pool).await.unwrap();
+    assert_eq!(in_community, community_posts(listing));
+
+    cleanup(data, pool).await;
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn post_listing_expired_person_block() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await;
+
+    let query = || PostQuery {
+      sort: (Some(SortType::New)),
+      community_id: (Some(data.inserted_community.id)),
+      local_user: (Some(&data.local_user_view)),
+      ..Default::default()
+    };
+    let blocked_person_posts = |posts: Vec<PostView>| {
+      posts
+        .into_iter()
+        .filter(|p| p.creator.id == data.inserted_blocked_person.id)
+        .count()
+    };
+    let listing = query().list(pool).await.unwrap();
+    assert_eq!(0, blocked_person_posts(listing));
+
+    // Once the block expires, the posts show up again
+    let expired_block = PersonBlockForm {
+      person_id: data.local_user_view.person.id,
+      target_id: data.inserted_blocked_person.id,
+      expires: Some(Some(naive_now() - chrono::Duration::days(1))),
+    };
+    PersonBlock::block(pool, &expired_block).await.unwrap();
+    let listing = query().list(pool).await.unwrap();
+    assert_eq!(1, blocked_person_posts(listing));
+
+    cleanup(data, pool).await;
+  }
+
   #[tokio::test]
   #[serial]
   async fn post_listing_like() {
@@ -804,6 +1327,69 @@ mod tests {
     cleanup(data, pool).await;
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn post_listing_reactions() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await;
+
+    for (person_id, emoji) in [
+      (data.local_user_view.person.id, "🎉"),
+      (data.inserted_bot.id, "🎉"),
+      (data.inserted_bot.id, ":blobcat:"),
+    ] {
+      let form = PostReactionForm {
+        post_id: data.inserted_post.id,
+        person_id,
+        emoji: emoji.to_string(),
+      };
+      PostReaction::react(pool, &form).await.unwrap();
+    }
+
+    let post_listing_single_with_person = PostView::read(
+      pool,
+      data.inserted_post.id,
+      Some(data.local_user_view.person.id),
+      None,
+    )
+    .await
+    .unwrap();
+
+    let expected_reactions = vec![
+      ReactionCount {
+        emoji: "🎉".to_string(),
+        count: 2,
+        my_reaction: true,
+      },
+      ReactionCount {
+        emoji: ":blobcat:".to_string(),
+        count: 1,
+        my_reaction: false,
+      },
+    ];
+    assert_eq!(
+      expected_reactions,
+      post_listing_single_with_person.reactions
+    );
+
+    let read_post_listing = PostQuery {
+      community_id: (Some(data.inserted_community.id)),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+    let post_with_reactions = read_post_listing
+      .iter()
+      .find(|p| p.post.id == data.inserted_post.id)
+      .unwrap();
+    assert_eq!(2, post_with_reactions.reactions.len());
+    assert!(post_with_reactions.reactions.iter().all(|r| !r.my_reaction));
+
+    cleanup(data, pool).await;
+  }
+
   #[tokio::test]
   #[serial]
   async fn post_listing_person_language() {
@@ -977,6 +1563,166 @@ mod tests {
     cleanup(data, pool).await;
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn post_listings_shadowbanned() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let mut data = init_data(pool).await;
+
+    // Shadowban the bot, as of its creation so that its post is hidden
+    Person::update(
+      pool,
+      data.inserted_bot.id,
+      &PersonUpdateForm::builder()
+        .shadowbanned_at(Some(Some(data.inserted_bot.published)))
+        .build(),
+    )
+    .await
+    .unwrap();
+
+    let contains_bot_post =
+      |posts: &[PostView]| posts.iter().any(|p| p.creator.id == data.inserted_bot.id);
+
+    // Other users don't see the post
+    let post_listings_no_person = PostQuery {
+      sort: Some(SortType::New),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+    assert!(!contains_bot_post(&post_listings_no_person));
+
+    // Admins still do
+    data.local_user_view.person.admin = true;
+    let post_listings_is_admin = PostQuery {
+      sort: Some(SortType::New),
+      local_user: Some(&data.local_user_view),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+    assert!(contains_bot_post(&post_listings_is_admin));
+
+    // The same applies when reading the post directly
+    let bot_post_id = post_listings_is_admin
+      .iter()
+      .find(|p| p.creator.id == data.inserted_bot.id)
+      .unwrap()
+      .post
+      .id;
+    assert!(PostView::read(pool, bot_post_id, None, None).await.is_err());
+    Person::update(
+      pool,
+      data.local_user_view.person.id,
+      &PersonUpdateForm::builder().admin(Some(true)).build(),
+    )
+    .await
+    .unwrap();
+    let post_read_is_admin = PostView::read(
+      pool,
+      bot_post_id,
+      Some(data.local_user_view.person.id),
+      None,
+    )
+    .await;
+    assert!(post_read_is_admin.is_ok());
+
+    cleanup(data, pool).await;
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn post_listings_contest_mode() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let mut data = init_data(pool).await;
+
+    let like_form = PostLikeForm {
+      post_id: data.inserted_post.id,
+      person_id: data.inserted_bot.id,
+      score: 1,
+    };
+    PostLike::like(pool, &like_form).await.unwrap();
+    let form = PostUpdateForm::builder()
+      .contest_mode_until(Some(Some(naive_now() + Duration::days(1))))
+      .build();
+    Post::update(pool, data.inserted_post.id, &form)
+      .await
+      .unwrap();
+
+    let contest_post = |posts: Vec<PostView>| {
+      posts
+        .into_iter()
+        .find(|p| p.post.id == data.inserted_post.id)
+    };
+
+    for sort in [
+      SortType::New,
+      SortType::TopAll,
+      SortType::TopDay,
+      SortType::Controversial,
+    ] {
+      let post_listing = PostQuery {
+        sort: Some(sort),
+        community_id: Some(data.inserted_community.id),
+        local_user: Some(&data.local_user_view),
+        ..Default::default()
+      }
+      .list(pool)
+      .await
+      .unwrap();
+      if sort == SortType::New {
+        // The post is listed by time, but without its votes
+        let post_view = contest_post(post_listing).unwrap();
+        assert_eq!(0, post_view.counts.score);
+        assert_eq!(0, post_view.counts.upvotes);
+      } else {
+        // It is left out of listings by votes, where its position would reveal them
+        assert!(contest_post(post_listing).is_none(), "{sort}");
+      }
+    }
+
+    // Admins see the actual votes everywhere
+    data.local_user_view.person.admin = true;
+    let post_listing = PostQuery {
+      sort: Some(SortType::TopAll),
+      community_id: Some(data.inserted_community.id),
+      local_user: Some(&data.local_user_view),
+      ..Default::default()
+    }
+    .list(pool)
+    .await
+    .unwrap();
+    let post_view = contest_post(post_listing).unwrap();
+    assert_eq!(1, post_view.counts.score);
+    assert_eq!(1, post_view.counts.upvotes);
+
+    cleanup(data, pool).await;
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn post_listings_trending() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let data = init_data(pool).await;
+
+    // Only communities are ranked by trending, so posts can't be sorted that way
+    let post_listing = PostQuery {
+      sort: Some(SortType::Trending),
+      local_user: Some(&data.local_user_view),
+      ..Default::default()
+    }
+    .list(pool)
+    .await;
+    assert!(post_listing.is_err());
+
+    cleanup(data, pool).await;
+  }
+
   async fn cleanup(data: Data, pool: &mut DbPool<'_>) {
     let num_deleted = Post::delete(pool, data.inserted_post.id).await.unwrap();
     Community::delete(pool, data.inserted_community.id)
@@ -1026,9 +1772,14 @@ mod tests {
         language_id: LanguageId(47),
         featured_community: false,
         featured_local: false,
+        contest_mode_until: None,
+        audio_mime_type: None,
+        audio_duration: None,
       },
       my_vote: None,
       unread_comments: 0,
+      reactions: vec![],
+      creator_note: None,
       creator: Person {
         id: inserted_person.id,
         name: inserted_person.name.clone(),
@@ -1048,6 +1799,7 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        shadowbanned_at: None,
         instance_id: data.inserted_instance.id,
         private_key: inserted_person.private_key.clone(),
         public_key: inserted_person.public_key.clone(),
@@ -1069,6 +1821,19 @@ mod tests {
         banner: None,
         hidden: false,
         posting_restricted_to_mods: false,
+        enable_downvotes: true,
+        requires_follow_approval: false,
+        post_type_restriction: PostTypeRestriction::Any,
+        nsfw_only: false,
+        post_slow_mode_seconds: 0,
+        comment_slow_mode_seconds: 0,
+        allow_undetermined_language: true,
+        strike_ban_threshold: 0,
+        strike_ban_days: 7,
+        min_account_age_days: 0,
+        min_site_karma: 0,
+        min_community_karma: 0,
+        notify_mods_on_mention: false,
         published: inserted_community.published,
         instance_id: data.inserted_instance.id,
         private_key: inserted_community.private_key.clone(),
@@ -10,7 +10,7 @@ use diesel::{
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   aliases,
-  newtypes::PrivateMessageReportId,
+  newtypes::{PrivateMessageReportId, ReportReasonId},
   schema::{person, private_message, private_message_report},
   source::{
     person::Person,
@@ -67,6 +67,10 @@ fn queries<'a>() -> Queries<
       query = query.filter(private_message_report::resolved.eq(false));
     }
 
+    if let Some(reason_id) = options.reason_id {
+      query = query.filter(private_message_report::reason_id.eq(reason_id));
+    }
+
     let (limit, offset) = limit_and_offset(options.page, options.limit)?;
 
     query
@@ -104,6 +108,26 @@ impl PrivateMessageReportView {
       .first::<i64>(conn)
       .await
   }
+
+  /// Returns the current unresolved private message report counts, grouped by their listed reason.
+  /// Reports without a listed reason are left out.
+  pub async fn get_report_count_by_reason(
+    pool: &mut DbPool<'_>,
+  ) -> Result<Vec<(ReportReasonId, i64)>, Error> {
+    use diesel::dsl::count;
+    let conn = &mut get_conn(pool).await?;
+
+    private_message_report::table
+      .filter(private_message_report::resolved.eq(false))
+      .filter(private_message_report::reason_id.is_not_null())
+      .group_by(private_message_report::reason_id)
+      .select((
+        private_message_report::reason_id.assume_not_null(),
+        count(private_message_report::id),
+      ))
+      .load::<(ReportReasonId, i64)>(conn)
+      .await
+  }
 }
 
 #[derive(Default)]
@@ -111,6 +135,7 @@ pub struct PrivateMessageReportQuery {
   pub page: Option<i64>,
   pub limit: Option<i64>,
   pub unresolved_only: Option<bool>,
+  pub reason_id: Option<ReportReasonId>,
 }
 
 impl PrivateMessageReportQuery {
@@ -188,6 +213,7 @@ mod tests {
       original_pm_text: pm.content.clone(),
       private_message_id: pm.id,
       reason: "its offensive".to_string(),
+      reason_id: None,
     };
     let pm_report = PrivateMessageReport::report(pool, &pm_report_form)
       .await
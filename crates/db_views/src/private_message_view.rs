@@ -6,6 +6,7 @@ use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   JoinOnDsl,
+  NullableExpressionMethods,
   QueryDsl,
 };
 use diesel_async::RunQueryDsl;
@@ -77,6 +78,18 @@ fn queries<'a>() -> Queries<
 
     let (limit, offset) = limit_and_offset(options.page, options.limit)?;
 
+    // messages of shadowbanned users are only visible to themselves
+    query = query.filter(
+      person::shadowbanned_at
+        .is_null()
+        .or(
+          private_message::published
+            .nullable()
+            .lt(person::shadowbanned_at),
+        )
+        .or(private_message::creator_id.eq(recipient_id)),
+    );
+
     query = query
       .filter(private_message::deleted.eq(false))
       .limit(limit)
@@ -110,9 +123,17 @@ impl PrivateMessageView {
     use diesel::dsl::count;
     let conn = &mut get_conn(pool).await?;
     private_message::table
+      .inner_join(person::table.on(private_message::creator_id.eq(person::id)))
       .filter(private_message::read.eq(false))
       .filter(private_message::recipient_id.eq(my_person_id))
       .filter(private_message::deleted.eq(false))
+      .filter(
+        person::shadowbanned_at.is_null().or(
+          private_message::published
+            .nullable()
+            .lt(person::shadowbanned_at),
+        ),
+      )
       .select(count(private_message::id))
       .first::<i64>(conn)
       .await
@@ -153,11 +174,11 @@ mod tests {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
-  use crate::private_message_view::PrivateMessageQuery;
+  use crate::{private_message_view::PrivateMessageQuery, structs::PrivateMessageView};
   use lemmy_db_schema::{
     source::{
       instance::Instance,
-      person::{Person, PersonInsertForm},
+      person::{Person, PersonInsertForm, PersonUpdateForm},
       private_message::{PrivateMessage, PrivateMessageInsertForm},
     },
     traits::Crud,
@@ -297,4 +318,67 @@ mod tests {
     assert_eq!(timmy_sara_unread_messages[0].creator.id, sara.id);
     assert_eq!(timmy_sara_unread_messages[0].recipient.id, timmy.id);
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_shadowbanned_creator() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let mut persons = vec![];
+    for name in ["shadowbanned_sender", "pm_recipient"] {
+      let form = PersonInsertForm::builder()
+        .name(name.into())
+        .public_key("pubkey".to_string())
+        .instance_id(instance.id)
+        .build();
+      persons.push(Person::create(pool, &form).await.unwrap());
+    }
+    let (sender, recipient) = (&persons[0], &persons[1]);
+
+    let form = PrivateMessageInsertForm::builder()
+      .creator_id(sender.id)
+      .recipient_id(recipient.id)
+      .content("before the shadowban".to_string())
+      .build();
+    let before = PrivateMessage::create(pool, &form).await.unwrap();
+
+    let form = PersonUpdateForm::builder()
+      .shadowbanned_at(Some(Some(before.published + chrono::Duration::seconds(1))))
+      .build();
+    Person::update(pool, sender.id, &form).await.unwrap();
+
+    let form = PrivateMessageInsertForm::builder()
+      .creator_id(sender.id)
+      .recipient_id(recipient.id)
+      .content("after the shadowban".to_string())
+      .published(Some(before.published + chrono::Duration::seconds(2)))
+      .build();
+    PrivateMessage::create(pool, &form).await.unwrap();
+
+    // The recipient only sees the message from before the shadowban
+    let received = PrivateMessageQuery::default()
+      .list(pool, recipient.id)
+      .await
+      .unwrap();
+    assert_eq!(1, received.len());
+    assert_eq!(before.id, received[0].private_message.id);
+    let unread = PrivateMessageView::get_unread_messages(pool, recipient.id)
+      .await
+      .unwrap();
+    assert_eq!(1, unread);
+
+    // The sender still sees both
+    let sent = PrivateMessageQuery::default()
+      .list(pool, sender.id)
+      .await
+      .unwrap();
+    assert_eq!(2, sent.len());
+
+    Instance::delete(pool, instance.id).await.unwrap();
+  }
 }
@@ -0,0 +1,27 @@
+use crate::structs::ReactionCount;
+use std::{collections::HashMap, hash::Hash};
+
+impl ReactionCount {
+  /// Groups per-emoji reaction counts by the post or comment they belong to. Emojis are
+  /// ordered by their count, with the most used first.
+  pub(crate) fn group<T: Eq + Hash>(
+    counts: Vec<(T, String, i64)>,
+    my_reactions: Vec<(T, String)>,
+  ) -> HashMap<T, Vec<ReactionCount>> {
+    let mut grouped: HashMap<T, Vec<ReactionCount>> = HashMap::new();
+    for (item_id, emoji, count) in counts {
+      let my_reaction = my_reactions
+        .iter()
+        .any(|(my_id, my_emoji)| my_id == &item_id && my_emoji == &emoji);
+      grouped.entry(item_id).or_default().push(ReactionCount {
+        emoji,
+        count,
+        my_reaction,
+      });
+    }
+    for reactions in grouped.values_mut() {
+      reactions.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.emoji.cmp(&b.emoji)));
+    }
+    grouped
+  }
+}
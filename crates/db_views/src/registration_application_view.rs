@@ -289,6 +289,16 @@ mod tests {
         password_encrypted: inserted_sara_local_user.password_encrypted,
         open_links_in_new_tab: inserted_sara_local_user.open_links_in_new_tab,
         infinite_scroll_enabled: inserted_sara_local_user.infinite_scroll_enabled,
+        anonymize_outgoing_votes: inserted_sara_local_user.anonymize_outgoing_votes,
+        notify_comment_replies: inserted_sara_local_user.notify_comment_replies,
+        notify_post_replies: inserted_sara_local_user.notify_post_replies,
+        notify_mentions: inserted_sara_local_user.notify_mentions,
+        notify_private_messages: inserted_sara_local_user.notify_private_messages,
+        auto_subscribe_threads: inserted_sara_local_user.auto_subscribe_threads,
+        email_mod_queue_digest: inserted_sara_local_user.email_mod_queue_digest,
+        mod_queue_digest_sent: inserted_sara_local_user.mod_queue_digest_sent,
+        email_login_alerts: inserted_sara_local_user.email_login_alerts,
+        admin_role: inserted_sara_local_user.admin_role,
       },
       creator: Person {
         id: inserted_sara_person.id,
@@ -300,6 +310,7 @@ mod tests {
         local: true,
         banned: false,
         ban_expires: None,
+        shadowbanned_at: None,
         deleted: false,
         admin: false,
         bot_account: false,
@@ -378,6 +389,7 @@ mod tests {
       local: true,
       banned: false,
       ban_expires: None,
+      shadowbanned_at: None,
       deleted: false,
       admin: true,
       bot_account: false,
@@ -4,12 +4,15 @@ use lemmy_db_schema::{
     comment::Comment,
     comment_report::CommentReport,
     community::Community,
+    community_application::CommunityApplication,
     custom_emoji::CustomEmoji,
     custom_emoji_keyword::CustomEmojiKeyword,
+    invite::{Invite, InviteUse},
     local_site::LocalSite,
     local_site_rate_limit::LocalSiteRateLimit,
     local_user::LocalUser,
     person::Person,
+    person_note::PersonNote,
     post::Post,
     post_report::PostReport,
     private_message::PrivateMessage,
@@ -58,6 +61,9 @@ pub struct CommentView {
   pub saved: bool,
   pub creator_blocked: bool,
   pub my_vote: Option<i16>,
+  pub reactions: Vec<ReactionCount>,
+  /// Your private note about the creator.
+  pub creator_note: Option<PersonNote>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -104,6 +110,26 @@ pub struct PostView {
   pub creator_blocked: bool,
   pub my_vote: Option<i16>,
   pub unread_comments: i64,
+  pub reactions: Vec<ReactionCount>,
+  /// Your private note about the creator.
+  pub creator_note: Option<PersonNote>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An opaque cursor to continue a post listing after a given post.
+pub struct PaginationCursor(pub String);
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// The number of emoji reactions of a given kind on a post or comment.
+pub struct ReactionCount {
+  pub emoji: String,
+  pub count: i64,
+  /// Whether the current user reacted with this emoji.
+  pub my_reaction: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
@@ -141,6 +167,17 @@ pub struct RegistrationApplicationView {
   pub admin: Option<Person>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An application to create a community.
+pub struct CommunityApplicationView {
+  pub community_application: CommunityApplication,
+  pub creator: Person,
+  pub admin: Option<Person>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -160,3 +197,43 @@ pub struct CustomEmojiView {
   pub custom_emoji: CustomEmoji,
   pub keywords: Vec<CustomEmojiKeyword>,
 }
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// An invite code with its creator.
+pub struct InviteView {
+  pub invite: Invite,
+  pub creator: Person,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A user who registered with an invite, and who invited them.
+pub struct InviteUseView {
+  pub invite_use: InviteUse,
+  pub invite: Invite,
+  pub inviter: Person,
+  pub invitee: Person,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// A vote on a post or comment, for mods and admins to see who voted.
+pub struct VoteView {
+  pub creator: Person,
+  pub score: i16,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Default)]
+/// New items in the mod queue of a moderator or admin, which are summarized in the digest email.
+pub struct ModQueueDigest {
+  /// Unresolved post and comment reports.
+  pub reports: i64,
+  /// Registration applications which are waiting for a decision, only counted for admins.
+  pub registration_applications: i64,
+  /// Posts and comments which were removed by automod, and are still removed.
+  pub automod_holds: i64,
+}
@@ -1,5 +1,6 @@
 use crate::structs::CommentReplyView;
 use diesel::{
+  dsl::now,
   pg::Pg,
   result::Error,
   BoolExpressionMethods,
@@ -94,7 +95,12 @@ fn queries<'a>() -> Queries<
         person_block::table.on(
           comment::creator_id
             .eq(person_block::target_id)
-            .and(person_block::person_id.eq(person_id_join)),
+            .and(person_block::person_id.eq(person_id_join))
+            .and(
+              person_block::expires
+                .is_null()
+                .or(person_block::expires.gt(now)),
+            ),
         ),
       )
       .left_join(
@@ -12,7 +12,7 @@ use lemmy_db_schema::{
   schema::{community, community_follower, person},
   source::{community::Community, person::Person},
   traits::JoinView,
-  utils::{get_conn, DbPool},
+  utils::{get_conn, limit_and_offset, DbPool},
 };
 
 type CommunityFollowerViewTuple = (Community, Person);
@@ -27,6 +27,7 @@ impl CommunityFollowerView {
     let conn = &mut get_conn(pool).await?;
     let res = community_follower::table
       .filter(community_follower::community_id.eq(community_id))
+      .filter(community_follower::pending.eq(false))
       .filter(not(person::local))
       .inner_join(person::table)
       .select(coalesce(person::shared_inbox_url, person::inbox_url))
@@ -36,6 +37,8 @@ impl CommunityFollowerView {
 
     Ok(res)
   }
+  /// Counts the accepted followers of a community, which are listed by
+  /// [CommunityFollowerView::list_follower_actor_ids].
   pub async fn count_community_followers(
     pool: &mut DbPool<'_>,
     community_id: CommunityId,
@@ -43,6 +46,7 @@ impl CommunityFollowerView {
     let conn = &mut get_conn(pool).await?;
     let res = community_follower::table
       .filter(community_follower::community_id.eq(community_id))
+      .filter(community_follower::pending.eq(false))
       .select(count_star())
       .first::<i64>(conn)
       .await?;
@@ -50,6 +54,46 @@ impl CommunityFollowerView {
     Ok(res)
   }
 
+  /// A page of the accepted followers of a community, newest first. Returns the id of each follow,
+  /// which is used to start the next page, together with the actor id of the follower.
+  pub async fn list_follower_actor_ids(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    max_id: Option<i32>,
+    limit: i64,
+  ) -> Result<Vec<(i32, DbUrl)>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let mut query = community_follower::table
+      .inner_join(person::table)
+      .filter(community_follower::community_id.eq(community_id))
+      .filter(community_follower::pending.eq(false))
+      .select((community_follower::id, person::actor_id))
+      .into_boxed();
+    if let Some(max_id) = max_id {
+      query = query.filter(community_follower::id.lt(max_id));
+    }
+    query
+      .order_by(community_follower::id.desc())
+      .limit(limit)
+      .load::<(i32, DbUrl)>(conn)
+      .await
+  }
+
+  /// Counts the followers of a community on this instance, including pending ones.
+  pub async fn count_local_followers(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> Result<i64, Error> {
+    let conn = &mut get_conn(pool).await?;
+    community_follower::table
+      .inner_join(person::table)
+      .filter(community_follower::community_id.eq(community_id))
+      .filter(person::local.eq(true))
+      .select(count_star())
+      .first::<i64>(conn)
+      .await
+  }
+
   pub async fn for_person(pool: &mut DbPool<'_>, person_id: PersonId) -> Result<Vec<Self>, Error> {
     let conn = &mut get_conn(pool).await?;
     let res = community_follower::table
@@ -65,6 +109,30 @@ impl CommunityFollowerView {
 
     Ok(res.into_iter().map(Self::from_tuple).collect())
   }
+
+  /// Follow requests which are waiting for approval by a community mod, oldest first.
+  pub async fn list_pending(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let (limit, offset) = limit_and_offset(page, limit)?;
+    let res = community_follower::table
+      .inner_join(community::table)
+      .inner_join(person::table)
+      .select((community::all_columns, person::all_columns))
+      .filter(community_follower::community_id.eq(community_id))
+      .filter(community_follower::pending.eq(true))
+      .order_by(community_follower::published.asc())
+      .limit(limit)
+      .offset(offset)
+      .load::<CommunityFollowerViewTuple>(conn)
+      .await?;
+
+    Ok(res.into_iter().map(Self::from_tuple).collect())
+  }
 }
 
 impl JoinView for CommunityFollowerView {
@@ -1,5 +1,6 @@
 use crate::structs::{CommunityModeratorView, CommunityView, PersonView};
 use diesel::{
+  dsl::exists,
   pg::Pg,
   result::Error,
   BoolExpressionMethods,
@@ -12,15 +13,31 @@ use diesel::{
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   aggregates::structs::CommunityAggregates,
-  newtypes::{CommunityId, PersonId},
-  schema::{community, community_aggregates, community_block, community_follower, local_user},
+  newtypes::{CategoryId, CommunityId, PersonId},
+  schema::{
+    community,
+    community_aggregates,
+    community_block,
+    community_category,
+    community_follower,
+    local_user,
+  },
   source::{
     community::{Community, CommunityFollower},
     community_block::CommunityBlock,
     local_user::LocalUser,
   },
   traits::JoinView,
-  utils::{fuzzy_search, limit_and_offset, DbConn, DbPool, ListFn, Queries, ReadFn},
+  utils::{
+    functions::community_unread_posts,
+    fuzzy_search,
+    limit_and_offset,
+    DbConn,
+    DbPool,
+    ListFn,
+    Queries,
+    ReadFn,
+  },
   ListingType,
   SortType,
 };
@@ -30,6 +47,7 @@ type CommunityViewTuple = (
   CommunityAggregates,
   Option<CommunityFollower>,
   Option<CommunityBlock>,
+  i64,
 );
 
 fn queries<'a>() -> Queries<
@@ -63,6 +81,8 @@ fn queries<'a>() -> Queries<
     community_aggregates::all_columns,
     community_follower::all_columns.nullable(),
     community_block::all_columns.nullable(),
+    // Only counted for followed communities
+    community_unread_posts(community_follower::person_id.nullable(), community::id),
   );
 
   let not_removed_or_deleted = community::removed
@@ -108,6 +128,18 @@ fn queries<'a>() -> Queries<
         .or_filter(community::title.ilike(searcher))
     }
 
+    if let Some(category_id) = options.category_id {
+      query = query.filter(exists(
+        community_category::table
+          .filter(community_category::community_id.eq(community::id))
+          .filter(community_category::category_id.eq(category_id)),
+      ));
+    }
+
+    if options.hidden_only.unwrap_or(false) {
+      query = query.filter(community::hidden.eq(true));
+    }
+
     // Hide deleted and removed for non-admins or mods
     if !options.is_mod_or_admin.unwrap_or(false) {
       query = query.filter(not_removed_or_deleted).filter(
@@ -119,6 +151,11 @@ fn queries<'a>() -> Queries<
 
     match options.sort.unwrap_or(Hot) {
       Hot | Active => query = query.order_by(community_aggregates::hot_rank.desc()),
+      Trending => {
+        query = query
+          .order_by(community_aggregates::trending_rank.desc())
+          .then_order_by(community_aggregates::hot_rank.desc())
+      }
       NewComments | TopDay | TopTwelveHour | TopSixHour | TopHour => {
         query = query.order_by(community_aggregates::users_active_day.desc())
       }
@@ -189,7 +226,7 @@ impl CommunityView {
       return Ok(true);
     }
 
-    PersonView::is_admin(pool, person_id).await
+    PersonView::is_content_admin(pool, person_id).await
   }
 }
 
@@ -201,6 +238,9 @@ pub struct CommunityQuery<'a> {
   pub search_term: Option<String>,
   pub is_mod_or_admin: Option<bool>,
   pub show_nsfw: Option<bool>,
+  pub category_id: Option<CategoryId>,
+  /// Only list communities which were hidden by admins
+  pub hidden_only: Option<bool>,
   pub page: Option<i64>,
   pub limit: Option<i64>,
 }
@@ -219,6 +259,7 @@ impl JoinView for CommunityView {
       counts: a.1,
       subscribed: CommunityFollower::to_subscribed_type(&a.2),
       blocked: a.3.is_some(),
+      unread_posts: a.4,
     }
   }
 }
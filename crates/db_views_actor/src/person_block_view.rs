@@ -1,5 +1,12 @@
 use crate::structs::PersonBlockView;
-use diesel::{result::Error, ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel::{
+  dsl::now,
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  JoinOnDsl,
+  QueryDsl,
+};
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   newtypes::PersonId,
@@ -9,7 +16,7 @@ use lemmy_db_schema::{
   utils::{get_conn, DbPool},
 };
 
-type PersonBlockViewTuple = (Person, Person);
+type PersonBlockViewTuple = (Person, Person, Option<chrono::NaiveDateTime>);
 
 impl PersonBlockView {
   pub async fn for_person(pool: &mut DbPool<'_>, person_id: PersonId) -> Result<Vec<Self>, Error> {
@@ -24,8 +31,14 @@ impl PersonBlockView {
       .select((
         person::all_columns,
         target_person_alias.fields(person::all_columns),
+        person_block::expires,
       ))
       .filter(person_block::person_id.eq(person_id))
+      .filter(
+        person_block::expires
+          .is_null()
+          .or(person_block::expires.gt(now)),
+      )
       .filter(target_person_alias.field(person::deleted).eq(false))
       .order_by(person_block::published)
       .load::<PersonBlockViewTuple>(conn)
@@ -41,6 +54,7 @@ impl JoinView for PersonBlockView {
     Self {
       person: a.0,
       target: a.1,
+      expires: a.2,
     }
   }
 }
@@ -7,6 +7,7 @@ use diesel::{
   ExpressionMethods,
   JoinOnDsl,
   NullableExpressionMethods,
+  PgSortExpressionMethods,
   QueryDsl,
 };
 use diesel_async::RunQueryDsl;
@@ -42,12 +43,12 @@ use lemmy_db_schema::{
 
 type PersonMentionViewTuple = (
   PersonMention,
-  Comment,
+  Option<Comment>,
   Person,
   Post,
   Community,
   Person,
-  CommentAggregates,
+  Option<CommentAggregates>,
   Option<CommunityPersonBan>,
   Option<CommunityFollower>,
   Option<CommentSaved>,
@@ -63,13 +64,28 @@ fn queries<'a>() -> Queries<
     // The left join below will return None in this case
     let person_id_join = my_person_id.unwrap_or(PersonId(-1));
 
+    // Mentions are either in a comment, or directly in a post
     query
-      .inner_join(comment::table)
-      .inner_join(person::table.on(comment::creator_id.eq(person::id)))
-      .inner_join(post::table.on(comment::post_id.eq(post::id)))
+      .left_join(comment::table)
+      .inner_join(
+        post::table.on(
+          comment::post_id
+            .eq(post::id)
+            .or(person_mention::post_id.eq(post::id.nullable())),
+        ),
+      )
+      .inner_join(
+        person::table.on(
+          comment::creator_id.eq(person::id).or(
+            person_mention::comment_id
+              .is_null()
+              .and(post::creator_id.eq(person::id)),
+          ),
+        ),
+      )
       .inner_join(community::table.on(post::community_id.eq(community::id)))
       .inner_join(aliases::person1)
-      .inner_join(comment_aggregates::table.on(comment::id.eq(comment_aggregates::comment_id)))
+      .left_join(comment_aggregates::table.on(comment::id.eq(comment_aggregates::comment_id)))
       .left_join(
         community_follower::table.on(
           post::community_id
@@ -86,9 +102,14 @@ fn queries<'a>() -> Queries<
       )
       .left_join(
         person_block::table.on(
-          comment::creator_id
+          person::id
             .eq(person_block::target_id)
-            .and(person_block::person_id.eq(person_id_join)),
+            .and(person_block::person_id.eq(person_id_join))
+            .and(
+              person_block::expires
+                .is_null()
+                .or(person_block::expires.gt(now)),
+            ),
         ),
       )
       .left_join(
@@ -102,12 +123,12 @@ fn queries<'a>() -> Queries<
 
   let selection = (
     person_mention::all_columns,
-    comment::all_columns,
+    comment::all_columns.nullable(),
     person::all_columns,
     post::all_columns,
     community::all_columns,
     aliases::person1.fields(person::all_columns),
-    comment_aggregates::all_columns,
+    comment_aggregates::all_columns.nullable(),
     community_person_ban::all_columns.nullable(),
     community_follower::all_columns.nullable(),
     comment_saved::all_columns.nullable(),
@@ -126,7 +147,7 @@ fn queries<'a>() -> Queries<
         community_person_ban::table.on(
           community::id
             .eq(community_person_ban::community_id)
-            .and(community_person_ban::person_id.eq(comment::creator_id)),
+            .and(community_person_ban::person_id.eq(person::id)),
         ),
       )
       .select(selection)
@@ -140,7 +161,7 @@ fn queries<'a>() -> Queries<
         community_person_ban::table.on(
           community::id
             .eq(community_person_ban::community_id)
-            .and(community_person_ban::person_id.eq(comment::creator_id))
+            .and(community_person_ban::person_id.eq(person::id))
             .and(
               community_person_ban::expires
                 .is_null()
@@ -162,15 +183,18 @@ fn queries<'a>() -> Queries<
       query = query.filter(person::bot_account.eq(false));
     };
 
+    // Post mentions have no comment aggregates, so they come after comment mentions when sorting
+    // by those
     query = match options.sort.unwrap_or(CommentSortType::Hot) {
-      CommentSortType::Hot => query.then_order_by(comment_aggregates::hot_rank.desc()),
+      CommentSortType::Hot => query.then_order_by(comment_aggregates::hot_rank.desc().nulls_last()),
       CommentSortType::Controversial => {
-        query.then_order_by(comment_aggregates::controversy_rank.desc())
+        query.then_order_by(comment_aggregates::controversy_rank.desc().nulls_last())
       }
-      CommentSortType::New => query.then_order_by(comment::published.desc()),
-      CommentSortType::Old => query.then_order_by(comment::published.asc()),
-      CommentSortType::Top => query.order_by(comment_aggregates::score.desc()),
+      CommentSortType::New => query.then_order_by(person_mention::published.desc()),
+      CommentSortType::Old => query.then_order_by(person_mention::published.asc()),
+      CommentSortType::Top => query.order_by(comment_aggregates::score.desc().nulls_last()),
     };
+    query = query.then_order_by(person_mention::published.desc());
 
     let (limit, offset) = limit_and_offset(options.page, options.limit)?;
 
@@ -204,11 +228,16 @@ impl PersonMentionView {
     let conn = &mut get_conn(pool).await?;
 
     person_mention::table
-      .inner_join(comment::table)
+      .left_join(comment::table)
+      .left_join(post::table)
       .filter(person_mention::recipient_id.eq(my_person_id))
       .filter(person_mention::read.eq(false))
-      .filter(comment::deleted.eq(false))
-      .filter(comment::removed.eq(false))
+      .filter(
+        comment::deleted
+          .eq(false)
+          .and(comment::removed.eq(false))
+          .or(post::deleted.eq(false).and(post::removed.eq(false))),
+      )
       .select(count(person_mention::id))
       .first::<i64>(conn)
       .await
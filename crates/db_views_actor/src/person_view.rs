@@ -1,8 +1,9 @@
 use crate::structs::PersonView;
 use diesel::{
-  dsl::now,
+  dsl::{exists, now},
   pg::Pg,
   result::Error,
+  select,
   BoolExpressionMethods,
   ExpressionMethods,
   PgTextExpressionMethods,
@@ -12,11 +13,11 @@ use diesel_async::RunQueryDsl;
 use lemmy_db_schema::{
   aggregates::structs::PersonAggregates,
   newtypes::PersonId,
-  schema,
-  schema::{person, person_aggregates},
+  schema::{local_user, person, person_aggregates},
   source::person::Person,
   traits::JoinView,
   utils::{fuzzy_search, get_conn, limit_and_offset, DbConn, DbPool, ListFn, Queries, ReadFn},
+  AdminRole,
   PersonSortType,
 };
 
@@ -25,6 +26,7 @@ type PersonViewTuple = (Person, PersonAggregates);
 enum ListMode {
   Admins,
   Banned,
+  Shadowbanned,
   Query(PersonQuery),
 }
 
@@ -62,6 +64,11 @@ fn queries<'a>(
           )
           .filter(person::deleted.eq(false));
       }
+      ListMode::Shadowbanned => {
+        query = query
+          .filter(person::shadowbanned_at.is_not_null())
+          .order_by(person::shadowbanned_at.desc());
+      }
       ListMode::Query(options) => {
         if let Some(search_term) = options.search_term {
           let searcher = fuzzy_search(&search_term);
@@ -94,15 +101,19 @@ impl PersonView {
     queries().read(pool, person_id).await
   }
 
-  pub async fn is_admin(pool: &mut DbPool<'_>, person_id: PersonId) -> Result<bool, Error> {
-    use schema::person::dsl::{admin, id, person};
+  /// Whether the person is an admin with the content or full admin role, which gives them
+  /// moderator powers in all communities.
+  pub async fn is_content_admin(pool: &mut DbPool<'_>, person_id: PersonId) -> Result<bool, Error> {
     let conn = &mut get_conn(pool).await?;
-    let is_admin = person
-      .filter(id.eq(person_id))
-      .select(admin)
-      .first::<bool>(conn)
-      .await?;
-    Ok(is_admin)
+    select(exists(
+      person::table
+        .inner_join(local_user::table)
+        .filter(person::id.eq(person_id))
+        .filter(person::admin.eq(true))
+        .filter(local_user::admin_role.eq_any([AdminRole::ContentAdmin, AdminRole::FullAdmin])),
+    ))
+    .get_result::<bool>(conn)
+    .await
   }
 
   pub async fn admins(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
@@ -112,6 +123,10 @@ impl PersonView {
   pub async fn banned(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
     queries().list(pool, ListMode::Banned).await
   }
+
+  pub async fn shadowbanned(pool: &mut DbPool<'_>) -> Result<Vec<Self>, Error> {
+    queries().list(pool, ListMode::Shadowbanned).await
+  }
 }
 
 #[derive(Default)]
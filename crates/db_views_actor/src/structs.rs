@@ -58,8 +58,11 @@ pub struct CommunityView {
   pub subscribed: SubscribedType,
   pub blocked: bool,
   pub counts: CommunityAggregates,
+  /// Number of new posts since the last visit, only counted for followed communities.
+  pub unread_posts: i64,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
 #[cfg_attr(feature = "full", ts(export))]
@@ -67,6 +70,7 @@ pub struct CommunityView {
 pub struct PersonBlockView {
   pub person: Person,
   pub target: Person,
+  pub expires: Option<chrono::NaiveDateTime>,
 }
 
 #[skip_serializing_none]
@@ -76,12 +80,13 @@ pub struct PersonBlockView {
 /// A person mention view.
 pub struct PersonMentionView {
   pub person_mention: PersonMention,
-  pub comment: Comment,
+  /// Empty if the person was mentioned in the post itself
+  pub comment: Option<Comment>,
   pub creator: Person,
   pub post: Post,
   pub community: Community,
   pub recipient: Person,
-  pub counts: CommentAggregates,
+  pub counts: Option<CommentAggregates>,
   pub creator_banned_from_community: bool,
   pub subscribed: SubscribedType,
   pub saved: bool,
@@ -0,0 +1,87 @@
+use crate::structs::{AdminMergePersonView, ModlogListParams};
+use diesel::{
+  result::Error,
+  BoolExpressionMethods,
+  ExpressionMethods,
+  IntoSql,
+  JoinOnDsl,
+  NullableExpressionMethods,
+  QueryDsl,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema::{
+  aliases,
+  newtypes::PersonId,
+  schema::{admin_merge_person, person},
+  source::{moderator::AdminMergePerson, person::Person},
+  traits::JoinView,
+  utils::{get_conn, limit_and_offset, DbPool},
+};
+
+type AdminMergePersonViewTuple = (AdminMergePerson, Option<Person>, Person, Person);
+
+impl AdminMergePersonView {
+  pub async fn list(pool: &mut DbPool<'_>, params: ModlogListParams) -> Result<Vec<Self>, Error> {
+    let conn = &mut get_conn(pool).await?;
+    let admin_person_id_join = params.mod_person_id.unwrap_or(PersonId(-1));
+    let show_mod_names = !params.hide_modlog_names;
+    let show_mod_names_expr = show_mod_names.as_sql::<diesel::sql_types::Bool>();
+
+    let admin_names_join = admin_merge_person::admin_person_id
+      .eq(person::id)
+      .and(show_mod_names_expr.or(person::id.eq(admin_person_id_join)));
+    let mut query = admin_merge_person::table
+      .left_join(person::table.on(admin_names_join))
+      .inner_join(
+        aliases::person1
+          .on(admin_merge_person::source_person_id.eq(aliases::person1.field(person::id))),
+      )
+      .inner_join(
+        aliases::person2
+          .on(admin_merge_person::target_person_id.eq(aliases::person2.field(person::id))),
+      )
+      .select((
+        admin_merge_person::all_columns,
+        person::all_columns.nullable(),
+        aliases::person1.fields(person::all_columns),
+        aliases::person2.fields(person::all_columns),
+      ))
+      .into_boxed();
+
+    if let Some(admin_person_id) = params.mod_person_id {
+      query = query.filter(admin_merge_person::admin_person_id.eq(admin_person_id));
+    };
+
+    if let Some(other_person_id) = params.other_person_id {
+      query = query.filter(
+        admin_merge_person::source_person_id
+          .eq(other_person_id)
+          .or(admin_merge_person::target_person_id.eq(other_person_id)),
+      );
+    };
+
+    let (limit, offset) = limit_and_offset(params.page, params.limit)?;
+
+    let res = query
+      .limit(limit)
+      .offset(offset)
+      .order_by(admin_merge_person::when_.desc())
+      .load::<AdminMergePersonViewTuple>(conn)
+      .await?;
+
+    let results = res.into_iter().map(Self::from_tuple).collect();
+    Ok(results)
+  }
+}
+
+impl JoinView for AdminMergePersonView {
+  type JoinTuple = AdminMergePersonViewTuple;
+  fn from_tuple(a: Self::JoinTuple) -> Self {
+    Self {
+      admin_merge_person: a.0,
+      admin: a.1,
+      source_person: a.2,
+      target_person: a.3,
+    }
+  }
+}
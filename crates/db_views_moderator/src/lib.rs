@@ -1,4 +1,6 @@
 #[cfg(feature = "full")]
+pub mod admin_merge_person_view;
+#[cfg(feature = "full")]
 pub mod admin_purge_comment_view;
 #[cfg(feature = "full")]
 pub mod admin_purge_community_view;
@@ -27,5 +29,7 @@ pub mod mod_remove_community_view;
 #[cfg(feature = "full")]
 pub mod mod_remove_post_view;
 #[cfg(feature = "full")]
+pub mod mod_shadowban_view;
+#[cfg(feature = "full")]
 pub mod mod_transfer_community_view;
 pub mod structs;
@@ -4,6 +4,7 @@ use lemmy_db_schema::{
     comment::Comment,
     community::Community,
     moderator::{
+      AdminMergePerson,
       AdminPurgeComment,
       AdminPurgeCommunity,
       AdminPurgePerson,
@@ -18,6 +19,7 @@ use lemmy_db_schema::{
       ModRemoveComment,
       ModRemoveCommunity,
       ModRemovePost,
+      ModShadowban,
       ModTransferCommunity,
     },
     person::Person,
@@ -64,6 +66,17 @@ pub struct ModBanFromCommunityView {
   pub banned_person: Person,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When someone is shadowbanned from the site.
+pub struct ModShadowbanView {
+  pub mod_shadowban: ModShadowban,
+  pub moderator: Option<Person>,
+  pub shadowbanned_person: Person,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -180,6 +193,18 @@ pub struct AdminPurgeCommunityView {
   pub admin: Option<Person>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(TS))]
+#[cfg_attr(feature = "full", ts(export))]
+/// When an admin merges a local account into another one.
+pub struct AdminMergePersonView {
+  pub admin_merge_person: AdminMergePerson,
+  pub admin: Option<Person>,
+  pub source_person: Person,
+  pub target_person: Person,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "full", derive(TS))]
@@ -3,8 +3,12 @@ use anyhow::anyhow;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::{
-  newtypes::LocalUserId,
-  source::{community::Community, local_user::LocalUser, person::Person},
+  source::{
+    community::Community,
+    local_user::LocalUser,
+    login_session::LoginSession,
+    person::Person,
+  },
   traits::{ApubActor, Crud},
   utils::DbPool,
   CommentSortType,
@@ -22,8 +26,9 @@ use lemmy_db_views_actor::{
 };
 use lemmy_utils::{
   cache_header::cache_1hour,
-  claims::Claims,
   error::LemmyError,
+  session::{hash_session_token, login_session_expires},
+  settings::SETTINGS,
   utils::markdown::markdown_to_html,
 };
 use once_cell::sync::Lazy;
@@ -182,7 +187,6 @@ async fn get_feed(
     _ => return Err(ErrorBadRequest(LemmyError::from(anyhow!("wrong_type")))),
   };
 
-  let jwt_secret = context.secret().jwt_secret.clone();
   let protocol_and_hostname = context.settings().get_protocol_and_hostname();
 
   let builder = match request_type {
@@ -211,7 +215,6 @@ async fn get_feed(
     RequestType::Front => {
       get_feed_front(
         &mut context.pool(),
-        &jwt_secret,
         &info.sort_type()?,
         &info.get_limit(),
         &info.get_page(),
@@ -220,15 +223,7 @@ async fn get_feed(
       )
       .await
     }
-    RequestType::Inbox => {
-      get_feed_inbox(
-        &mut context.pool(),
-        &jwt_secret,
-        &param,
-        &protocol_and_hostname,
-      )
-      .await
-    }
+    RequestType::Inbox => get_feed_inbox(&mut context.pool(), &param, &protocol_and_hostname).await,
   }
   .map_err(ErrorBadRequest)?;
 
@@ -317,7 +312,6 @@ async fn get_feed_community(
 #[tracing::instrument(skip_all)]
 async fn get_feed_front(
   pool: &mut DbPool<'_>,
-  jwt_secret: &str,
   sort_type: &SortType,
   limit: &i64,
   page: &i64,
@@ -325,7 +319,12 @@ async fn get_feed_front(
   protocol_and_hostname: &str,
 ) -> Result<ChannelBuilder, LemmyError> {
   let site_view = SiteView::read_local(pool).await?;
-  let local_user_id = LocalUserId(Claims::decode(jwt, jwt_secret)?.claims.sub);
+  let local_user_id = LoginSession::validate(
+    pool,
+    &hash_session_token(jwt),
+    login_session_expires(&SETTINGS),
+  )
+  .await?;
   let local_user = LocalUserView::read(pool, local_user_id).await?;
 
   let posts = PostQuery {
@@ -358,12 +357,16 @@ async fn get_feed_front(
 #[tracing::instrument(skip_all)]
 async fn get_feed_inbox(
   pool: &mut DbPool<'_>,
-  jwt_secret: &str,
   jwt: &str,
   protocol_and_hostname: &str,
 ) -> Result<ChannelBuilder, LemmyError> {
   let site_view = SiteView::read_local(pool).await?;
-  let local_user_id = LocalUserId(Claims::decode(jwt, jwt_secret)?.claims.sub);
+  let local_user_id = LoginSession::validate(
+    pool,
+    &hash_session_token(jwt),
+    login_session_expires(&SETTINGS),
+  )
+  .await?;
   let local_user = LocalUser::read(pool, local_user_id).await?;
   let person_id = local_user.person_id;
   let show_bot_accounts = local_user.show_bot_accounts;
@@ -430,15 +433,28 @@ fn create_reply_and_mention_items(
 
   let mut mention_items: Vec<Item> = mentions
     .iter()
-    .map(|m| {
-      let mention_url = format!("{}/comment/{}", protocol_and_hostname, m.comment.id);
-      build_item(
-        &m.creator.name,
-        &m.comment.published,
-        &mention_url,
-        &m.comment.content,
-        protocol_and_hostname,
-      )
+    .map(|m| match &m.comment {
+      Some(comment) => {
+        let mention_url = format!("{}/comment/{}", protocol_and_hostname, comment.id);
+        build_item(
+          &m.creator.name,
+          &comment.published,
+          &mention_url,
+          &comment.content,
+          protocol_and_hostname,
+        )
+      }
+      // Mentioned in the post itself
+      None => {
+        let mention_url = format!("{}/post/{}", protocol_and_hostname, m.post.id);
+        build_item(
+          &m.creator.name,
+          &m.post.published,
+          &mention_url,
+          m.post.body.as_deref().unwrap_or(&m.post.name),
+          protocol_and_hostname,
+        )
+      }
     })
     .collect::<Result<Vec<Item>, LemmyError>>()?;
 
@@ -12,9 +12,21 @@ use actix_web::{
 };
 use futures::stream::{Stream, StreamExt};
 use lemmy_api_common::{context::LemmyContext, utils::local_user_view_from_jwt};
-use lemmy_db_schema::source::local_site::LocalSite;
-use lemmy_utils::{claims::Claims, rate_limit::RateLimitCell, REQWEST_TIMEOUT};
-use reqwest::Body;
+use lemmy_db_schema::source::{
+  local_image::{LocalImage, LocalImageForm},
+  local_site::LocalSite,
+};
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorType},
+  rate_limit::RateLimitCell,
+  settings::structs::PictrsConfig,
+  utils::image_metadata::{strip_image_metadata, ImageFormat},
+  REQWEST_TIMEOUT,
+};
+use reqwest::{
+  multipart::{Form, Part},
+  Body,
+};
 use reqwest_middleware::{ClientWithMiddleware, RequestBuilder};
 use serde::{Deserialize, Serialize};
 
@@ -96,11 +108,21 @@ async fn upload(
     .cookie("jwt")
     .expect("No auth header for picture upload");
 
-  if Claims::decode(jwt.value(), &context.secret().jwt_secret).is_err() {
+  let Ok(local_user_view) = local_user_view_from_jwt(jwt.value(), &context).await else {
     return Ok(HttpResponse::Unauthorized().finish());
   };
+  let person_id = local_user_view.person.id;
 
   let pictrs_config = context.settings().pictrs_config()?;
+  if let Some(max_uploads) = pictrs_config.max_uploads_per_user {
+    let uploads = LocalImage::count_for_person(&mut context.pool(), person_id)
+      .await
+      .map_err(error::ErrorBadRequest)?;
+    if uploads >= max_uploads {
+      Err(LemmyError::from(LemmyErrorType::ImageUploadQuotaExceeded))?
+    }
+  }
+
   let image_url = format!("{}image", pictrs_config.url);
 
   let mut client_req = adapt_request(&req, &client, image_url);
@@ -116,11 +138,120 @@ async fn upload(
     .map_err(error::ErrorBadRequest)?;
 
   let status = res.status();
-  let images = res.json::<Images>().await.map_err(error::ErrorBadRequest)?;
+  let mut images = res.json::<Images>().await.map_err(error::ErrorBadRequest)?;
+  if pictrs_config.strip_image_metadata {
+    if let Some(files) = images.files.take() {
+      images.files = Some(strip_metadata(files, &client, &pictrs_config).await?);
+    }
+  }
+  if let Some(files) = &images.files {
+    for image in files {
+      let form = LocalImageForm::builder()
+        .person_id(person_id)
+        .pictrs_alias(image.file.clone())
+        .pictrs_delete_token(image.delete_token.clone())
+        .build();
+      LocalImage::create(&mut context.pool(), &form)
+        .await
+        .map_err(error::ErrorBadRequest)?;
+    }
+  }
 
   Ok(HttpResponse::build(status).json(images))
 }
 
+/// Makes sure that the uploaded images contain no metadata. Pictrs usually removes it already,
+/// otherwise the image is replaced with a stripped copy. If any image can't be sanitized, all of
+/// them are deleted.
+async fn strip_metadata(
+  files: Vec<Image>,
+  client: &ClientWithMiddleware,
+  pictrs_config: &PictrsConfig,
+) -> Result<Vec<Image>, LemmyError> {
+  let mut stripped_files = Vec::with_capacity(files.len());
+  let mut files = files.into_iter();
+  while let Some(image) = files.next() {
+    match strip_image(&image, client, pictrs_config).await {
+      Ok(Some(stripped)) => {
+        delete_image(&image, client, pictrs_config).await?;
+        stripped_files.push(stripped);
+      }
+      Ok(None) => stripped_files.push(image),
+      Err(e) => {
+        for image in stripped_files
+          .iter()
+          .chain(Some(&image))
+          .chain(files.as_slice())
+        {
+          delete_image(image, client, pictrs_config).await?;
+        }
+        return Err(e);
+      }
+    }
+  }
+  Ok(stripped_files)
+}
+
+/// Uploads a copy of the image without metadata, or returns `None` if it has none
+async fn strip_image(
+  image: &Image,
+  client: &ClientWithMiddleware,
+  pictrs_config: &PictrsConfig,
+) -> Result<Option<Image>, LemmyError> {
+  let original_url = format!("{}image/original/{}", pictrs_config.url, image.file);
+  let original = client
+    .get(original_url)
+    .timeout(REQWEST_TIMEOUT)
+    .send()
+    .await?
+    .error_for_status()?
+    .bytes()
+    .await?;
+  let stripped = strip_image_metadata(&original)?;
+  if stripped == original {
+    return Ok(None);
+  }
+
+  let mime_type = ImageFormat::detect(&stripped)
+    .map(|f| f.mime_type())
+    .unwrap_or_default();
+  let part = Part::bytes(stripped)
+    .file_name(image.file.clone())
+    .mime_str(mime_type)?;
+  let res = client
+    .post(format!("{}image", pictrs_config.url))
+    .timeout(REQWEST_TIMEOUT)
+    .multipart(Form::new().part("images[]", part))
+    .send()
+    .await?
+    .error_for_status()?
+    .json::<Images>()
+    .await?;
+  let stripped = res
+    .files
+    .and_then(|files| files.into_iter().next())
+    .ok_or(LemmyErrorType::UnsupportedImageFormat)?;
+  Ok(Some(stripped))
+}
+
+async fn delete_image(
+  image: &Image,
+  client: &ClientWithMiddleware,
+  pictrs_config: &PictrsConfig,
+) -> Result<(), LemmyError> {
+  let url = format!(
+    "{}image/delete/{}/{}",
+    pictrs_config.url, image.delete_token, image.file
+  );
+  client
+    .get(url)
+    .timeout(REQWEST_TIMEOUT)
+    .send()
+    .await?
+    .error_for_status()?;
+  Ok(())
+}
+
 async fn full_res(
   filename: web::Path<String>,
   web::Query(params): web::Query<PictrsParams>,
@@ -215,6 +346,12 @@ async fn delete(
 
   let res = client_req.send().await.map_err(error::ErrorBadRequest)?;
 
+  if res.status().is_success() {
+    LocalImage::delete_by_alias(&mut context.pool(), &file)
+      .await
+      .map_err(error::ErrorBadRequest)?;
+  }
+
   Ok(HttpResponse::build(res.status()).body(BodyStream::new(res.bytes_stream())))
 }
 
@@ -48,9 +48,11 @@ async fn node_info(context: web::Data<LemmyContext>) -> Result<HttpResponse, Err
   } else {
     None
   };
-  // Since there are 3 registration options,
-  // we need to set open_registrations as true if RegistrationMode is not Closed.
-  let open_registrations = Some(site_view.local_site.registration_mode != RegistrationMode::Closed);
+  // Registrations are open unless they are closed, or only possible with an invite.
+  let open_registrations = Some(matches!(
+    site_view.local_site.registration_mode,
+    RegistrationMode::Open | RegistrationMode::RequireApplication
+  ));
   let json = NodeInfo {
     version: Some("2.0".to_string()),
     software: Some(NodeInfoSoftware {
@@ -74,12 +76,12 @@ async fn node_info(context: web::Data<LemmyContext>) -> Result<HttpResponse, Err
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct NodeInfoWellKnown {
+pub struct NodeInfoWellKnown {
   pub links: Vec<NodeInfoWellKnownLinks>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct NodeInfoWellKnownLinks {
+pub struct NodeInfoWellKnownLinks {
   pub rel: Url,
   pub href: Url,
 }
@@ -5,7 +5,7 @@ use activitypub_federation::{
 use actix_web::{web, web::Query, HttpResponse};
 use lemmy_api_common::context::LemmyContext;
 use lemmy_db_schema::{
-  source::{community::Community, person::Person},
+  source::{community::Community, person::Person, person_alias::PersonAlias},
   traits::ApubActor,
 };
 use lemmy_utils::{cache_header::cache_3days, error::LemmyError};
@@ -38,10 +38,14 @@ async fn get_webfinger_response(
   let name = extract_webfinger_name(&info.resource, &context)?;
 
   let name_ = name.clone();
-  let user_id: Option<Url> = Person::read_from_name(&mut context.pool(), &name_, false)
-    .await
-    .ok()
-    .map(|c| c.actor_id.into());
+  let person = match Person::read_from_name(&mut context.pool(), &name_, false).await {
+    Ok(person) => Some(person),
+    // Former names of renamed users point to their current actor
+    Err(_) => PersonAlias::read_person_from_name(&mut context.pool(), &name_)
+      .await
+      .ok(),
+  };
+  let user_id: Option<Url> = person.map(|c| c.actor_id.into());
   let community_id: Option<Url> = Community::read_from_name(&mut context.pool(), &name, false)
     .await
     .ok()
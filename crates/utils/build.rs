@@ -1,6 +1,25 @@
+use serde_json::{Map, Value};
+use std::{env, fs, path::Path};
+
+/// English strings which are not part of the lemmy-translations submodule yet. Keys which also
+/// exist in the submodule are ignored, so strings can be removed here once they are merged
+/// upstream.
+const EXTRA_EN: &str = "translations_extra/email/en.json";
+const EN: &str = "translations/email/en.json";
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let mut en: Map<String, Value> = serde_json::from_str(&fs::read_to_string(EN)?)?;
+  let extra: Map<String, Value> = serde_json::from_str(&fs::read_to_string(EXTRA_EN)?)?;
+  for (key, value) in extra {
+    en.entry(key).or_insert(value);
+  }
+  let merged_en = Path::new(&env::var("OUT_DIR")?).join("en.json");
+  fs::write(&merged_en, serde_json::to_string_pretty(&en)?)?;
+  println!("cargo:rerun-if-changed={EN}");
+  println!("cargo:rerun-if-changed={EXTRA_EN}");
+
   rosetta_build::config()
-    .source("en", "translations/email/en.json")
+    .source("en", merged_en.to_string_lossy())
     .source("fi", "translations/email/fi.json")
     .source("ko", "translations/email/ko.json")
     .source("pt", "translations/email/pt.json")
@@ -74,6 +74,8 @@ pub enum LemmyErrorType {
   ReportTooLong,
   NotAModerator,
   NotAnAdmin,
+  /// The admin's role doesn't allow this action.
+  MissingAdminRole,
   CantBlockYourself,
   CantBlockAdmin,
   CouldntUpdateUser,
@@ -207,6 +209,116 @@ pub enum LemmyErrorType {
   CouldntCreateAudioCaptcha,
   InvalidUrlScheme,
   CouldntSendWebmention,
+  CouldntCreateTagline,
+  CouldntUpdateTagline,
+  CouldntCreateAnnouncement,
+  CouldntUpdateAnnouncement,
+  /// An announcement has to end after it starts.
+  InvalidAnnouncementTimes,
+  InvalidEmojiReaction,
+  CouldntReact,
+  VoteViewerDisabled,
+  CommunityFollowerNotPending,
+  CommunityTransferNotFound,
+  OnlyTextPostsInCommunity,
+  OnlyLinkPostsInCommunity,
+  NoImagePostsInCommunity,
+  InvalidSlowModeInterval,
+  SlowModeActive(i64),
+  InvalidContestModeDuration,
+  BlockedUrl,
+  InvalidBlockedUrl,
+  CouldntCreateBlockedUrl,
+  CouldntUpdateBlockedUrl,
+  CouldntCreateWordFilter,
+  CommunityWordFilterMatch,
+  CouldntFindScheduledJob,
+  CouldntUpdateScheduledJob,
+  InvalidScheduledJobInterval,
+  /// The trending sort is only available for communities.
+  InvalidSortType,
+  ScheduledJobDisabled,
+  ImageUploadQuotaExceeded,
+  UnsupportedImageFormat,
+  CantRefreshLocalObject,
+  DuplicateActivity,
+  CantRotateRemoteKeys,
+  CouldntCreateBlocklistSubscription,
+  EmptyBlocklist,
+  InvalidIntegrityProof,
+  CouldntCreateCategory,
+  CouldntUpdateCategory,
+  InvalidCategoryName,
+  TooManyCategories,
+  InvalidUnixTime,
+  SpamDetected,
+  InvalidEmailDomain,
+  EmailDomainNotAllowed,
+  EmailDomainBlocked,
+  CouldntUpdateEmailDomains,
+  DisposableEmail,
+  CaptchaNotConfigured,
+  CouldntUpdateCaptchaSecret,
+  InvalidRateLimit,
+  CouldntUpdateRateLimits,
+  InvalidNotificationInterval,
+  CouldntUpdateNotification,
+  CouldntSnoozeCommunity,
+  CouldntFindComment,
+  InvalidAudioDuration,
+  CouldntCreateSlurFilter,
+  InvalidReportReason,
+  CouldntCreateReportReason,
+  InvalidStrikeBanSettings,
+  InvalidCommunityRule,
+  CouldntCreateCommunityRule,
+  CouldntUpdateCommunityRule,
+  TooManyObjectsToResolve,
+  CantReplaySensitiveActivity,
+  CouldntFindActivity,
+  CouldntReplayActivity,
+  InvalidPostingThresholds,
+  /// The account must be at least this many days old to post in the community.
+  AccountTooNewForCommunity(i32),
+  /// The user needs at least this much site-wide karma to post in the community.
+  NotEnoughSiteKarma(i32),
+  /// The user needs at least this much karma within the community to post in it.
+  NotEnoughCommunityKarma(i32),
+  InvalidPersonNote,
+  BreachedPassword,
+  CouldntFindLoginSession,
+  CouldntFindCommunityExport,
+  CommunityExportNotFinished,
+  InvalidCommunityArchive,
+  CouldntFindCommunityImport,
+  /// The import stopped making progress, usually because the server was restarted.
+  CommunityImportInterrupted,
+  /// The invite code is missing, unknown, expired or used up.
+  InvalidInviteCode,
+  OnlyAdminsCanCreateInvites,
+  InvalidInviteMaxUses,
+  /// The expiry of the invite is in the past, or missing or too late for an invite of a user.
+  InvalidInviteExpires,
+  CouldntFindInvite,
+  /// The account must be at least this many days old to create a community.
+  AccountTooNewToCreateCommunity(i32),
+  /// The user needs at least this much site-wide karma to create a community.
+  NotEnoughKarmaToCreateCommunity(i32),
+  /// New communities have to be applied for with an application, which an admin approves.
+  CommunityCreationRequiresApproval,
+  CouldntFindCommunityApplication,
+  CommunityApplicationAlreadyDecided,
+  InvalidUsernameChangeSettings,
+  /// The user already changed their username as often as allowed.
+  UsernameChangeLimitReached,
+  /// The username was used by someone else recently, and can't be taken yet.
+  UsernameRecentlyUsed,
+  /// Only two different local accounts can be merged.
+  InvalidAccountMerge,
+  /// Too many login links were requested recently.
+  MagicLinkLimitReached,
+  /// The login link is unknown, expired or was already used.
+  InvalidMagicLink,
   Unknown(String),
 }
 
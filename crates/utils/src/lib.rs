@@ -9,10 +9,11 @@ pub mod email;
 pub mod rate_limit;
 pub mod settings;
 
-pub mod claims;
 pub mod error;
+pub mod password;
 pub mod request;
 pub mod response;
+pub mod session;
 pub mod utils;
 pub mod version;
 
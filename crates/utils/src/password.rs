@@ -0,0 +1,104 @@
+use crate::{
+  error::LemmyResult,
+  settings::{structs::PasswordHashingConfig, SETTINGS},
+};
+use argon2::{
+  password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+  Algorithm,
+  Argon2,
+  Params,
+  Version,
+};
+
+/// Prefix of the bcrypt hashes which were used before Argon2id. These can't be created anymore,
+/// only verified.
+pub const LEGACY_HASH_PREFIX: &str = "$2";
+
+/// Hashes a password with Argon2id, using the parameters from the config.
+pub fn hash_password(password: &str) -> LemmyResult<String> {
+  hash_password_with(password, &SETTINGS.password_hashing)
+}
+
+/// Checks a password against its stored hash, which may be a legacy bcrypt hash.
+pub fn verify_password(password: &str, hash: &str) -> bool {
+  if hash.starts_with(LEGACY_HASH_PREFIX) {
+    return bcrypt::verify(password, hash).unwrap_or(false);
+  }
+  PasswordHash::new(hash)
+    .and_then(|hash| Argon2::default().verify_password(password.as_bytes(), &hash))
+    .is_ok()
+}
+
+/// Whether the hash should be replaced after the next successful login, because it was created
+/// with bcrypt or with other parameters than the configured ones.
+pub fn password_needs_rehash(hash: &str) -> bool {
+  needs_rehash_with(hash, &SETTINGS.password_hashing)
+}
+
+fn hash_password_with(password: &str, config: &PasswordHashingConfig) -> LemmyResult<String> {
+  let salt = SaltString::generate(&mut OsRng);
+  let hash = argon2(config)?.hash_password(password.as_bytes(), &salt)?;
+  Ok(hash.to_string())
+}
+
+fn needs_rehash_with(hash: &str, config: &PasswordHashingConfig) -> bool {
+  if hash.starts_with(LEGACY_HASH_PREFIX) {
+    return true;
+  }
+  let Ok(hash) = PasswordHash::new(hash) else {
+    return true;
+  };
+  let Ok(params) = Params::try_from(&hash) else {
+    return true;
+  };
+  hash.algorithm != Algorithm::Argon2id.ident()
+    || hash.version != Some(Version::V0x13.into())
+    || params.m_cost() != config.memory_cost_kib
+    || params.t_cost() != config.time_cost
+    || params.p_cost() != config.parallelism
+}
+
+fn argon2(config: &PasswordHashingConfig) -> LemmyResult<Argon2<'static>> {
+  let params = Params::new(
+    config.memory_cost_kib,
+    config.time_cost,
+    config.parallelism,
+    None,
+  )?;
+  Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+
+  use crate::{
+    password::{hash_password_with, needs_rehash_with, verify_password},
+    settings::structs::PasswordHashingConfig,
+  };
+
+  #[test]
+  fn test_hash_password() {
+    let config = PasswordHashingConfig::default();
+    let hash = hash_password_with("hunter22", &config).unwrap();
+    assert!(hash.starts_with("$argon2id$"));
+    assert!(verify_password("hunter22", &hash));
+    assert!(!verify_password("hunter23", &hash));
+    assert!(!needs_rehash_with(&hash, &config));
+
+    let stronger = PasswordHashingConfig {
+      time_cost: config.time_cost + 1,
+      ..config
+    };
+    assert!(needs_rehash_with(&hash, &stronger));
+  }
+
+  #[test]
+  fn test_legacy_bcrypt_hash() {
+    let config = PasswordHashingConfig::default();
+    let hash = bcrypt::hash("hunter22", 4).unwrap();
+    assert!(verify_password("hunter22", &hash));
+    assert!(!verify_password("hunter23", &hash));
+    assert!(needs_rehash_with(&hash, &config));
+  }
+}
@@ -1,16 +1,23 @@
-use crate::error::{LemmyError, LemmyErrorType};
-use actix_web::dev::{ConnectionInfo, Service, ServiceRequest, ServiceResponse, Transform};
+use crate::{
+  error::{LemmyError, LemmyErrorType},
+  session::hash_session_token,
+};
+use actix_web::{
+  dev::{ConnectionInfo, Service, ServiceRequest, ServiceResponse, Transform},
+  web::Query,
+};
 use enum_map::enum_map;
 use futures::future::{ok, Ready};
 use rate_limiter::{InstantSecs, RateLimitStorage, RateLimitType};
 use serde::{Deserialize, Serialize};
 use std::{
+  collections::HashMap,
   future::Future,
   net::{IpAddr, Ipv4Addr, SocketAddr},
   pin::Pin,
   rc::Rc,
   str::FromStr,
-  sync::{Arc, Mutex},
+  sync::{Arc, Mutex, RwLock},
   task::{Context, Poll},
   time::Duration,
 };
@@ -65,9 +72,25 @@ struct RateLimit {
   pub rate_limit_config: RateLimitConfig,
 }
 
+/// The individual rate limit of a local user. Requests of the user are counted in their own
+/// buckets, no matter which IP address they come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserRateLimit {
+  pub local_user_id: i32,
+  /// The limit in percent of the site rate limits
+  pub limit_percent: i32,
+}
+
+/// Individual rate limits of users, keyed by the hashes of their login tokens.
+#[derive(Debug, Default)]
+struct UserRateLimits {
+  by_token_hash: HashMap<String, UserRateLimit>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RateLimitedGuard {
   rate_limit: Arc<Mutex<RateLimit>>,
+  user_rate_limits: Arc<RwLock<UserRateLimits>>,
   type_: RateLimitType,
 }
 
@@ -76,6 +99,7 @@ pub struct RateLimitedGuard {
 pub struct RateLimitCell {
   tx: Sender<RateLimitConfig>,
   rate_limit: Arc<Mutex<RateLimit>>,
+  user_rate_limits: Arc<RwLock<UserRateLimits>>,
 }
 
 impl RateLimitCell {
@@ -98,7 +122,11 @@ impl RateLimitCell {
               .rate_limit_config = r;
           }
         });
-        RateLimitCell { tx, rate_limit }
+        RateLimitCell {
+          tx,
+          rate_limit,
+          user_rate_limits: Default::default(),
+        }
       })
       .await
   }
@@ -109,6 +137,25 @@ impl RateLimitCell {
     Ok(())
   }
 
+  /// Replaces the individual rate limits of users, keyed by the hashes of their login tokens.
+  pub fn set_user_rate_limits(&self, by_token_hash: HashMap<String, UserRateLimit>) {
+    let mut guard = self
+      .user_rate_limits
+      .write()
+      .expect("Failed to lock user rate limits for updating");
+    *guard = UserRateLimits { by_token_hash };
+  }
+
+  /// Sets the individual rate limit for a single login token, eg after the user logged in.
+  pub fn set_user_rate_limit(&self, token_hash: String, user_rate_limit: UserRateLimit) {
+    self
+      .user_rate_limits
+      .write()
+      .expect("Failed to lock user rate limits for updating")
+      .by_token_hash
+      .insert(token_hash, user_rate_limit);
+  }
+
   /// Remove buckets older than the given duration
   pub fn remove_older_than(&self, mut duration: Duration) {
     let mut guard = self
@@ -165,6 +212,7 @@ impl RateLimitCell {
   fn kind(&self, type_: RateLimitType) -> RateLimitedGuard {
     RateLimitedGuard {
       rate_limit: self.rate_limit.clone(),
+      user_rate_limits: self.user_rate_limits.clone(),
       type_,
     }
   }
@@ -178,6 +226,12 @@ pub struct RateLimitedMiddleware<S> {
 impl RateLimitedGuard {
   /// Returns true if the request passed the rate limit, false if it failed and should be rejected.
   pub fn check(self, ip_addr: IpAddr) -> bool {
+    self.check_for_user(ip_addr, None)
+  }
+
+  /// Like [RateLimitedGuard::check], but users with an individual rate limit are checked against
+  /// their own buckets instead of those of their IP address.
+  fn check_for_user(self, ip_addr: IpAddr, user_rate_limit: Option<UserRateLimit>) -> bool {
     // Does not need to be blocking because the RwLock in settings never held across await points,
     // and the operation here locks only long enough to clone
     let mut guard = self
@@ -195,9 +249,55 @@ impl RateLimitedGuard {
       RateLimitType::Search => (rate_limit.search, rate_limit.search_per_second),
     };
     let limiter = &mut guard.rate_limiter;
+    let now = InstantSecs::now();
+
+    match user_rate_limit {
+      Some(user) => limiter.check_rate_limit_user(
+        self.type_,
+        user.local_user_id,
+        scale_rate_limit(kind, user.limit_percent),
+        interval,
+        now,
+      ),
+      None => limiter.check_rate_limit_full(self.type_, ip_addr, kind, interval, now),
+    }
+  }
 
-    limiter.check_rate_limit_full(self.type_, ip_addr, kind, interval, InstantSecs::now())
+  /// Returns the individual rate limit of the user who sent the request, if there is one.
+  fn user_rate_limit(&self, req: &ServiceRequest) -> Option<UserRateLimit> {
+    let user_rate_limits = self
+      .user_rate_limits
+      .read()
+      .expect("Failed to lock user rate limits for reading");
+    if user_rate_limits.by_token_hash.is_empty() {
+      return None;
+    }
+    let auth = request_auth(req)?;
+    user_rate_limits
+      .by_token_hash
+      .get(&hash_session_token(&auth))
+      .copied()
+  }
+}
+
+fn scale_rate_limit(limit: i32, percent: i32) -> i32 {
+  let scaled = i64::from(limit) * i64::from(percent) / 100;
+  i32::try_from(scaled).unwrap_or(i32::MAX)
+}
+
+#[derive(Deserialize)]
+struct AuthParam {
+  auth: Option<String>,
+}
+
+/// Reads the auth token from the query string, or from the `jwt` cookie which is also used for
+/// image uploads. Request bodies aren't read, so requests which only have the token in their json
+/// body are rate limited by IP address.
+fn request_auth(req: &ServiceRequest) -> Option<String> {
+  if let Ok(Query(AuthParam { auth: Some(auth) })) = Query::from_query(req.query_string()) {
+    return Some(auth);
   }
+  req.cookie("jwt").map(|c| c.value().to_string())
 }
 
 impl<S> Transform<S, ServiceRequest> for RateLimitedGuard
@@ -236,12 +336,13 @@ where
 
   fn call(&self, req: ServiceRequest) -> Self::Future {
     let ip_addr = get_ip(&req.connection_info());
+    let user_rate_limit = self.rate_limited.user_rate_limit(&req);
 
     let rate_limited = self.rate_limited.clone();
     let service = self.service.clone();
 
     Box::pin(async move {
-      if rate_limited.check(ip_addr) {
+      if rate_limited.check_for_user(ip_addr, user_rate_limit) {
         service.call(req).await
       } else {
         let (http_req, _) = req.into_parts();
@@ -278,6 +379,35 @@ mod tests {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
+  #[test]
+  fn test_scale_rate_limit() {
+    assert_eq!(6, super::scale_rate_limit(6, 100));
+    assert_eq!(30, super::scale_rate_limit(6, 500));
+    assert_eq!(3, super::scale_rate_limit(6, 50));
+    assert_eq!(0, super::scale_rate_limit(6, 0));
+    assert_eq!(i32::MAX, super::scale_rate_limit(i32::MAX, 200));
+  }
+
+  #[test]
+  fn test_request_auth() {
+    use actix_web::{cookie::Cookie, test::TestRequest};
+
+    let req = TestRequest::get()
+      .uri("/api/v3/user?auth=query_token")
+      .to_srv_request();
+    assert_eq!(Some("query_token".to_string()), super::request_auth(&req));
+
+    let req = TestRequest::post()
+      .cookie(Cookie::new("jwt", "cookie_token"))
+      .to_srv_request();
+    assert_eq!(Some("cookie_token".to_string()), super::request_auth(&req));
+
+    let req = TestRequest::post()
+      .set_payload(r#"{"auth":"body_token"}"#)
+      .to_srv_request();
+    assert_eq!(None, super::request_auth(&req));
+  }
+
   #[test]
   fn test_parse_ip() {
     let ip_addrs = [
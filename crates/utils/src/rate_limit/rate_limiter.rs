@@ -125,13 +125,15 @@ impl<C: Default> RateLimitedGroup<C> {
   }
 }
 
-/// Rate limiting based on rate type and IP addr
+/// Rate limiting based on rate type and IP addr, or local user for users with individual limits
 #[derive(PartialEq, Debug, Clone, Default)]
 pub struct RateLimitStorage {
   /// One bucket per individual IPv4 address
   ipv4_buckets: Map<Ipv4Addr, ()>,
   /// Seperate buckets for 48, 56, and 64 bit prefixes of IPv6 addresses
   ipv6_buckets: Map<[u8; 6], Map<u8, Map<u8, ()>>>,
+  /// One bucket per local user id, for users with individual rate limits
+  user_buckets: Map<i32, ()>,
 }
 
 impl RateLimitStorage {
@@ -193,6 +195,29 @@ impl RateLimitStorage {
     result
   }
 
+  /// Like [RateLimitStorage::check_rate_limit_full], but checks the bucket of a local user instead
+  /// of an IP address.
+  pub(super) fn check_rate_limit_user(
+    &mut self,
+    type_: RateLimitType,
+    local_user_id: i32,
+    capacity: i32,
+    secs_to_refill: i32,
+    now: InstantSecs,
+  ) -> bool {
+    let group = self
+      .user_buckets
+      .entry(local_user_id)
+      .or_insert(RateLimitedGroup::new(now));
+    let result = group.check_total(type_, now, capacity, secs_to_refill);
+
+    if !result {
+      debug!("Rate limited local user: {local_user_id}");
+    }
+
+    result
+  }
+
   /// Remove buckets older than the given duration
   pub(super) fn remove_older_than(&mut self, duration: Duration, now: InstantSecs) {
     // Only retain buckets that were last used after `instant`
@@ -208,6 +233,7 @@ impl RateLimitStorage {
     };
 
     retain_and_shrink(&mut self.ipv4_buckets, |_, group| is_recently_used(group));
+    retain_and_shrink(&mut self.user_buckets, |_, group| is_recently_used(group));
 
     retain_and_shrink(&mut self.ipv6_buckets, |_, group_48| {
       retain_and_shrink(&mut group_48.children, |_, group_56| {
@@ -320,12 +346,26 @@ mod tests {
           }
         ),]
         .into(),
+        user_buckets: Default::default(),
       }
     );
 
+    // Users with individual limits have their own buckets, which don't use up those of their IP
+    for _ in 0..3 {
+      assert!(rate_limiter.check_rate_limit_user(super::RateLimitType::Message, 7, 3, 1, now));
+    }
+    assert!(!rate_limiter.check_rate_limit_user(super::RateLimitType::Message, 7, 3, 1, now));
+    assert!(rate_limiter.check_rate_limit_user(super::RateLimitType::Message, 8, 3, 1, now));
+    assert_eq!(2, rate_limiter.user_buckets.len());
+    assert_eq!(
+      bottom_group(1.0),
+      rate_limiter.ipv4_buckets[&[123, 123, 123, 123].into()]
+    );
+
     now.secs += 2;
     rate_limiter.remove_older_than(std::time::Duration::from_secs(1), now);
     assert!(rate_limiter.ipv4_buckets.is_empty());
     assert!(rate_limiter.ipv6_buckets.is_empty());
+    assert!(rate_limiter.user_buckets.is_empty());
   }
 }
@@ -0,0 +1,44 @@
+use crate::{error::LemmyResult, settings::structs::Settings};
+use chrono::{Duration, NaiveDateTime, Utc};
+use openssl::{rand::rand_bytes, sha::sha256};
+
+/// Generates a new opaque login token. It is only handed to the client, the server keeps its hash.
+pub fn generate_session_token() -> LemmyResult<String> {
+  let mut bytes = [0; 32];
+  rand_bytes(&mut bytes)?;
+  Ok(to_hex(&bytes))
+}
+
+/// The hash under which a login token is stored, so that leaked database contents can't be used
+/// to log in.
+pub fn hash_session_token(token: &str) -> String {
+  to_hex(&sha256(token.as_bytes()))
+}
+
+/// When a login session which is used now ends, unless it is used again before.
+pub fn login_session_expires(settings: &Settings) -> NaiveDateTime {
+  Utc::now().naive_utc() + Duration::days(settings.login_session_days.into())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+
+  use crate::session::{generate_session_token, hash_session_token};
+
+  #[test]
+  fn test_session_token() {
+    let token = generate_session_token().unwrap();
+    assert_eq!(64, token.len());
+    assert_ne!(token, generate_session_token().unwrap());
+
+    let hash = hash_session_token(&token);
+    assert_eq!(64, hash.len());
+    assert_ne!(token, hash);
+    assert_eq!(hash, hash_session_token(&token));
+  }
+}
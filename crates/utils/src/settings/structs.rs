@@ -45,10 +45,68 @@ pub struct Settings {
   /// The number of activitypub federation retry workers that can be in-flight concurrently
   #[default(0)]
   pub retry_count: usize,
+  /// The number of incoming activities which are processed concurrently. Activities from the same
+  /// actor or community are always processed one after another, in the order they arrive.
+  #[default(100)]
+  pub inbox_worker_count: usize,
+  /// Number of days for which sent and received activities are kept in the database. Sent
+  /// activities are kept for at least 3 days regardless, because failed deliveries are retried for
+  /// that long.
+  #[default(90)]
+  pub activity_retention_days: u32,
+  /// Number of days after which the title, body and link of posts and comments deleted by their
+  /// creator are overwritten, and their uploaded images purged.
+  #[default(30)]
+  pub deleted_content_retention_days: u32,
+  /// Maximum number of posts fetched from the outbox of a remote community, when it gets its first
+  /// local follower. Set to 0 to disable.
+  #[default(50)]
+  pub outbox_backfill_limit: usize,
+  /// Number of items per page of the outbox and followers collections of local communities. At
+  /// most 50.
+  #[default(20)]
+  pub collection_page_size: u32,
+  /// Keypairs of local users, communities and the instance actor are replaced after this many
+  /// days. Set to 0 to disable.
+  #[default(0)]
+  pub key_rotation_days: u32,
+  /// Login sessions end after they weren't used for this many days.
+  #[default(90)]
+  pub login_session_days: u32,
   // Prometheus configuration.
   #[default(None)]
   #[doku(example = "Some(Default::default())")]
   pub prometheus: Option<PrometheusConfig>,
+  /// Fetch trust signals for federated instances from Fediseer. Disabled if not set.
+  #[default(None)]
+  #[doku(example = "Some(Default::default())")]
+  pub fediseer: Option<FediseerConfig>,
+  /// Score new registrations, posts, comments and private messages of local users for spam.
+  /// Disabled if not set.
+  #[default(None)]
+  #[doku(example = "Some(Default::default())")]
+  pub spam_check: Option<SpamCheckConfig>,
+  /// Detect signups with disposable email addresses. Disabled if not set.
+  #[default(None)]
+  #[doku(example = "Some(Default::default())")]
+  pub disposable_email: Option<DisposableEmailConfig>,
+  /// Which captcha is shown for signups, while captchas are enabled in the site settings
+  #[default(Default::default())]
+  pub captcha: CaptchaConfig,
+  /// Argon2id parameters for password hashes. Existing hashes with other parameters, or legacy
+  /// bcrypt hashes, are replaced when the user logs in.
+  #[default(Default::default())]
+  pub password_hashing: PasswordHashingConfig,
+  /// Where passwords are looked up, while checking for breached passwords is enabled in the site
+  /// settings
+  #[default(Default::default())]
+  pub breached_passwords: BreachedPasswordsConfig,
+  /// Markdown extensions which are rendered in the html of outgoing federated content
+  #[default(Default::default())]
+  pub markdown: MarkdownConfig,
+  /// Html which is kept in incoming federated content, when it is converted to markdown
+  #[default(Default::default())]
+  pub incoming_html: IncomingHtmlConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
@@ -62,6 +120,17 @@ pub struct PictrsConfig {
   /// Set a custom pictrs API key. ( Required for deleting images )
   #[default(None)]
   pub api_key: Option<String>,
+
+  /// Maximum number of images which a single user can upload. Unlimited if not set.
+  #[default(None)]
+  #[doku(example = "1000")]
+  pub max_uploads_per_user: Option<i64>,
+
+  /// Remove EXIF, XMP and similar metadata from uploaded images, as it can contain the location
+  /// where a photo was taken. Uploads in formats which can't be sanitized (anything other than
+  /// jpeg, png, webp and gif) are rejected while this is enabled.
+  #[default(true)]
+  pub strip_image_metadata: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
@@ -73,6 +142,53 @@ pub struct DatabaseConfig {
   /// Maximum number of active sql connections
   #[default(5)]
   pub pool_size: usize,
+
+  /// Maximum number of seconds to wait for a free connection from the pool, or for a new
+  /// connection to be established
+  #[default(5)]
+  pub connection_timeout_seconds: u64,
+
+  /// Queries running longer than this number of seconds are cancelled. Disabled if not set.
+  #[default(None)]
+  #[doku(example = "30")]
+  pub statement_timeout_seconds: Option<u64>,
+
+  /// Separate connection pool for federation (incoming and outgoing activities), so that a busy
+  /// inbox can't starve the API. Unset values fall back to the ones above. If this is not
+  /// specified, federation shares the main pool.
+  #[default(None)]
+  #[doku(example = "Some(Default::default())")]
+  pub federation: Option<DatabasePoolConfig>,
+
+  /// Statement timeout in seconds for the connections of scheduled tasks, which often run
+  /// longer queries than the API. Falls back to `statement_timeout_seconds` if not set.
+  #[default(None)]
+  #[doku(example = "300")]
+  pub scheduler_statement_timeout_seconds: Option<u64>,
+
+  /// Connection URIs of read-only replicas. Post, comment and search listings are spread over
+  /// these, everything else uses the primary database. Each replica gets its own pool of
+  /// `pool_size` connections.
+  #[default(Vec::new())]
+  #[doku(example = "postgresql:///lemmy?user=lemmy&host=/var/run/postgresql-replica")]
+  pub read_replicas: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabasePoolConfig {
+  /// Maximum number of active sql connections
+  #[default(None)]
+  #[doku(example = "5")]
+  pub pool_size: Option<usize>,
+  /// Maximum number of seconds to wait for a connection
+  #[default(None)]
+  #[doku(example = "5")]
+  pub connection_timeout_seconds: Option<u64>,
+  /// Queries running longer than this number of seconds are cancelled
+  #[default(None)]
+  #[doku(example = "30")]
+  pub statement_timeout_seconds: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
@@ -174,3 +290,154 @@ pub struct PrometheusConfig {
   #[doku(example = "10002")]
   pub port: Option<i32>,
 }
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct FediseerConfig {
+  /// Fediseer instance which is queried for guarantees, endorsements and censures
+  #[default(Url::parse("https://fediseer.com").expect("parse fediseer url"))]
+  #[doku(example = "https://fediseer.com")]
+  pub url: Url,
+  /// Instances whose censures are trusted. Only censures given by these instances are shown.
+  #[default(Vec::new())]
+  #[doku(example = "lemmy.example")]
+  pub trusted_instances: Vec<String>,
+  /// Defederate from instances which are censured by any of the trusted instances. The censures
+  /// are synced like a blocklist subscription, so that single entries can be pinned or removed.
+  #[default(false)]
+  #[doku(example = "false")]
+  pub restrict_censured: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpamCheckConfig {
+  /// Content with at least this score is rejected
+  #[default(100)]
+  #[doku(example = "100")]
+  pub reject_threshold: u32,
+  /// Content with at least this score is reported to the admins for review. New users need to
+  /// have their registration approved.
+  #[default(50)]
+  #[doku(example = "50")]
+  pub review_threshold: u32,
+  /// Look up the username and email of new users on StopForumSpam
+  #[default(false)]
+  #[doku(example = "false")]
+  pub stop_forum_spam: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisposableEmailConfig {
+  /// Refuse signups with disposable email addresses. Otherwise they need to have their
+  /// registration approved by an admin.
+  #[default(false)]
+  #[doku(example = "false")]
+  pub reject: bool,
+  /// List of disposable email domains with one domain per line, which is fetched daily. Domains
+  /// from the bundled list are detected in any case.
+  #[default(Some(Url::parse("https://raw.githubusercontent.com/disposable-email-domains/disposable-email-domains/master/disposable_email_blocklist.conf").expect("parse disposable email list url")))]
+  #[doku(
+    example = "https://raw.githubusercontent.com/disposable-email-domains/disposable-email-domains/master/disposable_email_blocklist.conf"
+  )]
+  pub list_url: Option<Url>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct CaptchaConfig {
+  /// The built in captcha, or a hosted captcha service. The secret keys for hosted services are
+  /// set through the admin api.
+  #[default(CaptchaProvider::Builtin)]
+  #[doku(example = "builtin")]
+  pub provider: CaptchaProvider,
+  /// Public site key, which is passed to the captcha widget of hosted services
+  #[default(None)]
+  #[doku(example = "10000000-ffff-ffff-ffff-000000000001")]
+  pub site_key: Option<String>,
+  /// Address of the mCaptcha instance. Only needed for mCaptcha.
+  #[default(None)]
+  #[doku(example = "https://mcaptcha.example.com")]
+  pub mcaptcha_url: Option<Url>,
+  /// Also require users to solve a captcha when creating posts. Admins are exempt.
+  #[default(false)]
+  #[doku(example = "false")]
+  pub require_for_posts: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Document)]
+#[serde(rename_all = "lowercase")]
+/// A provider of captchas.
+pub enum CaptchaProvider {
+  Builtin,
+  HCaptcha,
+  MCaptcha,
+  Turnstile,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct PasswordHashingConfig {
+  /// Memory used for each hash, in KiB
+  #[default(19456)]
+  #[doku(example = "19456")]
+  pub memory_cost_kib: u32,
+  /// Number of passes over the memory
+  #[default(2)]
+  #[doku(example = "2")]
+  pub time_cost: u32,
+  /// Number of lanes which are computed in parallel
+  #[default(1)]
+  #[doku(example = "1")]
+  pub parallelism: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct BreachedPasswordsConfig {
+  /// HaveIBeenPwned compatible range API. Only the first five characters of the SHA-1 hash of a
+  /// password are sent.
+  #[default(Some(Url::parse("https://api.pwnedpasswords.com").expect("parse pwned passwords url")))]
+  #[doku(example = "https://api.pwnedpasswords.com")]
+  pub api_url: Option<Url>,
+  /// Bloom filter over the SHA-1 hashes of breached passwords, which is used when the API is not
+  /// set or can't be reached. The file is the raw bit array of the filter.
+  #[default(None)]
+  #[doku(example = "/var/lib/lemmy/breached_passwords.bloom")]
+  pub bloom_filter_file: Option<String>,
+  /// Number of bits which are set in the bloom filter for each password
+  #[default(7)]
+  #[doku(example = "7")]
+  pub bloom_filter_hashes: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarkdownConfig {
+  /// Render `::: spoiler title` blocks as collapsible details
+  #[default(true)]
+  #[doku(example = "true")]
+  pub spoilers: bool,
+  /// Render `^text^` as superscript
+  #[default(true)]
+  #[doku(example = "true")]
+  pub superscript: bool,
+  /// Render `[^label]` references and `[^label]: text` definitions as footnotes
+  #[default(true)]
+  #[doku(example = "true")]
+  pub footnotes: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct IncomingHtmlConfig {
+  /// Tags which are kept. Other tags are removed while keeping their text, except for scripts and
+  /// styles which are always removed entirely.
+  #[default(vec![
+    "a", "p", "br", "ul", "ol", "li", "pre", "code", "blockquote", "em", "strong", "b", "i", "del",
+    "s", "h1", "h2", "h3", "h4", "h5", "h6", "hr", "img",
+  ].into_iter().map(ToString::to_string).collect())]
+  #[doku(example = "a")]
+  pub allowed_tags: Vec<String>,
+}
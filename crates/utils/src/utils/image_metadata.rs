@@ -0,0 +1,429 @@
+use crate::error::{LemmyErrorType, LemmyResult};
+
+/// Image formats from which metadata can be reliably removed. Uploads in any other format are
+/// rejected when metadata stripping is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+  Jpeg,
+  Png,
+  Webp,
+  Gif,
+}
+
+impl ImageFormat {
+  /// Detects the format from the magic bytes at the start of the file
+  pub fn detect(data: &[u8]) -> Option<Self> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+      Some(ImageFormat::Jpeg)
+    } else if data.starts_with(PNG_SIGNATURE) {
+      Some(ImageFormat::Png)
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+      Some(ImageFormat::Webp)
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+      Some(ImageFormat::Gif)
+    } else {
+      None
+    }
+  }
+
+  pub fn mime_type(&self) -> &'static str {
+    match self {
+      ImageFormat::Jpeg => "image/jpeg",
+      ImageFormat::Png => "image/png",
+      ImageFormat::Webp => "image/webp",
+      ImageFormat::Gif => "image/gif",
+    }
+  }
+}
+
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// Removes EXIF, XMP, IPTC and comment metadata from an image, as these can contain the location
+/// where a photo was taken or other information about the uploader. Pixel data and color profiles
+/// are kept unchanged.
+///
+/// Fails if the format isn't supported or the file is malformed.
+pub fn strip_image_metadata(data: &[u8]) -> LemmyResult<Vec<u8>> {
+  let stripped = match ImageFormat::detect(data) {
+    Some(ImageFormat::Jpeg) => strip_jpeg(data),
+    Some(ImageFormat::Png) => strip_png(data),
+    Some(ImageFormat::Webp) => strip_webp(data),
+    Some(ImageFormat::Gif) => strip_gif(data),
+    None => None,
+  };
+  stripped.ok_or_else(|| LemmyErrorType::UnsupportedImageFormat.into())
+}
+
+/// Reads big or little endian numbers and byte ranges, returning `None` past the end of the data.
+struct Reader<'a> {
+  data: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  fn new(data: &'a [u8], pos: usize) -> Self {
+    Reader { data, pos }
+  }
+
+  fn is_empty(&self) -> bool {
+    self.pos >= self.data.len()
+  }
+
+  fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+    let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+    self.pos += len;
+    Some(bytes)
+  }
+
+  fn u8(&mut self) -> Option<u8> {
+    self.bytes(1)?.first().copied()
+  }
+
+  fn u16_be(&mut self) -> Option<u16> {
+    Some(u16::from_be_bytes(self.bytes(2)?.try_into().ok()?))
+  }
+
+  fn u32_be(&mut self) -> Option<u32> {
+    Some(u32::from_be_bytes(self.bytes(4)?.try_into().ok()?))
+  }
+
+  fn u32_le(&mut self) -> Option<u32> {
+    Some(u32::from_le_bytes(self.bytes(4)?.try_into().ok()?))
+  }
+}
+
+/// Removes APPn segments other than JFIF, ICC profiles and Adobe color info, as well as comments.
+/// Anything after the end of the image is dropped, as some cameras append further images there
+/// which carry their own metadata.
+fn strip_jpeg(data: &[u8]) -> Option<Vec<u8>> {
+  const SOI: u8 = 0xD8;
+  const EOI: u8 = 0xD9;
+  const SOS: u8 = 0xDA;
+  const APP0: u8 = 0xE0;
+  const APP2: u8 = 0xE2;
+  const APP14: u8 = 0xEE;
+  const APP15: u8 = 0xEF;
+  const COM: u8 = 0xFE;
+
+  let mut reader = Reader::new(data, 0);
+  if reader.bytes(2)? != [0xFF, SOI] {
+    return None;
+  }
+  let mut out = vec![0xFF, SOI];
+  loop {
+    if reader.u8()? != 0xFF {
+      return None;
+    }
+    let mut marker = reader.u8()?;
+    // Markers may be preceded by any number of fill bytes
+    while marker == 0xFF {
+      marker = reader.u8()?;
+    }
+    if marker == EOI {
+      out.extend_from_slice(&[0xFF, EOI]);
+      return Some(out);
+    }
+    // Standalone markers without a length
+    if (0xD0..=0xD7).contains(&marker) || marker == 0x01 {
+      out.extend_from_slice(&[0xFF, marker]);
+      continue;
+    }
+    let len = usize::from(reader.u16_be()?);
+    let payload = reader.bytes(len.checked_sub(2)?)?;
+    let keep = match marker {
+      APP0 => payload.starts_with(b"JFIF\0") || payload.starts_with(b"JFXX\0"),
+      APP2 => payload.starts_with(b"ICC_PROFILE\0"),
+      APP14 => payload.starts_with(b"Adobe"),
+      0xE1..=APP15 | COM => false,
+      _ => true,
+    };
+    if keep {
+      out.extend_from_slice(&[0xFF, marker]);
+      out.extend_from_slice(&u16::try_from(len).ok()?.to_be_bytes());
+      out.extend_from_slice(payload);
+    }
+    if marker == SOS {
+      // Copy the entropy coded data up to the next marker. Inside it, 0xFF is only followed by a
+      // stuffed zero byte or a restart marker.
+      let scan_len = data
+        .get(reader.pos..)?
+        .windows(2)
+        .position(|w| matches!(w, [0xFF, next] if *next != 0 && !(0xD0..=0xD7).contains(next)))?;
+      out.extend_from_slice(reader.bytes(scan_len)?);
+    }
+  }
+}
+
+/// Removes EXIF, text and timestamp chunks.
+fn strip_png(data: &[u8]) -> Option<Vec<u8>> {
+  const REMOVED_CHUNKS: &[&[u8]] = &[b"eXIf", b"tEXt", b"zTXt", b"iTXt", b"tIME"];
+
+  let mut reader = Reader::new(data, PNG_SIGNATURE.len());
+  let mut out = PNG_SIGNATURE.to_vec();
+  loop {
+    let chunk_start = reader.pos;
+    let len = usize::try_from(reader.u32_be()?).ok()?;
+    let chunk_type = reader.bytes(4)?;
+    // Chunk data and CRC
+    reader.bytes(len.checked_add(4)?)?;
+    if !REMOVED_CHUNKS.contains(&chunk_type) {
+      out.extend_from_slice(data.get(chunk_start..reader.pos)?);
+    }
+    if chunk_type == b"IEND" {
+      return Some(out);
+    }
+  }
+}
+
+/// Removes EXIF and XMP chunks, and clears the corresponding flags in the extended header.
+fn strip_webp(data: &[u8]) -> Option<Vec<u8>> {
+  const EXIF_FLAG: u8 = 0x08;
+  const XMP_FLAG: u8 = 0x04;
+
+  let mut reader = Reader::new(data, 4);
+  let riff_len = usize::try_from(reader.u32_le()?).ok()?;
+  let end = riff_len.checked_add(8)?;
+  let mut reader = Reader::new(data.get(..end)?, 12);
+  let mut chunks = Vec::new();
+  while !reader.is_empty() {
+    let fourcc = reader.bytes(4)?;
+    let len = usize::try_from(reader.u32_le()?).ok()?;
+    let payload = reader.bytes(len)?;
+    // Chunks are padded to an even length
+    let padding = reader.bytes(len % 2)?;
+    if fourcc == b"EXIF" || fourcc == b"XMP " {
+      continue;
+    }
+    let len = u32::try_from(len).ok()?;
+    let mut chunk = [fourcc, &len.to_le_bytes(), payload, padding].concat();
+    if fourcc == b"VP8X" {
+      let flags = chunk.get_mut(8)?;
+      *flags &= !(EXIF_FLAG | XMP_FLAG);
+    }
+    chunks.push(chunk);
+  }
+
+  let body = chunks.concat();
+  let riff_len = u32::try_from(body.len().checked_add(4)?).ok()?;
+  Some([b"RIFF", &riff_len.to_le_bytes()[..], b"WEBP", &body].concat())
+}
+
+/// Removes comments and application extensions other than the ones controlling animation.
+fn strip_gif(data: &[u8]) -> Option<Vec<u8>> {
+  const EXTENSION: u8 = 0x21;
+  const IMAGE: u8 = 0x2C;
+  const TRAILER: u8 = 0x3B;
+  const APPLICATION: u8 = 0xFF;
+  const COMMENT: u8 = 0xFE;
+  const ANIMATION_APPLICATIONS: &[&[u8]] = &[b"NETSCAPE2.0", b"ANIMEXTS1.0"];
+
+  /// Size of the color table which follows a descriptor with the given flags
+  fn color_table_len(flags: u8) -> usize {
+    if flags & 0x80 != 0 {
+      3 << ((flags & 0x07) + 1)
+    } else {
+      0
+    }
+  }
+
+  /// Skips over a sequence of data sub-blocks, ending with an empty block
+  fn skip_sub_blocks(reader: &mut Reader) -> Option<()> {
+    loop {
+      let len = reader.u8()?;
+      if len == 0 {
+        return Some(());
+      }
+      reader.bytes(usize::from(len))?;
+    }
+  }
+
+  let mut reader = Reader::new(data, 0);
+  // Header and logical screen descriptor
+  reader.bytes(10)?;
+  let flags = reader.u8()?;
+  reader.bytes(2)?;
+  reader.bytes(color_table_len(flags))?;
+  let mut out = data.get(..reader.pos)?.to_vec();
+
+  loop {
+    let block_start = reader.pos;
+    match reader.u8()? {
+      EXTENSION => {
+        let label = reader.u8()?;
+        let keep = match label {
+          COMMENT => false,
+          APPLICATION => {
+            let identifier_len = usize::from(reader.u8()?);
+            // The identifier is the first data sub-block
+            let identifier = reader.bytes(identifier_len)?;
+            ANIMATION_APPLICATIONS.contains(&identifier)
+          }
+          _ => true,
+        };
+        skip_sub_blocks(&mut reader)?;
+        if keep {
+          out.extend_from_slice(data.get(block_start..reader.pos)?);
+        }
+      }
+      IMAGE => {
+        reader.bytes(8)?;
+        let flags = reader.u8()?;
+        reader.bytes(color_table_len(flags))?;
+        // LZW minimum code size
+        reader.u8()?;
+        skip_sub_blocks(&mut reader)?;
+        out.extend_from_slice(data.get(block_start..reader.pos)?);
+      }
+      TRAILER => {
+        out.push(TRAILER);
+        return Some(out);
+      }
+      _ => return None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #![allow(clippy::unwrap_used)]
+  #![allow(clippy::indexing_slicing)]
+
+  use crate::{
+    error::LemmyErrorType,
+    utils::image_metadata::{strip_image_metadata, ImageFormat, PNG_SIGNATURE},
+  };
+
+  fn jpeg_segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+    let len = u16::try_from(payload.len() + 2).unwrap();
+    [&[0xFF, marker], &len.to_be_bytes()[..], payload].concat()
+  }
+
+  fn png_chunk(chunk_type: &[u8], payload: &[u8]) -> Vec<u8> {
+    let len = u32::try_from(payload.len()).unwrap();
+    // The CRC isn't checked
+    [&len.to_be_bytes()[..], chunk_type, payload, &[0; 4]].concat()
+  }
+
+  fn webp_chunk(fourcc: &[u8], payload: &[u8]) -> Vec<u8> {
+    let len = u32::try_from(payload.len()).unwrap();
+    let padding: &[u8] = if payload.len() % 2 == 1 { &[0] } else { &[] };
+    [fourcc, &len.to_le_bytes()[..], payload, padding].concat()
+  }
+
+  fn webp(chunks: &[Vec<u8>]) -> Vec<u8> {
+    let body = chunks.concat();
+    let len = u32::try_from(body.len() + 4).unwrap();
+    [b"RIFF", &len.to_le_bytes()[..], b"WEBP", &body].concat()
+  }
+
+  fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+  }
+
+  #[test]
+  fn test_strip_jpeg() {
+    let jfif = jpeg_segment(0xE0, b"JFIF\0\x01\x01");
+    let icc = jpeg_segment(0xE2, b"ICC_PROFILE\0profile");
+    let quantization = jpeg_segment(0xDB, &[0; 65]);
+    let scan = [
+      jpeg_segment(0xDA, &[1, 2, 3]),
+      vec![0x12, 0xFF, 0x00, 0xFF, 0xD0, 0x34],
+    ]
+    .concat();
+    let end = vec![0xFF, 0xD9];
+    let image = [
+      vec![0xFF, 0xD8],
+      jfif.clone(),
+      jpeg_segment(0xE1, b"Exif\0\0GPS-LOCATION"),
+      jpeg_segment(0xE1, b"http://ns.adobe.com/xap/1.0/\0<xmp/>"),
+      icc.clone(),
+      jpeg_segment(0xED, b"Photoshop 3.0\0IPTC"),
+      jpeg_segment(0xFE, b"a comment"),
+      quantization.clone(),
+      scan.clone(),
+      end.clone(),
+      // A second image appended by the camera
+      vec![0xFF, 0xD8],
+      jpeg_segment(0xE1, b"Exif\0\0GPS-LOCATION"),
+    ]
+    .concat();
+
+    let stripped = strip_image_metadata(&image).unwrap();
+    let expected = [vec![0xFF, 0xD8], jfif, icc, quantization, scan, end].concat();
+    assert_eq!(expected, stripped);
+    assert!(!contains(&stripped, b"GPS-LOCATION"));
+  }
+
+  #[test]
+  fn test_strip_png() {
+    let header = png_chunk(b"IHDR", &[0; 13]);
+    let data = png_chunk(b"IDAT", &[1, 2, 3]);
+    let end = png_chunk(b"IEND", &[]);
+    let image = [
+      PNG_SIGNATURE.to_vec(),
+      header.clone(),
+      png_chunk(b"eXIf", b"GPS-LOCATION"),
+      png_chunk(b"tEXt", b"Author\0someone"),
+      data.clone(),
+      png_chunk(b"tIME", &[0; 7]),
+      end.clone(),
+    ]
+    .concat();
+
+    let stripped = strip_image_metadata(&image).unwrap();
+    let expected = [PNG_SIGNATURE.to_vec(), header, data, end].concat();
+    assert_eq!(expected, stripped);
+  }
+
+  #[test]
+  fn test_strip_webp() {
+    let extended_header = |flags: u8| webp_chunk(b"VP8X", &[flags, 0, 0, 0, 1, 0, 0, 1, 0, 0]);
+    let bitstream = webp_chunk(b"VP8 ", &[1, 2, 3]);
+    let image = webp(&[
+      extended_header(0x0C),
+      bitstream.clone(),
+      webp_chunk(b"EXIF", b"GPS-LOCATION"),
+      webp_chunk(b"XMP ", b"<xmp/>"),
+    ]);
+
+    let stripped = strip_image_metadata(&image).unwrap();
+    assert_eq!(Some(ImageFormat::Webp), ImageFormat::detect(&stripped));
+    assert_eq!(webp(&[extended_header(0), bitstream]), stripped);
+  }
+
+  #[test]
+  fn test_strip_gif() {
+    let header = [b"GIF89a", &[1, 0, 1, 0, 0x80, 0, 0][..], &[0; 6]].concat();
+    let animation = [&[0x21, 0xFF, 11][..], b"NETSCAPE2.0", &[3, 1, 0, 0, 0]].concat();
+    let graphic_control = vec![0x21, 0xF9, 4, 0, 0, 0, 0, 0];
+    let image_data = [&[0x2C][..], &[0; 8], &[0, 2, 2, 0x4C, 0x01, 0]].concat();
+    let image = [
+      header.clone(),
+      animation.clone(),
+      [&[0x21, 0xFE, 12][..], b"GPS-LOCATION", &[0]].concat(),
+      [&[0x21, 0xFF, 11][..], b"XMP DataXMP", &[6], b"<xmp/>", &[0]].concat(),
+      graphic_control.clone(),
+      image_data.clone(),
+      vec![0x3B],
+    ]
+    .concat();
+
+    let stripped = strip_image_metadata(&image).unwrap();
+    let expected = [header, animation, graphic_control, image_data, vec![0x3B]].concat();
+    assert_eq!(expected, stripped);
+  }
+
+  #[test]
+  fn test_reject_unsupported_images() {
+    let tiff = b"II*\0\x08\0\0\0";
+    assert_eq!(None, ImageFormat::detect(tiff));
+    assert_eq!(
+      Some(LemmyErrorType::UnsupportedImageFormat),
+      strip_image_metadata(tiff).err().map(|e| e.error_type)
+    );
+
+    // Truncated in the middle of a segment
+    let truncated = [&[0xFF, 0xD8][..], &jpeg_segment(0xE1, b"Exif\0\0")[..5]].concat();
+    assert!(strip_image_metadata(&truncated).is_err());
+  }
+}
@@ -1,17 +1,33 @@
+use crate::settings::{structs::MarkdownConfig, SETTINGS};
 use markdown_it::MarkdownIt;
 use once_cell::sync::Lazy;
 
+mod footnote_rule;
 mod spoiler_rule;
+mod superscript_rule;
 
-static MARKDOWN_PARSER: Lazy<MarkdownIt> = Lazy::new(|| {
+static MARKDOWN_PARSER: Lazy<MarkdownIt> = Lazy::new(|| markdown_parser(&SETTINGS.markdown));
+
+/// Builds a parser with the markdown extensions which are enabled in the config.
+fn markdown_parser(config: &MarkdownConfig) -> MarkdownIt {
   let mut parser = MarkdownIt::new();
   markdown_it::plugins::cmark::add(&mut parser);
   markdown_it::plugins::extra::add(&mut parser);
-  spoiler_rule::add(&mut parser);
+  if config.spoilers {
+    spoiler_rule::add(&mut parser);
+  }
+  if config.superscript {
+    superscript_rule::add(&mut parser);
+  }
+  if config.footnotes {
+    footnote_rule::add(&mut parser);
+  }
 
   parser
-});
+}
 
+/// Renders markdown as html. This is used for all outgoing federated content, feeds and previews,
+/// so that they look the same.
 pub fn markdown_to_html(text: &str) -> String {
   MARKDOWN_PARSER.parse(text).xrender()
 }
@@ -21,7 +37,10 @@ mod tests {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
-  use crate::utils::markdown::markdown_to_html;
+  use crate::{
+    settings::structs::MarkdownConfig,
+    utils::markdown::{markdown_parser, markdown_to_html},
+  };
 
   #[test]
   fn test_basic_markdown() {
@@ -71,6 +90,16 @@ mod tests {
         "::: spoiler click to see more\nhow spicy!\n:::\n",
         "<details><summary>click to see more</summary><p>how spicy!\n</p></details>\n"
       ),
+      (
+        "basic superscript",
+        "2^10^",
+        "<p>2<sup>10</sup></p>\n"
+      ),
+      (
+        "basic footnote",
+        "text[^1]\n\n[^1]: note",
+        "<p>text<sup><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup></p>\n<section class=\"footnotes\"><ol>\n<li id=\"fn1\"><p>note <a href=\"#fnref1\">↩</a></p></li>\n</ol></section>\n"
+      ),
     ];
 
     tests.iter().for_each(|&(msg, input, expected)| {
@@ -83,4 +112,69 @@ mod tests {
       );
     });
   }
+
+  #[test]
+  fn test_markdown_extensions() {
+    let all = MarkdownConfig::default();
+    let none = MarkdownConfig {
+      spoilers: false,
+      superscript: false,
+      footnotes: false,
+    };
+    let footnotes = "Text[^1] and more[^note], again[^1] and missing[^x].\n\n[^1]: First *note*\n  continued\n[^note]: Second[^1]\n";
+    let tests: Vec<_> = vec![
+      (
+        "superscript",
+        &all,
+        "e^iπ^+1=0",
+        "<p>e<sup>iπ</sup>+1=0</p>\n",
+      ),
+      (
+        "superscript disabled",
+        &none,
+        "e^iπ^+1=0",
+        "<p>e^iπ^+1=0</p>\n",
+      ),
+      (
+        "footnotes",
+        &all,
+        footnotes,
+        "<p>Text<sup><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup> and more<sup><a href=\"#fn2\" id=\"fnref2\">[2]</a></sup>, again<sup><a href=\"#fn1\">[1]</a></sup> and missing[^x].</p>\n<section class=\"footnotes\"><ol>\n<li id=\"fn1\"><p>First <em>note</em>\ncontinued <a href=\"#fnref1\">↩</a></p></li>\n<li id=\"fn2\"><p>Second<sup><a href=\"#fn1\">[1]</a></sup> <a href=\"#fnref2\">↩</a></p></li>\n</ol></section>\n",
+      ),
+      (
+        "footnotes disabled",
+        &none,
+        footnotes,
+        "<p>Text[^1] and more[^note], again[^1] and missing[^x].</p>\n<p>[^1]: First <em>note</em>\ncontinued\n[^note]: Second[^1]</p>\n",
+      ),
+      (
+        "footnote in blockquote",
+        &all,
+        "> quote[^q]\n>\n> [^q]: in quote\n",
+        "<blockquote>\n<p>quote<sup><a href=\"#fn1\" id=\"fnref1\">[1]</a></sup></p>\n</blockquote>\n<section class=\"footnotes\"><ol>\n<li id=\"fn1\"><p>in quote <a href=\"#fnref1\">↩</a></p></li>\n</ol></section>\n",
+      ),
+      (
+        "unreferenced footnote",
+        &all,
+        "Text\n\n[^1]: Unused\n",
+        "<p>Text</p>\n",
+      ),
+      (
+        "spoiler disabled",
+        &none,
+        "::: spoiler click to see more\nhow spicy!\n:::\n",
+        "<p>::: spoiler click to see more\nhow spicy!\n:::</p>\n",
+      ),
+    ];
+
+    tests.iter().for_each(|&(msg, config, input, expected)| {
+      let result = markdown_parser(config).parse(input).xrender();
+
+      assert_eq!(
+        result, expected,
+        "Testing {}, with original input '{}'",
+        msg, input
+      );
+    });
+  }
 }
@@ -0,0 +1,230 @@
+// Custom Markdown plugin for footnotes.
+//
+// Matches the common syntax of markdown-it-footnote, without support for block content or inline
+// footnotes:
+// https://github.com/markdown-it/markdown-it-footnote
+//
+// FORMAT:
+// Input Markdown: Some text[^note]\n\n[^note]: The footnote\n
+// Output HTML: <p>Some text<sup><a href="#fn1" id="fnref1">[1]</a></sup></p>
+//              <section class="footnotes"><ol><li id="fn1"><p>The footnote <a href="#fnref1">↩</a></p></li></ol></section>
+//
+// Footnotes are numbered in the order in which they are first referenced, and rendered at the end
+// of the document. Definitions which are never referenced are dropped, references without a
+// definition are left as plain text. A definition continues on the following lines until an empty
+// line or the next definition.
+
+use markdown_it::{
+  parser::{
+    block::{BlockRule, BlockState},
+    core::CoreRule,
+    inline::{InlineRoot, InlineRule, InlineState},
+  },
+  plugins::cmark::block::reference::ReferenceScanner,
+  MarkdownIt,
+  Node,
+  NodeValue,
+  Renderer,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+static FOOTNOTE_REFERENCE_REGEX: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"^\[\^([^\]\s]+)\]").expect("compile footnote reference markdown regex.")
+});
+static FOOTNOTE_DEFINITION_REGEX: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"^ *\[\^([^\]\s]+)\]:[ \t]*").expect("compile footnote definition markdown regex.")
+});
+
+#[derive(Debug)]
+struct FootnoteReference {
+  label: String,
+  number: Option<usize>,
+  // Only the first reference to a footnote is the target of its back link.
+  first: bool,
+}
+
+impl NodeValue for FootnoteReference {
+  fn render(&self, _node: &Node, fmt: &mut dyn Renderer) {
+    match self.number {
+      Some(number) => {
+        let mut attrs = vec![("href", format!("#fn{number}"))];
+        if self.first {
+          attrs.push(("id", format!("fnref{number}")));
+        }
+        fmt.open("sup", &[]);
+        fmt.open("a", &attrs);
+        fmt.text(&format!("[{number}]"));
+        fmt.close("a");
+        fmt.close("sup");
+      }
+      None => fmt.text(&format!("[^{}]", self.label)),
+    }
+  }
+}
+
+#[derive(Debug)]
+struct FootnoteDefinition {
+  label: String,
+  number: usize,
+}
+
+impl NodeValue for FootnoteDefinition {
+  fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+    fmt.open("li", &[("id", format!("fn{}", self.number))]);
+    fmt.open("p", &[]);
+    fmt.contents(&node.children);
+    fmt.text(" ");
+    fmt.open("a", &[("href", format!("#fnref{}", self.number))]);
+    fmt.text("↩");
+    fmt.close("a");
+    fmt.close("p");
+    fmt.close("li");
+    fmt.cr();
+  }
+}
+
+#[derive(Debug)]
+struct FootnoteSection;
+
+impl NodeValue for FootnoteSection {
+  fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+    fmt.cr();
+    fmt.open("section", &[("class", "footnotes".to_string())]);
+    fmt.open("ol", &[]);
+    fmt.cr();
+    fmt.contents(&node.children);
+    fmt.close("ol");
+    fmt.close("section");
+    fmt.cr();
+  }
+}
+
+struct FootnoteReferenceScanner;
+
+impl InlineRule for FootnoteReferenceScanner {
+  const MARKER: char = '[';
+
+  fn run(state: &mut InlineState) -> Option<(Node, usize)> {
+    let captures = FOOTNOTE_REFERENCE_REGEX.captures(&state.src[state.pos..state.pos_max])?;
+    let node = Node::new(FootnoteReference {
+      label: captures[1].to_string(),
+      number: None,
+      first: false,
+    });
+    Some((node, captures[0].len()))
+  }
+}
+
+struct FootnoteDefinitionScanner;
+
+impl BlockRule for FootnoteDefinitionScanner {
+  fn run(state: &mut BlockState) -> Option<(Node, usize)> {
+    // if it's indented more than 3 spaces, it should be a code block
+    if state.line_indent(state.line) >= 4 {
+      return None;
+    }
+    let label = FOOTNOTE_DEFINITION_REGEX.captures(state.get_line(state.line))?[1].to_string();
+
+    let mut end_line_idx = state.line + 1;
+    while end_line_idx < state.line_max
+      && !state.is_empty(end_line_idx)
+      && !FOOTNOTE_DEFINITION_REGEX.is_match(state.get_line(end_line_idx))
+    {
+      end_line_idx += 1;
+    }
+
+    let (content, mapping) = state.get_lines(state.line, end_line_idx, state.blk_indent, false);
+    let prefix_len = FOOTNOTE_DEFINITION_REGEX.find(&content)?.end();
+    let mapping = mapping
+      .into_iter()
+      .map(|(content_pos, source_pos)| {
+        if content_pos == 0 {
+          (0, source_pos + prefix_len)
+        } else {
+          (content_pos - prefix_len, source_pos)
+        }
+      })
+      .collect();
+
+    let mut node = Node::new(FootnoteDefinition { label, number: 0 });
+    node.children.push(Node::new(InlineRoot::new(
+      content[prefix_len..].to_string(),
+      mapping,
+    )));
+    Some((node, end_line_idx - state.line))
+  }
+}
+
+struct FootnoteCollector;
+
+impl CoreRule for FootnoteCollector {
+  // Moves the referenced definitions into a footnote section at the end of the document, and
+  // numbers the references to them.
+  fn run(root: &mut Node, _: &MarkdownIt) {
+    let mut definitions: HashMap<String, Node> = HashMap::new();
+    root.walk_mut(|node, _| {
+      if node.children.iter().any(|c| c.is::<FootnoteDefinition>()) {
+        let (found, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut node.children)
+          .into_iter()
+          .partition(|c| c.is::<FootnoteDefinition>());
+        node.children = rest;
+        for definition in found {
+          if let Some(d) = definition.cast::<FootnoteDefinition>() {
+            definitions.entry(d.label.clone()).or_insert(definition);
+          }
+        }
+      }
+    });
+
+    let mut labels: Vec<String> = vec![];
+    root.walk_mut(|node, _| {
+      if let Some(reference) = node.cast_mut::<FootnoteReference>() {
+        if definitions.contains_key(&reference.label) {
+          let position = labels.iter().position(|l| l == &reference.label);
+          reference.first = position.is_none();
+          if position.is_none() {
+            labels.push(reference.label.clone());
+          }
+          reference.number = Some(position.unwrap_or(labels.len() - 1) + 1);
+        }
+      }
+    });
+    if labels.is_empty() {
+      return;
+    }
+
+    let mut section = Node::new(FootnoteSection);
+    for (label, number) in labels.iter().zip(1..) {
+      if let Some(mut definition) = definitions.remove(label) {
+        if let Some(d) = definition.cast_mut::<FootnoteDefinition>() {
+          d.number = number;
+        }
+        section.children.push(definition);
+      }
+    }
+    // References within footnotes only link to footnotes which are referenced from the document.
+    section.walk_mut(|node, _| {
+      if let Some(reference) = node.cast_mut::<FootnoteReference>() {
+        reference.number = labels
+          .iter()
+          .position(|l| l == &reference.label)
+          .map(|p| p + 1);
+      }
+    });
+    root.children.push(section);
+  }
+}
+
+pub fn add(markdown_parser: &mut MarkdownIt) {
+  markdown_parser
+    .block
+    .add_rule::<FootnoteDefinitionScanner>()
+    .before::<ReferenceScanner>();
+  markdown_parser
+    .inline
+    .add_rule::<FootnoteReferenceScanner>()
+    .before_all();
+  markdown_parser.add_rule::<FootnoteCollector>().after_all();
+}
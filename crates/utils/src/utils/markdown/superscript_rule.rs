@@ -0,0 +1,22 @@
+// Custom Markdown plugin for superscript.
+//
+// FORMAT:
+// Input Markdown: e^iπ^+1=0
+// Output HTML: e<sup>iπ</sup>+1=0
+
+use markdown_it::{generics::inline::emph_pair, MarkdownIt, Node, NodeValue, Renderer};
+
+#[derive(Debug)]
+struct Superscript;
+
+impl NodeValue for Superscript {
+  fn render(&self, node: &Node, fmt: &mut dyn Renderer) {
+    fmt.open("sup", &node.attrs);
+    fmt.contents(&node.children);
+    fmt.close("sup");
+  }
+}
+
+pub fn add(markdown_parser: &mut MarkdownIt) {
+  emph_pair::add_with::<'^', 1, true>(markdown_parser, || Node::new(Superscript));
+}
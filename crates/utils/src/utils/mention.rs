@@ -5,7 +5,11 @@ use regex::Regex;
 static MENTIONS_REGEX: Lazy<Regex> = Lazy::new(|| {
   Regex::new(r"@(?P<name>[\w.]+)@(?P<domain>[a-zA-Z0-9._:-]+)").expect("compile regex")
 });
-// TODO nothing is done with community / group webfingers yet, so just ignore those for now
+static COMMUNITY_MENTIONS_REGEX: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r"!(?P<name>[\w.]+)@(?P<domain>[a-zA-Z0-9._:-]+)").expect("compile regex")
+});
+
+/// A mention of a person (`@name@domain`) or community (`!name@domain`)
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct MentionData {
   pub name: String,
@@ -22,8 +26,16 @@ impl MentionData {
 }
 
 pub fn scrape_text_for_mentions(text: &str) -> Vec<MentionData> {
+  scrape_text(&MENTIONS_REGEX, text)
+}
+
+pub fn scrape_text_for_community_mentions(text: &str) -> Vec<MentionData> {
+  scrape_text(&COMMUNITY_MENTIONS_REGEX, text)
+}
+
+fn scrape_text(regex: &Regex, text: &str) -> Vec<MentionData> {
   let mut out: Vec<MentionData> = Vec::new();
-  for caps in MENTIONS_REGEX.captures_iter(text) {
+  for caps in regex.captures_iter(text) {
     if let Some(name) = caps.name("name").map(|c| c.as_str().to_string()) {
       if let Some(domain) = caps.name("domain").map(|c| c.as_str().to_string()) {
         out.push(MentionData { name, domain });
@@ -38,7 +50,7 @@ mod test {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
-  use crate::utils::mention::scrape_text_for_mentions;
+  use crate::utils::mention::{scrape_text_for_community_mentions, scrape_text_for_mentions};
 
   #[test]
   fn test_mentions_regex() {
@@ -48,5 +60,14 @@ mod test {
     assert_eq!(mentions[0].name, "tedu".to_string());
     assert_eq!(mentions[0].domain, "honk.teduangst.com".to_string());
     assert_eq!(mentions[1].domain, "lemmy-alpha:8540".to_string());
+    assert_eq!(2, mentions.len());
+
+    let community_mentions = scrape_text_for_community_mentions(text);
+    assert_eq!(1, community_mentions.len());
+    assert_eq!(community_mentions[0].name, "test_community".to_string());
+    assert_eq!(
+      community_mentions[0].domain,
+      "fish.teduangst.com".to_string()
+    );
   }
 }
@@ -1,3 +1,4 @@
+pub mod image_metadata;
 pub mod markdown;
 pub mod mention;
 pub mod slurs;
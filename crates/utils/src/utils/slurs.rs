@@ -39,6 +39,21 @@ pub fn build_slur_regex(regex_str: Option<&str>) -> Option<Regex> {
   })
 }
 
+/// Combines the patterns of slur filters into a single regex, so they can be applied with
+/// [remove_slurs] and [check_slurs] like the site slur filter. Invalid patterns are skipped.
+pub fn build_slur_filter_regex(patterns: &[&str]) -> Option<Regex> {
+  let patterns = patterns
+    .iter()
+    .filter(|p| Regex::new(p).is_ok())
+    .map(|p| format!("(?:{p})"))
+    .collect::<Vec<_>>();
+  if patterns.is_empty() {
+    None
+  } else {
+    build_slur_regex(Some(&patterns.join("|")))
+  }
+}
+
 pub fn check_slurs(text: &str, slur_regex: &Option<Regex>) -> Result<(), LemmyError> {
   if let Err(slurs) = slur_check(text, slur_regex) {
     Err(anyhow::anyhow!("{}", slurs_vec_to_str(&slurs))).with_lemmy_type(LemmyErrorType::Slurs)
@@ -57,6 +72,16 @@ pub fn check_slurs_opt(
   }
 }
 
+/// Evaluates community word filters, which apply on top of the site slur filter. Returns the most
+/// severe action of all filters which match any of the texts.
+pub fn check_word_filters<A: Ord + Copy>(texts: &[&str], word_filters: &[(Regex, A)]) -> Option<A> {
+  word_filters
+    .iter()
+    .filter(|(regex, _)| texts.iter().any(|text| regex.is_match(text)))
+    .map(|(_, action)| *action)
+    .max()
+}
+
 pub(crate) fn slurs_vec_to_str(slurs: &[&str]) -> String {
   let start = "No slurs - ";
   let combined = &slurs.join(", ");
@@ -68,7 +93,13 @@ mod test {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
-  use crate::utils::slurs::{remove_slurs, slur_check, slurs_vec_to_str};
+  use crate::utils::slurs::{
+    build_slur_filter_regex,
+    check_word_filters,
+    remove_slurs,
+    slur_check,
+    slurs_vec_to_str,
+  };
   use regex::RegexBuilder;
 
   #[test]
@@ -100,6 +131,45 @@ mod test {
     }
   }
 
+  #[test]
+  fn test_check_word_filters() {
+    let word_filters = [
+      (
+        RegexBuilder::new(r"\bbuy now\b")
+          .case_insensitive(true)
+          .build()
+          .unwrap(),
+        1,
+      ),
+      (
+        RegexBuilder::new("crypto")
+          .case_insensitive(true)
+          .build()
+          .unwrap(),
+        2,
+      ),
+    ];
+    assert_eq!(check_word_filters(&["nothing to see"], &word_filters), None);
+    assert_eq!(check_word_filters(&["Buy Now!"], &word_filters), Some(1));
+    assert_eq!(
+      check_word_filters(&["buy now", "free CRYPTO"], &word_filters),
+      Some(2)
+    );
+    assert_eq!(check_word_filters(&["buy nowhere"], &word_filters), None);
+  }
+
+  #[test]
+  fn test_build_slur_filter_regex() {
+    assert!(build_slur_filter_regex(&[]).is_none());
+    assert!(build_slur_filter_regex(&["(unclosed"]).is_none());
+
+    let slur_regex = build_slur_filter_regex(&["schimpf|wort", "(unclosed", r"\bmot\b"]);
+    assert_eq!(
+      remove_slurs("Schimpf, Worte, mot, motif", &slur_regex),
+      "*removed*, *removed*e, *removed*, motif"
+    );
+  }
+
   // These helped with testing
   // #[test]
   // fn test_send_email() {
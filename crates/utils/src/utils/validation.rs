@@ -12,6 +12,10 @@ static VALID_POST_TITLE_REGEX: Lazy<Regex> =
 static VALID_MATRIX_ID_REGEX: Lazy<Regex> = Lazy::new(|| {
   Regex::new(r"^@[A-Za-z0-9._=-]+:[A-Za-z0-9.-]+\.[A-Za-z]{2,}$").expect("compile regex")
 });
+static VALID_EMOJI_SHORTCODE_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"^:[a-zA-Z0-9_+-]{1,64}(@[a-zA-Z0-9.-]+)?:$").expect("compile regex"));
+static VALID_HEX_COLOR_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"^#[0-9a-fA-F]{6}$").expect("compile regex"));
 // taken from https://en.wikipedia.org/wiki/UTM_parameters
 static CLEAN_URL_PARAMS_REGEX: Lazy<Regex> = Lazy::new(|| {
   Regex::new(r"^utm_source|utm_medium|utm_campaign|utm_term|utm_content|gclid|gclsrc|dclid|fbclid$")
@@ -24,6 +28,12 @@ const BIO_MAX_LENGTH: usize = 300;
 const SITE_NAME_MAX_LENGTH: usize = 20;
 const SITE_NAME_MIN_LENGTH: usize = 1;
 const SITE_DESCRIPTION_MAX_LENGTH: usize = 150;
+const EMOJI_REACTION_MAX_CHARS: usize = 16;
+const SLOW_MODE_MAX_SECONDS: i32 = 86400;
+const CATEGORY_NAME_MAX_LENGTH: usize = 50;
+const PERSON_NOTE_MAX_LENGTH: usize = 300;
+/// How many categories a community can assign to itself
+pub const COMMUNITY_CATEGORIES_MAX: usize = 3;
 //Invisible unicode characters, taken from https://invisible-characters.com/
 const FORBIDDEN_DISPLAY_CHARS: [char; 53] = [
   '\u{0009}',
@@ -119,6 +129,19 @@ pub fn is_valid_matrix_id(matrix_id: &str) -> LemmyResult<()> {
   }
 }
 
+/// Reactions are either a unicode emoji, or the shortcode of a custom emoji like `:blobcat:`.
+/// Custom emojis from other instances may include the domain, like `:blobcat@example.com:`.
+pub fn is_valid_emoji_reaction(emoji: &str) -> LemmyResult<()> {
+  let is_unicode = emoji.chars().count() <= EMOJI_REACTION_MAX_CHARS
+    && !emoji.is_ascii()
+    && !emoji.chars().any(|c| c.is_whitespace() || c.is_control());
+  if is_unicode || VALID_EMOJI_SHORTCODE_REGEX.is_match(emoji) {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::InvalidEmojiReaction.into())
+  }
+}
+
 pub fn is_valid_post_title(title: &str) -> LemmyResult<()> {
   let check = VALID_POST_TITLE_REGEX.is_match(title) && !has_newline(title);
   if !check {
@@ -284,22 +307,120 @@ pub fn build_totp_2fa(site_name: &str, username: &str, secret: &str) -> Result<T
   .with_lemmy_type(LemmyErrorType::CouldntGenerateTotp)
 }
 
+/// Private instances can only federate if they are set to federate with the allowlist.
 pub fn check_site_visibility_valid(
   current_private_instance: bool,
   current_federation_enabled: bool,
+  current_private_instance_federation: bool,
   new_private_instance: &Option<bool>,
   new_federation_enabled: &Option<bool>,
+  new_private_instance_federation: &Option<bool>,
 ) -> LemmyResult<()> {
   let private_instance = new_private_instance.unwrap_or(current_private_instance);
   let federation_enabled = new_federation_enabled.unwrap_or(current_federation_enabled);
+  let private_instance_federation =
+    new_private_instance_federation.unwrap_or(current_private_instance_federation);
 
-  if private_instance && federation_enabled {
+  if private_instance && federation_enabled && !private_instance_federation {
     return Err(LemmyErrorType::CantEnablePrivateInstanceAndFederationTogether.into());
   }
 
   Ok(())
 }
 
+pub fn check_slow_mode_interval(seconds: &Option<i32>) -> LemmyResult<()> {
+  if seconds.is_some_and(|s| !(0..=SLOW_MODE_MAX_SECONDS).contains(&s)) {
+    return Err(LemmyErrorType::InvalidSlowModeInterval.into());
+  }
+  Ok(())
+}
+
+/// Rate limits allow a number of actions per interval. An interval of zero would never refill the
+/// bucket, so it has to be at least one second.
+pub fn check_rate_limit(count: &Option<i32>, interval_seconds: &Option<i32>) -> LemmyResult<()> {
+  if count.is_some_and(|c| c < 0) || interval_seconds.is_some_and(|i| i < 1) {
+    return Err(LemmyErrorType::InvalidRateLimit.into());
+  }
+  Ok(())
+}
+
+/// A strike ban threshold of zero disables automatic bans. Bans for reaching it last up to a year.
+pub fn check_strike_ban_settings(threshold: &Option<i32>, days: &Option<i32>) -> LemmyResult<()> {
+  if threshold.is_some_and(|t| t < 0) || days.is_some_and(|d| !(1..=365).contains(&d)) {
+    return Err(LemmyErrorType::InvalidStrikeBanSettings.into());
+  }
+  Ok(())
+}
+
+/// Posting thresholds of communities can't be negative, and required account age is at most ten
+/// years.
+pub fn check_posting_thresholds(
+  min_account_age_days: &Option<i32>,
+  min_site_karma: &Option<i32>,
+  min_community_karma: &Option<i32>,
+) -> LemmyResult<()> {
+  if min_account_age_days.is_some_and(|d| !(0..=3650).contains(&d))
+    || min_site_karma.is_some_and(|k| k < 0)
+    || min_community_karma.is_some_and(|k| k < 0)
+  {
+    return Err(LemmyErrorType::InvalidPostingThresholds.into());
+  }
+  Ok(())
+}
+
+/// Username changes can't be negative, and former names are reserved for at most ten years.
+pub fn check_username_change_settings(
+  max_username_changes: &Option<i32>,
+  username_reuse_cooldown_days: &Option<i32>,
+) -> LemmyResult<()> {
+  if max_username_changes.is_some_and(|c| c < 0)
+    || username_reuse_cooldown_days.is_some_and(|d| !(0..=3650).contains(&d))
+  {
+    return Err(LemmyErrorType::InvalidUsernameChangeSettings.into());
+  }
+  Ok(())
+}
+
+pub fn check_audio_duration(seconds: &Option<i32>) -> LemmyResult<()> {
+  if seconds.is_some_and(|s| s < 0) {
+    return Err(LemmyErrorType::InvalidAudioDuration.into());
+  }
+  Ok(())
+}
+
+pub fn is_valid_category_name(name: &str) -> LemmyResult<()> {
+  let check = !name.trim().is_empty()
+    && name.trim() == name
+    && name.chars().count() <= CATEGORY_NAME_MAX_LENGTH
+    && !has_newline(name);
+  if !check {
+    Err(LemmyErrorType::InvalidCategoryName.into())
+  } else {
+    Ok(())
+  }
+}
+
+/// Notes about other users are short, and their optional color is given as `#rrggbb`.
+pub fn check_person_note(note: &str, color: &Option<String>) -> LemmyResult<()> {
+  let note_valid = !note.trim().is_empty() && note.chars().count() <= PERSON_NOTE_MAX_LENGTH;
+  let color_valid = color
+    .as_ref()
+    .map_or(true, |c| VALID_HEX_COLOR_REGEX.is_match(c));
+  if note_valid && color_valid {
+    Ok(())
+  } else {
+    Err(LemmyErrorType::InvalidPersonNote.into())
+  }
+}
+
+pub fn check_community_categories_count(count: usize) -> LemmyResult<()> {
+  if count > COMMUNITY_CATEGORIES_MAX {
+    Err(LemmyErrorType::TooManyCategories.into())
+  } else {
+    Ok(())
+  }
+}
+
 pub fn check_url_scheme(url: &Option<Url>) -> LemmyResult<()> {
   if let Some(url) = url {
     if url.scheme() != "http" && url.scheme() != "https" {
@@ -319,13 +440,23 @@ mod tests {
     error::LemmyErrorType,
     utils::validation::{
       build_and_check_regex,
+      check_audio_duration,
+      check_community_categories_count,
+      check_person_note,
+      check_posting_thresholds,
+      check_rate_limit,
       check_site_visibility_valid,
+      check_slow_mode_interval,
+      check_strike_ban_settings,
       check_url_scheme,
+      check_username_change_settings,
       clean_url_params,
       generate_totp_2fa_secret,
       is_valid_actor_name,
       is_valid_bio_field,
+      is_valid_category_name,
       is_valid_display_name,
+      is_valid_emoji_reaction,
       is_valid_matrix_id,
       is_valid_post_title,
       site_description_length_check,
@@ -380,6 +511,19 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_valid_emoji_reaction() {
+    assert!(is_valid_emoji_reaction("👍").is_ok());
+    assert!(is_valid_emoji_reaction("👨‍👩‍👧‍👦").is_ok());
+    assert!(is_valid_emoji_reaction(":blobcat:").is_ok());
+    assert!(is_valid_emoji_reaction(":blobcat@example.com:").is_ok());
+    assert!(is_valid_emoji_reaction("").is_err());
+    assert!(is_valid_emoji_reaction("blobcat").is_err());
+    assert!(is_valid_emoji_reaction(":blob cat:").is_err());
+    assert!(is_valid_emoji_reaction("👍 👍").is_err());
+    assert!(is_valid_emoji_reaction(&"👍".repeat(20)).is_err());
+  }
+
   #[test]
   fn test_valid_post_title() {
     assert!(is_valid_post_title("Post Title").is_ok());
@@ -518,14 +662,27 @@ mod tests {
 
   #[test]
   fn test_check_site_visibility_valid() {
-    assert!(check_site_visibility_valid(true, true, &None, &None).is_err());
-    assert!(check_site_visibility_valid(true, false, &None, &Some(true)).is_err());
-    assert!(check_site_visibility_valid(false, true, &Some(true), &None).is_err());
-    assert!(check_site_visibility_valid(false, false, &Some(true), &Some(true)).is_err());
-    assert!(check_site_visibility_valid(true, false, &None, &None).is_ok());
-    assert!(check_site_visibility_valid(false, true, &None, &None).is_ok());
-    assert!(check_site_visibility_valid(false, false, &Some(true), &None).is_ok());
-    assert!(check_site_visibility_valid(false, false, &None, &Some(true)).is_ok());
+    assert!(check_site_visibility_valid(true, true, false, &None, &None, &None).is_err());
+    assert!(check_site_visibility_valid(true, false, false, &None, &Some(true), &None).is_err());
+    assert!(check_site_visibility_valid(false, true, false, &Some(true), &None, &None).is_err());
+    assert!(
+      check_site_visibility_valid(false, false, false, &Some(true), &Some(true), &None).is_err()
+    );
+    assert!(check_site_visibility_valid(true, true, true, &None, &None, &Some(false)).is_err());
+    assert!(check_site_visibility_valid(true, false, false, &None, &None, &None).is_ok());
+    assert!(check_site_visibility_valid(false, true, false, &None, &None, &None).is_ok());
+    assert!(check_site_visibility_valid(false, false, false, &Some(true), &None, &None).is_ok());
+    assert!(check_site_visibility_valid(false, false, false, &None, &Some(true), &None).is_ok());
+    assert!(check_site_visibility_valid(true, true, true, &None, &None, &None).is_ok());
+    assert!(check_site_visibility_valid(
+      false,
+      false,
+      false,
+      &Some(true),
+      &Some(true),
+      &Some(true)
+    )
+    .is_ok());
   }
 
   #[test]
@@ -536,4 +693,87 @@ mod tests {
     assert!(check_url_scheme(&Some(Url::parse("ftp://example.com").unwrap())).is_err());
     assert!(check_url_scheme(&Some(Url::parse("javascript:void").unwrap())).is_err());
   }
+
+  #[test]
+  fn test_check_slow_mode_interval() {
+    assert!(check_slow_mode_interval(&None).is_ok());
+    assert!(check_slow_mode_interval(&Some(0)).is_ok());
+    assert!(check_slow_mode_interval(&Some(300)).is_ok());
+    assert!(check_slow_mode_interval(&Some(-1)).is_err());
+    assert!(check_slow_mode_interval(&Some(86401)).is_err());
+  }
+
+  #[test]
+  fn test_check_strike_ban_settings() {
+    assert!(check_strike_ban_settings(&None, &None).is_ok());
+    assert!(check_strike_ban_settings(&Some(0), &Some(7)).is_ok());
+    assert!(check_strike_ban_settings(&Some(3), &Some(365)).is_ok());
+    assert!(check_strike_ban_settings(&Some(-1), &None).is_err());
+    assert!(check_strike_ban_settings(&None, &Some(0)).is_err());
+    assert!(check_strike_ban_settings(&None, &Some(366)).is_err());
+  }
+
+  #[test]
+  fn test_check_person_note() {
+    assert!(check_person_note("helpful", &None).is_ok());
+    assert!(check_person_note("helpful", &Some("#00Ff88".to_string())).is_ok());
+    assert!(check_person_note(" ", &None).is_err());
+    assert!(check_person_note(&"a".repeat(301), &None).is_err());
+    assert!(check_person_note("helpful", &Some("green".to_string())).is_err());
+    assert!(check_person_note("helpful", &Some("#00ff8".to_string())).is_err());
+  }
+
+  #[test]
+  fn test_check_posting_thresholds() {
+    assert!(check_posting_thresholds(&None, &None, &None).is_ok());
+    assert!(check_posting_thresholds(&Some(0), &Some(0), &Some(0)).is_ok());
+    assert!(check_posting_thresholds(&Some(30), &Some(100), &Some(10)).is_ok());
+    assert!(check_posting_thresholds(&Some(-1), &None, &None).is_err());
+    assert!(check_posting_thresholds(&Some(3651), &None, &None).is_err());
+    assert!(check_posting_thresholds(&None, &Some(-5), &None).is_err());
+    assert!(check_posting_thresholds(&None, &None, &Some(-5)).is_err());
+  }
+
+  #[test]
+  fn test_check_username_change_settings() {
+    assert!(check_username_change_settings(&None, &None).is_ok());
+    assert!(check_username_change_settings(&Some(3), &Some(90)).is_ok());
+    assert!(check_username_change_settings(&Some(-1), &None).is_err());
+    assert!(check_username_change_settings(&None, &Some(-1)).is_err());
+    assert!(check_username_change_settings(&None, &Some(3651)).is_err());
+  }
+
+  #[test]
+  fn test_check_audio_duration() {
+    assert!(check_audio_duration(&None).is_ok());
+    assert!(check_audio_duration(&Some(0)).is_ok());
+    assert!(check_audio_duration(&Some(3600)).is_ok());
+    assert!(check_audio_duration(&Some(-1)).is_err());
+  }
+
+  #[test]
+  fn test_check_rate_limit() {
+    assert!(check_rate_limit(&None, &None).is_ok());
+    assert!(check_rate_limit(&Some(0), &Some(1)).is_ok());
+    assert!(check_rate_limit(&Some(180), &Some(60)).is_ok());
+    assert!(check_rate_limit(&Some(-1), &None).is_err());
+    assert!(check_rate_limit(&None, &Some(0)).is_err());
+  }
+
+  #[test]
+  fn test_valid_category_name() {
+    assert!(is_valid_category_name("Science").is_ok());
+    assert!(is_valid_category_name("Tabletop & Board Games").is_ok());
+    assert!(is_valid_category_name("").is_err());
+    assert!(is_valid_category_name(" Science").is_err());
+    assert!(is_valid_category_name("Sci\nence").is_err());
+    assert!(is_valid_category_name(&"a".repeat(51)).is_err());
+  }
+
+  #[test]
+  fn test_check_community_categories_count() {
+    assert!(check_community_categories_count(0).is_ok());
+    assert!(check_community_categories_count(3).is_ok());
+    assert!(check_community_categories_count(4).is_err());
+  }
 }
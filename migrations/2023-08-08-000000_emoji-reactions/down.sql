@@ -0,0 +1,4 @@
+DROP TABLE post_reaction;
+
+DROP TABLE comment_reaction;
+
@@ -0,0 +1,22 @@
+CREATE TABLE post_reaction (
+    id serial PRIMARY KEY,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    emoji varchar(100) NOT NULL,
+    published timestamp NOT NULL DEFAULT now(),
+    UNIQUE (post_id, person_id, emoji)
+);
+
+CREATE INDEX idx_post_reaction_post ON post_reaction (post_id);
+
+CREATE TABLE comment_reaction (
+    id serial PRIMARY KEY,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    emoji varchar(100) NOT NULL,
+    published timestamp NOT NULL DEFAULT now(),
+    UNIQUE (comment_id, person_id, emoji)
+);
+
+CREATE INDEX idx_comment_reaction_comment ON comment_reaction (comment_id);
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN enable_downvotes boolean NOT NULL DEFAULT TRUE;
+
@@ -0,0 +1,3 @@
+ALTER TABLE local_site
+    ADD COLUMN enable_vote_viewer boolean NOT NULL DEFAULT TRUE;
+
@@ -0,0 +1,7 @@
+DROP TABLE person_vote_pseudonym;
+
+ALTER TABLE local_user
+    DROP COLUMN anonymize_outgoing_votes;
+
+ALTER TABLE local_site
+    DROP COLUMN anonymize_outgoing_votes;
@@ -0,0 +1,12 @@
+ALTER TABLE local_site
+    ADD COLUMN anonymize_outgoing_votes boolean NOT NULL DEFAULT FALSE;
+
+ALTER TABLE local_user
+    ADD COLUMN anonymize_outgoing_votes boolean NOT NULL DEFAULT FALSE;
+
+-- Maps each local person to the pseudonymous actor which federates their votes.
+CREATE TABLE person_vote_pseudonym (
+    person_id int PRIMARY KEY REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    pseudonym_id int NOT NULL UNIQUE REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    published timestamp NOT NULL DEFAULT now()
+);
@@ -0,0 +1 @@
+DROP INDEX idx_comment_post_path;
@@ -0,0 +1,2 @@
+-- Lets cursor pagination walk a post's comment tree in path order without sorting all of it.
+CREATE INDEX idx_comment_post_path ON comment (post_id, path);
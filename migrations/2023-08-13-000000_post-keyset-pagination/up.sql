@@ -0,0 +1,58 @@
+-- Add the post id to the end of the listing indexes, so that keyset pagination can use them
+-- for the tiebreaker.
+
+DROP INDEX idx_post_aggregates_featured_local_hot;
+
+CREATE INDEX idx_post_aggregates_featured_local_hot ON post_aggregates (featured_local DESC, hot_rank DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_local_active;
+
+CREATE INDEX idx_post_aggregates_featured_local_active ON post_aggregates (featured_local DESC, hot_rank_active DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_local_score;
+
+CREATE INDEX idx_post_aggregates_featured_local_score ON post_aggregates (featured_local DESC, score DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_local_most_comments;
+
+CREATE INDEX idx_post_aggregates_featured_local_most_comments ON post_aggregates (featured_local DESC, comments DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_local_published;
+
+CREATE INDEX idx_post_aggregates_featured_local_published ON post_aggregates (featured_local DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_local_newest_comment_time;
+
+CREATE INDEX idx_post_aggregates_featured_local_newest_comment_time ON post_aggregates (featured_local DESC, newest_comment_time DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_local_controversy;
+
+CREATE INDEX idx_post_aggregates_featured_local_controversy ON post_aggregates (featured_local DESC, controversy_rank DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_community_hot;
+
+CREATE INDEX idx_post_aggregates_featured_community_hot ON post_aggregates (featured_community DESC, hot_rank DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_community_active;
+
+CREATE INDEX idx_post_aggregates_featured_community_active ON post_aggregates (featured_community DESC, hot_rank_active DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_community_score;
+
+CREATE INDEX idx_post_aggregates_featured_community_score ON post_aggregates (featured_community DESC, score DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_community_most_comments;
+
+CREATE INDEX idx_post_aggregates_featured_community_most_comments ON post_aggregates (featured_community DESC, comments DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_community_published;
+
+CREATE INDEX idx_post_aggregates_featured_community_published ON post_aggregates (featured_community DESC, published DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_community_newest_comment_time;
+
+CREATE INDEX idx_post_aggregates_featured_community_newest_comment_time ON post_aggregates (featured_community DESC, newest_comment_time DESC, post_id DESC);
+
+DROP INDEX idx_post_aggregates_featured_community_controversy;
+
+CREATE INDEX idx_post_aggregates_featured_community_controversy ON post_aggregates (featured_community DESC, controversy_rank DESC, post_id DESC);
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    DROP COLUMN requires_follow_approval;
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN requires_follow_approval boolean NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,2 @@
+DROP TABLE community_transfer_request;
+
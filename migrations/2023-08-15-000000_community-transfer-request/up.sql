@@ -0,0 +1,8 @@
+-- A pending community transfer, which only takes effect once the new owner accepts it
+CREATE TABLE community_transfer_request (
+    community_id int PRIMARY KEY REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    requester_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    recipient_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    published timestamp NOT NULL DEFAULT now()
+);
+
@@ -0,0 +1,8 @@
+ALTER TABLE community
+    DROP COLUMN post_type_restriction;
+
+ALTER TABLE community
+    DROP COLUMN nsfw_only;
+
+DROP TYPE post_type_restriction_enum;
+
@@ -0,0 +1,13 @@
+CREATE TYPE post_type_restriction_enum AS enum (
+    'Any',
+    'TextOnly',
+    'LinkOnly',
+    'NoImages'
+);
+
+ALTER TABLE community
+    ADD COLUMN post_type_restriction post_type_restriction_enum NOT NULL DEFAULT 'Any';
+
+ALTER TABLE community
+    ADD COLUMN nsfw_only boolean NOT NULL DEFAULT FALSE;
+
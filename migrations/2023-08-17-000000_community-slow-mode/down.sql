@@ -0,0 +1,6 @@
+ALTER TABLE community
+    DROP COLUMN post_slow_mode_seconds;
+
+ALTER TABLE community
+    DROP COLUMN comment_slow_mode_seconds;
+
@@ -0,0 +1,7 @@
+-- Minimum number of seconds between posts or comments of a user in the community, 0 disables it
+ALTER TABLE community
+    ADD COLUMN post_slow_mode_seconds int NOT NULL DEFAULT 0;
+
+ALTER TABLE community
+    ADD COLUMN comment_slow_mode_seconds int NOT NULL DEFAULT 0;
+
@@ -0,0 +1,3 @@
+ALTER TABLE post
+    DROP COLUMN contest_mode_until;
+
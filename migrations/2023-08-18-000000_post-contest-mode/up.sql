@@ -0,0 +1,4 @@
+-- While in the future, vote counts are hidden and comments are shown in random order
+ALTER TABLE post
+    ADD COLUMN contest_mode_until timestamp;
+
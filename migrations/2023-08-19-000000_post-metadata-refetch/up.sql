@@ -0,0 +1,9 @@
+-- Posts whose link metadata fetch failed, which are retried by the scheduler with a backoff
+CREATE TABLE post_metadata_refetch (
+    post_id int PRIMARY KEY REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    attempts int NOT NULL DEFAULT 0,
+    next_attempt timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_post_metadata_refetch_next_attempt ON post_metadata_refetch (next_attempt);
+
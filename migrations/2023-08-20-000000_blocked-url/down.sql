@@ -0,0 +1,2 @@
+DROP TABLE blocked_url;
+
@@ -0,0 +1,8 @@
+-- Domains and url prefixes which can't be linked in posts
+CREATE TABLE blocked_url (
+    id serial PRIMARY KEY,
+    url text NOT NULL UNIQUE,
+    published timestamp NOT NULL DEFAULT now(),
+    updated timestamp
+);
+
@@ -0,0 +1,4 @@
+DROP TABLE community_word_filter;
+
+DROP TYPE word_filter_action_enum;
+
@@ -0,0 +1,16 @@
+CREATE TYPE word_filter_action_enum AS enum (
+    'Report',
+    'Remove',
+    'Reject'
+);
+
+-- Additional regex filters which mods apply to content in their community
+CREATE TABLE community_word_filter (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    pattern text NOT NULL,
+    action word_filter_action_enum NOT NULL,
+    published timestamp NOT NULL DEFAULT now(),
+    UNIQUE (community_id, pattern)
+);
+
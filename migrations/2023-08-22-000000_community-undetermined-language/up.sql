@@ -0,0 +1,4 @@
+-- Whether posts and comments without a language are accepted in the community
+ALTER TABLE community
+    ADD COLUMN allow_undetermined_language boolean NOT NULL DEFAULT TRUE;
+
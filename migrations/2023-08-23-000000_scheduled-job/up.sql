@@ -0,0 +1,16 @@
+-- Background jobs of the scheduler. Each row also acts as a lock, so that a job is only run by one
+-- process at a time when multiple lemmy processes share the database.
+CREATE TABLE scheduled_job (
+    name text PRIMARY KEY,
+    interval_seconds int NOT NULL,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    next_run timestamp NOT NULL DEFAULT now(),
+    locked_until timestamp,
+    last_started timestamp,
+    last_finished timestamp,
+    last_duration_ms int,
+    last_error text,
+    run_count int NOT NULL DEFAULT 0,
+    failure_count int NOT NULL DEFAULT 0
+);
+
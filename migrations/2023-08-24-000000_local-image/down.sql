@@ -0,0 +1,2 @@
+DROP TABLE local_image;
+
@@ -0,0 +1,13 @@
+-- Images uploaded to pictrs by local users, and the post or comment they are used in
+CREATE TABLE local_image (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    pictrs_alias text NOT NULL UNIQUE,
+    pictrs_delete_token text NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE SET NULL,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE SET NULL,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_local_image_person ON local_image (person_id);
+
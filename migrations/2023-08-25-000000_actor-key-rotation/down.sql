@@ -0,0 +1,10 @@
+DROP TRIGGER actor_key_rotation ON person;
+
+DROP TRIGGER actor_key_rotation ON community;
+
+DROP TRIGGER actor_key_rotation ON site;
+
+DROP FUNCTION actor_key_rotation;
+
+DROP TABLE actor_key_rotation;
+
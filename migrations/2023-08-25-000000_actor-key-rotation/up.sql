@@ -0,0 +1,41 @@
+-- The previous public key of each actor whose keypair changed, so that signatures which were
+-- created shortly before the rotation can still be verified
+CREATE TABLE actor_key_rotation (
+    id serial PRIMARY KEY,
+    actor_id varchar(255) NOT NULL UNIQUE,
+    previous_public_key text NOT NULL,
+    rotated_at timestamp NOT NULL DEFAULT now()
+);
+
+CREATE FUNCTION actor_key_rotation ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    INSERT INTO actor_key_rotation (actor_id, previous_public_key, rotated_at)
+        VALUES (OLD.actor_id, OLD.public_key, now())
+    ON CONFLICT (actor_id)
+        DO UPDATE SET
+            previous_public_key = excluded.previous_public_key, rotated_at = excluded.rotated_at;
+    RETURN NULL;
+END
+$$;
+
+CREATE TRIGGER actor_key_rotation
+    AFTER UPDATE OF public_key ON person
+    FOR EACH ROW
+    WHEN (OLD.public_key IS DISTINCT FROM NEW.public_key)
+    EXECUTE FUNCTION actor_key_rotation ();
+
+CREATE TRIGGER actor_key_rotation
+    AFTER UPDATE OF public_key ON community
+    FOR EACH ROW
+    WHEN (OLD.public_key IS DISTINCT FROM NEW.public_key)
+    EXECUTE FUNCTION actor_key_rotation ();
+
+CREATE TRIGGER actor_key_rotation
+    AFTER UPDATE OF public_key ON site
+    FOR EACH ROW
+    WHEN (OLD.public_key IS DISTINCT FROM NEW.public_key)
+    EXECUTE FUNCTION actor_key_rotation ();
+
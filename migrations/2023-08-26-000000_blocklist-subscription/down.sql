@@ -0,0 +1,7 @@
+ALTER TABLE federation_blocklist
+    DROP COLUMN subscription_id,
+    DROP COLUMN reason,
+    DROP COLUMN pinned;
+
+DROP TABLE blocklist_subscription;
+
@@ -0,0 +1,18 @@
+-- External blocklists, which are synced into the federation blocklist
+CREATE TABLE blocklist_subscription (
+    id serial PRIMARY KEY,
+    url text NOT NULL UNIQUE,
+    published timestamp NOT NULL DEFAULT now(),
+    last_synced_at timestamp,
+    last_error text
+);
+
+-- Entries without a subscription were added by the admins of this instance. Pinned entries are
+-- never removed by a sync, and are kept when their subscription is deleted.
+ALTER TABLE federation_blocklist
+    ADD COLUMN subscription_id int REFERENCES blocklist_subscription ON UPDATE CASCADE ON DELETE SET NULL,
+    ADD COLUMN reason text,
+    ADD COLUMN pinned boolean NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_federation_blocklist_subscription ON federation_blocklist (subscription_id);
+
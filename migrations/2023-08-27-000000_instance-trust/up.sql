@@ -0,0 +1,11 @@
+-- Trust signals for federated instances, as reported by Fediseer
+CREATE TABLE instance_trust (
+    id serial PRIMARY KEY,
+    instance_id int REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE NOT NULL UNIQUE,
+    guarantor text,
+    endorsements int NOT NULL DEFAULT 0,
+    censures int NOT NULL DEFAULT 0,
+    censure_reasons text,
+    updated timestamp NOT NULL DEFAULT now()
+);
+
@@ -0,0 +1,11 @@
+-- Ed25519 keys for object integrity proofs (FEP-8b32). Remote actors publish theirs as
+-- assertionMethod, keys of local actors are generated when they are first needed.
+CREATE TABLE actor_integrity_key (
+    id serial PRIMARY KEY,
+    actor_id varchar(255) NOT NULL UNIQUE,
+    public_key text NOT NULL,
+    private_key text,
+    published timestamp NOT NULL DEFAULT now(),
+    updated timestamp
+);
+
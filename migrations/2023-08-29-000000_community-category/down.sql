@@ -0,0 +1,3 @@
+DROP TABLE community_category;
+
+DROP TABLE category;
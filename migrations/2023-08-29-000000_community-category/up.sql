@@ -0,0 +1,17 @@
+-- Topics which admins define, so that communities can be discovered by category
+CREATE TABLE category (
+    id serial PRIMARY KEY,
+    name varchar(50) NOT NULL UNIQUE,
+    description text,
+    published timestamp NOT NULL DEFAULT now(),
+    updated timestamp
+);
+
+CREATE TABLE community_category (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    category_id int REFERENCES category ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    UNIQUE (community_id, category_id)
+);
+
+CREATE INDEX idx_community_category_category ON community_category (category_id);
@@ -0,0 +1,14 @@
+-- Subscriber count at the last trending update, to measure growth since then
+ALTER TABLE community_aggregates
+    ADD COLUMN subscribers_day_ago bigint NOT NULL DEFAULT 0,
+    ADD COLUMN trending_rank integer NOT NULL DEFAULT 0;
+
+UPDATE
+    community_aggregates
+SET
+    subscribers_day_ago = subscribers;
+
+CREATE INDEX idx_community_aggregates_trending ON community_aggregates (trending_rank DESC);
+
+ALTER TYPE sort_type_enum
+    ADD VALUE 'Trending';
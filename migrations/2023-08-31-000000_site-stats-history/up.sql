@@ -0,0 +1,33 @@
+-- Daily snapshots of the site aggregates, so that statistics can be shown over time
+CREATE TABLE site_stats_history (
+    id serial PRIMARY KEY,
+    site_id int REFERENCES site ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    day date NOT NULL,
+    users bigint NOT NULL DEFAULT 0,
+    posts bigint NOT NULL DEFAULT 0,
+    comments bigint NOT NULL DEFAULT 0,
+    communities bigint NOT NULL DEFAULT 0,
+    users_active_day bigint NOT NULL DEFAULT 0,
+    users_active_week bigint NOT NULL DEFAULT 0,
+    users_active_month bigint NOT NULL DEFAULT 0,
+    users_active_half_year bigint NOT NULL DEFAULT 0,
+    UNIQUE (site_id, day)
+);
+
+-- Start the history with the current numbers
+INSERT INTO site_stats_history (site_id, day, users, posts, comments, communities, users_active_day, users_active_week, users_active_month, users_active_half_year)
+SELECT
+    sa.site_id,
+    (now() AT TIME ZONE 'utc')::date,
+    sa.users,
+    sa.posts,
+    sa.comments,
+    sa.communities,
+    sa.users_active_day,
+    sa.users_active_week,
+    sa.users_active_month,
+    sa.users_active_half_year
+FROM
+    site_aggregates sa
+    INNER JOIN local_site ls ON ls.site_id = sa.site_id;
+
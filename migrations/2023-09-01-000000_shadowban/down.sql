@@ -0,0 +1,5 @@
+DROP TABLE mod_shadowban;
+
+ALTER TABLE person
+    DROP COLUMN shadowbanned_at;
+
@@ -0,0 +1,13 @@
+-- New content of shadowbanned users is only shown to themselves, mods and admins
+ALTER TABLE person
+    ADD COLUMN shadowbanned_at timestamp;
+
+CREATE TABLE mod_shadowban (
+    id serial PRIMARY KEY,
+    mod_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    other_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    reason text,
+    shadowbanned boolean NOT NULL DEFAULT TRUE,
+    when_ timestamp NOT NULL DEFAULT now()
+);
+
@@ -0,0 +1,8 @@
+-- Email domains which are allowed or blocked for signups
+CREATE TABLE email_domain (
+    id serial PRIMARY KEY,
+    domain text NOT NULL UNIQUE,
+    allowed boolean NOT NULL,
+    published timestamp NOT NULL DEFAULT now()
+);
+
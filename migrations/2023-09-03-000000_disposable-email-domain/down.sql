@@ -0,0 +1,2 @@
+DROP TABLE disposable_email_domain;
+
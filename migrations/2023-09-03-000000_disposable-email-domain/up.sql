@@ -0,0 +1,9 @@
+-- Disposable email domains fetched from the configured list, and domains which admins don't
+-- consider disposable
+CREATE TABLE disposable_email_domain (
+    id serial PRIMARY KEY,
+    domain text NOT NULL UNIQUE,
+    exception boolean NOT NULL,
+    published timestamp NOT NULL DEFAULT now()
+);
+
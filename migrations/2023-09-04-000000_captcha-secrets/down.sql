@@ -0,0 +1,5 @@
+ALTER TABLE secret
+    DROP COLUMN hcaptcha_secret,
+    DROP COLUMN mcaptcha_secret,
+    DROP COLUMN turnstile_secret;
+
@@ -0,0 +1,6 @@
+-- Secret keys for hosted captcha services
+ALTER TABLE secret
+    ADD COLUMN hcaptcha_secret text,
+    ADD COLUMN mcaptcha_secret text,
+    ADD COLUMN turnstile_secret text;
+
@@ -0,0 +1,8 @@
+-- Individual rate limits for trusted users and bots, or problem users, in percent of the site limits
+CREATE TABLE rate_limit_override (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL UNIQUE,
+    limit_percent int NOT NULL,
+    published timestamp NOT NULL DEFAULT now()
+);
+
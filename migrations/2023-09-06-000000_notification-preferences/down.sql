@@ -0,0 +1,6 @@
+ALTER TABLE local_user
+    DROP COLUMN notify_comment_replies,
+    DROP COLUMN notify_post_replies,
+    DROP COLUMN notify_mentions,
+    DROP COLUMN notify_private_messages;
+
@@ -0,0 +1,7 @@
+-- Per type notification preferences. send_notifications_to_email still turns off all emails.
+ALTER TABLE local_user
+    ADD COLUMN notify_comment_replies boolean NOT NULL DEFAULT TRUE,
+    ADD COLUMN notify_post_replies boolean NOT NULL DEFAULT TRUE,
+    ADD COLUMN notify_mentions boolean NOT NULL DEFAULT TRUE,
+    ADD COLUMN notify_private_messages boolean NOT NULL DEFAULT TRUE;
+
@@ -0,0 +1,4 @@
+DROP TABLE community_notification;
+
+DROP TABLE post_notification;
+
@@ -0,0 +1,23 @@
+-- Users who want to be notified about new posts in a community they follow
+CREATE TABLE community_notification (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    -- At most one notification per interval, so that busy communities don't flood the inbox
+    min_interval_minutes int NOT NULL DEFAULT 0,
+    last_notified timestamp,
+    published timestamp NOT NULL DEFAULT now(),
+    UNIQUE (person_id, community_id)
+);
+
+CREATE TABLE post_notification (
+    id serial PRIMARY KEY,
+    recipient_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    read boolean NOT NULL DEFAULT FALSE,
+    published timestamp NOT NULL DEFAULT now(),
+    UNIQUE (recipient_id, post_id)
+);
+
+CREATE INDEX idx_community_notification_community ON community_notification (community_id);
+
@@ -0,0 +1,4 @@
+DROP TABLE thread_subscription;
+
+ALTER TABLE local_user
+    DROP COLUMN auto_subscribe_threads;
@@ -0,0 +1,20 @@
+-- Users who want to be notified about all new comments in a post, or below a comment
+CREATE TABLE thread_subscription (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    -- Null for a subscription to the whole post
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    published timestamp NOT NULL DEFAULT now(),
+    UNIQUE (person_id, post_id, comment_id)
+);
+
+CREATE UNIQUE INDEX idx_thread_subscription_post ON thread_subscription (person_id, post_id)
+WHERE
+    comment_id IS NULL;
+
+CREATE INDEX idx_thread_subscription_post_id ON thread_subscription (post_id);
+
+ALTER TABLE local_user
+    ADD COLUMN auto_subscribe_threads boolean NOT NULL DEFAULT FALSE;
+
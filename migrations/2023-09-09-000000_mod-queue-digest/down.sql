@@ -0,0 +1,3 @@
+ALTER TABLE local_user
+    DROP COLUMN email_mod_queue_digest,
+    DROP COLUMN mod_queue_digest_sent;
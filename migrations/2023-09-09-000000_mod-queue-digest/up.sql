@@ -0,0 +1,5 @@
+-- Opt-in email digest of new reports, registration applications and automod removals for mods
+ALTER TABLE local_user
+    ADD COLUMN email_mod_queue_digest boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN mod_queue_digest_sent timestamp;
+
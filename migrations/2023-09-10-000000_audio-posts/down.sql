@@ -0,0 +1,4 @@
+ALTER TABLE post
+    DROP COLUMN audio_mime_type,
+    DROP COLUMN audio_duration;
+
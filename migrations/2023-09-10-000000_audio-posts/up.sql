@@ -0,0 +1,5 @@
+-- Audio posts link to an audio file, which is played in the post instead of shown as a link
+ALTER TABLE post
+    ADD COLUMN audio_mime_type varchar(255),
+    ADD COLUMN audio_duration integer;
+
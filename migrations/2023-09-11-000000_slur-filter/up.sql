@@ -0,0 +1,11 @@
+-- Named slur filters which apply on top of the site slur filter, only to content in their language
+-- and/or community
+CREATE TABLE slur_filter (
+    id serial PRIMARY KEY,
+    name text NOT NULL UNIQUE,
+    pattern text NOT NULL,
+    language_id int REFERENCES LANGUAGE ON UPDATE CASCADE ON DELETE CASCADE,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    published timestamp NOT NULL DEFAULT now()
+);
+
@@ -0,0 +1,11 @@
+ALTER TABLE post_report
+    DROP COLUMN reason_id;
+
+ALTER TABLE comment_report
+    DROP COLUMN reason_id;
+
+ALTER TABLE private_message_report
+    DROP COLUMN reason_id;
+
+DROP TABLE report_reason;
+
@@ -0,0 +1,22 @@
+-- Reasons which can be picked when reporting content. Site wide reasons are defined by admins,
+-- others by the mods of a community.
+CREATE TABLE report_reason (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    published timestamp NOT NULL DEFAULT now(),
+    UNIQUE (community_id, name)
+);
+
+INSERT INTO report_reason (name)
+    VALUES ('Spam'), ('Harassment'), ('Illegal content');
+
+ALTER TABLE post_report
+    ADD COLUMN reason_id int REFERENCES report_reason ON UPDATE CASCADE ON DELETE SET NULL;
+
+ALTER TABLE comment_report
+    ADD COLUMN reason_id int REFERENCES report_reason ON UPDATE CASCADE ON DELETE SET NULL;
+
+ALTER TABLE private_message_report
+    ADD COLUMN reason_id int REFERENCES report_reason ON UPDATE CASCADE ON DELETE SET NULL;
+
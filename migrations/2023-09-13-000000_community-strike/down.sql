@@ -0,0 +1,8 @@
+ALTER TABLE community
+    DROP COLUMN strike_ban_threshold;
+
+ALTER TABLE community
+    DROP COLUMN strike_ban_days;
+
+DROP TABLE community_strike;
+
@@ -0,0 +1,22 @@
+-- Strikes which mods gave users in a community, when removing their content or banning them
+CREATE TABLE community_strike (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    mod_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE SET NULL,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE SET NULL,
+    reason text,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_community_strike_community_person ON community_strike (community_id, person_id);
+
+-- Number of strikes after which a user is temporarily banned from the community, 0 disables it
+ALTER TABLE community
+    ADD COLUMN strike_ban_threshold int NOT NULL DEFAULT 0;
+
+-- Length of the temporary ban in days
+ALTER TABLE community
+    ADD COLUMN strike_ban_days int NOT NULL DEFAULT 7;
+
@@ -0,0 +1,7 @@
+ALTER TABLE post_report
+    DROP COLUMN rule_id;
+
+ALTER TABLE comment_report
+    DROP COLUMN rule_id;
+
+DROP TABLE community_rule;
@@ -0,0 +1,17 @@
+-- Rules of a community, shown in the order of their position.
+CREATE TABLE community_rule (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    position int NOT NULL DEFAULT 0,
+    title text NOT NULL,
+    description text,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_community_rule_community ON community_rule (community_id);
+
+ALTER TABLE post_report
+    ADD COLUMN rule_id int REFERENCES community_rule ON UPDATE CASCADE ON DELETE SET NULL;
+
+ALTER TABLE comment_report
+    ADD COLUMN rule_id int REFERENCES community_rule ON UPDATE CASCADE ON DELETE SET NULL;
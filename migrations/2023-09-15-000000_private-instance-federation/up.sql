@@ -0,0 +1,4 @@
+-- Lets private instances federate with the instances in the allowlist
+ALTER TABLE local_site
+    ADD COLUMN private_instance_federation boolean NOT NULL DEFAULT false;
+
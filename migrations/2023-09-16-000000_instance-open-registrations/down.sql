@@ -0,0 +1,3 @@
+ALTER TABLE instance
+    DROP COLUMN open_registrations;
+
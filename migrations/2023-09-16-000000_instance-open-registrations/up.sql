@@ -0,0 +1,4 @@
+-- Whether the instance accepts new users, as reported in its nodeinfo
+ALTER TABLE instance
+    ADD COLUMN open_registrations boolean;
+
@@ -0,0 +1,12 @@
+-- Every attempt to deliver a sent activity to an inbox, with the HTTP status of the response. The
+-- status is null if the inbox couldn't be reached at all.
+CREATE TABLE sent_activity_delivery (
+    id bigserial PRIMARY KEY,
+    ap_id text NOT NULL,
+    inbox text NOT NULL,
+    status integer,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_sent_activity_delivery_ap_id ON sent_activity_delivery (ap_id);
+
@@ -0,0 +1,3 @@
+DROP FUNCTION community_unread_posts;
+
+DROP TABLE community_last_seen;
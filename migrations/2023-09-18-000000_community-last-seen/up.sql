@@ -0,0 +1,28 @@
+-- When a user last looked at the posts of a community, so that the number of new posts since then
+-- can be shown as an unread badge.
+CREATE TABLE community_last_seen (
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    last_seen timestamp NOT NULL DEFAULT now(),
+    PRIMARY KEY (person_id, community_id)
+);
+
+-- Number of posts in a community which are newer than the last visit of the person. Returns 0 if
+-- the person never visited the community.
+CREATE FUNCTION community_unread_posts (person_id_ int, community_id_ int)
+    RETURNS bigint
+    LANGUAGE sql
+    STABLE
+    AS $$
+    SELECT
+        count(*)
+    FROM
+        post p
+        INNER JOIN community_last_seen s ON s.community_id = p.community_id
+    WHERE
+        s.person_id = person_id_
+        AND p.community_id = community_id_
+        AND p.published > s.last_seen
+        AND NOT p.deleted
+        AND NOT p.removed
+$$;
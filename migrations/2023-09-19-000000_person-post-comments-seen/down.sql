@@ -0,0 +1,3 @@
+ALTER TABLE person_post_aggregates
+    DROP COLUMN comments_seen,
+    DROP COLUMN previous_comments_seen;
@@ -0,0 +1,5 @@
+-- When the person last listed the comments of the post, and the visit before that. Comments newer
+-- than the previous visit are marked as new.
+ALTER TABLE person_post_aggregates
+    ADD COLUMN comments_seen timestamp,
+    ADD COLUMN previous_comments_seen timestamp;
@@ -0,0 +1,8 @@
+ALTER TABLE community
+    DROP COLUMN min_account_age_days;
+
+ALTER TABLE community
+    DROP COLUMN min_site_karma;
+
+ALTER TABLE community
+    DROP COLUMN min_community_karma;
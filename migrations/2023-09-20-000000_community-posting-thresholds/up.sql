@@ -0,0 +1,10 @@
+-- Requirements for posting or commenting in the community, 0 disables them. Remote content of
+-- users below them is held for review.
+ALTER TABLE community
+    ADD COLUMN min_account_age_days int NOT NULL DEFAULT 0;
+
+ALTER TABLE community
+    ADD COLUMN min_site_karma int NOT NULL DEFAULT 0;
+
+ALTER TABLE community
+    ADD COLUMN min_community_karma int NOT NULL DEFAULT 0;
@@ -0,0 +1,11 @@
+-- The score of each person's posts and comments within a community. Recalculated regularly by a
+-- scheduled job.
+CREATE TABLE community_person_aggregates (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_score bigint NOT NULL DEFAULT 0,
+    comment_score bigint NOT NULL DEFAULT 0,
+    updated timestamp NOT NULL DEFAULT now(),
+    UNIQUE (community_id, person_id)
+);
@@ -0,0 +1,12 @@
+-- Private notes which users attach to other users, optionally with a color to show them as a tag.
+-- Only visible to the user who wrote them.
+CREATE TABLE person_note (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    target_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    note text NOT NULL,
+    color text,
+    published timestamp NOT NULL DEFAULT now(),
+    updated timestamp,
+    UNIQUE (person_id, target_id)
+);
@@ -0,0 +1,3 @@
+ALTER TABLE person_block
+    DROP COLUMN expires;
+
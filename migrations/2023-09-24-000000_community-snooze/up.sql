@@ -0,0 +1,9 @@
+-- Communities which a user hid from their feeds until the given time, without unsubscribing.
+CREATE TABLE community_snooze (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    expires timestamp NOT NULL,
+    published timestamp NOT NULL DEFAULT now(),
+    UNIQUE (person_id, community_id)
+);
@@ -0,0 +1,4 @@
+DROP TABLE login_session;
+
+ALTER TABLE secret
+    ADD COLUMN jwt_secret varchar NOT NULL DEFAULT gen_random_uuid ();
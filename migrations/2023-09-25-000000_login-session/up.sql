@@ -0,0 +1,15 @@
+-- Server side login sessions, which replace the stateless JWTs. Only a hash of each token is
+-- stored, so that a leaked database can't be used to log in.
+CREATE TABLE login_session (
+    id serial PRIMARY KEY,
+    token_hash text NOT NULL UNIQUE,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    ip text,
+    user_agent text,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_login_session_local_user ON login_session (local_user_id);
+
+ALTER TABLE secret
+    DROP COLUMN jwt_secret;
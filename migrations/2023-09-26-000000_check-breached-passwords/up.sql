@@ -0,0 +1,4 @@
+-- Reject passwords which are known from data breaches on signup and password change
+ALTER TABLE local_site
+    ADD COLUMN check_breached_passwords boolean NOT NULL DEFAULT false;
+
@@ -0,0 +1,4 @@
+-- Email users when they log in from a new device or network
+ALTER TABLE local_user
+    ADD COLUMN email_login_alerts boolean NOT NULL DEFAULT TRUE;
+
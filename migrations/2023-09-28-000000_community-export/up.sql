@@ -0,0 +1,14 @@
+-- Archives of communities which mods and admins requested for backups or migrations. They are
+-- generated in the background, so the archive stays empty until finished is set.
+CREATE TABLE community_export (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    archive jsonb,
+    error text,
+    published timestamp NOT NULL DEFAULT now(),
+    finished timestamp
+);
+
+CREATE INDEX idx_community_export_community ON community_export (community_id);
+
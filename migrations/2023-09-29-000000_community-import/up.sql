@@ -0,0 +1,16 @@
+-- Imports of community archives into new local communities. The progress is updated while the
+-- posts and comments are created in the background.
+CREATE TABLE community_import (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    posts_imported int NOT NULL DEFAULT 0,
+    comments_imported int NOT NULL DEFAULT 0,
+    skipped int NOT NULL DEFAULT 0,
+    error text,
+    published timestamp NOT NULL DEFAULT now(),
+    finished timestamp
+);
+
+CREATE INDEX idx_community_import_community ON community_import (community_id);
+
@@ -0,0 +1,4 @@
+-- Count 👍 and 👎 reactions from other platforms as votes instead of storing them as reactions
+ALTER TABLE local_site
+    ADD COLUMN emoji_reactions_as_votes boolean NOT NULL DEFAULT false;
+
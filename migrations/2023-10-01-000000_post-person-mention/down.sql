@@ -0,0 +1,9 @@
+DELETE FROM person_mention
+WHERE post_id IS NOT NULL;
+
+ALTER TABLE person_mention
+    DROP CONSTRAINT person_mention_comment_or_post,
+    DROP CONSTRAINT person_mention_recipient_id_post_id_key,
+    DROP COLUMN post_id,
+    ALTER COLUMN comment_id SET NOT NULL;
+
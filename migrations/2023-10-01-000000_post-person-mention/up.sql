@@ -0,0 +1,7 @@
+-- Mentions can be made in post bodies as well as in comments
+ALTER TABLE person_mention
+    ALTER COLUMN comment_id DROP NOT NULL,
+    ADD COLUMN post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    ADD CONSTRAINT person_mention_comment_or_post CHECK (num_nonnulls (comment_id, post_id) = 1),
+    ADD CONSTRAINT person_mention_recipient_id_post_id_key UNIQUE (recipient_id, post_id);
+
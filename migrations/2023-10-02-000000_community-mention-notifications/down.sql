@@ -0,0 +1,3 @@
+ALTER TABLE community
+    DROP COLUMN notify_mods_on_mention;
+
@@ -0,0 +1,4 @@
+-- Notify the mods of a local community when it is mentioned in a post or comment
+ALTER TABLE community
+    ADD COLUMN notify_mods_on_mention boolean NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,38 @@
+DROP TABLE invite_use;
+
+DROP TABLE invite;
+
+ALTER TABLE local_site
+    DROP COLUMN users_can_create_invites;
+
+UPDATE
+    local_site
+SET
+    registration_mode = 'Closed'
+WHERE
+    registration_mode = 'Invite';
+
+-- rename the old enum
+ALTER TYPE registration_mode_enum RENAME TO registration_mode_enum__;
+
+-- create the new enum
+CREATE TYPE registration_mode_enum AS ENUM (
+    'Closed',
+    'RequireApplication',
+    'Open'
+);
+
+-- alter all you enum columns
+ALTER TABLE local_site
+    ALTER COLUMN registration_mode DROP DEFAULT;
+
+ALTER TABLE local_site
+    ALTER COLUMN registration_mode TYPE registration_mode_enum
+    USING registration_mode::text::registration_mode_enum;
+
+ALTER TABLE local_site
+    ALTER COLUMN registration_mode SET DEFAULT 'RequireApplication';
+
+-- drop the old enum
+DROP TYPE registration_mode_enum__;
+
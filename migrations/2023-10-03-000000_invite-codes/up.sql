@@ -0,0 +1,29 @@
+-- Registration which requires an invite code
+ALTER TYPE registration_mode_enum
+    ADD VALUE 'Invite';
+
+ALTER TABLE local_site
+    ADD COLUMN users_can_create_invites boolean NOT NULL DEFAULT false;
+
+CREATE TABLE invite (
+    id serial PRIMARY KEY,
+    code text NOT NULL UNIQUE,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    max_uses int NOT NULL,
+    uses int NOT NULL DEFAULT 0,
+    expires timestamp,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_invite_creator ON invite (creator_id);
+
+-- Which invite each user registered with
+CREATE TABLE invite_use (
+    id serial PRIMARY KEY,
+    invite_id int REFERENCES invite ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL UNIQUE,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_invite_use_invite ON invite_use (invite_id);
+
@@ -0,0 +1,11 @@
+DROP TABLE community_application;
+
+ALTER TABLE local_site
+    DROP COLUMN community_creation_min_account_age_days;
+
+ALTER TABLE local_site
+    DROP COLUMN community_creation_min_karma;
+
+ALTER TABLE local_site
+    DROP COLUMN community_creation_requires_approval;
+
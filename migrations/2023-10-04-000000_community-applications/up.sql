@@ -0,0 +1,29 @@
+-- Requirements for users to create communities, 0 disables them. Admins are exempt.
+ALTER TABLE local_site
+    ADD COLUMN community_creation_min_account_age_days int NOT NULL DEFAULT 0;
+
+ALTER TABLE local_site
+    ADD COLUMN community_creation_min_karma int NOT NULL DEFAULT 0;
+
+-- New communities of non-admins have to be approved by an admin
+ALTER TABLE local_site
+    ADD COLUMN community_creation_requires_approval boolean NOT NULL DEFAULT false;
+
+CREATE TABLE community_application (
+    id serial PRIMARY KEY,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name varchar(255) NOT NULL,
+    title varchar(255) NOT NULL,
+    description text,
+    nsfw boolean NOT NULL DEFAULT false,
+    -- The full community creation request, which is used once it is approved
+    form jsonb NOT NULL,
+    admin_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    deny_reason text,
+    -- The community which was created on approval
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE SET NULL,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_community_application_creator ON community_application (creator_id);
+
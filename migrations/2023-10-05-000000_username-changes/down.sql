@@ -0,0 +1,8 @@
+DROP TABLE person_alias;
+
+ALTER TABLE local_site
+    DROP COLUMN max_username_changes;
+
+ALTER TABLE local_site
+    DROP COLUMN username_reuse_cooldown_days;
+
@@ -0,0 +1,20 @@
+-- Local users can change their username a limited number of times
+ALTER TABLE local_site
+    ADD COLUMN max_username_changes int NOT NULL DEFAULT 0;
+
+ALTER TABLE local_site
+    ADD COLUMN username_reuse_cooldown_days int NOT NULL DEFAULT 90;
+
+-- Former names of local users, so that their old actor ids keep working
+CREATE TABLE person_alias (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name varchar(255) NOT NULL,
+    actor_id varchar(255) NOT NULL,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_person_alias_person ON person_alias (person_id);
+
+CREATE INDEX idx_person_alias_lower_name ON person_alias (lower(name));
+
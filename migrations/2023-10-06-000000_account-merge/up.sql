@@ -0,0 +1,10 @@
+-- When an admin merges a duplicate local account into another one
+CREATE TABLE admin_merge_person (
+    id serial PRIMARY KEY,
+    admin_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    source_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    target_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    reason text,
+    when_ timestamp NOT NULL DEFAULT now()
+);
+
@@ -0,0 +1,2 @@
+DROP TABLE magic_login_token;
+
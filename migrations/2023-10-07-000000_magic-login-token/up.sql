@@ -0,0 +1,12 @@
+-- Single use tokens for passwordless login via an emailed link. Like login sessions, only a hash
+-- of each token is stored.
+CREATE TABLE magic_login_token (
+    id serial PRIMARY KEY,
+    token_hash text NOT NULL UNIQUE,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    published timestamp NOT NULL DEFAULT now(),
+    expires timestamp NOT NULL
+);
+
+CREATE INDEX idx_magic_login_token_local_user ON magic_login_token (local_user_id);
+
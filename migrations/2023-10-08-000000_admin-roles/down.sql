@@ -0,0 +1,5 @@
+ALTER TABLE local_user
+    DROP COLUMN admin_role;
+
+DROP TYPE admin_role_enum;
+
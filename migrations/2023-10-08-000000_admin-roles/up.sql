@@ -0,0 +1,11 @@
+CREATE TYPE admin_role_enum AS enum (
+    'UserAdmin',
+    'ContentAdmin',
+    'FederationAdmin',
+    'FullAdmin'
+);
+
+-- Limits what an admin can do. Existing admins keep all their permissions.
+ALTER TABLE local_user
+    ADD COLUMN admin_role admin_role_enum NOT NULL DEFAULT 'FullAdmin';
+
@@ -0,0 +1,4 @@
+DROP TABLE announcement;
+
+DROP TYPE announcement_severity_enum;
+
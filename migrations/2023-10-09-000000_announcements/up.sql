@@ -0,0 +1,18 @@
+CREATE TYPE announcement_severity_enum AS enum (
+    'Info',
+    'Warning',
+    'Critical'
+);
+
+-- Site wide banners, eg to warn about maintenance. They are only shown between their start and end time.
+CREATE TABLE announcement (
+    id serial PRIMARY KEY,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    message text NOT NULL,
+    severity announcement_severity_enum NOT NULL DEFAULT 'Info',
+    start_time timestamp NOT NULL DEFAULT now(),
+    end_time timestamp,
+    published timestamp NOT NULL DEFAULT now(),
+    updated timestamp
+);
+
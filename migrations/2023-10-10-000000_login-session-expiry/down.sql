@@ -0,0 +1,3 @@
+ALTER TABLE login_session
+    DROP COLUMN expires;
+
@@ -0,0 +1,10 @@
+-- Login sessions end when they aren't used for a while. Existing sessions get the default of 90
+-- days from now.
+ALTER TABLE login_session
+    ADD COLUMN expires timestamp NOT NULL DEFAULT now() + interval '90 days';
+
+ALTER TABLE login_session
+    ALTER COLUMN expires DROP DEFAULT;
+
+CREATE INDEX idx_login_session_expires ON login_session (expires);
+
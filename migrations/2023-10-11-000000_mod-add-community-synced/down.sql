@@ -0,0 +1,6 @@
+DELETE FROM mod_add_community
+WHERE mod_person_id IS NULL;
+
+ALTER TABLE mod_add_community
+    ALTER COLUMN mod_person_id SET NOT NULL;
+
@@ -0,0 +1,5 @@
+-- Moderator changes which are synced from the moderators collection of a remote community don't say
+-- who made them, so they are logged without a moderator
+ALTER TABLE mod_add_community
+    ALTER COLUMN mod_person_id DROP NOT NULL;
+
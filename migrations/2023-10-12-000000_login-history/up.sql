@@ -0,0 +1,13 @@
+-- The networks and devices which users logged in from, so that logins from new ones can be
+-- recognized even after the earlier sessions ended. Only coarse values are stored, not the exact
+-- IP address or user agent.
+CREATE TABLE login_history (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    ip_network text,
+    device text,
+    published timestamp NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_login_history_local_user ON login_history (local_user_id, published);
+
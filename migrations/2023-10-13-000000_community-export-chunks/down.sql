@@ -0,0 +1,16 @@
+ALTER TABLE community_export
+    ADD COLUMN archive jsonb;
+
+UPDATE
+    community_export
+SET
+    archive = (
+        SELECT
+            string_agg(data, '' ORDER BY seq)::jsonb
+        FROM
+            community_export_chunk
+        WHERE
+            export_id = community_export.id);
+
+DROP TABLE community_export_chunk;
+
@@ -0,0 +1,13 @@
+-- Archives are stored in pieces, so that they don't need to be held in memory as a whole while
+-- they are generated or downloaded. Concatenated in order of seq, the chunks form the json
+-- document.
+CREATE TABLE community_export_chunk (
+    export_id int REFERENCES community_export ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    seq int NOT NULL,
+    data text NOT NULL,
+    PRIMARY KEY (export_id, seq)
+);
+
+ALTER TABLE community_export
+    DROP COLUMN archive;
+
@@ -0,0 +1,5 @@
+ALTER TABLE community_import
+    DROP COLUMN updated;
+
+DROP TABLE community_import_object;
+
@@ -0,0 +1,15 @@
+-- Posts and comments which were created by community imports, by their activitypub id in the
+-- archive. Importing the same archive again doesn't duplicate them, and an interrupted import
+-- continues where it stopped.
+CREATE TABLE community_import_object (
+    source_ap_id text PRIMARY KEY,
+    import_id int REFERENCES community_import ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE
+);
+
+-- When the import last made progress. Imports without progress for a while were interrupted by
+-- a restart.
+ALTER TABLE community_import
+    ADD COLUMN updated timestamp;
+
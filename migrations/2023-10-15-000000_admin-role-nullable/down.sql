@@ -0,0 +1,11 @@
+UPDATE
+    local_user
+SET
+    admin_role = 'FullAdmin'
+WHERE
+    admin_role IS NULL;
+
+ALTER TABLE local_user
+    ALTER COLUMN admin_role SET DEFAULT 'FullAdmin',
+    ALTER COLUMN admin_role SET NOT NULL;
+
@@ -0,0 +1,15 @@
+-- Only admins have a role, so that a user who becomes admin doesn't get all permissions by default.
+ALTER TABLE local_user
+    ALTER COLUMN admin_role DROP NOT NULL,
+    ALTER COLUMN admin_role DROP DEFAULT;
+
+UPDATE
+    local_user
+SET
+    admin_role = NULL
+FROM
+    person
+WHERE
+    local_user.person_id = person.id
+    AND NOT person.admin;
+
@@ -1,21 +1,100 @@
 use actix_web::{guard, web, Error, HttpResponse, Result};
 use lemmy_api::{
-  comment::{distinguish::distinguish_comment, like::like_comment, save::save_comment},
+  comment::{
+    distinguish::distinguish_comment,
+    like::like_comment,
+    list_comment_likes::list_comment_likes,
+    react::react_to_comment,
+    save::save_comment,
+    subscribe::subscribe_to_thread,
+  },
   comment_report::{
     create::create_comment_report,
     list::list_comment_reports,
     resolve::resolve_comment_report,
   },
   community::{
+    accept_transfer::accept_community_transfer,
     add_mod::add_mod_to_community,
+    approve_follower::approve_follower,
     ban::ban_from_community,
     block::block_community,
+    export::{download_community_export, export_community, get_community_export},
     follow::follow_community,
     hide::hide_community,
+    import::{get_community_import, import_community, COMMUNITY_IMPORT_MAX_BYTES},
+    list_hidden::list_hidden_communities,
+    list_pending_follows::list_pending_follows,
+    notification::set_community_notification,
+    rule::{
+      create::create_community_rule,
+      delete::delete_community_rule,
+      update::update_community_rule,
+    },
+    snooze::snooze_community,
+    strikes::list_community_strikes,
+    word_filter::{
+      create::create_community_word_filter,
+      delete::delete_community_word_filter,
+      list::list_community_word_filters,
+    },
+  },
+  local_user::{
+    ban_person::ban_from_site,
+    change_password::change_password,
+    change_username::change_username,
+    force_logout::force_logout,
+    invite::{create_invite, list_invite_uses, list_invites, revoke_invite},
+    list_logins::list_logins,
+    list_media::list_media,
+    list_shadowbanned::list_shadowbanned,
+    login::login,
+    logout::logout,
+    magic_link::{redeem_magic_link, request_magic_link},
+    merge_person::merge_person,
+    note::set_person_note,
+    notifications::{
+      list_post_notifications::list_post_notifications,
+      mark_post_notification_read::mark_post_notification_as_read,
+      mark_reply_read::mark_reply_as_read,
+    },
+    revoke_login::revoke_login,
+    shadowban_person::shadowban_from_site,
+  },
+  post::{
+    contest_mode::set_contest_mode,
+    feature::feature_post,
+    like::like_post,
+    list_post_likes::list_post_likes,
+    lock::lock_post,
+    react::react_to_post,
   },
-  local_user::{ban_person::ban_from_site, notifications::mark_reply_read::mark_reply_as_read},
-  post::{feature::feature_post, like::like_post, lock::lock_post},
   post_report::create::create_post_report,
+  report_reason::{
+    create::create_report_reason,
+    delete::delete_report_reason,
+    list::list_report_reasons,
+  },
+  site::{
+    activity_deliveries::list_activity_deliveries,
+    captcha_secrets::set_captcha_secrets,
+    dashboard::get_admin_dashboard,
+    federation_blocklist::{list::list_blocked_instances, pin::pin_blocked_instance},
+    instance_deliveries::list_instance_deliveries,
+    instance_trust::list_instance_trust,
+    preview_markdown::preview_markdown,
+    rate_limit::{
+      edit_rate_limits,
+      get_rate_limits,
+      list_rate_limit_overrides,
+      set_rate_limit_override,
+    },
+    replay_activity::replay_activity,
+    requeue_deliveries::requeue_failed_deliveries,
+    rotate_keys::rotate_actor_keys,
+    scheduled_job::{edit::edit_scheduled_job, list::list_scheduled_jobs, run::run_scheduled_job},
+    stats_history::get_site_stats_history,
+  },
   Perform,
 };
 use lemmy_api_common::{
@@ -24,14 +103,12 @@ use lemmy_api_common::{
   person::{
     AddAdmin,
     BlockPerson,
-    ChangePassword,
     GetBannedPersons,
     GetCaptcha,
     GetPersonMentions,
     GetReplies,
     GetReportCount,
     GetUnreadCount,
-    Login,
     MarkAllAsRead,
     MarkPersonMentionAsRead,
     PasswordChangeAfterReset,
@@ -60,6 +137,25 @@ use lemmy_api_common::{
   },
 };
 use lemmy_api_crud::{
+  announcement::{
+    create::create_announcement,
+    delete::delete_announcement,
+    list::list_announcements,
+    update::update_announcement,
+  },
+  blocked_url::{
+    create::create_blocked_url,
+    delete::delete_blocked_url,
+    list::list_blocked_urls,
+    update::update_blocked_url,
+  },
+  blocklist_subscription::{
+    create::create_blocklist_subscription,
+    delete::delete_blocklist_subscription,
+    list::list_blocklist_subscriptions,
+    sync::sync_blocklist_subscription_now,
+  },
+  category::{create::create_category, delete::delete_category, update::update_category},
   comment::{
     create::create_comment,
     delete::delete_comment,
@@ -68,6 +164,11 @@ use lemmy_api_crud::{
     update::update_comment,
   },
   community::{
+    application::{
+      approve_community_application,
+      create_community_application,
+      list_community_applications,
+    },
     create::create_community,
     delete::delete_community,
     list::list_communities,
@@ -93,15 +194,25 @@ use lemmy_api_crud::{
     update::update_private_message,
   },
   site::{create::create_site, read::get_site, update::update_site},
+  slur_filter::{create::create_slur_filter, delete::delete_slur_filter, list::list_slur_filters},
+  tagline::{
+    create::create_tagline,
+    delete::delete_tagline,
+    list::list_taglines,
+    update::update_tagline,
+  },
   user::{create::register, delete::delete_account},
 };
 use lemmy_apub::{
   api::{
+    list_comment_children::list_comment_children,
     list_comments::list_comments,
     list_posts::list_posts,
     read_community::get_community,
     read_person::read_person,
+    refresh_object::refresh_object,
     resolve_object::resolve_object,
+    resolve_objects::resolve_objects,
     search::search,
   },
   SendActivity,
@@ -117,6 +228,7 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
         web::scope("/site")
           .wrap(rate_limit.message())
           .route("", web::get().to(get_site))
+          .route("/stats_history", web::get().to(get_site_stats_history))
           // Admin Actions
           .route("", web::post().to(create_site))
           .route("", web::put().to(update_site)),
@@ -126,6 +238,11 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .wrap(rate_limit.message())
           .route(web::get().to(route_get::<GetModlog>)),
       )
+      .service(
+        web::resource("/markdown/preview")
+          .wrap(rate_limit.message())
+          .route(web::post().to(preview_markdown)),
+      )
       .service(
         web::resource("/search")
           .wrap(rate_limit.search())
@@ -136,6 +253,16 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .wrap(rate_limit.message())
           .route(web::get().to(resolve_object)),
       )
+      .service(
+        web::resource("/resolve_objects")
+          .wrap(rate_limit.search())
+          .route(web::post().to(resolve_objects)),
+      )
+      .service(
+        web::resource("/refresh_object")
+          .wrap(rate_limit.search())
+          .route(web::post().to(refresh_object)),
+      )
       // Community
       .service(
         web::resource("/community")
@@ -143,21 +270,62 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .wrap(rate_limit.register())
           .route(web::post().to(create_community)),
       )
+      .service(
+        web::resource("/community/application")
+          .guard(guard::Post())
+          .wrap(rate_limit.register())
+          .route(web::post().to(create_community_application)),
+      )
       .service(
         web::scope("/community")
           .wrap(rate_limit.message())
           .route("", web::get().to(get_community))
           .route("", web::put().to(update_community))
           .route("/hide", web::put().to(hide_community))
+          .route("/hidden", web::get().to(list_hidden_communities))
           .route("/list", web::get().to(list_communities))
+          .route(
+            "/application/list",
+            web::get().to(list_community_applications),
+          )
           .route("/follow", web::post().to(follow_community))
+          .route("/notification", web::put().to(set_community_notification))
+          .route("/pending_follows", web::get().to(list_pending_follows))
+          .route("/pending_follows/approve", web::post().to(approve_follower))
           .route("/block", web::post().to(block_community))
+          .route("/snooze", web::put().to(snooze_community))
           .route("/delete", web::post().to(delete_community))
           // Mod Actions
           .route("/remove", web::post().to(remove_community))
           .route("/transfer", web::post().to(route_post::<TransferCommunity>))
+          .route(
+            "/transfer/accept",
+            web::post().to(accept_community_transfer),
+          )
           .route("/ban_user", web::post().to(ban_from_community))
-          .route("/mod", web::post().to(add_mod_to_community)),
+          .route("/export", web::post().to(export_community))
+          .route("/export", web::get().to(get_community_export))
+          .route("/export/download", web::get().to(download_community_export))
+          .service(
+            web::resource("/import")
+              .app_data(web::JsonConfig::default().limit(COMMUNITY_IMPORT_MAX_BYTES))
+              .route(web::post().to(import_community))
+              .route(web::get().to(get_community_import)),
+          )
+          .route("/mod", web::post().to(add_mod_to_community))
+          .route("/strikes", web::get().to(list_community_strikes))
+          .route("/rule", web::post().to(create_community_rule))
+          .route("/rule", web::put().to(update_community_rule))
+          .route("/rule/delete", web::post().to(delete_community_rule))
+          .route("/word_filter", web::post().to(create_community_word_filter))
+          .route(
+            "/word_filter/delete",
+            web::post().to(delete_community_word_filter),
+          )
+          .route(
+            "/word_filter/list",
+            web::get().to(list_community_word_filters),
+          ),
       )
       .service(
         web::scope("/federated_instances")
@@ -185,8 +353,11 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           )
           .route("/lock", web::post().to(lock_post))
           .route("/feature", web::post().to(feature_post))
+          .route("/contest_mode", web::post().to(set_contest_mode))
           .route("/list", web::get().to(list_posts))
           .route("/like", web::post().to(like_post))
+          .route("/like/list", web::get().to(list_post_likes))
+          .route("/react", web::post().to(react_to_post))
           .route("/save", web::put().to(route_post::<SavePost>))
           .route("/report", web::post().to(create_post_report))
           .route(
@@ -217,8 +388,12 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
           .route("/mark_as_read", web::post().to(mark_reply_as_read))
           .route("/distinguish", web::post().to(distinguish_comment))
           .route("/like", web::post().to(like_comment))
+          .route("/like/list", web::get().to(list_comment_likes))
+          .route("/react", web::post().to(react_to_comment))
           .route("/save", web::put().to(save_comment))
+          .route("/subscribe", web::put().to(subscribe_to_thread))
           .route("/list", web::get().to(list_comments))
+          .route("/children", web::get().to(list_comment_children))
           .route("/report", web::post().to(create_comment_report))
           .route("/report/resolve", web::put().to(resolve_comment_report))
           .route("/report/list", web::get().to(list_comment_reports)),
@@ -248,6 +423,14 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
             web::get().to(route_get::<ListPrivateMessageReports>),
           ),
       )
+      // Report reason
+      .service(
+        web::scope("/report_reason")
+          .wrap(rate_limit.message())
+          .route("", web::post().to(create_report_reason))
+          .route("/delete", web::post().to(delete_report_reason))
+          .route("/list", web::get().to(list_report_reasons)),
+      )
       // User
       .service(
         // Account action, I don't like that it's in /user maybe /accounts
@@ -274,12 +457,33 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
             web::post().to(route_post::<MarkPersonMentionAsRead>),
           )
           .route("/replies", web::get().to(route_get::<GetReplies>))
+          .route(
+            "/post_notifications",
+            web::get().to(list_post_notifications),
+          )
+          .route(
+            "/post_notifications/mark_as_read",
+            web::post().to(mark_post_notification_as_read),
+          )
           // Admin action. I don't like that it's in /user
           .route("/ban", web::post().to(ban_from_site))
           .route("/banned", web::get().to(route_get::<GetBannedPersons>))
+          .route("/shadowban", web::post().to(shadowban_from_site))
+          .route("/shadowbanned", web::get().to(list_shadowbanned))
           .route("/block", web::post().to(route_post::<BlockPerson>))
+          .route("/note", web::put().to(set_person_note))
+          .route("/invite", web::post().to(create_invite))
+          .route("/invite/list", web::get().to(list_invites))
+          .route("/invite/revoke", web::post().to(revoke_invite))
+          .route("/invite/uses", web::get().to(list_invite_uses))
+          .route("/list_media", web::get().to(list_media))
           // Account actions. I don't like that they're in /user maybe /accounts
-          .route("/login", web::post().to(route_post::<Login>))
+          .route("/login", web::post().to(login))
+          .route("/logout", web::post().to(logout))
+          .route("/magic_link", web::post().to(request_magic_link))
+          .route("/magic_link/redeem", web::post().to(redeem_magic_link))
+          .route("/list_logins", web::get().to(list_logins))
+          .route("/revoke_login", web::post().to(revoke_login))
           .route("/delete_account", web::post().to(delete_account))
           .route(
             "/password_reset",
@@ -298,10 +502,8 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
             "/save_user_settings",
             web::put().to(route_post::<SaveUserSettings>),
           )
-          .route(
-            "/change_password",
-            web::put().to(route_post::<ChangePassword>),
-          )
+          .route("/change_password", web::put().to(change_password))
+          .route("/change_username", web::put().to(change_username))
           .route("/report_count", web::get().to(route_get::<GetReportCount>))
           .route("/unread_count", web::get().to(route_get::<GetUnreadCount>))
           .route("/verify_email", web::post().to(route_post::<VerifyEmail>))
@@ -312,6 +514,35 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
         web::scope("/admin")
           .wrap(rate_limit.message())
           .route("/add", web::post().to(route_post::<AddAdmin>))
+          .route("/rotate_keys", web::post().to(rotate_actor_keys))
+          .route("/force_logout", web::post().to(force_logout))
+          .route("/merge_person", web::post().to(merge_person))
+          .route("/replay_activity", web::post().to(replay_activity))
+          .route(
+            "/requeue_deliveries",
+            web::post().to(requeue_failed_deliveries),
+          )
+          .route(
+            "/activity_deliveries",
+            web::get().to(list_activity_deliveries),
+          )
+          .route(
+            "/instance_deliveries",
+            web::get().to(list_instance_deliveries),
+          )
+          .route("/dashboard", web::get().to(get_admin_dashboard))
+          .route("/instance_trust", web::get().to(list_instance_trust))
+          .route("/captcha_secrets", web::put().to(set_captcha_secrets))
+          .route("/rate_limit", web::get().to(get_rate_limits))
+          .route("/rate_limit", web::put().to(edit_rate_limits))
+          .route(
+            "/rate_limit/override",
+            web::put().to(set_rate_limit_override),
+          )
+          .route(
+            "/rate_limit/override/list",
+            web::get().to(list_rate_limit_overrides),
+          )
           .route(
             "/registration_application/count",
             web::get().to(route_get::<GetUnreadRegistrationApplicationCount>),
@@ -324,12 +555,67 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimitCell) {
             "/registration_application/approve",
             web::put().to(route_post::<ApproveRegistrationApplication>),
           )
+          .route(
+            "/community_application/approve",
+            web::put().to(approve_community_application),
+          )
           .service(
             web::scope("/purge")
               .route("/person", web::post().to(route_post::<PurgePerson>))
               .route("/community", web::post().to(route_post::<PurgeCommunity>))
               .route("/post", web::post().to(route_post::<PurgePost>))
               .route("/comment", web::post().to(route_post::<PurgeComment>)),
+          )
+          .service(
+            web::scope("/announcement")
+              .route("", web::post().to(create_announcement))
+              .route("", web::put().to(update_announcement))
+              .route("/delete", web::post().to(delete_announcement))
+              .route("/list", web::get().to(list_announcements)),
+          )
+          .service(
+            web::scope("/tagline")
+              .route("", web::post().to(create_tagline))
+              .route("", web::put().to(update_tagline))
+              .route("/delete", web::post().to(delete_tagline))
+              .route("/list", web::get().to(list_taglines)),
+          )
+          .service(
+            web::scope("/blocked_url")
+              .route("", web::post().to(create_blocked_url))
+              .route("", web::put().to(update_blocked_url))
+              .route("/delete", web::post().to(delete_blocked_url))
+              .route("/list", web::get().to(list_blocked_urls)),
+          )
+          .service(
+            web::scope("/blocklist_subscription")
+              .route("", web::post().to(create_blocklist_subscription))
+              .route("/delete", web::post().to(delete_blocklist_subscription))
+              .route("/sync", web::post().to(sync_blocklist_subscription_now))
+              .route("/list", web::get().to(list_blocklist_subscriptions)),
+          )
+          .service(
+            web::scope("/category")
+              .route("", web::post().to(create_category))
+              .route("", web::put().to(update_category))
+              .route("/delete", web::post().to(delete_category)),
+          )
+          .service(
+            web::scope("/slur_filter")
+              .route("", web::post().to(create_slur_filter))
+              .route("/delete", web::post().to(delete_slur_filter))
+              .route("/list", web::get().to(list_slur_filters)),
+          )
+          .service(
+            web::scope("/federation_blocklist")
+              .route("/pin", web::post().to(pin_blocked_instance))
+              .route("/list", web::get().to(list_blocked_instances)),
+          )
+          .service(
+            web::scope("/scheduled_job")
+              .route("", web::put().to(edit_scheduled_job))
+              .route("/run", web::post().to(run_scheduled_job))
+              .route("/list", web::get().to(list_scheduled_jobs)),
           ),
       )
       .service(
@@ -34,6 +34,7 @@ use lemmy_db_schema::{
   },
   traits::Crud,
   utils::{get_conn, naive_now, DbPool},
+  AdminRole,
 };
 use lemmy_utils::{error::LemmyError, settings::structs::Settings};
 use tracing::info;
@@ -459,6 +460,7 @@ async fn initialize_local_site_2022_10_10(
       .person_id(person_inserted.id)
       .password_encrypted(setup.admin_password.clone())
       .email(setup.admin_email.clone())
+      .admin_role(Some(AdminRole::FullAdmin))
       .build();
     LocalUser::create(pool, &local_user_form).await?;
   };
@@ -12,7 +12,7 @@ use activitypub_federation::config::{FederationConfig, FederationMiddleware};
 use actix_cors::Cors;
 use actix_web::{
   middleware::{self, ErrorHandlers},
-  web::Data,
+  web::{self, Data},
   App,
   HttpServer,
   Result,
@@ -25,16 +25,25 @@ use lemmy_api_common::{
   utils::{
     check_private_instance_and_federation_enabled,
     local_site_rate_limit_to_rate_limit_config,
+    update_user_rate_limits,
   },
 };
 use lemmy_apub::{
   activities::{handle_outgoing_activities, match_outgoing_activities},
+  delivery_receipt::DeliveryReceipts,
+  objects::instance::ApubSite,
   VerifyUrlData,
   FEDERATION_HTTP_FETCH_LIMIT,
 };
 use lemmy_db_schema::{
   source::secret::Secret,
-  utils::{build_db_pool, get_database_url, run_migrations},
+  utils::{
+    build_db_pool,
+    build_db_replica_pools,
+    build_federation_db_pool,
+    get_database_url,
+    run_migrations,
+  },
 };
 use lemmy_routes::{feeds, images, nodeinfo, webfinger};
 use lemmy_utils::{
@@ -78,6 +87,8 @@ pub async fn start_lemmy_server() -> Result<(), LemmyError> {
 
   // Set up the connection pool
   let pool = build_db_pool(&settings).await?;
+  let read_replicas = build_db_replica_pools(&settings)?;
+  let federation_pool = build_federation_db_pool(&settings)?;
 
   // Run the Code-required migrations
   run_advanced_migrations(&mut (&pool).into(), &settings).await?;
@@ -104,6 +115,7 @@ pub async fn start_lemmy_server() -> Result<(), LemmyError> {
   let rate_limit_config =
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
   let rate_limit_cell = RateLimitCell::new(rate_limit_config).await;
+  update_user_rate_limits(&mut (&pool).into(), rate_limit_cell).await?;
 
   println!(
     "Starting http server at {}:{}",
@@ -119,6 +131,9 @@ pub async fn start_lemmy_server() -> Result<(), LemmyError> {
 
   let client = ClientBuilder::new(reqwest_client.clone())
     .with(TracingMiddleware::default())
+    .with(DeliveryReceipts(
+      federation_pool.clone().unwrap_or_else(|| pool.clone()),
+    ))
     .build();
 
   // Pictrs cannot use the retry middleware
@@ -131,37 +146,61 @@ pub async fn start_lemmy_server() -> Result<(), LemmyError> {
     client.clone(),
     secret.clone(),
     rate_limit_cell.clone(),
-  );
+  )
+  .with_read_replicas(read_replicas);
+
+  #[cfg(feature = "prometheus-metrics")]
+  serve_prometheus(settings.prometheus.as_ref(), {
+    let mut pools = vec![("api", context.inner_pool().clone())];
+    if let Some(federation_pool) = federation_pool {
+      pools.push(("federation", federation_pool));
+    }
+    pools
+  });
+
+  let settings_bind = settings.clone();
+
+  // Fetches are signed with the site actor, so that private instances can check where they come from
+  let site: ApubSite = site_view.site.into();
+  let build_federation_config = |context: LemmyContext| {
+    FederationConfig::builder()
+      .domain(settings.hostname.clone())
+      .url_verifier(Box::new(VerifyUrlData(context.inner_pool().clone())))
+      .app_data(context)
+      .client(client.clone())
+      .http_fetch_limit(FEDERATION_HTTP_FETCH_LIMIT)
+      .worker_count(settings.worker_count)
+      .retry_count(settings.retry_count)
+      .debug(*SYNCHRONOUS_FEDERATION)
+      .http_signature_compat(true)
+      .signed_fetch_actor(&site)
+      .clone()
+  };
+  // Used by the api, and anything else which isn't federation work
+  let api_federation_config = build_federation_config(context.clone()).build().await?;
+  // Used by the inbox, the apub http endpoints and for sending activities
+  let federation_config = match &federation_pool {
+    Some(federation_pool) => {
+      build_federation_config(context.with_pool(federation_pool.clone()))
+        .build()
+        .await?
+    }
+    None => api_federation_config.clone(),
+  };
 
   if scheduled_tasks_enabled {
     // Schedules various cleanup tasks for the DB
     thread::spawn({
       let context = context.clone();
+      let federation_config = federation_config.clone();
+      let runtime = tokio::runtime::Handle::current();
       move || {
-        scheduled_tasks::setup(db_url, user_agent, context)
+        scheduled_tasks::setup(db_url, user_agent, context, federation_config, runtime)
           .expect("Couldn't set up scheduled_tasks");
       }
     });
   }
 
-  #[cfg(feature = "prometheus-metrics")]
-  serve_prometheus(settings.prometheus.as_ref(), context.clone());
-
-  let settings_bind = settings.clone();
-
-  let federation_config = FederationConfig::builder()
-    .domain(settings.hostname.clone())
-    .app_data(context.clone())
-    .client(client.clone())
-    .http_fetch_limit(FEDERATION_HTTP_FETCH_LIMIT)
-    .worker_count(settings.worker_count)
-    .retry_count(settings.retry_count)
-    .debug(*SYNCHRONOUS_FEDERATION)
-    .http_signature_compat(true)
-    .url_verifier(Box::new(VerifyUrlData(context.inner_pool().clone())))
-    .build()
-    .await?;
-
   // this must come before the HttpServer creation
   // creates a middleware that populates http metrics for each path, method, and status code
   #[cfg(feature = "prometheus-metrics")]
@@ -204,7 +243,7 @@ pub async fn start_lemmy_server() -> Result<(), LemmyError> {
       .wrap(ErrorHandlers::new().default_handler(jsonify_plain_text_errors))
       .app_data(Data::new(context.clone()))
       .app_data(Data::new(rate_limit_cell.clone()))
-      .wrap(FederationMiddleware::new(federation_config.clone()));
+      .wrap(FederationMiddleware::new(api_federation_config.clone()));
 
     #[cfg(feature = "prometheus-metrics")]
     let app = app.wrap(prom_api_metrics.clone());
@@ -212,15 +251,21 @@ pub async fn start_lemmy_server() -> Result<(), LemmyError> {
     // The routes
     app
       .configure(|cfg| api_routes_http::config(cfg, rate_limit_cell))
+      .configure(feeds::config)
+      .configure(|cfg| images::config(cfg, pictrs_client.clone(), rate_limit_cell))
+      .configure(nodeinfo::config)
       .configure(|cfg| {
         if federation_enabled {
-          lemmy_apub::http::routes::config(cfg);
           webfinger::config(cfg);
+          // The apub endpoints use the federation pool. This scope matches all paths, so it has
+          // to come last.
+          cfg.service(
+            web::scope("")
+              .wrap(FederationMiddleware::new(federation_config.clone()))
+              .configure(lemmy_apub::http::routes::config),
+          );
         }
       })
-      .configure(feeds::config)
-      .configure(|cfg| images::config(cfg, pictrs_client.clone(), rate_limit_cell))
-      .configure(nodeinfo::config)
   })
   .bind((settings_bind.bind, settings_bind.port))?
   .run()
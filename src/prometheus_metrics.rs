@@ -1,33 +1,66 @@
 // TODO: should really not unwrap everywhere here....
 #![allow(clippy::unwrap_used)]
+use crate::scheduled_tasks::ACTIVITY_PRUNE_STATS;
 use actix_web::{rt::System, web, App, HttpResponse, HttpServer, Responder};
-use lemmy_api_common::context::LemmyContext;
+use lemmy_db_schema::{
+  source::local_user::LocalUser,
+  utils::{ActualDbPool, DB_POOL_STATS},
+};
 use lemmy_utils::settings::structs::PrometheusConfig;
-use prometheus::{default_registry, Encoder, Gauge, Opts, TextEncoder};
+use prometheus::{
+  default_registry,
+  Counter,
+  CounterVec,
+  Encoder,
+  Gauge,
+  GaugeVec,
+  Opts,
+  TextEncoder,
+};
 use std::{
   net::{IpAddr, Ipv4Addr},
-  sync::Arc,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+  },
   thread,
 };
 
 struct PromContext {
-  lemmy: LemmyContext,
+  /// Database pools with the workload they serve, used as `pool` label
+  db_pools: Vec<(&'static str, ActualDbPool)>,
   db_pool_metrics: DbPoolMetrics,
+  activity_metrics: ActivityMetrics,
+  legacy_password_hashes: Gauge,
 }
 
 struct DbPoolMetrics {
-  max_size: Gauge,
-  size: Gauge,
-  available: Gauge,
+  max_size: GaugeVec,
+  size: GaugeVec,
+  available: GaugeVec,
+  checkouts: Counter,
+  checkout_wait_seconds: Counter,
+  checkout_timeouts: Counter,
+  checked_out_seconds: Counter,
+}
+
+struct ActivityMetrics {
+  pruned: CounterVec,
+  table_bytes: GaugeVec,
 }
 
 static DEFAULT_BIND: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
 static DEFAULT_PORT: i32 = 10002;
 
-pub fn serve_prometheus(config: Option<&PrometheusConfig>, lemmy_context: LemmyContext) {
+pub fn serve_prometheus(
+  config: Option<&PrometheusConfig>,
+  db_pools: Vec<(&'static str, ActualDbPool)>,
+) {
   let context = Arc::new(PromContext {
-    lemmy: lemmy_context,
+    db_pools,
     db_pool_metrics: create_db_pool_metrics(),
+    activity_metrics: create_activity_metrics(),
+    legacy_password_hashes: create_legacy_password_hashes_metric(),
   });
 
   let (bind, port) = match config {
@@ -63,6 +96,8 @@ pub fn serve_prometheus(config: Option<&PrometheusConfig>, lemmy_context: LemmyC
 async fn metrics(context: web::Data<Arc<PromContext>>) -> impl Responder {
   // collect metrics
   collect_db_pool_metrics(&context).await;
+  collect_activity_metrics(&context.activity_metrics);
+  collect_legacy_password_hashes_metric(&context).await;
 
   let mut buffer = Vec::new();
   let encoder = TextEncoder::new();
@@ -77,46 +112,173 @@ async fn metrics(context: web::Data<Arc<PromContext>>) -> impl Responder {
 
 // create lemmy_db_pool_* metrics and register them with the default registry
 fn create_db_pool_metrics() -> DbPoolMetrics {
+  let pool_gauge =
+    |name: &str, help: &str| GaugeVec::new(Opts::new(name, help), &["pool"]).unwrap();
+  let counter = |name: &str, help: &str| Counter::with_opts(Opts::new(name, help)).unwrap();
   let metrics = DbPoolMetrics {
-    max_size: Gauge::with_opts(Opts::new(
+    max_size: pool_gauge(
       "lemmy_db_pool_max_connections",
       "Maximum number of connections in the pool",
-    ))
-    .unwrap(),
-    size: Gauge::with_opts(Opts::new(
+    ),
+    size: pool_gauge(
       "lemmy_db_pool_connections",
       "Current number of connections in the pool",
-    ))
-    .unwrap(),
-    available: Gauge::with_opts(Opts::new(
+    ),
+    available: pool_gauge(
       "lemmy_db_pool_available_connections",
       "Number of available connections in the pool",
-    ))
+    ),
+    checkouts: counter(
+      "lemmy_db_pool_checkouts_total",
+      "Number of connections taken from the pools",
+    ),
+    checkout_wait_seconds: counter(
+      "lemmy_db_pool_checkout_wait_seconds_total",
+      "Time spent waiting for a connection from the pools",
+    ),
+    checkout_timeouts: counter(
+      "lemmy_db_pool_checkout_timeouts_total",
+      "Number of times no connection was available before the timeout",
+    ),
+    checked_out_seconds: counter(
+      "lemmy_db_pool_checked_out_seconds_total",
+      "Time that connections were checked out of the pools, including work done between statements",
+    ),
+  };
+
+  for pool_gauge in [&metrics.max_size, &metrics.size, &metrics.available] {
+    default_registry()
+      .register(Box::new(pool_gauge.clone()))
+      .unwrap();
+  }
+  for counter in [
+    &metrics.checkouts,
+    &metrics.checkout_wait_seconds,
+    &metrics.checkout_timeouts,
+    &metrics.checked_out_seconds,
+  ] {
+    default_registry()
+      .register(Box::new(counter.clone()))
+      .unwrap();
+  }
+
+  metrics
+}
+
+async fn collect_db_pool_metrics(context: &PromContext) {
+  let metrics = &context.db_pool_metrics;
+  for (name, pool) in &context.db_pools {
+    let pool_status = pool.status();
+    metrics
+      .max_size
+      .with_label_values(&[name])
+      .set(pool_status.max_size as f64);
+    metrics
+      .size
+      .with_label_values(&[name])
+      .set(pool_status.size as f64);
+    metrics
+      .available
+      .with_label_values(&[name])
+      .set(pool_status.available as f64);
+  }
+
+  let micros_to_seconds = |micros: &AtomicU64| micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+  advance_counter(
+    &metrics.checkouts,
+    DB_POOL_STATS.checkouts.load(Ordering::Relaxed) as f64,
+  );
+  advance_counter(
+    &metrics.checkout_wait_seconds,
+    micros_to_seconds(&DB_POOL_STATS.checkout_wait_micros),
+  );
+  advance_counter(
+    &metrics.checkout_timeouts,
+    DB_POOL_STATS.checkout_timeouts.load(Ordering::Relaxed) as f64,
+  );
+  advance_counter(
+    &metrics.checked_out_seconds,
+    micros_to_seconds(&DB_POOL_STATS.checked_out_micros),
+  );
+}
+
+/// Brings a counter up to the total which is tracked elsewhere. Totals only ever grow, so this
+/// keeps the counter monotonic.
+fn advance_counter(counter: &Counter, total: f64) {
+  let increase = total - counter.get();
+  if increase > 0.0 {
+    counter.inc_by(increase);
+  }
+}
+
+// create lemmy_activity_* metrics and register them with the default registry
+fn create_activity_metrics() -> ActivityMetrics {
+  let table_gauge =
+    |name: &str, help: &str| GaugeVec::new(Opts::new(name, help), &["table"]).unwrap();
+  let metrics = ActivityMetrics {
+    pruned: CounterVec::new(
+      Opts::new(
+        "lemmy_activities_pruned_total",
+        "Number of activities removed after the retention period",
+      ),
+      &["table"],
+    )
     .unwrap(),
+    table_bytes: table_gauge(
+      "lemmy_activity_table_bytes",
+      "Size of the activity table including indexes, as of the last pruning run",
+    ),
   };
 
   default_registry()
-    .register(Box::new(metrics.max_size.clone()))
+    .register(Box::new(metrics.pruned.clone()))
     .unwrap();
   default_registry()
-    .register(Box::new(metrics.size.clone()))
-    .unwrap();
-  default_registry()
-    .register(Box::new(metrics.available.clone()))
+    .register(Box::new(metrics.table_bytes.clone()))
     .unwrap();
 
   metrics
 }
 
-async fn collect_db_pool_metrics(context: &PromContext) {
-  let pool_status = context.lemmy.inner_pool().status();
-  context
-    .db_pool_metrics
-    .max_size
-    .set(pool_status.max_size as f64);
-  context.db_pool_metrics.size.set(pool_status.size as f64);
-  context
-    .db_pool_metrics
-    .available
-    .set(pool_status.available as f64);
+fn collect_activity_metrics(metrics: &ActivityMetrics) {
+  let stats = &ACTIVITY_PRUNE_STATS;
+  advance_counter(
+    &metrics.pruned.with_label_values(&["sent_activity"]),
+    stats.sent_pruned.load(Ordering::Relaxed) as f64,
+  );
+  advance_counter(
+    &metrics.pruned.with_label_values(&["received_activity"]),
+    stats.received_pruned.load(Ordering::Relaxed) as f64,
+  );
+  metrics
+    .table_bytes
+    .with_label_values(&["sent_activity"])
+    .set(stats.sent_table_bytes.load(Ordering::Relaxed) as f64);
+  metrics
+    .table_bytes
+    .with_label_values(&["received_activity"])
+    .set(stats.received_table_bytes.load(Ordering::Relaxed) as f64);
+}
+
+// create the lemmy_legacy_password_hashes metric and register it with the default registry
+fn create_legacy_password_hashes_metric() -> Gauge {
+  let gauge = Gauge::with_opts(Opts::new(
+    "lemmy_legacy_password_hashes",
+    "Number of local users whose password is still hashed with bcrypt",
+  ))
+  .unwrap();
+  default_registry()
+    .register(Box::new(gauge.clone()))
+    .unwrap();
+  gauge
+}
+
+async fn collect_legacy_password_hashes_metric(context: &PromContext) {
+  let Some((_, pool)) = context.db_pools.first() else {
+    return;
+  };
+  match LocalUser::count_legacy_password_hashes(&mut pool.into()).await {
+    Ok(count) => context.legacy_password_hashes.set(count as f64),
+    Err(e) => tracing::warn!("Failed to count legacy password hashes: {e}"),
+  }
 }
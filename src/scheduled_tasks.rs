@@ -1,136 +1,334 @@
+use activitypub_federation::{
+  config::{Data, FederationConfig},
+  traits::Object,
+};
 use chrono::NaiveDateTime;
 use clokwerk::{Scheduler, TimeUnits as CTimeUnits};
 use diesel::{
-  dsl::{now, IntervalDsl},
-  sql_types::{Integer, Timestamp},
+  dsl::{exists, now, sql, IntervalDsl},
+  expression::SqlLiteral,
+  result::{ConnectionError, ConnectionResult},
+  sql_types::{BigInt, Bool, Integer, Text, Timestamp},
+  BoolExpressionMethods,
   Connection,
   ExpressionMethods,
   NullableExpressionMethods,
+  OptionalExtension,
   QueryDsl,
+  QueryResult,
   QueryableByName,
 };
 // Import week days and WeekDay
 use diesel::{sql_query, PgConnection, RunQueryDsl};
-use lemmy_api_common::context::LemmyContext;
+use futures_util::{future::LocalBoxFuture, stream, FutureExt, StreamExt};
+use lemmy_api_common::{
+  context::LemmyContext,
+  disposable_email::sync_disposable_email_domains,
+  fediseer::sync_fediseer,
+  request::{fetch_site_data, is_site_data_missing},
+  utils::{
+    rotate_community_keys,
+    rotate_person_keys,
+    rotate_site_keys,
+    sanitize_html_opt,
+    send_mod_queue_digests,
+    sync_blocklist_subscription,
+  },
+};
+use lemmy_apub::{
+  fetcher::refetch_object,
+  objects::{community::ApubCommunity, person::ApubPerson},
+};
 use lemmy_db_schema::{
+  newtypes::{CommentId, DbUrl, InstanceId, PostId},
   schema::{
+    blocklist_subscription,
     captcha_answer,
     comment,
+    community,
+    community_export,
+    community_follower,
+    community_import,
     community_person_ban,
+    community_snooze,
     instance,
+    local_image,
+    local_site,
+    login_history,
+    login_session,
+    magic_login_token,
     person,
+    person_block,
     post,
-    received_activity,
-    sent_activity,
+    post_metadata_refetch,
+    scheduled_job,
+    site,
+  },
+  source::{
+    blocklist_subscription::BlocklistSubscription,
+    community::Community,
+    instance::{Instance, InstanceForm},
+    person::Person,
+    post::{PostMetadataRefetch, PostUpdateForm},
+    scheduled_job::{ScheduledJob, ScheduledJobInsertForm},
+    site::Site,
   },
-  source::instance::{Instance, InstanceForm},
-  utils::{naive_now, DELETED_REPLACEMENT_TEXT},
+  utils::{naive_now, scheduler_statement_timeout_query, DELETED_REPLACEMENT_TEXT},
 };
-use lemmy_routes::nodeinfo::NodeInfo;
+use lemmy_routes::nodeinfo::{NodeInfo, NodeInfoWellKnown};
 use lemmy_utils::{
-  error::{LemmyError, LemmyResult},
+  error::{LemmyError, LemmyErrorType, LemmyResult},
+  settings::SETTINGS,
   REQWEST_TIMEOUT,
 };
-use reqwest::blocking::Client;
-use std::{thread, time::Duration};
+use once_cell::sync::Lazy;
+use reqwest::{blocking::Client, StatusCode};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicI64, AtomicU64, Ordering},
+    Arc,
+  },
+  thread,
+  time::Duration,
+};
+use tokio::{runtime::Handle, sync::Semaphore};
 use tracing::{error, info, warn};
+use url::Url;
+
+/// How often the database is checked for jobs which are due
+const JOB_POLL_INTERVAL: u32 = 10;
+
+/// How long a process may hold the lock of a running job. If it dies without releasing the lock,
+/// another process takes over after this time.
+const JOB_LOCK_HOURS: i64 = 1;
+
+/// Remote actors which weren't refreshed for this long are refetched in the background
+const STALE_ACTOR_DAYS: i64 = 1;
+
+/// Maximum number of remote communities and persons each, refreshed in a single run
+const STALE_ACTOR_BATCH_SIZE: i64 = 100;
+
+/// Maximum number of local actors of each type whose keys are rotated per run
+const KEY_ROTATION_BATCH_SIZE: i64 = 100;
+
+/// Maximum number of concurrent fetches by background jobs, and concurrent fetches from a single
+/// instance
+const FETCH_CONCURRENCY: usize = 20;
+const INSTANCE_FETCH_CONCURRENCY: usize = 2;
+
+/// Failed activity deliveries are retried in memory for up to 2.5 days. Sent activities are kept at
+/// least this long, so that they can still be fetched by the receiving instances.
+const DELIVERY_RETRY_DAYS: i32 = 3;
+
+/// Community exports are deleted after this many days, they should be downloaded before
+const COMMUNITY_EXPORT_RETENTION_DAYS: i32 = 7;
+
+/// Community imports run in the background of the process which received them. If one didn't make
+/// progress for this long, the process was stopped.
+const STALE_COMMUNITY_IMPORT_HOURS: i32 = 1;
+
+/// Logins from networks and devices which weren't used for this long trigger a login alert again
+const LOGIN_HISTORY_RETENTION_DAYS: i32 = 90;
+
+/// Maximum number of activities which are deleted by a single statement
+const ACTIVITY_PRUNE_BATCH_SIZE: i64 = 10_000;
+
+/// Results of activity pruning, exported as prometheus metrics
+#[derive(Default)]
+pub struct ActivityPruneStats {
+  pub sent_pruned: AtomicU64,
+  pub received_pruned: AtomicU64,
+  /// Size of the table including indexes, as of the last pruning run
+  pub sent_table_bytes: AtomicI64,
+  pub received_table_bytes: AtomicI64,
+}
+
+pub static ACTIVITY_PRUNE_STATS: Lazy<ActivityPruneStats> = Lazy::new(ActivityPruneStats::default);
+
+type JobFn = dyn FnMut(&mut PgConnection) -> LemmyResult<()> + Send;
+
+/// A periodic background job. Its schedule and state are persisted in the `scheduled_job` table,
+/// so that they survive restarts, and multiple lemmy processes never run the same job at once.
+struct Job {
+  name: &'static str,
+  /// Used when the job is first registered, admins can change it afterwards
+  default_interval: Duration,
+  /// Whether the job is also run when lemmy starts, instead of waiting for its next run
+  on_startup: bool,
+  run: Box<JobFn>,
+}
 
-/// Schedules various cleanup tasks for lemmy in a background thread
+impl Job {
+  fn new(
+    name: &'static str,
+    default_interval: Duration,
+    run: impl FnMut(&mut PgConnection) -> LemmyResult<()> + Send + 'static,
+  ) -> Self {
+    Job {
+      name,
+      default_interval,
+      on_startup: false,
+      run: Box::new(run),
+    }
+  }
+
+  fn run_on_startup(mut self) -> Self {
+    self.on_startup = true;
+    self
+  }
+}
+
+/// Schedules various cleanup tasks for lemmy in a background thread. Async jobs are run on the
+/// given runtime, which must be the one that the database pools and http clients were created on.
+/// Connections taken from a pool are bound to the runtime which opened them.
 pub fn setup(
   db_url: String,
   user_agent: String,
   context_1: LemmyContext,
+  federation_config: FederationConfig<LemmyContext>,
+  runtime: Handle,
 ) -> Result<(), LemmyError> {
-  // Setup the connections
   let mut scheduler = Scheduler::new();
 
-  startup_jobs(&db_url);
-
-  // Update active counts every hour
-  let url = db_url.clone();
-  scheduler.every(CTimeUnits::hour(1)).run(move || {
-    PgConnection::establish(&url)
-      .map(|mut conn| {
-        active_counts(&mut conn);
-        update_banned_when_expired(&mut conn);
-      })
-      .map_err(|e| {
-        error!("Failed to establish db connection for active counts update: {e}");
-      })
-      .ok();
-  });
-
-  // Update hot ranks every 15 minutes
-  let url = db_url.clone();
-  scheduler.every(CTimeUnits::minutes(15)).run(move || {
-    PgConnection::establish(&url)
-      .map(|mut conn| {
-        update_hot_ranks(&mut conn);
-      })
-      .map_err(|e| {
-        error!("Failed to establish db connection for hot ranks update: {e}");
-      })
-      .ok();
-  });
-
-  // Delete any captcha answers older than ten minutes, every ten minutes
-  let url = db_url.clone();
-  scheduler.every(CTimeUnits::minutes(10)).run(move || {
-    PgConnection::establish(&url)
-      .map(|mut conn| {
-        delete_expired_captcha_answers(&mut conn);
-      })
-      .map_err(|e| {
-        error!("Failed to establish db connection for captcha cleanup: {e}");
-      })
-      .ok();
-  });
-
-  // Clear old activities every week
-  let url = db_url.clone();
-  scheduler.every(CTimeUnits::weeks(1)).run(move || {
-    PgConnection::establish(&url)
-      .map(|mut conn| {
-        clear_old_activities(&mut conn);
-      })
-      .map_err(|e| {
-        error!("Failed to establish db connection for activity cleanup: {e}");
-      })
-      .ok();
-  });
-
-  // Remove old rate limit buckets after 1 to 2 hours of inactivity
+  // Remove old rate limit buckets after 1 to 2 hours of inactivity. These are kept in memory, so
+  // this runs in every process instead of being a persisted job.
   scheduler.every(CTimeUnits::hour(1)).run(move || {
     let hour = Duration::from_secs(3600);
     context_1.settings_updated_channel().remove_older_than(hour);
   });
 
-  // Overwrite deleted & removed posts and comments every day
-  let url = db_url.clone();
-  scheduler.every(CTimeUnits::days(1)).run(move || {
-    PgConnection::establish(&db_url)
-      .map(|mut conn| {
-        overwrite_deleted_posts_and_comments(&mut conn);
-      })
-      .map_err(|e| {
-        error!("Failed to establish db connection for deleted content cleanup: {e}");
-      })
-      .ok();
-  });
+  let metadata_client = ClientBuilder::new(
+    reqwest::Client::builder()
+      .user_agent(user_agent.clone())
+      .timeout(REQWEST_TIMEOUT)
+      .build()?,
+  )
+  .build();
+
+  let mut jobs = vec![
+    Job::new("active_counts", hours(1), |conn| {
+      active_counts(conn)?;
+      update_banned_when_expired(conn)
+    })
+    .run_on_startup(),
+    Job::new("hot_ranks", minutes(15), update_hot_ranks).run_on_startup(),
+    Job::new("trending_communities", days(1), update_trending_communities),
+    Job::new("site_stats_history", days(1), snapshot_site_stats),
+    Job::new(
+      "community_person_aggregates",
+      hours(1),
+      update_community_person_aggregates,
+    ),
+    Job::new(
+      "expired_captcha_answers",
+      minutes(10),
+      delete_expired_captcha_answers,
+    ),
+    Job::new(
+      "expired_magic_login_tokens",
+      hours(1),
+      delete_expired_magic_login_tokens,
+    ),
+    Job::new(
+      "expired_login_sessions",
+      hours(1),
+      delete_expired_login_sessions,
+    ),
+    Job::new(
+      "old_community_exports",
+      days(1),
+      delete_old_community_exports,
+    ),
+    Job::new(
+      "stale_community_imports",
+      hours(1),
+      fail_stale_community_imports,
+    )
+    .run_on_startup(),
+    Job::new("old_activities", days(1), |conn| {
+      clear_old_activities(conn, SETTINGS.activity_retention_days)
+    })
+    .run_on_startup(),
+    // Retry failed post link metadata
+    Job::new("post_metadata_refetch", hours(1), {
+      let runtime = runtime.clone();
+      move |conn| refetch_post_metadata(conn, &metadata_client, &runtime)
+    }),
+    Job::new("overwrite_deleted_content", days(1), |conn| {
+      overwrite_deleted_posts_and_comments(conn, SETTINGS.deleted_content_retention_days)
+    })
+    .run_on_startup(),
+    Job::new("orphaned_images", days(1), {
+      let user_agent = user_agent.clone();
+      move |conn| delete_orphaned_images(conn, &user_agent)
+    }),
+    Job::new("stale_actors", hours(1), {
+      let federation_config = federation_config.clone();
+      let runtime = runtime.clone();
+      move |conn| refresh_stale_actors(conn, &federation_config, &runtime)
+    }),
+    Job::new("community_moderators", days(1), {
+      let federation_config = federation_config.clone();
+      let runtime = runtime.clone();
+      move |conn| sync_community_moderators(conn, &federation_config, &runtime)
+    }),
+    Job::new("instance_software", days(1), move |conn| {
+      update_instance_software(conn, &user_agent)
+    }),
+    Job::new("fediseer", days(1), {
+      let federation_config = federation_config.clone();
+      let runtime = runtime.clone();
+      move |conn| sync_fediseer_trust(conn, &federation_config, &runtime)
+    }),
+    Job::new("disposable_email_domains", days(1), {
+      let federation_config = federation_config.clone();
+      let runtime = runtime.clone();
+      move |conn| sync_disposable_emails(conn, &federation_config, &runtime)
+    }),
+    Job::new("blocklist_subscriptions", days(1), {
+      let federation_config = federation_config.clone();
+      let runtime = runtime.clone();
+      move |conn| sync_blocklist_subscriptions(conn, &federation_config, &runtime)
+    }),
+    Job::new("mod_queue_digest", days(1), {
+      let federation_config = federation_config.clone();
+      let runtime = runtime.clone();
+      move |conn| send_mod_queue_digest_emails(conn, &federation_config, &runtime)
+    }),
+    Job::new("key_rotation", days(1), {
+      let runtime = runtime.clone();
+      move |conn| {
+        rotate_old_keys(
+          conn,
+          SETTINGS.key_rotation_days,
+          &federation_config,
+          &runtime,
+        )
+      }
+    }),
+  ];
 
-  // Update the Instance Software
-  scheduler.every(CTimeUnits::days(1)).run(move || {
-    PgConnection::establish(&url)
-      .map(|mut conn| {
-        update_instance_software(&mut conn, &user_agent)
-          .map_err(|e| warn!("Failed to update instance software: {e}"))
+  let mut conn = establish_connection(&db_url)?;
+  register_jobs(&mut conn, &jobs)?;
+  let mut conn = Some(conn);
+  scheduler
+    .every(CTimeUnits::seconds(JOB_POLL_INTERVAL))
+    .run(move || {
+      // Reconnect if the previous connection was lost
+      if conn.is_none() {
+        conn = establish_connection(&db_url)
+          .map_err(|e| error!("Failed to establish db connection for scheduled jobs: {e}"))
           .ok();
-      })
-      .map_err(|e| {
-        error!("Failed to establish db connection for instance software update: {e}");
-      })
-      .ok();
-  });
+      }
+      if let Some(c) = conn.as_mut() {
+        if let Err(e) = run_due_jobs(c, &mut jobs) {
+          error!("Failed to run scheduled jobs: {e}");
+          conn = None;
+        }
+      }
+    });
 
   // Manually run the scheduler in an event loop
   loop {
@@ -139,19 +337,121 @@ pub fn setup(
   }
 }
 
-/// Run these on server startup
-fn startup_jobs(db_url: &str) {
-  let mut conn = PgConnection::establish(db_url).expect("could not establish connection");
-  active_counts(&mut conn);
-  update_hot_ranks(&mut conn);
-  update_banned_when_expired(&mut conn);
-  clear_old_activities(&mut conn);
-  overwrite_deleted_posts_and_comments(&mut conn);
+fn minutes(minutes: u64) -> Duration {
+  Duration::from_secs(minutes * 60)
+}
+
+fn hours(hours: u64) -> Duration {
+  minutes(hours * 60)
+}
+
+fn days(days: u64) -> Duration {
+  hours(days * 24)
+}
+
+/// Opens a connection for scheduled tasks, with the statement timeout configured for them
+fn establish_connection(db_url: &str) -> ConnectionResult<PgConnection> {
+  let mut conn = PgConnection::establish(db_url)?;
+  if let Some(query) = scheduler_statement_timeout_query(&SETTINGS) {
+    sql_query(query)
+      .execute(&mut conn)
+      .map_err(ConnectionError::CouldntSetupConfiguration)?;
+  }
+  Ok(conn)
+}
+
+/// Adds jobs which aren't in the database yet. New jobs are due immediately, as are the jobs which
+/// run on startup.
+fn register_jobs(conn: &mut PgConnection, jobs: &[Job]) -> LemmyResult<()> {
+  let forms = jobs
+    .iter()
+    .map(|job| ScheduledJobInsertForm {
+      name: job.name.to_string(),
+      interval_seconds: i32::try_from(job.default_interval.as_secs()).unwrap_or(i32::MAX),
+    })
+    .collect::<Vec<_>>();
+  diesel::insert_into(scheduled_job::table)
+    .values(forms)
+    .on_conflict_do_nothing()
+    .execute(conn)?;
+
+  let startup_jobs = jobs
+    .iter()
+    .filter(|job| job.on_startup)
+    .map(|job| job.name)
+    .collect::<Vec<_>>();
+  diesel::update(scheduled_job::table.filter(scheduled_job::name.eq_any(startup_jobs)))
+    .set(scheduled_job::next_run.eq(now))
+    .execute(conn)?;
+  Ok(())
+}
+
+/// Runs all jobs which are due and not locked by another process, one after another
+fn run_due_jobs(conn: &mut PgConnection, jobs: &mut [Job]) -> LemmyResult<()> {
+  let due = scheduled_job::table
+    .filter(scheduled_job::enabled.eq(true))
+    .filter(scheduled_job::next_run.le(now))
+    .filter(
+      scheduled_job::locked_until
+        .is_null()
+        .or(scheduled_job::locked_until.lt(now.nullable())),
+    )
+    .select(scheduled_job::name)
+    .load::<String>(conn)?;
+
+  for job in jobs.iter_mut().filter(|j| due.iter().any(|d| d == j.name)) {
+    // Take the lock. If another process was faster, no row is updated.
+    let started = naive_now();
+    let claimed = diesel::update(
+      scheduled_job::table
+        .find(job.name)
+        .filter(scheduled_job::next_run.le(now))
+        .filter(
+          scheduled_job::locked_until
+            .is_null()
+            .or(scheduled_job::locked_until.lt(now.nullable())),
+        ),
+    )
+    .set((
+      scheduled_job::locked_until.eq(started + chrono::Duration::hours(JOB_LOCK_HOURS)),
+      scheduled_job::last_started.eq(started),
+    ))
+    .get_result::<ScheduledJob>(conn)
+    .optional()?;
+    let Some(claimed) = claimed else {
+      continue;
+    };
+
+    info!("Running scheduled job {}", job.name);
+    let result = (job.run)(conn);
+    let finished = naive_now();
+    let duration_ms = (finished - started).num_milliseconds();
+    let last_error = match &result {
+      Ok(()) => None,
+      Err(e) => {
+        warn!("Scheduled job {} failed: {e}", job.name);
+        Some(e.to_string())
+      }
+    };
+    diesel::update(scheduled_job::table.find(job.name))
+      .set((
+        scheduled_job::locked_until.eq(None::<NaiveDateTime>),
+        scheduled_job::last_finished.eq(finished),
+        scheduled_job::next_run
+          .eq(finished + chrono::Duration::seconds(claimed.interval_seconds.into())),
+        scheduled_job::last_duration_ms.eq(i32::try_from(duration_ms).unwrap_or(i32::MAX)),
+        scheduled_job::run_count.eq(scheduled_job::run_count + 1),
+        scheduled_job::failure_count.eq(scheduled_job::failure_count + i32::from(result.is_err())),
+        scheduled_job::last_error.eq(last_error),
+      ))
+      .execute(conn)?;
+  }
+  Ok(())
 }
 
 /// Update the hot_rank columns for the aggregates tables
 /// Runs in batches until all necessary rows are updated once
-fn update_hot_ranks(conn: &mut PgConnection) {
+fn update_hot_ranks(conn: &mut PgConnection) -> LemmyResult<()> {
   info!("Updating hot ranks for all history...");
 
   process_hot_ranks_in_batches(
@@ -160,23 +460,99 @@ fn update_hot_ranks(conn: &mut PgConnection) {
     "a.hot_rank != 0 OR a.hot_rank_active != 0",
     "SET hot_rank = hot_rank(a.score, a.published),
          hot_rank_active = hot_rank(a.score, a.newest_comment_time_necro)",
-  );
+  )?;
 
   process_hot_ranks_in_batches(
     conn,
     "comment_aggregates",
     "a.hot_rank != 0",
     "SET hot_rank = hot_rank(a.score, a.published)",
-  );
+  )?;
 
   process_hot_ranks_in_batches(
     conn,
     "community_aggregates",
     "a.hot_rank != 0",
     "SET hot_rank = hot_rank(a.subscribers, a.published)",
-  );
+  )?;
 
   info!("Finished hot ranks update!");
+  Ok(())
+}
+
+/// Ranks communities by their subscriber growth since the previous run, and by how much more
+/// active they were in the last day than on an average day of the week. Both are dampened by the
+/// size of the community, so that small communities can trend as well. Runs once per day, so that
+/// the growth is measured per day.
+fn update_trending_communities(conn: &mut PgConnection) -> LemmyResult<()> {
+  info!("Updating trending communities ...");
+  sql_query(
+    "UPDATE community_aggregates
+     SET trending_rank = least(round(10000 * (
+           greatest(subscribers - subscribers_day_ago, 0) / sqrt(subscribers_day_ago + 10)
+           + greatest(users_active_day - users_active_week / 7.0, 0)
+             / sqrt(users_active_week / 7.0 + 10))), 2147483647),
+         subscribers_day_ago = subscribers",
+  )
+  .execute(conn)?;
+  info!("Done.");
+  Ok(())
+}
+
+/// Stores the current aggregates of the local site, so that statistics can be shown over time.
+/// Running it again on the same day overwrites that day's snapshot.
+fn snapshot_site_stats(conn: &mut PgConnection) -> LemmyResult<()> {
+  info!("Taking snapshot of site stats ...");
+  sql_query(
+    "INSERT INTO site_stats_history (site_id, day, users, posts, comments, communities,
+       users_active_day, users_active_week, users_active_month, users_active_half_year)
+     SELECT sa.site_id, (now() AT TIME ZONE 'utc')::date, sa.users, sa.posts, sa.comments,
+       sa.communities, sa.users_active_day, sa.users_active_week, sa.users_active_month,
+       sa.users_active_half_year
+     FROM site_aggregates sa
+     INNER JOIN local_site ls ON ls.site_id = sa.site_id
+     ON CONFLICT (site_id, day) DO UPDATE SET
+       users = excluded.users,
+       posts = excluded.posts,
+       comments = excluded.comments,
+       communities = excluded.communities,
+       users_active_day = excluded.users_active_day,
+       users_active_week = excluded.users_active_week,
+       users_active_month = excluded.users_active_month,
+       users_active_half_year = excluded.users_active_half_year",
+  )
+  .execute(conn)?;
+  info!("Done.");
+  Ok(())
+}
+
+/// Recalculates the score of each person's posts and comments within each community.
+fn update_community_person_aggregates(conn: &mut PgConnection) -> LemmyResult<()> {
+  info!("Updating community person aggregates ...");
+  sql_query(
+    "INSERT INTO community_person_aggregates (community_id, person_id, post_score, comment_score,
+       updated)
+     SELECT s.community_id, s.person_id, sum(s.post_score), sum(s.comment_score), now()
+     FROM (
+       SELECT p.community_id, p.creator_id AS person_id, pa.score AS post_score,
+         0 AS comment_score
+       FROM post p
+       INNER JOIN post_aggregates pa ON pa.post_id = p.id
+       UNION ALL
+       SELECT p.community_id, c.creator_id, 0, ca.score
+       FROM comment c
+       INNER JOIN comment_aggregates ca ON ca.comment_id = c.id
+       INNER JOIN post p ON p.id = c.post_id
+     ) s
+     GROUP BY s.community_id, s.person_id
+     ON CONFLICT (community_id, person_id) DO UPDATE SET
+       post_score = excluded.post_score,
+       comment_score = excluded.comment_score,
+       updated = excluded.updated",
+  )
+  .execute(conn)?;
+  info!("Done.");
+  Ok(())
 }
 
 #[derive(QueryableByName)]
@@ -194,7 +570,7 @@ fn process_hot_ranks_in_batches(
   table_name: &str,
   where_clause: &str,
   set_clause: &str,
-) {
+) -> LemmyResult<()> {
   let process_start_time = NaiveDateTime::from_timestamp_opt(0, 0).expect("0 timestamp creation");
 
   let update_batch_size = 1000; // Bigger batches than this tend to cause seq scans
@@ -203,7 +579,7 @@ fn process_hot_ranks_in_batches(
   while let Some(previous_batch_last_published) = previous_batch_result {
     // Raw `sql_query` is used as a performance optimization - Diesel does not support doing this
     // in a single query (neither as a CTE, nor using a subquery)
-    let result = sql_query(format!(
+    let updated_rows = sql_query(format!(
       r#"WITH batch AS (SELECT a.id
                FROM {aggregates_table} a
                WHERE a.published > $1 AND ({where_clause})
@@ -219,92 +595,197 @@ fn process_hot_ranks_in_batches(
     ))
     .bind::<Timestamp, _>(previous_batch_last_published)
     .bind::<Integer, _>(update_batch_size)
-    .get_results::<HotRanksUpdateResult>(conn);
+    .get_results::<HotRanksUpdateResult>(conn)?;
 
-    match result {
-      Ok(updated_rows) => {
-        processed_rows_count += updated_rows.len();
-        previous_batch_result = updated_rows.last().map(|row| row.published);
-      }
-      Err(e) => {
-        error!("Failed to update {} hot_ranks: {}", table_name, e);
-        break;
-      }
-    }
+    processed_rows_count += updated_rows.len();
+    previous_batch_result = updated_rows.last().map(|row| row.published);
   }
   info!(
     "Finished process_hot_ranks_in_batches execution for {} (processed {} rows)",
     table_name, processed_rows_count
   );
+  Ok(())
 }
 
-fn delete_expired_captcha_answers(conn: &mut PgConnection) {
+fn delete_expired_captcha_answers(conn: &mut PgConnection) -> LemmyResult<()> {
   diesel::delete(
     captcha_answer::table.filter(captcha_answer::published.lt(now - IntervalDsl::minutes(10))),
   )
-  .execute(conn)
-  .map(|_| {
-    info!("Done.");
-  })
-  .map_err(|e| error!("Failed to clear old captcha answers: {e}"))
-  .ok();
+  .execute(conn)?;
+  info!("Done.");
+  Ok(())
 }
 
-/// Clear old activities (this table gets very large)
-fn clear_old_activities(conn: &mut PgConnection) {
-  info!("Clearing old activities...");
-  diesel::delete(sent_activity::table.filter(sent_activity::published.lt(now - 3.months())))
-    .execute(conn)
-    .map_err(|e| error!("Failed to clear old sent activities: {e}"))
-    .ok();
+fn delete_expired_magic_login_tokens(conn: &mut PgConnection) -> LemmyResult<()> {
+  diesel::delete(magic_login_token::table.filter(magic_login_token::expires.lt(now)))
+    .execute(conn)?;
+  info!("Done.");
+  Ok(())
+}
 
+fn delete_expired_login_sessions(conn: &mut PgConnection) -> LemmyResult<()> {
+  diesel::delete(login_session::table.filter(login_session::expires.lt(now))).execute(conn)?;
   diesel::delete(
-    received_activity::table.filter(received_activity::published.lt(now - 3.months())),
+    login_history::table
+      .filter(login_history::published.lt(now - IntervalDsl::days(LOGIN_HISTORY_RETENTION_DAYS))),
   )
-  .execute(conn)
-  .map(|_| info!("Done."))
-  .map_err(|e| error!("Failed to clear old received activities: {e}"))
-  .ok();
+  .execute(conn)?;
+  info!("Done.");
+  Ok(())
+}
+
+/// Community archives are large, so they are only kept for a limited time
+fn delete_old_community_exports(conn: &mut PgConnection) -> LemmyResult<()> {
+  info!("Deleting old community exports...");
+  let deleted = diesel::delete(community_export::table.filter(
+    community_export::published.lt(now - IntervalDsl::days(COMMUNITY_EXPORT_RETENTION_DAYS)),
+  ))
+  .execute(conn)?;
+  info!("Done, deleted {deleted} community exports.");
+  Ok(())
+}
+
+/// Imports can't be resumed because the archive is only kept in memory, so interrupted imports
+/// are marked as failed. Importing the archive again skips the posts and comments which were
+/// already imported.
+fn fail_stale_community_imports(conn: &mut PgConnection) -> LemmyResult<()> {
+  info!("Failing interrupted community imports...");
+  let stale_since = now - IntervalDsl::hours(STALE_COMMUNITY_IMPORT_HOURS);
+  let failed = diesel::update(
+    community_import::table
+      .filter(community_import::finished.is_null())
+      .filter(community_import::published.lt(stale_since))
+      .filter(
+        community_import::updated
+          .is_null()
+          .or(community_import::updated.lt(stale_since.nullable())),
+      ),
+  )
+  .set((
+    community_import::error.eq(LemmyErrorType::CommunityImportInterrupted.to_string()),
+    community_import::finished.eq(now),
+  ))
+  .execute(conn)?;
+  info!("Done, failed {failed} community imports.");
+  Ok(())
+}
+
+/// Clear old activities (this table gets very large)
+fn clear_old_activities(conn: &mut PgConnection, retention_days: u32) -> LemmyResult<()> {
+  info!("Clearing old activities...");
+  let retention_days = i32::try_from(retention_days).unwrap_or(i32::MAX);
+  let sent_retention_days = retention_days.max(DELIVERY_RETRY_DAYS);
+
+  let sent_pruned = delete_older_than(conn, "sent_activity", sent_retention_days)?;
+  ACTIVITY_PRUNE_STATS
+    .sent_pruned
+    .fetch_add(sent_pruned as u64, Ordering::Relaxed);
+
+  delete_older_than(conn, "sent_activity_delivery", sent_retention_days)?;
+
+  let received_pruned = delete_older_than(conn, "received_activity", retention_days)?;
+  ACTIVITY_PRUNE_STATS
+    .received_pruned
+    .fetch_add(received_pruned as u64, Ordering::Relaxed);
+
+  let table_size = |conn: &mut PgConnection, table: &str| {
+    sql_query(format!("select pg_total_relation_size('{table}') as bytes"))
+      .get_result::<TableSize>(conn)
+      .map(|s| s.bytes)
+  };
+  ACTIVITY_PRUNE_STATS
+    .sent_table_bytes
+    .store(table_size(conn, "sent_activity")?, Ordering::Relaxed);
+  ACTIVITY_PRUNE_STATS
+    .received_table_bytes
+    .store(table_size(conn, "received_activity")?, Ordering::Relaxed);
+
+  info!("Done, removed {sent_pruned} sent and {received_pruned} received activities.");
+  Ok(())
+}
+
+/// Deletes the rows of an activity table which were published more than the given number of days
+/// ago. This happens in batches, so that locks are only held briefly. Returns the total number of
+/// deleted rows.
+fn delete_older_than(conn: &mut PgConnection, table: &str, days: i32) -> QueryResult<usize> {
+  let query = format!(
+    "delete from {table} where id in (select id from {table}
+     where published < now() - make_interval(days => $1) limit $2)"
+  );
+  let mut total = 0;
+  loop {
+    let deleted = sql_query(&query)
+      .bind::<Integer, _>(days)
+      .bind::<BigInt, _>(ACTIVITY_PRUNE_BATCH_SIZE)
+      .execute(conn)?;
+    total += deleted;
+    if deleted < ACTIVITY_PRUNE_BATCH_SIZE as usize {
+      return Ok(total);
+    }
+  }
+}
+
+#[derive(QueryableByName)]
+struct TableSize {
+  #[diesel(sql_type = BigInt)]
+  bytes: i64,
 }
 
-/// overwrite posts and comments 30d after deletion
-fn overwrite_deleted_posts_and_comments(conn: &mut PgConnection) {
+/// Overwrites posts and comments which were deleted by their creator more than the given number of
+/// days ago
+fn overwrite_deleted_posts_and_comments(
+  conn: &mut PgConnection,
+  retention_days: u32,
+) -> LemmyResult<()> {
+  let retention_days = i32::try_from(retention_days).unwrap_or(i32::MAX);
+
   info!("Overwriting deleted posts...");
-  diesel::update(
+  let post_ids = diesel::update(
     post::table
       .filter(post::deleted.eq(true))
-      .filter(post::updated.lt(now.nullable() - 1.months()))
+      .filter(post::updated.lt(now.nullable() - retention_days.days()))
       .filter(post::body.ne(DELETED_REPLACEMENT_TEXT)),
   )
   .set((
     post::body.eq(DELETED_REPLACEMENT_TEXT),
     post::name.eq(DELETED_REPLACEMENT_TEXT),
+    post::url.eq(None::<DbUrl>),
+    post::thumbnail_url.eq(None::<DbUrl>),
+    post::audio_mime_type.eq(None::<String>),
+    post::audio_duration.eq(None::<i32>),
   ))
-  .execute(conn)
-  .map(|_| {
-    info!("Done.");
-  })
-  .map_err(|e| error!("Failed to overwrite deleted posts: {e}"))
-  .ok();
+  .returning(post::id)
+  .get_results::<PostId>(conn)?;
 
   info!("Overwriting deleted comments...");
-  diesel::update(
+  let comment_ids = diesel::update(
     comment::table
       .filter(comment::deleted.eq(true))
-      .filter(comment::updated.lt(now.nullable() - 1.months()))
+      .filter(comment::updated.lt(now.nullable() - retention_days.days()))
       .filter(comment::content.ne(DELETED_REPLACEMENT_TEXT)),
   )
   .set(comment::content.eq(DELETED_REPLACEMENT_TEXT))
-  .execute(conn)
-  .map(|_| {
-    info!("Done.");
-  })
-  .map_err(|e| error!("Failed to overwrite deleted comments: {e}"))
-  .ok();
+  .returning(comment::id)
+  .get_results::<CommentId>(conn)?;
+
+  // Unlink the uploaded images, so that they are purged as orphans
+  diesel::update(local_image::table.filter(local_image::post_id.eq_any(&post_ids)))
+    .set(local_image::post_id.eq(None::<PostId>))
+    .execute(conn)?;
+  diesel::update(local_image::table.filter(local_image::comment_id.eq_any(&comment_ids)))
+    .set(local_image::comment_id.eq(None::<CommentId>))
+    .execute(conn)?;
+
+  info!(
+    "Done, overwrote {} posts and {} comments.",
+    post_ids.len(),
+    comment_ids.len()
+  );
+  Ok(())
 }
 
 /// Re-calculate the site and community active counts every 12 hours
-fn active_counts(conn: &mut PgConnection) {
+fn active_counts(conn: &mut PgConnection) -> LemmyResult<()> {
   info!("Updating active site and community aggregates ...");
 
   let intervals = vec![
@@ -319,23 +800,18 @@ fn active_counts(conn: &mut PgConnection) {
       "update site_aggregates set users_active_{} = (select * from site_aggregates_activity('{}')) where site_id = 1",
       i.1, i.0
     );
-    sql_query(update_site_stmt)
-      .execute(conn)
-      .map_err(|e| error!("Failed to update site stats: {e}"))
-      .ok();
+    sql_query(update_site_stmt).execute(conn)?;
 
     let update_community_stmt = format!("update community_aggregates ca set users_active_{} = mv.count_ from community_aggregates_activity('{}') mv where ca.community_id = mv.community_id_", i.1, i.0);
-    sql_query(update_community_stmt)
-      .execute(conn)
-      .map_err(|e| error!("Failed to update community stats: {e}"))
-      .ok();
+    sql_query(update_community_stmt).execute(conn)?;
   }
 
   info!("Done.");
+  Ok(())
 }
 
 /// Set banned to false after ban expires
-fn update_banned_when_expired(conn: &mut PgConnection) {
+fn update_banned_when_expired(conn: &mut PgConnection) -> LemmyResult<()> {
   info!("Updating banned column if it expires ...");
 
   diesel::update(
@@ -344,14 +820,457 @@ fn update_banned_when_expired(conn: &mut PgConnection) {
       .filter(person::ban_expires.lt(now)),
   )
   .set(person::banned.eq(false))
-  .execute(conn)
-  .map_err(|e| error!("Failed to update person.banned when expires: {e}"))
-  .ok();
+  .execute(conn)?;
 
   diesel::delete(community_person_ban::table.filter(community_person_ban::expires.lt(now)))
-    .execute(conn)
-    .map_err(|e| error!("Failed to remove community_ban expired rows: {e}"))
-    .ok();
+    .execute(conn)?;
+
+  diesel::delete(person_block::table.filter(person_block::expires.lt(now))).execute(conn)?;
+
+  diesel::delete(community_snooze::table.filter(community_snooze::expires.lt(now)))
+    .execute(conn)?;
+  Ok(())
+}
+
+/// Retries fetching link metadata for posts where it previously failed, backing off each time
+fn refetch_post_metadata(
+  conn: &mut PgConnection,
+  client: &ClientWithMiddleware,
+  runtime: &Handle,
+) -> LemmyResult<()> {
+  info!("Refetching failed post metadata...");
+
+  let allow_sensitive = local_site::table
+    .select(local_site::enable_nsfw)
+    .first::<bool>(conn)
+    .unwrap_or(false);
+
+  let due = post_metadata_refetch::table
+    .inner_join(post::table)
+    .filter(post_metadata_refetch::next_attempt.lt(now))
+    .select((post_metadata_refetch::all_columns, post::url, post::nsfw))
+    .limit(100)
+    .load::<(PostMetadataRefetch, Option<DbUrl>, bool)>(conn)?;
+
+  for (refetch, post_url, nsfw) in due {
+    let refetch_row = post_metadata_refetch::table.find(refetch.post_id);
+    let Some(post_url) = post_url else {
+      // The link was removed from the post
+      diesel::delete(refetch_row).execute(conn)?;
+      continue;
+    };
+
+    let post_url = post_url.into();
+    let (metadata, thumbnail) = runtime.block_on(fetch_site_data(
+      client,
+      &SETTINGS,
+      Some(&post_url),
+      allow_sensitive || !nsfw,
+    ));
+
+    if !is_site_data_missing(&metadata, &thumbnail) {
+      let (embed_title, embed_description, embed_video_url) = metadata
+        .map(|m| (m.title, m.description, m.embed_video_url))
+        .unwrap_or_default();
+      let form = PostUpdateForm::builder()
+        .embed_title(Some(sanitize_html_opt(&embed_title)))
+        .embed_description(Some(sanitize_html_opt(&embed_description)))
+        .embed_video_url(Some(embed_video_url))
+        .thumbnail_url(thumbnail.map(Some))
+        .build();
+      diesel::update(post::table.find(refetch.post_id))
+        .set(&form)
+        .execute(conn)?;
+      diesel::delete(refetch_row).execute(conn)?;
+    } else if refetch.attempts + 1 >= PostMetadataRefetch::MAX_ATTEMPTS {
+      diesel::delete(refetch_row).execute(conn)?;
+    } else {
+      diesel::update(refetch_row)
+        .set((
+          post_metadata_refetch::attempts.eq(refetch.attempts + 1),
+          post_metadata_refetch::next_attempt.eq(PostMetadataRefetch::next_attempt_after(
+            refetch.attempts + 1,
+          )),
+        ))
+        .execute(conn)?;
+    }
+  }
+  info!("Done.");
+  Ok(())
+}
+
+#[derive(QueryableByName)]
+struct OrphanedImage {
+  #[diesel(sql_type = Text)]
+  pictrs_alias: String,
+  #[diesel(sql_type = Text)]
+  pictrs_delete_token: String,
+}
+
+/// Uploaded images which are at least a day old, and not used in any local content. The post or
+/// comment link of an image only records where it was used first, so the text of the other local
+/// posts and comments is checked as well. Without that, an image which is reused in a second post
+/// would be purged together with the first one.
+fn find_orphaned_images(conn: &mut PgConnection) -> QueryResult<Vec<OrphanedImage>> {
+  sql_query(
+    "with i as (select i.*, '%/pictrs/image/' || i.pictrs_alias as url,
+         '%/pictrs/image/' || i.pictrs_alias || '%' as in_text
+       from local_image i)
+     select i.pictrs_alias, i.pictrs_delete_token from i
+     where i.post_id is null and i.comment_id is null
+     and i.published < now() - interval '1 day'
+     and not exists (select 1 from person p where p.local
+       and (p.avatar like i.url or p.banner like i.url or p.bio like i.in_text))
+     and not exists (select 1 from community c where c.local
+       and (c.icon like i.url or c.banner like i.url or c.description like i.in_text))
+     and not exists (select 1 from community_rule r
+       inner join community c on c.id = r.community_id
+       where c.local and r.description like i.in_text)
+     and not exists (select 1 from site s
+       where s.icon like i.url or s.banner like i.url
+         or s.sidebar like i.in_text or s.description like i.in_text)
+     and not exists (select 1 from local_site ls where ls.legal_information like i.in_text)
+     and not exists (select 1 from tagline t where t.content like i.in_text)
+     and not exists (select 1 from category c where c.description like i.in_text)
+     and not exists (select 1 from custom_emoji e where e.image_url like i.url)
+     and not exists (select 1 from private_message pm where pm.local and pm.content like i.in_text)
+     and not exists (select 1 from post p where p.local
+       and (p.url like i.url or p.body like i.in_text))
+     and not exists (select 1 from comment c where c.local and c.content like i.in_text)
+     order by i.published
+     limit 100",
+  )
+  .load::<OrphanedImage>(conn)
+}
+
+/// Deletes uploaded images which have not been used anywhere for a day, see
+/// [find_orphaned_images].
+fn delete_orphaned_images(conn: &mut PgConnection, user_agent: &str) -> LemmyResult<()> {
+  let Ok(pictrs_config) = SETTINGS.pictrs_config() else {
+    return Ok(());
+  };
+  info!("Deleting orphaned images...");
+
+  let orphans = find_orphaned_images(conn)?;
+
+  let client = Client::builder()
+    .user_agent(user_agent)
+    .timeout(REQWEST_TIMEOUT)
+    .build()?;
+
+  for image in orphans {
+    let url = format!(
+      "{}image/delete/{}/{}",
+      pictrs_config.url, image.pictrs_delete_token, image.pictrs_alias
+    );
+    match client.get(url).send() {
+      // The image is already gone if pictrs doesn't know it
+      Ok(res) if res.status().is_success() || res.status() == StatusCode::NOT_FOUND => {
+        diesel::delete(
+          local_image::table.filter(local_image::pictrs_alias.eq(&image.pictrs_alias)),
+        )
+        .execute(conn)?;
+      }
+      Ok(res) => warn!(
+        "Failed to delete orphaned image {}: {}",
+        image.pictrs_alias,
+        res.status()
+      ),
+      Err(e) => warn!(
+        "Failed to delete orphaned image {}: {e}",
+        image.pictrs_alias
+      ),
+    }
+  }
+  info!("Done.");
+  Ok(())
+}
+
+/// Refetches remote communities and persons which weren't updated recently, because nobody
+/// interacted with them. Communities with the most local subscribers come first. Actors on dead
+/// instances are skipped.
+fn refresh_stale_actors(
+  conn: &mut PgConnection,
+  federation_config: &FederationConfig<LemmyContext>,
+  runtime: &Handle,
+) -> LemmyResult<()> {
+  info!("Refreshing stale remote actors...");
+  let local_subscribers = sql::<BigInt>(
+    "(select count(*) from community_follower cf inner join person p on p.id = cf.person_id
+      where cf.community_id = community.id and p.local)",
+  );
+
+  let communities = community::table
+    .inner_join(instance::table)
+    .filter(community::local.eq(false))
+    .filter(community::deleted.eq(false))
+    .filter(community::last_refreshed_at.lt(now - STALE_ACTOR_DAYS.days()))
+    .filter(instance_alive())
+    .select(community::all_columns)
+    .order_by(local_subscribers.desc())
+    .then_order_by(community::last_refreshed_at)
+    .limit(STALE_ACTOR_BATCH_SIZE)
+    .load::<Community>(conn)?;
+  let persons = person::table
+    .inner_join(instance::table)
+    .filter(person::local.eq(false))
+    .filter(person::deleted.eq(false))
+    .filter(person::last_refreshed_at.lt(now - STALE_ACTOR_DAYS.days()))
+    .filter(instance_alive())
+    .select(person::all_columns)
+    .order_by(person::last_refreshed_at)
+    .limit(STALE_ACTOR_BATCH_SIZE)
+    .load::<Person>(conn)?;
+
+  let context = federation_config.to_request_data();
+  let mut refreshes = Vec::new();
+  for c in communities {
+    let (instance_id, ap_id) = (c.instance_id, c.actor_id.clone().into());
+    let refresh = refresh_actor(ApubCommunity::from(c), ap_id, &context);
+    refreshes.push((instance_id, refresh.boxed_local()));
+  }
+  for p in persons {
+    let (instance_id, ap_id) = (p.instance_id, p.actor_id.clone().into());
+    let refresh = refresh_actor(ApubPerson::from(p), ap_id, &context);
+    refreshes.push((instance_id, refresh.boxed_local()));
+  }
+  let count = refreshes.len();
+  runtime.block_on(run_with_instance_limits(refreshes));
+
+  info!("Done, refreshed {count} actors.");
+  Ok(())
+}
+
+async fn refresh_actor<Kind>(actor: Kind, ap_id: Url, context: &Data<LemmyContext>)
+where
+  Kind: Object<DataType = LemmyContext, Error = LemmyError> + Send + 'static,
+  for<'de2> <Kind as Object>::Kind: serde::Deserialize<'de2>,
+{
+  if let Err(e) = refetch_object(actor, ap_id.clone(), context).await {
+    warn!("Failed to refresh {ap_id}: {e}");
+  }
+}
+
+/// Syncs the moderators of remote communities which have local subscribers, in case changes were
+/// made before this instance subscribed or the announcements were lost.
+fn sync_community_moderators(
+  conn: &mut PgConnection,
+  federation_config: &FederationConfig<LemmyContext>,
+  runtime: &Handle,
+) -> LemmyResult<()> {
+  info!("Syncing remote community moderators...");
+  let communities = community::table
+    .inner_join(instance::table)
+    .filter(community::local.eq(false))
+    .filter(community::deleted.eq(false))
+    .filter(instance_alive())
+    .filter(exists(
+      community_follower::table
+        .inner_join(person::table)
+        .filter(community_follower::community_id.eq(community::id))
+        .filter(person::local.eq(true)),
+    ))
+    .select(community::all_columns)
+    .load::<Community>(conn)?;
+
+  let context = federation_config.to_request_data();
+  let syncs = communities
+    .into_iter()
+    .map(|c| {
+      let instance_id = c.instance_id;
+      let community = ApubCommunity::from(c);
+      let context = &context;
+      let sync = async move {
+        if let Err(e) = community.fetch_moderators(context).await {
+          warn!("Failed to sync moderators of {}: {e}", community.actor_id);
+        }
+      };
+      (instance_id, sync.boxed_local())
+    })
+    .collect::<Vec<_>>();
+  let count = syncs.len();
+  runtime.block_on(run_with_instance_limits(syncs));
+
+  info!("Done, synced moderators of {count} communities.");
+  Ok(())
+}
+
+/// Fetches trust signals for known instances from Fediseer, if it is configured
+fn sync_fediseer_trust(
+  _conn: &mut PgConnection,
+  federation_config: &FederationConfig<LemmyContext>,
+  runtime: &Handle,
+) -> LemmyResult<()> {
+  let Some(config) = &SETTINGS.fediseer else {
+    return Ok(());
+  };
+  info!("Fetching instance trust signals from Fediseer...");
+  let context = federation_config.to_request_data();
+  let count = runtime.block_on(sync_fediseer(config, &context))?;
+
+  info!("Done, {count} instances have trust signals.");
+  Ok(())
+}
+
+/// Fetches the configured list of disposable email domains
+fn sync_disposable_emails(
+  _conn: &mut PgConnection,
+  federation_config: &FederationConfig<LemmyContext>,
+  runtime: &Handle,
+) -> LemmyResult<()> {
+  let Some(config) = &SETTINGS.disposable_email else {
+    return Ok(());
+  };
+  let context = federation_config.to_request_data();
+  let count = runtime.block_on(sync_disposable_email_domains(config, &context))?;
+
+  info!("Done, fetched {count} disposable email domains.");
+  Ok(())
+}
+
+/// Fetches all subscribed blocklists, and syncs them into the federation blocklist
+fn sync_blocklist_subscriptions(
+  conn: &mut PgConnection,
+  federation_config: &FederationConfig<LemmyContext>,
+  runtime: &Handle,
+) -> LemmyResult<()> {
+  info!("Syncing blocklist subscriptions...");
+  let subscriptions = blocklist_subscription::table.load::<BlocklistSubscription>(conn)?;
+
+  let context = federation_config.to_request_data();
+  let mut failed = 0;
+  runtime.block_on(async {
+    for s in &subscriptions {
+      let synced = sync_blocklist_subscription(s, &context).await?;
+      if synced.last_error.is_some() {
+        failed += 1;
+      }
+    }
+    Ok::<_, LemmyError>(())
+  })?;
+
+  info!(
+    "Done, synced {} blocklists, {failed} failed.",
+    subscriptions.len()
+  );
+  Ok(())
+}
+
+/// Emails the mods and admins who enabled it a summary of their mod queue
+fn send_mod_queue_digest_emails(
+  _conn: &mut PgConnection,
+  federation_config: &FederationConfig<LemmyContext>,
+  runtime: &Handle,
+) -> LemmyResult<()> {
+  if SETTINGS.email.is_none() {
+    return Ok(());
+  }
+  info!("Sending mod queue digests...");
+  let context = federation_config.to_request_data();
+  let count = runtime.block_on(send_mod_queue_digests(&context))?;
+
+  info!("Done, sent {count} mod queue digests.");
+  Ok(())
+}
+
+/// Replaces the keypairs of local actors whose key is older than the configured number of days.
+/// Only a limited number of actors is rotated per run, so that the Update activities are spread out.
+fn rotate_old_keys(
+  conn: &mut PgConnection,
+  rotation_days: u32,
+  federation_config: &FederationConfig<LemmyContext>,
+  runtime: &Handle,
+) -> LemmyResult<()> {
+  if rotation_days == 0 {
+    return Ok(());
+  }
+  info!("Rotating old actor keys...");
+  let rotation_days = i32::try_from(rotation_days)?;
+  let key_expired = |table: &str| {
+    sql::<Bool>(&format!(
+      "coalesce((select r.rotated_at from actor_key_rotation r where r.actor_id = {table}.actor_id),
+        {table}.published) < now() - interval '1 day' * "
+    ))
+    .bind::<Integer, _>(rotation_days)
+  };
+
+  let persons = person::table
+    .filter(person::local.eq(true))
+    .filter(person::deleted.eq(false))
+    .filter(key_expired("person"))
+    .limit(KEY_ROTATION_BATCH_SIZE)
+    .load::<Person>(conn)?;
+  let communities = community::table
+    .filter(community::local.eq(true))
+    .filter(community::deleted.eq(false))
+    .filter(key_expired("community"))
+    .limit(KEY_ROTATION_BATCH_SIZE)
+    .load::<Community>(conn)?;
+  let site = site::table
+    .inner_join(local_site::table)
+    .filter(key_expired("site"))
+    .select(site::all_columns)
+    .first::<Site>(conn)
+    .optional()?;
+  // Updates of a community are sent in the name of the site owner
+  let owner = person::table
+    .filter(person::admin.eq(true))
+    .filter(person::deleted.eq(false))
+    .order_by(person::published)
+    .first::<Person>(conn)
+    .optional()?;
+
+  let context = federation_config.to_request_data();
+  let count = persons.len() + communities.len() + usize::from(site.is_some());
+  runtime.block_on(async {
+    for p in persons {
+      if let Err(e) = rotate_person_keys(&p, &context).await {
+        warn!("Failed to rotate keys of {}: {e}", p.actor_id);
+      }
+    }
+    for c in communities {
+      let Some(owner) = owner.clone() else {
+        warn!("Can't rotate community keys without an admin");
+        break;
+      };
+      if let Err(e) = rotate_community_keys(&c, owner, &context).await {
+        warn!("Failed to rotate keys of {}: {e}", c.actor_id);
+      }
+    }
+    if let Some(site) = site {
+      if let Err(e) = rotate_site_keys(&site, &context).await {
+        warn!("Failed to rotate keys of {}: {e}", site.actor_id);
+      }
+    }
+  });
+
+  info!("Done, rotated keys of {count} actors.");
+  Ok(())
+}
+
+/// Instances which responded within the last 3 days, the same as for activity sending
+fn instance_alive() -> SqlLiteral<Bool> {
+  sql::<Bool>("coalesce(instance.updated, instance.published) > now() - interval '3 days'")
+}
+
+/// Runs federation requests concurrently, with a limit on the number of requests to each instance
+async fn run_with_instance_limits(tasks: Vec<(InstanceId, LocalBoxFuture<'_, ()>)>) {
+  let mut instance_limits = HashMap::new();
+  let tasks = tasks.into_iter().map(|(instance_id, task)| {
+    let limit: Arc<Semaphore> = instance_limits
+      .entry(instance_id)
+      .or_insert_with(|| Arc::new(Semaphore::new(INSTANCE_FETCH_CONCURRENCY)))
+      .clone();
+    async move {
+      if let Ok(_permit) = limit.acquire().await {
+        task.await;
+      }
+    }
+  });
+  stream::iter(tasks)
+    .buffer_unordered(FETCH_CONCURRENCY)
+    .collect::<()>()
+    .await;
 }
 
 /// Updates the instance software and version
@@ -369,7 +1288,7 @@ fn update_instance_software(conn: &mut PgConnection, user_agent: &str) -> LemmyR
   let instances = instance::table.get_results::<Instance>(conn)?;
 
   for instance in instances {
-    let node_info_url = format!("https://{}/nodeinfo/2.0.json", instance.domain);
+    let node_info_url = node_info_url(&client, &instance.domain);
 
     // The `updated` column is used to check if instances are alive. If it is more than three days
     // in the past, no outgoing activities will be sent to that instance. However not every
@@ -394,6 +1313,7 @@ fn update_instance_software(conn: &mut PgConnection, user_agent: &str) -> LemmyR
               .updated(Some(naive_now()))
               .software(software.and_then(|s| s.name.clone()))
               .version(software.and_then(|s| s.version.clone()))
+              .open_registrations(node_info.open_registrations)
               .build(),
           )
         }
@@ -417,13 +1337,60 @@ fn update_instance_software(conn: &mut PgConnection, user_agent: &str) -> LemmyR
   Ok(())
 }
 
+/// Finds the nodeinfo of an instance through its well-known document, preferring the newest
+/// schema version. Falls back to the path used by Lemmy if there is none.
+fn node_info_url(client: &Client, domain: &str) -> String {
+  const SCHEMA_2: &str = "http://nodeinfo.diaspora.software/ns/schema/2.";
+  let well_known = client
+    .get(format!("https://{domain}/.well-known/nodeinfo"))
+    .send()
+    .and_then(|res| res.error_for_status())
+    .and_then(|res| res.json::<NodeInfoWellKnown>());
+  well_known
+    .ok()
+    .and_then(|w| {
+      w.links
+        .into_iter()
+        .filter(|l| l.rel.as_str().starts_with(SCHEMA_2))
+        .max_by(|a, b| a.rel.as_str().cmp(b.rel.as_str()))
+    })
+    .map(|l| l.href.to_string())
+    .unwrap_or_else(|| format!("https://{domain}/nodeinfo/2.0.json"))
+}
+
 #[cfg(test)]
 mod tests {
   #![allow(clippy::unwrap_used)]
   #![allow(clippy::indexing_slicing)]
 
+  use super::{fail_stale_community_imports, find_orphaned_images};
+  use diesel::{
+    dsl::{now, IntervalDsl},
+    Connection,
+    ExpressionMethods,
+    PgConnection,
+    RunQueryDsl,
+  };
+  use lemmy_db_schema::{
+    schema::{community_import, local_image},
+    source::{
+      community::{Community, CommunityInsertForm},
+      community_import::{CommunityImport, CommunityImportForm},
+      custom_emoji::{CustomEmoji, CustomEmojiInsertForm},
+      instance::Instance,
+      local_image::{LocalImage, LocalImageForm},
+      local_site::{LocalSite, LocalSiteInsertForm},
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+      site::{Site, SiteInsertForm},
+    },
+    traits::Crud,
+    utils::{build_db_pool_for_tests, get_database_url},
+  };
   use lemmy_routes::nodeinfo::NodeInfo;
   use reqwest::Client;
+  use serial_test::serial;
+  use url::Url;
 
   #[tokio::test]
   #[ignore]
@@ -440,4 +1407,158 @@ mod tests {
 
     assert_eq!(lemmy_ml_nodeinfo.software.unwrap().name.unwrap(), "lemmy");
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_find_orphaned_images() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let conn = &mut PgConnection::establish(&get_database_url(None)).unwrap();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+
+    let site_form = SiteInsertForm::builder()
+      .name("test_site".into())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_site = Site::create(pool, &site_form).await.unwrap();
+    let local_site_form = LocalSiteInsertForm::builder()
+      .site_id(inserted_site.id)
+      .build();
+    let inserted_local_site = LocalSite::create(pool, &local_site_form).await.unwrap();
+
+    let new_person = PersonInsertForm::builder()
+      .name("orphan_image_uploader".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &new_person).await.unwrap();
+
+    let new_community = CommunityInsertForm::builder()
+      .name("orphan_image_community".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let aliases = ["emoji.png", "reused.png", "unused.png"];
+    for alias in aliases {
+      let form = LocalImageForm::builder()
+        .person_id(inserted_person.id)
+        .pictrs_alias(alias.to_string())
+        .pictrs_delete_token("token".to_string())
+        .build();
+      LocalImage::create(pool, &form).await.unwrap();
+    }
+    diesel::update(local_image::table)
+      .filter(local_image::pictrs_alias.eq_any(aliases))
+      .set(local_image::published.eq(now - 2_i32.days()))
+      .execute(conn)
+      .unwrap();
+
+    let emoji_url = Url::parse("https://my_domain.tld/pictrs/image/emoji.png").unwrap();
+    let emoji_form = CustomEmojiInsertForm::builder()
+      .local_site_id(inserted_local_site.id)
+      .shortcode("orphan_test".to_string())
+      .image_url(emoji_url.into())
+      .alt_text("emoji".to_string())
+      .category("test".to_string())
+      .build();
+    let inserted_emoji = CustomEmoji::create(pool, &emoji_form).await.unwrap();
+
+    // The same image is used in two posts, only the first one is linked to it
+    let mut post_ids = vec![];
+    for name in ["first post", "second post"] {
+      let new_post = PostInsertForm::builder()
+        .name(name.into())
+        .body(Some(
+          "![](https://my_domain.tld/pictrs/image/reused.png)".into(),
+        ))
+        .creator_id(inserted_person.id)
+        .community_id(inserted_community.id)
+        .build();
+      post_ids.push(Post::create(pool, &new_post).await.unwrap().id);
+    }
+    LocalImage::link_to_post(pool, &["reused.png".to_string()], post_ids[0])
+      .await
+      .unwrap();
+    // Purging the first post removes the link
+    Post::delete(pool, post_ids[0]).await.unwrap();
+
+    let orphans = find_orphaned_images(conn)
+      .unwrap()
+      .into_iter()
+      .map(|i| i.pictrs_alias)
+      .filter(|a| aliases.contains(&a.as_str()))
+      .collect::<Vec<_>>();
+
+    CustomEmoji::delete(pool, inserted_emoji.id).await.unwrap();
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Site::delete(pool, inserted_site.id).await.unwrap();
+    LocalSite::delete(pool).await.unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert_eq!(vec!["unused.png".to_string()], orphans);
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_fail_stale_community_imports() {
+    let pool = &build_db_pool_for_tests().await;
+    let pool = &mut pool.into();
+    let conn = &mut PgConnection::establish(&get_database_url(None)).unwrap();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld".to_string())
+      .await
+      .unwrap();
+    let new_person = PersonInsertForm::builder()
+      .name("stale_importer".into())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_person = Person::create(pool, &new_person).await.unwrap();
+    let new_community = CommunityInsertForm::builder()
+      .name("stale_import_community".to_string())
+      .title("nada".to_owned())
+      .public_key("pubkey".to_string())
+      .instance_id(inserted_instance.id)
+      .build();
+    let inserted_community = Community::create(pool, &new_community).await.unwrap();
+
+    let form = CommunityImportForm {
+      community_id: inserted_community.id,
+      creator_id: inserted_person.id,
+    };
+    let stale = CommunityImport::create(pool, &form).await.unwrap();
+    let running = CommunityImport::create(pool, &form).await.unwrap();
+    diesel::update(community_import::table)
+      .filter(community_import::id.eq_any([stale.id, running.id]))
+      .set(community_import::published.eq(now - 2_i32.hours()))
+      .execute(conn)
+      .unwrap();
+    // The second import made progress recently
+    CommunityImport::add_progress(pool, running.id, 1, 0, 0)
+      .await
+      .unwrap();
+
+    fail_stale_community_imports(conn).unwrap();
+    let stale = CommunityImport::read(pool, stale.id).await.unwrap();
+    let running = CommunityImport::read(pool, running.id).await.unwrap();
+
+    Person::delete(pool, inserted_person.id).await.unwrap();
+    Community::delete(pool, inserted_community.id)
+      .await
+      .unwrap();
+    Instance::delete(pool, inserted_instance.id).await.unwrap();
+
+    assert!(stale.finished.is_some());
+    assert_eq!(Some("CommunityImportInterrupted".to_string()), stale.error);
+    assert_eq!(None, running.finished);
+  }
 }